@@ -103,6 +103,20 @@ impl<Idx, T> Arena<Idx, T> {
         self.entities.clear();
     }
 
+    /// Shrinks the capacity of the arena as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.entities.shrink_to_fit();
+    }
+
+    /// Removes the most recently allocated entity from the arena and returns it if any.
+    ///
+    /// # Note
+    ///
+    /// The index of the removed entity becomes invalid and is reused by the next allocation.
+    pub fn pop(&mut self) -> Option<T> {
+        self.entities.pop()
+    }
+
     /// Returns an iterator over the shared reference of the arena entities.
     pub fn iter(&self) -> Iter<Idx, T> {
         Iter {
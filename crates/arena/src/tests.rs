@@ -57,6 +57,19 @@ mod arena {
         assert_eq!(arena.get(arena.len()), None);
     }
 
+    #[test]
+    fn pop_works() {
+        let mut arena = alloc_arena(TEST_ENTITIES);
+        // Pop all elements in reverse order of their allocation.
+        for (idx, entity) in TEST_ENTITIES.iter().enumerate().rev() {
+            assert_eq!(arena.pop(), Some(*entity));
+            assert_eq!(arena.len(), idx);
+            assert_eq!(arena.get(idx), None);
+        }
+        assert_eq!(arena.pop(), None);
+        assert!(arena.is_empty());
+    }
+
     #[test]
     fn iter_works() {
         let arena = alloc_arena(TEST_ENTITIES);
@@ -1,11 +1,13 @@
 use anyhow::{Context, Error, Result};
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::{
     ffi::OsStr,
     net::SocketAddr,
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
+use wasmi::Config;
 use wasmi_wasi::{ambient_authority, Dir, TcpListener, WasiCtx, WasiCtxBuilder};
 
 /// A CLI flag value key-value argument.
@@ -36,10 +38,47 @@ impl FromStr for KeyValue {
     }
 }
 
+/// Parses a CLI flag value as [`Duration`].
+///
+/// Supports the `ms`, `s` and `m` units and defaults to seconds without unit.
+///
+/// # Errors
+///
+/// If the string cannot be parsed into a [`Duration`].
+fn parse_duration(s: &str) -> Result<Duration> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value = value
+        .parse::<u64>()
+        .map_err(|_| anyhow::anyhow!("invalid duration: missing amount in `{}`", s))?;
+    let duration = match unit {
+        "ms" => Duration::from_millis(value),
+        "" | "s" => Duration::from_secs(value),
+        "m" => Duration::from_secs(value.saturating_mul(60)),
+        _ => anyhow::bail!("invalid duration: unknown unit `{}` in `{}`", unit, s),
+    };
+    if duration.is_zero() {
+        anyhow::bail!("invalid duration: `{}` must not be zero", s)
+    }
+    Ok(duration)
+}
+
 /// The Wasmi CLI application arguments.
 #[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None, trailing_var_arg = true)]
+#[clap(
+    author,
+    version,
+    about,
+    long_about = None,
+    trailing_var_arg = true,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true,
+)]
 pub struct Args {
+    /// The optional subcommand to run instead of executing a Wasm module.
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     /// The host directory to pre-open for the `guest` to use.
     #[clap(
         long = "dir",
@@ -70,8 +109,9 @@ pub struct Args {
     #[clap(
         value_name = "MODULE",
         value_hint = clap::ValueHint::FilePath,
+        required = true,
     )]
-    wasm_file: PathBuf,
+    wasm_file: Option<PathBuf>,
 
     /// The function to invoke.
     ///
@@ -92,11 +132,206 @@ pub struct Args {
     #[clap(long = "fuel", value_name = "N")]
     fuel: Option<u64>,
 
+    /// Interrupt the execution after the given duration, e.g. `500ms`, `10s` or `1m`.
+    ///
+    /// The duration includes running the start function of the Wasm module.
+    /// Upon timeout the Wasmi CLI exits with status code 124.
+    ///
+    /// Note that calls to host functions cannot be interrupted.
+    #[clap(
+        long = "timeout",
+        value_name = "DURATION",
+        value_parser(parse_duration)
+    )]
+    timeout: Option<Duration>,
+
+    /// The format in which the outcome of the invocation is printed.
+    ///
+    /// The `json` format prints a single JSON object to stdout containing the
+    /// invocation results, fuel usage, traps with their backtraces and timings.
+    #[clap(long = "output", value_enum, default_value_t=OutputFormat::Text)]
+    output: OutputFormat,
+
+    /// Print the translated Wasmi bytecode of all functions as JSON instead of executing.
+    ///
+    /// The JSON object contains the instructions, register assignments, function
+    /// local constant values and branch targets of all internally defined functions.
+    #[clap(long = "dump-bytecode")]
+    dump_bytecode: bool,
+
     /// Arguments given to the Wasm module or the invoked function.
     #[clap(value_name = "ARGS")]
     func_args: Vec<String>,
 }
 
+/// The subcommands of the Wasmi CLI application.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Validates a Wasm module without executing it.
+    ///
+    /// Exits with a nonzero status code if the Wasm module is invalid.
+    Validate(ValidateArgs),
+    /// Runs the directives of a `.wast` script such as `assert_return` and `assert_trap`.
+    ///
+    /// Exits with a nonzero status code if any directive failed.
+    Wast(WastArgs),
+}
+
+/// The arguments of the `validate` subcommand.
+#[derive(clap::Args, Debug)]
+pub struct ValidateArgs {
+    /// The file containing the WebAssembly module to validate.
+    #[clap(
+        value_name = "MODULE",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    wasm_file: PathBuf,
+
+    /// Enables the Wasm proposal during validation.
+    #[clap(
+        long = "enable",
+        value_name = "FEATURE",
+        value_enum,
+        action = clap::ArgAction::Append,
+    )]
+    enable: Vec<WasmFeature>,
+
+    /// Disables the Wasm proposal during validation.
+    ///
+    /// Takes precedence over `--enable` for the same Wasm proposal.
+    #[clap(
+        long = "disable",
+        value_name = "FEATURE",
+        value_enum,
+        action = clap::ArgAction::Append,
+    )]
+    disable: Vec<WasmFeature>,
+
+    /// The format in which validation errors are printed.
+    ///
+    /// The `json` format prints a single JSON object to stdout.
+    #[clap(long = "output", value_enum, default_value_t=OutputFormat::Text)]
+    output: OutputFormat,
+}
+
+/// The arguments of the `wast` subcommand.
+#[derive(clap::Args, Debug)]
+pub struct WastArgs {
+    /// The `.wast` script to run.
+    #[clap(
+        value_name = "SCRIPT",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    wast_file: PathBuf,
+
+    /// Enables the Wasm proposal for all Wasm modules of the script.
+    #[clap(
+        long = "enable",
+        value_name = "FEATURE",
+        value_enum,
+        action = clap::ArgAction::Append,
+    )]
+    enable: Vec<WasmFeature>,
+
+    /// Disables the Wasm proposal for all Wasm modules of the script.
+    ///
+    /// Takes precedence over `--enable` for the same Wasm proposal.
+    #[clap(
+        long = "disable",
+        value_name = "FEATURE",
+        value_enum,
+        action = clap::ArgAction::Append,
+    )]
+    disable: Vec<WasmFeature>,
+
+    /// The Wasmi compilation mode used for all Wasm modules of the script.
+    #[clap(long = "compilation-mode", value_enum, default_value_t=CompilationMode::Eager)]
+    compilation_mode: CompilationMode,
+}
+
+/// A Wasm proposal that can be toggled for validation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum WasmFeature {
+    MutableGlobal,
+    SignExtension,
+    SaturatingFloatToInt,
+    MultiValue,
+    BulkMemory,
+    ReferenceTypes,
+    TailCall,
+    ExtendedConst,
+}
+
+impl WasmFeature {
+    /// Enables or disables the Wasm proposal for the `config`.
+    fn apply(self, config: &mut Config, enable: bool) {
+        match self {
+            Self::MutableGlobal => config.wasm_mutable_global(enable),
+            Self::SignExtension => config.wasm_sign_extension(enable),
+            Self::SaturatingFloatToInt => config.wasm_saturating_float_to_int(enable),
+            Self::MultiValue => config.wasm_multi_value(enable),
+            Self::BulkMemory => config.wasm_bulk_memory(enable),
+            Self::ReferenceTypes => config.wasm_reference_types(enable),
+            Self::TailCall => config.wasm_tail_call(enable),
+            Self::ExtendedConst => config.wasm_extended_const(enable),
+        };
+    }
+
+    /// Returns the [`Config`] with the default Wasm proposals for execution
+    /// as well as the `enable`d and `disable`d Wasm proposals applied.
+    fn config(enable: &[Self], disable: &[Self]) -> Config {
+        let mut config = Config::default();
+        config.wasm_tail_call(true);
+        config.wasm_extended_const(true);
+        for feature in enable {
+            feature.apply(&mut config, true);
+        }
+        for feature in disable {
+            feature.apply(&mut config, false);
+        }
+        config
+    }
+}
+
+impl ValidateArgs {
+    /// Returns the Wasm file path to validate.
+    pub fn wasm_file(&self) -> &Path {
+        &self.wasm_file
+    }
+
+    /// Returns the chosen [`OutputFormat`].
+    pub fn output(&self) -> OutputFormat {
+        self.output
+    }
+
+    /// Returns the [`Config`] with the Wasm proposals enabled for validation.
+    ///
+    /// # Note
+    ///
+    /// By default the same Wasm proposals are enabled as for execution.
+    pub fn config(&self) -> Config {
+        WasmFeature::config(&self.enable, &self.disable)
+    }
+}
+
+impl WastArgs {
+    /// Returns the `.wast` script path to run.
+    pub fn wast_file(&self) -> &Path {
+        &self.wast_file
+    }
+
+    /// Returns the [`Config`] used for all Wasm modules of the script.
+    ///
+    /// # Note
+    ///
+    /// By default the same Wasm proposals are enabled as for execution.
+    pub fn config(&self) -> Config {
+        let mut config = WasmFeature::config(&self.enable, &self.disable);
+        config.compilation_mode(self.compilation_mode.into());
+        config
+    }
+}
+
 /// The chosen Wasmi compilation mode.
 #[derive(Debug, Default, Copy, Clone, ValueEnum)]
 enum CompilationMode {
@@ -106,6 +341,16 @@ enum CompilationMode {
     Lazy,
 }
 
+/// The chosen output format of the Wasmi CLI.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable text.
+    #[default]
+    Text,
+    /// Structured JSON for consumption by scripts.
+    Json,
+}
+
 impl From<CompilationMode> for wasmi::CompilationMode {
     fn from(mode: CompilationMode) -> Self {
         match mode {
@@ -117,9 +362,20 @@ impl From<CompilationMode> for wasmi::CompilationMode {
 }
 
 impl Args {
+    /// Returns the subcommand given to the CLI app if any.
+    pub fn command(&self) -> Option<&Command> {
+        self.command.as_ref()
+    }
+
     /// Returns the Wasm file path given to the CLI app.
+    ///
+    /// # Panics
+    ///
+    /// If a subcommand was given to the CLI app.
     pub fn wasm_file(&self) -> &Path {
-        &self.wasm_file
+        self.wasm_file
+            .as_deref()
+            .expect("the Wasm file is required without subcommand")
     }
 
     /// Returns the name of the invoked function if any.
@@ -137,6 +393,21 @@ impl Args {
         self.fuel
     }
 
+    /// Returns the execution timeout given to the CLI app if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Returns the chosen [`OutputFormat`].
+    pub fn output(&self) -> OutputFormat {
+        self.output
+    }
+
+    /// Returns `true` if the translated Wasmi bytecode is printed instead of executed.
+    pub fn dump_bytecode(&self) -> bool {
+        self.dump_bytecode
+    }
+
     /// Returns `true` if lazy Wasm compilation is enabled.
     pub fn compilation_mode(&self) -> wasmi::CompilationMode {
         self.compilation_mode.into()
@@ -187,7 +458,7 @@ impl Args {
         // The WebAssembly filename is expected to be the first argument to WASI.
         // Note that the module name still has it's `.wasm` file extension.
         let module_name = self
-            .wasm_file
+            .wasm_file()
             .file_name()
             .and_then(OsStr::to_str)
             .unwrap_or("")
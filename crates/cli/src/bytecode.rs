@@ -0,0 +1,43 @@
+use crate::utils;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value as Json};
+use std::path::Path;
+use wasmi::{CompilationMode, Config, Engine, Module};
+
+/// Prints the translated Wasmi bytecode of all internally defined functions as a single JSON object.
+///
+/// # Note
+///
+/// The Wasm module is eagerly compiled so that the bytecode of all functions is available.
+///
+/// # Errors
+///
+/// If the Wasm module could not be read, validated or compiled.
+pub fn dump(wasm_file: &Path) -> Result<()> {
+    let mut config = Config::default();
+    config.wasm_tail_call(true);
+    config.wasm_extended_const(true);
+    config.compilation_mode(CompilationMode::Eager);
+    let engine = Engine::new(&config);
+    let wasm_bytes = utils::read_wasm_or_wat(wasm_file)?;
+    let module = Module::new(&engine, &wasm_bytes[..]).map_err(|error| {
+        anyhow!("failed to parse and validate Wasm module {wasm_file:?}: {error}")
+    })?;
+    let funcs = module
+        .func_code_sizes()
+        .iter()
+        .map(|size| {
+            let func_index = size.func_index();
+            let bytecode = engine
+                .bytecode_json(&module, func_index)
+                .unwrap_or_else(|| {
+                    panic!("missing bytecode of eagerly compiled function {func_index}")
+                });
+            let mut func: Json = serde_json::from_str(&bytecode)?;
+            func["name"] = json!(module.func_name(func_index));
+            Ok(func)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    println!("{}", json!({ "file": wasm_file, "funcs": funcs }));
+    Ok(())
+}
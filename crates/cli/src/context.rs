@@ -1,7 +1,18 @@
 use crate::utils;
 use anyhow::{anyhow, Error};
-use std::path::Path;
-use wasmi::{CompilationMode, Config, ExternType, Func, FuncType, Instance, Module, Store};
+use std::{fmt, path::Path, time::Duration};
+use wasmi::{
+    core::TrapCode,
+    watchdog::Watchdog,
+    CompilationMode,
+    Config,
+    ExternType,
+    Func,
+    FuncType,
+    Instance,
+    Module,
+    Store,
+};
 use wasmi_wasi::WasiCtx;
 
 /// The [`Context`] for the Wasmi CLI application.
@@ -14,6 +25,39 @@ pub struct Context {
     store: Store<WasiCtx>,
     /// The Wasm module instance to operate on.
     instance: Instance,
+    /// The timer thread driving the execution timeout if any.
+    ///
+    /// This is never read but must be kept alive until the execution has finished.
+    _watchdog: Option<Watchdog>,
+}
+
+/// The error returned when the execution of the Wasm module exceeded its timeout.
+#[derive(Debug)]
+pub struct Timeout {
+    /// The duration after which the execution was interrupted.
+    pub duration: Duration,
+}
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "execution timed out after {:?}", self.duration)
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+impl Timeout {
+    /// The exit status code of the Wasmi CLI upon timeouts.
+    pub const EXIT_CODE: i32 = 124;
+
+    /// Returns `true` if the Wasmi `error` was caused by an execution timeout.
+    ///
+    /// # Note
+    ///
+    /// The Wasmi CLI only ever interrupts executions due to `--timeout`.
+    pub fn is_timeout(error: &wasmi::Error) -> bool {
+        matches!(error.as_trap_code(), Some(TrapCode::Interrupted))
+    }
 }
 
 impl Context {
@@ -23,11 +67,14 @@ impl Context {
     ///
     /// - If parsing, validating, compiling or instantiating the Wasm module failed.
     /// - If adding WASI defintions to the linker failed.
+    /// - If the start function of the Wasm module exceeded the `timeout`.
     pub fn new(
         wasm_file: &Path,
         wasi_ctx: WasiCtx,
         fuel: Option<u64>,
+        timeout: Option<Duration>,
         compilation_mode: CompilationMode,
+        address_maps: bool,
     ) -> Result<Self, Error> {
         let mut config = Config::default();
         config.wasm_tail_call(true);
@@ -35,7 +82,11 @@ impl Context {
         if fuel.is_some() {
             config.consume_fuel(true);
         }
+        if timeout.is_some() {
+            config.epoch_interruption(true);
+        }
         config.compilation_mode(compilation_mode);
+        config.generate_address_maps(address_maps);
         let engine = wasmi::Engine::new(&config);
         let wasm_bytes = utils::read_wasm_or_wat(wasm_file)?;
         let module = wasmi::Module::new(&engine, &mut &wasm_bytes[..]).map_err(|error| {
@@ -47,17 +98,27 @@ impl Context {
                 panic!("error: fuel metering is enabled but encountered: {error}")
             });
         }
+        // Note: We arm the timeout before instantiation so that it also bounds the start function.
+        let watchdog = timeout.map(|timeout| {
+            let (tick, ticks) = timeout_ticks(timeout);
+            store.set_epoch_deadline(ticks);
+            Watchdog::new(&engine, tick)
+        });
         let mut linker = <wasmi::Linker<WasiCtx>>::new(&engine);
         wasmi_wasi::add_to_linker(&mut linker, |ctx| ctx)
             .map_err(|error| anyhow!("failed to add WASI definitions to the linker: {error}"))?;
         let instance = linker
             .instantiate(&mut store, &module)
             .and_then(|pre| pre.start(&mut store))
-            .map_err(|error| anyhow!("failed to instantiate and start the Wasm module: {error}"))?;
+            .map_err(|error| match timeout {
+                Some(duration) if Timeout::is_timeout(&error) => Error::new(Timeout { duration }),
+                _ => anyhow!("failed to instantiate and start the Wasm module: {error}"),
+            })?;
         Ok(Self {
             module,
             store,
             instance,
+            _watchdog: watchdog,
         })
     }
 
@@ -91,3 +152,14 @@ impl Context {
             .ok_or_else(|| anyhow!("failed to find function named {name:?} in the Wasm module"))
     }
 }
+
+/// Returns the epoch tick duration and the number of ticks that make up the `timeout`.
+///
+/// # Note
+///
+/// A single tick is roughly 1% of the `timeout` but at least 1ms.
+fn timeout_ticks(timeout: Duration) -> (Duration, u32) {
+    let tick = (timeout / 100).max(Duration::from_millis(1));
+    let ticks = timeout.as_nanos().div_ceil(tick.as_nanos());
+    (tick, u32::try_from(ticks).unwrap_or(u32::MAX))
+}
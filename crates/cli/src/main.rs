@@ -1,32 +1,76 @@
 use crate::{
-    args::Args,
+    args::{Args, Command, OutputFormat},
     display::{DisplayExportedFuncs, DisplayFuncType, DisplaySequence, DisplayValue},
+    report::JsonReport,
 };
 use anyhow::{anyhow, bail, Error, Result};
 use clap::Parser;
-use context::Context;
-use std::{path::Path, process};
-use wasmi::{Func, FuncType, Value};
+use context::{Context, Timeout};
+use std::{
+    path::Path,
+    process,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use wasmi::{Func, FuncType, Value, WasmBacktrace};
 
 mod args;
+mod bytecode;
 mod context;
 mod display;
+mod report;
 mod utils;
+mod validate;
+mod wast;
 
 #[cfg(test)]
 mod tests;
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    if let Some(command) = args.command() {
+        return match command {
+            Command::Validate(args) => validate::validate(args),
+            Command::Wast(args) => wast::run(args),
+        };
+    }
     let wasm_file = args.wasm_file();
+    if args.dump_bytecode() {
+        return bytecode::dump(wasm_file);
+    }
     let wasi_ctx = args.wasi_context()?;
-    let mut ctx = Context::new(wasm_file, wasi_ctx, args.fuel(), args.compilation_mode())?;
+    let json = args.output() == OutputFormat::Json;
+    let setup_start = Instant::now();
+    let mut ctx = Context::new(
+        wasm_file,
+        wasi_ctx,
+        args.fuel(),
+        args.timeout(),
+        args.compilation_mode(),
+        json,
+    )
+    .unwrap_or_else(|error| match error.downcast_ref::<Timeout>() {
+        Some(timeout) => exit_timeout(timeout),
+        None => exit_error(error),
+    });
+    let setup_time = setup_start.elapsed();
     let (func_name, func) = get_invoked_func(&args, &ctx)?;
     let ty = func.ty(ctx.store());
     let func_args = utils::decode_func_args(&ty, args.func_args())?;
     let mut func_results = utils::prepare_func_results(&ty);
     typecheck_args(&func_name, &ty, &func_args)?;
 
+    if json {
+        execute_json(
+            &args,
+            &mut ctx,
+            &func_name,
+            func,
+            &func_args,
+            &mut func_results,
+            setup_time,
+        )
+    }
     print_execution_start(args.wasm_file(), &func_name, &func_args);
     if args.invoked().is_some() && ty.params().len() != args.func_args().len() {
         bail!(
@@ -52,18 +96,96 @@ fn main() -> Result<()> {
                 print_pretty_results(&func_results);
                 process::exit(exit_code)
             }
+            if let Some(duration) = args.timeout() {
+                if Timeout::is_timeout(&error) {
+                    print_remaining_fuel(&args, &ctx);
+                    exit_timeout(&Timeout { duration })
+                }
+            }
             bail!("failed during execution of {func_name}: {error}")
         }
     }
 }
 
+/// Executes `func` and prints the outcome as a single JSON object.
+///
+/// Exits the process with the WASI exit code if any or with `1` upon traps.
+fn execute_json(
+    args: &Args,
+    ctx: &mut Context,
+    func_name: &str,
+    func: Func,
+    func_args: &[Value],
+    func_results: &mut [Value],
+    setup_time: Duration,
+) -> ! {
+    // Note: The backtrace is only available to the trap hook since the
+    //       call stack has already been torn down once the call returns.
+    let backtrace = <Arc<Mutex<Option<WasmBacktrace>>>>::default();
+    ctx.store_mut().set_trap_hook({
+        let backtrace = backtrace.clone();
+        move |frame| {
+            backtrace
+                .lock()
+                .unwrap()
+                .get_or_insert_with(|| frame.backtrace().clone());
+        }
+    });
+    let execution_start = Instant::now();
+    let outcome = func.call(ctx.store_mut(), func_args, func_results);
+    let execution_time = execution_start.elapsed();
+    let report = JsonReport {
+        wasm_file: args.wasm_file(),
+        func_name,
+        func_args,
+        func_results,
+        fuel: remaining_fuel(args, ctx),
+        timeout: args.timeout(),
+        setup_time,
+        execution_time,
+    };
+    let backtrace = backtrace.lock().unwrap();
+    println!(
+        "{}",
+        report.to_json(outcome.as_ref().err(), backtrace.as_ref())
+    );
+    let exit_code = match outcome {
+        Ok(()) => 0,
+        Err(error) if args.timeout().is_some() && Timeout::is_timeout(&error) => Timeout::EXIT_CODE,
+        Err(error) => error.i32_exit_status().unwrap_or(1),
+    };
+    process::exit(exit_code)
+}
+
+/// Prints the `timeout` error and exits the process with [`Timeout::EXIT_CODE`].
+///
+/// This allows scripts to tell timeouts apart from traps of the Wasm module.
+fn exit_timeout(timeout: &Timeout) -> ! {
+    eprintln!("Error: {timeout}");
+    process::exit(Timeout::EXIT_CODE)
+}
+
+/// Prints the `error` and exits the process with `1`.
+///
+/// This mirrors how errors returned from `main` are reported.
+fn exit_error(error: Error) -> ! {
+    eprintln!("Error: {error:?}");
+    process::exit(1)
+}
+
+/// Returns the consumed and remaining fuel so far if fuel metering was enabled.
+fn remaining_fuel(args: &Args, ctx: &Context) -> Option<(u64, u64)> {
+    let total_fuel = args.fuel()?;
+    let consumed = ctx
+        .store()
+        .fuel_consumed()
+        .unwrap_or_else(|| panic!("fuel metering is enabled but could not query consumed fuel"));
+    Some((consumed, total_fuel - consumed))
+}
+
 /// Prints the remaining fuel so far if fuel metering was enabled.
 fn print_remaining_fuel(args: &Args, ctx: &Context) {
-    if let Some(total_fuel) = args.fuel() {
-        let consumed = ctx.store().fuel_consumed().unwrap_or_else(|| {
-            panic!("fuel metering is enabled but could not query consumed fuel")
-        });
-        let remaining = total_fuel - consumed;
+    if let Some((consumed, remaining)) = remaining_fuel(args, ctx) {
         println!("fuel consumed: {consumed}, fuel remaining: {remaining}");
     }
 }
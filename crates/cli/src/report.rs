@@ -0,0 +1,135 @@
+use crate::context::Timeout;
+use serde_json::{json, Value as Json};
+use std::{path::Path, time::Duration};
+use wasmi::{Error, Value, WasmBacktrace};
+
+/// The outcome of a Wasm function invocation printed via `--output json`.
+pub struct JsonReport<'a> {
+    /// The executed Wasm file.
+    pub wasm_file: &'a Path,
+    /// The name of the invoked function.
+    pub func_name: &'a str,
+    /// The arguments given to the invoked function.
+    pub func_args: &'a [Value],
+    /// The results of the invoked function.
+    pub func_results: &'a [Value],
+    /// The consumed and remaining fuel if fuel metering is enabled.
+    pub fuel: Option<(u64, u64)>,
+    /// The execution timeout if any.
+    pub timeout: Option<Duration>,
+    /// The time it took to compile and instantiate the Wasm module.
+    pub setup_time: Duration,
+    /// The time it took to execute the invoked function.
+    pub execution_time: Duration,
+}
+
+impl JsonReport<'_> {
+    /// Returns the JSON of the [`JsonReport`] given the `error` of the invocation if any.
+    ///
+    /// The `backtrace` is the one captured by the trap hook if the invocation trapped.
+    pub fn to_json(&self, error: Option<&Error>, backtrace: Option<&WasmBacktrace>) -> Json {
+        let (status, exit_code, trap) = match error {
+            None => ("ok", None, Json::Null),
+            Some(error) => match error.i32_exit_status() {
+                Some(exit_code) => ("exit", Some(exit_code), Json::Null),
+                None if self.timeout.is_some() && Timeout::is_timeout(error) => {
+                    ("timeout", None, trap_to_json(error, backtrace))
+                }
+                None => ("trap", None, trap_to_json(error, backtrace)),
+            },
+        };
+        let fuel = match self.fuel {
+            Some((consumed, remaining)) => json!({
+                "consumed": consumed,
+                "remaining": remaining,
+            }),
+            None => Json::Null,
+        };
+        json!({
+            "file": self.wasm_file,
+            "invoke": self.func_name,
+            "args": values_to_json(self.func_args),
+            "status": status,
+            "results": values_to_json(self.func_results),
+            "exit_code": exit_code,
+            "fuel": fuel,
+            "trap": trap,
+            "timing": {
+                "setup_ms": duration_to_millis(self.setup_time),
+                "execution_ms": duration_to_millis(self.execution_time),
+                "timeout_ms": self.timeout.map(duration_to_millis),
+            },
+        })
+    }
+}
+
+/// Returns the JSON of a trap `error` and its `backtrace` if any.
+fn trap_to_json(error: &Error, backtrace: Option<&WasmBacktrace>) -> Json {
+    let backtrace = backtrace
+        .map(|backtrace| {
+            backtrace
+                .frames()
+                .iter()
+                .map(|frame| {
+                    json!({
+                        "func_index": frame.func_index(),
+                        "func_name": frame.func_name(),
+                        "wasm_offset": frame.wasm_offset(),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    json!({
+        "message": error.to_string(),
+        "code": error.as_trap_code().map(|code| format!("{code:?}")),
+        "func_index": error.func_index(),
+        "wasm_offset": error.wasm_offset(),
+        "backtrace": backtrace,
+    })
+}
+
+/// Returns the JSON of the Wasm `values`.
+fn values_to_json(values: &[Value]) -> Json {
+    values.iter().map(value_to_json).collect()
+}
+
+/// Returns the JSON of the Wasm `value`.
+///
+/// # Note
+///
+/// Non-finite floats are encoded as strings since JSON has no representation for them.
+/// References are encoded as `null` or as an opaque string if non-null.
+fn value_to_json(value: &Value) -> Json {
+    let (ty, value) = match value {
+        Value::I32(value) => ("i32", json!(value)),
+        Value::I64(value) => ("i64", json!(value)),
+        Value::F32(value) => ("f32", float_to_json(f64::from(f32::from(*value)))),
+        Value::F64(value) => ("f64", float_to_json(f64::from(*value))),
+        Value::FuncRef(value) => ("funcref", ref_to_json(value.is_null())),
+        Value::ExternRef(value) => ("externref", ref_to_json(value.is_null())),
+    };
+    json!({ "type": ty, "value": value })
+}
+
+/// Returns the JSON of the float `value`.
+fn float_to_json(value: f64) -> Json {
+    if value.is_finite() {
+        json!(value)
+    } else {
+        json!(value.to_string())
+    }
+}
+
+/// Returns the JSON of a reference value that `is_null` or not.
+fn ref_to_json(is_null: bool) -> Json {
+    match is_null {
+        true => Json::Null,
+        false => json!("<ref>"),
+    }
+}
+
+/// Returns the `duration` in fractional milliseconds.
+fn duration_to_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -0,0 +1,177 @@
+use crate::{
+    args::{OutputFormat, ValidateArgs},
+    utils,
+};
+use anyhow::Result;
+use serde_json::{json, Value as Json};
+use std::{collections::BTreeMap, ops::Range, path::Path, process};
+use wasmi::{errors::ErrorKind, Engine, Error, Module};
+use wasmparser::{Name, NameSectionReader, Parser, Payload, TypeRef};
+
+/// Validates the Wasm module given to the `validate` subcommand and prints the outcome.
+///
+/// Exits the process with `1` if the Wasm module is invalid.
+///
+/// # Errors
+///
+/// If the Wasm module could not be read from its file.
+pub fn validate(args: &ValidateArgs) -> Result<()> {
+    let wasm_file = args.wasm_file();
+    let wasm_bytes = utils::read_wasm_or_wat(wasm_file)?;
+    let engine = Engine::new(&args.config());
+    let error = match Module::validate(&engine, &wasm_bytes[..]) {
+        Ok(()) => {
+            match args.output() {
+                OutputFormat::Text => println!("{wasm_file:?} is a valid Wasm module"),
+                OutputFormat::Json => println!("{}", json!({ "file": wasm_file, "valid": true })),
+            }
+            return Ok(());
+        }
+        Err(error) => ValidationError::new(&error, &wasm_bytes[..]),
+    };
+    match args.output() {
+        OutputFormat::Text => eprintln!("{}", error.to_text(wasm_file)),
+        OutputFormat::Json => println!("{}", error.to_json(wasm_file)),
+    }
+    process::exit(1)
+}
+
+/// A structured Wasm validation error.
+#[derive(Debug)]
+pub struct ValidationError {
+    /// The message of the validation error.
+    message: String,
+    /// The offset within the Wasm binary at which the error occurred if known.
+    offset: Option<usize>,
+    /// The index of the Wasm function in which the error occurred if any.
+    func_index: Option<u32>,
+    /// The name of the Wasm function in which the error occurred if any.
+    func_name: Option<String>,
+}
+
+impl ValidationError {
+    /// Creates a new [`ValidationError`] for the `error` of the Wasm module `wasm`.
+    pub fn new(error: &Error, wasm: &[u8]) -> Self {
+        let (message, offset) = match error.kind() {
+            ErrorKind::Wasm(error) => (error.message().to_string(), Some(error.offset())),
+            _ => (error.to_string(), None),
+        };
+        let func = offset.and_then(|offset| FuncLocator::new(wasm).locate(offset));
+        let (func_index, func_name) = match func {
+            Some((func_index, func_name)) => (Some(func_index), func_name),
+            None => (None, None),
+        };
+        Self {
+            message,
+            offset,
+            func_index,
+            func_name,
+        }
+    }
+
+    /// Returns the human readable representation of the [`ValidationError`].
+    pub fn to_text(&self, wasm_file: &Path) -> String {
+        let mut text = format!("error: failed to validate {wasm_file:?}: {}", self.message);
+        if let Some(offset) = self.offset {
+            text.push_str(&format!("\n  at offset: 0x{offset:x}"));
+        }
+        match (self.func_index, &self.func_name) {
+            (Some(index), Some(name)) => {
+                text.push_str(&format!("\n  in function: {index} ({name})"))
+            }
+            (Some(index), None) => text.push_str(&format!("\n  in function: {index}")),
+            _ => {}
+        }
+        text
+    }
+
+    /// Returns the JSON representation of the [`ValidationError`].
+    pub fn to_json(&self, wasm_file: &Path) -> Json {
+        json!({
+            "file": wasm_file,
+            "valid": false,
+            "error": {
+                "message": self.message,
+                "offset": self.offset,
+                "func_index": self.func_index,
+                "func_name": self.func_name,
+            },
+        })
+    }
+}
+
+/// Locates the Wasm functions of a Wasm module by byte offset.
+///
+/// # Note
+///
+/// This only parses the Wasm module without validating it so that it
+/// also works for invalid Wasm modules. Parsing stops at the first
+/// malformed section and the collected information so far is kept.
+#[derive(Debug, Default)]
+struct FuncLocator {
+    /// The number of imported Wasm functions.
+    len_imported: u32,
+    /// The byte ranges of the bodies of the internal Wasm functions.
+    bodies: Vec<Range<usize>>,
+    /// The names of the Wasm functions by function index.
+    names: BTreeMap<u32, String>,
+}
+
+impl FuncLocator {
+    /// Parses the [`FuncLocator`] from the Wasm module `wasm`.
+    fn new(wasm: &[u8]) -> Self {
+        let mut locator = Self::default();
+        for payload in Parser::new(0).parse_all(wasm) {
+            let Ok(payload) = payload else { break };
+            match payload {
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        let Ok(import) = import else { break };
+                        if let TypeRef::Func(_) = import.ty {
+                            locator.len_imported += 1;
+                        }
+                    }
+                }
+                Payload::CodeSectionEntry(body) => {
+                    locator.bodies.push(body.range());
+                }
+                Payload::CustomSection(reader) if reader.name() == "name" => {
+                    locator.parse_names(reader.data(), reader.data_offset());
+                }
+                _ => {}
+            }
+        }
+        locator
+    }
+
+    /// Parses the Wasm function names of the `name` custom section.
+    ///
+    /// # Note
+    ///
+    /// Malformed subsections are ignored as mandated by the Wasm specification.
+    fn parse_names(&mut self, data: &[u8], offset: usize) {
+        for subsection in NameSectionReader::new(data, offset) {
+            if let Ok(Name::Function(naming)) = subsection {
+                self.names = naming
+                    .into_iter()
+                    .filter_map(Result::ok)
+                    .map(|naming| (naming.index, naming.name.to_string()))
+                    .collect();
+            }
+        }
+    }
+
+    /// Returns the index and name of the Wasm function whose body contains `offset` if any.
+    fn locate(&self, offset: usize) -> Option<(u32, Option<String>)> {
+        // Note: Errors at the end of a function body are reported at its end offset.
+        let position = self
+            .bodies
+            .iter()
+            .position(|body| body.start <= offset && offset <= body.end)?;
+        let func_index = u32::try_from(position)
+            .ok()?
+            .checked_add(self.len_imported)?;
+        let func_name = self.names.get(&func_index).cloned();
+        Some((func_index, func_name))
+    }
+}
@@ -0,0 +1,543 @@
+use crate::{args::WastArgs, display::DisplaySequence};
+use anyhow::{anyhow, bail, Context as _, Result};
+use std::{collections::HashMap, fmt, fs, path::Path, process};
+use wasmi::{
+    core::{ValueType, F32, F64},
+    Config,
+    Engine,
+    Extern,
+    ExternRef,
+    Func,
+    FuncRef,
+    Global,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Mutability,
+    Store,
+    Table,
+    TableType,
+    Value,
+};
+use wast::{
+    core::{HeapType, NanPattern, WastArgCore, WastRetCore},
+    lexer::Lexer,
+    parser::{self, ParseBuffer},
+    QuoteWat,
+    Wast,
+    WastArg,
+    WastDirective,
+    WastExecute,
+    WastInvoke,
+    WastRet,
+    Wat,
+};
+
+/// Runs the `.wast` script given to the `wast` subcommand and prints the outcome.
+///
+/// Failed directives are reported with their location and do not stop the script.
+/// Exits the process with `1` if any directive failed.
+///
+/// # Errors
+///
+/// If the `.wast` script could not be read or parsed.
+pub fn run(args: &WastArgs) -> Result<()> {
+    let wast_file = args.wast_file();
+    let source = fs::read_to_string(wast_file)
+        .with_context(|| format!("failed to read `.wast` script {wast_file:?}"))?;
+    let mut lexer = Lexer::new(&source);
+    lexer.allow_confusing_unicode(true);
+    let buffer = ParseBuffer::new_with_lexer(lexer).map_err(|error| with_path(error, wast_file))?;
+    let wast = parser::parse::<Wast>(&buffer).map_err(|error| with_path(error, wast_file))?;
+    let mut runner = WastRunner::new(&args.config());
+    let mut summary = Summary::default();
+    for directive in wast.directives {
+        let (line, column) = directive.span().linecol_in(&source);
+        match runner.execute(directive) {
+            Ok(Outcome::Passed) => summary.passed += 1,
+            Ok(Outcome::Skipped) => summary.skipped += 1,
+            Err(error) => {
+                summary.failed += 1;
+                eprintln!(
+                    "{}:{}:{}: {error}",
+                    wast_file.display(),
+                    line + 1,
+                    column + 1
+                );
+            }
+        }
+    }
+    println!("{summary}");
+    if summary.failed != 0 {
+        process::exit(1)
+    }
+    Ok(())
+}
+
+/// Attaches the `path` of the `.wast` script to a parse `error`.
+fn with_path(mut error: wast::Error, path: &Path) -> anyhow::Error {
+    error.set_path(path);
+    anyhow!("failed to parse `.wast` script: {error}")
+}
+
+/// The number of passed, failed and skipped directives of a `.wast` script.
+#[derive(Debug, Default)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} skipped",
+            self.passed, self.failed, self.skipped
+        )
+    }
+}
+
+/// The outcome of a successfully executed `.wast` directive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Outcome {
+    /// The directive passed.
+    Passed,
+    /// The directive is not supported by Wasmi and has been skipped.
+    ///
+    /// This is the case for directives requiring the Wasm component model or exceptions.
+    Skipped,
+}
+
+/// Executes the directives of a `.wast` script.
+struct WastRunner {
+    /// The linker holding the `spectest` definitions and all registered instances.
+    linker: Linker<()>,
+    /// The store holding all Wasm module instances of the script.
+    store: Store<()>,
+    /// The named Wasm module instances.
+    instances: HashMap<String, Instance>,
+    /// The most recently instantiated Wasm module instance if any.
+    last_instance: Option<Instance>,
+}
+
+impl WastRunner {
+    /// Creates a new [`WastRunner`] using an [`Engine`] with the given [`Config`].
+    ///
+    /// Defines the `spectest` host module commonly imported by `.wast` scripts.
+    fn new(config: &Config) -> Self {
+        let engine = Engine::new(config);
+        let mut linker = <Linker<()>>::new(&engine);
+        let mut store = Store::new(&engine, ());
+        let memory = Memory::new(&mut store, MemoryType::new(1, Some(2)).unwrap()).unwrap();
+        let table = Table::new(
+            &mut store,
+            TableType::new(ValueType::FuncRef, 10, Some(20)),
+            Value::default(ValueType::FuncRef),
+        )
+        .unwrap();
+        let globals = [
+            ("global_i32", Value::I32(666)),
+            ("global_i64", Value::I64(666)),
+            ("global_f32", Value::F32(666.0.into())),
+            ("global_f64", Value::F64(666.0.into())),
+        ];
+        let funcs = [
+            ("print", Func::wrap(&mut store, || println!("print"))),
+            (
+                "print_i32",
+                Func::wrap(&mut store, |value: i32| println!("print: {value}")),
+            ),
+            (
+                "print_i64",
+                Func::wrap(&mut store, |value: i64| println!("print: {value}")),
+            ),
+            (
+                "print_f32",
+                Func::wrap(&mut store, |value: F32| println!("print: {value:?}")),
+            ),
+            (
+                "print_f64",
+                Func::wrap(&mut store, |value: F64| println!("print: {value:?}")),
+            ),
+            (
+                "print_i32_f32",
+                Func::wrap(&mut store, |v0: i32, v1: F32| {
+                    println!("print: {v0:?} {v1:?}")
+                }),
+            ),
+            (
+                "print_f64_f64",
+                Func::wrap(&mut store, |v0: F64, v1: F64| {
+                    println!("print: {v0:?} {v1:?}")
+                }),
+            ),
+        ];
+        linker.define("spectest", "memory", memory).unwrap();
+        linker.define("spectest", "table", table).unwrap();
+        for (name, value) in globals {
+            let global = Global::new(&mut store, value, Mutability::Const);
+            linker.define("spectest", name, global).unwrap();
+        }
+        for (name, func) in funcs {
+            linker.define("spectest", name, func).unwrap();
+        }
+        Self {
+            linker,
+            store,
+            instances: HashMap::new(),
+            last_instance: None,
+        }
+    }
+
+    /// Executes a single `.wast` `directive`.
+    ///
+    /// # Errors
+    ///
+    /// If the directive failed, e.g. if an assertion does not hold.
+    fn execute(&mut self, directive: WastDirective) -> Result<Outcome> {
+        match directive {
+            WastDirective::Wat(mut module) => {
+                let Some(name) = module_name(&module) else {
+                    return Ok(Outcome::Skipped);
+                };
+                let wasm = module.encode()?;
+                self.instantiate(name, &wasm)?;
+            }
+            WastDirective::AssertMalformed {
+                mut module,
+                message,
+                ..
+            }
+            | WastDirective::AssertInvalid {
+                mut module,
+                message,
+                ..
+            } => {
+                if module_name(&module).is_none() {
+                    return Ok(Outcome::Skipped);
+                }
+                // Note: Malformed modules might already fail to encode.
+                if let Ok(wasm) = module.encode() {
+                    if Module::new(self.store.engine(), &wasm[..]).is_ok() {
+                        bail!(
+                            "expected module to fail compilation with `{message}` but it succeeded"
+                        )
+                    }
+                }
+            }
+            WastDirective::AssertUnlinkable {
+                module: Wat::Module(mut module),
+                message,
+                ..
+            } => {
+                let wasm = module.encode()?;
+                if self.instantiate(None, &wasm).is_ok() {
+                    bail!("expected module to fail linking with `{message}` but it succeeded")
+                }
+            }
+            WastDirective::AssertUnlinkable { .. } => return Ok(Outcome::Skipped),
+            WastDirective::Register { name, module, .. } => {
+                let instance = self.instance(module.map(|id| id.name()))?;
+                self.register(name, instance)?;
+            }
+            WastDirective::Invoke(invoke) => {
+                self.invoke(invoke)??;
+            }
+            WastDirective::AssertReturn { exec, results, .. } => {
+                let Some(outcome) = self.execute_wast(exec)? else {
+                    return Ok(Outcome::Skipped);
+                };
+                let actual =
+                    outcome.map_err(|error| anyhow!("expected results but failed: {error}"))?;
+                self.assert_results(&actual, &results)?;
+            }
+            WastDirective::AssertTrap { exec, message, .. } => {
+                let Some(outcome) = self.execute_wast(exec)? else {
+                    return Ok(Outcome::Skipped);
+                };
+                assert_trap(outcome, message)?;
+            }
+            WastDirective::AssertExhaustion { call, message, .. } => {
+                assert_trap(self.invoke(call)?, message)?;
+            }
+            WastDirective::AssertException { .. } => return Ok(Outcome::Skipped),
+        }
+        Ok(Outcome::Passed)
+    }
+
+    /// Compiles and instantiates the Wasm module `wasm` and registers it under `name` if any.
+    ///
+    /// # Errors
+    ///
+    /// If compilation, instantiation or running the start function failed.
+    fn instantiate(&mut self, name: Option<&str>, wasm: &[u8]) -> Result<Instance, wasmi::Error> {
+        let module = Module::new(self.store.engine(), wasm)?;
+        let instance = self
+            .linker
+            .instantiate(&mut self.store, &module)?
+            .start(&mut self.store)?;
+        if let Some(name) = name {
+            self.instances.insert(name.into(), instance);
+        }
+        self.last_instance = Some(instance);
+        Ok(instance)
+    }
+
+    /// Returns the Wasm module instance registered under `name` or the last one if `name` is `None`.
+    ///
+    /// # Errors
+    ///
+    /// If there is no such Wasm module instance.
+    fn instance(&self, name: Option<&str>) -> Result<Instance> {
+        match name {
+            Some(name) => self
+                .instances
+                .get(name)
+                .copied()
+                .ok_or_else(|| anyhow!("found no Wasm module instance named `{name}`")),
+            None => self
+                .last_instance
+                .ok_or_else(|| anyhow!("found no Wasm module instance")),
+        }
+    }
+
+    /// Makes the exports of `instance` available for imports from the module `name`.
+    ///
+    /// # Errors
+    ///
+    /// If the exports conflict with previous definitions.
+    fn register(&mut self, name: &str, instance: Instance) -> Result<()> {
+        self.instances.insert(name.into(), instance);
+        let exports = instance
+            .exports(&self.store)
+            .map(|export| (export.name().to_string(), export.into_extern()))
+            .collect::<Vec<_>>();
+        for (field, export) in exports {
+            self.linker.define(name, &field, export)?;
+        }
+        Ok(())
+    }
+
+    /// Executes the `exec` of an assertion.
+    ///
+    /// Returns `None` if the execution is not supported by Wasmi.
+    /// Returns the results or the error of the execution otherwise.
+    ///
+    /// # Errors
+    ///
+    /// If the execution is malformed, e.g. if the invoked function does not exist.
+    fn execute_wast(
+        &mut self,
+        exec: WastExecute,
+    ) -> Result<Option<Result<Vec<Value>, wasmi::Error>>> {
+        let outcome = match exec {
+            WastExecute::Invoke(invoke) => self.invoke(invoke)?,
+            WastExecute::Wat(Wat::Module(mut module)) => {
+                let wasm = module.encode()?;
+                self.instantiate(None, &wasm).map(|_| Vec::new())
+            }
+            WastExecute::Wat(Wat::Component(_)) => return Ok(None),
+            WastExecute::Get { module, global } => {
+                let instance = self.instance(module.map(|id| id.name()))?;
+                let global = instance
+                    .get_export(&self.store, global)
+                    .and_then(Extern::into_global)
+                    .ok_or_else(|| anyhow!("found no exported global named `{global}`"))?;
+                Ok(vec![global.get(&self.store)])
+            }
+        };
+        Ok(Some(outcome))
+    }
+
+    /// Invokes the function of the `invoke` directive.
+    ///
+    /// Returns the results or the error of the invocation.
+    ///
+    /// # Errors
+    ///
+    /// If the invoked function does not exist or the arguments are not supported.
+    fn invoke(&mut self, invoke: WastInvoke) -> Result<Result<Vec<Value>, wasmi::Error>> {
+        let instance = self.instance(invoke.module.map(|id| id.name()))?;
+        let name = invoke.name;
+        let func = instance
+            .get_func(&self.store, name)
+            .ok_or_else(|| anyhow!("found no exported function named `{name}`"))?;
+        let args = invoke
+            .args
+            .iter()
+            .map(|arg| match arg {
+                WastArg::Core(arg) => self.value(arg),
+                WastArg::Component(_) => bail!("Wasmi does not support the Wasm component model"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut results = func
+            .ty(&self.store)
+            .results()
+            .iter()
+            .copied()
+            .map(Value::default)
+            .collect::<Vec<_>>();
+        Ok(func
+            .call(&mut self.store, &args, &mut results)
+            .map(|_| results))
+    }
+
+    /// Converts the `.wast` argument `arg` into a [`Value`].
+    ///
+    /// # Errors
+    ///
+    /// If the argument type is not supported by Wasmi.
+    fn value(&mut self, arg: &WastArgCore) -> Result<Value> {
+        let value = match arg {
+            WastArgCore::I32(value) => Value::I32(*value),
+            WastArgCore::I64(value) => Value::I64(*value),
+            WastArgCore::F32(value) => Value::F32(F32::from_bits(value.bits)),
+            WastArgCore::F64(value) => Value::F64(F64::from_bits(value.bits)),
+            WastArgCore::RefNull(HeapType::Func) => Value::FuncRef(FuncRef::null()),
+            WastArgCore::RefNull(HeapType::Extern) => Value::ExternRef(ExternRef::null()),
+            WastArgCore::RefExtern(value) => {
+                Value::ExternRef(ExternRef::new(&mut self.store, *value))
+            }
+            unsupported => bail!("unsupported argument: {unsupported:?}"),
+        };
+        Ok(value)
+    }
+
+    /// Asserts that the `actual` results match the `expected` results.
+    ///
+    /// # Errors
+    ///
+    /// If the results do not match.
+    fn assert_results(&self, actual: &[Value], expected: &[WastRet]) -> Result<()> {
+        let matches = actual.len() == expected.len()
+            && actual
+                .iter()
+                .zip(expected)
+                .all(|(actual, expected)| match expected {
+                    WastRet::Core(expected) => self.matches(actual, expected),
+                    WastRet::Component(_) => false,
+                });
+        if !matches {
+            bail!(
+                "expected results [{}] but found [{}]",
+                DisplaySequence::new(", ", expected.iter().map(DisplayExpected)),
+                DisplaySequence::new(", ", actual.iter().map(|value| DisplayActual(self, value)))
+            )
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the `actual` value matches the `expected` result.
+    fn matches(&self, actual: &Value, expected: &WastRetCore) -> bool {
+        match (actual, expected) {
+            (Value::I32(actual), WastRetCore::I32(expected)) => actual == expected,
+            (Value::I64(actual), WastRetCore::I64(expected)) => actual == expected,
+            (Value::F32(actual), WastRetCore::F32(expected)) => match expected {
+                NanPattern::CanonicalNan => actual.to_bits() & 0x7fff_ffff == 0x7fc0_0000,
+                NanPattern::ArithmeticNan => actual.to_bits() & 0x7fc0_0000 == 0x7fc0_0000,
+                NanPattern::Value(expected) => actual.to_bits() == expected.bits,
+            },
+            (Value::F64(actual), WastRetCore::F64(expected)) => match expected {
+                NanPattern::CanonicalNan => {
+                    actual.to_bits() & 0x7fff_ffff_ffff_ffff == 0x7ff8_0000_0000_0000
+                }
+                NanPattern::ArithmeticNan => {
+                    actual.to_bits() & 0x7ff8_0000_0000_0000 == 0x7ff8_0000_0000_0000
+                }
+                NanPattern::Value(expected) => actual.to_bits() == expected.bits,
+            },
+            (Value::FuncRef(actual), WastRetCore::RefNull(None | Some(HeapType::Func))) => {
+                actual.is_null()
+            }
+            (Value::FuncRef(actual), WastRetCore::RefFunc(_)) => !actual.is_null(),
+            (Value::ExternRef(actual), WastRetCore::RefNull(None | Some(HeapType::Extern))) => {
+                actual.is_null()
+            }
+            (Value::ExternRef(actual), WastRetCore::RefExtern(expected)) => actual
+                .data(&self.store)
+                .and_then(|data| data.downcast_ref::<u32>())
+                .is_some_and(|actual| actual == expected),
+            (actual, WastRetCore::Either(expected)) => expected
+                .iter()
+                .any(|expected| self.matches(actual, expected)),
+            _ => false,
+        }
+    }
+}
+
+/// Returns the name of the Wasm module of `module` if it is not a Wasm component.
+///
+/// The outer `Option` is `None` for Wasm components and the inner `Option` is `None` for unnamed Wasm modules.
+fn module_name<'a>(module: &QuoteWat<'a>) -> Option<Option<&'a str>> {
+    match module {
+        QuoteWat::Wat(Wat::Module(module)) => Some(module.id.map(|id| id.name())),
+        QuoteWat::QuoteModule(..) => Some(None),
+        QuoteWat::Wat(Wat::Component(_)) | QuoteWat::QuoteComponent(..) => None,
+    }
+}
+
+/// Asserts that `outcome` is a trap or error whose message contains `message`.
+///
+/// # Errors
+///
+/// If `outcome` is successful or fails with another message.
+fn assert_trap<T: fmt::Debug>(outcome: Result<T, wasmi::Error>, message: &str) -> Result<()> {
+    match outcome {
+        Ok(results) => bail!("expected trap with `{message}` but succeeded with {results:?}"),
+        Err(error) if !error.to_string().contains(message) => {
+            bail!("expected trap with `{message}` but failed with `{error}`")
+        }
+        Err(_) => Ok(()),
+    }
+}
+
+/// [`Display`](fmt::Display)-wrapper for expected `.wast` results.
+struct DisplayExpected<'a>(&'a WastRet<'a>);
+
+impl fmt::Display for DisplayExpected<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            WastRet::Core(WastRetCore::I32(value)) => write!(f, "i32:{value}"),
+            WastRet::Core(WastRetCore::I64(value)) => write!(f, "i64:{value}"),
+            WastRet::Core(WastRetCore::F32(NanPattern::Value(value))) => {
+                write!(f, "f32:{}", F32::from_bits(value.bits))
+            }
+            WastRet::Core(WastRetCore::F64(NanPattern::Value(value))) => {
+                write!(f, "f64:{}", F64::from_bits(value.bits))
+            }
+            WastRet::Core(WastRetCore::F32(_)) => write!(f, "f32:nan"),
+            WastRet::Core(WastRetCore::F64(_)) => write!(f, "f64:nan"),
+            WastRet::Core(WastRetCore::RefNull(_)) => write!(f, "ref.null"),
+            WastRet::Core(WastRetCore::RefExtern(value)) => write!(f, "ref.extern:{value}"),
+            WastRet::Core(WastRetCore::RefFunc(_)) => write!(f, "ref.func"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// [`Display`](fmt::Display)-wrapper for actual results.
+struct DisplayActual<'a>(&'a WastRunner, &'a Value);
+
+impl fmt::Display for DisplayActual<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.1 {
+            Value::I32(value) => write!(f, "i32:{value}"),
+            Value::I64(value) => write!(f, "i64:{value}"),
+            Value::F32(value) => write!(f, "f32:{value}"),
+            Value::F64(value) => write!(f, "f64:{value}"),
+            Value::FuncRef(value) if value.is_null() => write!(f, "ref.null"),
+            Value::FuncRef(_) => write!(f, "ref.func"),
+            Value::ExternRef(value) => match value
+                .data(&self.0.store)
+                .and_then(|data| data.downcast_ref::<u32>())
+            {
+                Some(value) => write!(f, "ref.extern:{value}"),
+                None if value.is_null() => write!(f, "ref.null"),
+                None => write!(f, "ref.extern"),
+            },
+        }
+    }
+}
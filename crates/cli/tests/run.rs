@@ -30,8 +30,228 @@ fn test_proc_exit() {
     assert.failure().code(1);
 }
 
+#[test]
+fn test_json_output() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .args(["--output", "json", "--fuel", "1000", "--invoke", "div"])
+        .arg(get_bin_path("div"))
+        .args(["7", "2"])
+        .assert();
+    let json = parse_json(&assert.success().get_output().stdout);
+    assert_eq!(json["status"], "ok");
+    assert_eq!(json["invoke"], "div");
+    assert_eq!(json["results"][0]["type"], "i32");
+    assert_eq!(json["results"][0]["value"], 3);
+    assert_eq!(json["trap"], serde_json::Value::Null);
+    let consumed = json["fuel"]["consumed"].as_u64().unwrap();
+    let remaining = json["fuel"]["remaining"].as_u64().unwrap();
+    assert_eq!(consumed + remaining, 1000);
+    assert!(json["timing"]["execution_ms"].is_f64());
+}
+
+#[test]
+fn test_json_output_trap() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .args(["--output", "json", "--invoke", "div"])
+        .arg(get_bin_path("div"))
+        .args(["1", "0"])
+        .assert();
+    let json = parse_json(&assert.failure().code(1).get_output().stdout);
+    assert_eq!(json["status"], "trap");
+    assert_eq!(json["fuel"], serde_json::Value::Null);
+    let trap = &json["trap"];
+    assert_eq!(trap["code"], "IntegerDivisionByZero");
+    assert_eq!(trap["func_index"], 0);
+    let backtrace = trap["backtrace"].as_array().unwrap();
+    assert_eq!(backtrace.len(), 2);
+    assert_eq!(backtrace[0]["func_name"], "div");
+    assert_eq!(backtrace[0]["wasm_offset"], trap["wasm_offset"]);
+    assert_eq!(backtrace[1]["func_index"], 1);
+    assert!(backtrace[1]["wasm_offset"].is_u64());
+}
+
+#[test]
+fn test_timeout() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .args(["--timeout", "50ms", "--invoke", "loop"])
+        .arg(get_bin_path("loop"))
+        .assert();
+    let assert = assert.failure().code(124);
+    let stderr = &assert.get_output().stderr;
+    assert!(contains_slice(stderr, b"execution timed out after 50ms"));
+}
+
+#[test]
+fn test_timeout_start() {
+    let mut cmd = get_cmd();
+    cmd.args(["--timeout", "50ms"])
+        .arg(get_bin_path("start_loop"))
+        .assert()
+        .failure()
+        .code(124);
+}
+
+#[test]
+fn test_timeout_not_exceeded() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .args(["--timeout", "10s", "--invoke", "div"])
+        .arg(get_bin_path("div"))
+        .args(["7", "2"])
+        .assert();
+    let assert = assert.success();
+    assert!(contains_slice(&assert.get_output().stdout, b"3"));
+}
+
+#[test]
+fn test_timeout_json_output() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .args(["--output", "json", "--timeout", "50ms", "--invoke", "loop"])
+        .arg(get_bin_path("loop"))
+        .assert();
+    let json = parse_json(&assert.failure().code(124).get_output().stdout);
+    assert_eq!(json["status"], "timeout");
+    assert_eq!(json["trap"]["code"], "Interrupted");
+    assert_eq!(json["timing"]["timeout_ms"], 50.0);
+}
+
+#[test]
+fn test_timeout_invalid() {
+    let mut cmd = get_cmd();
+    cmd.args(["--timeout", "5h"])
+        .arg(get_bin_path("div"))
+        .assert()
+        .failure()
+        .code(2);
+}
+
+#[test]
+fn test_validate() {
+    let mut cmd = get_cmd();
+    let assert = cmd.arg("validate").arg(get_bin_path("div")).assert();
+    let assert = assert.success();
+    let stdout = &assert.get_output().stdout;
+    assert!(contains_slice(stdout, b"is a valid Wasm module"));
+}
+
+#[test]
+fn test_validate_invalid() {
+    let mut cmd = get_cmd();
+    let assert = cmd.arg("validate").arg(get_bin_path("invalid")).assert();
+    let assert = assert.failure().code(1);
+    let stderr = &assert.get_output().stderr;
+    assert!(contains_slice(stderr, b"type mismatch"));
+    assert!(contains_slice(stderr, b"in function: 2 (invalid)"));
+}
+
+#[test]
+fn test_validate_json_output() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .args(["validate", "--output", "json"])
+        .arg(get_bin_path("invalid"))
+        .assert();
+    let json = parse_json(&assert.failure().code(1).get_output().stdout);
+    assert_eq!(json["valid"], false);
+    let error = &json["error"];
+    assert!(error["offset"].is_u64());
+    assert_eq!(error["func_index"], 2);
+    assert_eq!(error["func_name"], "invalid");
+}
+
+#[test]
+fn test_validate_features() {
+    let mut cmd = get_cmd();
+    cmd.arg("validate")
+        .arg(get_bin_path("tail_call"))
+        .assert()
+        .success();
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .args(["validate", "--disable", "tail-call", "--output", "json"])
+        .arg(get_bin_path("tail_call"))
+        .assert();
+    let json = parse_json(&assert.failure().code(1).get_output().stdout);
+    assert_eq!(json["valid"], false);
+    assert_eq!(json["error"]["func_index"], 0);
+    assert_eq!(json["error"]["func_name"], "f");
+}
+
+#[test]
+fn test_wast() {
+    let mut cmd = get_cmd();
+    let assert = cmd.arg("wast").arg(get_wast_path("passing")).assert();
+    let assert = assert.success();
+    let stdout = &assert.get_output().stdout;
+    assert!(contains_slice(stdout, b"11 passed, 0 failed, 0 skipped"));
+}
+
+#[test]
+fn test_wast_failure() {
+    let mut cmd = get_cmd();
+    let assert = cmd.arg("wast").arg(get_wast_path("failing")).assert();
+    let assert = assert.failure().code(1);
+    let output = assert.get_output();
+    assert!(contains_slice(
+        &output.stdout,
+        b"2 passed, 2 failed, 0 skipped"
+    ));
+    assert!(contains_slice(
+        &output.stderr,
+        b"failing.wast:8:2: expected results [i32:4] but found [i32:3]"
+    ));
+    assert!(contains_slice(
+        &output.stderr,
+        b"failing.wast:9:2: expected trap"
+    ));
+}
+
+#[test]
+fn test_dump_bytecode() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .arg("--dump-bytecode")
+        .arg(get_bin_path("bytecode"))
+        .assert();
+    let json = parse_json(&assert.success().get_output().stdout);
+    let funcs = json["funcs"].as_array().unwrap();
+    assert_eq!(funcs.len(), 2);
+    assert_eq!(funcs[0]["func_index"], 0);
+    assert_eq!(funcs[0]["name"], "select");
+    assert_eq!(funcs[1]["func_index"], 1);
+    for func in funcs {
+        assert!(func["registers"]["len"].is_u64());
+        assert!(func["consts"].is_array());
+        let instrs = func["instrs"].as_array().unwrap();
+        assert!(!instrs.is_empty());
+        for (index, instr) in instrs.iter().enumerate() {
+            assert_eq!(instr["index"], index);
+            assert!(instr["op"].is_string());
+            for target in instr["branch_targets"].as_array().unwrap() {
+                assert!(target.as_u64().unwrap() < instrs.len() as u64);
+            }
+        }
+    }
+    let branches = funcs[1]["instrs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|instr| !instr["branch_targets"].as_array().unwrap().is_empty())
+        .count();
+    assert!(branches >= 2);
+}
+
 /// UTILS
 
+/// parses the JSON printed to stdout by `--output json`
+fn parse_json(stdout: &[u8]) -> serde_json::Value {
+    serde_json::from_slice(stdout).expect("stdout is not valid JSON")
+}
+
 /// gets the path to a wasm binary given it's name
 fn get_bin_path(name: &str) -> PathBuf {
     let mut path = PathBuf::new();
@@ -41,6 +261,15 @@ fn get_bin_path(name: &str) -> PathBuf {
     path
 }
 
+/// gets the path to a `.wast` script given it's name
+fn get_wast_path(name: &str) -> PathBuf {
+    let mut path = PathBuf::new();
+    path.push("tests");
+    path.push("wasts");
+    path.push(format!("{name}.wast"));
+    path
+}
+
 fn get_cmd() -> assert_cmd::Command {
     Command::cargo_bin("wasmi_cli").expect("could not create wasmi_cli command")
 }
@@ -0,0 +1,38 @@
+(module $math
+  (func (export "add") (param i32 i32) (result i32)
+    (i32.add (local.get 0) (local.get 1))
+  )
+  (func (export "div") (param i32 i32) (result i32)
+    (i32.div_s (local.get 0) (local.get 1))
+  )
+  (func (export "mul") (param i64 i64) (result i64)
+    (i64.mul (local.get 0) (local.get 1))
+  )
+  (func (export "nan") (result f32)
+    (f32.div (f32.const 0) (f32.const 0))
+  )
+)
+(register "math" $math)
+
+(assert_return (invoke "add" (i32.const 1) (i32.const 2)) (i32.const 3))
+(assert_return (invoke "mul" (i64.const 6) (i64.const 7)) (i64.const 42))
+(assert_return (invoke "nan") (f32.const nan:canonical))
+(assert_trap (invoke "div" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_invalid
+  (module (func (result i32) (i64.const 0)))
+  "type mismatch"
+)
+(assert_malformed (module quote "(func (i32.const))") "unexpected token")
+
+(module
+  (import "math" "add" (func $add (param i32 i32) (result i32)))
+  (import "spectest" "print_i32" (func $print (param i32)))
+  (func (export "twice") (param i32) (result i32)
+    (call $add (local.get 0) (local.get 0))
+  )
+)
+(assert_return (invoke "twice" (i32.const 21)) (i32.const 42))
+(assert_unlinkable
+  (module (import "math" "missing" (func)))
+  "unknown import"
+)
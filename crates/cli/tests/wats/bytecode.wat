@@ -0,0 +1,17 @@
+(module
+    (func $select (export "select") (param f32) (result f32)
+        (if (result f32) (f32.lt (local.get 0) (f32.const 0.5))
+            (then (f32.const inf))
+            (else (f32.const nan))
+        )
+    )
+    (func (export "table") (param i32) (result f64)
+        (block $b
+            (block $a
+                (br_table $a $b (local.get 0))
+            )
+            (return (f64.const -1.25))
+        )
+        (f64.const 1e-7)
+    )
+)
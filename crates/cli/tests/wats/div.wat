@@ -0,0 +1,8 @@
+(module
+    (func $div (param $lhs i32) (param $rhs i32) (result i32)
+        (i32.div_s (local.get $lhs) (local.get $rhs))
+    )
+    (func (export "div") (param i32 i32) (result i32)
+        (call $div (local.get 0) (local.get 1))
+    )
+)
@@ -0,0 +1,9 @@
+(module
+    (import "env" "log" (func $log (param i32)))
+    (func $valid (param i32)
+        (call $log (local.get 0))
+    )
+    (func $invalid (result i32)
+        (i64.const 0)
+    )
+)
@@ -0,0 +1,7 @@
+(module
+    (func (export "loop")
+        (loop $continue
+            (br $continue)
+        )
+    )
+)
@@ -0,0 +1,8 @@
+(module
+    (func $start
+        (loop $continue
+            (br $continue)
+        )
+    )
+    (start $start)
+)
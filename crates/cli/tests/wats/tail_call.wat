@@ -0,0 +1,8 @@
+(module
+    (func $f (export "f") (result i32)
+        (return_call $g)
+    )
+    (func $g (result i32)
+        (i32.const 42)
+    )
+)
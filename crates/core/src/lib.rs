@@ -1,3 +1,9 @@
+//! Core primitives for the `wasmi` WebAssembly interpreter.
+//!
+//! Besides the basic Wasm types this crate provides [`UntypedValue`] which implements
+//! the semantics of all numeric Wasm operations. Embedders may use it to evaluate Wasm
+//! operations without duplicating their semantics, e.g. in custom constant evaluators.
+
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(
     clippy::cast_lossless,
@@ -27,14 +33,13 @@ extern crate std as alloc;
 use self::value::{
     ArithmeticOps,
     ExtendInto,
-    Float,
     Integer,
     LittleEndianConvert,
     SignExtendFrom,
-    TruncateSaturateInto,
-    TryTruncateInto,
     WrapInto,
 };
+#[cfg(feature = "float")]
+use self::value::{Float, TruncateSaturateInto, TryTruncateInto};
 pub use self::{
     host_error::HostError,
     nan_preserving_float::{F32, F64},
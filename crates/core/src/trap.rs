@@ -217,6 +217,7 @@ impl StdError for Trap {
 ///
 /// [`Trap`]: struct.Trap.html
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum TrapCode {
     /// Wasm code executed `unreachable` opcode.
     ///
@@ -285,12 +286,32 @@ pub enum TrapCode {
     /// This is useful to deterministically halt or yield a WebAssembly execution.
     OutOfFuel,
 
+    /// This trap is raised when a WebAssembly execution exhausted its instruction budget.
+    ///
+    /// The Wasmi execution engine can be configured to instrument its
+    /// internal bytecode so that executed instructions are counted.
+    /// This is a cheaper alternative to fuel metering for a rough bound on execution.
+    OutOfInstructions,
+
+    /// This trap is raised when a WebAssembly execution exceeded its epoch deadline.
+    ///
+    /// The Wasmi execution engine can be configured to instrument its internal
+    /// bytecode so that function entries and loop headers check the epoch deadline.
+    /// This is useful to interrupt long running executions, e.g. after a timeout.
+    Interrupted,
+
     /// This trap is raised when a growth operation was attempted and an
     /// installed `wasmi::ResourceLimiter` returned `Err(...)` from the
     /// associated `table_growing` or `memory_growing` method, indicating a
     /// desire on the part of the embedder to trap the interpreter rather than
     /// merely fail the growth operation.
     GrowthOperationLimited,
+
+    /// Attempt to write to a read-only region of linear memory.
+    ///
+    /// Regions of linear memory can be made read-only by the host,
+    /// e.g. to protect loaded code or constants from being overwritten.
+    MemoryWriteProtected,
 }
 
 impl TrapCode {
@@ -312,13 +333,41 @@ impl TrapCode {
             Self::StackOverflow => "call stack exhausted",
             Self::BadSignature => "indirect call type mismatch",
             Self::OutOfFuel => "all fuel consumed by WebAssembly",
+            Self::OutOfInstructions => "instruction budget exhausted by WebAssembly",
+            Self::Interrupted => "interrupted",
             Self::GrowthOperationLimited => "growth operation limited",
+            Self::MemoryWriteProtected => "write to read-only memory",
+        }
+    }
+
+    /// Returns the stable numeric code of the [`TrapCode`].
+    ///
+    /// # Note
+    ///
+    /// Codes are stable across versions and are never reused.
+    /// New [`TrapCode`]s are assigned the next free code.
+    pub const fn code(&self) -> u8 {
+        match self {
+            Self::UnreachableCodeReached => 0x00,
+            Self::MemoryOutOfBounds => 0x01,
+            Self::TableOutOfBounds => 0x02,
+            Self::IndirectCallToNull => 0x03,
+            Self::IntegerDivisionByZero => 0x04,
+            Self::IntegerOverflow => 0x05,
+            Self::BadConversionToInteger => 0x06,
+            Self::StackOverflow => 0x07,
+            Self::BadSignature => 0x08,
+            Self::OutOfFuel => 0x09,
+            Self::OutOfInstructions => 0x0A,
+            Self::Interrupted => 0x0B,
+            Self::GrowthOperationLimited => 0x0C,
+            Self::MemoryWriteProtected => 0x0D,
         }
     }
 }
 
 impl Display for TrapCode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.trap_message())
+        f.write_str(self.trap_message())
     }
 }
@@ -2,26 +2,66 @@ use crate::{
     value::{LoadInto, StoreFrom},
     ArithmeticOps,
     ExtendInto,
-    Float,
     Integer,
     LittleEndianConvert,
     SignExtendFrom,
     TrapCode,
-    TruncateSaturateInto,
-    TryTruncateInto,
+    ValueType,
     WrapInto,
     F32,
     F64,
 };
 use core::{
     fmt::{self, Display},
-    ops::{Neg, Shl, Shr},
+    ops::{Shl, Shr},
 };
 use paste::paste;
+#[cfg(feature = "float")]
+use {
+    crate::{Float, TruncateSaturateInto, TryTruncateInto},
+    core::ops::Neg,
+};
 
 /// An untyped value.
 ///
 /// Provides a dense and simple interface to all functional Wasm operations.
+///
+/// # Representation
+///
+/// An [`UntypedValue`] is 64 bits wide and does not know the type of its value.
+/// Instead every operation interprets its operands according to the Wasm type in its name.
+///
+/// - `i32` and `f32` values occupy the lower 32 bits, the upper 32 bits are zero.
+/// - `i64` and `f64` values occupy all 64 bits.
+/// - Floating point values are stored as their IEEE 754 bits so that NaN payloads are preserved.
+/// - Results of comparisons are `i32` values that are either `0` or `1`.
+///
+/// # Operations
+///
+/// The operations mirror the Wasm instruction of the same name, e.g. [`UntypedValue::i32_add`]
+/// executes `i32.add`, and follow its semantics as defined in the [WebAssembly specification].
+/// Operations that trap in Wasm return `Result<UntypedValue, TrapCode>` with the [`TrapCode`]
+/// that Wasm would trap with, for example [`TrapCode::IntegerDivisionByZero`].
+///
+/// Operations are only meaningful if the operands are of the Wasm types the operation expects.
+/// Passing [`UntypedValue`]s of other types is safe but yields unspecified results.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi_core::{TrapCode, UntypedValue};
+/// let lhs = UntypedValue::from(7_i32);
+/// let rhs = UntypedValue::from(2_i32);
+/// assert_eq!(i32::from(lhs.i32_mul(rhs)), 14);
+/// assert_eq!(i32::from(lhs.i32_div_s(rhs).unwrap()), 3);
+/// assert_eq!(
+///     lhs.i32_div_s(UntypedValue::from(0_i32)),
+///     Err(TrapCode::IntegerDivisionByZero),
+/// );
+/// assert!(bool::from(rhs.i32_lt_s(lhs)));
+/// ```
+///
+/// [WebAssembly specification]: https://webassembly.github.io/spec/core/exec/numerics.html
 #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(transparent)]
 pub struct UntypedValue {
@@ -31,10 +71,76 @@ pub struct UntypedValue {
 }
 
 impl UntypedValue {
+    /// Creates an [`UntypedValue`] from its underlying `bits`.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self { bits }
+    }
+
     /// Returns the underlying bits of the [`UntypedValue`].
     pub fn to_bits(self) -> u64 {
         self.bits
     }
+
+    /// Returns the underlying bits of the [`UntypedValue`] as little-endian bytes.
+    ///
+    /// # Note
+    ///
+    /// The encoding is the same on all architectures and inverted by [`UntypedValue::from_le_bytes`].
+    pub fn to_le_bytes(self) -> [u8; 8] {
+        self.bits.to_le_bytes()
+    }
+
+    /// Creates an [`UntypedValue`] from the little-endian `bytes` of its underlying bits.
+    pub fn from_le_bytes(bytes: [u8; 8]) -> Self {
+        Self::from_bits(u64::from_le_bytes(bytes))
+    }
+
+    /// Returns the number of bytes of the little-endian encoding of a value of type `ty`.
+    ///
+    /// This is 4 for [`ValueType::I32`] and [`ValueType::F32`] and 8 for all other types.
+    pub fn typed_le_bytes_len(ty: ValueType) -> usize {
+        match ty {
+            ValueType::I32 | ValueType::F32 => 4,
+            ValueType::I64 | ValueType::F64 | ValueType::FuncRef | ValueType::ExternRef => 8,
+        }
+    }
+
+    /// Writes the value of type `ty` as little-endian bytes into `bytes`.
+    ///
+    /// # Note
+    ///
+    /// - Values of type `i32` and `f32` are encoded using 4 bytes and values of type `i64`
+    ///   and `f64` using 8 bytes, matching their encoding in Wasm linear memory.
+    /// - Floating point values are encoded via their IEEE 754 bits so that NaN payloads are preserved.
+    /// - Reference values are encoded using all 8 bytes of their underlying bits.
+    ///   Their bits are only meaningful within the store that created them.
+    ///
+    /// # Errors
+    ///
+    /// If the length of `bytes` does not match [`UntypedValue::typed_le_bytes_len`] for `ty`.
+    pub fn write_typed_le_bytes(self, ty: ValueType, bytes: &mut [u8]) -> Result<(), UntypedError> {
+        if bytes.len() != Self::typed_le_bytes_len(ty) {
+            return Err(UntypedError::invalid_byte_len());
+        }
+        bytes.copy_from_slice(&self.to_le_bytes()[..bytes.len()]);
+        Ok(())
+    }
+
+    /// Creates an [`UntypedValue`] of type `ty` from its little-endian `bytes`.
+    ///
+    /// This inverts [`UntypedValue::write_typed_le_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// If the length of `bytes` does not match [`UntypedValue::typed_le_bytes_len`] for `ty`.
+    pub fn from_typed_le_bytes(ty: ValueType, bytes: &[u8]) -> Result<Self, UntypedError> {
+        if bytes.len() != Self::typed_le_bytes_len(ty) {
+            return Err(UntypedError::invalid_byte_len());
+        }
+        let mut buffer = [0x00_u8; 8];
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        Ok(Self::from_le_bytes(buffer))
+    }
 }
 
 macro_rules! impl_from_untyped_for_int {
@@ -198,6 +304,7 @@ impl UntypedValue {
     ///
     /// - If `address + offset` overflows.
     /// - If `address + offset` loads out of bounds from `memory`.
+    #[cfg(feature = "float")]
     pub fn f32_load(memory: &[u8], address: Self, offset: u32) -> Result<Self, TrapCode> {
         Self::load::<F32>(memory, address, offset)
     }
@@ -208,6 +315,7 @@ impl UntypedValue {
     ///
     /// - If `address + offset` overflows.
     /// - If `address + offset` loads out of bounds from `memory`.
+    #[cfg(feature = "float")]
     pub fn f64_load(memory: &[u8], address: Self, offset: u32) -> Result<Self, TrapCode> {
         Self::load::<F64>(memory, address, offset)
     }
@@ -385,6 +493,7 @@ impl UntypedValue {
     ///
     /// - If `address + offset` overflows.
     /// - If `address + offset` stores out of bounds from `memory`.
+    #[cfg(feature = "float")]
     pub fn f32_store(
         memory: &mut [u8],
         address: Self,
@@ -400,6 +509,7 @@ impl UntypedValue {
     ///
     /// - If `address + offset` overflows.
     /// - If `address + offset` stores out of bounds from `memory`.
+    #[cfg(feature = "float")]
     pub fn f64_store(
         memory: &mut [u8],
         address: Self,
@@ -494,6 +604,7 @@ impl UntypedValue {
     }
 
     /// Execute an infallible generic operation on `T` that returns an `R`.
+    #[cfg(feature = "float")]
     fn try_execute_unary<T, R>(self, op: fn(T) -> Result<R, TrapCode>) -> Result<Self, TrapCode>
     where
         T: From<Self>,
@@ -578,8 +689,7 @@ impl UntypedValue {
     ///
     /// # Errors
     ///
-    /// - If `rhs` is equal to zero.
-    /// - If the operation result overflows.
+    /// If `rhs` is equal to zero.
     pub fn i32_div_u(self, rhs: Self) -> Result<Self, TrapCode> {
         self.try_execute_binary(rhs, <u32 as Integer<u32>>::div)
     }
@@ -588,8 +698,7 @@ impl UntypedValue {
     ///
     /// # Errors
     ///
-    /// - If `rhs` is equal to zero.
-    /// - If the operation result overflows.
+    /// If `rhs` is equal to zero.
     pub fn i64_div_u(self, rhs: Self) -> Result<Self, TrapCode> {
         self.try_execute_binary(rhs, <u64 as Integer<u64>>::div)
     }
@@ -598,8 +707,7 @@ impl UntypedValue {
     ///
     /// # Errors
     ///
-    /// - If `rhs` is equal to zero.
-    /// - If the operation result overflows.
+    /// If `rhs` is equal to zero.
     pub fn i32_rem_s(self, rhs: Self) -> Result<Self, TrapCode> {
         self.try_execute_binary(rhs, <i32 as Integer<i32>>::rem)
     }
@@ -608,8 +716,7 @@ impl UntypedValue {
     ///
     /// # Errors
     ///
-    /// - If `rhs` is equal to zero.
-    /// - If the operation result overflows.
+    /// If `rhs` is equal to zero.
     pub fn i64_rem_s(self, rhs: Self) -> Result<Self, TrapCode> {
         self.try_execute_binary(rhs, <i64 as Integer<i64>>::rem)
     }
@@ -618,8 +725,7 @@ impl UntypedValue {
     ///
     /// # Errors
     ///
-    /// - If `rhs` is equal to zero.
-    /// - If the operation result overflows.
+    /// If `rhs` is equal to zero.
     pub fn i32_rem_u(self, rhs: Self) -> Result<Self, TrapCode> {
         self.try_execute_binary(rhs, <u32 as Integer<u32>>::rem)
     }
@@ -628,8 +734,7 @@ impl UntypedValue {
     ///
     /// # Errors
     ///
-    /// - If `rhs` is equal to zero.
-    /// - If the operation result overflows.
+    /// If `rhs` is equal to zero.
     pub fn i64_rem_u(self, rhs: Self) -> Result<Self, TrapCode> {
         self.try_execute_binary(rhs, <u64 as Integer<u64>>::rem)
     }
@@ -765,11 +870,13 @@ impl UntypedValue {
     }
 
     /// Execute `f32.eq` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_eq(self, rhs: Self) -> Self {
         self.execute_binary::<F32, bool>(rhs, op!(==))
     }
 
     /// Execute `f64.eq` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_eq(self, rhs: Self) -> Self {
         self.execute_binary::<F64, bool>(rhs, op!(==))
     }
@@ -785,11 +892,13 @@ impl UntypedValue {
     }
 
     /// Execute `f32.ne` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_ne(self, rhs: Self) -> Self {
         self.execute_binary::<F32, bool>(rhs, op!(!=))
     }
 
     /// Execute `f64.ne` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_ne(self, rhs: Self) -> Self {
         self.execute_binary::<F64, bool>(rhs, op!(!=))
     }
@@ -815,11 +924,13 @@ impl UntypedValue {
     }
 
     /// Execute `f32.lt` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_lt(self, rhs: Self) -> Self {
         self.execute_binary::<F32, bool>(rhs, op!(<))
     }
 
     /// Execute `f64.lt` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_lt(self, rhs: Self) -> Self {
         self.execute_binary::<F64, bool>(rhs, op!(<))
     }
@@ -845,11 +956,13 @@ impl UntypedValue {
     }
 
     /// Execute `f32.le` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_le(self, rhs: Self) -> Self {
         self.execute_binary::<F32, bool>(rhs, op!(<=))
     }
 
     /// Execute `f64.le` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_le(self, rhs: Self) -> Self {
         self.execute_binary::<F64, bool>(rhs, op!(<=))
     }
@@ -875,11 +988,13 @@ impl UntypedValue {
     }
 
     /// Execute `f32.gt` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_gt(self, rhs: Self) -> Self {
         self.execute_binary::<F32, bool>(rhs, op!(>))
     }
 
     /// Execute `f64.gt` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_gt(self, rhs: Self) -> Self {
         self.execute_binary::<F64, bool>(rhs, op!(>))
     }
@@ -905,151 +1020,181 @@ impl UntypedValue {
     }
 
     /// Execute `f32.ge` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_ge(self, rhs: Self) -> Self {
         self.execute_binary::<F32, bool>(rhs, op!(>=))
     }
 
     /// Execute `f64.ge` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_ge(self, rhs: Self) -> Self {
         self.execute_binary::<F64, bool>(rhs, op!(>=))
     }
 
     /// Execute `f32.abs` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_abs(self) -> Self {
         self.execute_unary(<F32 as Float<F32>>::abs)
     }
 
     /// Execute `f32.neg` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_neg(self) -> Self {
         self.execute_unary(<F32 as Neg>::neg)
     }
 
     /// Execute `f32.ceil` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_ceil(self) -> Self {
         self.execute_unary(<F32 as Float<F32>>::ceil)
     }
 
     /// Execute `f32.floor` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_floor(self) -> Self {
         self.execute_unary(<F32 as Float<F32>>::floor)
     }
 
     /// Execute `f32.trunc` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_trunc(self) -> Self {
         self.execute_unary(<F32 as Float<F32>>::trunc)
     }
 
     /// Execute `f32.nearest` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_nearest(self) -> Self {
         self.execute_unary(<F32 as Float<F32>>::nearest)
     }
 
     /// Execute `f32.sqrt` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_sqrt(self) -> Self {
         self.execute_unary(<F32 as Float<F32>>::sqrt)
     }
 
     /// Execute `f32.min` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_min(self, other: Self) -> Self {
         self.execute_binary(other, <F32 as Float<F32>>::min)
     }
 
     /// Execute `f32.max` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_max(self, other: Self) -> Self {
         self.execute_binary(other, <F32 as Float<F32>>::max)
     }
 
     /// Execute `f32.copysign` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_copysign(self, other: Self) -> Self {
         self.execute_binary(other, <F32 as Float<F32>>::copysign)
     }
 
     /// Execute `f64.abs` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_abs(self) -> Self {
         self.execute_unary(<F64 as Float<F64>>::abs)
     }
 
     /// Execute `f64.neg` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_neg(self) -> Self {
         self.execute_unary(<F64 as Neg>::neg)
     }
 
     /// Execute `f64.ceil` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_ceil(self) -> Self {
         self.execute_unary(<F64 as Float<F64>>::ceil)
     }
 
     /// Execute `f64.floor` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_floor(self) -> Self {
         self.execute_unary(<F64 as Float<F64>>::floor)
     }
 
     /// Execute `f64.trunc` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_trunc(self) -> Self {
         self.execute_unary(<F64 as Float<F64>>::trunc)
     }
 
     /// Execute `f64.nearest` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_nearest(self) -> Self {
         self.execute_unary(<F64 as Float<F64>>::nearest)
     }
 
     /// Execute `f64.sqrt` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_sqrt(self) -> Self {
         self.execute_unary(<F64 as Float<F64>>::sqrt)
     }
 
     /// Execute `f32.add` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_add(self, rhs: Self) -> Self {
         self.execute_binary(rhs, <F32 as ArithmeticOps<F32>>::add)
     }
 
     /// Execute `f64.add` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_add(self, rhs: Self) -> Self {
         self.execute_binary(rhs, <F64 as ArithmeticOps<F64>>::add)
     }
 
     /// Execute `f32.sub` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_sub(self, rhs: Self) -> Self {
         self.execute_binary(rhs, <F32 as ArithmeticOps<F32>>::sub)
     }
 
     /// Execute `f64.sub` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_sub(self, rhs: Self) -> Self {
         self.execute_binary(rhs, <F64 as ArithmeticOps<F64>>::sub)
     }
 
     /// Execute `f32.mul` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_mul(self, rhs: Self) -> Self {
         self.execute_binary(rhs, <F32 as ArithmeticOps<F32>>::mul)
     }
 
     /// Execute `f64.mul` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_mul(self, rhs: Self) -> Self {
         self.execute_binary(rhs, <F64 as ArithmeticOps<F64>>::mul)
     }
 
     /// Execute `f32.div` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_div(self, rhs: Self) -> Self {
         self.execute_binary(rhs, <F32 as Float<F32>>::div)
     }
 
     /// Execute `f64.div` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_div(self, rhs: Self) -> Self {
         self.execute_binary(rhs, <F64 as Float<F64>>::div)
     }
 
     /// Execute `f64.min` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_min(self, other: Self) -> Self {
         self.execute_binary(other, <F64 as Float<F64>>::min)
     }
 
     /// Execute `f64.max` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_max(self, other: Self) -> Self {
         self.execute_binary(other, <F64 as Float<F64>>::max)
     }
 
     /// Execute `f64.copysign` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_copysign(self, other: Self) -> Self {
         self.execute_binary(other, <F64 as Float<F64>>::copysign)
     }
@@ -1071,6 +1216,7 @@ impl UntypedValue {
     ///
     /// [WebAssembly specification]:
     /// https://webassembly.github.io/spec/core/exec/numerics.html#op-trunc-s
+    #[cfg(feature = "float")]
     pub fn i32_trunc_f32_s(self) -> Result<Self, TrapCode> {
         self.try_execute_unary(<F32 as TryTruncateInto<i32, TrapCode>>::try_truncate_into)
     }
@@ -1087,6 +1233,7 @@ impl UntypedValue {
     ///
     /// [WebAssembly specification]:
     /// https://webassembly.github.io/spec/core/exec/numerics.html#op-trunc-s
+    #[cfg(feature = "float")]
     pub fn i32_trunc_f32_u(self) -> Result<Self, TrapCode> {
         self.try_execute_unary(<F32 as TryTruncateInto<u32, TrapCode>>::try_truncate_into)
     }
@@ -1103,6 +1250,7 @@ impl UntypedValue {
     ///
     /// [WebAssembly specification]:
     /// https://webassembly.github.io/spec/core/exec/numerics.html#op-trunc-s
+    #[cfg(feature = "float")]
     pub fn i32_trunc_f64_s(self) -> Result<Self, TrapCode> {
         self.try_execute_unary(<F64 as TryTruncateInto<i32, TrapCode>>::try_truncate_into)
     }
@@ -1119,6 +1267,7 @@ impl UntypedValue {
     ///
     /// [WebAssembly specification]:
     /// https://webassembly.github.io/spec/core/exec/numerics.html#op-trunc-s
+    #[cfg(feature = "float")]
     pub fn i32_trunc_f64_u(self) -> Result<Self, TrapCode> {
         self.try_execute_unary(<F64 as TryTruncateInto<u32, TrapCode>>::try_truncate_into)
     }
@@ -1145,6 +1294,7 @@ impl UntypedValue {
     ///
     /// [WebAssembly specification]:
     /// https://webassembly.github.io/spec/core/exec/numerics.html#op-trunc-s
+    #[cfg(feature = "float")]
     pub fn i64_trunc_f32_s(self) -> Result<Self, TrapCode> {
         self.try_execute_unary(<F32 as TryTruncateInto<i64, TrapCode>>::try_truncate_into)
     }
@@ -1161,6 +1311,7 @@ impl UntypedValue {
     ///
     /// [WebAssembly specification]:
     /// https://webassembly.github.io/spec/core/exec/numerics.html#op-trunc-s
+    #[cfg(feature = "float")]
     pub fn i64_trunc_f32_u(self) -> Result<Self, TrapCode> {
         self.try_execute_unary(<F32 as TryTruncateInto<u64, TrapCode>>::try_truncate_into)
     }
@@ -1177,6 +1328,7 @@ impl UntypedValue {
     ///
     /// [WebAssembly specification]:
     /// https://webassembly.github.io/spec/core/exec/numerics.html#op-trunc-s
+    #[cfg(feature = "float")]
     pub fn i64_trunc_f64_s(self) -> Result<Self, TrapCode> {
         self.try_execute_unary(<F64 as TryTruncateInto<i64, TrapCode>>::try_truncate_into)
     }
@@ -1193,56 +1345,67 @@ impl UntypedValue {
     ///
     /// [WebAssembly specification]:
     /// https://webassembly.github.io/spec/core/exec/numerics.html#op-trunc-s
+    #[cfg(feature = "float")]
     pub fn i64_trunc_f64_u(self) -> Result<Self, TrapCode> {
         self.try_execute_unary(<F64 as TryTruncateInto<u64, TrapCode>>::try_truncate_into)
     }
 
     /// Execute `f32.convert_i32_s` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_convert_i32_s(self) -> Self {
         self.execute_unary(<i32 as ExtendInto<F32>>::extend_into)
     }
 
     /// Execute `f32.convert_i32_u` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_convert_i32_u(self) -> Self {
         self.execute_unary(<u32 as ExtendInto<F32>>::extend_into)
     }
 
     /// Execute `f32.convert_i64_s` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_convert_i64_s(self) -> Self {
         self.execute_unary(<i64 as WrapInto<F32>>::wrap_into)
     }
 
     /// Execute `f32.convert_i64_u` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_convert_i64_u(self) -> Self {
         self.execute_unary(<u64 as WrapInto<F32>>::wrap_into)
     }
 
     /// Execute `f32.demote_f64` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f32_demote_f64(self) -> Self {
         self.execute_unary(<F64 as WrapInto<F32>>::wrap_into)
     }
 
     /// Execute `f64.convert_i32_s` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_convert_i32_s(self) -> Self {
         self.execute_unary(<i32 as ExtendInto<F64>>::extend_into)
     }
 
     /// Execute `f64.convert_i32_u` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_convert_i32_u(self) -> Self {
         self.execute_unary(<u32 as ExtendInto<F64>>::extend_into)
     }
 
     /// Execute `f64.convert_i64_s` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_convert_i64_s(self) -> Self {
         self.execute_unary(<i64 as ExtendInto<F64>>::extend_into)
     }
 
     /// Execute `f64.convert_i64_u` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_convert_i64_u(self) -> Self {
         self.execute_unary(<u64 as ExtendInto<F64>>::extend_into)
     }
 
     /// Execute `f64.promote_f32` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn f64_promote_f32(self) -> Self {
         self.execute_unary(<F32 as ExtendInto<F64>>::extend_into)
     }
@@ -1272,42 +1435,90 @@ impl UntypedValue {
         self.execute_unary(<i64 as SignExtendFrom<i32>>::sign_extend_from)
     }
 
+    /// Execute `i32.reinterpret_f32` Wasm operation.
+    ///
+    /// # Note
+    ///
+    /// This is a no-op since `i32` and `f32` values share their representation.
+    #[cfg(feature = "float")]
+    pub fn i32_reinterpret_f32(self) -> Self {
+        self
+    }
+
+    /// Execute `i64.reinterpret_f64` Wasm operation.
+    ///
+    /// # Note
+    ///
+    /// This is a no-op since `i64` and `f64` values share their representation.
+    #[cfg(feature = "float")]
+    pub fn i64_reinterpret_f64(self) -> Self {
+        self
+    }
+
+    /// Execute `f32.reinterpret_i32` Wasm operation.
+    ///
+    /// # Note
+    ///
+    /// This is a no-op since `f32` and `i32` values share their representation.
+    #[cfg(feature = "float")]
+    pub fn f32_reinterpret_i32(self) -> Self {
+        self
+    }
+
+    /// Execute `f64.reinterpret_i64` Wasm operation.
+    ///
+    /// # Note
+    ///
+    /// This is a no-op since `f64` and `i64` values share their representation.
+    #[cfg(feature = "float")]
+    pub fn f64_reinterpret_i64(self) -> Self {
+        self
+    }
+
     /// Execute `i32.trunc_sat_f32_s` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn i32_trunc_sat_f32_s(self) -> Self {
         self.execute_unary(<F32 as TruncateSaturateInto<i32>>::truncate_saturate_into)
     }
 
     /// Execute `i32.trunc_sat_f32_u` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn i32_trunc_sat_f32_u(self) -> Self {
         self.execute_unary(<F32 as TruncateSaturateInto<u32>>::truncate_saturate_into)
     }
 
     /// Execute `i32.trunc_sat_f64_s` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn i32_trunc_sat_f64_s(self) -> Self {
         self.execute_unary(<F64 as TruncateSaturateInto<i32>>::truncate_saturate_into)
     }
 
     /// Execute `i32.trunc_sat_f64_u` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn i32_trunc_sat_f64_u(self) -> Self {
         self.execute_unary(<F64 as TruncateSaturateInto<u32>>::truncate_saturate_into)
     }
 
     /// Execute `i64.trunc_sat_f32_s` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn i64_trunc_sat_f32_s(self) -> Self {
         self.execute_unary(<F32 as TruncateSaturateInto<i64>>::truncate_saturate_into)
     }
 
     /// Execute `i64.trunc_sat_f32_u` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn i64_trunc_sat_f32_u(self) -> Self {
         self.execute_unary(<F32 as TruncateSaturateInto<u64>>::truncate_saturate_into)
     }
 
     /// Execute `i64.trunc_sat_f64_s` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn i64_trunc_sat_f64_s(self) -> Self {
         self.execute_unary(<F64 as TruncateSaturateInto<i64>>::truncate_saturate_into)
     }
 
     /// Execute `i64.trunc_sat_f64_u` Wasm operation.
+    #[cfg(feature = "float")]
     pub fn i64_trunc_sat_f64_u(self) -> Self {
         self.execute_unary(<F64 as TruncateSaturateInto<u64>>::truncate_saturate_into)
     }
@@ -1341,6 +1552,8 @@ macro_rules! for_each_tuple {
 pub enum UntypedError {
     /// The [`UntypedValue`] slice length did not match `Self`.
     InvalidLen,
+    /// The length of the encoded bytes of an [`UntypedValue`] did not match its [`ValueType`].
+    InvalidByteLen,
 }
 
 impl UntypedError {
@@ -1349,6 +1562,12 @@ impl UntypedError {
     pub fn invalid_len() -> Self {
         Self::InvalidLen
     }
+
+    /// Creates a new `InvalidByteLen` [`UntypedError`].
+    #[cold]
+    pub fn invalid_byte_len() -> Self {
+        Self::InvalidByteLen
+    }
 }
 
 impl Display for UntypedError {
@@ -1357,6 +1576,9 @@ impl Display for UntypedError {
             UntypedError::InvalidLen => {
                 write!(f, "mismatched length of the untyped slice",)
             }
+            UntypedError::InvalidByteLen => {
+                write!(f, "mismatched length of the encoded bytes")
+            }
         }
     }
 }
@@ -1500,3 +1722,113 @@ macro_rules! impl_encode_untyped_slice {
     };
 }
 for_each_tuple!(impl_encode_untyped_slice);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn representation() {
+        assert_eq!(UntypedValue::from(-1_i32).to_bits(), 0xFFFF_FFFF);
+        assert_eq!(UntypedValue::from(-1_i64).to_bits(), u64::MAX);
+        assert_eq!(
+            UntypedValue::from(1.0_f32).to_bits(),
+            u64::from(1.0_f32.to_bits())
+        );
+        assert_eq!(UntypedValue::from(1.0_f64).to_bits(), 1.0_f64.to_bits());
+        let value = UntypedValue::from_bits(0x1234_5678_9ABC_DEF0);
+        assert_eq!(value.to_bits(), 0x1234_5678_9ABC_DEF0);
+        let cmp = UntypedValue::from(1_i32).i32_lt_s(UntypedValue::from(2_i32));
+        assert_eq!(cmp.to_bits(), 1);
+    }
+
+    #[test]
+    fn trapping_operations() {
+        let min = UntypedValue::from(i32::MIN);
+        let minus_one = UntypedValue::from(-1_i32);
+        let zero = UntypedValue::from(0_i32);
+        assert_eq!(min.i32_div_s(minus_one), Err(TrapCode::IntegerOverflow));
+        assert_eq!(min.i32_div_u(zero), Err(TrapCode::IntegerDivisionByZero));
+        assert_eq!(min.i32_rem_s(minus_one), Ok(zero));
+        assert_eq!(min.i32_rem_u(zero), Err(TrapCode::IntegerDivisionByZero));
+    }
+
+    #[test]
+    #[cfg(feature = "float")]
+    fn trapping_conversions() {
+        assert_eq!(
+            UntypedValue::from(f32::NAN).i32_trunc_f32_s(),
+            Err(TrapCode::BadConversionToInteger)
+        );
+        assert_eq!(
+            UntypedValue::from(f64::INFINITY).i64_trunc_f64_u(),
+            Err(TrapCode::IntegerOverflow)
+        );
+        assert_eq!(
+            i32::from(UntypedValue::from(f32::NAN).i32_trunc_sat_f32_s()),
+            0
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "float")]
+    fn reinterpret() {
+        let value = UntypedValue::from(-1.5_f32).i32_reinterpret_f32();
+        assert_eq!(i32::from(value), (-1.5_f32).to_bits() as i32);
+        let value = UntypedValue::from(-1.5_f64).i64_reinterpret_f64();
+        assert_eq!(i64::from(value), (-1.5_f64).to_bits() as i64);
+        let value = UntypedValue::from(0x3FC0_0000_i32).f32_reinterpret_i32();
+        assert_eq!(f32::from(value), 1.5);
+        let value = UntypedValue::from(0x3FF8_0000_0000_0000_i64).f64_reinterpret_i64();
+        assert_eq!(f64::from(value), 1.5);
+    }
+
+    #[test]
+    fn le_bytes() {
+        let value = UntypedValue::from_bits(0x0102_0304_0506_0708);
+        let bytes = [0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01];
+        assert_eq!(value.to_le_bytes(), bytes);
+        assert_eq!(UntypedValue::from_le_bytes(bytes), value);
+    }
+
+    #[test]
+    fn typed_le_bytes() {
+        let values = [
+            (
+                ValueType::I32,
+                UntypedValue::from(-2_i32),
+                &[0xFE, 0xFF, 0xFF, 0xFF][..],
+            ),
+            (
+                ValueType::I64,
+                UntypedValue::from(-2_i64),
+                &[0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF][..],
+            ),
+            (
+                ValueType::F32,
+                UntypedValue::from(1.5_f32),
+                &[0x00, 0x00, 0xC0, 0x3F][..],
+            ),
+            (
+                ValueType::F64,
+                UntypedValue::from(1.5_f64),
+                &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xF8, 0x3F][..],
+            ),
+        ];
+        for (ty, value, expected) in values {
+            let mut bytes = [0x00_u8; 8];
+            let bytes = &mut bytes[..UntypedValue::typed_le_bytes_len(ty)];
+            value.write_typed_le_bytes(ty, bytes).unwrap();
+            assert_eq!(bytes, expected);
+            assert_eq!(UntypedValue::from_typed_le_bytes(ty, bytes).unwrap(), value);
+        }
+        assert!(matches!(
+            UntypedValue::from(1_i32).write_typed_le_bytes(ValueType::I32, &mut [0x00; 8]),
+            Err(UntypedError::InvalidByteLen)
+        ));
+        assert!(matches!(
+            UntypedValue::from_typed_le_bytes(ValueType::F64, &[0x00; 4]),
+            Err(UntypedError::InvalidByteLen)
+        ));
+    }
+}
@@ -54,6 +54,7 @@ pub trait WrapInto<T> {
 ///
 /// Traps when the input float cannot be represented by the target integer or
 /// when the input float is NaN.
+#[cfg(feature = "float")]
 pub trait TryTruncateInto<T, E> {
     /// Convert one type to another by rounding to the nearest integer towards zero.
     ///
@@ -76,6 +77,7 @@ pub trait TryTruncateInto<T, E> {
 /// - `0` when the input is NaN.
 /// - `int::MIN` when the input is -INF.
 /// - `int::MAX` when the input is +INF.
+#[cfg(feature = "float")]
 pub trait TruncateSaturateInto<T> {
     /// Convert one type to another by rounding to the nearest integer towards zero.
     fn truncate_saturate_into(self) -> T;
@@ -94,6 +96,7 @@ pub trait SignExtendFrom<T> {
 }
 
 /// Reinterprets the bits of a value of one type as another type.
+#[cfg(feature = "float")]
 pub trait TransmuteInto<T> {
     /// Reinterprets the bits of a value of one type as another type.
     fn transmute_into(self) -> T;
@@ -239,6 +242,7 @@ pub trait Integer<T>: ArithmeticOps<T> {
 }
 
 /// Float-point value.
+#[cfg(feature = "float")]
 pub trait Float<T>: ArithmeticOps<T> {
     /// Get absolute value.
     fn abs(self) -> T;
@@ -310,6 +314,7 @@ impl WrapInto<F32> for F64 {
 
 macro_rules! impl_try_truncate_into {
     (@primitive $from: ident, $into: ident, $to_primitive:path, $rmin:literal, $rmax:literal) => {
+        #[cfg(feature = "float")]
         impl TryTruncateInto<$into, TrapCode> for $from {
             #[inline]
             fn try_truncate_into(self) -> Result<$into, TrapCode> {
@@ -323,6 +328,7 @@ macro_rules! impl_try_truncate_into {
             }
         }
 
+        #[cfg(feature = "float")]
         impl TruncateSaturateInto<$into> for $from {
             #[inline]
             fn truncate_saturate_into(self) -> $into {
@@ -340,6 +346,7 @@ macro_rules! impl_try_truncate_into {
         }
     };
     (@wrapped $from:ident, $intermediate:ident, $into:ident) => {
+        #[cfg(feature = "float")]
         impl TryTruncateInto<$into, TrapCode> for $from {
             #[inline]
             fn try_truncate_into(self) -> Result<$into, TrapCode> {
@@ -347,6 +354,7 @@ macro_rules! impl_try_truncate_into {
             }
         }
 
+        #[cfg(feature = "float")]
         impl TruncateSaturateInto<$into> for $from {
             #[inline]
             fn truncate_saturate_into(self) -> $into {
@@ -447,6 +455,7 @@ impl_sign_extend_from! {
 
 macro_rules! impl_transmute_into_self {
     ($type: ident) => {
+        #[cfg(feature = "float")]
         impl TransmuteInto<$type> for $type {
             #[inline]
             fn transmute_into(self) -> $type {
@@ -465,6 +474,7 @@ impl_transmute_into_self!(F64);
 
 macro_rules! impl_transmute_into_as {
     ($from: ident, $into: ident) => {
+        #[cfg(feature = "float")]
         impl TransmuteInto<$into> for $from {
             #[inline]
             fn transmute_into(self) -> $into {
@@ -480,6 +490,7 @@ impl_transmute_into_as!(i64, u64);
 
 macro_rules! impl_transmute_into_npf {
     ($npf:ident, $float:ident, $signed:ident, $unsigned:ident) => {
+        #[cfg(feature = "float")]
         impl TransmuteInto<$float> for $npf {
             #[inline]
             fn transmute_into(self) -> $float {
@@ -487,6 +498,7 @@ macro_rules! impl_transmute_into_npf {
             }
         }
 
+        #[cfg(feature = "float")]
         impl TransmuteInto<$npf> for $float {
             #[inline]
             fn transmute_into(self) -> $npf {
@@ -494,6 +506,7 @@ macro_rules! impl_transmute_into_npf {
             }
         }
 
+        #[cfg(feature = "float")]
         impl TransmuteInto<$signed> for $npf {
             #[inline]
             fn transmute_into(self) -> $signed {
@@ -501,6 +514,7 @@ macro_rules! impl_transmute_into_npf {
             }
         }
 
+        #[cfg(feature = "float")]
         impl TransmuteInto<$unsigned> for $npf {
             #[inline]
             fn transmute_into(self) -> $unsigned {
@@ -508,6 +522,7 @@ macro_rules! impl_transmute_into_npf {
             }
         }
 
+        #[cfg(feature = "float")]
         impl TransmuteInto<$npf> for $signed {
             #[inline]
             fn transmute_into(self) -> $npf {
@@ -515,6 +530,7 @@ macro_rules! impl_transmute_into_npf {
             }
         }
 
+        #[cfg(feature = "float")]
         impl TransmuteInto<$npf> for $unsigned {
             #[inline]
             fn transmute_into(self) -> $npf {
@@ -527,6 +543,7 @@ macro_rules! impl_transmute_into_npf {
 impl_transmute_into_npf!(F32, f32, i32, u32);
 impl_transmute_into_npf!(F64, f64, i64, u64);
 
+#[cfg(feature = "float")]
 impl TransmuteInto<i32> for f32 {
     #[inline]
     fn transmute_into(self) -> i32 {
@@ -534,6 +551,7 @@ impl TransmuteInto<i32> for f32 {
     }
 }
 
+#[cfg(feature = "float")]
 impl TransmuteInto<i64> for f64 {
     #[inline]
     fn transmute_into(self) -> i64 {
@@ -541,6 +559,7 @@ impl TransmuteInto<i64> for f64 {
     }
 }
 
+#[cfg(feature = "float")]
 impl TransmuteInto<f32> for i32 {
     #[inline]
     fn transmute_into(self) -> f32 {
@@ -548,6 +567,7 @@ impl TransmuteInto<f32> for i32 {
     }
 }
 
+#[cfg(feature = "float")]
 impl TransmuteInto<f64> for i64 {
     #[inline]
     fn transmute_into(self) -> f64 {
@@ -555,6 +575,7 @@ impl TransmuteInto<f64> for i64 {
     }
 }
 
+#[cfg(feature = "float")]
 impl TransmuteInto<i32> for u32 {
     #[inline]
     fn transmute_into(self) -> i32 {
@@ -562,6 +583,7 @@ impl TransmuteInto<i32> for u32 {
     }
 }
 
+#[cfg(feature = "float")]
 impl TransmuteInto<i64> for u64 {
     #[inline]
     fn transmute_into(self) -> i64 {
@@ -666,13 +688,13 @@ impl_integer!(u32);
 impl_integer!(i64);
 impl_integer!(u64);
 
-#[cfg(feature = "std")]
+#[cfg(all(feature = "std", feature = "float"))]
 mod fmath {
     pub use f32;
     pub use f64;
 }
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(not(feature = "std"), feature = "float"))]
 mod fmath {
     pub use super::libm_adapters::{f32, f64};
 }
@@ -683,6 +705,7 @@ mod fmath {
 macro_rules! impl_float {
     ($type:ident, $fXX:ident, $iXX:ident) => {
         // In this particular instance we want to directly compare floating point numbers.
+        #[cfg(feature = "float")]
         impl Float<Self> for $type {
             #[inline]
             fn abs(self) -> Self {
@@ -788,6 +811,7 @@ macro_rules! impl_float {
 }
 
 #[test]
+#[cfg(feature = "float")]
 fn wasm_float_min_regression_works() {
     assert_eq!(
         Float::min(F32::from(-0.0), F32::from(0.0)).to_bits(),
@@ -800,6 +824,7 @@ fn wasm_float_min_regression_works() {
 }
 
 #[test]
+#[cfg(feature = "float")]
 fn wasm_float_max_regression_works() {
     assert_eq!(
         Float::max(F32::from(-0.0), F32::from(0.0)).to_bits(),
@@ -817,6 +842,7 @@ impl_float!(F32, f32, i32);
 impl_float!(F64, f64, i64);
 
 #[test]
+#[cfg(feature = "float")]
 fn copysign_regression_works() {
     // This test has been directly extracted from a WebAssembly Specification assertion.
     use Float as _;
@@ -829,7 +855,7 @@ fn copysign_regression_works() {
     )
 }
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(not(feature = "std"), feature = "float"))]
 mod libm_adapters {
     pub mod f32 {
         #[inline]
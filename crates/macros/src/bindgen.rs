@@ -0,0 +1,400 @@
+//! Implementation of the `bindgen!` macro.
+
+use crate::{
+    host_func::{HostFunc, Param, Receiver},
+    wit::{self, Document, Function, Type, World, WorldItem},
+};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use std::path::PathBuf;
+use syn::{Ident, LitStr};
+
+/// The arguments of the `bindgen!` macro.
+#[derive(Default)]
+pub struct Args {
+    /// The inline WIT source.
+    pub inline: Option<LitStr>,
+    /// The path to the WIT source relative to the crate root.
+    pub path: Option<LitStr>,
+    /// The name of the world for which bindings are generated.
+    pub world: Option<LitStr>,
+}
+
+impl Args {
+    /// Parses a single `bindgen!` argument.
+    pub fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        let arg = if meta.path.is_ident("inline") {
+            &mut self.inline
+        } else if meta.path.is_ident("path") {
+            &mut self.path
+        } else if meta.path.is_ident("world") {
+            &mut self.world
+        } else {
+            return Err(meta.error("unsupported `bindgen` argument"));
+        };
+        *arg = Some(meta.value()?.parse()?);
+        Ok(())
+    }
+}
+
+/// Expands the `bindgen!` macro with the given `args`.
+pub fn expand(args: Args) -> syn::Result<TokenStream2> {
+    let (source, tracked, span) = match (args.inline, args.path) {
+        (Some(inline), None) => (inline.value(), None, inline.span()),
+        (None, Some(path)) => {
+            let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+            let file = PathBuf::from(root).join(path.value());
+            let source = std::fs::read_to_string(&file).map_err(|error| {
+                syn::Error::new(
+                    path.span(),
+                    format!("failed to read {}: {error}", file.display()),
+                )
+            })?;
+            let file = file.display().to_string();
+            (source, Some(file), path.span())
+        }
+        _ => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "expected exactly one of `inline = \"...\"` or `path = \"...\"`",
+            ))
+        }
+    };
+    let document = wit::parse(&source).map_err(|error| syn::Error::new(span, error))?;
+    let world = select_world(&document, args.world.as_ref())?;
+    let mut bindings = Bindings::new(&document, world);
+    bindings.generate(span)?;
+    // Makes the compiler rebuild the bindings whenever the WIT file changes.
+    let tracked = tracked.map(|file| quote! { const _: &str = include_str!(#file); });
+    Ok(quote! {
+        #tracked
+        #bindings
+    })
+}
+
+/// Returns the [`World`] of the `document` for which bindings are generated.
+fn select_world<'a>(document: &'a Document, name: Option<&LitStr>) -> syn::Result<&'a World> {
+    match name {
+        Some(name) => document
+            .worlds
+            .iter()
+            .find(|world| world.name == name.value())
+            .ok_or_else(|| {
+                syn::Error::new(name.span(), format!("unknown world `{}`", name.value()))
+            }),
+        None => match &document.worlds[..] {
+            [world] => Ok(world),
+            _ => Err(syn::Error::new(
+                Span::call_site(),
+                "expected exactly one world or a `world = \"...\"` argument",
+            )),
+        },
+    }
+}
+
+/// The Rust bindings generated for a [`World`].
+struct Bindings<'a> {
+    /// The package of the WIT document if any.
+    package: Option<&'a str>,
+    /// The world for which bindings are generated.
+    world: &'a World,
+    /// The host traits for the imports of the world.
+    traits: Vec<TokenStream2>,
+    /// The names of the host traits that the host state must implement.
+    bounds: Vec<Ident>,
+    /// The `Linker` definitions of the imported host functions.
+    definitions: Vec<TokenStream2>,
+    /// The typed function fields for the exports of the world.
+    fields: Vec<TokenStream2>,
+    /// The initializers of the typed function fields.
+    inits: Vec<TokenStream2>,
+    /// The methods calling the exports of the world.
+    methods: Vec<TokenStream2>,
+}
+
+impl<'a> Bindings<'a> {
+    /// Creates new empty [`Bindings`] for the `world` of the `document`.
+    fn new(document: &'a Document, world: &'a World) -> Self {
+        Self {
+            package: document.package.as_deref(),
+            world,
+            traits: Vec::new(),
+            bounds: Vec::new(),
+            definitions: Vec::new(),
+            fields: Vec::new(),
+            inits: Vec::new(),
+            methods: Vec::new(),
+        }
+    }
+
+    /// Generates the bindings for all imports and exports of the world.
+    ///
+    /// Errors are reported at `span`.
+    fn generate(&mut self, span: Span) -> syn::Result<()> {
+        let world = self.world;
+        let root_funcs = world
+            .imports
+            .iter()
+            .filter_map(|item| match item {
+                WorldItem::Func(func) => Some(func.clone()),
+                WorldItem::Interface(..) => None,
+            })
+            .collect::<Vec<_>>();
+        if !root_funcs.is_empty() {
+            let name = format!("{}-imports", world.name);
+            let doc = format!("Host functions imported by the `{}` world.", world.name);
+            self.import(&name, "$root", &doc, &root_funcs, span)?;
+        }
+        for item in &world.imports {
+            if let WorldItem::Interface(name, funcs) = item {
+                let module = self.qualify(name);
+                let doc = format!("Host functions of the imported `{module}` interface.");
+                self.import(name, &module, &doc, funcs, span)?;
+            }
+        }
+        for item in &world.exports {
+            match item {
+                WorldItem::Func(func) => self.export(&func.name, &func.name, func, span)?,
+                WorldItem::Interface(name, funcs) => {
+                    let module = self.qualify(name);
+                    for func in funcs {
+                        let export = format!("{module}#{}", func.name);
+                        let method = format!("{name}-{}", func.name);
+                        self.export(&method, &export, func, span)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the fully qualified name of the interface `name`.
+    fn qualify(&self, name: &str) -> String {
+        let Some(package) = self.package else {
+            return name.into();
+        };
+        match package.split_once('@') {
+            Some((package, version)) => format!("{package}/{name}@{version}"),
+            None => format!("{package}/{name}"),
+        }
+    }
+
+    /// Generates the host trait `name` for the `funcs` imported from `module`.
+    fn import(
+        &mut self,
+        name: &str,
+        module: &str,
+        doc: &str,
+        funcs: &[Function],
+        span: Span,
+    ) -> syn::Result<()> {
+        let trait_ident = upper_camel_ident(name);
+        let mut signatures = Vec::new();
+        for func in funcs {
+            let ident = snake_ident(&func.name);
+            let mut params = Vec::new();
+            let mut inputs = Vec::new();
+            for (name, ty) in &func.params {
+                let param_ident = snake_ident(name);
+                let (rust_ty, param) = import_param(param_ident.clone(), ty, span)?;
+                inputs.push(quote! { #param_ident: #rust_ty });
+                params.push(param);
+            }
+            let output = func
+                .result
+                .as_ref()
+                .map(|ty| core_type(ty, span))
+                .transpose()?
+                .map(|ty| quote! { -> #ty });
+            let doc = format!("Implements the imported `{}` function.", func.name);
+            signatures.push(quote! {
+                #[doc = #doc]
+                fn #ident(&mut self, #( #inputs ),*) #output;
+            });
+            let host_func = HostFunc::new(ident, Receiver::Mut, params, false);
+            let closure = host_func.expand(&quote! { T }, &quote! { <T as #trait_ident> });
+            let func_name = &func.name;
+            self.definitions.push(quote! {
+                linker.func_wrap(#module, #func_name, #closure)?;
+            });
+        }
+        self.traits.push(quote! {
+            #[doc = #doc]
+            pub trait #trait_ident {
+                #( #signatures )*
+            }
+        });
+        self.bounds.push(trait_ident);
+        Ok(())
+    }
+
+    /// Generates the method `name` calling the exported `func` named `export`.
+    fn export(&mut self, name: &str, export: &str, func: &Function, span: Span) -> syn::Result<()> {
+        let ident = snake_ident(name);
+        let mut params = Vec::new();
+        let mut param_tys = Vec::new();
+        for (name, ty) in &func.params {
+            params.push(snake_ident(name));
+            param_tys.push(core_type(ty, span)?);
+        }
+        let result = match &func.result {
+            Some(ty) => core_type(ty, span)?,
+            None => quote! { () },
+        };
+        self.fields.push(quote! {
+            #ident: ::wasmi::TypedFunc<( #( #param_tys, )* ), #result>
+        });
+        self.inits.push(quote! {
+            #ident: instance.get_typed_func(&store, #export)?
+        });
+        let doc = format!("Calls the exported `{export}` function.");
+        self.methods.push(quote! {
+            #[doc = #doc]
+            ///
+            /// # Errors
+            ///
+            /// If the called function traps.
+            pub fn #ident(
+                &self,
+                store: impl ::wasmi::AsContextMut,
+                #( #params: #param_tys ),*
+            ) -> ::core::result::Result<#result, ::wasmi::Error> {
+                self.#ident.call(store, ( #( #params, )* ))
+            }
+        });
+        Ok(())
+    }
+}
+
+impl quote::ToTokens for Bindings<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Self {
+            world,
+            traits,
+            bounds,
+            definitions,
+            fields,
+            inits,
+            methods,
+            ..
+        } = self;
+        let ident = upper_camel_ident(&world.name);
+        let doc = format!(
+            "Bindings to the exports and host function imports of the `{}` world.",
+            world.name
+        );
+        tokens.extend(quote! {
+            #( #traits )*
+
+            #[doc = #doc]
+            #[derive(Debug, Copy, Clone)]
+            pub struct #ident {
+                #( #fields, )*
+            }
+
+            impl #ident {
+                /// Defines the host functions imported by the world in the `linker`.
+                ///
+                /// # Errors
+                ///
+                /// If any of the host functions is already defined in the `linker`.
+                #[allow(unused_mut, unused_variables, clippy::needless_question_mark)]
+                pub fn add_to_linker<T>(
+                    linker: &mut ::wasmi::Linker<T>,
+                ) -> ::core::result::Result<(), ::wasmi::errors::LinkerError>
+                where
+                    T: #( #bounds + )* 'static,
+                {
+                    #( #definitions )*
+                    ::core::result::Result::Ok(())
+                }
+
+                /// Creates the bindings to the exports of the `instance`.
+                ///
+                /// # Errors
+                ///
+                /// If the `instance` is missing an export of the world or if its type mismatches.
+                #[allow(unused_variables)]
+                pub fn new(
+                    store: impl ::wasmi::AsContext,
+                    instance: &::wasmi::Instance,
+                ) -> ::core::result::Result<Self, ::wasmi::Error> {
+                    ::core::result::Result::Ok(Self {
+                        #( #inits, )*
+                    })
+                }
+
+                #( #methods )*
+            }
+        });
+    }
+}
+
+/// Returns the Rust type and the host function [`Param`] for an imported function parameter.
+fn import_param(ident: Ident, ty: &Type, span: Span) -> syn::Result<(TokenStream2, Param)> {
+    let rust_ty = match ty {
+        Type::Bool => return Ok((quote! { bool }, Param::Bool(ident))),
+        Type::String => return Ok((quote! { &str }, Param::Str(ident))),
+        Type::List(elem) if **elem == Type::U8 => {
+            return Ok((quote! { &[u8] }, Param::Bytes(ident)))
+        }
+        Type::U8 => quote! { u8 },
+        Type::U16 => quote! { u16 },
+        Type::S8 => quote! { i8 },
+        Type::S16 => quote! { i16 },
+        _ => core_type(ty, span)?,
+    };
+    let param_ty = Box::new(syn::parse_quote! { #rust_ty });
+    let param = match ty {
+        Type::U8 | Type::U16 | Type::S8 | Type::S16 => Param::Int(ident, param_ty),
+        _ => Param::Value(ident, param_ty),
+    };
+    Ok((rust_ty, param))
+}
+
+/// Returns the Rust type of a WIT type that maps to a core Wasm value type.
+fn core_type(ty: &Type, span: Span) -> syn::Result<TokenStream2> {
+    let rust_ty = match ty {
+        Type::U32 => quote! { u32 },
+        Type::S32 => quote! { i32 },
+        Type::U64 => quote! { u64 },
+        Type::S64 => quote! { i64 },
+        Type::F32 => quote! { ::wasmi::core::F32 },
+        Type::F64 => quote! { ::wasmi::core::F64 },
+        _ => {
+            return Err(syn::Error::new(
+                span,
+                format!("WIT type `{ty}` is not supported in this position"),
+            ))
+        }
+    };
+    Ok(rust_ty)
+}
+
+/// Returns the Rust identifier for the kebab-case WIT `name` in snake case.
+fn snake_ident(name: &str) -> Ident {
+    rust_ident(&name.replace('-', "_"))
+}
+
+/// Returns the Rust identifier for the kebab-case WIT `name` in upper camel case.
+fn upper_camel_ident(name: &str) -> Ident {
+    let name = name
+        .split(['-', '_'])
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<String>();
+    rust_ident(&name)
+}
+
+/// Returns `name` as Rust identifier using a raw identifier for keywords.
+fn rust_ident(name: &str) -> Ident {
+    match syn::parse_str::<Ident>(name) {
+        Ok(ident) => ident,
+        Err(_) => format_ident!("r#{name}"),
+    }
+}
@@ -0,0 +1,283 @@
+//! Generation of host function closures shared by the macros of this crate.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{spanned::Spanned, FnArg, Ident, ImplItemFn, Pat, ReturnType, Type};
+
+/// The way a host function accesses the host state.
+pub enum Receiver {
+    /// The host function takes `&self`.
+    Ref,
+    /// The host function takes `&mut self`.
+    Mut,
+    /// The host function takes a `Caller` as first parameter.
+    Caller,
+    /// The host function takes neither of both.
+    None,
+}
+
+/// A parameter of a host function and how it is converted from its Wasm representation.
+pub enum Param {
+    /// A parameter that is forwarded as is.
+    Value(Ident, Box<Type>),
+    /// A `bool` parameter passed as `i32`.
+    Bool(Ident),
+    /// A small integer parameter passed as `i32`.
+    Int(Ident, Box<Type>),
+    /// A `&[u8]` parameter passed as pointer and length into the linear memory.
+    Bytes(Ident),
+    /// A `&str` parameter passed as pointer and length into the linear memory.
+    Str(Ident),
+}
+
+impl Param {
+    /// Returns `true` if the parameter refers to the linear memory.
+    fn is_guest_slice(&self) -> bool {
+        matches!(self, Self::Bytes(_) | Self::Str(_))
+    }
+}
+
+/// A host function that is defined in a `Linker` by generated code.
+pub struct HostFunc {
+    /// The name of the Rust function implementing the host function.
+    ident: Ident,
+    /// How the host function accesses the host state.
+    receiver: Receiver,
+    /// The parameters of the host function excluding the receiver.
+    params: Vec<Param>,
+    /// Whether the method returns a `Result`.
+    returns_result: bool,
+}
+
+impl HostFunc {
+    /// Creates a new [`HostFunc`] that is implemented by the Rust function `ident`.
+    pub fn new(ident: Ident, receiver: Receiver, params: Vec<Param>, returns_result: bool) -> Self {
+        Self {
+            ident,
+            receiver,
+            params,
+            returns_result,
+        }
+    }
+
+    /// Parses the signature of `method`.
+    pub fn parse(method: &ImplItemFn) -> syn::Result<Self> {
+        let sig = &method.sig;
+        if !sig.generics.params.is_empty() {
+            return Err(syn::Error::new(
+                sig.generics.span(),
+                "host functions cannot be generic",
+            ));
+        }
+        if let Some(asyncness) = &sig.asyncness {
+            return Err(syn::Error::new(
+                asyncness.span(),
+                "host functions cannot be async",
+            ));
+        }
+        let mut inputs = sig.inputs.iter().peekable();
+        let mut receiver = Receiver::None;
+        if let Some(FnArg::Receiver(arg)) = inputs.peek() {
+            receiver = match (&arg.reference, &arg.mutability) {
+                (Some(_), Some(_)) => Receiver::Mut,
+                (Some(_), None) => Receiver::Ref,
+                (None, _) => {
+                    return Err(syn::Error::new(
+                        arg.span(),
+                        "host functions cannot take `self` by value",
+                    ))
+                }
+            };
+            inputs.next();
+        }
+        if let Some(FnArg::Typed(arg)) = inputs.peek() {
+            if matches!(receiver, Receiver::None) && is_caller(&arg.ty) {
+                receiver = Receiver::Caller;
+                inputs.next();
+            }
+        }
+        let params = inputs
+            .map(Self::parse_param)
+            .collect::<syn::Result<Vec<_>>>()?;
+        if matches!(receiver, Receiver::Caller) && params.iter().any(Param::is_guest_slice) {
+            return Err(syn::Error::new(
+                sig.inputs.span(),
+                "guest slices cannot be combined with a `Caller` parameter",
+            ));
+        }
+        let returns_result = match &sig.output {
+            ReturnType::Default => false,
+            ReturnType::Type(_, ty) => last_segment_is(ty, "Result"),
+        };
+        Ok(Self {
+            ident: sig.ident.clone(),
+            receiver,
+            params,
+            returns_result,
+        })
+    }
+
+    /// Parses a single non-receiver parameter of a host function.
+    fn parse_param(arg: &FnArg) -> syn::Result<Param> {
+        let FnArg::Typed(arg) = arg else {
+            return Err(syn::Error::new(arg.span(), "unexpected `self` parameter"));
+        };
+        let Pat::Ident(pat) = &*arg.pat else {
+            return Err(syn::Error::new(
+                arg.pat.span(),
+                "host function parameters must be plain identifiers",
+            ));
+        };
+        let ident = pat.ident.clone();
+        if let Type::Reference(reference) = &*arg.ty {
+            if reference.mutability.is_some() {
+                return Err(syn::Error::new(
+                    reference.span(),
+                    "guest slices must be shared references",
+                ));
+            }
+            return match &*reference.elem {
+                Type::Slice(slice) if last_segment_is(&slice.elem, "u8") => Ok(Param::Bytes(ident)),
+                elem if last_segment_is(elem, "str") => Ok(Param::Str(ident)),
+                _ => Err(syn::Error::new(
+                    reference.span(),
+                    "only `&[u8]` and `&str` are supported as reference parameters",
+                )),
+            };
+        }
+        if is_caller(&arg.ty) {
+            return Err(syn::Error::new(
+                arg.ty.span(),
+                "`Caller` must be the first parameter and cannot be combined with `self`",
+            ));
+        }
+        if last_segment_is(&arg.ty, "bool") {
+            return Ok(Param::Bool(ident));
+        }
+        if ["u8", "u16", "i8", "i16"]
+            .iter()
+            .any(|name| last_segment_is(&arg.ty, name))
+        {
+            return Ok(Param::Int(ident, arg.ty.clone()));
+        }
+        Ok(Param::Value(ident, arg.ty.clone()))
+    }
+
+    /// Expands the closure that is registered in the `Linker`.
+    ///
+    /// The closure takes a `Caller` with host state of type `state_ty`
+    /// and calls the host function as associated function of `owner`.
+    pub fn expand(&self, state_ty: &TokenStream2, owner: &TokenStream2) -> TokenStream2 {
+        let caller = format_ident!("__wasmi_caller");
+        let memory = format_ident!("__wasmi_memory");
+        let state = format_ident!("__wasmi_state");
+        let mut closure_params = vec![quote! { mut #caller: ::wasmi::Caller<'_, #state_ty> }];
+        let mut conversions = Vec::new();
+        let mut args = Vec::new();
+        for param in &self.params {
+            match param {
+                Param::Value(ident, ty) => {
+                    closure_params.push(quote! { #ident: #ty });
+                }
+                Param::Bool(ident) => {
+                    closure_params.push(quote! { #ident: i32 });
+                    conversions.push(try_expr(
+                        ident,
+                        quote! { ::wasmi::__private::guest_bool(#ident) },
+                    ));
+                }
+                Param::Int(ident, ty) => {
+                    closure_params.push(quote! { #ident: i32 });
+                    conversions.push(try_expr(
+                        ident,
+                        quote! { ::wasmi::__private::guest_int::<#ty>(#ident) },
+                    ));
+                }
+                Param::Bytes(ident) | Param::Str(ident) => {
+                    let ptr = format_ident!("{ident}_ptr");
+                    let len = format_ident!("{ident}_len");
+                    closure_params.push(quote! { #ptr: u32 });
+                    closure_params.push(quote! { #len: u32 });
+                    let convert = match param {
+                        Param::Str(_) => quote! { guest_str },
+                        _ => quote! { guest_bytes },
+                    };
+                    conversions.push(try_expr(
+                        ident,
+                        quote! { ::wasmi::__private::#convert(#memory, #ptr, #len) },
+                    ));
+                }
+            }
+            let ident = match param {
+                Param::Value(ident, _)
+                | Param::Bool(ident)
+                | Param::Int(ident, _)
+                | Param::Bytes(ident)
+                | Param::Str(ident) => ident,
+            };
+            args.push(quote! { #ident });
+        }
+        let uses_memory = self.params.iter().any(Param::is_guest_slice);
+        let access_memory = uses_memory.then(|| {
+            let memory_ident = memory.clone();
+            let resolve = try_expr(
+                &memory_ident,
+                quote! { ::wasmi::__private::exported_memory(&#caller) },
+            );
+            quote! {
+                #resolve
+                let (#memory, #state) = #memory.data_and_store_mut(&mut #caller);
+                let #memory: &[u8] = #memory;
+            }
+        });
+        let receiver = match (&self.receiver, uses_memory) {
+            (Receiver::Ref, false) => Some(quote! { #caller.data() }),
+            (Receiver::Mut, false) => Some(quote! { #caller.data_mut() }),
+            (Receiver::Ref, true) => Some(quote! { &*#state }),
+            (Receiver::Mut, true) => Some(quote! { #state }),
+            (Receiver::Caller, _) => Some(quote! { #caller }),
+            (Receiver::None, _) => None,
+        };
+        args.splice(0..0, receiver);
+        let ident = &self.ident;
+        let call = quote! { #owner::#ident(#( #args ),*) };
+        let result = match self.returns_result {
+            true => quote! { #call.map_err(::wasmi::__private::IntoError::into_error) },
+            false => quote! { ::core::result::Result::<_, ::wasmi::Error>::Ok(#call) },
+        };
+        quote! {
+            move |#( #closure_params ),*| {
+                #access_memory
+                #( #conversions )*
+                #result
+            }
+        }
+    }
+}
+
+/// Returns a `let` statement binding `ident` to the `Ok` value of `expr` or returning its `Err`.
+pub fn try_expr(ident: &Ident, expr: TokenStream2) -> TokenStream2 {
+    quote! {
+        let #ident = match #expr {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(error) => return ::core::result::Result::Err(error),
+        };
+    }
+}
+
+/// Returns `true` if `ty` is a path type whose last segment is `name`.
+pub fn last_segment_is(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == name),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `ty` is a `Caller` type.
+pub fn is_caller(ty: &Type) -> bool {
+    last_segment_is(ty, "Caller")
+}
@@ -0,0 +1,91 @@
+//! Implementation of the `#[host_module]` macro.
+
+use crate::host_func::HostFunc;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{spanned::Spanned, ImplItem, ImplItemFn, ItemImpl, LitStr};
+
+/// Expands the `impl` block annotated with `#[host_module]`.
+pub fn expand(module: &LitStr, mut item: ItemImpl) -> syn::Result<TokenStream2> {
+    if let Some((_, path, _)) = &item.trait_ {
+        return Err(syn::Error::new(
+            path.span(),
+            "`host_module` cannot be used on trait implementations",
+        ));
+    }
+    let mut definitions = Vec::new();
+    for impl_item in &mut item.items {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+        let attrs = HostAttrs::extract(method)?;
+        if attrs.skip {
+            continue;
+        }
+        let name = attrs
+            .name
+            .unwrap_or_else(|| LitStr::new(&method.sig.ident.to_string(), method.sig.ident.span()));
+        let closure = HostFunc::parse(method)?.expand(&quote! { Self }, &quote! { Self });
+        definitions.push(quote! {
+            linker.func_wrap(#module, #name, #closure)?;
+        });
+    }
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+    let self_ty = &item.self_ty;
+    Ok(quote! {
+        #item
+
+        impl #impl_generics #self_ty #where_clause {
+            /// Defines all host functions of this host module in the `linker`.
+            ///
+            /// # Errors
+            ///
+            /// If any of the host functions is already defined in the `linker`.
+            #[allow(unused_mut, unused_variables, clippy::needless_question_mark)]
+            pub fn add_to_linker(
+                linker: &mut ::wasmi::Linker<Self>,
+            ) -> ::core::result::Result<(), ::wasmi::errors::LinkerError> {
+                #( #definitions )*
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+/// The `#[host(...)]` attributes of a host function.
+#[derive(Default)]
+struct HostAttrs {
+    /// The custom name of the host function if any.
+    name: Option<LitStr>,
+    /// Whether the method is not exposed as host function.
+    skip: bool,
+}
+
+impl HostAttrs {
+    /// Removes all `#[host(...)]` attributes from `method` and returns their contents.
+    fn extract(method: &mut ImplItemFn) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+        let mut result = Ok(());
+        method.attrs.retain(|attr| {
+            if !attr.path().is_ident("host") {
+                return true;
+            }
+            let parsed = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    attrs.name = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+                if meta.path.is_ident("skip") {
+                    attrs.skip = true;
+                    return Ok(());
+                }
+                Err(meta.error("unsupported `host` attribute argument"))
+            });
+            if let Err(error) = parsed {
+                result = Err(error);
+            }
+            false
+        });
+        result.map(|()| attrs)
+    }
+}
@@ -0,0 +1,236 @@
+//! Procedural macros for the Wasmi WebAssembly interpreter.
+//!
+//! This crate is not meant to be used directly.
+//! Instead enable the `macros` crate feature of `wasmi` which re-exports its macros.
+
+mod bindgen;
+mod host_func;
+mod host_module;
+mod wit;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use syn::{parse_macro_input, ItemImpl, LitStr};
+
+/// Generates [`Linker`] registration code for the annotated `impl` block.
+///
+/// Every method of the `impl` block is exposed as a host function of the
+/// Wasm module given by the `module` argument. The generated associated
+/// function `add_to_linker` defines all of them in a [`Linker<Self>`].
+///
+/// # Methods
+///
+/// Host functions may take `&self` or `&mut self` which refers to the host
+/// state of the [`Store`]. Alternatively they may take a [`Caller<'_, Self>`]
+/// as their first parameter, or neither of both.
+///
+/// The following method attributes are supported:
+///
+/// - `#[host(name = "...")]`: Use a custom host function name instead of the method name.
+/// - `#[host(skip)]`: Do not expose the method as host function.
+///
+/// # Parameters
+///
+/// - Types that are supported by [`Func::wrap`] are forwarded as is.
+/// - `bool` is passed as `i32` that must be either `0` or `1`.
+/// - `u8` and `u16` are passed as `i32` and checked to be in bounds of the type.
+/// - `i8` and `i16` are passed as `i32` and checked to be in bounds of the type.
+/// - `&[u8]` and `&str` are passed as a pair of `i32` pointer and length into the
+///   linear memory exported as `"memory"` by the calling instance.
+///   A `&str` must be valid UTF-8.
+///
+/// Guest slices cannot be combined with a [`Caller`] parameter.
+/// The host function traps if a parameter conversion fails.
+///
+/// # Results
+///
+/// Results may be any type supported by [`Func::wrap`]. Furthermore a host function
+/// may return a `Result<T, E>` where `E` is either [`Error`] or implements [`HostError`].
+///
+/// # Example
+///
+/// ```
+/// use wasmi::{host_module, Caller, Engine, Linker, Module, Store};
+///
+/// #[derive(Default)]
+/// struct Host {
+///     log: Vec<String>,
+/// }
+///
+/// #[host_module(module = "env")]
+/// impl Host {
+///     fn print(&mut self, message: &str) {
+///         self.log.push(message.into());
+///     }
+///
+///     #[host(name = "add_i32")]
+///     fn add(a: i32, b: i32) -> i32 {
+///         a.wrapping_add(b)
+///     }
+/// }
+///
+/// let wasm = wat::parse_str(r#"
+///     (module
+///         (import "env" "print" (func $print (param i32 i32)))
+///         (import "env" "add_i32" (func $add (param i32 i32) (result i32)))
+///         (memory (export "memory") 1)
+///         (data (i32.const 0) "Hello")
+///         (func (export "run") (result i32)
+///             (call $print (i32.const 0) (i32.const 5))
+///             (call $add (i32.const 1) (i32.const 2))
+///         )
+///     )
+/// "#).unwrap();
+/// let engine = Engine::default();
+/// let module = Module::new(&engine, &wasm[..]).unwrap();
+/// let mut store = Store::new(&engine, Host::default());
+/// let mut linker = <Linker<Host>>::new(&engine);
+/// Host::add_to_linker(&mut linker).unwrap();
+/// let instance = linker
+///     .instantiate(&mut store, &module)
+///     .unwrap()
+///     .start(&mut store)
+///     .unwrap();
+/// let run = instance.get_typed_func::<(), i32>(&store, "run").unwrap();
+/// assert_eq!(run.call(&mut store, ()).unwrap(), 3);
+/// assert_eq!(store.data().log, ["Hello"]);
+/// ```
+///
+/// [`Linker`]: https://docs.rs/wasmi/latest/wasmi/struct.Linker.html
+/// [`Linker<Self>`]: https://docs.rs/wasmi/latest/wasmi/struct.Linker.html
+/// [`Store`]: https://docs.rs/wasmi/latest/wasmi/struct.Store.html
+/// [`Caller`]: https://docs.rs/wasmi/latest/wasmi/struct.Caller.html
+/// [`Caller<'_, Self>`]: https://docs.rs/wasmi/latest/wasmi/struct.Caller.html
+/// [`Func::wrap`]: https://docs.rs/wasmi/latest/wasmi/struct.Func.html#method.wrap
+/// [`Error`]: https://docs.rs/wasmi/latest/wasmi/struct.Error.html
+/// [`HostError`]: https://docs.rs/wasmi/latest/wasmi/core/trait.HostError.html
+#[proc_macro_attribute]
+pub fn host_module(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut module: Option<LitStr> = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("module") {
+            module = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+        Err(meta.error("unsupported `host_module` argument"))
+    });
+    parse_macro_input!(attr with parser);
+    let item = parse_macro_input!(item as ItemImpl);
+    let Some(module) = module else {
+        return syn::Error::new(Span::call_site(), "missing `module = \"...\"` argument")
+            .into_compile_error()
+            .into();
+    };
+    host_module::expand(&module, item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates host traits and [`Linker`] wiring from a WIT world.
+///
+/// The WIT source is given either inline via `inline = "..."` or as file via
+/// `path = "..."` relative to the root of the crate. If the WIT source defines
+/// multiple worlds, the world must be selected via `world = "..."`.
+///
+/// Bindings use the core module lowering of the component model's canonical ABI:
+///
+/// - Functions imported by the world itself are imported from the `$root` module.
+/// - Functions of an imported interface are imported from the module named
+///   after the fully qualified interface, e.g. `example:host/logging`.
+/// - Functions of an exported interface are exported as `{interface}#{func}`.
+///
+/// # Generated Items
+///
+/// - A host trait per imported interface named after the interface in upper camel case.
+/// - A host trait named `{World}Imports` for the functions imported by the world itself.
+/// - A struct named after the world providing:
+///     - `add_to_linker` to define all imported host functions in a [`Linker<T>`]
+///       for a host state `T` implementing all host traits.
+///     - `new` to look up the exports of an [`Instance`].
+///     - A method per exported function.
+///
+/// # Types
+///
+/// - Parameters and results of type `u32`, `s32`, `u64`, `s64`, `f32` and `f64`
+///   map to their core Wasm value types.
+/// - Imported functions may also take parameters of type `bool`, `u8`, `u16`, `s8`
+///   and `s16`, passed as `i32`, as well as `string` and `list<u8>`, passed as
+///   pointer and length into the linear memory exported as `"memory"`.
+///
+/// Type definitions, resources and other types are not supported.
+///
+/// # Example
+///
+/// ```
+/// use wasmi::{bindgen, Engine, Linker, Module, Store};
+///
+/// bindgen!(inline = "
+///     package example:host;
+///
+///     interface logging {
+///         log: func(message: string);
+///     }
+///
+///     world guest {
+///         import logging;
+///         import random: func() -> u32;
+///         export run: func(n: u32) -> u32;
+///     }
+/// ");
+///
+/// #[derive(Default)]
+/// struct Host {
+///     log: Vec<String>,
+/// }
+///
+/// impl Logging for Host {
+///     fn log(&mut self, message: &str) {
+///         self.log.push(message.into());
+///     }
+/// }
+///
+/// impl GuestImports for Host {
+///     fn random(&mut self) -> u32 {
+///         4
+///     }
+/// }
+///
+/// let wasm = wat::parse_str(r#"
+///     (module
+///         (import "example:host/logging" "log" (func $log (param i32 i32)))
+///         (import "$root" "random" (func $random (result i32)))
+///         (memory (export "memory") 1)
+///         (data (i32.const 0) "running")
+///         (func (export "run") (param i32) (result i32)
+///             (call $log (i32.const 0) (i32.const 7))
+///             (i32.add (local.get 0) (call $random))
+///         )
+///     )
+/// "#).unwrap();
+/// let engine = Engine::default();
+/// let module = Module::new(&engine, &wasm[..]).unwrap();
+/// let mut store = Store::new(&engine, Host::default());
+/// let mut linker = <Linker<Host>>::new(&engine);
+/// Guest::add_to_linker(&mut linker).unwrap();
+/// let instance = linker
+///     .instantiate(&mut store, &module)
+///     .unwrap()
+///     .start(&mut store)
+///     .unwrap();
+/// let guest = Guest::new(&store, &instance).unwrap();
+/// assert_eq!(guest.run(&mut store, 1).unwrap(), 5);
+/// assert_eq!(store.data().log, ["running"]);
+/// ```
+///
+/// [`Linker`]: https://docs.rs/wasmi/latest/wasmi/struct.Linker.html
+/// [`Linker<T>`]: https://docs.rs/wasmi/latest/wasmi/struct.Linker.html
+/// [`Instance`]: https://docs.rs/wasmi/latest/wasmi/struct.Instance.html
+#[proc_macro]
+pub fn bindgen(input: TokenStream) -> TokenStream {
+    let mut args = bindgen::Args::default();
+    let parser = syn::meta::parser(|meta| args.parse(meta));
+    parse_macro_input!(input with parser);
+    bindgen::expand(args)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
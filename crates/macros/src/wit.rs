@@ -0,0 +1,553 @@
+//! A parser for the subset of WIT supported by the `bindgen!` macro.
+//!
+//! Supported are `package` declarations, `interface` definitions containing
+//! functions and `world` definitions importing and exporting functions and
+//! interfaces. Type definitions, `use` statements and resources are not supported.
+
+use std::{fmt, iter::Peekable, str::CharIndices};
+
+/// A parsed WIT document.
+#[derive(Debug, Default)]
+pub struct Document {
+    /// The name of the package of the document if any, e.g. `example:host@0.1.0`.
+    pub package: Option<String>,
+    /// The interfaces defined in the document.
+    pub interfaces: Vec<Interface>,
+    /// The worlds defined in the document.
+    pub worlds: Vec<World>,
+}
+
+/// A WIT interface definition.
+#[derive(Debug)]
+pub struct Interface {
+    /// The name of the interface.
+    pub name: String,
+    /// The functions of the interface.
+    pub funcs: Vec<Function>,
+}
+
+/// A WIT world definition.
+#[derive(Debug)]
+pub struct World {
+    /// The name of the world.
+    pub name: String,
+    /// The imports of the world.
+    pub imports: Vec<WorldItem>,
+    /// The exports of the world.
+    pub exports: Vec<WorldItem>,
+}
+
+/// An item imported or exported by a [`World`].
+#[derive(Debug)]
+pub enum WorldItem {
+    /// A freestanding function.
+    Func(Function),
+    /// An interface with its name and functions.
+    Interface(String, Vec<Function>),
+}
+
+/// A WIT function signature.
+#[derive(Debug, Clone)]
+pub struct Function {
+    /// The name of the function.
+    pub name: String,
+    /// The named parameters of the function.
+    pub params: Vec<(String, Type)>,
+    /// The result of the function if any.
+    pub result: Option<Type>,
+}
+
+/// A WIT type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    S8,
+    S16,
+    S32,
+    S64,
+    F32,
+    F64,
+    Char,
+    String,
+    List(Box<Type>),
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Bool => "bool",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::S8 => "s8",
+            Self::S16 => "s16",
+            Self::S32 => "s32",
+            Self::S64 => "s64",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+            Self::Char => "char",
+            Self::String => "string",
+            Self::List(elem) => return write!(f, "list<{elem}>"),
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// An error that occurred while parsing a WIT document.
+#[derive(Debug)]
+pub struct Error {
+    /// The line at which the error occurred.
+    line: usize,
+    /// The description of the error.
+    message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid WIT at line {}: {}", self.line, self.message)
+    }
+}
+
+/// A token of a WIT document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// An identifier or keyword.
+    Ident(String),
+    /// The `->` arrow.
+    Arrow,
+    /// Any other single character punctuation.
+    Punct(char),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ident(ident) => write!(f, "`{ident}`"),
+            Self::Arrow => write!(f, "`->`"),
+            Self::Punct(punct) => write!(f, "`{punct}`"),
+        }
+    }
+}
+
+/// Splits `source` into its tokens and their lines.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, Error> {
+    let mut tokens = Vec::new();
+    let mut chars: Peekable<CharIndices> = source.char_indices().peekable();
+    let mut line = 1;
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+            }
+            '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                chars.next();
+                let mut prev = ' ';
+                loop {
+                    let Some((_, c)) = chars.next() else {
+                        return Err(Error {
+                            line,
+                            message: String::from("unterminated block comment"),
+                        });
+                    };
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '-' if matches!(chars.peek(), Some((_, '>'))) => {
+                chars.next();
+                tokens.push((Token::Arrow, line));
+            }
+            c if is_ident_char(c) => {
+                let mut end = start + c.len_utf8();
+                while let Some((index, c)) = chars.next_if(|&(_, c)| is_ident_char(c)) {
+                    end = index + c.len_utf8();
+                }
+                let ident = source[start..end].trim_start_matches('%');
+                tokens.push((Token::Ident(ident.into()), line));
+            }
+            c => tokens.push((Token::Punct(c), line)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Returns `true` if `c` may be part of a WIT identifier.
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '%')
+}
+
+/// Parses the WIT `source` into a [`Document`].
+///
+/// # Errors
+///
+/// If `source` is not valid WIT or uses unsupported WIT features.
+pub fn parse(source: &str) -> Result<Document, Error> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        tokens: tokens.into_iter().peekable(),
+        line: 1,
+        references: Vec::new(),
+    };
+    let mut document = parser.parse_document()?;
+    for reference in parser.references {
+        let Some(interface) = document
+            .interfaces
+            .iter()
+            .find(|interface| interface.name == reference.name)
+        else {
+            return Err(Error {
+                line: reference.line,
+                message: format!("unknown interface `{}`", reference.name),
+            });
+        };
+        let funcs = interface.funcs.clone();
+        let world = &mut document.worlds[reference.world];
+        let items = match reference.import {
+            true => &mut world.imports,
+            false => &mut world.exports,
+        };
+        items[reference.item] = WorldItem::Interface(reference.name, funcs);
+    }
+    Ok(document)
+}
+
+/// A reference to an interface by a world that is resolved after parsing.
+struct InterfaceRef {
+    /// The name of the referenced interface.
+    name: String,
+    /// The line of the reference.
+    line: usize,
+    /// The index of the referencing world.
+    world: usize,
+    /// Whether the interface is imported or exported.
+    import: bool,
+    /// The index of the referencing item within the imports or exports of the world.
+    item: usize,
+}
+
+/// Parses a WIT document from its tokens.
+struct Parser {
+    /// The remaining tokens.
+    tokens: Peekable<std::vec::IntoIter<(Token, usize)>>,
+    /// The line of the last consumed token.
+    line: usize,
+    /// The interfaces referenced by worlds by name.
+    references: Vec<InterfaceRef>,
+}
+
+impl Parser {
+    /// Returns an [`Error`] with `message` at the current line.
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the next token.
+    fn next(&mut self) -> Result<Token, Error> {
+        let (token, line) = self
+            .tokens
+            .next()
+            .ok_or_else(|| self.error("unexpected end of input"))?;
+        self.line = line;
+        Ok(token)
+    }
+
+    /// Returns `true` if the next token is `expected`.
+    fn peek_is(&mut self, expected: &Token) -> bool {
+        matches!(self.tokens.peek(), Some((token, _)) if token == expected)
+    }
+
+    /// Consumes the next token and checks that it is `expected`.
+    fn expect(&mut self, expected: Token) -> Result<(), Error> {
+        let token = self.next()?;
+        if token != expected {
+            return Err(self.error(format!("expected {expected} but found {token}")));
+        }
+        Ok(())
+    }
+
+    /// Consumes the next token and checks that it is the punctuation `punct`.
+    fn expect_punct(&mut self, punct: char) -> Result<(), Error> {
+        self.expect(Token::Punct(punct))
+    }
+
+    /// Consumes the next token if it is the punctuation `punct`.
+    fn eat_punct(&mut self, punct: char) -> Result<bool, Error> {
+        if self.peek_is(&Token::Punct(punct)) {
+            self.next()?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Consumes the next token and returns it as identifier.
+    fn ident(&mut self) -> Result<String, Error> {
+        match self.next()? {
+            Token::Ident(ident) => Ok(ident),
+            token => Err(self.error(format!("expected identifier but found {token}"))),
+        }
+    }
+
+    /// Parses a whole [`Document`].
+    fn parse_document(&mut self) -> Result<Document, Error> {
+        let mut document = Document::default();
+        while self.tokens.peek().is_some() {
+            match self.ident()?.as_str() {
+                "package" => {
+                    if document.package.is_some() {
+                        return Err(self.error("duplicate `package` declaration"));
+                    }
+                    document.package = Some(self.parse_package()?);
+                }
+                "interface" => {
+                    let name = self.ident()?;
+                    let funcs = self.parse_interface_body()?;
+                    document.interfaces.push(Interface { name, funcs });
+                }
+                "world" => {
+                    let world = self.parse_world(document.worlds.len())?;
+                    document.worlds.push(world);
+                }
+                item => return Err(self.error(format!("unsupported WIT item `{item}`"))),
+            }
+        }
+        Ok(document)
+    }
+
+    /// Parses the name of a `package` declaration up to its terminating `;`.
+    fn parse_package(&mut self) -> Result<String, Error> {
+        let mut package = String::new();
+        loop {
+            match self.next()? {
+                Token::Punct(';') => break,
+                Token::Ident(ident) => package.push_str(&ident),
+                Token::Punct(punct @ (':' | '@' | '.' | '/')) => package.push(punct),
+                token => return Err(self.error(format!("unexpected {token} in package name"))),
+            }
+        }
+        Ok(package)
+    }
+
+    /// Parses the `{ ... }` body of an interface.
+    fn parse_interface_body(&mut self) -> Result<Vec<Function>, Error> {
+        self.expect_punct('{')?;
+        let mut funcs = Vec::new();
+        while !self.eat_punct('}')? {
+            let name = self.ident()?;
+            self.expect_punct(':')?;
+            funcs.push(self.parse_func(name)?);
+        }
+        Ok(funcs)
+    }
+
+    /// Parses a `func(...) -> ...;` signature with the given `name`.
+    fn parse_func(&mut self, name: String) -> Result<Function, Error> {
+        match self.ident()?.as_str() {
+            "func" => {}
+            item => return Err(self.error(format!("unsupported interface item `{item}`"))),
+        }
+        self.expect_punct('(')?;
+        let mut params = Vec::new();
+        while !self.eat_punct(')')? {
+            let param = self.ident()?;
+            self.expect_punct(':')?;
+            params.push((param, self.parse_type()?));
+            if !self.peek_is(&Token::Punct(')')) {
+                self.expect_punct(',')?;
+            }
+        }
+        let mut result = None;
+        if self.peek_is(&Token::Arrow) {
+            self.next()?;
+            result = Some(self.parse_type()?);
+        }
+        self.expect_punct(';')?;
+        Ok(Function {
+            name,
+            params,
+            result,
+        })
+    }
+
+    /// Parses a [`Type`].
+    fn parse_type(&mut self) -> Result<Type, Error> {
+        let ty = match self.ident()?.as_str() {
+            "bool" => Type::Bool,
+            "u8" => Type::U8,
+            "u16" => Type::U16,
+            "u32" => Type::U32,
+            "u64" => Type::U64,
+            "s8" => Type::S8,
+            "s16" => Type::S16,
+            "s32" => Type::S32,
+            "s64" => Type::S64,
+            "f32" | "float32" => Type::F32,
+            "f64" | "float64" => Type::F64,
+            "char" => Type::Char,
+            "string" => Type::String,
+            "list" => {
+                self.expect_punct('<')?;
+                let elem = self.parse_type()?;
+                self.expect_punct('>')?;
+                Type::List(Box::new(elem))
+            }
+            ty => return Err(self.error(format!("unsupported WIT type `{ty}`"))),
+        };
+        Ok(ty)
+    }
+
+    /// Parses the `index`-th `world` definition after its `world` keyword.
+    fn parse_world(&mut self, index: usize) -> Result<World, Error> {
+        let name = self.ident()?;
+        self.expect_punct('{')?;
+        let mut world = World {
+            name,
+            imports: Vec::new(),
+            exports: Vec::new(),
+        };
+        while !self.eat_punct('}')? {
+            let import = match self.ident()?.as_str() {
+                "import" => true,
+                "export" => false,
+                item => return Err(self.error(format!("unsupported world item `{item}`"))),
+            };
+            let items = match import {
+                true => &mut world.imports,
+                false => &mut world.exports,
+            };
+            let name = self.ident()?;
+            if self.eat_punct(';')? {
+                self.references.push(InterfaceRef {
+                    name: name.clone(),
+                    line: self.line,
+                    world: index,
+                    import,
+                    item: items.len(),
+                });
+                items.push(WorldItem::Interface(name, Vec::new()));
+                continue;
+            }
+            self.expect_punct(':')?;
+            if self.peek_is(&Token::Ident(String::from("interface"))) {
+                self.next()?;
+                let funcs = self.parse_interface_body()?;
+                items.push(WorldItem::Interface(name, funcs));
+                continue;
+            }
+            items.push(WorldItem::Func(self.parse_func(name)?));
+        }
+        Ok(world)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_world_works() {
+        let document = parse(
+            "
+            // The package of the document.
+            package example:host@0.1.0;
+
+            /* An interface that is referenced
+               before it is defined. */
+            world guest {
+                import logging;
+                import clock: interface {
+                    now: func() -> u64;
+                }
+                import %random: func(bound: u32) -> u32;
+                export run: func(args: list<u8>, verbose: bool);
+                export api;
+            }
+
+            interface logging {
+                log: func(level: u8, message: string);
+            }
+
+            interface api {
+                add: func(a: s32, b: s32) -> s32;
+            }
+            ",
+        )
+        .unwrap();
+        assert_eq!(document.package.as_deref(), Some("example:host@0.1.0"));
+        assert_eq!(document.interfaces.len(), 2);
+        let [world] = &document.worlds[..] else {
+            panic!("expected a single world: {:?}", document.worlds)
+        };
+        assert_eq!(world.name, "guest");
+        let names = |items: &[WorldItem]| {
+            items
+                .iter()
+                .map(|item| match item {
+                    WorldItem::Func(func) => format!("func {}", func.name),
+                    WorldItem::Interface(name, funcs) => {
+                        format!("interface {name}/{}", funcs.len())
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(&world.imports),
+            ["interface logging/1", "interface clock/1", "func random"]
+        );
+        assert_eq!(names(&world.exports), ["func run", "interface api/1"]);
+        let WorldItem::Interface(_, logging) = &world.imports[0] else {
+            panic!("expected an interface: {:?}", world.imports[0])
+        };
+        assert_eq!(
+            logging[0].params,
+            [
+                (String::from("level"), Type::U8),
+                (String::from("message"), Type::String)
+            ]
+        );
+        assert_eq!(logging[0].result, None);
+        let WorldItem::Func(run) = &world.exports[0] else {
+            panic!("expected a function: {:?}", world.exports[0])
+        };
+        assert_eq!(run.params[0].1, Type::List(Box::new(Type::U8)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |source: &str| parse(source).unwrap_err().to_string();
+        assert_eq!(
+            error("world w {\n import missing;\n}"),
+            "invalid WIT at line 2: unknown interface `missing`"
+        );
+        assert_eq!(
+            error("interface i {\n f: func(a: record);\n}"),
+            "invalid WIT at line 2: unsupported WIT type `record`"
+        );
+        assert_eq!(
+            error("interface i {\n f: func() -> u32\n}"),
+            "invalid WIT at line 3: expected `;` but found `}`"
+        );
+        assert_eq!(
+            error("world w {\n include other;\n}"),
+            "invalid WIT at line 2: unsupported world item `include`"
+        );
+        assert_eq!(
+            error("/* unterminated"),
+            "invalid WIT at line 1: unterminated block comment"
+        );
+    }
+}
@@ -0,0 +1,699 @@
+//! Pluggable backends for the WASI implementation.
+//!
+//! By default a [`WasiCtx`] built via `WasiCtxBuilder` accesses the file system,
+//! clocks, randomness and stdio of the host. The traits in this module allow
+//! embedders to substitute any of those, e.g. with an encrypted file system,
+//! simulated time, deterministic randomness or captured stdio.
+//!
+//! Install the backends into a [`WasiCtx`] via [`WasiCtxExt`].
+
+use std::{
+    any::Any,
+    io::{self, IoSlice, IoSliceMut, SeekFrom},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+use wasi_common::{
+    clocks::{WasiMonotonicClock, WasiSystemClock},
+    dir::{ReaddirCursor, ReaddirEntity},
+    file::{FdFlags, FileCaps, FileType, Filestat, OFlags},
+    Error,
+    ErrorExt,
+    SystemTimeSpec,
+    WasiCtx,
+    WasiDir,
+    WasiFile,
+};
+
+/// The metadata of an entry of a [`WasiFs`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WasiFsMetadata {
+    /// `true` if the entry is a directory.
+    pub is_dir: bool,
+    /// The size of the file in bytes or `0` for directories.
+    pub len: u64,
+}
+
+/// A file system backend for WASI guests.
+///
+/// # Note
+///
+/// - Paths are relative to the root of the file system and use `/` as separator.
+///   The root itself is denoted by the empty path.
+/// - Paths are normalized before they are handed to the backend so that guests
+///   can never escape the root, e.g. via `..` components.
+/// - Files are read as a whole when opened by the guest and written back
+///   as a whole whenever the guest modifies them.
+pub trait WasiFs: Send + Sync {
+    /// Returns the metadata of the entry at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::ErrorKind::NotFound`] error if there is no entry at `path`.
+    fn metadata(&self, path: &str) -> io::Result<WasiFsMetadata>;
+
+    /// Returns the contents of the file at `path`.
+    fn read_file(&self, path: &str) -> io::Result<Vec<u8>>;
+
+    /// Creates or replaces the file at `path` with `contents`.
+    fn write_file(&self, path: &str, contents: &[u8]) -> io::Result<()>;
+
+    /// Returns the names of all entries of the directory at `path`.
+    fn read_dir(&self, path: &str) -> io::Result<Vec<String>>;
+
+    /// Creates a new directory at `path`.
+    fn create_dir(&self, path: &str) -> io::Result<()>;
+
+    /// Removes the file at `path`.
+    fn remove_file(&self, path: &str) -> io::Result<()>;
+
+    /// Removes the empty directory at `path`.
+    fn remove_dir(&self, path: &str) -> io::Result<()>;
+
+    /// Moves the entry at `from` to `to`, replacing any file at `to`.
+    fn rename(&self, from: &str, to: &str) -> io::Result<()>;
+}
+
+/// A clock backend for WASI guests.
+pub trait WasiClock: Send + Sync {
+    /// Returns the resolution of the clock.
+    fn resolution(&self) -> Duration {
+        Duration::from_nanos(1)
+    }
+
+    /// Returns the current wall clock time.
+    fn system_time(&self) -> SystemTime;
+
+    /// Returns the monotonic time elapsed since an arbitrary but fixed point in time.
+    ///
+    /// # Note
+    ///
+    /// Consecutive calls must never return decreasing durations.
+    fn monotonic_time(&self) -> Duration;
+}
+
+/// A source of randomness for WASI guests.
+pub trait WasiRandom: Send + Sync {
+    /// Fills `buf` with random bytes.
+    fn fill_bytes(&mut self, buf: &mut [u8]);
+}
+
+/// A stdio backend for WASI guests.
+///
+/// By default stdin is empty and all output is discarded.
+pub trait WasiStdio: Send + Sync {
+    /// Reads from stdin into `buf` and returns the number of bytes read.
+    ///
+    /// Returns `0` once the end of stdin has been reached.
+    fn read_stdin(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Ok(0)
+    }
+
+    /// Writes `buf` to stdout and returns the number of bytes written.
+    fn write_stdout(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    /// Writes `buf` to stderr and returns the number of bytes written.
+    fn write_stderr(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+}
+
+/// Extension methods to install custom backends into a [`WasiCtx`].
+pub trait WasiCtxExt {
+    /// Preopens the root of `fs` at `guest_path` with full access.
+    ///
+    /// # Errors
+    ///
+    /// If the file descriptor table of the [`WasiCtx`] is full.
+    fn preopen_fs(
+        &mut self,
+        fs: impl WasiFs + 'static,
+        guest_path: impl AsRef<Path>,
+    ) -> Result<(), Error>;
+
+    /// Uses `clock` for the realtime and monotonic clocks.
+    fn set_clock(&mut self, clock: impl WasiClock + 'static);
+
+    /// Uses `random` as source of randomness.
+    fn set_random(&mut self, random: impl WasiRandom + 'static);
+
+    /// Uses `stdio` for stdin, stdout and stderr.
+    fn set_stdio(&mut self, stdio: impl WasiStdio + 'static);
+}
+
+impl WasiCtxExt for WasiCtx {
+    fn preopen_fs(
+        &mut self,
+        fs: impl WasiFs + 'static,
+        guest_path: impl AsRef<Path>,
+    ) -> Result<(), Error> {
+        let dir = FsDir {
+            fs: Arc::new(fs),
+            path: String::new(),
+        };
+        self.push_preopened_dir(Box::new(dir), guest_path)
+    }
+
+    fn set_clock(&mut self, clock: impl WasiClock + 'static) {
+        let clock = Arc::new(clock);
+        // Note: The monotonic clock of `wasi-common` is based on `Instant`
+        //       which cannot be constructed from a `Duration` directly.
+        let origin = cap_std::time::Instant::from_std(std::time::Instant::now());
+        self.clocks.system = Box::new(ClockAdapter {
+            clock: clock.clone(),
+            origin,
+        });
+        self.clocks.monotonic = Box::new(ClockAdapter {
+            clock: clock.clone(),
+            origin,
+        });
+        self.clocks.creation_time = origin + clock.monotonic_time();
+    }
+
+    fn set_random(&mut self, random: impl WasiRandom + 'static) {
+        self.random = Box::new(RandomAdapter(random));
+    }
+
+    fn set_stdio(&mut self, stdio: impl WasiStdio + 'static) {
+        let stdio: Arc<Mutex<dyn WasiStdio>> = Arc::new(Mutex::new(stdio));
+        for stream in [Stream::Stdin, Stream::Stdout, Stream::Stderr] {
+            let file = Box::new(StdioFile {
+                stdio: stdio.clone(),
+                stream,
+            });
+            self.insert_file(stream as u32, file, stream.caps());
+        }
+    }
+}
+
+/// Adapts a [`WasiClock`] to the clock traits of `wasi-common`.
+struct ClockAdapter<C> {
+    /// The adapted clock.
+    clock: Arc<C>,
+    /// The [`Instant`] corresponding to a [`WasiClock::monotonic_time`] of zero.
+    ///
+    /// [`Instant`]: cap_std::time::Instant
+    origin: cap_std::time::Instant,
+}
+
+impl<C: WasiClock> WasiSystemClock for ClockAdapter<C> {
+    fn resolution(&self) -> Duration {
+        self.clock.resolution()
+    }
+
+    fn now(&self, _precision: Duration) -> cap_std::time::SystemTime {
+        cap_std::time::SystemTime::from_std(self.clock.system_time())
+    }
+}
+
+impl<C: WasiClock> WasiMonotonicClock for ClockAdapter<C> {
+    fn resolution(&self) -> Duration {
+        self.clock.resolution()
+    }
+
+    fn now(&self, _precision: Duration) -> cap_std::time::Instant {
+        self.origin + self.clock.monotonic_time()
+    }
+}
+
+/// Adapts a [`WasiRandom`] to the [`RngCore`](cap_rand::RngCore) trait used by `wasi-common`.
+struct RandomAdapter<R>(R);
+
+impl<R: WasiRandom> cap_rand::RngCore for RandomAdapter<R> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0x00; 4];
+        self.0.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0x00; 8];
+        self.0.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), cap_rand::Error> {
+        self.0.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// The stdio streams of a WASI guest.
+#[derive(Debug, Copy, Clone)]
+enum Stream {
+    Stdin = 0,
+    Stdout = 1,
+    Stderr = 2,
+}
+
+impl Stream {
+    /// Returns the capabilities of the [`Stream`].
+    fn caps(self) -> FileCaps {
+        let caps = FileCaps::FDSTAT_SET_FLAGS | FileCaps::FILESTAT_GET | FileCaps::POLL_READWRITE;
+        match self {
+            Self::Stdin => caps | FileCaps::READ,
+            Self::Stdout | Self::Stderr => caps | FileCaps::WRITE,
+        }
+    }
+}
+
+/// A stdio stream backed by a [`WasiStdio`].
+struct StdioFile {
+    /// The [`WasiStdio`] shared by all stdio streams.
+    stdio: Arc<Mutex<dyn WasiStdio>>,
+    /// The stream accessed by this file.
+    stream: Stream,
+}
+
+#[wiggle::async_trait]
+impl WasiFile for StdioFile {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn get_filetype(&mut self) -> Result<FileType, Error> {
+        Ok(FileType::Pipe)
+    }
+
+    async fn read_vectored<'a>(&mut self, bufs: &mut [IoSliceMut<'a>]) -> Result<u64, Error> {
+        if !matches!(self.stream, Stream::Stdin) {
+            return Err(Error::badf());
+        }
+        let mut stdio = self.stdio.lock().unwrap_or_else(|error| error.into_inner());
+        let mut total = 0;
+        for buf in bufs.iter_mut().filter(|buf| !buf.is_empty()) {
+            let len = stdio.read_stdin(buf)?;
+            total += len;
+            if len < buf.len() {
+                break;
+            }
+        }
+        Ok(total as u64)
+    }
+
+    async fn write_vectored<'a>(&mut self, bufs: &[IoSlice<'a>]) -> Result<u64, Error> {
+        let mut stdio = self.stdio.lock().unwrap_or_else(|error| error.into_inner());
+        let mut total = 0;
+        for buf in bufs.iter().filter(|buf| !buf.is_empty()) {
+            let len = match self.stream {
+                Stream::Stdin => return Err(Error::badf()),
+                Stream::Stdout => stdio.write_stdout(buf)?,
+                Stream::Stderr => stdio.write_stderr(buf)?,
+            };
+            total += len;
+            if len < buf.len() {
+                break;
+            }
+        }
+        Ok(total as u64)
+    }
+}
+
+/// Converts an [`io::Error`] returned by a [`WasiFs`] into a WASI [`Error`].
+///
+/// # Note
+///
+/// `wasi-common` turns [`io::Error`]s without an OS error code and of an
+/// unknown kind into traps instead of error codes returned to the guest.
+fn fs_error(error: io::Error) -> Error {
+    match error.kind() {
+        io::ErrorKind::NotFound => Error::not_found(),
+        io::ErrorKind::PermissionDenied => Error::perm(),
+        io::ErrorKind::AlreadyExists => Error::exist(),
+        io::ErrorKind::InvalidInput => Error::invalid_argument(),
+        _ => Error::io(),
+    }
+    .context(error)
+}
+
+/// Returns the [`Filestat`] for the `metadata` of a [`WasiFs`] entry.
+fn filestat(metadata: WasiFsMetadata) -> Filestat {
+    let filetype = match metadata.is_dir {
+        true => FileType::Directory,
+        false => FileType::RegularFile,
+    };
+    Filestat {
+        device_id: 0,
+        inode: 0,
+        filetype,
+        nlink: 1,
+        size: metadata.len,
+        atim: None,
+        mtim: None,
+        ctim: None,
+    }
+}
+
+/// A directory of a [`WasiFs`].
+struct FsDir {
+    /// The file system of the directory.
+    fs: Arc<dyn WasiFs>,
+    /// The normalized path of the directory.
+    path: String,
+}
+
+impl FsDir {
+    /// Resolves the guest provided `path` relative to this directory.
+    ///
+    /// # Errors
+    ///
+    /// If `path` is absolute or escapes the root of the file system.
+    fn resolve(&self, path: &str) -> Result<String, Error> {
+        if path.starts_with('/') {
+            return Err(Error::perm().context("absolute paths are not supported"));
+        }
+        let mut components: Vec<&str> = self.path.split('/').filter(|c| !c.is_empty()).collect();
+        for component in path.split('/') {
+            match component {
+                "" | "." => {}
+                ".." => {
+                    if components.pop().is_none() {
+                        return Err(Error::perm().context("path escapes the file system root"));
+                    }
+                }
+                component => components.push(component),
+            }
+        }
+        Ok(components.join("/"))
+    }
+
+    /// Returns the metadata of the entry at the resolved `path` or `None` if it does not exist.
+    fn metadata(&self, path: &str) -> Result<Option<WasiFsMetadata>, Error> {
+        match self.fs.metadata(path) {
+            Ok(metadata) => Ok(Some(metadata)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(fs_error(error)),
+        }
+    }
+}
+
+#[wiggle::async_trait]
+impl WasiDir for FsDir {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn open_file(
+        &self,
+        _symlink_follow: bool,
+        path: &str,
+        oflags: OFlags,
+        read: bool,
+        write: bool,
+        fdflags: FdFlags,
+    ) -> Result<Box<dyn WasiFile>, Error> {
+        let path = self.resolve(path)?;
+        let contents = match self.metadata(&path)? {
+            Some(_) if oflags.contains(OFlags::CREATE | OFlags::EXCLUSIVE) => {
+                return Err(Error::exist())
+            }
+            Some(metadata) if metadata.is_dir => {
+                return Err(Error::not_supported().context("cannot open a directory as file"))
+            }
+            Some(_) if oflags.contains(OFlags::TRUNCATE) => {
+                self.fs.write_file(&path, &[]).map_err(fs_error)?;
+                Vec::new()
+            }
+            Some(_) => self.fs.read_file(&path).map_err(fs_error)?,
+            None if oflags.contains(OFlags::CREATE) => {
+                self.fs.write_file(&path, &[]).map_err(fs_error)?;
+                Vec::new()
+            }
+            None => return Err(Error::not_found()),
+        };
+        Ok(Box::new(FsFile {
+            fs: self.fs.clone(),
+            path,
+            contents,
+            position: 0,
+            read,
+            write,
+            append: fdflags.contains(FdFlags::APPEND),
+        }))
+    }
+
+    async fn open_dir(&self, _symlink_follow: bool, path: &str) -> Result<Box<dyn WasiDir>, Error> {
+        let path = self.resolve(path)?;
+        match self.metadata(&path)? {
+            Some(metadata) if metadata.is_dir => Ok(Box::new(FsDir {
+                fs: self.fs.clone(),
+                path,
+            })),
+            Some(_) => Err(Error::not_dir()),
+            None => Err(Error::not_found()),
+        }
+    }
+
+    async fn create_dir(&self, path: &str) -> Result<(), Error> {
+        let path = self.resolve(path)?;
+        self.fs.create_dir(&path).map_err(fs_error)
+    }
+
+    async fn readdir(
+        &self,
+        cursor: ReaddirCursor,
+    ) -> Result<Box<dyn Iterator<Item = Result<ReaddirEntity, Error>> + Send>, Error> {
+        let mut entries = vec![
+            (String::from("."), FileType::Directory),
+            (String::from(".."), FileType::Directory),
+        ];
+        for name in self.fs.read_dir(&self.path).map_err(fs_error)? {
+            let path = self.resolve(&name)?;
+            let metadata = self.fs.metadata(&path).map_err(fs_error)?;
+            entries.push((name, filestat(metadata).filetype));
+        }
+        let entries = entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, filetype))| {
+                Ok(ReaddirEntity {
+                    next: ReaddirCursor::from(index as u64 + 1),
+                    inode: 0,
+                    name,
+                    filetype,
+                })
+            })
+            .skip(u64::from(cursor) as usize);
+        Ok(Box::new(entries))
+    }
+
+    async fn symlink(&self, _old_path: &str, _new_path: &str) -> Result<(), Error> {
+        Err(Error::not_supported())
+    }
+
+    async fn remove_dir(&self, path: &str) -> Result<(), Error> {
+        let path = self.resolve(path)?;
+        self.fs.remove_dir(&path).map_err(fs_error)
+    }
+
+    async fn unlink_file(&self, path: &str) -> Result<(), Error> {
+        let path = self.resolve(path)?;
+        self.fs.remove_file(&path).map_err(fs_error)
+    }
+
+    async fn read_link(&self, _path: &str) -> Result<PathBuf, Error> {
+        Err(Error::not_supported())
+    }
+
+    async fn get_filestat(&self) -> Result<Filestat, Error> {
+        self.get_path_filestat(".", true).await
+    }
+
+    async fn get_path_filestat(
+        &self,
+        path: &str,
+        _follow_symlinks: bool,
+    ) -> Result<Filestat, Error> {
+        let path = self.resolve(path)?;
+        let metadata = self.fs.metadata(&path).map_err(fs_error)?;
+        Ok(filestat(metadata))
+    }
+
+    async fn rename(
+        &self,
+        path: &str,
+        dest_dir: &dyn WasiDir,
+        dest_path: &str,
+    ) -> Result<(), Error> {
+        let dest_dir = dest_dir
+            .as_any()
+            .downcast_ref::<FsDir>()
+            .filter(|dest_dir| Arc::ptr_eq(&self.fs, &dest_dir.fs))
+            .ok_or_else(|| Error::not_supported().context("cannot rename across file systems"))?;
+        let from = self.resolve(path)?;
+        let to = dest_dir.resolve(dest_path)?;
+        self.fs.rename(&from, &to).map_err(fs_error)
+    }
+
+    async fn hard_link(
+        &self,
+        _path: &str,
+        _target_dir: &dyn WasiDir,
+        _target_path: &str,
+    ) -> Result<(), Error> {
+        Err(Error::not_supported())
+    }
+
+    async fn set_times(
+        &self,
+        _path: &str,
+        _atime: Option<SystemTimeSpec>,
+        _mtime: Option<SystemTimeSpec>,
+        _follow_symlinks: bool,
+    ) -> Result<(), Error> {
+        Err(Error::not_supported())
+    }
+}
+
+/// An open file of a [`WasiFs`].
+///
+/// The contents of the file are kept in memory and written back to the
+/// [`WasiFs`] whenever they are modified.
+struct FsFile {
+    /// The file system of the file.
+    fs: Arc<dyn WasiFs>,
+    /// The normalized path of the file.
+    path: String,
+    /// The current contents of the file.
+    contents: Vec<u8>,
+    /// The current position of the file cursor.
+    position: u64,
+    /// `true` if the file was opened for reading.
+    read: bool,
+    /// `true` if the file was opened for writing.
+    write: bool,
+    /// `true` if all writes append to the end of the file.
+    append: bool,
+}
+
+impl FsFile {
+    /// Reads from the file at `offset` into `bufs` and returns the number of bytes read.
+    fn read_at(&self, bufs: &mut [IoSliceMut], offset: u64) -> Result<u64, Error> {
+        if !self.read {
+            return Err(Error::badf());
+        }
+        let offset = usize::try_from(offset)?.min(self.contents.len());
+        let mut remaining = &self.contents[offset..];
+        let mut total = 0;
+        for buf in bufs.iter_mut() {
+            let len = buf.len().min(remaining.len());
+            buf[..len].copy_from_slice(&remaining[..len]);
+            remaining = &remaining[len..];
+            total += len;
+        }
+        Ok(total as u64)
+    }
+
+    /// Writes `bufs` to the file at `offset` and returns the number of bytes written.
+    ///
+    /// The modified contents are written back to the [`WasiFs`].
+    fn write_at(&mut self, bufs: &[IoSlice], offset: u64) -> Result<u64, Error> {
+        if !self.write {
+            return Err(Error::badf());
+        }
+        let mut offset = usize::try_from(offset)?;
+        let start = offset;
+        for buf in bufs {
+            let end = offset + buf.len();
+            if self.contents.len() < end {
+                self.contents.resize(end, 0x00);
+            }
+            self.contents[offset..end].copy_from_slice(buf);
+            offset = end;
+        }
+        self.flush()?;
+        Ok((offset - start) as u64)
+    }
+
+    /// Writes the contents of the file back to the [`WasiFs`].
+    fn flush(&self) -> Result<(), Error> {
+        self.fs
+            .write_file(&self.path, &self.contents)
+            .map_err(fs_error)
+    }
+}
+
+#[wiggle::async_trait]
+impl WasiFile for FsFile {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn get_filetype(&mut self) -> Result<FileType, Error> {
+        Ok(FileType::RegularFile)
+    }
+
+    async fn get_fdflags(&mut self) -> Result<FdFlags, Error> {
+        match self.append {
+            true => Ok(FdFlags::APPEND),
+            false => Ok(FdFlags::empty()),
+        }
+    }
+
+    async fn get_filestat(&mut self) -> Result<Filestat, Error> {
+        Ok(filestat(WasiFsMetadata {
+            is_dir: false,
+            len: self.contents.len() as u64,
+        }))
+    }
+
+    async fn set_filestat_size(&mut self, size: u64) -> Result<(), Error> {
+        if !self.write {
+            return Err(Error::badf());
+        }
+        self.contents.resize(usize::try_from(size)?, 0x00);
+        self.flush()
+    }
+
+    async fn read_vectored<'a>(&mut self, bufs: &mut [IoSliceMut<'a>]) -> Result<u64, Error> {
+        let len = self.read_at(bufs, self.position)?;
+        self.position += len;
+        Ok(len)
+    }
+
+    async fn read_vectored_at<'a>(
+        &mut self,
+        bufs: &mut [IoSliceMut<'a>],
+        offset: u64,
+    ) -> Result<u64, Error> {
+        self.read_at(bufs, offset)
+    }
+
+    async fn write_vectored<'a>(&mut self, bufs: &[IoSlice<'a>]) -> Result<u64, Error> {
+        if self.append {
+            self.position = self.contents.len() as u64;
+        }
+        let len = self.write_at(bufs, self.position)?;
+        self.position += len;
+        Ok(len)
+    }
+
+    async fn write_vectored_at<'a>(
+        &mut self,
+        bufs: &[IoSlice<'a>],
+        offset: u64,
+    ) -> Result<u64, Error> {
+        self.write_at(bufs, offset)
+    }
+
+    async fn seek(&mut self, pos: SeekFrom) -> Result<u64, Error> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => (self.contents.len() as u64).checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        self.position = position.ok_or_else(Error::invalid_argument)?;
+        Ok(self.position)
+    }
+
+    async fn num_ready_bytes(&self) -> Result<u64, Error> {
+        Ok((self.contents.len() as u64).saturating_sub(self.position))
+    }
+}
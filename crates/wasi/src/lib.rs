@@ -1,13 +1,18 @@
 //! This crate provides support for WASI `preview1` for the Wasmi interpreter.
 //!
 //! Use [`add_to_linker`] to add all supported WASI definitions to the Wasmi linker.
+//! Use [`WasiCtxExt`] to substitute the file system, clocks, randomness or stdio of the host.
 
+mod backend;
 mod guest_memory;
 
 #[cfg(feature = "sync")]
 pub mod sync;
 
-pub use self::guest_memory::WasmiGuestMemory;
+pub use self::{
+    backend::{WasiClock, WasiCtxExt, WasiFs, WasiFsMetadata, WasiRandom, WasiStdio},
+    guest_memory::WasmiGuestMemory,
+};
 pub use wasi_common::{Error, WasiCtx, WasiDir, WasiFile};
 
 /// Sync mode is the "default" of this crate, so we also export it at the top level.
@@ -3,7 +3,9 @@
 //! in sync.
 
 pub mod snapshots;
+mod sockets;
 
+pub use self::sockets::{UdpSocket, WasiSockets};
 pub use wasi_cap_std_sync::*;
 
 #[doc(inline)]
@@ -0,0 +1,204 @@
+//! Capability based configuration of the sockets available to WASI guests.
+//!
+//! WASI `preview1` does not allow guests to open sockets by themselves.
+//! Instead the embedder grants access to individual sockets that are
+//! bound or connected ahead of time and inserted at fixed file descriptors.
+//! Guests then use `sock_accept`, `sock_recv`, `sock_send` and `sock_shutdown`
+//! on those file descriptors.
+
+use std::{
+    any::Any,
+    io::{IoSlice, IoSliceMut},
+    net::{self, SocketAddr, ToSocketAddrs},
+};
+use wasi_cap_std_sync::net::Socket;
+use wasi_common::{
+    file::{FdFlags, FileCaps, FileType, RiFlags, RoFlags, SdFlags, SiFlags},
+    Error,
+    ErrorExt,
+    WasiCtx,
+    WasiFile,
+};
+
+/// The capabilities of sockets over which guests can send and receive data.
+const STREAM_CAPS: FileCaps = FileCaps::READ
+    .union(FileCaps::WRITE)
+    .union(FileCaps::FDSTAT_SET_FLAGS)
+    .union(FileCaps::FILESTAT_GET)
+    .union(FileCaps::POLL_READWRITE);
+
+/// The capabilities of listening sockets.
+///
+/// Guests can only accept incoming connections on them.
+const LISTENER_CAPS: FileCaps = FileCaps::READ
+    .union(FileCaps::FDSTAT_SET_FLAGS)
+    .union(FileCaps::FILESTAT_GET)
+    .union(FileCaps::POLL_READWRITE);
+
+/// The set of TCP and UDP sockets granted to a WASI guest.
+///
+/// Every socket is opened by the host when it is added and then inserted
+/// at its file descriptor via [`WasiSockets::add_to_ctx`].
+/// Guests are not able to open any other sockets.
+///
+/// # Example
+///
+/// ```no_run
+/// # fn main() -> Result<(), wasmi_wasi::Error> {
+/// use wasmi_wasi::{WasiCtxBuilder, WasiSockets};
+///
+/// let mut sockets = WasiSockets::new();
+/// let addr = sockets.tcp_listen(3, "127.0.0.1:0")?;
+/// println!("guest is listening on {addr}");
+/// let mut wasi = WasiCtxBuilder::new().inherit_stdio().build();
+/// sockets.add_to_ctx(&mut wasi);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct WasiSockets {
+    /// The granted sockets and their file descriptors.
+    sockets: Vec<(u32, Box<dyn WasiFile>, FileCaps)>,
+}
+
+impl WasiSockets {
+    /// Creates a new [`WasiSockets`] without any granted sockets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds a TCP listener to `addr` and grants it to the guest at file descriptor `fd`.
+    ///
+    /// Returns the local address of the listener which is useful when binding to port 0.
+    ///
+    /// # Errors
+    ///
+    /// If binding the listener fails.
+    pub fn tcp_listen(&mut self, fd: u32, addr: impl ToSocketAddrs) -> Result<SocketAddr, Error> {
+        let listener = net::TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let listener = Socket::from(cap_std::net::TcpListener::from_std(listener));
+        self.sockets.push((fd, listener.into(), LISTENER_CAPS));
+        Ok(local_addr)
+    }
+
+    /// Connects a TCP stream to `addr` and grants it to the guest at file descriptor `fd`.
+    ///
+    /// Returns the address of the connected peer.
+    ///
+    /// # Errors
+    ///
+    /// If connecting the stream fails.
+    pub fn tcp_connect(&mut self, fd: u32, addr: impl ToSocketAddrs) -> Result<SocketAddr, Error> {
+        let stream = net::TcpStream::connect(addr)?;
+        let peer_addr = stream.peer_addr()?;
+        let stream = Socket::from(cap_std::net::TcpStream::from_std(stream));
+        self.sockets.push((fd, stream.into(), STREAM_CAPS));
+        Ok(peer_addr)
+    }
+
+    /// Binds a UDP socket to `local`, connects it to `remote` and grants it to the guest at file descriptor `fd`.
+    ///
+    /// The guest can only exchange datagrams with `remote`.
+    /// Returns the local address of the socket which is useful when binding to port 0.
+    ///
+    /// # Errors
+    ///
+    /// If binding or connecting the socket fails.
+    pub fn udp_connect(
+        &mut self,
+        fd: u32,
+        local: impl ToSocketAddrs,
+        remote: impl ToSocketAddrs,
+    ) -> Result<SocketAddr, Error> {
+        let socket = net::UdpSocket::bind(local)?;
+        socket.connect(remote)?;
+        let local_addr = socket.local_addr()?;
+        self.sockets
+            .push((fd, Box::new(UdpSocket::from_std(socket)), STREAM_CAPS));
+        Ok(local_addr)
+    }
+
+    /// Inserts all granted sockets into the [`WasiCtx`] at their file descriptors.
+    ///
+    /// # Note
+    ///
+    /// Existing files at the same file descriptors are replaced.
+    pub fn add_to_ctx(self, ctx: &mut WasiCtx) {
+        for (fd, file, caps) in self.sockets {
+            ctx.insert_file(fd, file, caps);
+        }
+    }
+}
+
+/// A connected UDP socket exposed to WASI guests.
+///
+/// Every `sock_send` sends a single datagram and every `sock_recv` receives a single datagram.
+pub struct UdpSocket(net::UdpSocket);
+
+impl UdpSocket {
+    /// Creates a new [`UdpSocket`] from a connected [`std::net::UdpSocket`].
+    pub fn from_std(socket: net::UdpSocket) -> Self {
+        Self(socket)
+    }
+}
+
+#[wiggle::async_trait]
+impl WasiFile for UdpSocket {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn get_filetype(&mut self) -> Result<FileType, Error> {
+        Ok(FileType::SocketDgram)
+    }
+
+    async fn get_fdflags(&mut self) -> Result<FdFlags, Error> {
+        Ok(FdFlags::empty())
+    }
+
+    async fn sock_recv<'a>(
+        &mut self,
+        ri_data: &mut [IoSliceMut<'a>],
+        ri_flags: RiFlags,
+    ) -> Result<(u64, RoFlags), Error> {
+        if ri_flags & !RiFlags::RECV_PEEK != RiFlags::empty() {
+            return Err(Error::not_supported());
+        }
+        // Note: One additional byte is used to detect truncated datagrams.
+        let capacity: usize = ri_data.iter().map(|buf| buf.len()).sum();
+        let mut buffer = vec![0x00_u8; capacity + 1];
+        let len = match ri_flags.contains(RiFlags::RECV_PEEK) {
+            true => self.0.peek(&mut buffer)?,
+            false => self.0.recv(&mut buffer)?,
+        };
+        let (len, ro_flags) = match len > capacity {
+            true => (capacity, RoFlags::RECV_DATA_TRUNCATED),
+            false => (len, RoFlags::empty()),
+        };
+        let mut remaining = &buffer[..len];
+        for buf in ri_data.iter_mut() {
+            let n = buf.len().min(remaining.len());
+            buf[..n].copy_from_slice(&remaining[..n]);
+            remaining = &remaining[n..];
+        }
+        Ok((len as u64, ro_flags))
+    }
+
+    async fn sock_send<'a>(
+        &mut self,
+        si_data: &[IoSlice<'a>],
+        si_flags: SiFlags,
+    ) -> Result<u64, Error> {
+        if si_flags != SiFlags::empty() {
+            return Err(Error::not_supported());
+        }
+        let datagram: Vec<u8> = si_data.iter().flat_map(|buf| buf.iter().copied()).collect();
+        let len = self.0.send(&datagram)?;
+        Ok(len as u64)
+    }
+
+    async fn sock_shutdown(&mut self, _how: SdFlags) -> Result<(), Error> {
+        Err(Error::not_supported())
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+        Mutex,
+    },
+    time::{Duration, SystemTime},
+};
+use wasi_cap_std_sync::WasiCtxBuilder;
+use wasmi::{Engine, Linker, Memory, Module, Store, Value};
+use wasmi_wasi::{
+    add_to_linker,
+    WasiClock,
+    WasiCtx,
+    WasiCtxExt,
+    WasiFs,
+    WasiFsMetadata,
+    WasiRandom,
+    WasiStdio,
+};
+
+/// WASI error code for existing files.
+const ERRNO_EXIST: i32 = 20;
+
+/// WASI error code for missing files.
+const ERRNO_NOENT: i32 = 44;
+
+/// WASI error code for denied permissions.
+const ERRNO_PERM: i32 = 63;
+
+/// The WASI `oflags` to create a file.
+const OFLAGS_CREATE: i32 = 1;
+
+/// The WASI `oflags` to exclusively create a file.
+const OFLAGS_EXCL: i32 = 1 | 4;
+
+/// The WASI `oflags` to truncate a file.
+const OFLAGS_TRUNC: i32 = 8;
+
+/// The WASI `fdflags` for appending writes.
+const FDFLAGS_APPEND: i32 = 1;
+
+/// The WASI rights `fd_read`, `fd_seek` and `fd_write`.
+const RIGHTS_READ_WRITE: i64 = (1 << 1) | (1 << 2) | (1 << 6);
+
+/// The file descriptor of the preopened [`WasiFs`].
+const FS_FD: i32 = 3;
+
+/// An entry of the [`MemoryFs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Entry {
+    Dir,
+    File(Vec<u8>),
+}
+
+/// An in-memory [`WasiFs`] that can be inspected by the host.
+#[derive(Clone, Default)]
+struct MemoryFs {
+    entries: Arc<Mutex<BTreeMap<String, Entry>>>,
+}
+
+impl MemoryFs {
+    fn get(&self, path: &str) -> Option<Entry> {
+        self.entries.lock().unwrap().get(path).cloned()
+    }
+
+    fn insert(&self, path: &str, entry: Entry) {
+        self.entries.lock().unwrap().insert(path.into(), entry);
+    }
+}
+
+fn not_found() -> io::Error {
+    io::Error::from(io::ErrorKind::NotFound)
+}
+
+impl WasiFs for MemoryFs {
+    fn metadata(&self, path: &str) -> io::Result<WasiFsMetadata> {
+        match (path, self.get(path)) {
+            ("", _) | (_, Some(Entry::Dir)) => Ok(WasiFsMetadata {
+                is_dir: true,
+                len: 0,
+            }),
+            (_, Some(Entry::File(contents))) => Ok(WasiFsMetadata {
+                is_dir: false,
+                len: contents.len() as u64,
+            }),
+            (_, None) => Err(not_found()),
+        }
+    }
+
+    fn read_file(&self, path: &str) -> io::Result<Vec<u8>> {
+        match self.get(path) {
+            Some(Entry::File(contents)) => Ok(contents),
+            _ => Err(not_found()),
+        }
+    }
+
+    fn write_file(&self, path: &str, contents: &[u8]) -> io::Result<()> {
+        self.insert(path, Entry::File(contents.to_vec()));
+        Ok(())
+    }
+
+    fn read_dir(&self, path: &str) -> io::Result<Vec<String>> {
+        let prefix = match path {
+            "" => String::new(),
+            path => format!("{path}/"),
+        };
+        let entries = self.entries.lock().unwrap();
+        Ok(entries
+            .keys()
+            .filter_map(|key| key.strip_prefix(&prefix))
+            .filter(|name| !name.contains('/'))
+            .map(String::from)
+            .collect())
+    }
+
+    fn create_dir(&self, path: &str) -> io::Result<()> {
+        if self.get(path).is_some() {
+            return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+        }
+        self.insert(path, Entry::Dir);
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &str) -> io::Result<()> {
+        match self.entries.lock().unwrap().remove(path) {
+            Some(_) => Ok(()),
+            None => Err(not_found()),
+        }
+    }
+
+    fn remove_dir(&self, path: &str) -> io::Result<()> {
+        self.remove_file(path)
+    }
+
+    fn rename(&self, from: &str, to: &str) -> io::Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.remove(from).ok_or_else(not_found)?;
+        entries.insert(to.into(), entry);
+        Ok(())
+    }
+}
+
+/// A simulated clock advancing by one second whenever it is queried.
+#[derive(Default)]
+struct SimulatedClock {
+    ticks: AtomicU64,
+}
+
+impl WasiClock for SimulatedClock {
+    fn system_time(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_000)
+    }
+
+    fn monotonic_time(&self) -> Duration {
+        Duration::from_secs(self.ticks.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A deterministic source of counting bytes.
+struct CountingRandom(u8);
+
+impl WasiRandom for CountingRandom {
+    fn fill_bytes(&mut self, buf: &mut [u8]) {
+        for byte in buf {
+            *byte = self.0;
+            self.0 = self.0.wrapping_add(1);
+        }
+    }
+}
+
+/// Stdio reading from a fixed stdin and capturing stdout and stderr.
+#[derive(Clone, Default)]
+struct CapturedStdio {
+    stdin: Arc<Mutex<Vec<u8>>>,
+    stdout: Arc<Mutex<Vec<u8>>>,
+    stderr: Arc<Mutex<Vec<u8>>>,
+}
+
+impl WasiStdio for CapturedStdio {
+    fn read_stdin(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut stdin = self.stdin.lock().unwrap();
+        let len = buf.len().min(stdin.len());
+        buf[..len].copy_from_slice(&stdin[..len]);
+        stdin.drain(..len);
+        Ok(len)
+    }
+
+    fn write_stdout(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stdout.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn write_stderr(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stderr.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+}
+
+/// A guest calling raw WASI functions with memory at offset `1024` used for data.
+struct Guest {
+    store: Store<WasiCtx>,
+    instance: wasmi::Instance,
+    memory: Memory,
+}
+
+impl Guest {
+    /// Instantiates the `syscalls.wat` guest after applying `setup` to its [`WasiCtx`].
+    fn new(setup: impl FnOnce(&mut WasiCtx)) -> Self {
+        let wasm = wat::parse_bytes(include_bytes!("wat/syscalls.wat")).unwrap();
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut wasi = WasiCtxBuilder::new().build();
+        setup(&mut wasi);
+        let mut store = Store::new(&engine, wasi);
+        let mut linker = <Linker<WasiCtx>>::new(&engine);
+        add_to_linker(&mut linker, |ctx| ctx).unwrap();
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let memory = instance.get_memory(&store, "memory").unwrap();
+        Self {
+            store,
+            instance,
+            memory,
+        }
+    }
+
+    /// Calls the WASI function `name` with `args` and returns its error code.
+    fn call(&mut self, name: &str, args: &[Value]) -> i32 {
+        let func = self.instance.get_func(&self.store, name).unwrap();
+        let mut result = [Value::I32(0)];
+        func.call(&mut self.store, args, &mut result).unwrap();
+        result[0].i32().unwrap()
+    }
+
+    fn read(&self, offset: usize, len: usize) -> Vec<u8> {
+        self.memory.data(&self.store)[offset..offset + len].to_vec()
+    }
+
+    fn read_u32(&self, offset: usize) -> u32 {
+        u32::from_le_bytes(self.read(offset, 4).try_into().unwrap())
+    }
+
+    fn read_u64(&self, offset: usize) -> u64 {
+        u64::from_le_bytes(self.read(offset, 8).try_into().unwrap())
+    }
+
+    fn write(&mut self, offset: usize, bytes: &[u8]) {
+        self.memory.write(&mut self.store, offset, bytes).unwrap();
+    }
+
+    /// Writes `data` to `fd` and returns the error code and number of written bytes.
+    fn fd_write(&mut self, fd: i32, data: &[u8]) -> (i32, u32) {
+        self.write(1024, data);
+        self.write(0, &1024_u32.to_le_bytes());
+        self.write(4, &(data.len() as u32).to_le_bytes());
+        let errno = self.call("fd_write", &[fd.into(), 0.into(), 1.into(), 8.into()]);
+        (errno, self.read_u32(8))
+    }
+
+    /// Reads up to `len` bytes from `fd` and returns the error code and the read bytes.
+    fn fd_read(&mut self, fd: i32, len: u32) -> (i32, Vec<u8>) {
+        self.write(0, &1024_u32.to_le_bytes());
+        self.write(4, &len.to_le_bytes());
+        let errno = self.call("fd_read", &[fd.into(), 0.into(), 1.into(), 8.into()]);
+        let read = self.read_u32(8) as usize;
+        (errno, self.read(1024, read))
+    }
+
+    /// Opens `path` relative to the preopened [`WasiFs`] and returns the error code and file descriptor.
+    fn path_open(&mut self, path: &str, oflags: i32, fdflags: i32) -> (i32, i32) {
+        self.write(512, path.as_bytes());
+        let errno = self.call(
+            "path_open",
+            &[
+                FS_FD.into(),
+                0.into(),
+                512.into(),
+                (path.len() as i32).into(),
+                oflags.into(),
+                RIGHTS_READ_WRITE.into(),
+                0_i64.into(),
+                fdflags.into(),
+                8.into(),
+            ],
+        );
+        (errno, self.read_u32(8) as i32)
+    }
+
+    /// Calls the WASI function `name` with `fd` and the given `path`.
+    fn path_call(&mut self, name: &str, path: &str) -> i32 {
+        self.write(512, path.as_bytes());
+        self.call(
+            name,
+            &[FS_FD.into(), 512.into(), (path.len() as i32).into()],
+        )
+    }
+}
+
+#[test]
+fn stdio() {
+    let stdio = CapturedStdio::default();
+    stdio.stdin.lock().unwrap().extend_from_slice(b"input");
+    let mut guest = Guest::new(|wasi| wasi.set_stdio(stdio.clone()));
+    assert_eq!(guest.fd_write(1, b"hello"), (0, 5));
+    assert_eq!(guest.fd_write(2, b"oops"), (0, 4));
+    assert_eq!(guest.fd_read(0, 3), (0, b"inp".to_vec()));
+    assert_eq!(guest.fd_read(0, 10), (0, b"ut".to_vec()));
+    assert_eq!(guest.fd_read(0, 10), (0, Vec::new()));
+    assert_eq!(&stdio.stdout.lock().unwrap()[..], b"hello");
+    assert_eq!(&stdio.stderr.lock().unwrap()[..], b"oops");
+}
+
+#[test]
+fn clock() {
+    let mut guest = Guest::new(|wasi| wasi.set_clock(SimulatedClock::default()));
+    // realtime
+    assert_eq!(
+        guest.call("clock_time_get", &[0.into(), 0_i64.into(), 0.into()]),
+        0
+    );
+    assert_eq!(guest.read_u64(0), 1_000_000_000_000);
+    // monotonic
+    for expected in [1, 2, 3] {
+        assert_eq!(
+            guest.call("clock_time_get", &[1.into(), 0_i64.into(), 0.into()]),
+            0
+        );
+        assert_eq!(guest.read_u64(0), expected * 1_000_000_000);
+    }
+}
+
+#[test]
+fn random() {
+    let mut guest = Guest::new(|wasi| wasi.set_random(CountingRandom(10)));
+    assert_eq!(guest.call("random_get", &[0.into(), 4.into()]), 0);
+    assert_eq!(guest.read(0, 4), [10, 11, 12, 13]);
+    assert_eq!(guest.call("random_get", &[0.into(), 2.into()]), 0);
+    assert_eq!(guest.read(0, 2), [14, 15]);
+}
+
+#[test]
+fn fs() {
+    let fs = MemoryFs::default();
+    fs.insert("data.txt", Entry::File(b"0123456789".to_vec()));
+    let mut guest = Guest::new(|wasi| wasi.preopen_fs(fs.clone(), "/").unwrap());
+    // Read an existing file.
+    let (errno, fd) = guest.path_open("data.txt", 0, 0);
+    assert_eq!(errno, 0);
+    assert_eq!(guest.fd_read(fd, 4), (0, b"0123".to_vec()));
+    // Overwrite parts of it.
+    assert_eq!(guest.fd_write(fd, b"ab"), (0, 2));
+    assert_eq!(
+        fs.get("data.txt"),
+        Some(Entry::File(b"0123ab6789".to_vec()))
+    );
+    assert_eq!(guest.fd_read(fd, 10), (0, b"6789".to_vec()));
+    // Create files within new directories.
+    assert_eq!(guest.path_call("path_create_directory", "dir"), 0);
+    assert_eq!(guest.path_call("path_create_directory", "dir"), ERRNO_EXIST);
+    let (errno, fd) = guest.path_open("dir/./new.txt", OFLAGS_CREATE, 0);
+    assert_eq!(errno, 0);
+    assert_eq!(fs.get("dir/new.txt"), Some(Entry::File(Vec::new())));
+    assert_eq!(guest.fd_write(fd, b"new"), (0, 3));
+    assert_eq!(
+        guest.path_open("dir/new.txt", OFLAGS_EXCL, 0).0,
+        ERRNO_EXIST
+    );
+    // Append to and truncate files.
+    let (_, fd) = guest.path_open("dir/../dir/new.txt", 0, FDFLAGS_APPEND);
+    assert_eq!(guest.fd_write(fd, b"er"), (0, 2));
+    assert_eq!(fs.get("dir/new.txt"), Some(Entry::File(b"newer".to_vec())));
+    let (_, fd) = guest.path_open("data.txt", OFLAGS_TRUNC, 0);
+    assert_eq!(guest.fd_read(fd, 10), (0, Vec::new()));
+    assert_eq!(fs.get("data.txt"), Some(Entry::File(Vec::new())));
+    // Rename and remove files.
+    guest.write(600, b"data.txt");
+    guest.write(700, b"dir/moved.txt");
+    let args = [FS_FD, 600, 8, FS_FD, 700, 13].map(Value::from);
+    assert_eq!(guest.call("path_rename", &args), 0);
+    assert_eq!(fs.get("data.txt"), None);
+    assert_eq!(guest.path_call("path_unlink_file", "dir/moved.txt"), 0);
+    assert_eq!(
+        guest.path_call("path_unlink_file", "dir/moved.txt"),
+        ERRNO_NOENT
+    );
+    assert_eq!(guest.path_open("missing.txt", 0, 0).0, ERRNO_NOENT);
+}
+
+#[test]
+fn fs_sandbox() {
+    let fs = MemoryFs::default();
+    let mut guest = Guest::new(|wasi| wasi.preopen_fs(fs.clone(), "/").unwrap());
+    for path in ["../escape.txt", "dir/../../escape.txt", "/absolute.txt"] {
+        assert_eq!(guest.path_open(path, OFLAGS_CREATE, 0).0, ERRNO_PERM);
+    }
+    assert!(fs.entries.lock().unwrap().is_empty());
+}
@@ -1 +1,3 @@
+mod backend;
+mod sockets;
 mod wasi_wat;
@@ -0,0 +1,129 @@
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream, UdpSocket},
+    thread,
+};
+use wasi_cap_std_sync::WasiCtxBuilder;
+use wasmi::{Engine, Linker, Module, Store, TypedFunc};
+use wasmi_wasi::{add_to_linker, WasiCtx, WasiSockets};
+
+/// WASI error code for bad file descriptors.
+const ERRNO_BADF: i32 = 8;
+
+/// WASI error code for unsupported operations.
+const ERRNO_NOTSUP: i32 = 58;
+
+/// The exported functions of the `echo.wat` guest.
+struct Echo {
+    store: Store<WasiCtx>,
+    accept: TypedFunc<i32, i32>,
+    echo: TypedFunc<i32, i32>,
+    shutdown: TypedFunc<i32, i32>,
+}
+
+impl Echo {
+    /// Instantiates the `echo.wat` guest with access to the granted `sockets`.
+    fn new(sockets: WasiSockets) -> Self {
+        let wasm = wat::parse_bytes(include_bytes!("wat/echo.wat")).unwrap();
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut wasi = WasiCtxBuilder::new().build();
+        sockets.add_to_ctx(&mut wasi);
+        let mut store = Store::new(&engine, wasi);
+        let mut linker = <Linker<WasiCtx>>::new(&engine);
+        add_to_linker(&mut linker, |ctx| ctx).unwrap();
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let accept = instance.get_typed_func(&store, "accept").unwrap();
+        let echo = instance.get_typed_func(&store, "echo").unwrap();
+        let shutdown = instance.get_typed_func(&store, "shutdown").unwrap();
+        Self {
+            store,
+            accept,
+            echo,
+            shutdown,
+        }
+    }
+
+    fn accept(&mut self, fd: i32) -> i32 {
+        self.accept.call(&mut self.store, fd).unwrap()
+    }
+
+    fn echo(&mut self, fd: i32) -> i32 {
+        self.echo.call(&mut self.store, fd).unwrap()
+    }
+
+    fn shutdown(&mut self, fd: i32) -> i32 {
+        self.shutdown.call(&mut self.store, fd).unwrap()
+    }
+}
+
+#[test]
+fn tcp_listen() {
+    let mut sockets = WasiSockets::new();
+    let addr = sockets.tcp_listen(3, "127.0.0.1:0").unwrap();
+    let mut guest = Echo::new(sockets);
+    let client = thread::spawn(move || {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(b"ping").unwrap();
+        let mut buffer = [0x00_u8; 4];
+        stream.read_exact(&mut buffer).unwrap();
+        buffer
+    });
+    let fd = guest.accept(3);
+    assert!(fd > 3);
+    assert_eq!(guest.echo(fd), 4);
+    assert_eq!(&client.join().unwrap(), b"ping");
+    assert_eq!(guest.shutdown(fd), 0);
+    // Listening sockets cannot be used to send or receive data.
+    assert_eq!(guest.echo(3), -ERRNO_BADF);
+}
+
+#[test]
+fn tcp_connect() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut sockets = WasiSockets::new();
+    let peer = sockets
+        .tcp_connect(3, listener.local_addr().unwrap())
+        .unwrap();
+    assert_eq!(peer, listener.local_addr().unwrap());
+    let mut guest = Echo::new(sockets);
+    let (mut stream, _) = listener.accept().unwrap();
+    stream.write_all(b"hello").unwrap();
+    assert_eq!(guest.echo(3), 5);
+    let mut buffer = [0x00_u8; 5];
+    stream.read_exact(&mut buffer).unwrap();
+    assert_eq!(&buffer, b"hello");
+}
+
+#[test]
+fn udp_connect() {
+    let host = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mut sockets = WasiSockets::new();
+    let addr = sockets
+        .udp_connect(4, "127.0.0.1:0", host.local_addr().unwrap())
+        .unwrap();
+    let mut guest = Echo::new(sockets);
+    host.connect(addr).unwrap();
+    host.send(b"datagram").unwrap();
+    assert_eq!(guest.echo(4), 8);
+    let mut buffer = [0x00_u8; 128];
+    assert_eq!(host.recv(&mut buffer).unwrap(), 8);
+    assert_eq!(&buffer[..8], b"datagram");
+    // Datagrams that do not fit into the guest buffer are truncated.
+    host.send(&[0xAB; 100]).unwrap();
+    assert_eq!(guest.echo(4), 64);
+    assert_eq!(host.recv(&mut buffer).unwrap(), 64);
+    assert!(buffer[..64].iter().all(|&byte| byte == 0xAB));
+    assert_eq!(guest.shutdown(4), ERRNO_NOTSUP);
+}
+
+#[test]
+fn ungranted_sockets() {
+    let mut guest = Echo::new(WasiSockets::new());
+    assert_eq!(guest.accept(3), -ERRNO_BADF);
+    assert_eq!(guest.echo(3), -ERRNO_BADF);
+}
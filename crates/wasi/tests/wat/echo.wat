@@ -0,0 +1,51 @@
+;; Echoes data received on a socket back to its peer.
+;;
+;; Negative results are negated WASI error codes.
+(module
+    (import "wasi_snapshot_preview1" "sock_accept" (func $sock_accept (param i32 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "sock_recv" (func $sock_recv (param i32 i32 i32 i32 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "sock_send" (func $sock_send (param i32 i32 i32 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "sock_shutdown" (func $sock_shutdown (param i32 i32) (result i32)))
+
+    (memory 1)
+    (export "memory" (memory 0))
+
+    ;; Accepts a connection on the listening socket `fd` and returns the new socket.
+    (func (export "accept") (param $fd i32) (result i32)
+        (local $errno i32)
+        (local.set $errno (call $sock_accept (local.get $fd) (i32.const 0) (i32.const 0)))
+        (if (local.get $errno)
+            (then (return (i32.sub (i32.const 0) (local.get $errno))))
+        )
+        (i32.load (i32.const 0))
+    )
+
+    ;; Receives up to 64 bytes on `fd`, sends them back and returns their number.
+    (func (export "echo") (param $fd i32) (result i32)
+        (local $errno i32)
+        ;; iovec { buf: 64, buf_len: 64 }
+        (i32.store (i32.const 8) (i32.const 64))
+        (i32.store (i32.const 12) (i32.const 64))
+        (local.set $errno
+            (call $sock_recv (local.get $fd) (i32.const 8) (i32.const 1) (i32.const 0) (i32.const 16) (i32.const 20))
+        )
+        (if (local.get $errno)
+            (then (return (i32.sub (i32.const 0) (local.get $errno))))
+        )
+        ;; ciovec { buf: 64, buf_len: received }
+        (i32.store (i32.const 24) (i32.const 64))
+        (i32.store (i32.const 28) (i32.load (i32.const 16)))
+        (local.set $errno
+            (call $sock_send (local.get $fd) (i32.const 24) (i32.const 1) (i32.const 0) (i32.const 32))
+        )
+        (if (local.get $errno)
+            (then (return (i32.sub (i32.const 0) (local.get $errno))))
+        )
+        (i32.load (i32.const 32))
+    )
+
+    ;; Shuts down both directions of `fd` and returns the WASI error code.
+    (func (export "shutdown") (param $fd i32) (result i32)
+        (call $sock_shutdown (local.get $fd) (i32.const 3))
+    )
+)
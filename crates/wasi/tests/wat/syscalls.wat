@@ -0,0 +1,41 @@
+;; Exports wrappers of raw WASI functions so that tests can call them directly.
+;;
+;; Note: WASI functions must be called from within an instance exporting its memory.
+(module
+    (import "wasi_snapshot_preview1" "clock_time_get" (func $clock_time_get (param i32 i64 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "fd_read" (func $fd_read (param i32 i32 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "path_create_directory" (func $path_create_directory (param i32 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "path_open" (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "path_rename" (func $path_rename (param i32 i32 i32 i32 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "path_unlink_file" (func $path_unlink_file (param i32 i32 i32) (result i32)))
+    (import "wasi_snapshot_preview1" "random_get" (func $random_get (param i32 i32) (result i32)))
+
+    (memory 1)
+    (export "memory" (memory 0))
+
+    (func (export "clock_time_get") (param i32 i64 i32) (result i32)
+        (call $clock_time_get (local.get 0) (local.get 1) (local.get 2))
+    )
+    (func (export "fd_read") (param i32 i32 i32 i32) (result i32)
+        (call $fd_read (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+    )
+    (func (export "fd_write") (param i32 i32 i32 i32) (result i32)
+        (call $fd_write (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+    )
+    (func (export "path_create_directory") (param i32 i32 i32) (result i32)
+        (call $path_create_directory (local.get 0) (local.get 1) (local.get 2))
+    )
+    (func (export "path_open") (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)
+        (call $path_open (local.get 0) (local.get 1) (local.get 2) (local.get 3) (local.get 4) (local.get 5) (local.get 6) (local.get 7) (local.get 8))
+    )
+    (func (export "path_rename") (param i32 i32 i32 i32 i32 i32) (result i32)
+        (call $path_rename (local.get 0) (local.get 1) (local.get 2) (local.get 3) (local.get 4) (local.get 5))
+    )
+    (func (export "path_unlink_file") (param i32 i32 i32) (result i32)
+        (call $path_unlink_file (local.get 0) (local.get 1) (local.get 2))
+    )
+    (func (export "random_get") (param i32 i32) (result i32)
+        (call $random_get (local.get 0) (local.get 1))
+    )
+)
@@ -0,0 +1,476 @@
+//! Debugging APIs for building reversible debuggers on top of the [`Engine`].
+//!
+//! # Example
+//!
+//! ```
+//! use wasmi::{debug::TimeTravel, Caller, Engine, HostCallRecorder, Linker, Module, Store, Value};
+//!
+//! let wasm = wat::parse_str(r#"
+//!     (module
+//!         (import "env" "random" (func $random (result i32)))
+//!         (global $sum (export "sum") (mut i32) (i32.const 0))
+//!         (func (export "run") (result i32)
+//!             (global.set $sum (call $random))
+//!             (global.set $sum (i32.add (global.get $sum) (call $random)))
+//!             (global.get $sum)
+//!         )
+//!     )
+//! "#).unwrap();
+//! let engine = Engine::default();
+//! let module = Module::new(&engine, &wasm[..]).unwrap();
+//! let mut store = Store::new(&engine, 0);
+//! let mut linker = <Linker<i32>>::new(&engine);
+//! linker.func_wrap("env", "random", |mut caller: Caller<i32>| {
+//!     *caller.data_mut() += 10;
+//!     *caller.data()
+//! }).unwrap();
+//! let instance = linker
+//!     .instantiate(&mut store, &module)
+//!     .unwrap()
+//!     .start(&mut store)
+//!     .unwrap();
+//! let run = instance.get_func(&store, "run").unwrap();
+//!
+//! // Record the execution of `run` ...
+//! let recorder = HostCallRecorder::new();
+//! recorder.install(&mut store);
+//! let mut results = [Value::I32(0)];
+//! run.call(&mut store, &[], &mut results).unwrap();
+//! assert_eq!(results[0].i32(), Some(30));
+//!
+//! // ... and travel through it in time.
+//! let sum = instance.get_global(&store, "sum").unwrap();
+//! sum.set(&mut store, Value::I32(0)).unwrap();
+//! let mut debugger = TimeTravel::new(&mut store, instance, run, &[], recorder.take_log(), 1).unwrap();
+//! debugger.step_forward(&mut store).unwrap();
+//! assert_eq!(sum.get(&store).i32(), Some(10));
+//! debugger.step_forward(&mut store).unwrap();
+//! assert!(debugger.is_finished());
+//! assert_eq!(sum.get(&store).i32(), Some(30));
+//! debugger.step_backward(&mut store).unwrap();
+//! assert_eq!(debugger.position(), 1);
+//! assert_eq!(sum.get(&store).i32(), Some(10));
+//! ```
+//!
+//! [`Engine`]: crate::Engine
+
+use crate::{
+    core::{HostError, TrapCode},
+    global::GlobalEntity,
+    memory::MemoryEntity,
+    table::TableEntity,
+    AsContext,
+    AsContextMut,
+    Error,
+    Func,
+    Global,
+    HostCallLog,
+    HostCallOutcome,
+    HostCallRecord,
+    Instance,
+    Memory,
+    ResumableCall,
+    ResumableInvocation,
+    Store,
+    Table,
+    Value,
+};
+use alloc::{
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{fmt, mem::replace};
+use spin::Mutex;
+
+/// A snapshot of the linear memories, tables and global variables of an [`Instance`].
+///
+/// # Note
+///
+/// Imported entities are part of the snapshot, too. Passive data and element
+/// segments as well as the entities of other instances are not captured.
+#[derive(Debug, Clone)]
+pub struct InstanceSnapshot {
+    /// The captured linear memories.
+    memories: Vec<(Memory, MemoryEntity)>,
+    /// The captured tables.
+    tables: Vec<(Table, TableEntity)>,
+    /// The captured global variables.
+    globals: Vec<(Global, GlobalEntity)>,
+}
+
+impl InstanceSnapshot {
+    /// Captures the current state of the `instance`.
+    pub fn capture(ctx: impl AsContext, instance: &Instance) -> Self {
+        let store = &ctx.as_context().store.inner;
+        let entity = store.resolve_instance(instance);
+        let memories = (0..)
+            .map_while(|index| entity.get_memory(index))
+            .map(|memory| (memory, store.resolve_memory(&memory).clone()))
+            .collect();
+        let tables = (0..)
+            .map_while(|index| entity.get_table(index))
+            .map(|table| (table, store.resolve_table(&table).clone()))
+            .collect();
+        let globals = (0..)
+            .map_while(|index| entity.get_global(index))
+            .map(|global| (global, store.resolve_global(&global).clone()))
+            .collect();
+        Self {
+            memories,
+            tables,
+            globals,
+        }
+    }
+
+    /// Restores the captured state into the [`Store`] of the captured [`Instance`].
+    ///
+    /// # Note
+    ///
+    /// Linear memories and tables are shrunk back to their captured sizes if necessary.
+    pub fn restore(&self, mut ctx: impl AsContextMut) {
+        let store = &mut ctx.as_context_mut().store.inner;
+        for (memory, entity) in &self.memories {
+            *store.resolve_memory_mut(memory) = entity.clone();
+        }
+        for (table, entity) in &self.tables {
+            *store.resolve_table_mut(table) = entity.clone();
+        }
+        for (global, entity) in &self.globals {
+            *store.resolve_global_mut(global) = entity.clone();
+        }
+    }
+}
+
+/// The host error used to pause a [`TimeTravel`] execution at every host function call.
+#[derive(Debug)]
+struct Paused;
+
+impl fmt::Display for Paused {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "paused at host function call")
+    }
+}
+
+impl HostError for Paused {}
+
+/// A host function call that paused a [`TimeTravel`] execution.
+#[derive(Debug)]
+struct PendingCall {
+    /// The module name of the called host function if any.
+    module: Option<String>,
+    /// The name of the called host function if any.
+    name: Option<String>,
+    /// The parameters of the host function call.
+    params: Vec<Value>,
+}
+
+/// A state of a [`TimeTravel`] execution that can be restored.
+#[derive(Debug)]
+struct Checkpoint {
+    /// The position of the [`TimeTravel`] execution.
+    position: usize,
+    /// The state of the debugged [`Instance`].
+    snapshot: InstanceSnapshot,
+    /// The paused execution or `None` if the execution has not yet been started.
+    invocation: Option<ResumableInvocation>,
+}
+
+/// The current state of a [`TimeTravel`] execution.
+#[derive(Debug)]
+enum State {
+    /// The execution is paused at a host function call.
+    Paused(ResumableInvocation),
+    /// The execution has finished with the given outcome.
+    Finished(Result<Vec<Value>, Error>),
+}
+
+/// A reversible execution of a Wasm function driven by a recorded [`HostCallLog`].
+///
+/// # Note
+///
+/// - The execution pauses at every host function call which is answered by the
+///   next [`HostCallRecord`] of the log instead of calling the host function.
+///   The position of the execution is the number of answered host function calls.
+/// - Every `interval` host function calls a checkpoint is taken consisting of an
+///   [`InstanceSnapshot`] and a copy of the paused execution. Stepping backwards
+///   restores the nearest earlier checkpoint and re-executes forward to the target.
+/// - Only the state of the debugged [`Instance`] is restored. Host function calls
+///   are expected to modify at most the linear memory exported as `"memory"` by it.
+///
+/// The [`TimeTravel`] installs itself as host call interceptor of the [`Store`]
+/// which must not be replaced while the execution is debugged.
+#[derive(Debug)]
+pub struct TimeTravel {
+    /// The instance whose state is restored when travelling backwards.
+    instance: Instance,
+    /// The debugged Wasm function.
+    func: Func,
+    /// The parameters of the debugged Wasm function.
+    params: Vec<Value>,
+    /// The recorded host function calls that answer the paused execution.
+    records: Vec<HostCallRecord>,
+    /// The number of host function calls between two checkpoints.
+    interval: usize,
+    /// The host function call that paused the execution if any.
+    pending: Arc<Mutex<Option<PendingCall>>>,
+    /// The checkpoints sorted by position.
+    checkpoints: Vec<Checkpoint>,
+    /// The number of answered host function calls.
+    position: usize,
+    /// The current state of the execution.
+    state: State,
+}
+
+impl TimeTravel {
+    /// Starts a reversible execution of `func` with `params` and pauses at the first host function call.
+    ///
+    /// A checkpoint is taken every `interval` host function calls.
+    ///
+    /// # Errors
+    ///
+    /// - If `interval` is zero.
+    /// - If `params` do not match the parameters of `func`.
+    /// - If the execution diverges from the `log`.
+    pub fn new<T>(
+        store: &mut Store<T>,
+        instance: Instance,
+        func: Func,
+        params: &[Value],
+        log: HostCallLog,
+        interval: usize,
+    ) -> Result<Self, Error> {
+        if interval == 0 {
+            return Err(Error::new(
+                "time travel checkpoint interval must not be zero",
+            ));
+        }
+        let pending = <Arc<Mutex<Option<PendingCall>>>>::default();
+        store.set_host_call_interceptor({
+            let pending = pending.clone();
+            move |_caller, call| {
+                *pending.lock() = Some(PendingCall {
+                    module: call.module().map(String::from),
+                    name: call.name().map(String::from),
+                    params: call.params().collect(),
+                });
+                Err(Error::host(Paused))
+            }
+        });
+        let checkpoint = Checkpoint {
+            position: 0,
+            snapshot: InstanceSnapshot::capture(&*store, &instance),
+            invocation: None,
+        };
+        let mut this = Self {
+            instance,
+            func,
+            params: params.to_vec(),
+            records: log.records().to_vec(),
+            interval,
+            pending,
+            checkpoints: Vec::from([checkpoint]),
+            position: 0,
+            state: State::Finished(Ok(Vec::new())),
+        };
+        this.start(store)?;
+        Ok(this)
+    }
+
+    /// Returns the number of answered host function calls.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns `true` if the execution has finished.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, State::Finished(_))
+    }
+
+    /// Returns the outcome of the execution if it has finished.
+    pub fn outcome(&self) -> Option<&Result<Vec<Value>, Error>> {
+        match &self.state {
+            State::Paused(_) => None,
+            State::Finished(outcome) => Some(outcome),
+        }
+    }
+
+    /// Returns the recorded host function call at which the execution is paused if any.
+    pub fn current_host_call(&self) -> Option<&HostCallRecord> {
+        match self.state {
+            State::Paused(_) => self.records.get(self.position),
+            State::Finished(_) => None,
+        }
+    }
+
+    /// Returns the positions of all checkpoints.
+    pub fn checkpoints(&self) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.checkpoints
+            .iter()
+            .map(|checkpoint| checkpoint.position)
+    }
+
+    /// Answers the current host function call and runs until the next one.
+    ///
+    /// # Errors
+    ///
+    /// - If the execution has already finished.
+    /// - If the execution diverges from the recorded [`HostCallLog`].
+    pub fn step_forward<T>(&mut self, store: &mut Store<T>) -> Result<(), Error> {
+        if self.is_finished() {
+            return Err(Error::new("time travel execution has already finished"));
+        }
+        let record = &self.records[self.position];
+        let memory = self.instance.get_memory(&*store, "memory");
+        record.replay_memory(&mut *store, memory)?;
+        let State::Paused(invocation) = replace(&mut self.state, State::Finished(Ok(Vec::new())))
+        else {
+            unreachable!("time travel execution must be paused")
+        };
+        self.position += 1;
+        let outcome = match record.outcome() {
+            HostCallOutcome::Return(results) => {
+                let mut outputs = self.outputs(store);
+                invocation
+                    .resume(&mut *store, results, &mut outputs)
+                    .map(|call| (call, outputs))
+            }
+            HostCallOutcome::Trap(trap_code) => Err(Error::from(*trap_code)),
+            HostCallOutcome::Error(message) => Err(Error::new(message.to_string())),
+        };
+        self.enter(store, outcome)
+    }
+
+    /// Travels back to the previous host function call.
+    ///
+    /// # Errors
+    ///
+    /// If the execution is at its start.
+    pub fn step_backward<T>(&mut self, store: &mut Store<T>) -> Result<(), Error> {
+        let Some(target) = self.position.checked_sub(1) else {
+            return Err(Error::new("time travel execution is already at its start"));
+        };
+        self.seek(store, target)
+    }
+
+    /// Travels to the `target` position of the execution.
+    ///
+    /// # Errors
+    ///
+    /// - If the execution finishes before reaching `target`.
+    /// - If the execution diverges from the recorded [`HostCallLog`].
+    pub fn seek<T>(&mut self, store: &mut Store<T>, target: usize) -> Result<(), Error> {
+        if target < self.position {
+            let index = self
+                .checkpoints
+                .partition_point(|checkpoint| checkpoint.position <= target);
+            let checkpoint = &self.checkpoints[index - 1];
+            checkpoint.snapshot.restore(&mut *store);
+            self.position = checkpoint.position;
+            match &checkpoint.invocation {
+                Some(invocation) => {
+                    self.state = State::Paused(invocation.fork(Error::host(Paused)));
+                }
+                None => self.start(store)?,
+            }
+        }
+        while self.position < target {
+            self.step_forward(store)?;
+        }
+        Ok(())
+    }
+
+    /// Returns a buffer for the results of the debugged Wasm function.
+    fn outputs<T>(&self, store: &Store<T>) -> Vec<Value> {
+        self.func
+            .ty(store)
+            .results()
+            .iter()
+            .copied()
+            .map(Value::default)
+            .collect()
+    }
+
+    /// Starts the execution of the debugged Wasm function from the beginning.
+    fn start<T>(&mut self, store: &mut Store<T>) -> Result<(), Error> {
+        let mut outputs = self.outputs(store);
+        let outcome = self
+            .func
+            .call_resumable(&mut *store, &self.params, &mut outputs)
+            .map(|call| (call, outputs));
+        self.enter(store, outcome)
+    }
+
+    /// Enters the state after running the execution with the given `outcome`.
+    ///
+    /// Takes a checkpoint if the execution paused at a multiple of the interval.
+    fn enter<T>(
+        &mut self,
+        store: &mut Store<T>,
+        mut outcome: Result<(ResumableCall, Vec<Value>), Error>,
+    ) -> Result<(), Error> {
+        let invocation = loop {
+            match outcome {
+                Ok((ResumableCall::Resumable(invocation), _)) => break invocation,
+                Ok((ResumableCall::Yielded(yielded), mut outputs)) => {
+                    // Note: Only host function calls are positions of the execution.
+                    outcome = yielded
+                        .resume(&mut *store, &mut outputs)
+                        .map(|call| (call, outputs));
+                }
+                Ok((ResumableCall::OutOfFuel(_), _)) => {
+                    self.state = State::Finished(Err(Error::from(TrapCode::OutOfFuel)));
+                    return Ok(());
+                }
+                Ok((ResumableCall::Finished, outputs)) => {
+                    self.state = State::Finished(Ok(outputs));
+                    return Ok(());
+                }
+                Err(error) => {
+                    self.state = State::Finished(Err(error));
+                    return Ok(());
+                }
+            }
+        };
+        let pending = self.pending.lock().take();
+        let (Some(pending), Some(record)) = (pending, self.records.get(self.position)) else {
+            return self.diverged(Error::new(
+                "host call replay diverged: the host call log has been exhausted",
+            ));
+        };
+        if let Err(error) = record.check_call(
+            pending.module.as_deref(),
+            pending.name.as_deref(),
+            pending.params.into_iter(),
+        ) {
+            return self.diverged(error);
+        }
+        let position = self.position;
+        if position.is_multiple_of(self.interval) {
+            let index = self
+                .checkpoints
+                .partition_point(|checkpoint| checkpoint.position < position);
+            if self
+                .checkpoints
+                .get(index)
+                .map(|checkpoint| checkpoint.position)
+                != Some(position)
+            {
+                let checkpoint = Checkpoint {
+                    position,
+                    snapshot: InstanceSnapshot::capture(&*store, &self.instance),
+                    invocation: Some(invocation.fork(Error::host(Paused))),
+                };
+                self.checkpoints.insert(index, checkpoint);
+            }
+        }
+        self.state = State::Paused(invocation);
+        Ok(())
+    }
+
+    /// Finishes the execution since it diverged from the recorded [`HostCallLog`] with `error`.
+    fn diverged(&mut self, error: Error) -> Result<(), Error> {
+        self.state = State::Finished(Err(Error::new(error.to_string())));
+        Err(error)
+    }
+}
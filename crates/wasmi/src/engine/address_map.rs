@@ -0,0 +1,72 @@
+use crate::module::{ArtifactError, Decode, Decoder, Encode, Encoder};
+use alloc::{sync::Arc, vec::Vec};
+
+/// Maps the Wasmi bytecode instructions of a compiled function back to their Wasm bytecode offsets.
+///
+/// Query it via [`Engine::address_map`] if [`Config::generate_address_maps`] is enabled.
+///
+/// # Note
+///
+/// - Wasm bytecode offsets are relative to the start of the Wasm binary of the [`Module`].
+/// - Consecutive instructions that originate from the same Wasm operator share a single entry.
+///
+/// [`Engine::address_map`]: crate::Engine::address_map
+/// [`Config::generate_address_maps`]: crate::Config::generate_address_maps
+/// [`Module`]: crate::Module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressMap {
+    /// Pairs of the first instruction index and its Wasm bytecode offset sorted by instruction index.
+    entries: Arc<[(u32, u32)]>,
+}
+
+impl AddressMap {
+    /// Creates a new [`AddressMap`] from the Wasm bytecode offsets of all instructions in order.
+    pub(crate) fn new(offsets: impl IntoIterator<Item = u32>) -> Self {
+        let mut entries = <Vec<(u32, u32)>>::new();
+        for (instr, offset) in (0_u32..).zip(offsets) {
+            if entries.last().map(|&(_, last)| last) != Some(offset) {
+                entries.push((instr, offset));
+            }
+        }
+        Self {
+            entries: entries.into(),
+        }
+    }
+
+    /// Returns the Wasm bytecode offset of the Wasmi bytecode instruction at index `instr`.
+    ///
+    /// Returns `None` if `instr` precedes all instructions with a known Wasm bytecode offset.
+    pub fn wasm_offset(&self, instr: u32) -> Option<u32> {
+        let index = match self
+            .entries
+            .binary_search_by_key(&instr, |&(instr, _)| instr)
+        {
+            Ok(index) => index,
+            Err(0) => return None,
+            Err(index) => index - 1,
+        };
+        Some(self.entries[index].1)
+    }
+
+    /// Returns an iterator over all pairs of the first instruction index and its Wasm bytecode offset.
+    ///
+    /// The pairs are yielded in ascending order of instruction indices.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.entries.iter().copied()
+    }
+}
+
+impl Encode for AddressMap {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode_all(&self.entries[..]);
+    }
+}
+
+impl Decode for AddressMap {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let entries = decoder.decode_all::<(u32, u32)>()?;
+        Ok(Self {
+            entries: entries.into(),
+        })
+    }
+}
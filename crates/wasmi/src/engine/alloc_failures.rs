@@ -0,0 +1,106 @@
+use spin::Mutex;
+
+/// Determines which internal allocations of an [`Engine`] fail on purpose.
+///
+/// This allows to test that error handling paths of embedders around
+/// Wasm translation as well as linear memory and table growth work
+/// under out-of-memory conditions.
+///
+/// The following allocations are subject to failure injection:
+///
+/// - The compiled code of Wasm functions upon their translation.
+/// - The linear memories and tables upon their creation and growth.
+///
+/// Injected failures are reported the same way as actual allocation failures:
+///
+/// - Failed translations return [`TranslationError::AllocationFailed`].
+/// - Failed creations of linear memories and tables return an error.
+/// - Failed growth of linear memories and tables returns `-1` to Wasm.
+///
+/// [`Engine`]: crate::Engine
+/// [`TranslationError::AllocationFailed`]: crate::errors::TranslationError::AllocationFailed
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AllocFailures {
+    /// Fails the `n`-th allocation of the [`Engine`] counting from zero.
+    ///
+    /// All other allocations succeed.
+    ///
+    /// [`Engine`]: crate::Engine
+    Nth(u64),
+    /// Fails each allocation of the [`Engine`] with a probability of `1 / one_in`.
+    ///
+    /// The sequence of failed allocations is the same for the same `seed`.
+    /// No allocation fails if `one_in` is zero.
+    ///
+    /// [`Engine`]: crate::Engine
+    Random {
+        /// The seed of the pseudo-random sequence of failures.
+        seed: u64,
+        /// The inverse probability of an allocation to fail.
+        one_in: u32,
+    },
+}
+
+/// Decides which internal allocations of an [`Engine`] fail.
+///
+/// [`Engine`]: crate::Engine
+#[derive(Debug)]
+pub struct AllocFailureInjector {
+    /// The configured failures.
+    failures: AllocFailures,
+    /// The mutable state of the injector.
+    state: Mutex<InjectorState>,
+}
+
+/// The mutable state of an [`AllocFailureInjector`].
+#[derive(Debug)]
+struct InjectorState {
+    /// The number of allocations so far.
+    count: u64,
+    /// The state of the pseudo-random number generator.
+    rng: u64,
+}
+
+impl InjectorState {
+    /// Returns the next pseudo-random number using the `SplitMix64` algorithm.
+    fn next_random(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl AllocFailureInjector {
+    /// Creates a new [`AllocFailureInjector`] for the given `failures`.
+    pub fn new(failures: AllocFailures) -> Self {
+        let rng = match failures {
+            AllocFailures::Nth(_) => 0,
+            AllocFailures::Random { seed, .. } => seed,
+        };
+        Self {
+            failures,
+            state: Mutex::new(InjectorState { count: 0, rng }),
+        }
+    }
+
+    /// Registers the next allocation and returns `true` if it shall fail.
+    pub fn fail_next(&self) -> bool {
+        let mut state = self.state.lock();
+        let index = state.count;
+        state.count = index.wrapping_add(1);
+        match self.failures {
+            AllocFailures::Nth(n) => index == n,
+            AllocFailures::Random { one_in: 0, .. } => false,
+            AllocFailures::Random { one_in, .. } => {
+                state.next_random() <= u64::MAX / u64::from(one_in)
+            }
+        }
+    }
+
+    /// Returns the number of allocations registered so far.
+    pub fn count(&self) -> u64 {
+        self.state.lock().count
+    }
+}
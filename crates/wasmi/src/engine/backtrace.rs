@@ -0,0 +1,179 @@
+use super::{
+    code_map::{CodeMap, InstructionPtr},
+    executor::CallStack,
+    EngineInner,
+    Symbol,
+    SymbolQuery,
+};
+use crate::{store::StoreInner, Instance};
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+/// A Wasm call frame of a [`WasmBacktrace`].
+#[derive(Debug, Clone)]
+pub struct FrameInfo {
+    /// The instance of the called Wasm function.
+    instance: Instance,
+    /// The index of the called Wasm function within its module.
+    func_index: u32,
+    /// The Wasm bytecode offset of the pending call instruction if known.
+    wasm_offset: Option<u32>,
+    /// The name of the called Wasm function from the `name` custom section if any.
+    func_name: Option<Box<str>>,
+    /// The [`Symbol`] resolved by the [`SymbolProvider`] of the [`Engine`] if any.
+    ///
+    /// [`SymbolProvider`]: crate::SymbolProvider
+    /// [`Engine`]: crate::Engine
+    symbol: Option<Symbol>,
+}
+
+impl FrameInfo {
+    /// Returns the [`Instance`] of the called Wasm function.
+    pub fn instance(&self) -> Instance {
+        self.instance
+    }
+
+    /// Returns the index of the called Wasm function within its module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the Wasm bytecode offset of the pending call instruction of the frame.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` unless [`Config::generate_address_maps`] is enabled.
+    ///
+    /// [`Config::generate_address_maps`]: crate::Config::generate_address_maps
+    pub fn wasm_offset(&self) -> Option<u32> {
+        self.wasm_offset
+    }
+
+    /// Returns the name of the called Wasm function if provided by the `name` custom section.
+    pub fn func_name(&self) -> Option<&str> {
+        self.func_name.as_deref()
+    }
+
+    /// Returns the [`Symbol`] of the frame if resolved by the registered [`SymbolProvider`].
+    ///
+    /// [`SymbolProvider`]: crate::SymbolProvider
+    pub fn symbol(&self) -> Option<&Symbol> {
+        self.symbol.as_ref()
+    }
+}
+
+/// A snapshot of the Wasm call frames on the call stack.
+///
+/// Captured via [`Caller::backtrace`].
+///
+/// [`Caller::backtrace`]: crate::Caller::backtrace
+#[derive(Debug, Default, Clone)]
+pub struct WasmBacktrace {
+    /// The Wasm call frames ordered from the innermost to the outermost.
+    frames: Vec<FrameInfo>,
+}
+
+impl WasmBacktrace {
+    /// Returns the Wasm call frames ordered from the innermost to the outermost.
+    pub fn frames(&self) -> &[FrameInfo] {
+        &self.frames
+    }
+
+    /// Returns the number of Wasm call frames of the [`WasmBacktrace`].
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if the [`WasmBacktrace`] has no Wasm call frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+impl fmt::Display for WasmBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, frame) in self.frames.iter().enumerate() {
+            write!(f, "{n:>4}: ")?;
+            match (frame.symbol(), frame.func_name()) {
+                (Some(symbol), _) => write!(f, "{}", symbol.name())?,
+                (None, Some(name)) => write!(f, "{name}")?,
+                (None, None) => write!(f, "<func {}>", frame.func_index)?,
+            }
+            if let Some(offset) = frame.wasm_offset {
+                write!(f, " @ {offset:#x}")?;
+            }
+            if let Some(symbol) = frame.symbol() {
+                if let Some(file) = symbol.file() {
+                    write!(f, " at {file}")?;
+                    if let Some(line) = symbol.line() {
+                        write!(f, ":{line}")?;
+                        if let Some(column) = symbol.column() {
+                            write!(f, ":{column}")?;
+                        }
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl WasmBacktrace {
+    /// Captures the [`WasmBacktrace`] of the Wasm call frames on the `calls` stack.
+    ///
+    /// If `trap_instr` is `Some` it points to the trapping instruction of the innermost frame.
+    pub(crate) fn capture(
+        code_map: &CodeMap,
+        store: &StoreInner,
+        calls: &CallStack,
+        trap_instr: Option<InstructionPtr>,
+    ) -> Self {
+        let provider = store.engine().inner.symbol_provider();
+        let frames = calls
+            .frames()
+            .iter()
+            .rev()
+            .enumerate()
+            .filter_map(|(depth, frame)| {
+                let func = code_map.get_compiled(frame.func())?;
+                let func_index = func.func_index();
+                let wasm_offset = func.address_map().and_then(|address_map| {
+                    let instr = match (depth, trap_instr) {
+                        (0, Some(trap_instr)) => trap_instr.index_in(func.instrs())?,
+                        // Note: The instruction pointer of a call frame points to the instruction
+                        //       following its pending call and therefore we have to step back.
+                        _ => frame.instr_ptr().index_in(func.instrs())?.checked_sub(1)?,
+                    };
+                    address_map.wasm_offset(u32::try_from(instr).ok()?)
+                });
+                let instance = *frame.instance();
+                let entity = store.resolve_instance(&instance);
+                let func_name = entity.get_func_name(func_index);
+                let symbol = provider.as_ref().and_then(|provider| {
+                    provider.symbolicate(&SymbolQuery::new(
+                        entity.get_module_name(),
+                        func_index,
+                        func_name,
+                        wasm_offset,
+                    ))
+                });
+                Some(FrameInfo {
+                    instance,
+                    func_index,
+                    wasm_offset,
+                    func_name: func_name.map(Box::from),
+                    symbol,
+                })
+            })
+            .collect();
+        Self { frames }
+    }
+}
+
+impl EngineInner {
+    /// Captures the [`WasmBacktrace`] of the Wasm call frames on the `calls` stack.
+    pub fn capture_backtrace(&self, store: &StoreInner, calls: &CallStack) -> WasmBacktrace {
+        WasmBacktrace::capture(&self.res.read().code_map, store, calls, None)
+    }
+}
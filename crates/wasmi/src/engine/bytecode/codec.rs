@@ -0,0 +1,3200 @@
+//! Encoding of Wasmi bytecode [`Instruction`]s for precompiled artifacts.
+//!
+//! # Note
+//!
+//! Every [`Instruction`] is encoded as its `u16` variant tag in declaration order
+//! followed by its fields in declaration order. Variants that are compiled out via
+//! crate features keep their tags so that the tags of all other variants are stable.
+
+use super::Instruction;
+use crate::module::{ArtifactError, Decode, Decoder, Encode, Encoder};
+
+impl Encode for Instruction {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            #[cfg(not(all(not(feature = "reference-types"), not(feature = "bulk-table"))))]
+            Self::TableIdx(value) => {
+                encoder.encode(&0_u16);
+                encoder.encode(value);
+            }
+            Self::DataSegmentIdx(value) => {
+                encoder.encode(&1_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::ElementSegmentIdx(value) => {
+                encoder.encode(&2_u16);
+                encoder.encode(value);
+            }
+            Self::Const32(value) => {
+                encoder.encode(&3_u16);
+                encoder.encode(value);
+            }
+            Self::I64Const32(value) => {
+                encoder.encode(&4_u16);
+                encoder.encode(value);
+            }
+            Self::F64Const32(value) => {
+                encoder.encode(&5_u16);
+                encoder.encode(value);
+            }
+            Self::Register(value) => {
+                encoder.encode(&6_u16);
+                encoder.encode(value);
+            }
+            Self::Register2(value) => {
+                encoder.encode(&7_u16);
+                encoder.encode(value);
+            }
+            Self::Register3(value) => {
+                encoder.encode(&8_u16);
+                encoder.encode(value);
+            }
+            Self::RegisterList(value) => {
+                encoder.encode(&9_u16);
+                encoder.encode(value);
+            }
+            Self::CallIndirectParams(value) => {
+                encoder.encode(&10_u16);
+                encoder.encode(value);
+            }
+            Self::CallIndirectParamsImm16(value) => {
+                encoder.encode(&11_u16);
+                encoder.encode(value);
+            }
+            Self::Trap(value) => {
+                encoder.encode(&12_u16);
+                encoder.encode(value);
+            }
+            Self::ConsumeFuel(value) => {
+                encoder.encode(&13_u16);
+                encoder.encode(value);
+            }
+            Self::ConsumeInstrs(value) => {
+                encoder.encode(&14_u16);
+                encoder.encode(value);
+            }
+            Self::CheckEpoch => encoder.encode(&15_u16),
+            Self::Return => encoder.encode(&16_u16),
+            Self::ReturnReg { value } => {
+                encoder.encode(&17_u16);
+                encoder.encode(value);
+            }
+            Self::ReturnReg2 { values } => {
+                encoder.encode(&18_u16);
+                encoder.encode(values);
+            }
+            Self::ReturnReg3 { values } => {
+                encoder.encode(&19_u16);
+                encoder.encode(values);
+            }
+            Self::ReturnImm32 { value } => {
+                encoder.encode(&20_u16);
+                encoder.encode(value);
+            }
+            Self::ReturnI64Imm32 { value } => {
+                encoder.encode(&21_u16);
+                encoder.encode(value);
+            }
+            Self::ReturnF64Imm32 { value } => {
+                encoder.encode(&22_u16);
+                encoder.encode(value);
+            }
+            Self::ReturnSpan { values } => {
+                encoder.encode(&23_u16);
+                encoder.encode(values);
+            }
+            Self::ReturnMany { values } => {
+                encoder.encode(&24_u16);
+                encoder.encode(values);
+            }
+            Self::ReturnNez { condition } => {
+                encoder.encode(&25_u16);
+                encoder.encode(condition);
+            }
+            Self::ReturnNezReg { condition, value } => {
+                encoder.encode(&26_u16);
+                encoder.encode(condition);
+                encoder.encode(value);
+            }
+            Self::ReturnNezReg2 { condition, values } => {
+                encoder.encode(&27_u16);
+                encoder.encode(condition);
+                encoder.encode(values);
+            }
+            Self::ReturnNezImm32 { condition, value } => {
+                encoder.encode(&28_u16);
+                encoder.encode(condition);
+                encoder.encode(value);
+            }
+            Self::ReturnNezI64Imm32 { condition, value } => {
+                encoder.encode(&29_u16);
+                encoder.encode(condition);
+                encoder.encode(value);
+            }
+            Self::ReturnNezF64Imm32 { condition, value } => {
+                encoder.encode(&30_u16);
+                encoder.encode(condition);
+                encoder.encode(value);
+            }
+            Self::ReturnNezSpan { condition, values } => {
+                encoder.encode(&31_u16);
+                encoder.encode(condition);
+                encoder.encode(values);
+            }
+            Self::ReturnNezMany { condition, values } => {
+                encoder.encode(&32_u16);
+                encoder.encode(condition);
+                encoder.encode(values);
+            }
+            Self::Branch { offset } => {
+                encoder.encode(&33_u16);
+                encoder.encode(offset);
+            }
+            Self::BranchCmpFallback { lhs, rhs, params } => {
+                encoder.encode(&34_u16);
+                encoder.encode(lhs);
+                encoder.encode(rhs);
+                encoder.encode(params);
+            }
+            Self::BranchI32And(value) => {
+                encoder.encode(&35_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32AndImm(value) => {
+                encoder.encode(&36_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32Or(value) => {
+                encoder.encode(&37_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32OrImm(value) => {
+                encoder.encode(&38_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32Xor(value) => {
+                encoder.encode(&39_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32XorImm(value) => {
+                encoder.encode(&40_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32AndEqz(value) => {
+                encoder.encode(&41_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32AndEqzImm(value) => {
+                encoder.encode(&42_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32OrEqz(value) => {
+                encoder.encode(&43_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32OrEqzImm(value) => {
+                encoder.encode(&44_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32XorEqz(value) => {
+                encoder.encode(&45_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32XorEqzImm(value) => {
+                encoder.encode(&46_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32Eq(value) => {
+                encoder.encode(&47_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32EqImm(value) => {
+                encoder.encode(&48_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32Ne(value) => {
+                encoder.encode(&49_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32NeImm(value) => {
+                encoder.encode(&50_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32LtS(value) => {
+                encoder.encode(&51_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32LtSImm(value) => {
+                encoder.encode(&52_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32LtU(value) => {
+                encoder.encode(&53_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32LtUImm(value) => {
+                encoder.encode(&54_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32LeS(value) => {
+                encoder.encode(&55_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32LeSImm(value) => {
+                encoder.encode(&56_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32LeU(value) => {
+                encoder.encode(&57_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32LeUImm(value) => {
+                encoder.encode(&58_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32GtS(value) => {
+                encoder.encode(&59_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32GtSImm(value) => {
+                encoder.encode(&60_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32GtU(value) => {
+                encoder.encode(&61_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32GtUImm(value) => {
+                encoder.encode(&62_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32GeS(value) => {
+                encoder.encode(&63_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32GeSImm(value) => {
+                encoder.encode(&64_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32GeU(value) => {
+                encoder.encode(&65_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI32GeUImm(value) => {
+                encoder.encode(&66_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64Eq(value) => {
+                encoder.encode(&67_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64EqImm(value) => {
+                encoder.encode(&68_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64Ne(value) => {
+                encoder.encode(&69_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64NeImm(value) => {
+                encoder.encode(&70_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64LtS(value) => {
+                encoder.encode(&71_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64LtSImm(value) => {
+                encoder.encode(&72_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64LtU(value) => {
+                encoder.encode(&73_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64LtUImm(value) => {
+                encoder.encode(&74_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64LeS(value) => {
+                encoder.encode(&75_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64LeSImm(value) => {
+                encoder.encode(&76_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64LeU(value) => {
+                encoder.encode(&77_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64LeUImm(value) => {
+                encoder.encode(&78_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64GtS(value) => {
+                encoder.encode(&79_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64GtSImm(value) => {
+                encoder.encode(&80_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64GtU(value) => {
+                encoder.encode(&81_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64GtUImm(value) => {
+                encoder.encode(&82_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64GeS(value) => {
+                encoder.encode(&83_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64GeSImm(value) => {
+                encoder.encode(&84_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64GeU(value) => {
+                encoder.encode(&85_u16);
+                encoder.encode(value);
+            }
+            Self::BranchI64GeUImm(value) => {
+                encoder.encode(&86_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF32Eq(value) => {
+                encoder.encode(&87_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF32Ne(value) => {
+                encoder.encode(&88_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF32Lt(value) => {
+                encoder.encode(&89_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF32Le(value) => {
+                encoder.encode(&90_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF32Gt(value) => {
+                encoder.encode(&91_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF32Ge(value) => {
+                encoder.encode(&92_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF64Eq(value) => {
+                encoder.encode(&93_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF64Ne(value) => {
+                encoder.encode(&94_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF64Lt(value) => {
+                encoder.encode(&95_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF64Le(value) => {
+                encoder.encode(&96_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF64Gt(value) => {
+                encoder.encode(&97_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::BranchF64Ge(value) => {
+                encoder.encode(&98_u16);
+                encoder.encode(value);
+            }
+            Self::BranchTable { index, len_targets } => {
+                encoder.encode(&99_u16);
+                encoder.encode(index);
+                encoder.encode(len_targets);
+            }
+            Self::BranchTableSparse { index, len_runs } => {
+                encoder.encode(&100_u16);
+                encoder.encode(index);
+                encoder.encode(len_runs);
+            }
+            Self::Copy { result, value } => {
+                encoder.encode(&101_u16);
+                encoder.encode(result);
+                encoder.encode(value);
+            }
+            Self::Copy2 { results, values } => {
+                encoder.encode(&102_u16);
+                encoder.encode(results);
+                encoder.encode(values);
+            }
+            Self::CopyImm32 { result, value } => {
+                encoder.encode(&103_u16);
+                encoder.encode(result);
+                encoder.encode(value);
+            }
+            Self::CopyI64Imm32 { result, value } => {
+                encoder.encode(&104_u16);
+                encoder.encode(result);
+                encoder.encode(value);
+            }
+            Self::CopyF64Imm32 { result, value } => {
+                encoder.encode(&105_u16);
+                encoder.encode(result);
+                encoder.encode(value);
+            }
+            Self::CopySpan {
+                results,
+                values,
+                len,
+            } => {
+                encoder.encode(&106_u16);
+                encoder.encode(results);
+                encoder.encode(values);
+                encoder.encode(len);
+            }
+            Self::CopySpanNonOverlapping {
+                results,
+                values,
+                len,
+            } => {
+                encoder.encode(&107_u16);
+                encoder.encode(results);
+                encoder.encode(values);
+                encoder.encode(len);
+            }
+            Self::CopyMany { results, values } => {
+                encoder.encode(&108_u16);
+                encoder.encode(results);
+                encoder.encode(values);
+            }
+            Self::CopyManyNonOverlapping { results, values } => {
+                encoder.encode(&109_u16);
+                encoder.encode(results);
+                encoder.encode(values);
+            }
+            Self::ReturnCallInternal0 { func } => {
+                encoder.encode(&110_u16);
+                encoder.encode(func);
+            }
+            Self::ReturnCallInternal { func } => {
+                encoder.encode(&111_u16);
+                encoder.encode(func);
+            }
+            Self::ReturnCallImported0 { func } => {
+                encoder.encode(&112_u16);
+                encoder.encode(func);
+            }
+            Self::ReturnCallImported { func } => {
+                encoder.encode(&113_u16);
+                encoder.encode(func);
+            }
+            Self::ReturnCallIndirect0 { func_type } => {
+                encoder.encode(&114_u16);
+                encoder.encode(func_type);
+            }
+            Self::ReturnCallIndirect { func_type } => {
+                encoder.encode(&115_u16);
+                encoder.encode(func_type);
+            }
+            Self::CallInternal0 { results, func } => {
+                encoder.encode(&116_u16);
+                encoder.encode(results);
+                encoder.encode(func);
+            }
+            Self::CallInternal { results, func } => {
+                encoder.encode(&117_u16);
+                encoder.encode(results);
+                encoder.encode(func);
+            }
+            Self::CallImported0 { results, func } => {
+                encoder.encode(&118_u16);
+                encoder.encode(results);
+                encoder.encode(func);
+            }
+            Self::CallImported { results, func } => {
+                encoder.encode(&119_u16);
+                encoder.encode(results);
+                encoder.encode(func);
+            }
+            Self::CallIntrinsic0 { results, func } => {
+                encoder.encode(&120_u16);
+                encoder.encode(results);
+                encoder.encode(func);
+            }
+            Self::CallIntrinsic { results, func } => {
+                encoder.encode(&121_u16);
+                encoder.encode(results);
+                encoder.encode(func);
+            }
+            Self::CallIndirect0 { results, func_type } => {
+                encoder.encode(&122_u16);
+                encoder.encode(results);
+                encoder.encode(func_type);
+            }
+            Self::CallIndirect { results, func_type } => {
+                encoder.encode(&123_u16);
+                encoder.encode(results);
+                encoder.encode(func_type);
+            }
+            Self::Select {
+                result,
+                condition,
+                lhs,
+            } => {
+                encoder.encode(&124_u16);
+                encoder.encode(result);
+                encoder.encode(condition);
+                encoder.encode(lhs);
+            }
+            Self::SelectRev {
+                result,
+                condition,
+                rhs,
+            } => {
+                encoder.encode(&125_u16);
+                encoder.encode(result);
+                encoder.encode(condition);
+                encoder.encode(rhs);
+            }
+            Self::SelectImm32 {
+                result_or_condition,
+                lhs_or_rhs,
+            } => {
+                encoder.encode(&126_u16);
+                encoder.encode(result_or_condition);
+                encoder.encode(lhs_or_rhs);
+            }
+            Self::SelectI64Imm32 {
+                result_or_condition,
+                lhs_or_rhs,
+            } => {
+                encoder.encode(&127_u16);
+                encoder.encode(result_or_condition);
+                encoder.encode(lhs_or_rhs);
+            }
+            Self::SelectF64Imm32 {
+                result_or_condition,
+                lhs_or_rhs,
+            } => {
+                encoder.encode(&128_u16);
+                encoder.encode(result_or_condition);
+                encoder.encode(lhs_or_rhs);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::RefFunc { result, func } => {
+                encoder.encode(&129_u16);
+                encoder.encode(result);
+                encoder.encode(func);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::TableGet { result, index } => {
+                encoder.encode(&130_u16);
+                encoder.encode(result);
+                encoder.encode(index);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::TableGetImm { result, index } => {
+                encoder.encode(&131_u16);
+                encoder.encode(result);
+                encoder.encode(index);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::TableSize { result, table } => {
+                encoder.encode(&132_u16);
+                encoder.encode(result);
+                encoder.encode(table);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::TableSet { index, value } => {
+                encoder.encode(&133_u16);
+                encoder.encode(index);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::TableSetAt { index, value } => {
+                encoder.encode(&134_u16);
+                encoder.encode(index);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableCopy { dst, src, len } => {
+                encoder.encode(&135_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableCopyTo { dst, src, len } => {
+                encoder.encode(&136_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableCopyFrom { dst, src, len } => {
+                encoder.encode(&137_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableCopyFromTo { dst, src, len } => {
+                encoder.encode(&138_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableCopyExact { dst, src, len } => {
+                encoder.encode(&139_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableCopyToExact { dst, src, len } => {
+                encoder.encode(&140_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableCopyFromExact { dst, src, len } => {
+                encoder.encode(&141_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableCopyFromToExact { dst, src, len } => {
+                encoder.encode(&142_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableInit { dst, src, len } => {
+                encoder.encode(&143_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableInitTo { dst, src, len } => {
+                encoder.encode(&144_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableInitFrom { dst, src, len } => {
+                encoder.encode(&145_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableInitFromTo { dst, src, len } => {
+                encoder.encode(&146_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableInitExact { dst, src, len } => {
+                encoder.encode(&147_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableInitToExact { dst, src, len } => {
+                encoder.encode(&148_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableInitFromExact { dst, src, len } => {
+                encoder.encode(&149_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::TableInitFromToExact { dst, src, len } => {
+                encoder.encode(&150_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::TableFill { dst, len, value } => {
+                encoder.encode(&151_u16);
+                encoder.encode(dst);
+                encoder.encode(len);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::TableFillAt { dst, len, value } => {
+                encoder.encode(&152_u16);
+                encoder.encode(dst);
+                encoder.encode(len);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::TableFillExact { dst, len, value } => {
+                encoder.encode(&153_u16);
+                encoder.encode(dst);
+                encoder.encode(len);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::TableFillAtExact { dst, len, value } => {
+                encoder.encode(&154_u16);
+                encoder.encode(dst);
+                encoder.encode(len);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::TableGrow {
+                result,
+                delta,
+                value,
+            } => {
+                encoder.encode(&155_u16);
+                encoder.encode(result);
+                encoder.encode(delta);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "reference-types")]
+            Self::TableGrowImm {
+                result,
+                delta,
+                value,
+            } => {
+                encoder.encode(&156_u16);
+                encoder.encode(result);
+                encoder.encode(delta);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "bulk-table")]
+            Self::ElemDrop(value) => {
+                encoder.encode(&157_u16);
+                encoder.encode(value);
+            }
+            Self::DataDrop(value) => {
+                encoder.encode(&158_u16);
+                encoder.encode(value);
+            }
+            Self::MemorySize { result } => {
+                encoder.encode(&159_u16);
+                encoder.encode(result);
+            }
+            Self::MemoryGrow { result, delta } => {
+                encoder.encode(&160_u16);
+                encoder.encode(result);
+                encoder.encode(delta);
+            }
+            Self::MemoryGrowBy { result, delta } => {
+                encoder.encode(&161_u16);
+                encoder.encode(result);
+                encoder.encode(delta);
+            }
+            Self::MemoryCopy { dst, src, len } => {
+                encoder.encode(&162_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryCopyTo { dst, src, len } => {
+                encoder.encode(&163_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryCopyFrom { dst, src, len } => {
+                encoder.encode(&164_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryCopyFromTo { dst, src, len } => {
+                encoder.encode(&165_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryCopyExact { dst, src, len } => {
+                encoder.encode(&166_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryCopyToExact { dst, src, len } => {
+                encoder.encode(&167_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryCopyFromExact { dst, src, len } => {
+                encoder.encode(&168_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryCopyFromToExact { dst, src, len } => {
+                encoder.encode(&169_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryFill { dst, value, len } => {
+                encoder.encode(&170_u16);
+                encoder.encode(dst);
+                encoder.encode(value);
+                encoder.encode(len);
+            }
+            Self::MemoryFillAt { dst, value, len } => {
+                encoder.encode(&171_u16);
+                encoder.encode(dst);
+                encoder.encode(value);
+                encoder.encode(len);
+            }
+            Self::MemoryFillImm { dst, value, len } => {
+                encoder.encode(&172_u16);
+                encoder.encode(dst);
+                encoder.encode(value);
+                encoder.encode(len);
+            }
+            Self::MemoryFillExact { dst, value, len } => {
+                encoder.encode(&173_u16);
+                encoder.encode(dst);
+                encoder.encode(value);
+                encoder.encode(len);
+            }
+            Self::MemoryFillAtImm { dst, value, len } => {
+                encoder.encode(&174_u16);
+                encoder.encode(dst);
+                encoder.encode(value);
+                encoder.encode(len);
+            }
+            Self::MemoryFillAtExact { dst, value, len } => {
+                encoder.encode(&175_u16);
+                encoder.encode(dst);
+                encoder.encode(value);
+                encoder.encode(len);
+            }
+            Self::MemoryFillImmExact { dst, value, len } => {
+                encoder.encode(&176_u16);
+                encoder.encode(dst);
+                encoder.encode(value);
+                encoder.encode(len);
+            }
+            Self::MemoryFillAtImmExact { dst, value, len } => {
+                encoder.encode(&177_u16);
+                encoder.encode(dst);
+                encoder.encode(value);
+                encoder.encode(len);
+            }
+            Self::MemoryInit { dst, src, len } => {
+                encoder.encode(&178_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryInitTo { dst, src, len } => {
+                encoder.encode(&179_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryInitFrom { dst, src, len } => {
+                encoder.encode(&180_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryInitFromTo { dst, src, len } => {
+                encoder.encode(&181_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryInitExact { dst, src, len } => {
+                encoder.encode(&182_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryInitToExact { dst, src, len } => {
+                encoder.encode(&183_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryInitFromExact { dst, src, len } => {
+                encoder.encode(&184_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::MemoryInitFromToExact { dst, src, len } => {
+                encoder.encode(&185_u16);
+                encoder.encode(dst);
+                encoder.encode(src);
+                encoder.encode(len);
+            }
+            Self::GlobalGet { result, global } => {
+                encoder.encode(&186_u16);
+                encoder.encode(result);
+                encoder.encode(global);
+            }
+            Self::GlobalSet { global, input } => {
+                encoder.encode(&187_u16);
+                encoder.encode(global);
+                encoder.encode(input);
+            }
+            Self::GlobalSetI32Imm16 { global, input } => {
+                encoder.encode(&188_u16);
+                encoder.encode(global);
+                encoder.encode(input);
+            }
+            Self::GlobalSetI64Imm16 { global, input } => {
+                encoder.encode(&189_u16);
+                encoder.encode(global);
+                encoder.encode(input);
+            }
+            Self::I32Load(value) => {
+                encoder.encode(&190_u16);
+                encoder.encode(value);
+            }
+            Self::I32LoadAt(value) => {
+                encoder.encode(&191_u16);
+                encoder.encode(value);
+            }
+            Self::I32LoadOffset16(value) => {
+                encoder.encode(&192_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load(value) => {
+                encoder.encode(&193_u16);
+                encoder.encode(value);
+            }
+            Self::I64LoadAt(value) => {
+                encoder.encode(&194_u16);
+                encoder.encode(value);
+            }
+            Self::I64LoadOffset16(value) => {
+                encoder.encode(&195_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Load(value) => {
+                encoder.encode(&196_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32LoadAt(value) => {
+                encoder.encode(&197_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32LoadOffset16(value) => {
+                encoder.encode(&198_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Load(value) => {
+                encoder.encode(&199_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64LoadAt(value) => {
+                encoder.encode(&200_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64LoadOffset16(value) => {
+                encoder.encode(&201_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load8s(value) => {
+                encoder.encode(&202_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load8sAt(value) => {
+                encoder.encode(&203_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load8sOffset16(value) => {
+                encoder.encode(&204_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load8u(value) => {
+                encoder.encode(&205_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load8uAt(value) => {
+                encoder.encode(&206_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load8uOffset16(value) => {
+                encoder.encode(&207_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load16s(value) => {
+                encoder.encode(&208_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load16sAt(value) => {
+                encoder.encode(&209_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load16sOffset16(value) => {
+                encoder.encode(&210_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load16u(value) => {
+                encoder.encode(&211_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load16uAt(value) => {
+                encoder.encode(&212_u16);
+                encoder.encode(value);
+            }
+            Self::I32Load16uOffset16(value) => {
+                encoder.encode(&213_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load8s(value) => {
+                encoder.encode(&214_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load8sAt(value) => {
+                encoder.encode(&215_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load8sOffset16(value) => {
+                encoder.encode(&216_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load8u(value) => {
+                encoder.encode(&217_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load8uAt(value) => {
+                encoder.encode(&218_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load8uOffset16(value) => {
+                encoder.encode(&219_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load16s(value) => {
+                encoder.encode(&220_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load16sAt(value) => {
+                encoder.encode(&221_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load16sOffset16(value) => {
+                encoder.encode(&222_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load16u(value) => {
+                encoder.encode(&223_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load16uAt(value) => {
+                encoder.encode(&224_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load16uOffset16(value) => {
+                encoder.encode(&225_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load32s(value) => {
+                encoder.encode(&226_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load32sAt(value) => {
+                encoder.encode(&227_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load32sOffset16(value) => {
+                encoder.encode(&228_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load32u(value) => {
+                encoder.encode(&229_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load32uAt(value) => {
+                encoder.encode(&230_u16);
+                encoder.encode(value);
+            }
+            Self::I64Load32uOffset16(value) => {
+                encoder.encode(&231_u16);
+                encoder.encode(value);
+            }
+            Self::I32Store(value) => {
+                encoder.encode(&232_u16);
+                encoder.encode(value);
+            }
+            Self::I32StoreOffset16(value) => {
+                encoder.encode(&233_u16);
+                encoder.encode(value);
+            }
+            Self::I32StoreOffset16Imm16(value) => {
+                encoder.encode(&234_u16);
+                encoder.encode(value);
+            }
+            Self::I32StoreAt(value) => {
+                encoder.encode(&235_u16);
+                encoder.encode(value);
+            }
+            Self::I32StoreAtImm16(value) => {
+                encoder.encode(&236_u16);
+                encoder.encode(value);
+            }
+            Self::I32Store8(value) => {
+                encoder.encode(&237_u16);
+                encoder.encode(value);
+            }
+            Self::I32Store8Offset16(value) => {
+                encoder.encode(&238_u16);
+                encoder.encode(value);
+            }
+            Self::I32Store8Offset16Imm(value) => {
+                encoder.encode(&239_u16);
+                encoder.encode(value);
+            }
+            Self::I32Store8At(value) => {
+                encoder.encode(&240_u16);
+                encoder.encode(value);
+            }
+            Self::I32Store8AtImm(value) => {
+                encoder.encode(&241_u16);
+                encoder.encode(value);
+            }
+            Self::I32Store16(value) => {
+                encoder.encode(&242_u16);
+                encoder.encode(value);
+            }
+            Self::I32Store16Offset16(value) => {
+                encoder.encode(&243_u16);
+                encoder.encode(value);
+            }
+            Self::I32Store16Offset16Imm(value) => {
+                encoder.encode(&244_u16);
+                encoder.encode(value);
+            }
+            Self::I32Store16At(value) => {
+                encoder.encode(&245_u16);
+                encoder.encode(value);
+            }
+            Self::I32Store16AtImm(value) => {
+                encoder.encode(&246_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store(value) => {
+                encoder.encode(&247_u16);
+                encoder.encode(value);
+            }
+            Self::I64StoreOffset16(value) => {
+                encoder.encode(&248_u16);
+                encoder.encode(value);
+            }
+            Self::I64StoreOffset16Imm16(value) => {
+                encoder.encode(&249_u16);
+                encoder.encode(value);
+            }
+            Self::I64StoreAt(value) => {
+                encoder.encode(&250_u16);
+                encoder.encode(value);
+            }
+            Self::I64StoreAtImm16(value) => {
+                encoder.encode(&251_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store8(value) => {
+                encoder.encode(&252_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store8Offset16(value) => {
+                encoder.encode(&253_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store8Offset16Imm(value) => {
+                encoder.encode(&254_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store8At(value) => {
+                encoder.encode(&255_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store8AtImm(value) => {
+                encoder.encode(&256_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store16(value) => {
+                encoder.encode(&257_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store16Offset16(value) => {
+                encoder.encode(&258_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store16Offset16Imm(value) => {
+                encoder.encode(&259_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store16At(value) => {
+                encoder.encode(&260_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store16AtImm(value) => {
+                encoder.encode(&261_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store32(value) => {
+                encoder.encode(&262_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store32Offset16(value) => {
+                encoder.encode(&263_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store32Offset16Imm16(value) => {
+                encoder.encode(&264_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store32At(value) => {
+                encoder.encode(&265_u16);
+                encoder.encode(value);
+            }
+            Self::I64Store32AtImm16(value) => {
+                encoder.encode(&266_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Store(value) => {
+                encoder.encode(&267_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32StoreOffset16(value) => {
+                encoder.encode(&268_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32StoreAt(value) => {
+                encoder.encode(&269_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Store(value) => {
+                encoder.encode(&270_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64StoreOffset16(value) => {
+                encoder.encode(&271_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64StoreAt(value) => {
+                encoder.encode(&272_u16);
+                encoder.encode(value);
+            }
+            Self::I32Eq(value) => {
+                encoder.encode(&273_u16);
+                encoder.encode(value);
+            }
+            Self::I32EqImm16(value) => {
+                encoder.encode(&274_u16);
+                encoder.encode(value);
+            }
+            Self::I64Eq(value) => {
+                encoder.encode(&275_u16);
+                encoder.encode(value);
+            }
+            Self::I64EqImm16(value) => {
+                encoder.encode(&276_u16);
+                encoder.encode(value);
+            }
+            Self::I32Ne(value) => {
+                encoder.encode(&277_u16);
+                encoder.encode(value);
+            }
+            Self::I32NeImm16(value) => {
+                encoder.encode(&278_u16);
+                encoder.encode(value);
+            }
+            Self::I64Ne(value) => {
+                encoder.encode(&279_u16);
+                encoder.encode(value);
+            }
+            Self::I64NeImm16(value) => {
+                encoder.encode(&280_u16);
+                encoder.encode(value);
+            }
+            Self::I32LtS(value) => {
+                encoder.encode(&281_u16);
+                encoder.encode(value);
+            }
+            Self::I32LtU(value) => {
+                encoder.encode(&282_u16);
+                encoder.encode(value);
+            }
+            Self::I32LtSImm16(value) => {
+                encoder.encode(&283_u16);
+                encoder.encode(value);
+            }
+            Self::I32LtUImm16(value) => {
+                encoder.encode(&284_u16);
+                encoder.encode(value);
+            }
+            Self::I64LtS(value) => {
+                encoder.encode(&285_u16);
+                encoder.encode(value);
+            }
+            Self::I64LtU(value) => {
+                encoder.encode(&286_u16);
+                encoder.encode(value);
+            }
+            Self::I64LtSImm16(value) => {
+                encoder.encode(&287_u16);
+                encoder.encode(value);
+            }
+            Self::I64LtUImm16(value) => {
+                encoder.encode(&288_u16);
+                encoder.encode(value);
+            }
+            Self::I32GtS(value) => {
+                encoder.encode(&289_u16);
+                encoder.encode(value);
+            }
+            Self::I32GtU(value) => {
+                encoder.encode(&290_u16);
+                encoder.encode(value);
+            }
+            Self::I32GtSImm16(value) => {
+                encoder.encode(&291_u16);
+                encoder.encode(value);
+            }
+            Self::I32GtUImm16(value) => {
+                encoder.encode(&292_u16);
+                encoder.encode(value);
+            }
+            Self::I64GtS(value) => {
+                encoder.encode(&293_u16);
+                encoder.encode(value);
+            }
+            Self::I64GtU(value) => {
+                encoder.encode(&294_u16);
+                encoder.encode(value);
+            }
+            Self::I64GtSImm16(value) => {
+                encoder.encode(&295_u16);
+                encoder.encode(value);
+            }
+            Self::I64GtUImm16(value) => {
+                encoder.encode(&296_u16);
+                encoder.encode(value);
+            }
+            Self::I32LeS(value) => {
+                encoder.encode(&297_u16);
+                encoder.encode(value);
+            }
+            Self::I32LeU(value) => {
+                encoder.encode(&298_u16);
+                encoder.encode(value);
+            }
+            Self::I32LeSImm16(value) => {
+                encoder.encode(&299_u16);
+                encoder.encode(value);
+            }
+            Self::I32LeUImm16(value) => {
+                encoder.encode(&300_u16);
+                encoder.encode(value);
+            }
+            Self::I64LeS(value) => {
+                encoder.encode(&301_u16);
+                encoder.encode(value);
+            }
+            Self::I64LeU(value) => {
+                encoder.encode(&302_u16);
+                encoder.encode(value);
+            }
+            Self::I64LeSImm16(value) => {
+                encoder.encode(&303_u16);
+                encoder.encode(value);
+            }
+            Self::I64LeUImm16(value) => {
+                encoder.encode(&304_u16);
+                encoder.encode(value);
+            }
+            Self::I32GeS(value) => {
+                encoder.encode(&305_u16);
+                encoder.encode(value);
+            }
+            Self::I32GeU(value) => {
+                encoder.encode(&306_u16);
+                encoder.encode(value);
+            }
+            Self::I32GeSImm16(value) => {
+                encoder.encode(&307_u16);
+                encoder.encode(value);
+            }
+            Self::I32GeUImm16(value) => {
+                encoder.encode(&308_u16);
+                encoder.encode(value);
+            }
+            Self::I64GeS(value) => {
+                encoder.encode(&309_u16);
+                encoder.encode(value);
+            }
+            Self::I64GeU(value) => {
+                encoder.encode(&310_u16);
+                encoder.encode(value);
+            }
+            Self::I64GeSImm16(value) => {
+                encoder.encode(&311_u16);
+                encoder.encode(value);
+            }
+            Self::I64GeUImm16(value) => {
+                encoder.encode(&312_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Eq(value) => {
+                encoder.encode(&313_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Eq(value) => {
+                encoder.encode(&314_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Ne(value) => {
+                encoder.encode(&315_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Ne(value) => {
+                encoder.encode(&316_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Lt(value) => {
+                encoder.encode(&317_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Lt(value) => {
+                encoder.encode(&318_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Le(value) => {
+                encoder.encode(&319_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Le(value) => {
+                encoder.encode(&320_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Gt(value) => {
+                encoder.encode(&321_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Gt(value) => {
+                encoder.encode(&322_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Ge(value) => {
+                encoder.encode(&323_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Ge(value) => {
+                encoder.encode(&324_u16);
+                encoder.encode(value);
+            }
+            Self::I32Clz(value) => {
+                encoder.encode(&325_u16);
+                encoder.encode(value);
+            }
+            Self::I64Clz(value) => {
+                encoder.encode(&326_u16);
+                encoder.encode(value);
+            }
+            Self::I32Ctz(value) => {
+                encoder.encode(&327_u16);
+                encoder.encode(value);
+            }
+            Self::I64Ctz(value) => {
+                encoder.encode(&328_u16);
+                encoder.encode(value);
+            }
+            Self::I32Popcnt(value) => {
+                encoder.encode(&329_u16);
+                encoder.encode(value);
+            }
+            Self::I64Popcnt(value) => {
+                encoder.encode(&330_u16);
+                encoder.encode(value);
+            }
+            Self::I32Add(value) => {
+                encoder.encode(&331_u16);
+                encoder.encode(value);
+            }
+            Self::I64Add(value) => {
+                encoder.encode(&332_u16);
+                encoder.encode(value);
+            }
+            Self::I32AddImm16(value) => {
+                encoder.encode(&333_u16);
+                encoder.encode(value);
+            }
+            Self::I64AddImm16(value) => {
+                encoder.encode(&334_u16);
+                encoder.encode(value);
+            }
+            Self::I32Sub(value) => {
+                encoder.encode(&335_u16);
+                encoder.encode(value);
+            }
+            Self::I64Sub(value) => {
+                encoder.encode(&336_u16);
+                encoder.encode(value);
+            }
+            Self::I32SubImm16(value) => {
+                encoder.encode(&337_u16);
+                encoder.encode(value);
+            }
+            Self::I64SubImm16(value) => {
+                encoder.encode(&338_u16);
+                encoder.encode(value);
+            }
+            Self::I32SubImm16Rev(value) => {
+                encoder.encode(&339_u16);
+                encoder.encode(value);
+            }
+            Self::I64SubImm16Rev(value) => {
+                encoder.encode(&340_u16);
+                encoder.encode(value);
+            }
+            Self::I32Mul(value) => {
+                encoder.encode(&341_u16);
+                encoder.encode(value);
+            }
+            Self::I64Mul(value) => {
+                encoder.encode(&342_u16);
+                encoder.encode(value);
+            }
+            Self::I32MulImm16(value) => {
+                encoder.encode(&343_u16);
+                encoder.encode(value);
+            }
+            Self::I64MulImm16(value) => {
+                encoder.encode(&344_u16);
+                encoder.encode(value);
+            }
+            Self::I32DivS(value) => {
+                encoder.encode(&345_u16);
+                encoder.encode(value);
+            }
+            Self::I64DivS(value) => {
+                encoder.encode(&346_u16);
+                encoder.encode(value);
+            }
+            Self::I32DivSImm16(value) => {
+                encoder.encode(&347_u16);
+                encoder.encode(value);
+            }
+            Self::I64DivSImm16(value) => {
+                encoder.encode(&348_u16);
+                encoder.encode(value);
+            }
+            Self::I32DivSImm16Rev(value) => {
+                encoder.encode(&349_u16);
+                encoder.encode(value);
+            }
+            Self::I64DivSImm16Rev(value) => {
+                encoder.encode(&350_u16);
+                encoder.encode(value);
+            }
+            Self::I32DivU(value) => {
+                encoder.encode(&351_u16);
+                encoder.encode(value);
+            }
+            Self::I64DivU(value) => {
+                encoder.encode(&352_u16);
+                encoder.encode(value);
+            }
+            Self::I32DivUImm16(value) => {
+                encoder.encode(&353_u16);
+                encoder.encode(value);
+            }
+            Self::I64DivUImm16(value) => {
+                encoder.encode(&354_u16);
+                encoder.encode(value);
+            }
+            Self::I32DivUImm16Rev(value) => {
+                encoder.encode(&355_u16);
+                encoder.encode(value);
+            }
+            Self::I64DivUImm16Rev(value) => {
+                encoder.encode(&356_u16);
+                encoder.encode(value);
+            }
+            Self::I32RemS(value) => {
+                encoder.encode(&357_u16);
+                encoder.encode(value);
+            }
+            Self::I64RemS(value) => {
+                encoder.encode(&358_u16);
+                encoder.encode(value);
+            }
+            Self::I32RemSImm16(value) => {
+                encoder.encode(&359_u16);
+                encoder.encode(value);
+            }
+            Self::I64RemSImm16(value) => {
+                encoder.encode(&360_u16);
+                encoder.encode(value);
+            }
+            Self::I32RemSImm16Rev(value) => {
+                encoder.encode(&361_u16);
+                encoder.encode(value);
+            }
+            Self::I64RemSImm16Rev(value) => {
+                encoder.encode(&362_u16);
+                encoder.encode(value);
+            }
+            Self::I32RemU(value) => {
+                encoder.encode(&363_u16);
+                encoder.encode(value);
+            }
+            Self::I64RemU(value) => {
+                encoder.encode(&364_u16);
+                encoder.encode(value);
+            }
+            Self::I32RemUImm16(value) => {
+                encoder.encode(&365_u16);
+                encoder.encode(value);
+            }
+            Self::I64RemUImm16(value) => {
+                encoder.encode(&366_u16);
+                encoder.encode(value);
+            }
+            Self::I32RemUImm16Rev(value) => {
+                encoder.encode(&367_u16);
+                encoder.encode(value);
+            }
+            Self::I64RemUImm16Rev(value) => {
+                encoder.encode(&368_u16);
+                encoder.encode(value);
+            }
+            Self::I32And(value) => {
+                encoder.encode(&369_u16);
+                encoder.encode(value);
+            }
+            Self::I32AndEqz(value) => {
+                encoder.encode(&370_u16);
+                encoder.encode(value);
+            }
+            Self::I32AndEqzImm16(value) => {
+                encoder.encode(&371_u16);
+                encoder.encode(value);
+            }
+            Self::I32AndImm16(value) => {
+                encoder.encode(&372_u16);
+                encoder.encode(value);
+            }
+            Self::I64And(value) => {
+                encoder.encode(&373_u16);
+                encoder.encode(value);
+            }
+            Self::I64AndImm16(value) => {
+                encoder.encode(&374_u16);
+                encoder.encode(value);
+            }
+            Self::I32Or(value) => {
+                encoder.encode(&375_u16);
+                encoder.encode(value);
+            }
+            Self::I32OrEqz(value) => {
+                encoder.encode(&376_u16);
+                encoder.encode(value);
+            }
+            Self::I32OrEqzImm16(value) => {
+                encoder.encode(&377_u16);
+                encoder.encode(value);
+            }
+            Self::I32OrImm16(value) => {
+                encoder.encode(&378_u16);
+                encoder.encode(value);
+            }
+            Self::I64Or(value) => {
+                encoder.encode(&379_u16);
+                encoder.encode(value);
+            }
+            Self::I64OrImm16(value) => {
+                encoder.encode(&380_u16);
+                encoder.encode(value);
+            }
+            Self::I32Xor(value) => {
+                encoder.encode(&381_u16);
+                encoder.encode(value);
+            }
+            Self::I32XorEqz(value) => {
+                encoder.encode(&382_u16);
+                encoder.encode(value);
+            }
+            Self::I32XorEqzImm16(value) => {
+                encoder.encode(&383_u16);
+                encoder.encode(value);
+            }
+            Self::I32XorImm16(value) => {
+                encoder.encode(&384_u16);
+                encoder.encode(value);
+            }
+            Self::I64Xor(value) => {
+                encoder.encode(&385_u16);
+                encoder.encode(value);
+            }
+            Self::I64XorImm16(value) => {
+                encoder.encode(&386_u16);
+                encoder.encode(value);
+            }
+            Self::I32Shl(value) => {
+                encoder.encode(&387_u16);
+                encoder.encode(value);
+            }
+            Self::I64Shl(value) => {
+                encoder.encode(&388_u16);
+                encoder.encode(value);
+            }
+            Self::I32ShlImm(value) => {
+                encoder.encode(&389_u16);
+                encoder.encode(value);
+            }
+            Self::I64ShlImm(value) => {
+                encoder.encode(&390_u16);
+                encoder.encode(value);
+            }
+            Self::I32ShlImm16Rev(value) => {
+                encoder.encode(&391_u16);
+                encoder.encode(value);
+            }
+            Self::I64ShlImm16Rev(value) => {
+                encoder.encode(&392_u16);
+                encoder.encode(value);
+            }
+            Self::I32ShrU(value) => {
+                encoder.encode(&393_u16);
+                encoder.encode(value);
+            }
+            Self::I64ShrU(value) => {
+                encoder.encode(&394_u16);
+                encoder.encode(value);
+            }
+            Self::I32ShrUImm(value) => {
+                encoder.encode(&395_u16);
+                encoder.encode(value);
+            }
+            Self::I64ShrUImm(value) => {
+                encoder.encode(&396_u16);
+                encoder.encode(value);
+            }
+            Self::I32ShrUImm16Rev(value) => {
+                encoder.encode(&397_u16);
+                encoder.encode(value);
+            }
+            Self::I64ShrUImm16Rev(value) => {
+                encoder.encode(&398_u16);
+                encoder.encode(value);
+            }
+            Self::I32ShrS(value) => {
+                encoder.encode(&399_u16);
+                encoder.encode(value);
+            }
+            Self::I64ShrS(value) => {
+                encoder.encode(&400_u16);
+                encoder.encode(value);
+            }
+            Self::I32ShrSImm(value) => {
+                encoder.encode(&401_u16);
+                encoder.encode(value);
+            }
+            Self::I64ShrSImm(value) => {
+                encoder.encode(&402_u16);
+                encoder.encode(value);
+            }
+            Self::I32ShrSImm16Rev(value) => {
+                encoder.encode(&403_u16);
+                encoder.encode(value);
+            }
+            Self::I64ShrSImm16Rev(value) => {
+                encoder.encode(&404_u16);
+                encoder.encode(value);
+            }
+            Self::I32Rotl(value) => {
+                encoder.encode(&405_u16);
+                encoder.encode(value);
+            }
+            Self::I64Rotl(value) => {
+                encoder.encode(&406_u16);
+                encoder.encode(value);
+            }
+            Self::I32RotlImm(value) => {
+                encoder.encode(&407_u16);
+                encoder.encode(value);
+            }
+            Self::I64RotlImm(value) => {
+                encoder.encode(&408_u16);
+                encoder.encode(value);
+            }
+            Self::I32RotlImm16Rev(value) => {
+                encoder.encode(&409_u16);
+                encoder.encode(value);
+            }
+            Self::I64RotlImm16Rev(value) => {
+                encoder.encode(&410_u16);
+                encoder.encode(value);
+            }
+            Self::I32Rotr(value) => {
+                encoder.encode(&411_u16);
+                encoder.encode(value);
+            }
+            Self::I64Rotr(value) => {
+                encoder.encode(&412_u16);
+                encoder.encode(value);
+            }
+            Self::I32RotrImm(value) => {
+                encoder.encode(&413_u16);
+                encoder.encode(value);
+            }
+            Self::I64RotrImm(value) => {
+                encoder.encode(&414_u16);
+                encoder.encode(value);
+            }
+            Self::I32RotrImm16Rev(value) => {
+                encoder.encode(&415_u16);
+                encoder.encode(value);
+            }
+            Self::I64RotrImm16Rev(value) => {
+                encoder.encode(&416_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Abs(value) => {
+                encoder.encode(&417_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Abs(value) => {
+                encoder.encode(&418_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Neg(value) => {
+                encoder.encode(&419_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Neg(value) => {
+                encoder.encode(&420_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Ceil(value) => {
+                encoder.encode(&421_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Ceil(value) => {
+                encoder.encode(&422_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Floor(value) => {
+                encoder.encode(&423_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Floor(value) => {
+                encoder.encode(&424_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Trunc(value) => {
+                encoder.encode(&425_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Trunc(value) => {
+                encoder.encode(&426_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Nearest(value) => {
+                encoder.encode(&427_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Nearest(value) => {
+                encoder.encode(&428_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Sqrt(value) => {
+                encoder.encode(&429_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Sqrt(value) => {
+                encoder.encode(&430_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Add(value) => {
+                encoder.encode(&431_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Add(value) => {
+                encoder.encode(&432_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Sub(value) => {
+                encoder.encode(&433_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Sub(value) => {
+                encoder.encode(&434_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Mul(value) => {
+                encoder.encode(&435_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Mul(value) => {
+                encoder.encode(&436_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Div(value) => {
+                encoder.encode(&437_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Div(value) => {
+                encoder.encode(&438_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Min(value) => {
+                encoder.encode(&439_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Min(value) => {
+                encoder.encode(&440_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Max(value) => {
+                encoder.encode(&441_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Max(value) => {
+                encoder.encode(&442_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32Copysign(value) => {
+                encoder.encode(&443_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64Copysign(value) => {
+                encoder.encode(&444_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32CopysignImm(value) => {
+                encoder.encode(&445_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64CopysignImm(value) => {
+                encoder.encode(&446_u16);
+                encoder.encode(value);
+            }
+            Self::I32WrapI64(value) => {
+                encoder.encode(&447_u16);
+                encoder.encode(value);
+            }
+            Self::I64ExtendI32S(value) => {
+                encoder.encode(&448_u16);
+                encoder.encode(value);
+            }
+            Self::I64ExtendI32U(value) => {
+                encoder.encode(&449_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I32TruncF32S(value) => {
+                encoder.encode(&450_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I32TruncF32U(value) => {
+                encoder.encode(&451_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I32TruncF64S(value) => {
+                encoder.encode(&452_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I32TruncF64U(value) => {
+                encoder.encode(&453_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I64TruncF32S(value) => {
+                encoder.encode(&454_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I64TruncF32U(value) => {
+                encoder.encode(&455_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I64TruncF64S(value) => {
+                encoder.encode(&456_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I64TruncF64U(value) => {
+                encoder.encode(&457_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I32TruncSatF32S(value) => {
+                encoder.encode(&458_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I32TruncSatF32U(value) => {
+                encoder.encode(&459_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I32TruncSatF64S(value) => {
+                encoder.encode(&460_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I32TruncSatF64U(value) => {
+                encoder.encode(&461_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I64TruncSatF32S(value) => {
+                encoder.encode(&462_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I64TruncSatF32U(value) => {
+                encoder.encode(&463_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I64TruncSatF64S(value) => {
+                encoder.encode(&464_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::I64TruncSatF64U(value) => {
+                encoder.encode(&465_u16);
+                encoder.encode(value);
+            }
+            Self::I32Extend8S(value) => {
+                encoder.encode(&466_u16);
+                encoder.encode(value);
+            }
+            Self::I32Extend16S(value) => {
+                encoder.encode(&467_u16);
+                encoder.encode(value);
+            }
+            Self::I64Extend8S(value) => {
+                encoder.encode(&468_u16);
+                encoder.encode(value);
+            }
+            Self::I64Extend16S(value) => {
+                encoder.encode(&469_u16);
+                encoder.encode(value);
+            }
+            Self::I64Extend32S(value) => {
+                encoder.encode(&470_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32DemoteF64(value) => {
+                encoder.encode(&471_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64PromoteF32(value) => {
+                encoder.encode(&472_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32ConvertI32S(value) => {
+                encoder.encode(&473_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32ConvertI32U(value) => {
+                encoder.encode(&474_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32ConvertI64S(value) => {
+                encoder.encode(&475_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F32ConvertI64U(value) => {
+                encoder.encode(&476_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64ConvertI32S(value) => {
+                encoder.encode(&477_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64ConvertI32U(value) => {
+                encoder.encode(&478_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64ConvertI64S(value) => {
+                encoder.encode(&479_u16);
+                encoder.encode(value);
+            }
+            #[cfg(feature = "float")]
+            Self::F64ConvertI64U(value) => {
+                encoder.encode(&480_u16);
+                encoder.encode(value);
+            }
+        }
+    }
+}
+
+impl Decode for Instruction {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let instr = match decoder.decode::<u16>()? {
+            #[cfg(not(all(not(feature = "reference-types"), not(feature = "bulk-table"))))]
+            0 => Self::TableIdx(decoder.decode()?),
+            1 => Self::DataSegmentIdx(decoder.decode()?),
+            #[cfg(feature = "bulk-table")]
+            2 => Self::ElementSegmentIdx(decoder.decode()?),
+            3 => Self::Const32(decoder.decode()?),
+            4 => Self::I64Const32(decoder.decode()?),
+            5 => Self::F64Const32(decoder.decode()?),
+            6 => Self::Register(decoder.decode()?),
+            7 => Self::Register2(decoder.decode()?),
+            8 => Self::Register3(decoder.decode()?),
+            9 => Self::RegisterList(decoder.decode()?),
+            10 => Self::CallIndirectParams(decoder.decode()?),
+            11 => Self::CallIndirectParamsImm16(decoder.decode()?),
+            12 => Self::Trap(decoder.decode()?),
+            13 => Self::ConsumeFuel(decoder.decode()?),
+            14 => Self::ConsumeInstrs(decoder.decode()?),
+            15 => Self::CheckEpoch,
+            16 => Self::Return,
+            17 => Self::ReturnReg {
+                value: decoder.decode()?,
+            },
+            18 => Self::ReturnReg2 {
+                values: decoder.decode()?,
+            },
+            19 => Self::ReturnReg3 {
+                values: decoder.decode()?,
+            },
+            20 => Self::ReturnImm32 {
+                value: decoder.decode()?,
+            },
+            21 => Self::ReturnI64Imm32 {
+                value: decoder.decode()?,
+            },
+            22 => Self::ReturnF64Imm32 {
+                value: decoder.decode()?,
+            },
+            23 => Self::ReturnSpan {
+                values: decoder.decode()?,
+            },
+            24 => Self::ReturnMany {
+                values: decoder.decode()?,
+            },
+            25 => Self::ReturnNez {
+                condition: decoder.decode()?,
+            },
+            26 => Self::ReturnNezReg {
+                condition: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            27 => Self::ReturnNezReg2 {
+                condition: decoder.decode()?,
+                values: decoder.decode()?,
+            },
+            28 => Self::ReturnNezImm32 {
+                condition: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            29 => Self::ReturnNezI64Imm32 {
+                condition: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            30 => Self::ReturnNezF64Imm32 {
+                condition: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            31 => Self::ReturnNezSpan {
+                condition: decoder.decode()?,
+                values: decoder.decode()?,
+            },
+            32 => Self::ReturnNezMany {
+                condition: decoder.decode()?,
+                values: decoder.decode()?,
+            },
+            33 => Self::Branch {
+                offset: decoder.decode()?,
+            },
+            34 => Self::BranchCmpFallback {
+                lhs: decoder.decode()?,
+                rhs: decoder.decode()?,
+                params: decoder.decode()?,
+            },
+            35 => Self::BranchI32And(decoder.decode()?),
+            36 => Self::BranchI32AndImm(decoder.decode()?),
+            37 => Self::BranchI32Or(decoder.decode()?),
+            38 => Self::BranchI32OrImm(decoder.decode()?),
+            39 => Self::BranchI32Xor(decoder.decode()?),
+            40 => Self::BranchI32XorImm(decoder.decode()?),
+            41 => Self::BranchI32AndEqz(decoder.decode()?),
+            42 => Self::BranchI32AndEqzImm(decoder.decode()?),
+            43 => Self::BranchI32OrEqz(decoder.decode()?),
+            44 => Self::BranchI32OrEqzImm(decoder.decode()?),
+            45 => Self::BranchI32XorEqz(decoder.decode()?),
+            46 => Self::BranchI32XorEqzImm(decoder.decode()?),
+            47 => Self::BranchI32Eq(decoder.decode()?),
+            48 => Self::BranchI32EqImm(decoder.decode()?),
+            49 => Self::BranchI32Ne(decoder.decode()?),
+            50 => Self::BranchI32NeImm(decoder.decode()?),
+            51 => Self::BranchI32LtS(decoder.decode()?),
+            52 => Self::BranchI32LtSImm(decoder.decode()?),
+            53 => Self::BranchI32LtU(decoder.decode()?),
+            54 => Self::BranchI32LtUImm(decoder.decode()?),
+            55 => Self::BranchI32LeS(decoder.decode()?),
+            56 => Self::BranchI32LeSImm(decoder.decode()?),
+            57 => Self::BranchI32LeU(decoder.decode()?),
+            58 => Self::BranchI32LeUImm(decoder.decode()?),
+            59 => Self::BranchI32GtS(decoder.decode()?),
+            60 => Self::BranchI32GtSImm(decoder.decode()?),
+            61 => Self::BranchI32GtU(decoder.decode()?),
+            62 => Self::BranchI32GtUImm(decoder.decode()?),
+            63 => Self::BranchI32GeS(decoder.decode()?),
+            64 => Self::BranchI32GeSImm(decoder.decode()?),
+            65 => Self::BranchI32GeU(decoder.decode()?),
+            66 => Self::BranchI32GeUImm(decoder.decode()?),
+            67 => Self::BranchI64Eq(decoder.decode()?),
+            68 => Self::BranchI64EqImm(decoder.decode()?),
+            69 => Self::BranchI64Ne(decoder.decode()?),
+            70 => Self::BranchI64NeImm(decoder.decode()?),
+            71 => Self::BranchI64LtS(decoder.decode()?),
+            72 => Self::BranchI64LtSImm(decoder.decode()?),
+            73 => Self::BranchI64LtU(decoder.decode()?),
+            74 => Self::BranchI64LtUImm(decoder.decode()?),
+            75 => Self::BranchI64LeS(decoder.decode()?),
+            76 => Self::BranchI64LeSImm(decoder.decode()?),
+            77 => Self::BranchI64LeU(decoder.decode()?),
+            78 => Self::BranchI64LeUImm(decoder.decode()?),
+            79 => Self::BranchI64GtS(decoder.decode()?),
+            80 => Self::BranchI64GtSImm(decoder.decode()?),
+            81 => Self::BranchI64GtU(decoder.decode()?),
+            82 => Self::BranchI64GtUImm(decoder.decode()?),
+            83 => Self::BranchI64GeS(decoder.decode()?),
+            84 => Self::BranchI64GeSImm(decoder.decode()?),
+            85 => Self::BranchI64GeU(decoder.decode()?),
+            86 => Self::BranchI64GeUImm(decoder.decode()?),
+            #[cfg(feature = "float")]
+            87 => Self::BranchF32Eq(decoder.decode()?),
+            #[cfg(feature = "float")]
+            88 => Self::BranchF32Ne(decoder.decode()?),
+            #[cfg(feature = "float")]
+            89 => Self::BranchF32Lt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            90 => Self::BranchF32Le(decoder.decode()?),
+            #[cfg(feature = "float")]
+            91 => Self::BranchF32Gt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            92 => Self::BranchF32Ge(decoder.decode()?),
+            #[cfg(feature = "float")]
+            93 => Self::BranchF64Eq(decoder.decode()?),
+            #[cfg(feature = "float")]
+            94 => Self::BranchF64Ne(decoder.decode()?),
+            #[cfg(feature = "float")]
+            95 => Self::BranchF64Lt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            96 => Self::BranchF64Le(decoder.decode()?),
+            #[cfg(feature = "float")]
+            97 => Self::BranchF64Gt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            98 => Self::BranchF64Ge(decoder.decode()?),
+            99 => Self::BranchTable {
+                index: decoder.decode()?,
+                len_targets: decoder.decode()?,
+            },
+            100 => Self::BranchTableSparse {
+                index: decoder.decode()?,
+                len_runs: decoder.decode()?,
+            },
+            101 => Self::Copy {
+                result: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            102 => Self::Copy2 {
+                results: decoder.decode()?,
+                values: decoder.decode()?,
+            },
+            103 => Self::CopyImm32 {
+                result: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            104 => Self::CopyI64Imm32 {
+                result: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            105 => Self::CopyF64Imm32 {
+                result: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            106 => Self::CopySpan {
+                results: decoder.decode()?,
+                values: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            107 => Self::CopySpanNonOverlapping {
+                results: decoder.decode()?,
+                values: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            108 => Self::CopyMany {
+                results: decoder.decode()?,
+                values: decoder.decode()?,
+            },
+            109 => Self::CopyManyNonOverlapping {
+                results: decoder.decode()?,
+                values: decoder.decode()?,
+            },
+            110 => Self::ReturnCallInternal0 {
+                func: decoder.decode()?,
+            },
+            111 => Self::ReturnCallInternal {
+                func: decoder.decode()?,
+            },
+            112 => Self::ReturnCallImported0 {
+                func: decoder.decode()?,
+            },
+            113 => Self::ReturnCallImported {
+                func: decoder.decode()?,
+            },
+            114 => Self::ReturnCallIndirect0 {
+                func_type: decoder.decode()?,
+            },
+            115 => Self::ReturnCallIndirect {
+                func_type: decoder.decode()?,
+            },
+            116 => Self::CallInternal0 {
+                results: decoder.decode()?,
+                func: decoder.decode()?,
+            },
+            117 => Self::CallInternal {
+                results: decoder.decode()?,
+                func: decoder.decode()?,
+            },
+            118 => Self::CallImported0 {
+                results: decoder.decode()?,
+                func: decoder.decode()?,
+            },
+            119 => Self::CallImported {
+                results: decoder.decode()?,
+                func: decoder.decode()?,
+            },
+            120 => Self::CallIntrinsic0 {
+                results: decoder.decode()?,
+                func: decoder.decode()?,
+            },
+            121 => Self::CallIntrinsic {
+                results: decoder.decode()?,
+                func: decoder.decode()?,
+            },
+            122 => Self::CallIndirect0 {
+                results: decoder.decode()?,
+                func_type: decoder.decode()?,
+            },
+            123 => Self::CallIndirect {
+                results: decoder.decode()?,
+                func_type: decoder.decode()?,
+            },
+            124 => Self::Select {
+                result: decoder.decode()?,
+                condition: decoder.decode()?,
+                lhs: decoder.decode()?,
+            },
+            125 => Self::SelectRev {
+                result: decoder.decode()?,
+                condition: decoder.decode()?,
+                rhs: decoder.decode()?,
+            },
+            126 => Self::SelectImm32 {
+                result_or_condition: decoder.decode()?,
+                lhs_or_rhs: decoder.decode()?,
+            },
+            127 => Self::SelectI64Imm32 {
+                result_or_condition: decoder.decode()?,
+                lhs_or_rhs: decoder.decode()?,
+            },
+            128 => Self::SelectF64Imm32 {
+                result_or_condition: decoder.decode()?,
+                lhs_or_rhs: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            129 => Self::RefFunc {
+                result: decoder.decode()?,
+                func: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            130 => Self::TableGet {
+                result: decoder.decode()?,
+                index: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            131 => Self::TableGetImm {
+                result: decoder.decode()?,
+                index: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            132 => Self::TableSize {
+                result: decoder.decode()?,
+                table: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            133 => Self::TableSet {
+                index: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            134 => Self::TableSetAt {
+                index: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            135 => Self::TableCopy {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            136 => Self::TableCopyTo {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            137 => Self::TableCopyFrom {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            138 => Self::TableCopyFromTo {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            139 => Self::TableCopyExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            140 => Self::TableCopyToExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            141 => Self::TableCopyFromExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            142 => Self::TableCopyFromToExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            143 => Self::TableInit {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            144 => Self::TableInitTo {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            145 => Self::TableInitFrom {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            146 => Self::TableInitFromTo {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            147 => Self::TableInitExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            148 => Self::TableInitToExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            149 => Self::TableInitFromExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            150 => Self::TableInitFromToExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            151 => Self::TableFill {
+                dst: decoder.decode()?,
+                len: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            152 => Self::TableFillAt {
+                dst: decoder.decode()?,
+                len: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            153 => Self::TableFillExact {
+                dst: decoder.decode()?,
+                len: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            154 => Self::TableFillAtExact {
+                dst: decoder.decode()?,
+                len: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            155 => Self::TableGrow {
+                result: decoder.decode()?,
+                delta: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            #[cfg(feature = "reference-types")]
+            156 => Self::TableGrowImm {
+                result: decoder.decode()?,
+                delta: decoder.decode()?,
+                value: decoder.decode()?,
+            },
+            #[cfg(feature = "bulk-table")]
+            157 => Self::ElemDrop(decoder.decode()?),
+            158 => Self::DataDrop(decoder.decode()?),
+            159 => Self::MemorySize {
+                result: decoder.decode()?,
+            },
+            160 => Self::MemoryGrow {
+                result: decoder.decode()?,
+                delta: decoder.decode()?,
+            },
+            161 => Self::MemoryGrowBy {
+                result: decoder.decode()?,
+                delta: decoder.decode()?,
+            },
+            162 => Self::MemoryCopy {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            163 => Self::MemoryCopyTo {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            164 => Self::MemoryCopyFrom {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            165 => Self::MemoryCopyFromTo {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            166 => Self::MemoryCopyExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            167 => Self::MemoryCopyToExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            168 => Self::MemoryCopyFromExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            169 => Self::MemoryCopyFromToExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            170 => Self::MemoryFill {
+                dst: decoder.decode()?,
+                value: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            171 => Self::MemoryFillAt {
+                dst: decoder.decode()?,
+                value: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            172 => Self::MemoryFillImm {
+                dst: decoder.decode()?,
+                value: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            173 => Self::MemoryFillExact {
+                dst: decoder.decode()?,
+                value: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            174 => Self::MemoryFillAtImm {
+                dst: decoder.decode()?,
+                value: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            175 => Self::MemoryFillAtExact {
+                dst: decoder.decode()?,
+                value: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            176 => Self::MemoryFillImmExact {
+                dst: decoder.decode()?,
+                value: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            177 => Self::MemoryFillAtImmExact {
+                dst: decoder.decode()?,
+                value: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            178 => Self::MemoryInit {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            179 => Self::MemoryInitTo {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            180 => Self::MemoryInitFrom {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            181 => Self::MemoryInitFromTo {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            182 => Self::MemoryInitExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            183 => Self::MemoryInitToExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            184 => Self::MemoryInitFromExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            185 => Self::MemoryInitFromToExact {
+                dst: decoder.decode()?,
+                src: decoder.decode()?,
+                len: decoder.decode()?,
+            },
+            186 => Self::GlobalGet {
+                result: decoder.decode()?,
+                global: decoder.decode()?,
+            },
+            187 => Self::GlobalSet {
+                global: decoder.decode()?,
+                input: decoder.decode()?,
+            },
+            188 => Self::GlobalSetI32Imm16 {
+                global: decoder.decode()?,
+                input: decoder.decode()?,
+            },
+            189 => Self::GlobalSetI64Imm16 {
+                global: decoder.decode()?,
+                input: decoder.decode()?,
+            },
+            190 => Self::I32Load(decoder.decode()?),
+            191 => Self::I32LoadAt(decoder.decode()?),
+            192 => Self::I32LoadOffset16(decoder.decode()?),
+            193 => Self::I64Load(decoder.decode()?),
+            194 => Self::I64LoadAt(decoder.decode()?),
+            195 => Self::I64LoadOffset16(decoder.decode()?),
+            #[cfg(feature = "float")]
+            196 => Self::F32Load(decoder.decode()?),
+            #[cfg(feature = "float")]
+            197 => Self::F32LoadAt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            198 => Self::F32LoadOffset16(decoder.decode()?),
+            #[cfg(feature = "float")]
+            199 => Self::F64Load(decoder.decode()?),
+            #[cfg(feature = "float")]
+            200 => Self::F64LoadAt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            201 => Self::F64LoadOffset16(decoder.decode()?),
+            202 => Self::I32Load8s(decoder.decode()?),
+            203 => Self::I32Load8sAt(decoder.decode()?),
+            204 => Self::I32Load8sOffset16(decoder.decode()?),
+            205 => Self::I32Load8u(decoder.decode()?),
+            206 => Self::I32Load8uAt(decoder.decode()?),
+            207 => Self::I32Load8uOffset16(decoder.decode()?),
+            208 => Self::I32Load16s(decoder.decode()?),
+            209 => Self::I32Load16sAt(decoder.decode()?),
+            210 => Self::I32Load16sOffset16(decoder.decode()?),
+            211 => Self::I32Load16u(decoder.decode()?),
+            212 => Self::I32Load16uAt(decoder.decode()?),
+            213 => Self::I32Load16uOffset16(decoder.decode()?),
+            214 => Self::I64Load8s(decoder.decode()?),
+            215 => Self::I64Load8sAt(decoder.decode()?),
+            216 => Self::I64Load8sOffset16(decoder.decode()?),
+            217 => Self::I64Load8u(decoder.decode()?),
+            218 => Self::I64Load8uAt(decoder.decode()?),
+            219 => Self::I64Load8uOffset16(decoder.decode()?),
+            220 => Self::I64Load16s(decoder.decode()?),
+            221 => Self::I64Load16sAt(decoder.decode()?),
+            222 => Self::I64Load16sOffset16(decoder.decode()?),
+            223 => Self::I64Load16u(decoder.decode()?),
+            224 => Self::I64Load16uAt(decoder.decode()?),
+            225 => Self::I64Load16uOffset16(decoder.decode()?),
+            226 => Self::I64Load32s(decoder.decode()?),
+            227 => Self::I64Load32sAt(decoder.decode()?),
+            228 => Self::I64Load32sOffset16(decoder.decode()?),
+            229 => Self::I64Load32u(decoder.decode()?),
+            230 => Self::I64Load32uAt(decoder.decode()?),
+            231 => Self::I64Load32uOffset16(decoder.decode()?),
+            232 => Self::I32Store(decoder.decode()?),
+            233 => Self::I32StoreOffset16(decoder.decode()?),
+            234 => Self::I32StoreOffset16Imm16(decoder.decode()?),
+            235 => Self::I32StoreAt(decoder.decode()?),
+            236 => Self::I32StoreAtImm16(decoder.decode()?),
+            237 => Self::I32Store8(decoder.decode()?),
+            238 => Self::I32Store8Offset16(decoder.decode()?),
+            239 => Self::I32Store8Offset16Imm(decoder.decode()?),
+            240 => Self::I32Store8At(decoder.decode()?),
+            241 => Self::I32Store8AtImm(decoder.decode()?),
+            242 => Self::I32Store16(decoder.decode()?),
+            243 => Self::I32Store16Offset16(decoder.decode()?),
+            244 => Self::I32Store16Offset16Imm(decoder.decode()?),
+            245 => Self::I32Store16At(decoder.decode()?),
+            246 => Self::I32Store16AtImm(decoder.decode()?),
+            247 => Self::I64Store(decoder.decode()?),
+            248 => Self::I64StoreOffset16(decoder.decode()?),
+            249 => Self::I64StoreOffset16Imm16(decoder.decode()?),
+            250 => Self::I64StoreAt(decoder.decode()?),
+            251 => Self::I64StoreAtImm16(decoder.decode()?),
+            252 => Self::I64Store8(decoder.decode()?),
+            253 => Self::I64Store8Offset16(decoder.decode()?),
+            254 => Self::I64Store8Offset16Imm(decoder.decode()?),
+            255 => Self::I64Store8At(decoder.decode()?),
+            256 => Self::I64Store8AtImm(decoder.decode()?),
+            257 => Self::I64Store16(decoder.decode()?),
+            258 => Self::I64Store16Offset16(decoder.decode()?),
+            259 => Self::I64Store16Offset16Imm(decoder.decode()?),
+            260 => Self::I64Store16At(decoder.decode()?),
+            261 => Self::I64Store16AtImm(decoder.decode()?),
+            262 => Self::I64Store32(decoder.decode()?),
+            263 => Self::I64Store32Offset16(decoder.decode()?),
+            264 => Self::I64Store32Offset16Imm16(decoder.decode()?),
+            265 => Self::I64Store32At(decoder.decode()?),
+            266 => Self::I64Store32AtImm16(decoder.decode()?),
+            #[cfg(feature = "float")]
+            267 => Self::F32Store(decoder.decode()?),
+            #[cfg(feature = "float")]
+            268 => Self::F32StoreOffset16(decoder.decode()?),
+            #[cfg(feature = "float")]
+            269 => Self::F32StoreAt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            270 => Self::F64Store(decoder.decode()?),
+            #[cfg(feature = "float")]
+            271 => Self::F64StoreOffset16(decoder.decode()?),
+            #[cfg(feature = "float")]
+            272 => Self::F64StoreAt(decoder.decode()?),
+            273 => Self::I32Eq(decoder.decode()?),
+            274 => Self::I32EqImm16(decoder.decode()?),
+            275 => Self::I64Eq(decoder.decode()?),
+            276 => Self::I64EqImm16(decoder.decode()?),
+            277 => Self::I32Ne(decoder.decode()?),
+            278 => Self::I32NeImm16(decoder.decode()?),
+            279 => Self::I64Ne(decoder.decode()?),
+            280 => Self::I64NeImm16(decoder.decode()?),
+            281 => Self::I32LtS(decoder.decode()?),
+            282 => Self::I32LtU(decoder.decode()?),
+            283 => Self::I32LtSImm16(decoder.decode()?),
+            284 => Self::I32LtUImm16(decoder.decode()?),
+            285 => Self::I64LtS(decoder.decode()?),
+            286 => Self::I64LtU(decoder.decode()?),
+            287 => Self::I64LtSImm16(decoder.decode()?),
+            288 => Self::I64LtUImm16(decoder.decode()?),
+            289 => Self::I32GtS(decoder.decode()?),
+            290 => Self::I32GtU(decoder.decode()?),
+            291 => Self::I32GtSImm16(decoder.decode()?),
+            292 => Self::I32GtUImm16(decoder.decode()?),
+            293 => Self::I64GtS(decoder.decode()?),
+            294 => Self::I64GtU(decoder.decode()?),
+            295 => Self::I64GtSImm16(decoder.decode()?),
+            296 => Self::I64GtUImm16(decoder.decode()?),
+            297 => Self::I32LeS(decoder.decode()?),
+            298 => Self::I32LeU(decoder.decode()?),
+            299 => Self::I32LeSImm16(decoder.decode()?),
+            300 => Self::I32LeUImm16(decoder.decode()?),
+            301 => Self::I64LeS(decoder.decode()?),
+            302 => Self::I64LeU(decoder.decode()?),
+            303 => Self::I64LeSImm16(decoder.decode()?),
+            304 => Self::I64LeUImm16(decoder.decode()?),
+            305 => Self::I32GeS(decoder.decode()?),
+            306 => Self::I32GeU(decoder.decode()?),
+            307 => Self::I32GeSImm16(decoder.decode()?),
+            308 => Self::I32GeUImm16(decoder.decode()?),
+            309 => Self::I64GeS(decoder.decode()?),
+            310 => Self::I64GeU(decoder.decode()?),
+            311 => Self::I64GeSImm16(decoder.decode()?),
+            312 => Self::I64GeUImm16(decoder.decode()?),
+            #[cfg(feature = "float")]
+            313 => Self::F32Eq(decoder.decode()?),
+            #[cfg(feature = "float")]
+            314 => Self::F64Eq(decoder.decode()?),
+            #[cfg(feature = "float")]
+            315 => Self::F32Ne(decoder.decode()?),
+            #[cfg(feature = "float")]
+            316 => Self::F64Ne(decoder.decode()?),
+            #[cfg(feature = "float")]
+            317 => Self::F32Lt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            318 => Self::F64Lt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            319 => Self::F32Le(decoder.decode()?),
+            #[cfg(feature = "float")]
+            320 => Self::F64Le(decoder.decode()?),
+            #[cfg(feature = "float")]
+            321 => Self::F32Gt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            322 => Self::F64Gt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            323 => Self::F32Ge(decoder.decode()?),
+            #[cfg(feature = "float")]
+            324 => Self::F64Ge(decoder.decode()?),
+            325 => Self::I32Clz(decoder.decode()?),
+            326 => Self::I64Clz(decoder.decode()?),
+            327 => Self::I32Ctz(decoder.decode()?),
+            328 => Self::I64Ctz(decoder.decode()?),
+            329 => Self::I32Popcnt(decoder.decode()?),
+            330 => Self::I64Popcnt(decoder.decode()?),
+            331 => Self::I32Add(decoder.decode()?),
+            332 => Self::I64Add(decoder.decode()?),
+            333 => Self::I32AddImm16(decoder.decode()?),
+            334 => Self::I64AddImm16(decoder.decode()?),
+            335 => Self::I32Sub(decoder.decode()?),
+            336 => Self::I64Sub(decoder.decode()?),
+            337 => Self::I32SubImm16(decoder.decode()?),
+            338 => Self::I64SubImm16(decoder.decode()?),
+            339 => Self::I32SubImm16Rev(decoder.decode()?),
+            340 => Self::I64SubImm16Rev(decoder.decode()?),
+            341 => Self::I32Mul(decoder.decode()?),
+            342 => Self::I64Mul(decoder.decode()?),
+            343 => Self::I32MulImm16(decoder.decode()?),
+            344 => Self::I64MulImm16(decoder.decode()?),
+            345 => Self::I32DivS(decoder.decode()?),
+            346 => Self::I64DivS(decoder.decode()?),
+            347 => Self::I32DivSImm16(decoder.decode()?),
+            348 => Self::I64DivSImm16(decoder.decode()?),
+            349 => Self::I32DivSImm16Rev(decoder.decode()?),
+            350 => Self::I64DivSImm16Rev(decoder.decode()?),
+            351 => Self::I32DivU(decoder.decode()?),
+            352 => Self::I64DivU(decoder.decode()?),
+            353 => Self::I32DivUImm16(decoder.decode()?),
+            354 => Self::I64DivUImm16(decoder.decode()?),
+            355 => Self::I32DivUImm16Rev(decoder.decode()?),
+            356 => Self::I64DivUImm16Rev(decoder.decode()?),
+            357 => Self::I32RemS(decoder.decode()?),
+            358 => Self::I64RemS(decoder.decode()?),
+            359 => Self::I32RemSImm16(decoder.decode()?),
+            360 => Self::I64RemSImm16(decoder.decode()?),
+            361 => Self::I32RemSImm16Rev(decoder.decode()?),
+            362 => Self::I64RemSImm16Rev(decoder.decode()?),
+            363 => Self::I32RemU(decoder.decode()?),
+            364 => Self::I64RemU(decoder.decode()?),
+            365 => Self::I32RemUImm16(decoder.decode()?),
+            366 => Self::I64RemUImm16(decoder.decode()?),
+            367 => Self::I32RemUImm16Rev(decoder.decode()?),
+            368 => Self::I64RemUImm16Rev(decoder.decode()?),
+            369 => Self::I32And(decoder.decode()?),
+            370 => Self::I32AndEqz(decoder.decode()?),
+            371 => Self::I32AndEqzImm16(decoder.decode()?),
+            372 => Self::I32AndImm16(decoder.decode()?),
+            373 => Self::I64And(decoder.decode()?),
+            374 => Self::I64AndImm16(decoder.decode()?),
+            375 => Self::I32Or(decoder.decode()?),
+            376 => Self::I32OrEqz(decoder.decode()?),
+            377 => Self::I32OrEqzImm16(decoder.decode()?),
+            378 => Self::I32OrImm16(decoder.decode()?),
+            379 => Self::I64Or(decoder.decode()?),
+            380 => Self::I64OrImm16(decoder.decode()?),
+            381 => Self::I32Xor(decoder.decode()?),
+            382 => Self::I32XorEqz(decoder.decode()?),
+            383 => Self::I32XorEqzImm16(decoder.decode()?),
+            384 => Self::I32XorImm16(decoder.decode()?),
+            385 => Self::I64Xor(decoder.decode()?),
+            386 => Self::I64XorImm16(decoder.decode()?),
+            387 => Self::I32Shl(decoder.decode()?),
+            388 => Self::I64Shl(decoder.decode()?),
+            389 => Self::I32ShlImm(decoder.decode()?),
+            390 => Self::I64ShlImm(decoder.decode()?),
+            391 => Self::I32ShlImm16Rev(decoder.decode()?),
+            392 => Self::I64ShlImm16Rev(decoder.decode()?),
+            393 => Self::I32ShrU(decoder.decode()?),
+            394 => Self::I64ShrU(decoder.decode()?),
+            395 => Self::I32ShrUImm(decoder.decode()?),
+            396 => Self::I64ShrUImm(decoder.decode()?),
+            397 => Self::I32ShrUImm16Rev(decoder.decode()?),
+            398 => Self::I64ShrUImm16Rev(decoder.decode()?),
+            399 => Self::I32ShrS(decoder.decode()?),
+            400 => Self::I64ShrS(decoder.decode()?),
+            401 => Self::I32ShrSImm(decoder.decode()?),
+            402 => Self::I64ShrSImm(decoder.decode()?),
+            403 => Self::I32ShrSImm16Rev(decoder.decode()?),
+            404 => Self::I64ShrSImm16Rev(decoder.decode()?),
+            405 => Self::I32Rotl(decoder.decode()?),
+            406 => Self::I64Rotl(decoder.decode()?),
+            407 => Self::I32RotlImm(decoder.decode()?),
+            408 => Self::I64RotlImm(decoder.decode()?),
+            409 => Self::I32RotlImm16Rev(decoder.decode()?),
+            410 => Self::I64RotlImm16Rev(decoder.decode()?),
+            411 => Self::I32Rotr(decoder.decode()?),
+            412 => Self::I64Rotr(decoder.decode()?),
+            413 => Self::I32RotrImm(decoder.decode()?),
+            414 => Self::I64RotrImm(decoder.decode()?),
+            415 => Self::I32RotrImm16Rev(decoder.decode()?),
+            416 => Self::I64RotrImm16Rev(decoder.decode()?),
+            #[cfg(feature = "float")]
+            417 => Self::F32Abs(decoder.decode()?),
+            #[cfg(feature = "float")]
+            418 => Self::F64Abs(decoder.decode()?),
+            #[cfg(feature = "float")]
+            419 => Self::F32Neg(decoder.decode()?),
+            #[cfg(feature = "float")]
+            420 => Self::F64Neg(decoder.decode()?),
+            #[cfg(feature = "float")]
+            421 => Self::F32Ceil(decoder.decode()?),
+            #[cfg(feature = "float")]
+            422 => Self::F64Ceil(decoder.decode()?),
+            #[cfg(feature = "float")]
+            423 => Self::F32Floor(decoder.decode()?),
+            #[cfg(feature = "float")]
+            424 => Self::F64Floor(decoder.decode()?),
+            #[cfg(feature = "float")]
+            425 => Self::F32Trunc(decoder.decode()?),
+            #[cfg(feature = "float")]
+            426 => Self::F64Trunc(decoder.decode()?),
+            #[cfg(feature = "float")]
+            427 => Self::F32Nearest(decoder.decode()?),
+            #[cfg(feature = "float")]
+            428 => Self::F64Nearest(decoder.decode()?),
+            #[cfg(feature = "float")]
+            429 => Self::F32Sqrt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            430 => Self::F64Sqrt(decoder.decode()?),
+            #[cfg(feature = "float")]
+            431 => Self::F32Add(decoder.decode()?),
+            #[cfg(feature = "float")]
+            432 => Self::F64Add(decoder.decode()?),
+            #[cfg(feature = "float")]
+            433 => Self::F32Sub(decoder.decode()?),
+            #[cfg(feature = "float")]
+            434 => Self::F64Sub(decoder.decode()?),
+            #[cfg(feature = "float")]
+            435 => Self::F32Mul(decoder.decode()?),
+            #[cfg(feature = "float")]
+            436 => Self::F64Mul(decoder.decode()?),
+            #[cfg(feature = "float")]
+            437 => Self::F32Div(decoder.decode()?),
+            #[cfg(feature = "float")]
+            438 => Self::F64Div(decoder.decode()?),
+            #[cfg(feature = "float")]
+            439 => Self::F32Min(decoder.decode()?),
+            #[cfg(feature = "float")]
+            440 => Self::F64Min(decoder.decode()?),
+            #[cfg(feature = "float")]
+            441 => Self::F32Max(decoder.decode()?),
+            #[cfg(feature = "float")]
+            442 => Self::F64Max(decoder.decode()?),
+            #[cfg(feature = "float")]
+            443 => Self::F32Copysign(decoder.decode()?),
+            #[cfg(feature = "float")]
+            444 => Self::F64Copysign(decoder.decode()?),
+            #[cfg(feature = "float")]
+            445 => Self::F32CopysignImm(decoder.decode()?),
+            #[cfg(feature = "float")]
+            446 => Self::F64CopysignImm(decoder.decode()?),
+            447 => Self::I32WrapI64(decoder.decode()?),
+            448 => Self::I64ExtendI32S(decoder.decode()?),
+            449 => Self::I64ExtendI32U(decoder.decode()?),
+            #[cfg(feature = "float")]
+            450 => Self::I32TruncF32S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            451 => Self::I32TruncF32U(decoder.decode()?),
+            #[cfg(feature = "float")]
+            452 => Self::I32TruncF64S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            453 => Self::I32TruncF64U(decoder.decode()?),
+            #[cfg(feature = "float")]
+            454 => Self::I64TruncF32S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            455 => Self::I64TruncF32U(decoder.decode()?),
+            #[cfg(feature = "float")]
+            456 => Self::I64TruncF64S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            457 => Self::I64TruncF64U(decoder.decode()?),
+            #[cfg(feature = "float")]
+            458 => Self::I32TruncSatF32S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            459 => Self::I32TruncSatF32U(decoder.decode()?),
+            #[cfg(feature = "float")]
+            460 => Self::I32TruncSatF64S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            461 => Self::I32TruncSatF64U(decoder.decode()?),
+            #[cfg(feature = "float")]
+            462 => Self::I64TruncSatF32S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            463 => Self::I64TruncSatF32U(decoder.decode()?),
+            #[cfg(feature = "float")]
+            464 => Self::I64TruncSatF64S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            465 => Self::I64TruncSatF64U(decoder.decode()?),
+            466 => Self::I32Extend8S(decoder.decode()?),
+            467 => Self::I32Extend16S(decoder.decode()?),
+            468 => Self::I64Extend8S(decoder.decode()?),
+            469 => Self::I64Extend16S(decoder.decode()?),
+            470 => Self::I64Extend32S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            471 => Self::F32DemoteF64(decoder.decode()?),
+            #[cfg(feature = "float")]
+            472 => Self::F64PromoteF32(decoder.decode()?),
+            #[cfg(feature = "float")]
+            473 => Self::F32ConvertI32S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            474 => Self::F32ConvertI32U(decoder.decode()?),
+            #[cfg(feature = "float")]
+            475 => Self::F32ConvertI64S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            476 => Self::F32ConvertI64U(decoder.decode()?),
+            #[cfg(feature = "float")]
+            477 => Self::F64ConvertI32S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            478 => Self::F64ConvertI32U(decoder.decode()?),
+            #[cfg(feature = "float")]
+            479 => Self::F64ConvertI64S(decoder.decode()?),
+            #[cfg(feature = "float")]
+            480 => Self::F64ConvertI64U(decoder.decode()?),
+            _ => return Err(ArtifactError::Malformed),
+        };
+        Ok(instr)
+    }
+}
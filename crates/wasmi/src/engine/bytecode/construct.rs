@@ -1,8 +1,9 @@
+#[cfg(feature = "bulk-table")]
+use super::ElementSegmentIdx;
 use super::{
-    utils::{BranchOffset16, Sign},
+    utils::BranchOffset16,
     AnyConst32,
     BinInstr,
-    BinInstrImm,
     BinInstrImm16,
     BranchBinOpInstr,
     BranchBinOpInstrImm16,
@@ -12,7 +13,6 @@ use super::{
     Const16,
     Const32,
     DataSegmentIdx,
-    ElementSegmentIdx,
     FuncIdx,
     GlobalIdx,
     Instruction,
@@ -29,15 +29,21 @@ use super::{
     TableIdx,
     UnaryInstr,
 };
+#[cfg(feature = "float")]
+use super::{utils::Sign, BinInstrImm};
 use core::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
 
 macro_rules! constructor_for {
     (
         $(
+            $( #[$attr:meta] )*
             fn $fn_name:ident($mode:ident) -> Self::$op_code:ident;
         )* $(,)?
     ) => {
-        $( constructor_for! { @impl fn $fn_name($mode) -> Self::$op_code } )*
+        $(
+            $( #[$attr] )*
+            constructor_for! { @impl fn $fn_name($mode) -> Self::$op_code }
+        )*
     };
     ( @impl fn $fn_name:ident(unary) -> Self::$op_code:ident ) => {
         #[doc = concat!("Creates a new [`Instruction::", stringify!($op_code), "`].")]
@@ -175,9 +181,10 @@ impl Instruction {
 }
 
 macro_rules! constructor_for_branch_binop {
-    ( $( fn $name:ident() -> Self::$op_code:ident; )* ) => {
+    ( $( $( #[$attr:meta] )* fn $name:ident() -> Self::$op_code:ident; )* ) => {
         impl Instruction {
             $(
+                $( #[$attr] )*
                 #[doc = concat!("Creates a new [`Instruction::", stringify!($op_code), "`].")]
                 pub fn $name(lhs: Register, rhs: Register, offset: BranchOffset16) -> Self {
                     Self::$op_code(BranchBinOpInstr::new(lhs, rhs, offset))
@@ -215,18 +222,30 @@ constructor_for_branch_binop! {
     fn branch_i64_ge_s() -> Self::BranchI64GeS;
     fn branch_i64_ge_u() -> Self::BranchI64GeU;
 
+    #[cfg(feature = "float")]
     fn branch_f32_eq() -> Self::BranchF32Eq;
+    #[cfg(feature = "float")]
     fn branch_f32_ne() -> Self::BranchF32Ne;
+    #[cfg(feature = "float")]
     fn branch_f32_lt() -> Self::BranchF32Lt;
+    #[cfg(feature = "float")]
     fn branch_f32_le() -> Self::BranchF32Le;
+    #[cfg(feature = "float")]
     fn branch_f32_gt() -> Self::BranchF32Gt;
+    #[cfg(feature = "float")]
     fn branch_f32_ge() -> Self::BranchF32Ge;
 
+    #[cfg(feature = "float")]
     fn branch_f64_eq() -> Self::BranchF64Eq;
+    #[cfg(feature = "float")]
     fn branch_f64_ne() -> Self::BranchF64Ne;
+    #[cfg(feature = "float")]
     fn branch_f64_lt() -> Self::BranchF64Lt;
+    #[cfg(feature = "float")]
     fn branch_f64_le() -> Self::BranchF64Le;
+    #[cfg(feature = "float")]
     fn branch_f64_gt() -> Self::BranchF64Gt;
+    #[cfg(feature = "float")]
     fn branch_f64_ge() -> Self::BranchF64Ge;
 }
 
@@ -475,6 +494,14 @@ impl Instruction {
         }
     }
 
+    /// Creates a new [`Instruction::BranchTableSparse`] for the given `index` and `len_runs`.
+    pub fn branch_table_sparse(index: Register, len_runs: impl Into<Const32<u32>>) -> Self {
+        Self::BranchTableSparse {
+            index,
+            len_runs: len_runs.into(),
+        }
+    }
+
     /// Creates a new [`Instruction::Copy`].
     pub fn copy(result: impl Into<Register>, value: impl Into<Register>) -> Self {
         Self::Copy {
@@ -600,11 +627,13 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::F32CopysignImm`] instruction.
+    #[cfg(feature = "float")]
     pub fn f32_copysign_imm(result: Register, lhs: Register, rhs: Sign) -> Self {
         Self::F32CopysignImm(BinInstrImm::new(result, lhs, rhs))
     }
 
     /// Creates a new [`Instruction::F64CopysignImm`] instruction.
+    #[cfg(feature = "float")]
     pub fn f64_copysign_imm(result: Register, lhs: Register, rhs: Sign) -> Self {
         Self::F64CopysignImm(BinInstrImm::new(result, lhs, rhs))
     }
@@ -658,6 +687,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::RefFunc`] with the given `result` and `func`.
+    #[cfg(feature = "reference-types")]
     pub fn ref_func(result: Register, func: impl Into<FuncIdx>) -> Self {
         Self::RefFunc {
             result,
@@ -671,21 +701,25 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::ElementSegmentIdx`] from the given `index`.
+    #[cfg(feature = "bulk-table")]
     pub fn elem_idx(index: impl Into<ElementSegmentIdx>) -> Self {
         Self::ElementSegmentIdx(index.into())
     }
 
     /// Creates a new [`Instruction::TableIdx`] from the given `index`.
+    #[cfg(not(all(not(feature = "reference-types"), not(feature = "bulk-table"))))]
     pub fn table_idx(index: impl Into<TableIdx>) -> Self {
         Self::TableIdx(index.into())
     }
 
     /// Creates a new [`Instruction::TableGet`] with the given `result` and `index`.
+    #[cfg(feature = "reference-types")]
     pub fn table_get(result: Register, index: Register) -> Self {
         Self::TableGet { result, index }
     }
 
     /// Creates a new [`Instruction::TableGetImm`] with the given `result` and `index`.
+    #[cfg(feature = "reference-types")]
     pub fn table_get_imm(result: Register, index: impl Into<Const32<u32>>) -> Self {
         Self::TableGetImm {
             result,
@@ -694,6 +728,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableSize`] with the given `result` and `table`.
+    #[cfg(feature = "reference-types")]
     pub fn table_size(result: Register, table: impl Into<TableIdx>) -> Self {
         Self::TableSize {
             result,
@@ -702,11 +737,13 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableSet`] with the given `index` and `value`.
+    #[cfg(feature = "reference-types")]
     pub fn table_set(index: Register, value: Register) -> Self {
         Self::TableSet { index, value }
     }
 
     /// Creates a new [`Instruction::TableSetAt`] with the given `index` and `value`.
+    #[cfg(feature = "reference-types")]
     pub fn table_set_at(index: impl Into<Const32<u32>>, value: Register) -> Self {
         Self::TableSetAt {
             index: index.into(),
@@ -715,11 +752,13 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableCopy`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_copy(dst: Register, src: Register, len: Register) -> Self {
         Self::TableCopy { dst, src, len }
     }
 
     /// Creates a new [`Instruction::TableCopyTo`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_copy_to(dst: impl Into<Const16<u32>>, src: Register, len: Register) -> Self {
         Self::TableCopyTo {
             dst: dst.into(),
@@ -729,6 +768,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableCopyFrom`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_copy_from(dst: Register, src: impl Into<Const16<u32>>, len: Register) -> Self {
         Self::TableCopyFrom {
             dst,
@@ -738,6 +778,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableCopyFromTo`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_copy_from_to(
         dst: impl Into<Const16<u32>>,
         src: impl Into<Const16<u32>>,
@@ -751,6 +792,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableCopyExact`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_copy_exact(dst: Register, src: Register, len: impl Into<Const16<u32>>) -> Self {
         Self::TableCopyExact {
             dst,
@@ -760,6 +802,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableCopyToExact`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_copy_to_exact(
         dst: impl Into<Const16<u32>>,
         src: Register,
@@ -773,6 +816,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableCopyFromExact`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_copy_from_exact(
         dst: Register,
         src: impl Into<Const16<u32>>,
@@ -786,6 +830,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableCopyFromToExact`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_copy_from_to_exact(
         dst: impl Into<Const16<u32>>,
         src: impl Into<Const16<u32>>,
@@ -799,11 +844,13 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableInit`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_init(dst: Register, src: Register, len: Register) -> Self {
         Self::TableInit { dst, src, len }
     }
 
     /// Creates a new [`Instruction::TableInitTo`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_init_to(dst: impl Into<Const16<u32>>, src: Register, len: Register) -> Self {
         Self::TableInitTo {
             dst: dst.into(),
@@ -813,6 +860,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableInitFrom`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_init_from(dst: Register, src: impl Into<Const16<u32>>, len: Register) -> Self {
         Self::TableInitFrom {
             dst,
@@ -822,6 +870,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableInitFromTo`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_init_from_to(
         dst: impl Into<Const16<u32>>,
         src: impl Into<Const16<u32>>,
@@ -835,6 +884,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableInitExact`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_init_exact(dst: Register, src: Register, len: impl Into<Const16<u32>>) -> Self {
         Self::TableInitExact {
             dst,
@@ -844,6 +894,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableInitToExact`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_init_to_exact(
         dst: impl Into<Const16<u32>>,
         src: Register,
@@ -857,6 +908,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableInitFromExact`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_init_from_exact(
         dst: Register,
         src: impl Into<Const16<u32>>,
@@ -870,6 +922,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableInitFromToExact`] with the given `dst`, `src` and `len`.
+    #[cfg(feature = "bulk-table")]
     pub fn table_init_from_to_exact(
         dst: impl Into<Const16<u32>>,
         src: impl Into<Const16<u32>>,
@@ -883,11 +936,13 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableFill`] with the given `dst`, `len` and `value`.
+    #[cfg(feature = "reference-types")]
     pub fn table_fill(dst: Register, len: Register, value: Register) -> Self {
         Self::TableFill { dst, len, value }
     }
 
     /// Creates a new [`Instruction::TableFillAt`] with the given `dst`, `len` and `value`.
+    #[cfg(feature = "reference-types")]
     pub fn table_fill_at(dst: impl Into<Const16<u32>>, len: Register, value: Register) -> Self {
         Self::TableFillAt {
             dst: dst.into(),
@@ -897,6 +952,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableFillExact`] with the given `dst`, `len` and `value`.
+    #[cfg(feature = "reference-types")]
     pub fn table_fill_exact(dst: Register, len: impl Into<Const16<u32>>, value: Register) -> Self {
         Self::TableFillExact {
             dst,
@@ -906,6 +962,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableFillAtExact`] with the given `dst`, `len` and `value`.
+    #[cfg(feature = "reference-types")]
     pub fn table_fill_at_exact(
         dst: impl Into<Const16<u32>>,
         len: impl Into<Const16<u32>>,
@@ -919,6 +976,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableGrow`] with the given `result`, `delta` and `value`.
+    #[cfg(feature = "reference-types")]
     pub fn table_grow(result: Register, delta: Register, value: Register) -> Self {
         Self::TableGrow {
             result,
@@ -928,6 +986,7 @@ impl Instruction {
     }
 
     /// Creates a new [`Instruction::TableGrowImm`] with the given `result`, `delta` and `value`.
+    #[cfg(feature = "reference-types")]
     pub fn table_grow_imm(
         result: Register,
         delta: impl Into<Const16<u32>>,
@@ -1305,6 +1364,22 @@ impl Instruction {
         }
     }
 
+    /// Creates a new [`Instruction::CallIntrinsic0`] for the given `func`.
+    pub fn call_intrinsic_0(results: RegisterSpan, func: impl Into<FuncIdx>) -> Self {
+        Self::CallIntrinsic0 {
+            results,
+            func: func.into(),
+        }
+    }
+
+    /// Creates a new [`Instruction::CallIntrinsic`] for the given `func`.
+    pub fn call_intrinsic(results: RegisterSpan, func: impl Into<FuncIdx>) -> Self {
+        Self::CallIntrinsic {
+            results,
+            func: func.into(),
+        }
+    }
+
     /// Creates a new [`Instruction::CallIndirect0`] for the given `func`.
     pub fn call_indirect_0(results: RegisterSpan, func_type: impl Into<SignatureIdx>) -> Self {
         Self::CallIndirect0 {
@@ -1372,12 +1447,18 @@ impl Instruction {
         fn i64_load32_u_at(load_at) -> Self::I64Load32uAt;
         fn i64_load32_u_offset16(load_offset16) -> Self::I64Load32uOffset16;
 
+        #[cfg(feature = "float")]
         fn f32_load(load) -> Self::F32Load;
+        #[cfg(feature = "float")]
         fn f32_load_at(load_at) -> Self::F32LoadAt;
+        #[cfg(feature = "float")]
         fn f32_load_offset16(load_offset16) -> Self::F32LoadOffset16;
 
+        #[cfg(feature = "float")]
         fn f64_load(load) -> Self::F64Load;
+        #[cfg(feature = "float")]
         fn f64_load_at(load_at) -> Self::F64LoadAt;
+        #[cfg(feature = "float")]
         fn f64_load_offset16(load_offset16) -> Self::F64LoadOffset16;
 
         // Store
@@ -1424,12 +1505,18 @@ impl Instruction {
         fn i64_store32_at(store_at) -> Self::I64Store32At;
         fn i64_store32_at_imm16(store_at_imm16) -> Self::I64Store32AtImm16;
 
+        #[cfg(feature = "float")]
         fn f32_store(store) -> Self::F32Store;
+        #[cfg(feature = "float")]
         fn f32_store_offset16(store_offset16) -> Self::F32StoreOffset16;
+        #[cfg(feature = "float")]
         fn f32_store_at(store_at) -> Self::F32StoreAt;
 
+        #[cfg(feature = "float")]
         fn f64_store(store) -> Self::F64Store;
+        #[cfg(feature = "float")]
         fn f64_store_offset16(store_offset16) -> Self::F64StoreOffset16;
+        #[cfg(feature = "float")]
         fn f64_store_at(store_at) -> Self::F64StoreAt;
 
         // Integer Unary
@@ -1444,37 +1531,65 @@ impl Instruction {
 
         // Float Unary
 
+        #[cfg(feature = "float")]
         fn f32_abs(unary) -> Self::F32Abs;
+        #[cfg(feature = "float")]
         fn f32_neg(unary) -> Self::F32Neg;
+        #[cfg(feature = "float")]
         fn f32_ceil(unary) -> Self::F32Ceil;
+        #[cfg(feature = "float")]
         fn f32_floor(unary) -> Self::F32Floor;
+        #[cfg(feature = "float")]
         fn f32_trunc(unary) -> Self::F32Trunc;
+        #[cfg(feature = "float")]
         fn f32_nearest(unary) -> Self::F32Nearest;
+        #[cfg(feature = "float")]
         fn f32_sqrt(unary) -> Self::F32Sqrt;
 
+        #[cfg(feature = "float")]
         fn f64_abs(unary) -> Self::F64Abs;
+        #[cfg(feature = "float")]
         fn f64_neg(unary) -> Self::F64Neg;
+        #[cfg(feature = "float")]
         fn f64_ceil(unary) -> Self::F64Ceil;
+        #[cfg(feature = "float")]
         fn f64_floor(unary) -> Self::F64Floor;
+        #[cfg(feature = "float")]
         fn f64_trunc(unary) -> Self::F64Trunc;
+        #[cfg(feature = "float")]
         fn f64_nearest(unary) -> Self::F64Nearest;
+        #[cfg(feature = "float")]
         fn f64_sqrt(unary) -> Self::F64Sqrt;
 
         // Float Arithmetic
 
+        #[cfg(feature = "float")]
         fn f32_add(binary) -> Self::F32Add;
+        #[cfg(feature = "float")]
         fn f64_add(binary) -> Self::F64Add;
+        #[cfg(feature = "float")]
         fn f32_sub(binary) -> Self::F32Sub;
+        #[cfg(feature = "float")]
         fn f64_sub(binary) -> Self::F64Sub;
+        #[cfg(feature = "float")]
         fn f32_mul(binary) -> Self::F32Mul;
+        #[cfg(feature = "float")]
         fn f64_mul(binary) -> Self::F64Mul;
+        #[cfg(feature = "float")]
         fn f32_div(binary) -> Self::F32Div;
+        #[cfg(feature = "float")]
         fn f64_div(binary) -> Self::F64Div;
+        #[cfg(feature = "float")]
         fn f32_min(binary) -> Self::F32Min;
+        #[cfg(feature = "float")]
         fn f64_min(binary) -> Self::F64Min;
+        #[cfg(feature = "float")]
         fn f32_max(binary) -> Self::F32Max;
+        #[cfg(feature = "float")]
         fn f64_max(binary) -> Self::F64Max;
+        #[cfg(feature = "float")]
         fn f32_copysign(binary) -> Self::F32Copysign;
+        #[cfg(feature = "float")]
         fn f64_copysign(binary) -> Self::F64Copysign;
 
         // Integer Comparison
@@ -1541,17 +1656,29 @@ impl Instruction {
 
         // Float Comparison
 
+        #[cfg(feature = "float")]
         fn f32_eq(binary) -> Self::F32Eq;
+        #[cfg(feature = "float")]
         fn f64_eq(binary) -> Self::F64Eq;
+        #[cfg(feature = "float")]
         fn f32_ne(binary) -> Self::F32Ne;
+        #[cfg(feature = "float")]
         fn f64_ne(binary) -> Self::F64Ne;
+        #[cfg(feature = "float")]
         fn f32_lt(binary) -> Self::F32Lt;
+        #[cfg(feature = "float")]
         fn f64_lt(binary) -> Self::F64Lt;
+        #[cfg(feature = "float")]
         fn f32_le(binary) -> Self::F32Le;
+        #[cfg(feature = "float")]
         fn f64_le(binary) -> Self::F64Le;
+        #[cfg(feature = "float")]
         fn f32_gt(binary) -> Self::F32Gt;
+        #[cfg(feature = "float")]
         fn f64_gt(binary) -> Self::F64Gt;
+        #[cfg(feature = "float")]
         fn f32_ge(binary) -> Self::F32Ge;
+        #[cfg(feature = "float")]
         fn f64_ge(binary) -> Self::F64Ge;
 
         // Integer Arithmetic
@@ -1682,37 +1809,63 @@ impl Instruction {
         fn i64_extend_i32_s(unary) -> Self::I64ExtendI32S;
         fn i64_extend_i32_u(unary) -> Self::I64ExtendI32U;
 
+        #[cfg(feature = "float")]
         fn f32_demote_f64(unary) -> Self::F32DemoteF64;
+        #[cfg(feature = "float")]
         fn f64_promote_f32(unary) -> Self::F64PromoteF32;
 
+        #[cfg(feature = "float")]
         fn i32_trunc_f32_s(unary) -> Self::I32TruncF32S;
+        #[cfg(feature = "float")]
         fn i32_trunc_f32_u(unary) -> Self::I32TruncF32U;
+        #[cfg(feature = "float")]
         fn i32_trunc_f64_s(unary) -> Self::I32TruncF64S;
+        #[cfg(feature = "float")]
         fn i32_trunc_f64_u(unary) -> Self::I32TruncF64U;
 
+        #[cfg(feature = "float")]
         fn i64_trunc_f32_s(unary) -> Self::I64TruncF32S;
+        #[cfg(feature = "float")]
         fn i64_trunc_f32_u(unary) -> Self::I64TruncF32U;
+        #[cfg(feature = "float")]
         fn i64_trunc_f64_s(unary) -> Self::I64TruncF64S;
+        #[cfg(feature = "float")]
         fn i64_trunc_f64_u(unary) -> Self::I64TruncF64U;
 
+        #[cfg(feature = "float")]
         fn i32_trunc_sat_f32_s(unary) -> Self::I32TruncSatF32S;
+        #[cfg(feature = "float")]
         fn i32_trunc_sat_f32_u(unary) -> Self::I32TruncSatF32U;
+        #[cfg(feature = "float")]
         fn i32_trunc_sat_f64_s(unary) -> Self::I32TruncSatF64S;
+        #[cfg(feature = "float")]
         fn i32_trunc_sat_f64_u(unary) -> Self::I32TruncSatF64U;
 
+        #[cfg(feature = "float")]
         fn i64_trunc_sat_f32_s(unary) -> Self::I64TruncSatF32S;
+        #[cfg(feature = "float")]
         fn i64_trunc_sat_f32_u(unary) -> Self::I64TruncSatF32U;
+        #[cfg(feature = "float")]
         fn i64_trunc_sat_f64_s(unary) -> Self::I64TruncSatF64S;
+        #[cfg(feature = "float")]
         fn i64_trunc_sat_f64_u(unary) -> Self::I64TruncSatF64U;
 
+        #[cfg(feature = "float")]
         fn f32_convert_i32_s(unary) -> Self::F32ConvertI32S;
+        #[cfg(feature = "float")]
         fn f32_convert_i32_u(unary) -> Self::F32ConvertI32U;
+        #[cfg(feature = "float")]
         fn f32_convert_i64_s(unary) -> Self::F32ConvertI64S;
+        #[cfg(feature = "float")]
         fn f32_convert_i64_u(unary) -> Self::F32ConvertI64U;
 
+        #[cfg(feature = "float")]
         fn f64_convert_i32_s(unary) -> Self::F64ConvertI32S;
+        #[cfg(feature = "float")]
         fn f64_convert_i32_u(unary) -> Self::F64ConvertI32U;
+        #[cfg(feature = "float")]
         fn f64_convert_i64_s(unary) -> Self::F64ConvertI64S;
+        #[cfg(feature = "float")]
         fn f64_convert_i64_u(unary) -> Self::F64ConvertI64U;
     }
 }
@@ -1,3 +1,4 @@
+use crate::module::{ArtifactError, Decode, Decoder, Encode, Encoder};
 use core::{
     fmt::Debug,
     marker::PhantomData,
@@ -616,3 +617,76 @@ impl From<AnyConst32> for F64 {
         F64::from(f64::from(value))
     }
 }
+
+impl Encode for AnyConst16 {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&self.0);
+    }
+}
+
+impl Decode for AnyConst16 {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        decoder.decode().map(Self)
+    }
+}
+
+impl Encode for AnyConst32 {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&u32::from(*self));
+    }
+}
+
+impl Decode for AnyConst32 {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        decoder.decode::<u32>().map(Self::from)
+    }
+}
+
+impl<T> Encode for Const16<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&self.inner);
+    }
+}
+
+macro_rules! impl_decode_for_const16 {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl Decode for Const16<$ty> {
+                fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+                    decoder.decode().map(Self::new)
+                }
+            }
+        )*
+    };
+}
+impl_decode_for_const16!(i32, u32, i64, u64);
+
+macro_rules! impl_decode_for_nonzero_const16 {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl Decode for Const16<$ty> {
+                fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+                    let value = Self::new(decoder.decode()?);
+                    // Note: `Const16` of non-zero types must never be zero.
+                    if value.is_zero() {
+                        return Err(ArtifactError::Malformed);
+                    }
+                    Ok(value)
+                }
+            }
+        )*
+    };
+}
+impl_decode_for_nonzero_const16!(NonZeroI32, NonZeroU32, NonZeroI64, NonZeroU64);
+
+impl<T> Encode for Const32<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&self.inner);
+    }
+}
+
+impl<T> Decode for Const32<T> {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        decoder.decode().map(Self::new)
+    }
+}
@@ -1,21 +1,31 @@
+mod codec;
 mod construct;
 mod immediate;
+#[cfg(feature = "compiler")]
 mod provider;
 mod utils;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "bulk-table")]
+pub(crate) use self::utils::ElementSegmentIdx;
+#[cfg(feature = "float")]
+pub(crate) use self::utils::Sign;
+#[cfg(feature = "compiler")]
 pub(crate) use self::{
-    immediate::{AnyConst16, AnyConst32, Const16, Const32},
+    immediate::AnyConst16,
     provider::{Provider, ProviderSliceStack, UntypedProvider},
+    utils::BranchBinOpInstrImm,
+};
+pub(crate) use self::{
+    immediate::{AnyConst32, Const16, Const32},
     utils::{
         BinInstr,
         BinInstrImm,
         BinInstrImm16,
         BlockFuel,
         BranchBinOpInstr,
-        BranchBinOpInstrImm,
         BranchBinOpInstrImm16,
         BranchComparator,
         BranchOffset,
@@ -23,16 +33,15 @@ pub(crate) use self::{
         CallIndirectParams,
         ComparatorOffsetParam,
         DataSegmentIdx,
-        ElementSegmentIdx,
         FuncIdx,
         GlobalIdx,
+        IntrinsicIdx,
         LoadAtInstr,
         LoadInstr,
         LoadOffset16Instr,
         Register,
         RegisterSpan,
         RegisterSpanIter,
-        Sign,
         SignatureIdx,
         StoreAtInstr,
         StoreInstr,
@@ -75,6 +84,7 @@ pub enum Instruction {
     ///
     /// This [`Instruction`] only acts as a parameter to another
     /// one and will never be executed itself directly.
+    #[cfg(not(all(not(feature = "reference-types"), not(feature = "bulk-table"))))]
     TableIdx(TableIdx),
     /// A [`DataSegmentIdx`] instruction parameter.
     ///
@@ -89,6 +99,7 @@ pub enum Instruction {
     ///
     /// This [`Instruction`] only acts as a parameter to another
     /// one and will never be executed itself directly.
+    #[cfg(feature = "bulk-table")]
     ElementSegmentIdx(ElementSegmentIdx),
     /// A [`AnyConst32`] instruction parameter.
     ///
@@ -167,6 +178,19 @@ pub enum Instruction {
     ///
     /// These instructions are only generated if fuel metering is enabled.
     ConsumeFuel(BlockFuel),
+    /// Instruction generated to count the instructions of its associated basic block.
+    ///
+    /// # Note
+    ///
+    /// These instructions are only generated if instruction budget metering is enabled.
+    ConsumeInstrs(BlockFuel),
+    /// Instruction generated to check the epoch deadline and interrupt flag of the `Store`.
+    ///
+    /// # Note
+    ///
+    /// These instructions are only generated at function entries and loop headers
+    /// if epoch based interruption or signal based preemption is enabled.
+    CheckEpoch,
 
     /// A Wasm `return` instruction.
     ///
@@ -607,31 +631,43 @@ pub enum Instruction {
     BranchI64GeUImm(BranchBinOpInstrImm16<u64>),
 
     /// A fused [`Instruction::F32Eq`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF32Eq(BranchBinOpInstr),
     /// A fused [`Instruction::F32Ne`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF32Ne(BranchBinOpInstr),
 
     /// A fused [`Instruction::F32Lt`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF32Lt(BranchBinOpInstr),
     /// A fused [`Instruction::F32Le`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF32Le(BranchBinOpInstr),
     /// A fused [`Instruction::F32Gt`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF32Gt(BranchBinOpInstr),
     /// A fused [`Instruction::F32Ge`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF32Ge(BranchBinOpInstr),
 
     /// A fused [`Instruction::F64Eq`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF64Eq(BranchBinOpInstr),
     /// A fused [`Instruction::F64Ne`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF64Ne(BranchBinOpInstr),
 
     /// A fused [`Instruction::F64Lt`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF64Lt(BranchBinOpInstr),
     /// A fused [`Instruction::F64Le`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF64Le(BranchBinOpInstr),
     /// A fused [`Instruction::F64Gt`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF64Gt(BranchBinOpInstr),
     /// A fused [`Instruction::F64Ge`] and Wasm branch instruction.
+    #[cfg(feature = "float")]
     BranchF64Ge(BranchBinOpInstr),
 
     /// A Wasm `br_table` instruction.
@@ -654,6 +690,34 @@ pub enum Instruction {
         /// The number of branch table targets including the default target.
         len_targets: Const32<u32>,
     },
+    /// A Wasm `br_table` instruction with run-length encoded targets.
+    ///
+    /// # Note
+    ///
+    /// Used instead of [`Instruction::BranchTable`] for large `br_table`s whose
+    /// targets form few runs of equal consecutive targets. Each run is encoded only
+    /// once and the run of the `index` is found via binary search over the run bounds.
+    ///
+    /// # Encoding
+    ///
+    /// 1. May be followed by one of the copy instructions.
+    /// 1. Must be followed `len_runs - 1` times by [`Instruction::Const32`] holding
+    ///    the first `index` of all but the first run in ascending order.
+    /// 1. Must be followed `len_runs` times by any of:
+    ///
+    /// - [`Instruction::Branch`]
+    /// - [`Instruction::Return`]
+    /// - [`Instruction::ReturnReg`]
+    /// - [`Instruction::ReturnImm32`]
+    /// - [`Instruction::ReturnI64Imm32`]
+    /// - [`Instruction::ReturnF64Imm32`]
+    /// - [`Instruction::ReturnSpan`]
+    BranchTableSparse {
+        /// The register holding the index of the instruction.
+        index: Register,
+        /// The number of runs of equal branch table targets including the default target.
+        len_runs: Const32<u32>,
+    },
 
     /// Copies `value` to `result`.
     ///
@@ -936,6 +1000,44 @@ pub enum Instruction {
         /// The called imported function.
         func: FuncIdx,
     },
+    /// Wasm `call` equivalent Wasmi instruction for calls to host intrinsics.
+    ///
+    /// # Note
+    ///
+    /// Used for calling imported functions without parameters that have been
+    /// lowered to an [`Intrinsic`](crate::Intrinsic) during translation.
+    /// Falls back to [`Instruction::CallImported0`] semantics if the imported
+    /// function did not resolve to the [`Intrinsic`](crate::Intrinsic) upon instantiation.
+    CallIntrinsic0 {
+        /// The registers storing the results of the call.
+        results: RegisterSpan,
+        /// The called imported function.
+        func: FuncIdx,
+    },
+    /// Wasm `call` equivalent Wasmi instruction for calls to host intrinsics.
+    ///
+    /// # Note
+    ///
+    /// Used for calling imported functions with parameters that have been
+    /// lowered to an [`Intrinsic`](crate::Intrinsic) during translation.
+    /// Falls back to [`Instruction::CallImported`] semantics if the imported
+    /// function did not resolve to the [`Intrinsic`](crate::Intrinsic) upon instantiation.
+    ///
+    /// # Encoding (Parameters)
+    ///
+    /// Must be followed by
+    ///
+    /// 1. Zero or more [`Instruction::RegisterList`]
+    /// 2. Followed by one of
+    ///     - [`Instruction::Register`]
+    ///     - [`Instruction::Register2`]
+    ///     - [`Instruction::Register3`]
+    CallIntrinsic {
+        /// The registers storing the results of the call.
+        results: RegisterSpan,
+        /// The called imported function.
+        func: FuncIdx,
+    },
 
     /// Wasm `call_indirect` equivalent Wasmi instruction.
     ///
@@ -1070,6 +1172,7 @@ pub enum Instruction {
     },
 
     /// A Wasm `ref.func` equivalent Wasmi instruction.
+    #[cfg(feature = "reference-types")]
     RefFunc {
         /// The register storing the result of the instruction.
         result: Register,
@@ -1082,6 +1185,7 @@ pub enum Instruction {
     /// # Encoding
     ///
     /// This [`Instruction`] must be followed by an [`Instruction::TableIdx`].
+    #[cfg(feature = "reference-types")]
     TableGet {
         /// The register storing the result of the instruction.
         result: Register,
@@ -1093,6 +1197,7 @@ pub enum Instruction {
     /// # Encoding
     ///
     /// This [`Instruction`] must be followed by an [`Instruction::TableIdx`].
+    #[cfg(feature = "reference-types")]
     TableGetImm {
         /// The register storing the result of the instruction.
         result: Register,
@@ -1101,6 +1206,7 @@ pub enum Instruction {
     },
 
     /// A Wasm `table.size` instruction.
+    #[cfg(feature = "reference-types")]
     TableSize {
         /// The register storing the result of the instruction.
         result: Register,
@@ -1113,6 +1219,7 @@ pub enum Instruction {
     /// # Encoding
     ///
     /// This [`Instruction`] must be followed by an [`Instruction::TableIdx`].
+    #[cfg(feature = "reference-types")]
     TableSet {
         /// The register holding the `index` of the instruction.
         index: Register,
@@ -1124,6 +1231,7 @@ pub enum Instruction {
     /// # Encoding
     ///
     /// This [`Instruction`] must be followed by an [`Instruction::TableIdx`].
+    #[cfg(feature = "reference-types")]
     TableSetAt {
         /// The constant `index` of the instruction.
         index: Const32<u32>,
@@ -1141,6 +1249,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the `dst` Wasm table instance
     /// 2. [`Instruction::TableIdx`]: the `src` Wasm table instance
+    #[cfg(feature = "bulk-table")]
     TableCopy {
         /// The start index of the `dst` table.
         dst: Register,
@@ -1157,6 +1266,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the `dst` Wasm table instance
     /// 2. [`Instruction::TableIdx`]: the `src` Wasm table instance
+    #[cfg(feature = "bulk-table")]
     TableCopyTo {
         /// The start index of the `dst` table.
         dst: Const16<u32>,
@@ -1173,6 +1283,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the `dst` Wasm table instance
     /// 2. [`Instruction::TableIdx`]: the `src` Wasm table instance
+    #[cfg(feature = "bulk-table")]
     TableCopyFrom {
         /// The start index of the `dst` table.
         dst: Register,
@@ -1189,6 +1300,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the `dst` Wasm table instance
     /// 2. [`Instruction::TableIdx`]: the `src` Wasm table instance
+    #[cfg(feature = "bulk-table")]
     TableCopyFromTo {
         /// The start index of the `dst` table.
         dst: Const16<u32>,
@@ -1209,6 +1321,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the `dst` Wasm table instance
     /// 2. [`Instruction::TableIdx`]: the `src` Wasm table instance
+    #[cfg(feature = "bulk-table")]
     TableCopyExact {
         /// The start index of the `dst` table.
         dst: Register,
@@ -1229,6 +1342,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the `dst` Wasm table instance
     /// 2. [`Instruction::TableIdx`]: the `src` Wasm table instance
+    #[cfg(feature = "bulk-table")]
     TableCopyToExact {
         /// The start index of the `dst` table.
         dst: Const16<u32>,
@@ -1249,6 +1363,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the `dst` Wasm table instance
     /// 2. [`Instruction::TableIdx`]: the `src` Wasm table instance
+    #[cfg(feature = "bulk-table")]
     TableCopyFromExact {
         /// The start index of the `dst` table.
         dst: Register,
@@ -1269,6 +1384,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the `dst` Wasm table instance
     /// 2. [`Instruction::TableIdx`]: the `src` Wasm table instance
+    #[cfg(feature = "bulk-table")]
     TableCopyFromToExact {
         /// The start index of the `dst` table.
         dst: Const16<u32>,
@@ -1288,6 +1404,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
     /// 2. [`Instruction::ElementSegmentIdx`]: the Wasm `element` segment instance
+    #[cfg(feature = "bulk-table")]
     TableInit {
         /// The start index of the `dst` table.
         dst: Register,
@@ -1304,6 +1421,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
     /// 2. [`Instruction::ElementSegmentIdx`]: the Wasm `element` segment instance
+    #[cfg(feature = "bulk-table")]
     TableInitTo {
         /// The start index of the `dst` table.
         dst: Const16<u32>,
@@ -1320,6 +1438,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
     /// 2. [`Instruction::ElementSegmentIdx`]: the Wasm `element` segment instance
+    #[cfg(feature = "bulk-table")]
     TableInitFrom {
         /// The start index of the `dst` table.
         dst: Register,
@@ -1336,6 +1455,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
     /// 2. [`Instruction::ElementSegmentIdx`]: the Wasm `element` segment instance
+    #[cfg(feature = "bulk-table")]
     TableInitFromTo {
         /// The start index of the `dst` table.
         dst: Const16<u32>,
@@ -1356,6 +1476,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
     /// 2. [`Instruction::ElementSegmentIdx`]: the Wasm `element` segment instance
+    #[cfg(feature = "bulk-table")]
     TableInitExact {
         /// The start index of the `dst` table.
         dst: Register,
@@ -1376,6 +1497,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
     /// 2. [`Instruction::ElementSegmentIdx`]: the Wasm `element` segment instance
+    #[cfg(feature = "bulk-table")]
     TableInitToExact {
         /// The start index of the `dst` table.
         dst: Const16<u32>,
@@ -1396,6 +1518,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
     /// 2. [`Instruction::ElementSegmentIdx`]: the Wasm `element` segment instance
+    #[cfg(feature = "bulk-table")]
     TableInitFromExact {
         /// The start index of the `dst` table.
         dst: Register,
@@ -1416,6 +1539,7 @@ pub enum Instruction {
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
     /// 2. [`Instruction::ElementSegmentIdx`]: the Wasm `element` segment instance
+    #[cfg(feature = "bulk-table")]
     TableInitFromToExact {
         /// The start index of the `dst` table.
         dst: Const16<u32>,
@@ -1432,6 +1556,7 @@ pub enum Instruction {
     /// This [`Instruction`] must be followed by
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
+    #[cfg(feature = "reference-types")]
     TableFill {
         /// The start index of the table to fill.
         dst: Register,
@@ -1447,6 +1572,7 @@ pub enum Instruction {
     /// This [`Instruction`] must be followed by
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
+    #[cfg(feature = "reference-types")]
     TableFillAt {
         /// The start index of the table to fill.
         dst: Const16<u32>,
@@ -1462,6 +1588,7 @@ pub enum Instruction {
     /// This [`Instruction`] must be followed by
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
+    #[cfg(feature = "reference-types")]
     TableFillExact {
         /// The start index of the table to fill.
         dst: Register,
@@ -1477,6 +1604,7 @@ pub enum Instruction {
     /// This [`Instruction`] must be followed by
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
+    #[cfg(feature = "reference-types")]
     TableFillAtExact {
         /// The start index of the table to fill.
         dst: Const16<u32>,
@@ -1493,6 +1621,7 @@ pub enum Instruction {
     /// This [`Instruction`] must be followed by
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
+    #[cfg(feature = "reference-types")]
     TableGrow {
         /// Register holding the result of the instruction.
         result: Register,
@@ -1508,6 +1637,7 @@ pub enum Instruction {
     /// This [`Instruction`] must be followed by
     ///
     /// 1. [`Instruction::TableIdx`]: the Wasm `table` instance
+    #[cfg(feature = "reference-types")]
     TableGrowImm {
         /// Register holding the result of the instruction.
         result: Register,
@@ -1518,6 +1648,7 @@ pub enum Instruction {
     },
 
     /// A Wasm `elem.drop` equalivalent Wasmi instruction.
+    #[cfg(feature = "bulk-table")]
     ElemDrop(ElementSegmentIdx),
     /// A Wasm `data.drop` equalivalent Wasmi instruction.
     DataDrop(DataSegmentIdx),
@@ -1915,18 +2046,21 @@ pub enum Instruction {
     ///
     /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
     /// that represents the `offset` for the load/store operation.
+    #[cfg(feature = "float")]
     F32Load(LoadInstr),
     /// Wasm `f32.load` equivalent Wasmi instruction.
     ///
     /// # Note
     ///
     /// Variant of [`Instruction::F32Load`] with a constant load address.
+    #[cfg(feature = "float")]
     F32LoadAt(LoadAtInstr),
     /// Wasm `f32.load` equivalent Wasmi instruction.
     ///
     /// # Note
     ///
     /// Variant of [`Instruction::F32Load`] for small offset values.
+    #[cfg(feature = "float")]
     F32LoadOffset16(LoadOffset16Instr),
 
     /// Wasm `f64.load` equivalent Wasmi instruction.
@@ -1935,18 +2069,21 @@ pub enum Instruction {
     ///
     /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
     /// that represents the `offset` for the load/store operation.
+    #[cfg(feature = "float")]
     F64Load(LoadInstr),
     /// Wasm `f64.load` equivalent Wasmi instruction.
     ///
     /// # Note
     ///
     /// Variant of [`Instruction::F64Load`] with a constant load address.
+    #[cfg(feature = "float")]
     F64LoadAt(LoadAtInstr),
     /// Wasm `f64.load` equivalent Wasmi instruction.
     ///
     /// # Note
     ///
     /// Variant of [`Instruction::F64Load`] for small offset values.
+    #[cfg(feature = "float")]
     F64LoadOffset16(LoadOffset16Instr),
 
     /// Wasm `i32.load8_s` equivalent Wasmi instruction.
@@ -2259,10 +2396,13 @@ pub enum Instruction {
     /// # Encoding
     ///
     /// Must be followed by an [`Instruction::Register`] to encode `value`.
+    #[cfg(feature = "float")]
     F32Store(StoreInstr),
     /// Variant of [`Instruction::F32Store`] for 16-bit `offset`.
+    #[cfg(feature = "float")]
     F32StoreOffset16(StoreOffset16Instr<Register>),
     /// Variant of [`Instruction::F32Store`] for constant `address`.
+    #[cfg(feature = "float")]
     F32StoreAt(StoreAtInstr<Register>),
 
     /// Wasm `f32.store` equivalent Wasmi instruction.
@@ -2270,10 +2410,13 @@ pub enum Instruction {
     /// # Encoding
     ///
     /// Must be followed by an [`Instruction::Register`] to encode `value`.
+    #[cfg(feature = "float")]
     F64Store(StoreInstr),
     /// Variant of [`Instruction::F32Store`] for 16-bit `offset`.
+    #[cfg(feature = "float")]
     F64StoreOffset16(StoreOffset16Instr<Register>),
     /// Variant of [`Instruction::F32Store`] for constant `address`.
+    #[cfg(feature = "float")]
     F64StoreAt(StoreAtInstr<Register>),
 
     /// `i32` equality comparison instruction: `r0 = r1 == r2`
@@ -2469,39 +2612,51 @@ pub enum Instruction {
     I64GeUImm16(BinInstrImm16<u64>),
 
     /// `f32` equality comparison instruction: `r0 = r1 == r2`
+    #[cfg(feature = "float")]
     F32Eq(BinInstr),
 
     /// `f64` equality comparison instruction: `r0 = r1 == r2`
+    #[cfg(feature = "float")]
     F64Eq(BinInstr),
 
     /// `f32` inequality comparison instruction: `r0 = r1 != r2`
+    #[cfg(feature = "float")]
     F32Ne(BinInstr),
 
     /// `f64` inequality comparison instruction: `r0 = r1 != r2`
+    #[cfg(feature = "float")]
     F64Ne(BinInstr),
 
     /// `f32` less-than comparison instruction: `r0 = r1 < r2`
+    #[cfg(feature = "float")]
     F32Lt(BinInstr),
 
     /// `f64` less-than comparison instruction: `r0 = r1 < r2`
+    #[cfg(feature = "float")]
     F64Lt(BinInstr),
 
     /// `f32` less-than or equals comparison instruction: `r0 = r1 <= r2`
+    #[cfg(feature = "float")]
     F32Le(BinInstr),
 
     /// `f64` less-than or equals comparison instruction: `r0 = r1 <= r2`
+    #[cfg(feature = "float")]
     F64Le(BinInstr),
 
     /// `f32` greater-than comparison instruction: `r0 = r1 > r2`
+    #[cfg(feature = "float")]
     F32Gt(BinInstr),
 
     /// `f64` greater-than comparison instruction: `r0 = r1 > r2`
+    #[cfg(feature = "float")]
     F64Gt(BinInstr),
 
     /// `f32` greater-than or equals comparison instruction: `r0 = r1 >= r2`
+    #[cfg(feature = "float")]
     F32Ge(BinInstr),
 
     /// `f64` greater-than or equals comparison instruction: `r0 = r1 >= r2`
+    #[cfg(feature = "float")]
     F64Ge(BinInstr),
 
     /// `i32` count-leading-zeros (clz) instruction.
@@ -2960,71 +3115,101 @@ pub enum Instruction {
     I64RotrImm16Rev(BinInstrImm16<i64>),
 
     /// Wasm `f32.abs` instruction.
+    #[cfg(feature = "float")]
     F32Abs(UnaryInstr),
     /// Wasm `f64.abs` instruction.
+    #[cfg(feature = "float")]
     F64Abs(UnaryInstr),
     /// Wasm `f32.neg` instruction.
+    #[cfg(feature = "float")]
     F32Neg(UnaryInstr),
     /// Wasm `f64.neg` instruction.
+    #[cfg(feature = "float")]
     F64Neg(UnaryInstr),
     /// Wasm `f32.ceil` instruction.
+    #[cfg(feature = "float")]
     F32Ceil(UnaryInstr),
     /// Wasm `f64.ceil` instruction.
+    #[cfg(feature = "float")]
     F64Ceil(UnaryInstr),
     /// Wasm `f32.floor` instruction.
+    #[cfg(feature = "float")]
     F32Floor(UnaryInstr),
     /// Wasm `f64.floor` instruction.
+    #[cfg(feature = "float")]
     F64Floor(UnaryInstr),
     /// Wasm `f32.trunc` instruction.
+    #[cfg(feature = "float")]
     F32Trunc(UnaryInstr),
     /// Wasm `f64.trunc` instruction.
+    #[cfg(feature = "float")]
     F64Trunc(UnaryInstr),
     /// Wasm `f32.nearest` instruction.
+    #[cfg(feature = "float")]
     F32Nearest(UnaryInstr),
     /// Wasm `f64.nearest` instruction.
+    #[cfg(feature = "float")]
     F64Nearest(UnaryInstr),
     /// Wasm `f32.sqrt` instruction.
+    #[cfg(feature = "float")]
     F32Sqrt(UnaryInstr),
     /// Wasm `f64.sqrt` instruction.
+    #[cfg(feature = "float")]
     F64Sqrt(UnaryInstr),
 
     /// Wasm `f32.add` instruction: `r0 = r1 + r2`
+    #[cfg(feature = "float")]
     F32Add(BinInstr),
     /// Wasm `f64.add` instruction: `r0 = r1 + r2`
+    #[cfg(feature = "float")]
     F64Add(BinInstr),
 
     /// Wasm `f32.sub` instruction: `r0 = r1 - r2`
+    #[cfg(feature = "float")]
     F32Sub(BinInstr),
     /// Wasm `f64.sub` instruction: `r0 = r1 - r2`
+    #[cfg(feature = "float")]
     F64Sub(BinInstr),
 
     /// Wasm `f32.mul` instruction: `r0 = r1 * r2`
+    #[cfg(feature = "float")]
     F32Mul(BinInstr),
     /// Wasm `f64.mul` instruction: `r0 = r1 * r2`
+    #[cfg(feature = "float")]
     F64Mul(BinInstr),
 
     /// Wasm `f32.div` instruction: `r0 = r1 / r2`
+    #[cfg(feature = "float")]
     F32Div(BinInstr),
     /// Wasm `f64.div` instruction: `r0 = r1 / r2`
+    #[cfg(feature = "float")]
     F64Div(BinInstr),
 
     /// Wasm `f32.min` instruction: `r0 = min(r1, r2)`
+    #[cfg(feature = "float")]
     F32Min(BinInstr),
     /// Wasm `f64.min` instruction: `r0 = min(r1, r2)`
+    #[cfg(feature = "float")]
     F64Min(BinInstr),
 
     /// Wasm `f32.max` instruction: `r0 = max(r1, r2)`
+    #[cfg(feature = "float")]
     F32Max(BinInstr),
     /// Wasm `f64.max` instruction: `r0 = max(r1, r2)`
+    #[cfg(feature = "float")]
     F64Max(BinInstr),
 
     /// Wasm `f32.copysign` instruction: `r0 = copysign(r1, r2)`
+    #[cfg(feature = "float")]
     F32Copysign(BinInstr),
     /// Wasm `f64.copysign` instruction: `r0 = copysign(r1, r2)`
+    #[cfg(feature = "float")]
     F64Copysign(BinInstr),
     /// Wasm `f32.copysign` instruction with immediate: `r0 = copysign(r1, c0)`
+    #[cfg(feature = "float")]
     F32CopysignImm(BinInstrImm<Sign>),
     /// Wasm `f64.copysign` instruction with immediate: `r0 = copysign(r1, c0)`
+    #[cfg(feature = "float")]
     F64CopysignImm(BinInstrImm<Sign>),
 
     /// Wasm `i32.wrap_i64` instruction.
@@ -3035,20 +3220,28 @@ pub enum Instruction {
     I64ExtendI32U(UnaryInstr),
 
     /// Wasm `i32.trunc_f32_s` instruction.
+    #[cfg(feature = "float")]
     I32TruncF32S(UnaryInstr),
     /// Wasm `i32.trunc_f32_u` instruction.
+    #[cfg(feature = "float")]
     I32TruncF32U(UnaryInstr),
     /// Wasm `i32.trunc_f64_s` instruction.
+    #[cfg(feature = "float")]
     I32TruncF64S(UnaryInstr),
     /// Wasm `i32.trunc_f64_u` instruction.
+    #[cfg(feature = "float")]
     I32TruncF64U(UnaryInstr),
     /// Wasm `i64.trunc_f32_s` instruction.
+    #[cfg(feature = "float")]
     I64TruncF32S(UnaryInstr),
     /// Wasm `i64.trunc_f32_u` instruction.
+    #[cfg(feature = "float")]
     I64TruncF32U(UnaryInstr),
     /// Wasm `i64.trunc_f64_s` instruction.
+    #[cfg(feature = "float")]
     I64TruncF64S(UnaryInstr),
     /// Wasm `i64.trunc_f64_u` instruction.
+    #[cfg(feature = "float")]
     I64TruncF64U(UnaryInstr),
 
     /// Wasm `i32.trunc_sat_f32_s` instruction.
@@ -3056,48 +3249,56 @@ pub enum Instruction {
     /// # Note
     ///
     /// Instruction from the Wasm `non-trapping float-to-int conversions` proposal.
+    #[cfg(feature = "float")]
     I32TruncSatF32S(UnaryInstr),
     /// Wasm `i32.trunc_sat_f32_u` instruction.
     ///
     /// # Note
     ///
     /// Instruction from the Wasm `non-trapping float-to-int conversions` proposal.
+    #[cfg(feature = "float")]
     I32TruncSatF32U(UnaryInstr),
     /// Wasm `i32.trunc_sat_f64_s` instruction.
     ///
     /// # Note
     ///
     /// Instruction from the Wasm `non-trapping float-to-int conversions` proposal.
+    #[cfg(feature = "float")]
     I32TruncSatF64S(UnaryInstr),
     /// Wasm `i32.trunc_sat_f64_u` instruction.
     ///
     /// # Note
     ///
     /// Instruction from the Wasm `non-trapping float-to-int conversions` proposal.
+    #[cfg(feature = "float")]
     I32TruncSatF64U(UnaryInstr),
     /// Wasm `i64.trunc_sat_f32_s` instruction.
     ///
     /// # Note
     ///
     /// Instruction from the Wasm `non-trapping float-to-int conversions` proposal.
+    #[cfg(feature = "float")]
     I64TruncSatF32S(UnaryInstr),
     /// Wasm `i64.trunc_sat_f32_u` instruction.
     ///
     /// # Note
     ///
     /// Instruction from the Wasm `non-trapping float-to-int conversions` proposal.
+    #[cfg(feature = "float")]
     I64TruncSatF32U(UnaryInstr),
     /// Wasm `i64.trunc_sat_f64_s` instruction.
     ///
     /// # Note
     ///
     /// Instruction from the Wasm `non-trapping float-to-int conversions` proposal.
+    #[cfg(feature = "float")]
     I64TruncSatF64S(UnaryInstr),
     /// Wasm `i64.trunc_sat_f64_u` instruction.
     ///
     /// # Note
     ///
     /// Instruction from the Wasm `non-trapping float-to-int conversions` proposal.
+    #[cfg(feature = "float")]
     I64TruncSatF64U(UnaryInstr),
 
     /// Wasm `i32.extend8_s` instruction.
@@ -3132,25 +3333,35 @@ pub enum Instruction {
     I64Extend32S(UnaryInstr),
 
     /// Wasm `f32.demote_f64` instruction.
+    #[cfg(feature = "float")]
     F32DemoteF64(UnaryInstr),
     /// Wasm `f64.promote_f32` instruction.
+    #[cfg(feature = "float")]
     F64PromoteF32(UnaryInstr),
 
     /// Wasm `f32.convert_i32_s` instruction.
+    #[cfg(feature = "float")]
     F32ConvertI32S(UnaryInstr),
     /// Wasm `f32.convert_i32_u` instruction.
+    #[cfg(feature = "float")]
     F32ConvertI32U(UnaryInstr),
     /// Wasm `f32.convert_i64_s` instruction.
+    #[cfg(feature = "float")]
     F32ConvertI64S(UnaryInstr),
     /// Wasm `f32.convert_i64_u` instruction.
+    #[cfg(feature = "float")]
     F32ConvertI64U(UnaryInstr),
     /// Wasm `f64.convert_i32_s` instruction.
+    #[cfg(feature = "float")]
     F64ConvertI32S(UnaryInstr),
     /// Wasm `f64.convert_i32_u` instruction.
+    #[cfg(feature = "float")]
     F64ConvertI32U(UnaryInstr),
     /// Wasm `f64.convert_i64_s` instruction.
+    #[cfg(feature = "float")]
     F64ConvertI64S(UnaryInstr),
     /// Wasm `f64.convert_i64_u` instruction.
+    #[cfg(feature = "float")]
     F64ConvertI64U(UnaryInstr),
 }
 
@@ -3161,15 +3372,27 @@ impl Instruction {
         Ok(Self::ConsumeFuel(block_fuel))
     }
 
+    /// Convenience method to create a new [`Instruction::ConsumeInstrs`].
+    pub fn consume_instrs(amount: u64) -> Result<Self, Error> {
+        let block_fuel = BlockFuel::try_from(amount)?;
+        Ok(Self::ConsumeInstrs(block_fuel))
+    }
+
     /// Increases the fuel consumption of the [`Instruction::ConsumeFuel`] instruction by `delta`.
     ///
+    /// # Note
+    ///
+    /// For [`Instruction::ConsumeInstrs`] this increases the number of counted instructions.
+    ///
     /// # Panics
     ///
-    /// - If `self` is not a [`Instruction::ConsumeFuel`] instruction.
+    /// - If `self` is neither [`Instruction::ConsumeFuel`] nor [`Instruction::ConsumeInstrs`].
     /// - If the new fuel consumption overflows the internal `u64` value.
     pub fn bump_fuel_consumption(&mut self, delta: u64) -> Result<(), Error> {
         match self {
-            Self::ConsumeFuel(block_fuel) => block_fuel.bump_by(delta),
+            Self::ConsumeFuel(block_fuel) | Self::ConsumeInstrs(block_fuel) => {
+                block_fuel.bump_by(delta)
+            }
             instr => panic!("expected Instruction::ConsumeFuel but found: {instr:?}"),
         }
     }
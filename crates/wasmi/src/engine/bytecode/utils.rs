@@ -1,6 +1,7 @@
 use super::{Const16, Const32};
 use crate::{
     engine::{Instr, TranslationError},
+    module::{ArtifactError, Decode, Decoder, Encode, Encoder},
     Error,
 };
 use num_derive::FromPrimitive;
@@ -459,6 +460,7 @@ impl<T> StoreAtInstr<T> {
 }
 
 /// The sign of a value.
+#[cfg(feature = "float")]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Sign {
     /// Positive sign.
@@ -467,6 +469,7 @@ pub enum Sign {
     Neg,
 }
 
+#[cfg(feature = "float")]
 impl Sign {
     /// Converts the [`Sign`] into an `f32` value.
     pub fn to_f32(self) -> f32 {
@@ -612,6 +615,24 @@ impl FuncIdx {
     }
 }
 
+/// An index referring to an [`Intrinsic`](crate::Intrinsic) of an [`Engine`](crate::Engine).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct IntrinsicIdx(u32);
+
+impl From<u32> for IntrinsicIdx {
+    fn from(index: u32) -> Self {
+        Self(index)
+    }
+}
+
+impl IntrinsicIdx {
+    /// Returns the index value as `u32`.
+    pub fn to_u32(self) -> u32 {
+        self.0
+    }
+}
+
 /// A table index.
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 #[repr(transparent)]
@@ -703,16 +724,19 @@ impl DataSegmentIdx {
 /// Refers to a data segment of a [`Store`].
 ///
 /// [`Store`]: [`crate::Store`]
+#[cfg(feature = "bulk-table")]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct ElementSegmentIdx(u32);
 
+#[cfg(feature = "bulk-table")]
 impl From<u32> for ElementSegmentIdx {
     fn from(index: u32) -> Self {
         Self(index)
     }
 }
 
+#[cfg(feature = "bulk-table")]
 impl ElementSegmentIdx {
     /// Returns the index value as `u32`.
     pub fn to_u32(self) -> u32 {
@@ -858,18 +882,30 @@ pub enum BranchComparator {
     I64GeS = 24,
     I64GeU = 25,
 
+    #[cfg(feature = "float")]
     F32Eq = 26,
+    #[cfg(feature = "float")]
     F32Ne = 27,
+    #[cfg(feature = "float")]
     F32Lt = 28,
+    #[cfg(feature = "float")]
     F32Le = 29,
+    #[cfg(feature = "float")]
     F32Gt = 30,
+    #[cfg(feature = "float")]
     F32Ge = 31,
 
+    #[cfg(feature = "float")]
     F64Eq = 32,
+    #[cfg(feature = "float")]
     F64Ne = 33,
+    #[cfg(feature = "float")]
     F64Lt = 34,
+    #[cfg(feature = "float")]
     F64Le = 35,
+    #[cfg(feature = "float")]
     F64Gt = 36,
+    #[cfg(feature = "float")]
     F64Ge = 37,
 }
 
@@ -924,3 +960,90 @@ impl From<ComparatorOffsetParam> for UntypedValue {
         Self::from(params.as_u64())
     }
 }
+
+macro_rules! impl_codec_for_newtype {
+    ( $( $(#[$attr:meta])* $name:ident ),* $(,)? ) => {
+        $(
+            $(#[$attr])*
+            impl Encode for $name {
+                fn encode(&self, encoder: &mut Encoder) {
+                    encoder.encode(&self.0);
+                }
+            }
+
+            $(#[$attr])*
+            impl Decode for $name {
+                fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+                    decoder.decode().map(Self)
+                }
+            }
+        )*
+    };
+}
+impl_codec_for_newtype!(
+    Register,
+    RegisterSpan,
+    BranchOffset16,
+    BranchOffset,
+    BlockFuel,
+    FuncIdx,
+    TableIdx,
+    SignatureIdx,
+    GlobalIdx,
+    DataSegmentIdx,
+    IntrinsicIdx,
+    #[cfg(feature = "bulk-table")]
+    ElementSegmentIdx,
+);
+
+macro_rules! impl_codec_for_struct {
+    ( $( $name:ident $(<$generic:ident>)? { $( $field:ident ),* $(,)? } ),* $(,)? ) => {
+        $(
+            impl$(<$generic: Encode>)? Encode for $name$(<$generic>)? {
+                fn encode(&self, encoder: &mut Encoder) {
+                    $( encoder.encode(&self.$field); )*
+                }
+            }
+
+            impl$(<$generic: Decode>)? Decode for $name$(<$generic>)? {
+                fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+                    Ok(Self {
+                        $( $field: decoder.decode()?, )*
+                    })
+                }
+            }
+        )*
+    };
+}
+impl_codec_for_struct!(
+    RegisterSpanIter { next, last },
+    BinInstr { result, lhs, rhs },
+    BinInstrImm<T> { result, reg_in, imm_in },
+    UnaryInstr { result, input },
+    LoadInstr { result, ptr },
+    LoadAtInstr { result, address },
+    LoadOffset16Instr { result, ptr, offset },
+    StoreInstr { ptr, offset },
+    StoreOffset16Instr<T> { ptr, offset, value },
+    StoreAtInstr<T> { address, value },
+    CallIndirectParams<T> { table, index },
+    BranchBinOpInstr { lhs, rhs, offset },
+    BranchBinOpInstrImm<T> { lhs, rhs, offset },
+);
+
+#[cfg(feature = "float")]
+impl Encode for Sign {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&matches!(self, Self::Neg));
+    }
+}
+
+#[cfg(feature = "float")]
+impl Decode for Sign {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        match decoder.decode::<bool>()? {
+            false => Ok(Self::Pos),
+            true => Ok(Self::Neg),
+        }
+    }
+}
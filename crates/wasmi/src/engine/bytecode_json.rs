@@ -0,0 +1,262 @@
+use super::{bytecode::Instruction, code_map::CompiledFuncEntity};
+use alloc::{format, string::String, vec::Vec};
+
+/// Returns the JSON representation of the translated Wasmi bytecode of `func`.
+///
+/// See [`Engine::bytecode_json`] for the format.
+///
+/// [`Engine::bytecode_json`]: crate::Engine::bytecode_json
+pub fn func_to_json(func: &CompiledFuncEntity) -> String {
+    let consts = func.consts();
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{{\"func_index\":{},\"registers\":{{\"len\":{},\"len_consts\":{},\"len_cells\":{}}}",
+        func.func_index(),
+        func.len_registers(),
+        consts.len(),
+        func.len_cells(),
+    ));
+    out.push_str(",\"consts\":[");
+    // Note: function local constant values are stored in reverse order
+    //       of their negative register indices starting at `-1`.
+    for (n, value) in consts.iter().rev().enumerate() {
+        if n != 0 {
+            out.push(',');
+        }
+        out.push_str(&format!(
+            "{{\"register\":-{},\"bits\":{}}}",
+            n + 1,
+            value.to_bits()
+        ));
+    }
+    out.push_str("],\"instrs\":[");
+    for (index, instr) in func.instrs().iter().enumerate() {
+        if index != 0 {
+            out.push(',');
+        }
+        write_instr(&mut out, index, instr);
+    }
+    out.push_str("]}");
+    out
+}
+
+/// Writes the JSON representation of the `instr` at `index` to `out`.
+fn write_instr(out: &mut String, index: usize, instr: &Instruction) {
+    let debug = format!("{instr:?}");
+    let mut writer = DebugToJson::new(&debug, out);
+    let op = writer.ident();
+    writer.out.push_str(&format!(
+        "{{\"index\":{index},\"op\":\"{op}\",\"operands\":"
+    ));
+    if !writer.body() {
+        writer.out.push_str("{}");
+    }
+    writer.out.push_str(",\"branch_targets\":[");
+    for (n, offset) in writer.branch_offsets.iter().enumerate() {
+        if n != 0 {
+            writer.out.push(',');
+        }
+        writer.out.push_str(&format!("{}", index as i64 + offset));
+    }
+    writer.out.push_str("]}");
+}
+
+/// Converts the derived [`Debug`] representation of an [`Instruction`] into JSON.
+///
+/// # Note
+///
+/// - Structs are converted into JSON objects and tuples into JSON arrays.
+/// - Named values are wrapped into a JSON object with their name as the only key,
+///   e.g. `Register(3)` is converted into `{"Register":3}`.
+/// - Values without name such as numbers and booleans are kept as is.
+/// - Non-finite floating point numbers are converted into JSON strings.
+/// - Type markers such as `PhantomData<T>` are converted into `null`.
+struct DebugToJson<'a> {
+    /// The [`Debug`] representation to convert.
+    input: &'a [u8],
+    /// The current position within `input`.
+    pos: usize,
+    /// The JSON output.
+    out: &'a mut String,
+    /// The branch offsets encountered during the conversion.
+    branch_offsets: Vec<i64>,
+}
+
+impl<'a> DebugToJson<'a> {
+    /// Creates a new [`DebugToJson`] converting `input` to `out`.
+    fn new(input: &'a str, out: &'a mut String) -> Self {
+        Self {
+            input: input.as_bytes(),
+            pos: 0,
+            out,
+            branch_offsets: Vec::new(),
+        }
+    }
+
+    /// Returns the next byte of `input` after skipping whitespace if any.
+    fn peek(&mut self) -> Option<u8> {
+        while self
+            .input
+            .get(self.pos)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+        self.input.get(self.pos).copied()
+    }
+
+    /// Consumes the next byte of `input` if it is equal to `byte`.
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.peek() == Some(byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    /// Consumes and returns the bytes of `input` while `f` returns `true`.
+    fn take_while(&mut self, f: impl Fn(u8) -> bool) -> &'a str {
+        self.peek();
+        let start = self.pos;
+        while self.input.get(self.pos).copied().is_some_and(&f) {
+            self.pos += 1;
+        }
+        core::str::from_utf8(&self.input[start..self.pos])
+            .unwrap_or_else(|error| panic!("split non-ASCII Debug output: {error}"))
+    }
+
+    /// Consumes and returns the next identifier.
+    fn ident(&mut self) -> &'a str {
+        self.take_while(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+    }
+
+    /// Converts the struct or tuple body following a name if any.
+    ///
+    /// Returns `false` if there was no body.
+    fn body(&mut self) -> bool {
+        if self.eat(b'{') {
+            self.out.push('{');
+            let mut first = true;
+            while !self.eat(b'}') {
+                if !first {
+                    self.out.push(',');
+                }
+                first = false;
+                let field = self.ident();
+                self.out.push_str(&format!("\"{field}\":"));
+                self.eat(b':');
+                self.value();
+                self.eat(b',');
+            }
+            self.out.push('}');
+            return true;
+        }
+        if self.eat(b'(') {
+            self.sequence(b')');
+            return true;
+        }
+        false
+    }
+
+    /// Converts a sequence of values as JSON array until the closing `end` byte.
+    ///
+    /// Returns the number of converted values.
+    fn sequence(&mut self, end: u8) -> usize {
+        self.out.push('[');
+        let mut len = 0;
+        while !self.eat(end) {
+            if len != 0 {
+                self.out.push(',');
+            }
+            len += 1;
+            self.value();
+            self.eat(b',');
+        }
+        self.out.push(']');
+        len
+    }
+
+    /// Skips the generic arguments of a type marker such as `PhantomData<fn() -> T>`.
+    fn skip_generics(&mut self) {
+        let mut depth = 0_usize;
+        while let Some(byte) = self.input.get(self.pos).copied() {
+            let is_arrow = self.pos > 0 && self.input[self.pos - 1] == b'-';
+            self.pos += 1;
+            match byte {
+                b'<' => depth += 1,
+                b'>' if !is_arrow => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Converts the next value.
+    fn value(&mut self) {
+        let Some(byte) = self.peek() else {
+            self.out.push_str("null");
+            return;
+        };
+        if byte == b'[' {
+            self.pos += 1;
+            self.sequence(b']');
+            return;
+        }
+        if byte == b'-' && self.input.get(self.pos + 1) == Some(&b'i') {
+            self.pos += 1;
+            let ident = self.ident();
+            self.out.push_str(&format!("\"-{ident}\""));
+            return;
+        }
+        if byte == b'-' || byte.is_ascii_digit() {
+            let number = self.take_while(|byte| {
+                byte.is_ascii_digit() || matches!(byte, b'-' | b'+' | b'.' | b'e' | b'E')
+            });
+            self.out.push_str(number);
+            return;
+        }
+        let ident = self.ident();
+        if ident.is_empty() {
+            // Note: skip unexpected bytes to guarantee progress.
+            self.pos += 1;
+            self.out.push_str("null");
+            return;
+        }
+        match ident {
+            "true" | "false" => return self.out.push_str(ident),
+            "NaN" | "inf" => return self.out.push_str(&format!("\"{ident}\"")),
+            _ => {}
+        }
+        if self.peek() == Some(b'<') {
+            self.skip_generics();
+            self.out.push_str("null");
+            return;
+        }
+        if !matches!(self.peek(), Some(b'{' | b'(')) {
+            self.out.push_str(&format!("\"{ident}\""));
+            return;
+        }
+        self.out.push_str(&format!("{{\"{ident}\":"));
+        let start = self.out.len();
+        if self.eat(b'(') {
+            if self.sequence(b')') == 1 {
+                // Note: single element tuples such as `Register(3)` are unwrapped.
+                let inner = String::from(&self.out[start + 1..self.out.len() - 1]);
+                self.out.truncate(start);
+                self.out.push_str(&inner);
+            }
+        } else {
+            self.body();
+        }
+        if matches!(ident, "BranchOffset" | "BranchOffset16") {
+            if let Ok(offset) = self.out[start..].parse::<i64>() {
+                self.branch_offsets.push(offset);
+            }
+        }
+        self.out.push('}');
+    }
+}
@@ -1,12 +1,16 @@
+#[cfg(feature = "bulk-table")]
 use crate::{
-    engine::bytecode::{DataSegmentIdx, ElementSegmentIdx, FuncIdx, GlobalIdx, TableIdx},
+    engine::bytecode::ElementSegmentIdx,
     instance::InstanceEntity,
-    memory::DataSegment,
-    module::DEFAULT_MEMORY_INDEX,
-    store::Fuel,
     table::TableEntity,
     ElementSegment,
     ElementSegmentEntity,
+};
+use crate::{
+    engine::bytecode::{DataSegmentIdx, FuncIdx, GlobalIdx, TableIdx},
+    memory::{DataSegment, MemoryEntity},
+    module::DEFAULT_MEMORY_INDEX,
+    store::Fuel,
     Func,
     Instance,
     Memory,
@@ -22,6 +26,10 @@ use wasmi_core::UntypedValue;
 pub struct InstanceCache {
     /// The bytes of a default linear memory of the currently used [`Instance`].
     default_memory_bytes: Option<NonNull<[u8]>>,
+    /// Is `true` if the cached default linear memory has read-only regions.
+    ///
+    /// Only valid while `default_memory_bytes` is populated.
+    default_memory_protected: bool,
     /// The last accessed global variable value of the currently used [`Instance`].
     last_global: Option<(GlobalIdx, NonNull<UntypedValue>)>,
     /// The current instance in use.
@@ -43,6 +51,7 @@ impl From<&'_ Instance> for InstanceCache {
             last_func: None,
             last_global: None,
             default_memory_bytes: None,
+            default_memory_protected: false,
         }
     }
 }
@@ -95,6 +104,7 @@ impl InstanceCache {
     /// # Panics
     ///
     /// If there is no [`ElementSegment`] for the [`Instance`] at the `index`.
+    #[cfg(feature = "bulk-table")]
     #[inline]
     pub fn get_element_segment(
         &mut self,
@@ -119,11 +129,11 @@ impl InstanceCache {
         &mut self,
         ctx: &'a mut StoreInner,
         segment: DataSegmentIdx,
-    ) -> (&'a mut [u8], &'a [u8], &'a mut Fuel) {
+    ) -> (&'a mut MemoryEntity, &'a [u8], &'a mut Fuel) {
         let seg = self.get_data_segment(ctx, segment.to_u32());
         let mem = self.default_memory(ctx);
         let (memory, segment, fuel) = ctx.resolve_memory_init_triplet(mem, &seg);
-        (memory.data_mut(), segment.bytes(), fuel)
+        (memory, segment.bytes(), fuel)
     }
 
     /// Returns all necessary data required to execute a `table.init` instruction.
@@ -132,6 +142,7 @@ impl InstanceCache {
     ///
     /// - If there is no [`Table`] for given `table` index.
     /// - If there is no [`ElementSegment`] for `segment` index.
+    #[cfg(feature = "bulk-table")]
     #[inline]
     pub fn get_table_init_params<'a>(
         &mut self,
@@ -195,6 +206,24 @@ impl InstanceCache {
         unsafe { bytes.as_mut() }
     }
 
+    /// Returns a cached default linear memory and whether it has read-only regions.
+    ///
+    /// # Note
+    ///
+    /// The returned flag is cached alongside the bytes so that writes to
+    /// unprotected linear memories never need to inspect the memory entity.
+    #[inline]
+    pub fn default_memory_bytes_and_protection<'ctx>(
+        &mut self,
+        ctx: &'ctx mut StoreInner,
+    ) -> (&'ctx mut [u8], bool) {
+        let bytes = match self.default_memory_bytes {
+            Some(ref mut cached) => cached,
+            None => self.load_default_memory_bytes(ctx),
+        };
+        (unsafe { bytes.as_mut() }, self.default_memory_protected)
+    }
+
     /// Loads and populates the cached default memory instance.
     ///
     /// Returns an exclusive reference to the cached default memory.
@@ -202,8 +231,9 @@ impl InstanceCache {
     #[inline]
     fn load_default_memory_bytes(&mut self, ctx: &mut StoreInner) -> &mut NonNull<[u8]> {
         let memory = *self.default_memory(ctx);
-        self.default_memory_bytes
-            .insert(ctx.resolve_memory_mut(&memory).data_mut().into())
+        let memory = ctx.resolve_memory_mut(&memory);
+        self.default_memory_protected = memory.is_protected();
+        self.default_memory_bytes.insert(memory.data_mut().into())
     }
 
     /// Clears the cached default memory instance.
@@ -5,27 +5,38 @@
 //! This is the data structure specialized to handle compiled
 //! register machine based bytecode functions.
 
-use super::{FuncTranslationDriver, FuncTranslator, TranslationError, ValidatingFuncTranslator};
+use super::{
+    const_pool::{ConstPool, FuncConsts},
+    AddressMap,
+    TranslationError,
+};
 use crate::{
     core::UntypedValue,
     engine::bytecode::Instruction,
-    module::{FuncIdx, ModuleHeader},
-    store::{Fuel, FuelError},
+    module::{ArtifactError, Decode, Decoder, Encode, Encoder},
+    store::Fuel,
     Error,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use core::{
     cell::UnsafeCell,
     fmt,
     hint,
     mem,
-    ops,
-    slice,
     sync::atomic::{AtomicU8, Ordering},
 };
 use wasmi_arena::{Arena, ArenaIndex};
-use wasmi_core::TrapCode;
-use wasmparser::{FuncToValidate, ValidatorResources};
+#[cfg(feature = "compiler")]
+use {
+    super::{FuncTranslationDriver, FuncTranslator, ValidatingFuncTranslator},
+    crate::{
+        module::{FuncIdx, ModuleHeader},
+        store::FuelError,
+    },
+    core::{ops, slice},
+    wasmi_core::TrapCode,
+    wasmparser::{FuncToValidate, ValidatorResources},
+};
 
 /// A reference to a compiled function stored in the [`CodeMap`] of an [`Engine`](crate::Engine).
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -73,6 +84,7 @@ impl From<CompiledFuncEntity> for InternalFuncEntity {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl From<UncompiledFuncEntity> for InternalFuncEntity {
     fn from(func: UncompiledFuncEntity) -> Self {
         Self::Uncompiled(func)
@@ -96,6 +108,8 @@ impl InternalFuncEntity {
     ///
     /// - If function translation failed.
     /// - If `ctx` ran out of fuel in case fuel consumption is enabled.
+    /// - If the compiled function exceeds the compiled code budget of the `engine`.
+    #[cfg(feature = "compiler")]
     fn compile(&mut self, fuel: Option<&mut Fuel>) -> Result<(), Error> {
         let uncompiled = match self {
             InternalFuncEntity::Uncompiled(func) => func,
@@ -104,6 +118,7 @@ impl InternalFuncEntity {
             }
         };
         let func_idx = uncompiled.func_idx;
+        let offset = uncompiled.offset;
         let bytes = mem::take(&mut uncompiled.bytes);
         if let Some(fuel) = fuel {
             match fuel.consume_fuel(|costs| costs.fuel_for_bytes(bytes.as_slice().len() as u64)) {
@@ -124,32 +139,53 @@ impl InternalFuncEntity {
                 let translator = FuncTranslator::new(func_idx, module, allocs.0)?;
                 let validator = func_to_validate.into_validator(allocs.1);
                 let translator = ValidatingFuncTranslator::new(validator, translator)?;
-                let allocs = FuncTranslationDriver::new(0, &bytes[..], translator)?.translate(
-                    |compiled_func| {
+                let allocs = FuncTranslationDriver::new(func_idx, offset, &bytes[..], translator)?
+                    .translate(|compiled_func| {
+                        engine.charge_compiled_code(compiled_func.code_size())?;
                         *self = InternalFuncEntity::Compiled(compiled_func);
-                    },
-                )?;
+                        Ok(())
+                    })?;
                 engine.recycle_allocs(allocs.translation, allocs.validation);
             }
             None => {
                 let allocs = engine.get_translation_allocs();
                 let translator = FuncTranslator::new(func_idx, module, allocs)?;
-                let allocs = FuncTranslationDriver::new(0, &bytes[..], translator)?.translate(
-                    |compiled_func| {
+                let allocs = FuncTranslationDriver::new(func_idx, offset, &bytes[..], translator)?
+                    .translate(|compiled_func| {
+                        engine.charge_compiled_code(compiled_func.code_size())?;
                         *self = InternalFuncEntity::Compiled(compiled_func);
-                    },
-                )?;
+                        Ok(())
+                    })?;
                 engine.recycle_translation_allocs(allocs);
             }
         };
+        engine.counters().func_translated();
         Ok(())
     }
+
+    /// Compile the uncompiled [`FuncEntity`].
+    ///
+    /// # Note
+    ///
+    /// Without the Wasm compiler there are no uncompiled functions.
+    #[cfg(not(feature = "compiler"))]
+    fn compile(&mut self, _fuel: Option<&mut Fuel>) -> Result<(), Error> {
+        match self {
+            InternalFuncEntity::Uncompiled(func) => match *func {},
+            InternalFuncEntity::Compiled(func) => {
+                unreachable!("expected func to be uncompiled: {func:?}")
+            }
+        }
+    }
 }
 
 /// An internal uncompiled function entity.
+#[cfg(feature = "compiler")]
 pub struct UncompiledFuncEntity {
     /// The index of the function within the `module`.
     func_idx: FuncIdx,
+    /// The offset of the function body within the Wasm binary.
+    offset: usize,
     /// The Wasm binary bytes.
     bytes: SmallByteSlice,
     /// The Wasm module of the Wasm function.
@@ -163,16 +199,19 @@ pub struct UncompiledFuncEntity {
     func_to_validate: Option<FuncToValidate<ValidatorResources>>,
 }
 
+#[cfg(feature = "compiler")]
 impl UncompiledFuncEntity {
     /// Creates a new [`UncompiledFuncEntity`].
     pub fn new(
         func_idx: FuncIdx,
+        offset: usize,
         bytes: impl Into<SmallByteSlice>,
         module: ModuleHeader,
         func_to_validate: impl Into<Option<FuncToValidate<ValidatorResources>>>,
     ) -> Self {
         Self {
             func_idx,
+            offset,
             bytes: bytes.into(),
             module,
             func_to_validate: func_to_validate.into(),
@@ -180,10 +219,12 @@ impl UncompiledFuncEntity {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl fmt::Debug for UncompiledFuncEntity {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("UncompiledFuncEntity")
             .field("func_idx", &self.func_idx)
+            .field("offset", &self.offset)
             .field("bytes", &self.bytes)
             .field("module", &self.module)
             .field("validate", &self.func_to_validate.is_some())
@@ -191,9 +232,21 @@ impl fmt::Debug for UncompiledFuncEntity {
     }
 }
 
+/// An internal uncompiled function entity.
+///
+/// # Note
+///
+/// Without the Wasm compiler all functions are initialized as compiled functions.
+#[cfg(not(feature = "compiler"))]
+#[derive(Debug)]
+pub enum UncompiledFuncEntity {}
+
 /// A boxed byte slice that stores up to 30 bytes inline.
+#[cfg(feature = "compiler")]
 #[derive(Debug)]
 pub enum SmallByteSlice {
+    /// The byte slice refers to a read-only Wasm binary shared with other processes.
+    Shared(&'static [u8]),
     /// The byte slice fits in the inline buffer.
     Small {
         /// The length of the byte slice.
@@ -207,6 +260,7 @@ pub enum SmallByteSlice {
     Big(Box<[u8]>),
 }
 
+#[cfg(feature = "compiler")]
 impl Default for SmallByteSlice {
     fn default() -> Self {
         Self::Small {
@@ -216,6 +270,7 @@ impl Default for SmallByteSlice {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl SmallByteSlice {
     /// The maximum amount of bytes that can be stored inline.
     const MAX_INLINE_SIZE: usize = 30;
@@ -224,12 +279,14 @@ impl SmallByteSlice {
     #[inline]
     pub fn as_slice(&self) -> &[u8] {
         match self {
+            SmallByteSlice::Shared(bytes) => bytes,
             SmallByteSlice::Small { len, bytes } => &bytes[..usize::from(*len)],
             SmallByteSlice::Big(bytes) => &bytes[..],
         }
     }
 }
 
+#[cfg(feature = "compiler")]
 impl<I> ops::Index<I> for SmallByteSlice
 where
     I: slice::SliceIndex<[u8]>,
@@ -242,6 +299,7 @@ where
     }
 }
 
+#[cfg(feature = "compiler")]
 impl<'a> From<&'a [u8]> for SmallByteSlice {
     fn from(bytes: &'a [u8]) -> Self {
         if bytes.len() <= Self::MAX_INLINE_SIZE {
@@ -257,6 +315,8 @@ impl<'a> From<&'a [u8]> for SmallByteSlice {
 /// Meta information about a [`CompiledFunc`].
 #[derive(Debug)]
 pub struct CompiledFuncEntity {
+    /// The index of the function within its Wasm module.
+    func_index: u32,
     /// The sequence of [`Instruction`] of the [`CompiledFuncEntity`].
     instrs: Box<[Instruction]>,
     /// The number of registers used by the [`CompiledFunc`] in total.
@@ -267,7 +327,14 @@ pub struct CompiledFuncEntity {
     /// function parameters, function locals and dynamically used registers.
     len_registers: u16,
     /// The constant values local to the [`CompiledFunc`].
-    consts: Box<[UntypedValue]>,
+    ///
+    /// # Note
+    ///
+    /// Equal constant values of eagerly compiled functions of the
+    /// same Wasm module share the same slice of a [`ConstPool`].
+    consts: FuncConsts,
+    /// Maps the instructions back to their Wasm bytecode offsets if generated.
+    address_map: Option<AddressMap>,
 }
 
 impl CompiledFuncEntity {
@@ -277,33 +344,48 @@ impl CompiledFuncEntity {
     ///
     /// - If `instrs` is empty.
     /// - If `instrs` contains more than `u32::MAX` instructions.
-    pub fn new<I, C>(len_registers: u16, instrs: I, consts: C) -> Self
+    pub fn new<I, C>(
+        func_index: u32,
+        len_registers: u16,
+        instrs: I,
+        consts: C,
+        address_map: Option<AddressMap>,
+    ) -> Self
     where
         I: IntoIterator<Item = Instruction>,
         C: IntoIterator<Item = UntypedValue>,
     {
         let instrs: Box<[Instruction]> = instrs.into_iter().collect();
-        let consts: Box<[UntypedValue]> = consts.into_iter().collect();
+        let consts: FuncConsts = consts.into_iter().collect();
         assert!(
             !instrs.is_empty(),
             "compiled functions must have at least one instruction"
         );
         Self {
+            func_index,
             instrs,
             len_registers,
             consts,
+            address_map,
         }
     }
 
     /// Create a new uninitialized [`CompiledFuncEntity`].
     fn uninit() -> Self {
         Self {
+            func_index: 0,
             instrs: [].into(),
             len_registers: 0,
-            consts: [].into(),
+            consts: FuncConsts::default(),
+            address_map: None,
         }
     }
 
+    /// Returns the index of the [`CompiledFunc`] within its Wasm module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
     /// Returns the sequence of [`Instruction`] of the [`CompiledFunc`].
     pub fn instrs(&self) -> &[Instruction] {
         &self.instrs[..]
@@ -332,9 +414,54 @@ impl CompiledFuncEntity {
     }
 
     /// Returns the function local constant values of the [`CompiledFunc`].
-    pub fn consts(&self) -> &[UntypedValue] {
+    pub fn consts(&self) -> &FuncConsts {
         &self.consts
     }
+
+    /// Returns the [`AddressMap`] of the [`CompiledFunc`] if generated.
+    pub fn address_map(&self) -> Option<&AddressMap> {
+        self.address_map.as_ref()
+    }
+
+    /// Returns the size of the instructions and function local constant values in bytes.
+    pub fn code_size(&self) -> usize {
+        let size_instrs = self.instrs.len() * mem::size_of::<Instruction>();
+        let size_consts = self.consts.size();
+        size_instrs + size_consts
+    }
+}
+
+impl Encode for CompiledFuncEntity {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&self.func_index);
+        encoder.encode(&self.len_registers);
+        encoder.encode_len(self.consts.len());
+        for value in self.consts.iter() {
+            encoder.encode(&value);
+        }
+        encoder.encode_all(&self.instrs[..]);
+        encoder.encode(&self.address_map);
+    }
+}
+
+impl Decode for CompiledFuncEntity {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let func_index = decoder.decode()?;
+        let len_registers = decoder.decode::<u16>()?;
+        let consts = decoder.decode_all::<UntypedValue>()?;
+        let instrs = decoder.decode_all::<Instruction>()?;
+        let address_map = decoder.decode()?;
+        if instrs.is_empty() || consts.len() > usize::from(len_registers) {
+            return Err(ArtifactError::Malformed);
+        }
+        Ok(Self::new(
+            func_index,
+            len_registers,
+            instrs,
+            consts,
+            address_map,
+        ))
+    }
 }
 
 /// Datastructure to efficiently store information about compiled functions.
@@ -462,6 +589,7 @@ impl AtomicCompilationPhase {
     /// # Errors
     ///
     /// If the current [`CompilationPhase`] is not [`CompilationPhase::Uninitialized`].
+    #[cfg(feature = "compiler")]
     pub fn init_uncompiled(&mut self) -> Result<(), CompilationPhaseError> {
         self.change_phase_mut(
             CompilationPhase::Uninitialized,
@@ -565,9 +693,11 @@ impl FuncEntity {
     /// # Panics
     ///
     /// If `func` has already been initialized [`CompiledFunc`].
+    #[cfg(feature = "compiler")]
     pub fn init_uncompiled(
         &mut self,
         func_idx: FuncIdx,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
@@ -578,8 +708,13 @@ impl FuncEntity {
             self.func,
             self.phase
         );
+        let bytes = match module.get_shared_bytes(bytes) {
+            Some(shared) => SmallByteSlice::Shared(shared),
+            None => SmallByteSlice::from(bytes),
+        };
         *self.func.get_mut() =
-            UncompiledFuncEntity::new(func_idx, bytes, module.clone(), func_to_validate).into();
+            UncompiledFuncEntity::new(func_idx, offset, bytes, module.clone(), func_to_validate)
+                .into();
         assert!(
             self.phase.init_uncompiled().is_ok(),
             "function ({:?}) must be initializing but found: {:?}",
@@ -664,6 +799,11 @@ impl FuncEntity {
 }
 
 impl CodeMap {
+    /// Shrinks the capacity of the [`CodeMap`] as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.funcs.shrink_to_fit();
+    }
+
     /// Allocates a new uninitialized [`CompiledFunc`] to the [`CodeMap`].
     ///
     /// # Note
@@ -693,10 +833,12 @@ impl CodeMap {
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
     /// - If `func` refers to an already initialized [`CompiledFunc`].
+    #[cfg(feature = "compiler")]
     pub fn init_lazy_func(
         &mut self,
         func: CompiledFunc,
         func_idx: FuncIdx,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
@@ -704,7 +846,68 @@ impl CodeMap {
         let Some(func) = self.funcs.get_mut(func) else {
             panic!("encountered invalid function index for initialization: {func:?}")
         };
-        func.init_uncompiled(func_idx, bytes, module, func_to_validate);
+        func.init_uncompiled(func_idx, offset, bytes, module, func_to_validate);
+    }
+
+    /// Deduplicates the function local constant values of all compiled `funcs` via a shared pool.
+    ///
+    /// Returns the pooled function local constant values of the compiled `funcs`
+    /// that are to be installed via [`CodeMap::set_pooled_consts`].
+    ///
+    /// # Note
+    ///
+    /// Functions that have not yet been compiled keep their own constant values
+    /// once they are lazily compiled.
+    ///
+    /// # Panics
+    ///
+    /// If any of the `funcs` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn pool_consts(&self, funcs: &[CompiledFunc]) -> Vec<(CompiledFunc, FuncConsts)> {
+        let mut pool = ConstPool::default();
+        let mut pooled = Vec::new();
+        for &compiled_func in funcs {
+            let Some(func) = self.get_compiled(compiled_func) else {
+                continue;
+            };
+            if func.consts.is_empty() {
+                continue;
+            }
+            let consts = func.consts.as_slice();
+            let start = pool.intern(consts);
+            pooled.push((compiled_func, start, consts.len() as u32));
+        }
+        if pool.is_empty() {
+            return Vec::new();
+        }
+        let pool = pool.finish();
+        pooled
+            .into_iter()
+            .map(|(compiled_func, start, len)| {
+                let pool = pool.clone();
+                (compiled_func, FuncConsts::Pooled { pool, start, len })
+            })
+            .collect()
+    }
+
+    /// Replaces the function local constant values of compiled functions with their `pooled` ones.
+    ///
+    /// # Note
+    ///
+    /// This must only be called before any of the pooled functions has been executed.
+    ///
+    /// # Panics
+    ///
+    /// If any of the `pooled` functions is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn set_pooled_consts(&mut self, pooled: Vec<(CompiledFunc, FuncConsts)>) {
+        for (compiled_func, consts) in pooled {
+            let Some(func) = self.funcs.get_mut(compiled_func) else {
+                panic!("invalid compiled func: {compiled_func:?}")
+            };
+            if let InternalFuncEntity::Compiled(func) = func.func.get_mut() {
+                debug_assert_eq!(func.consts.as_slice(), consts.as_slice());
+                func.consts = consts;
+            }
+        }
     }
 
     /// Returns the [`InternalFuncEntity`] of the [`CompiledFunc`].
@@ -727,6 +930,19 @@ impl CodeMap {
             None => func.compile_and_get(fuel),
         }
     }
+
+    /// Returns the [`CompiledFuncEntity`] of `compiled_func` if it has already been compiled.
+    ///
+    /// # Panics
+    ///
+    /// If `compiled_func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    #[track_caller]
+    pub fn get_compiled(&self, compiled_func: CompiledFunc) -> Option<&CompiledFuncEntity> {
+        let Some(func) = self.funcs.get(compiled_func) else {
+            panic!("invalid compiled func: {compiled_func:?}")
+        };
+        func.get_compiled()
+    }
 }
 
 /// The instruction pointer to the instruction of a function on the call stack.
@@ -776,6 +992,18 @@ impl InstructionPtr {
         self.ptr = unsafe { self.ptr.add(delta) };
     }
 
+    /// Returns the index of the pointed-to [`Instruction`] within `instrs`.
+    ///
+    /// Returns `None` if the [`InstructionPtr`] does not point into `instrs`.
+    pub fn index_in(&self, instrs: &[Instruction]) -> Option<usize> {
+        let range = instrs.as_ptr_range();
+        if !range.contains(&self.ptr) {
+            return None;
+        }
+        let delta = self.ptr as usize - range.start as usize;
+        Some(delta / mem::size_of::<Instruction>())
+    }
+
     /// Returns a shared reference to the currently pointed at [`Instruction`].
     ///
     /// # Safety
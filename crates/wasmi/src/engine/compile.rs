@@ -0,0 +1,143 @@
+use crate::{Engine, Error, Module};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use std::{
+    panic,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    thread::{self, JoinHandle},
+};
+
+/// A Wasm [`Module`] that is compiled on a background thread.
+///
+/// Created via [`Engine::compile_async`].
+///
+/// # Note
+///
+/// The finished [`Module`] is either retrieved by blocking via
+/// [`CompilationHandle::wait`] or by `.await`ing the [`CompilationHandle`]
+/// which does not block the polling thread.
+#[derive(Debug)]
+pub struct CompilationHandle {
+    /// The background thread compiling the Wasm module.
+    ///
+    /// This is `None` once the result has been taken.
+    thread: Option<JoinHandle<Result<Module, Error>>>,
+    /// Signals the completion of the background thread.
+    signal: Arc<Mutex<Signal>>,
+}
+
+/// The completion state shared with the background thread of a [`CompilationHandle`].
+#[derive(Debug, Default)]
+struct Signal {
+    /// Is `true` once the background thread has finished.
+    done: bool,
+    /// The [`Waker`] of the task awaiting the [`CompilationHandle`] if any.
+    waker: Option<Waker>,
+}
+
+/// Locks the shared `signal` ignoring poisoning.
+///
+/// # Note
+///
+/// Poisoning can be ignored since [`Signal`] is always in a valid state.
+fn lock(signal: &Mutex<Signal>) -> MutexGuard<'_, Signal> {
+    signal.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Signals completion to the [`CompilationHandle`] upon drop.
+///
+/// # Note
+///
+/// This also signals completion if compilation panics so that
+/// the panic is propagated instead of awaiting forever.
+struct NotifyOnDrop(Arc<Mutex<Signal>>);
+
+impl Drop for NotifyOnDrop {
+    fn drop(&mut self) {
+        let waker = {
+            let mut signal = lock(&self.0);
+            signal.done = true;
+            signal.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl CompilationHandle {
+    /// Spawns a background thread compiling `bytes` into a [`Module`] for `engine`.
+    pub(crate) fn spawn<T>(engine: &Engine, bytes: T) -> Self
+    where
+        T: AsRef<[u8]> + Send + 'static,
+    {
+        let engine = engine.clone();
+        let signal = <Arc<Mutex<Signal>>>::default();
+        let thread = thread::spawn({
+            let notify = NotifyOnDrop(signal.clone());
+            move || {
+                let _notify = notify;
+                Module::new(&engine, bytes.as_ref())
+            }
+        });
+        Self {
+            thread: Some(thread),
+            signal,
+        }
+    }
+
+    /// Returns `true` if the compilation has finished.
+    ///
+    /// If this returns `true` neither [`CompilationHandle::wait`] nor
+    /// `.await`ing the [`CompilationHandle`] will wait for the compilation.
+    pub fn is_finished(&self) -> bool {
+        lock(&self.signal).done
+    }
+
+    /// Blocks the current thread until the compilation has finished and returns its result.
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm bytes failed to parse, validate or translate.
+    ///
+    /// # Panics
+    ///
+    /// If the compilation panicked.
+    pub fn wait(mut self) -> Result<Module, Error> {
+        self.join()
+    }
+
+    /// Joins the background thread and returns its result.
+    ///
+    /// # Panics
+    ///
+    /// - If the compilation panicked.
+    /// - If the result has already been taken.
+    fn join(&mut self) -> Result<Module, Error> {
+        let thread = self
+            .thread
+            .take()
+            .unwrap_or_else(|| panic!("polled `CompilationHandle` after completion"));
+        thread
+            .join()
+            .unwrap_or_else(|payload| panic::resume_unwind(payload))
+    }
+}
+
+impl Future for CompilationHandle {
+    type Output = Result<Module, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        {
+            let mut signal = lock(&self.signal);
+            if !signal.done {
+                signal.waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+        }
+        Poll::Ready(self.join())
+    }
+}
@@ -1,20 +1,33 @@
-use super::StackLimits;
+use super::{Diagnostic, DiagnosticsSink, StackLimits, WasmFeatures};
+use alloc::{boxed::Box, collections::BTreeSet};
 use core::{mem::size_of, num::NonZeroU64};
 use wasmi_core::UntypedValue;
-use wasmparser::WasmFeatures;
+
+#[cfg(feature = "fault-injection")]
+use super::AllocFailures;
 
 /// The default amount of stacks kept in the cache at most.
 const DEFAULT_CACHED_STACKS: usize = 2;
 
+/// The default maximum number of nested re-entrant executions.
+const DEFAULT_MAX_REENTRANCY_DEPTH: usize = 100;
+
 /// Configuration for an [`Engine`].
 ///
+/// # Note
+///
+/// [`Config`] does not implement `Copy` since it may own callbacks
+/// such as its [`Config::diagnostics_sink`]. Use `Clone` instead.
+///
 /// [`Engine`]: [`crate::Engine`]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct Config {
     /// The limits set on the value stack and call stack.
     stack_limits: StackLimits,
     /// The amount of Wasm stacks to keep in cache at most.
     cached_stacks: usize,
+    /// The maximum number of nested executions started from within host functions.
+    max_reentrancy_depth: usize,
     /// Is `true` if the `mutable-global` Wasm proposal is enabled.
     mutable_global: bool,
     /// Is `true` if the `sign-extension` Wasm proposal is enabled.
@@ -33,14 +46,52 @@ pub struct Config {
     extended_const: bool,
     /// Is `true` if Wasm instructions on `f32` and `f64` types are allowed.
     floats: bool,
+    /// The names of the Wasm operators that are rejected upon translation.
+    denied_operators: BTreeSet<Box<str>>,
     /// Is `true` if Wasmi executions shall consume fuel.
     consume_fuel: bool,
+    /// Is `true` if Wasmi executions shall count executed instructions against a budget.
+    consume_instructions: bool,
+    /// The number of executed instructions after which resumable calls yield if any.
+    time_slice: Option<u64>,
+    /// Is `true` if Wasmi executions shall check the epoch deadline of their `Store`.
+    epoch_interruption: bool,
+    /// Is `true` if Wasmi executions can be preempted via POSIX signals.
+    signal_preemption: bool,
+    /// The placement of the safepoints for fuel metering and interruption if configured.
+    safepoint_granularity: Option<SafepointGranularity>,
+    /// Is `true` if Wasmi executions shall account the CPU time they consume to their `Store`.
+    cpu_time_accounting: bool,
+    /// Is `true` if Wasmi executions shall report entering and exiting Wasm functions to their `Store`.
+    wasm_func_hooks: bool,
+    /// Is `true` if instantiations shall record the active data segments of their linear memories.
+    memory_reset: bool,
+    /// Is `true` if instantiations shall record the state required by `Instance::reset`.
+    instance_reset: bool,
     /// The configured fuel costs of all Wasmi bytecode instructions.
     fuel_costs: FuelCosts,
     /// The mode of Wasm to Wasmi bytecode compilation.
     compilation_mode: CompilationMode,
     /// Is `true` if Wasmi executions shall generate a runtime signature.
     update_runtime_signature: bool,
+    /// The sink receiving non-fatal translation diagnostics if any.
+    diagnostics_sink: Option<DiagnosticsSink>,
+    /// Is `true` if Wasmi shall generate address maps for compiled functions.
+    generate_address_maps: bool,
+    /// Is `true` if the compilation phases of Wasm modules shall be timed.
+    compile_stats: bool,
+    /// Is `true` if the static analysis of Wasm modules shall be computed.
+    module_analysis: bool,
+    /// Is `true` if the artifact hashes of Wasm modules shall be computed.
+    artifact_hashes: bool,
+    /// The maximum number of bytes of compiled code of all Wasm modules of an `Engine` if any.
+    max_compiled_code_bytes: Option<usize>,
+    /// Is `true` if panics of host functions are caught and converted into traps.
+    #[cfg(feature = "std")]
+    host_panic_as_trap: bool,
+    /// Determines which internal allocations of an `Engine` fail on purpose if any.
+    #[cfg(feature = "fault-injection")]
+    alloc_failures: Option<AllocFailures>,
 }
 
 /// Type storing all kinds of fuel costs of instructions.
@@ -85,12 +136,12 @@ impl FuelCosts {
     }
 
     /// Returns the number of register copies performed per unit of fuel.
-    fn copies_per_fuel(&self) -> NonZeroU64 {
+    pub(crate) fn copies_per_fuel(&self) -> NonZeroU64 {
         self.copies_per_fuel
     }
 
     /// Returns the number of byte copies performed per unit of fuel.
-    fn bytes_per_fuel(&self) -> NonZeroU64 {
+    pub(crate) fn bytes_per_fuel(&self) -> NonZeroU64 {
         self.bytes_per_fuel
     }
 
@@ -133,6 +184,22 @@ impl FuelCosts {
     }
 }
 
+impl FuelCosts {
+    /// Returns the [`FuelCosts`] used for instruction budget metering.
+    ///
+    /// # Note
+    ///
+    /// Every instruction costs exactly one unit and register or byte
+    /// copies are free so that only executed instructions are counted.
+    pub(crate) fn instruction_count() -> Self {
+        Self {
+            base: 1,
+            copies_per_fuel: NonZeroU64::MAX,
+            bytes_per_fuel: NonZeroU64::MAX,
+        }
+    }
+}
+
 impl Default for FuelCosts {
     fn default() -> Self {
         let bytes_per_fuel = 64;
@@ -165,24 +232,69 @@ pub enum CompilationMode {
     Lazy,
 }
 
+/// The placement of the safepoints at which Wasmi executions can be halted.
+///
+/// Safepoints are the places at which fuel is consumed and at which the epoch
+/// deadline and the interrupt flag of the [`Store`] are checked.
+/// Finer granularity reduces the latency of interruption at the cost of execution overhead.
+///
+/// [`Store`]: crate::Store
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SafepointGranularity {
+    /// Safepoints are placed before every Wasm instruction.
+    Instruction,
+    /// Safepoints are placed at the start of every basic block.
+    ///
+    /// These are function entries, loop headers and the branches of `if` and `else`.
+    BasicBlock,
+    /// Safepoints are placed at function entries and loop headers only.
+    ///
+    /// # Note
+    ///
+    /// With fuel metering the fuel of the `then` and `else` branches is consumed
+    /// upfront regardless of whether the branches are taken.
+    LoopsAndCalls,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             stack_limits: StackLimits::default(),
             cached_stacks: DEFAULT_CACHED_STACKS,
+            max_reentrancy_depth: DEFAULT_MAX_REENTRANCY_DEPTH,
             mutable_global: true,
             sign_extension: true,
             saturating_float_to_int: true,
             multi_value: true,
-            bulk_memory: true,
-            reference_types: true,
+            bulk_memory: cfg!(feature = "bulk-table"),
+            reference_types: cfg!(feature = "reference-types"),
             tail_call: false,
             extended_const: false,
-            floats: true,
+            floats: cfg!(feature = "float"),
+            denied_operators: BTreeSet::new(),
             consume_fuel: false,
+            consume_instructions: false,
+            time_slice: None,
+            epoch_interruption: false,
+            signal_preemption: false,
+            safepoint_granularity: None,
+            cpu_time_accounting: false,
+            wasm_func_hooks: false,
+            memory_reset: false,
+            instance_reset: false,
             fuel_costs: FuelCosts::default(),
             compilation_mode: CompilationMode::default(),
             update_runtime_signature: false,
+            diagnostics_sink: None,
+            generate_address_maps: false,
+            compile_stats: false,
+            module_analysis: false,
+            artifact_hashes: false,
+            max_compiled_code_bytes: None,
+            #[cfg(feature = "std")]
+            host_panic_as_trap: false,
+            #[cfg(feature = "fault-injection")]
+            alloc_failures: None,
         }
     }
 }
@@ -203,7 +315,20 @@ impl Config {
     ///
     /// # Note
     ///
+    /// Every execution of a [`Func`] requires its own stack. Stacks are taken
+    /// from and returned to a pool of the [`Engine`] so that consecutive executions
+    /// do not need to allocate and initialize a new stack each time. Stacks keep
+    /// their size when returned to the pool. Concurrent or nested executions,
+    /// e.g. host functions calling back into Wasm, use multiple stacks at once.
+    ///
+    /// - Use `0` to disable caching of stacks.
+    /// - Use [`Engine::clear_caches`] to release all cached stacks.
+    ///
     /// Defaults to 2.
+    ///
+    /// [`Func`]: crate::Func
+    /// [`Engine`]: crate::Engine
+    /// [`Engine::clear_caches`]: crate::Engine::clear_caches
     pub fn set_cached_stacks(&mut self, amount: usize) -> &mut Self {
         self.cached_stacks = amount;
         self
@@ -214,6 +339,32 @@ impl Config {
         self.cached_stacks
     }
 
+    /// Sets the maximum number of nested executions started from within host functions.
+    ///
+    /// # Note
+    ///
+    /// Host functions may call back into Wasm via their [`Caller`] which in turn may
+    /// call host functions again. Every such re-entrant execution uses native stack
+    /// space of the host. Executions that would exceed this depth trap with
+    /// [`TrapCode::StackOverflow`] instead of exhausting the native stack.
+    ///
+    /// The Wasm call frames of all nested executions additionally share the
+    /// recursion limit of the [`StackLimits`].
+    ///
+    /// Defaults to 100.
+    ///
+    /// [`Caller`]: crate::Caller
+    /// [`TrapCode::StackOverflow`]: crate::core::TrapCode::StackOverflow
+    pub fn max_reentrancy_depth(&mut self, depth: usize) -> &mut Self {
+        self.max_reentrancy_depth = depth;
+        self
+    }
+
+    /// Returns the maximum number of nested executions started from within host functions.
+    pub(crate) fn get_max_reentrancy_depth(&self) -> usize {
+        self.max_reentrancy_depth
+    }
+
     /// Enable or disable the [`mutable-global`] Wasm proposal for the [`Config`].
     ///
     /// # Note
@@ -267,10 +418,19 @@ impl Config {
     ///
     /// # Note
     ///
-    /// Enabled by default.
+    /// Enabled by default unless Wasmi was compiled without the `bulk-table` crate feature.
+    ///
+    /// # Panics
+    ///
+    /// If `enable` is `true` and Wasmi was compiled without the `bulk-table` crate feature
+    /// since the `table.copy`, `table.init` and `elem.drop` instructions are compiled out.
     ///
     /// [`bulk-memory`]: https://github.com/WebAssembly/bulk-memory-operations
     pub fn wasm_bulk_memory(&mut self, enable: bool) -> &mut Self {
+        assert!(
+            !(enable && cfg!(not(feature = "bulk-table"))),
+            "cannot enable the bulk-memory Wasm proposal without the bulk-table crate feature"
+        );
         self.bulk_memory = enable;
         self
     }
@@ -279,7 +439,8 @@ impl Config {
     ///
     /// # Note
     ///
-    /// Enabled by default.
+    /// Enabled by default unless Wasmi was compiled without the `reference-types` crate feature
+    /// in which case the [`reference-types`] proposal cannot be enabled.
     ///
     /// [`reference-types`]: https://github.com/WebAssembly/reference-types
     pub fn wasm_reference_types(&mut self, enable: bool) -> &mut Self {
@@ -314,11 +475,45 @@ impl Config {
     /// Enable or disable Wasm floating point (`f32` and `f64`) instructions and types.
     ///
     /// Enabled by default.
+    ///
+    /// # Note
+    ///
+    /// Floating point support cannot be enabled if Wasmi was compiled without the `float` crate feature.
     pub fn floats(&mut self, enable: bool) -> &mut Self {
         self.floats = enable;
         self
     }
 
+    /// Denies the Wasm operators with the given `names` so that translating them fails.
+    ///
+    /// # Note
+    ///
+    /// - Names are written as in the Wasm text format, e.g. `memory.grow` or `call_indirect`.
+    /// - A name of the form `<prefix>.*` denies all Wasm operators with that prefix, e.g. `f32.*`.
+    /// - Translating a Wasm function that uses a denied Wasm operator fails with
+    ///   [`TranslationError::DeniedOperator`] upon [`Module`] creation regardless
+    ///   of the [`CompilationMode`].
+    /// - Calling this multiple times accumulates the denied Wasm operators.
+    ///
+    /// No Wasm operators are denied by default.
+    ///
+    /// [`TranslationError::DeniedOperator`]: crate::errors::TranslationError::DeniedOperator
+    /// [`Module`]: crate::Module
+    pub fn deny_operators<I>(&mut self, names: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.denied_operators
+            .extend(names.into_iter().map(|name| Box::from(name.as_ref())));
+        self
+    }
+
+    /// Returns the names of the Wasm operators denied by the [`Config`].
+    pub(crate) fn get_denied_operators(&self) -> &BTreeSet<Box<str>> {
+        &self.denied_operators
+    }
+
     /// Configures whether Wasmi will consume fuel during execution to either halt execution as desired.
     ///
     /// # Note
@@ -348,6 +543,260 @@ impl Config {
         self.consume_fuel
     }
 
+    /// Configures whether Wasmi will count executed instructions against a budget.
+    ///
+    /// # Note
+    ///
+    /// This is a lightweight alternative to fuel metering for users who only need
+    /// a rough bound on the amount of executed instructions. Instructions are counted
+    /// per basic block and every Wasmi bytecode instruction counts as one regardless
+    /// of its costs. Once the budget is exhausted a
+    /// [`TrapCode::OutOfInstructions`](crate::core::TrapCode::OutOfInstructions) trap is raised.
+    ///
+    /// - Use [`Store::set_instruction_budget`](crate::Store::set_instruction_budget) to set
+    ///   the budget of the [`Store`] before executing some code as the [`Store`] starts with
+    ///   an empty budget.
+    /// - Fuel metering takes precedence if it is enabled as well.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Store`]: crate::Store
+    pub fn consume_instructions(&mut self, enable: bool) -> &mut Self {
+        self.consume_instructions = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables instruction budget metering by the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// This is `false` if fuel metering is enabled as well since it takes precedence.
+    ///
+    /// [`Engine`]: crate::Engine
+    pub(crate) fn get_consume_instructions(&self) -> bool {
+        self.consume_instructions && !self.consume_fuel
+    }
+
+    /// Configures resumable calls to yield every `instructions` executed instructions.
+    ///
+    /// # Note
+    ///
+    /// This allows cooperative multitasking of Wasm guests without fuel metering.
+    /// Calls started via [`Func::call_resumable`] or [`TypedFunc::call_resumable`]
+    /// return [`ResumableCall::Yielded`] once they executed at least `instructions`
+    /// instructions since they were started or last resumed.
+    ///
+    /// - Instructions are counted per basic block and every Wasmi bytecode instruction
+    ///   counts as one the same as with [`Config::consume_instructions`].
+    ///   Therefore calls yield at the start of the first basic block after the time slice
+    ///   is exhausted.
+    /// - Calls that are not resumable, e.g. via [`Func::call`], never yield.
+    /// - Use `0` to disable time slicing.
+    /// - Fuel metering takes precedence if it is enabled as well.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Func::call_resumable`]: crate::Func::call_resumable
+    /// [`TypedFunc::call_resumable`]: crate::TypedFunc::call_resumable
+    /// [`ResumableCall::Yielded`]: crate::ResumableCall::Yielded
+    /// [`Func::call`]: crate::Func::call
+    pub fn time_slice(&mut self, instructions: u64) -> &mut Self {
+        self.time_slice = (instructions != 0).then_some(instructions);
+        self
+    }
+
+    /// Returns the number of executed instructions after which resumable calls yield if any.
+    ///
+    /// # Note
+    ///
+    /// This is `None` if fuel metering is enabled since it takes precedence.
+    pub(crate) fn get_time_slice(&self) -> Option<u64> {
+        self.time_slice.filter(|_| !self.consume_fuel)
+    }
+
+    /// Configures whether Wasmi executions can be interrupted via epochs.
+    ///
+    /// # Note
+    ///
+    /// If enabled, Wasmi checks the epoch deadline of the [`Store`] upon every function
+    /// entry and loop iteration and traps with
+    /// [`TrapCode::Interrupted`](crate::core::TrapCode::Interrupted) once it is reached.
+    ///
+    /// - Use [`Engine::increment_epoch`] to advance the epoch of the [`Engine`],
+    ///   e.g. from a timer thread such as [`Watchdog`].
+    /// - Use [`Store::set_epoch_deadline`] to set the deadline of a [`Store`].
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::set_epoch_deadline`]: crate::Store::set_epoch_deadline
+    /// [`Engine`]: crate::Engine
+    /// [`Engine::increment_epoch`]: crate::Engine::increment_epoch
+    /// [`Watchdog`]: crate::watchdog::Watchdog
+    pub fn epoch_interruption(&mut self, enable: bool) -> &mut Self {
+        self.epoch_interruption = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables epoch based interruption.
+    pub(crate) fn get_epoch_interruption(&self) -> bool {
+        self.epoch_interruption
+    }
+
+    /// Configures whether Wasmi executions can be preempted via POSIX signals.
+    ///
+    /// # Note
+    ///
+    /// If enabled, Wasmi checks the interrupt flag of the [`Store`] at the same
+    /// safepoints as with [`Config::epoch_interruption`] and traps with
+    /// [`TrapCode::Interrupted`](crate::core::TrapCode::Interrupted) once it is set.
+    /// Additionally executions bind the interrupt flag of their [`Store`] to the
+    /// executing thread so that the signal handler installed via
+    /// [`preempt::install_signal_handler`] can set it.
+    ///
+    /// - Use [`preempt::PreemptibleThread::preempt`] to signal a thread executing Wasm.
+    /// - Use [`Store::interrupt_handle`] to set the interrupt flag without signals.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::interrupt_handle`]: crate::Store::interrupt_handle
+    /// [`preempt::install_signal_handler`]: crate::preempt::install_signal_handler
+    /// [`preempt::PreemptibleThread::preempt`]: crate::preempt::PreemptibleThread::preempt
+    pub fn signal_preemption(&mut self, enable: bool) -> &mut Self {
+        self.signal_preemption = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables signal based preemption.
+    pub(crate) fn get_signal_preemption(&self) -> bool {
+        self.signal_preemption
+    }
+
+    /// Configures the placement of the safepoints at which Wasmi executions can be halted.
+    ///
+    /// # Note
+    ///
+    /// This applies to fuel metering, instruction budget metering, time slicing as well as
+    /// epoch based interruption and signal based preemption.
+    ///
+    /// By default fuel is consumed per [`SafepointGranularity::BasicBlock`] while the epoch
+    /// deadline and interrupt flag are checked per [`SafepointGranularity::LoopsAndCalls`].
+    pub fn safepoint_granularity(&mut self, granularity: SafepointGranularity) -> &mut Self {
+        self.safepoint_granularity = Some(granularity);
+        self
+    }
+
+    /// Returns the [`SafepointGranularity`] for fuel metering.
+    pub(crate) fn get_fuel_safepoints(&self) -> SafepointGranularity {
+        self.safepoint_granularity
+            .unwrap_or(SafepointGranularity::BasicBlock)
+    }
+
+    /// Returns the [`SafepointGranularity`] for epoch based interruption and signal based preemption.
+    pub(crate) fn get_epoch_safepoints(&self) -> SafepointGranularity {
+        self.safepoint_granularity
+            .unwrap_or(SafepointGranularity::LoopsAndCalls)
+    }
+
+    /// Configures whether Wasmi executions account the CPU time they consume to their [`Store`].
+    ///
+    /// # Note
+    ///
+    /// If enabled, the CPU time of the executing thread is sampled via
+    /// `clock_gettime(CLOCK_THREAD_CPUTIME_ID)` whenever an execution enters or
+    /// leaves Wasm code, i.e. upon calls, returns, host calls and traps.
+    /// The CPU time spent in Wasm code is summed up per [`Store`] and queried
+    /// via [`Store::cpu_time`]. Time spent in host functions is not included.
+    ///
+    /// This is only supported on Unix platforms and ignored otherwise.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::cpu_time`]: crate::Store::cpu_time
+    pub fn cpu_time_accounting(&mut self, enable: bool) -> &mut Self {
+        self.cpu_time_accounting = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables CPU time accounting.
+    pub(crate) fn get_cpu_time_accounting(&self) -> bool {
+        self.cpu_time_accounting
+    }
+
+    /// Configures whether Wasmi executions report entering and exiting Wasm functions to their [`Store`].
+    ///
+    /// # Note
+    ///
+    /// If enabled, the hook installed via [`Store::set_wasm_func_hook`] is called
+    /// whenever a call frame of a Wasm function is pushed to or popped from the call
+    /// stack. This allows hosts to trace precise call graphs within guest code and
+    /// to attribute latencies to individual Wasm functions at the cost of slower calls.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::set_wasm_func_hook`]: crate::Store::set_wasm_func_hook
+    pub fn wasm_func_hooks(&mut self, enable: bool) -> &mut Self {
+        self.wasm_func_hooks = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables Wasm function enter and exit hooks.
+    pub(crate) fn get_wasm_func_hooks(&self) -> bool {
+        self.wasm_func_hooks
+    }
+
+    /// Enables or disables [`Memory::reset`] for linear memories of the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// If enabled, instantiations keep the active data segments applied to their
+    /// linear memories so that [`Memory::reset`] can re-apply them. This retains
+    /// the bytes of those data segments for as long as the linear memories live.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Memory::reset`]: crate::Memory::reset
+    pub fn memory_reset(&mut self, enable: bool) -> &mut Self {
+        self.memory_reset = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables [`Memory::reset`].
+    ///
+    /// [`Memory::reset`]: crate::Memory::reset
+    pub(crate) fn get_memory_reset(&self) -> bool {
+        self.memory_reset || self.instance_reset
+    }
+
+    /// Enables or disables [`Instance::reset`] for instances of the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// If enabled, instantiations record the initial values of the mutable global
+    /// variables, the elements written by active element segments and the state of
+    /// all data and element segments of their instances so that [`Instance::reset`]
+    /// can restore them. This also enables [`Config::memory_reset`].
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Instance::reset`]: crate::Instance::reset
+    pub fn instance_reset(&mut self, enable: bool) -> &mut Self {
+        self.instance_reset = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables [`Instance::reset`].
+    ///
+    /// [`Instance::reset`]: crate::Instance::reset
+    pub(crate) fn get_instance_reset(&self) -> bool {
+        self.instance_reset
+    }
+
     pub fn update_runtime_signature(&mut self, enable: bool) -> &mut Self {
         self.update_runtime_signature = enable;
         self
@@ -377,18 +826,227 @@ impl Config {
         self.compilation_mode
     }
 
+    /// Sets the sink receiving non-fatal [`Diagnostic`]s emitted during Wasm to Wasmi bytecode translation.
+    ///
+    /// # Note
+    ///
+    /// Diagnostics explain why the translated bytecode of a Wasm function may be
+    /// slower or larger than expected. With lazy [`CompilationMode`]s they are emitted
+    /// once the call that first translated a Wasm function has returned.
+    ///
+    /// The `sink` is never called while the [`Engine`] holds internal locks
+    /// and thus may use the [`Engine`] itself.
+    ///
+    /// By default no diagnostics are collected.
+    ///
+    /// [`Engine`]: crate::Engine
+    pub fn diagnostics_sink(
+        &mut self,
+        sink: impl Fn(&Diagnostic) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.diagnostics_sink = Some(DiagnosticsSink::new(sink));
+        self
+    }
+
+    /// Returns the [`DiagnosticsSink`] of the [`Config`] if any.
+    pub(crate) fn get_diagnostics_sink(&self) -> Option<&DiagnosticsSink> {
+        self.diagnostics_sink.as_ref()
+    }
+
+    /// Enables or disables generation of [`AddressMap`]s for compiled functions.
+    ///
+    /// # Note
+    ///
+    /// An [`AddressMap`] maps the Wasmi bytecode of a compiled function back to
+    /// the offsets of its original Wasm bytecode. Query it via [`Engine::address_map`].
+    /// Generating address maps slightly slows down translation and increases memory usage.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`AddressMap`]: crate::AddressMap
+    /// [`Engine::address_map`]: crate::Engine::address_map
+    pub fn generate_address_maps(&mut self, enable: bool) -> &mut Self {
+        self.generate_address_maps = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables generation of address maps.
+    pub(crate) fn get_generate_address_maps(&self) -> bool {
+        self.generate_address_maps
+    }
+
+    /// Enables or disables timing of the compilation phases of Wasm modules.
+    ///
+    /// # Note
+    ///
+    /// If enabled, the wall time spent parsing, validating and translating a Wasm module
+    /// is recorded and can be queried via [`Module::compile_stats`].
+    /// Timing requires the `std` crate feature and is ignored otherwise.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Module::compile_stats`]: crate::Module::compile_stats
+    pub fn compile_stats(&mut self, enable: bool) -> &mut Self {
+        self.compile_stats = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables timing of Wasm module compilation phases.
+    pub(crate) fn get_compile_stats(&self) -> bool {
+        self.compile_stats
+    }
+
+    /// Enables or disables the static analysis of Wasm modules.
+    ///
+    /// # Note
+    ///
+    /// If enabled, the Wasm function bodies are analyzed while a Wasm module is compiled
+    /// for estimates of their call depth and linear memory growth which can be queried
+    /// via [`Module::analysis`] together with the value stack usage of their call frames.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Module::analysis`]: crate::Module::analysis
+    pub fn module_analysis(&mut self, enable: bool) -> &mut Self {
+        self.module_analysis = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables the static analysis of Wasm modules.
+    pub(crate) fn get_module_analysis(&self) -> bool {
+        self.module_analysis
+    }
+
+    /// Enables or disables computing the artifact hashes of Wasm modules.
+    ///
+    /// # Note
+    ///
+    /// If enabled, the hash of the artifact that [`Module::precompile`] yields for a
+    /// Wasm module is computed while the Wasm module is compiled and can be queried
+    /// via [`Module::artifact_hash`].
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Module::precompile`]: crate::Module::precompile
+    /// [`Module::artifact_hash`]: crate::Module::artifact_hash
+    pub fn artifact_hashes(&mut self, enable: bool) -> &mut Self {
+        self.artifact_hashes = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] enables computing artifact hashes of Wasm modules.
+    pub(crate) fn get_artifact_hashes(&self) -> bool {
+        self.artifact_hashes
+    }
+
+    /// Sets the maximum number of bytes of compiled code of all Wasm modules of an [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// The budget is shared by all Wasm modules compiled into the same [`Engine`].
+    /// Compiled code is never freed by the [`Engine`] and therefore counts against
+    /// the budget for the lifetime of the [`Engine`].
+    ///
+    /// - Upon eager compilation [`Module::new`] fails once the budget would be exceeded.
+    /// - Upon lazy compilation the call that compiles the function fails instead.
+    ///
+    /// Query the used budget via [`Engine::compiled_code_bytes`].
+    ///
+    /// Unlimited by default.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Module::new`]: crate::Module::new
+    /// [`Engine::compiled_code_bytes`]: crate::Engine::compiled_code_bytes
+    pub fn max_compiled_code_bytes(&mut self, max: usize) -> &mut Self {
+        self.max_compiled_code_bytes = Some(max);
+        self
+    }
+
+    /// Returns the maximum number of bytes of compiled code of an `Engine` if any.
+    pub(crate) fn get_max_compiled_code_bytes(&self) -> Option<usize> {
+        self.max_compiled_code_bytes
+    }
+
+    /// Enables or disables catching panics of host functions and converting them into traps.
+    ///
+    /// # Note
+    ///
+    /// If enabled, a panic inside a host function is caught at the boundary
+    /// between the host function and the calling Wasm or host code and surfaced
+    /// as an [`ErrorKind::HostPanic`] error carrying the panic message.
+    /// Otherwise, the panic unwinds through the Wasmi executor.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`ErrorKind::HostPanic`]: crate::errors::ErrorKind::HostPanic
+    #[cfg(feature = "std")]
+    pub fn host_panic_as_trap(&mut self, enable: bool) -> &mut Self {
+        self.host_panic_as_trap = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Config`] converts panics of host functions into traps.
+    #[cfg(feature = "std")]
+    pub(crate) fn get_host_panic_as_trap(&self) -> bool {
+        self.host_panic_as_trap
+    }
+
+    /// Makes internal allocations of the [`Engine`] fail on purpose as determined by `failures`.
+    ///
+    /// # Note
+    ///
+    /// This is meant for testing the error handling of embedders under out-of-memory
+    /// conditions and requires the `fault-injection` crate feature.
+    /// See [`AllocFailures`] for the affected allocations.
+    ///
+    /// Use [`Engine::alloc_count`] to query the number of allocations so far.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Engine::alloc_count`]: crate::Engine::alloc_count
+    #[cfg(feature = "fault-injection")]
+    pub fn alloc_failures(&mut self, failures: AllocFailures) -> &mut Self {
+        self.alloc_failures = Some(failures);
+        self
+    }
+
+    /// Returns the [`AllocFailures`] of the [`Config`] if any.
+    #[cfg(feature = "fault-injection")]
+    pub(crate) fn get_alloc_failures(&self) -> Option<AllocFailures> {
+        self.alloc_failures
+    }
+
     /// Returns the [`WasmFeatures`] represented by the [`Config`].
-    pub(crate) fn wasm_features(&self) -> WasmFeatures {
+    pub(crate) fn features(&self) -> WasmFeatures {
         WasmFeatures {
-            multi_value: self.multi_value,
             mutable_global: self.mutable_global,
-            saturating_float_to_int: self.saturating_float_to_int,
             sign_extension: self.sign_extension,
+            saturating_float_to_int: self.saturating_float_to_int,
+            multi_value: self.multi_value,
             bulk_memory: self.bulk_memory,
-            reference_types: self.reference_types,
+            reference_types: self.reference_types && cfg!(feature = "reference-types"),
             tail_call: self.tail_call,
             extended_const: self.extended_const,
-            floats: self.floats,
+            floats: self.floats && cfg!(feature = "float"),
+            stack_limits: self.stack_limits(),
+        }
+    }
+
+    /// Returns the [`wasmparser::WasmFeatures`] represented by the [`Config`].
+    #[cfg(feature = "compiler")]
+    pub(crate) fn wasm_features(&self) -> wasmparser::WasmFeatures {
+        let features = self.features();
+        wasmparser::WasmFeatures {
+            multi_value: features.multi_value,
+            mutable_global: features.mutable_global,
+            saturating_float_to_int: features.saturating_float_to_int,
+            sign_extension: features.sign_extension,
+            bulk_memory: features.bulk_memory,
+            reference_types: features.reference_types,
+            tail_call: features.tail_call,
+            extended_const: features.extended_const,
+            floats: features.floats,
             component_model: false,
             simd: false,
             relaxed_simd: false,
@@ -0,0 +1,179 @@
+//! Module-wide deduplication of function local constant values.
+
+use crate::core::UntypedValue;
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use core::{iter::Copied, mem, slice};
+
+/// The function local constant values of a compiled function.
+///
+/// # Note
+///
+/// The function local constant values are stored in reverse order
+/// of their negative register indices starting at `-1`.
+#[derive(Debug)]
+pub enum FuncConsts {
+    /// The constant values are owned by the compiled function.
+    Owned(Box<[UntypedValue]>),
+    /// The constant values are shared with all compiled functions of the same Wasm module.
+    Pooled {
+        /// The deduplicated constant values of the Wasm module.
+        pool: Arc<[UntypedValue]>,
+        /// The index of the first constant value of the compiled function within `pool`.
+        start: u32,
+        /// The number of constant values of the compiled function.
+        len: u32,
+    },
+}
+
+impl Default for FuncConsts {
+    fn default() -> Self {
+        Self::Owned(Box::from([]))
+    }
+}
+
+impl FromIterator<UntypedValue> for FuncConsts {
+    fn from_iter<T: IntoIterator<Item = UntypedValue>>(iter: T) -> Self {
+        Self::Owned(iter.into_iter().collect())
+    }
+}
+
+impl FuncConsts {
+    /// Returns the function local constant values as contiguous slice.
+    pub fn as_slice(&self) -> &[UntypedValue] {
+        match self {
+            Self::Owned(values) => values,
+            Self::Pooled { pool, start, len } => {
+                let start = *start as usize;
+                &pool[start..start + *len as usize]
+            }
+        }
+    }
+
+    /// Returns the number of function local constant values.
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Returns `true` if there are no function local constant values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the function local constant values.
+    pub fn iter(&self) -> Copied<slice::Iter<UntypedValue>> {
+        self.as_slice().iter().copied()
+    }
+
+    /// Copies the function local constant values into `cells`.
+    ///
+    /// # Panics
+    ///
+    /// If `cells` and `self` have different lengths.
+    pub fn copy_to(&self, cells: &mut [UntypedValue]) {
+        cells.copy_from_slice(self.as_slice())
+    }
+
+    /// Returns the number of bytes used to store the function local constant values.
+    ///
+    /// # Note
+    ///
+    /// For a [`FuncConsts::Pooled`] this is the share of the pool used by the compiled function.
+    pub fn size(&self) -> usize {
+        mem::size_of_val(self.as_slice())
+    }
+}
+
+/// Deduplicates the function local constant values of all compiled functions of a Wasm module.
+///
+/// # Note
+///
+/// The constant values of all compiled functions of the Wasm module are stored
+/// in a single pool that is shared by all of them. Compiled functions with equal
+/// constant values share the same contiguous slice of the pool so that they can
+/// still be copied onto the value stack at once upon a call. The pool is owned by
+/// the compiled functions that use it so that it is released together with them.
+#[derive(Debug, Default)]
+pub struct ConstPool {
+    /// The constant values of all distinct function local constant slices.
+    values: Vec<UntypedValue>,
+    /// The start indices of the distinct function local constant slices within `values`.
+    slices: BTreeMap<Box<[UntypedValue]>, u32>,
+}
+
+impl ConstPool {
+    /// Interns the constant values of `consts` and returns their start index within the [`ConstPool`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`ConstPool`] holds more than `u32::MAX` constant values.
+    pub fn intern(&mut self, consts: &[UntypedValue]) -> u32 {
+        if let Some(start) = self.slices.get(consts) {
+            return *start;
+        }
+        if let Err(error) = u32::try_from(self.values.len() + consts.len()) {
+            panic!("out of bounds index for constant value pool: {error}")
+        }
+        let start = self.values.len() as u32;
+        self.values.extend_from_slice(consts);
+        self.slices.insert(consts.into(), start);
+        start
+    }
+
+    /// Returns `true` if the [`ConstPool`] has no constant values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Finishes the [`ConstPool`] and returns the shared pool of constant values.
+    pub fn finish(self) -> Arc<[UntypedValue]> {
+        self.values.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consts(values: &[i32]) -> Box<[UntypedValue]> {
+        values.iter().copied().map(UntypedValue::from).collect()
+    }
+
+    fn values(consts: &FuncConsts) -> Vec<i32> {
+        consts.iter().map(i32::from).collect()
+    }
+
+    #[test]
+    fn intern_dedups_equal_slices() {
+        let mut pool = ConstPool::default();
+        let a = pool.intern(&consts(&[1, 2, 3]));
+        let b = pool.intern(&consts(&[3, 4]));
+        let c = pool.intern(&consts(&[1, 2, 3]));
+        assert_eq!((a, b, c), (0, 3, 0));
+        let pool = pool.finish();
+        assert_eq!(pool.len(), 5);
+        let b = FuncConsts::Pooled {
+            pool: pool.clone(),
+            start: b,
+            len: 2,
+        };
+        let c = FuncConsts::Pooled {
+            pool,
+            start: c,
+            len: 3,
+        };
+        assert_eq!(values(&b), [3, 4]);
+        assert_eq!(values(&c), [1, 2, 3]);
+        assert_eq!(c.iter().rev().map(i32::from).collect::<Vec<_>>(), [3, 2, 1]);
+        assert_eq!(c.size(), 3 * mem::size_of::<UntypedValue>());
+        let mut cells = [UntypedValue::from(0_i32); 3];
+        c.copy_to(&mut cells);
+        assert_eq!(cells.map(i32::from), [1, 2, 3]);
+    }
+
+    #[test]
+    fn empty_pool() {
+        let mut pool = ConstPool::default();
+        assert_eq!(pool.intern(&consts(&[])), 0);
+        assert!(pool.is_empty());
+    }
+}
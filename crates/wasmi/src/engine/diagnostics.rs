@@ -0,0 +1,106 @@
+use alloc::sync::Arc;
+use core::{fmt, fmt::Display};
+
+/// A non-fatal diagnostic emitted while translating a Wasm function to Wasmi bytecode.
+///
+/// Diagnostics point out constructs that translate into slower or larger Wasmi
+/// bytecode than usual. Collect them via [`Config::diagnostics_sink`].
+///
+/// [`Config::diagnostics_sink`]: crate::Config::diagnostics_sink
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The index of the translated Wasm function.
+    func_index: u32,
+    /// The kind of the [`Diagnostic`].
+    kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+    /// Creates a new [`Diagnostic`] for the function at `func_index`.
+    pub(crate) fn new(func_index: u32, kind: DiagnosticKind) -> Self {
+        Self { func_index, kind }
+    }
+
+    /// Returns the index of the Wasm function that emitted the [`Diagnostic`].
+    ///
+    /// # Note
+    ///
+    /// The index space includes imported functions.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the [`DiagnosticKind`] of the [`Diagnostic`].
+    pub fn kind(&self) -> DiagnosticKind {
+        self.kind
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "function {}: {}", self.func_index, self.kind)
+    }
+}
+
+/// The kind of a translation [`Diagnostic`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DiagnosticKind {
+    /// A `br_table` was lowered to its slow path.
+    ///
+    /// Its targets expect their branch parameters in different registers
+    /// which requires copies in each of its branch arms.
+    BranchTableSlowPath {
+        /// The number of targets of the `br_table` including its default target.
+        len_targets: usize,
+    },
+    /// A `local.set` or `local.tee` required an extra copy to preserve the old
+    /// value of a local variable that is still in use on the value stack.
+    PreservedLocal {
+        /// The index of the preserved local variable.
+        local_index: u32,
+    },
+}
+
+impl Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BranchTableSlowPath { len_targets } => write!(
+                f,
+                "br_table with {len_targets} targets lowered to slow path with per-target copies"
+            ),
+            Self::PreservedLocal { local_index } => write!(
+                f,
+                "register pressure caused an extra copy to preserve local {local_index}"
+            ),
+        }
+    }
+}
+
+/// The callback receiving all [`Diagnostic`]s of an [`Engine`].
+///
+/// [`Engine`]: crate::Engine
+#[derive(Clone)]
+pub(crate) struct DiagnosticsSink {
+    sink: Arc<dyn Fn(&Diagnostic) + Send + Sync>,
+}
+
+impl fmt::Debug for DiagnosticsSink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DiagnosticsSink").finish_non_exhaustive()
+    }
+}
+
+impl DiagnosticsSink {
+    /// Creates a new [`DiagnosticsSink`] from the given callback.
+    pub fn new(sink: impl Fn(&Diagnostic) + Send + Sync + 'static) -> Self {
+        Self {
+            sink: Arc::new(sink),
+        }
+    }
+
+    /// Emits the `diagnostic` to the [`DiagnosticsSink`].
+    pub fn emit(&self, diagnostic: Diagnostic) {
+        (self.sink)(&diagnostic)
+    }
+}
@@ -4,19 +4,24 @@ use crate::{
     core::{TrapCode, UntypedValue},
     engine::{
         bytecode::{
-            AnyConst32, BinInstr, BinInstrImm16, BlockFuel, Const16, FuncIdx, Instruction,
+            AnyConst32, BinInstr, BinInstrImm16, BlockFuel, Const16, Instruction,
             Register, RegisterSpan, UnaryInstr,
         },
         cache::InstanceCache,
-        code_map::InstructionPtr,
+        code_map::{CompiledFunc, InstructionPtr},
         executor::stack::{CallFrame, CallStack, FrameRegisters, ValueStack},
-        func_types::FuncTypeRegistry,
         CodeMap,
+        Intrinsics,
+        TrapFrame,
+        WasmBacktrace,
     },
-    store::ResourceLimiterRef,
-    Error, Func, FuncRef, StoreInner,
+    store::{ResourceLimiterRef, WasmFuncEvent},
+    Error, Func, StoreInner,
 };
 
+#[cfg(feature = "reference-types")]
+use crate::{engine::bytecode::FuncIdx, FuncRef};
+
 mod binary;
 mod branch;
 mod call;
@@ -29,6 +34,7 @@ mod memory;
 mod return_;
 mod select;
 mod store;
+#[cfg(not(all(not(feature = "reference-types"), not(feature = "bulk-table"))))]
 mod table;
 mod unary;
 
@@ -63,7 +69,7 @@ macro_rules! forward_return {
 ///
 /// A Wasm execution includes everything but host calls.
 /// In other words: Everything in between host calls is a Wasm execution.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug)]
 pub enum WasmOutcome {
     /// The Wasm execution has ended and returns to the host side.
     Return,
@@ -73,6 +79,14 @@ pub enum WasmOutcome {
         host_func: Func,
         call_kind: CallKind,
     },
+    /// The Wasm execution ran out of fuel and can be resumed after refueling.
+    ///
+    /// Carries the [`TrapCode::OutOfFuel`] error annotated with its trap location.
+    OutOfFuel(Error),
+    /// The Wasm execution exhausted its time slice and can be resumed.
+    Yield,
+    /// The Wasm execution reached the epoch deadline and the epoch deadline callback must be called.
+    EpochDeadline,
 }
 
 /// Executes compiled function instructions until either
@@ -91,11 +105,13 @@ pub fn execute_instrs<'ctx, 'engine>(
     value_stack: &'engine mut ValueStack,
     call_stack: &'engine mut CallStack,
     code_map: &'engine CodeMap,
-    func_types: &'engine FuncTypeRegistry,
+    intrinsics: &'engine Intrinsics,
     resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
 ) -> Result<WasmOutcome, Error> {
-    Executor::new(ctx, cache, value_stack, call_stack, code_map, func_types)
+    let mut executor = Executor::new(ctx, cache, value_stack, call_stack, code_map, intrinsics);
+    executor
         .execute(resource_limiter)
+        .map_err(|error| executor.handle_trap(error))
 }
 
 /// An execution context for executing a Wasmi function frame.
@@ -130,12 +146,12 @@ struct Executor<'ctx, 'engine> {
     ///
     /// This is used to lookup Wasm function information.
     code_map: &'engine CodeMap,
-    /// The Wasm function type registry.
+    /// The host intrinsics of the engine.
     ///
     /// # Note
     ///
-    /// This is used to lookup Wasm function information.
-    func_types: &'engine FuncTypeRegistry,
+    /// This is used to call host intrinsics directly.
+    intrinsics: &'engine Intrinsics,
 }
 
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
@@ -147,7 +163,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         value_stack: &'engine mut ValueStack,
         call_stack: &'engine mut CallStack,
         code_map: &'engine CodeMap,
-        func_types: &'engine FuncTypeRegistry,
+        intrinsics: &'engine Intrinsics,
     ) -> Self {
         let frame = call_stack
             .peek()
@@ -165,14 +181,14 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             value_stack,
             call_stack,
             code_map,
-            func_types,
+            intrinsics,
         }
     }
 
     /// Executes the function frame until it returns or traps.
     #[inline(always)]
     fn execute(
-        mut self,
+        &mut self,
         resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
     ) -> Result<WasmOutcome, Error> {
         use Instruction as Instr;
@@ -182,8 +198,10 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 // update the runtime signature with the current instruction
                 // we map the instruction to a unique 64-bit prime number
                 let instr_prime = match instr {
+                    #[cfg(not(all(not(feature = "reference-types"), not(feature = "bulk-table"))))]
                     Instr::TableIdx(_) => 0xf360371a61b48ca1,
                     Instr::DataSegmentIdx(_) => 0xce5750f577a4a9bd,
+                    #[cfg(feature = "bulk-table")]
                     Instr::ElementSegmentIdx(_) => 0xdb013c4da009cbe9,
                     Instr::Const32(_) => 0xe3a461c24c1edf67,
                     Instr::I64Const32(_) => 0x93e0632ef59fbf8d,
@@ -196,6 +214,8 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     Instr::CallIndirectParamsImm16(_) => 0xd853e6a184c25f0d,
                     Instr::Trap(_) => 0xb18d650b9f5998a7,
                     Instr::ConsumeFuel(_) => 0xe6118441cda42713,
+                    Instr::ConsumeInstrs(_) => 0xf7ca3354c37e3545,
+                    Instr::CheckEpoch => 0x8f16aaa4ab109f1d,
                     Instr::Return => 0xc8b8b1c1bcbd90e5,
                     Instr::ReturnReg { .. } => 0xbaab8e9341e08dbf,
                     Instr::ReturnReg2 { .. } => 0xa73d1157b48ca275,
@@ -267,19 +287,32 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     Instr::BranchI64GeSImm(_) => 0xf857874dc48a27e9,
                     Instr::BranchI64GeU(_) => 0x8b3ce0fa63214359,
                     Instr::BranchI64GeUImm(_) => 0x93f90f4418d24385,
+                    #[cfg(feature = "float")]
                     Instr::BranchF32Eq(_) => 0x8647b33a7b8d4ea9,
+                    #[cfg(feature = "float")]
                     Instr::BranchF32Ne(_) => 0x9efcbece1096b201,
+                    #[cfg(feature = "float")]
                     Instr::BranchF32Lt(_) => 0xb2ab8327611d4843,
+                    #[cfg(feature = "float")]
                     Instr::BranchF32Le(_) => 0xfdb94010ae03ebad,
+                    #[cfg(feature = "float")]
                     Instr::BranchF32Gt(_) => 0xc74489c6752ef2e3,
+                    #[cfg(feature = "float")]
                     Instr::BranchF32Ge(_) => 0xb2588add33b6dc8d,
+                    #[cfg(feature = "float")]
                     Instr::BranchF64Eq(_) => 0xb0f911188eef530b,
+                    #[cfg(feature = "float")]
                     Instr::BranchF64Ne(_) => 0xb3a436328722e3af,
+                    #[cfg(feature = "float")]
                     Instr::BranchF64Lt(_) => 0x996ae1e7999d71a5,
+                    #[cfg(feature = "float")]
                     Instr::BranchF64Le(_) => 0xb00795c450f79fd7,
+                    #[cfg(feature = "float")]
                     Instr::BranchF64Gt(_) => 0xfd0f65f70976783f,
+                    #[cfg(feature = "float")]
                     Instr::BranchF64Ge(_) => 0xab728f867409f623,
                     Instr::BranchTable { .. } => 0xe2510e47b282102d,
+                    Instr::BranchTableSparse { .. } => 0x8144d1e1eef3c323,
                     Instr::Copy { .. } => 0xf476618f2886dc2f,
                     Instr::Copy2 { .. } => 0x81e0ef8904c1cfd5,
                     Instr::CopyImm32 { .. } => 0xaafc797a3f40deeb,
@@ -299,6 +332,8 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     Instr::CallInternal { .. } => 0xab093cfe38b97547,
                     Instr::CallImported0 { .. } => 0xe866c937356994c5,
                     Instr::CallImported { .. } => 0xa9b3f7092e7cd01b,
+                    Instr::CallIntrinsic0 { .. } => 0x3c1f9a7be05d2e61,
+                    Instr::CallIntrinsic { .. } => 0x8418611d86c042d5,
                     Instr::CallIndirect0 { .. } => 0x89fdcc51af24bead,
                     Instr::CallIndirect { .. } => 0xbda3e8601077a917,
                     Instr::Select { .. } => 0xcab5aefcb578755f,
@@ -306,34 +341,63 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     Instr::SelectImm32 { .. } => 0xe640723b1c13c87f,
                     Instr::SelectI64Imm32 { .. } => 0xdcdfa8f4a8043ef7,
                     Instr::SelectF64Imm32 { .. } => 0x9bbf27a9403e07e3,
+                    #[cfg(feature = "reference-types")]
                     Instr::RefFunc { .. } => 0xd1cd7a96bb99ad23,
+                    #[cfg(feature = "reference-types")]
                     Instr::TableGet { .. } => 0x90f6c6bb3c114319,
+                    #[cfg(feature = "reference-types")]
                     Instr::TableGetImm { .. } => 0x9595b2107e23cb21,
+                    #[cfg(feature = "reference-types")]
                     Instr::TableSize { .. } => 0xd396ced918e61bc5,
+                    #[cfg(feature = "reference-types")]
                     Instr::TableSet { .. } => 0xf5b649b2b404d197,
+                    #[cfg(feature = "reference-types")]
                     Instr::TableSetAt { .. } => 0xcdebf347b50872d3,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableCopy { .. } => 0xf422dd12f6642265,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableCopyTo { .. } => 0xfe2f83b88da7fa03,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableCopyFrom { .. } => 0xfe202c3d504679e1,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableCopyFromTo { .. } => 0x9d9ebedd147ee0c3,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableCopyExact { .. } => 0x9dcc8c066927a9db,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableCopyToExact { .. } => 0xbe7fae07ca7d32ef,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableCopyFromExact { .. } => 0x88d7ecf054f2807d,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableCopyFromToExact { .. } => 0xff641f66fa9a63d3,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableInit { .. } => 0x82bb6ff383050763,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableInitTo { .. } => 0x932d2a71ef983f85,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableInitFrom { .. } => 0xd8cdfe120accedcf,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableInitFromTo { .. } => 0xebca0cc0890416c5,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableInitExact { .. } => 0xc626dd2b280ae6e3,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableInitToExact { .. } => 0xdace86517a593a71,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableInitFromExact { .. } => 0xe5c82cb9a1eac895,
+                    #[cfg(feature = "bulk-table")]
                     Instr::TableInitFromToExact { .. } => 0xe5450bc5eb2d7631,
+                    #[cfg(feature = "reference-types")]
                     Instr::TableFill { .. } => 0xe4e6730feefdc50f,
+                    #[cfg(feature = "reference-types")]
                     Instr::TableFillAt { .. } => 0xc23bd8546b888c6b,
+                    #[cfg(feature = "reference-types")]
                     Instr::TableFillExact { .. } => 0x98f8babdc99204f3,
+                    #[cfg(feature = "reference-types")]
                     Instr::TableFillAtExact { .. } => 0x8e49dd000adf0689,
+                    #[cfg(feature = "reference-types")]
                     Instr::TableGrow { .. } => 0x9e61c8c958c6b891,
+                    #[cfg(feature = "reference-types")]
                     Instr::TableGrowImm { .. } => 0x927de647f4278045,
+                    #[cfg(feature = "bulk-table")]
                     Instr::ElemDrop(_) => 0xbc4deb8b398e8a67,
                     Instr::DataDrop(_) => 0xaf73214c7ebdae49,
                     Instr::MemorySize { .. } => 0xc99e9ec6fd30df43,
@@ -373,11 +437,17 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     Instr::I64Load(_) => 0xcde7973deae4d139,
                     Instr::I64LoadAt(_) => 0xc07cc699947471df,
                     Instr::I64LoadOffset16(_) => 0xbfd2b00e2b3c39d5,
+                    #[cfg(feature = "float")]
                     Instr::F32Load(_) => 0xef1fbab218f04407,
+                    #[cfg(feature = "float")]
                     Instr::F32LoadAt(_) => 0xa8306192cd73002d,
+                    #[cfg(feature = "float")]
                     Instr::F32LoadOffset16(_) => 0xed0992f6c6239c7f,
+                    #[cfg(feature = "float")]
                     Instr::F64Load(_) => 0xf6689ac5b352c02f,
+                    #[cfg(feature = "float")]
                     Instr::F64LoadAt(_) => 0x97f205959c2a3d0b,
+                    #[cfg(feature = "float")]
                     Instr::F64LoadOffset16(_) => 0x94fbb4628a79462b,
                     Instr::I32Load8s(_) => 0xfbb04e5f0a302d7b,
                     Instr::I32Load8sAt(_) => 0x8e95f3bd70e298e7,
@@ -444,11 +514,17 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     Instr::I64Store32Offset16Imm16(_) => 0xc8435df9b5285e43,
                     Instr::I64Store32At(_) => 0xb1cb0f6ea058bbbb,
                     Instr::I64Store32AtImm16(_) => 0x8f79394f20bbda89,
+                    #[cfg(feature = "float")]
                     Instr::F32Store(_) => 0xd6df58b0ab76e99f,
+                    #[cfg(feature = "float")]
                     Instr::F32StoreOffset16(_) => 0xff1461bc14215f77,
+                    #[cfg(feature = "float")]
                     Instr::F32StoreAt(_) => 0xd2f62bd6fa3c90b9,
+                    #[cfg(feature = "float")]
                     Instr::F64Store(_) => 0xda484e6b7bd8d5db,
+                    #[cfg(feature = "float")]
                     Instr::F64StoreOffset16(_) => 0xac6256a3ca2605cb,
+                    #[cfg(feature = "float")]
                     Instr::F64StoreAt(_) => 0xe366beba3742040b,
                     Instr::I32Eq(_) => 0x9aa2499f95dc3711,
                     Instr::I32EqImm16(_) => 0x92ce6da978fdc40f,
@@ -490,17 +566,29 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     Instr::I64GeU(_) => 0x98bfaac3f19897e1,
                     Instr::I64GeSImm16(_) => 0x8956eaaa98c2e647,
                     Instr::I64GeUImm16(_) => 0xe11e8b930ba0afed,
+                    #[cfg(feature = "float")]
                     Instr::F32Eq(_) => 0xc3587b028ec7b7d7,
+                    #[cfg(feature = "float")]
                     Instr::F64Eq(_) => 0x90fa962604933679,
+                    #[cfg(feature = "float")]
                     Instr::F32Ne(_) => 0xe8b028b8a40b6323,
+                    #[cfg(feature = "float")]
                     Instr::F64Ne(_) => 0xe511c632ed75d0ad,
+                    #[cfg(feature = "float")]
                     Instr::F32Lt(_) => 0xafe8adc3497d922f,
+                    #[cfg(feature = "float")]
                     Instr::F64Lt(_) => 0xa24d51fe3b08563d,
+                    #[cfg(feature = "float")]
                     Instr::F32Le(_) => 0xa9470d623de1df2f,
+                    #[cfg(feature = "float")]
                     Instr::F64Le(_) => 0xe5e889a7f2d74d67,
+                    #[cfg(feature = "float")]
                     Instr::F32Gt(_) => 0x8cbe5aa7efd2dac5,
+                    #[cfg(feature = "float")]
                     Instr::F64Gt(_) => 0xa2b5a501d74cc69b,
+                    #[cfg(feature = "float")]
                     Instr::F32Ge(_) => 0x9103bfb43045fc5b,
+                    #[cfg(feature = "float")]
                     Instr::F64Ge(_) => 0xe4832a9c5a4a0741,
                     Instr::I32Clz(_) => 0xd0b363eee33e2a75,
                     Instr::I64Clz(_) => 0xbb13e80b90e6d539,
@@ -594,77 +682,131 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     Instr::I64RotrImm(_) => 0xdc854e900d8c8b91,
                     Instr::I32RotrImm16Rev(_) => 0xc3da2fbf5194e14f,
                     Instr::I64RotrImm16Rev(_) => 0x8ce225f0c3ba867d,
+                    #[cfg(feature = "float")]
                     Instr::F32Abs(_) => 0xcd5c2fff391d82cb,
+                    #[cfg(feature = "float")]
                     Instr::F64Abs(_) => 0xc4736057bf6ce827,
+                    #[cfg(feature = "float")]
                     Instr::F32Neg(_) => 0xd366f959bf938435,
+                    #[cfg(feature = "float")]
                     Instr::F64Neg(_) => 0x8c01a158032456c5,
+                    #[cfg(feature = "float")]
                     Instr::F32Ceil(_) => 0xf5684f567a1e5c81,
+                    #[cfg(feature = "float")]
                     Instr::F64Ceil(_) => 0xbc6729b56b5bf64f,
+                    #[cfg(feature = "float")]
                     Instr::F32Floor(_) => 0xc3397446971c7b1b,
+                    #[cfg(feature = "float")]
                     Instr::F64Floor(_) => 0xc21648fabc149443,
+                    #[cfg(feature = "float")]
                     Instr::F32Trunc(_) => 0x930c87d1457a0b2f,
+                    #[cfg(feature = "float")]
                     Instr::F64Trunc(_) => 0xc457947a5515448d,
+                    #[cfg(feature = "float")]
                     Instr::F32Nearest(_) => 0xdcbd8018d58a0133,
+                    #[cfg(feature = "float")]
                     Instr::F64Nearest(_) => 0xe719d229d8dc9d11,
+                    #[cfg(feature = "float")]
                     Instr::F32Sqrt(_) => 0x8e031a9674797f6f,
+                    #[cfg(feature = "float")]
                     Instr::F64Sqrt(_) => 0xede241e1bdbf8add,
+                    #[cfg(feature = "float")]
                     Instr::F32Add(_) => 0xc0246fd5a4fa2569,
+                    #[cfg(feature = "float")]
                     Instr::F64Add(_) => 0xae61b186b8d627b1,
+                    #[cfg(feature = "float")]
                     Instr::F32Sub(_) => 0xf37398a1108c36cb,
+                    #[cfg(feature = "float")]
                     Instr::F64Sub(_) => 0xaaf86176c0dc89f5,
+                    #[cfg(feature = "float")]
                     Instr::F32Mul(_) => 0xbe5eb79b83c0c7b1,
+                    #[cfg(feature = "float")]
                     Instr::F64Mul(_) => 0x9b200d1c1640bf0d,
+                    #[cfg(feature = "float")]
                     Instr::F32Div(_) => 0xd22d29503c878647,
+                    #[cfg(feature = "float")]
                     Instr::F64Div(_) => 0x91b08c54e524bb09,
+                    #[cfg(feature = "float")]
                     Instr::F32Min(_) => 0xf83af276dd4b617f,
+                    #[cfg(feature = "float")]
                     Instr::F64Min(_) => 0xeb5d7d82375f7be7,
+                    #[cfg(feature = "float")]
                     Instr::F32Max(_) => 0x8f4d06f60f1c84fb,
+                    #[cfg(feature = "float")]
                     Instr::F64Max(_) => 0xb24f6877be71ebd5,
+                    #[cfg(feature = "float")]
                     Instr::F32Copysign(_) => 0xec122620e993dfcd,
+                    #[cfg(feature = "float")]
                     Instr::F64Copysign(_) => 0xf27f1850006566c9,
+                    #[cfg(feature = "float")]
                     Instr::F32CopysignImm(_) => 0x94d19450082a4ce9,
+                    #[cfg(feature = "float")]
                     Instr::F64CopysignImm(_) => 0x84b094c8c0503805,
                     Instr::I32WrapI64(_) => 0xd7348da1051ffdf5,
                     Instr::I64ExtendI32S(_) => 0xbffb8ca25ae4bf8f,
                     Instr::I64ExtendI32U(_) => 0xec15704d37b95ec7,
+                    #[cfg(feature = "float")]
                     Instr::I32TruncF32S(_) => 0xa8edf1813c31175b,
+                    #[cfg(feature = "float")]
                     Instr::I32TruncF32U(_) => 0xf980305a8ba3be0f,
+                    #[cfg(feature = "float")]
                     Instr::I32TruncF64S(_) => 0xb982c8f45dcd5731,
+                    #[cfg(feature = "float")]
                     Instr::I32TruncF64U(_) => 0x9ca1670d1e934f45,
+                    #[cfg(feature = "float")]
                     Instr::I64TruncF32S(_) => 0xeb2506c7a7cfe6f7,
+                    #[cfg(feature = "float")]
                     Instr::I64TruncF32U(_) => 0xa230e0381f36668d,
+                    #[cfg(feature = "float")]
                     Instr::I64TruncF64S(_) => 0xce02765ab94df325,
+                    #[cfg(feature = "float")]
                     Instr::I64TruncF64U(_) => 0xb39253799e21a72d,
+                    #[cfg(feature = "float")]
                     Instr::I32TruncSatF32S(_) => 0xa164fb50eec581d3,
+                    #[cfg(feature = "float")]
                     Instr::I32TruncSatF32U(_) => 0xabac89637bdb1d8f,
+                    #[cfg(feature = "float")]
                     Instr::I32TruncSatF64S(_) => 0xe8b8c4421046aedd,
+                    #[cfg(feature = "float")]
                     Instr::I32TruncSatF64U(_) => 0x91c87015a56a944d,
+                    #[cfg(feature = "float")]
                     Instr::I64TruncSatF32S(_) => 0xb909e169382afddd,
+                    #[cfg(feature = "float")]
                     Instr::I64TruncSatF32U(_) => 0xc6f884d2705bf2d3,
+                    #[cfg(feature = "float")]
                     Instr::I64TruncSatF64S(_) => 0xa5e8386963664fa3,
+                    #[cfg(feature = "float")]
                     Instr::I64TruncSatF64U(_) => 0xa43800f9e4975aff,
                     Instr::I32Extend8S(_) => 0xdecfc0dc5cb809af,
                     Instr::I32Extend16S(_) => 0xcdf6bb7756026125,
                     Instr::I64Extend8S(_) => 0xb906176cee2380bf,
                     Instr::I64Extend16S(_) => 0xf0382669ed7a55f1,
                     Instr::I64Extend32S(_) => 0xb61b2de5652d06e9,
+                    #[cfg(feature = "float")]
                     Instr::F32DemoteF64(_) => 0xbf82e5dd4495233b,
+                    #[cfg(feature = "float")]
                     Instr::F64PromoteF32(_) => 0xf42a79d7ed7c17c3,
+                    #[cfg(feature = "float")]
                     Instr::F32ConvertI32S(_) => 0x9e65030287165e29,
+                    #[cfg(feature = "float")]
                     Instr::F32ConvertI32U(_) => 0xe244f0acd2209f0b,
+                    #[cfg(feature = "float")]
                     Instr::F32ConvertI64S(_) => 0xd007d6d9333c7405,
+                    #[cfg(feature = "float")]
                     Instr::F32ConvertI64U(_) => 0xf31a7af87a7b7f61,
+                    #[cfg(feature = "float")]
                     Instr::F64ConvertI32S(_) => 0xbef1a0dfa7540b4d,
+                    #[cfg(feature = "float")]
                     Instr::F64ConvertI32U(_) => 0xa168200e59e18dcd,
+                    #[cfg(feature = "float")]
                     Instr::F64ConvertI64S(_) => 0xb3a2d5946ee565e3,
+                    #[cfg(feature = "float")]
                     Instr::F64ConvertI64U(_) => 0x92ad2f2873e8fbc5,
                 };
                 self.update_runtime_signature(instr_prime);
             }
             match instr {
-                Instr::TableIdx(_)
-                | Instr::DataSegmentIdx(_)
-                | Instr::ElementSegmentIdx(_)
+                Instr::DataSegmentIdx(_)
                 | Instr::Const32(_)
                 | Instr::I64Const32(_)
                 | Instr::F64Const32(_)
@@ -674,8 +816,29 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 | Instr::RegisterList(_)
                 | Instr::CallIndirectParams(_)
                 | Instr::CallIndirectParamsImm16(_) => self.invalid_instruction_word()?,
+                #[cfg(not(all(not(feature = "reference-types"), not(feature = "bulk-table"))))]
+                Instr::TableIdx(_) => self.invalid_instruction_word()?,
+                #[cfg(feature = "bulk-table")]
+                Instr::ElementSegmentIdx(_) => self.invalid_instruction_word()?,
                 Instr::Trap(trap_code) => self.execute_trap(trap_code)?,
-                Instr::ConsumeFuel(block_fuel) => self.execute_consume_fuel(block_fuel)?,
+                Instr::ConsumeFuel(block_fuel) => {
+                    if let Err(trap_code) = self.execute_consume_fuel(block_fuel) {
+                        let error = self.handle_trap(Error::from(trap_code));
+                        return Ok(self.suspend(WasmOutcome::OutOfFuel(error)));
+                    }
+                }
+                Instr::ConsumeInstrs(block_fuel) => {
+                    if self.ctx.time_slice_exhausted() {
+                        return Ok(self.suspend(WasmOutcome::Yield));
+                    }
+                    self.execute_consume_instrs(block_fuel)?
+                }
+                Instr::CheckEpoch => {
+                    if self.ctx.epoch_deadline_callback_due() {
+                        return Ok(self.suspend(WasmOutcome::EpochDeadline));
+                    }
+                    self.execute_check_epoch()?
+                }
                 Instr::Return => {
                     forward_return!(self.execute_return())
                 }
@@ -731,6 +894,9 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 Instr::BranchTable { index, len_targets } => {
                     self.execute_branch_table(index, len_targets)
                 }
+                Instr::BranchTableSparse { index, len_runs } => {
+                    self.execute_branch_table_sparse(index, len_runs)
+                }
                 Instr::BranchCmpFallback { lhs, rhs, params } => {
                     self.execute_branch_cmp_fallback(lhs, rhs, params)
                 }
@@ -786,17 +952,29 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 Instr::BranchI64GeSImm(instr) => self.execute_branch_i64_ge_s_imm(instr),
                 Instr::BranchI64GeU(instr) => self.execute_branch_i64_ge_u(instr),
                 Instr::BranchI64GeUImm(instr) => self.execute_branch_i64_ge_u_imm(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF32Eq(instr) => self.execute_branch_f32_eq(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF32Ne(instr) => self.execute_branch_f32_ne(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF32Lt(instr) => self.execute_branch_f32_lt(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF32Le(instr) => self.execute_branch_f32_le(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF32Gt(instr) => self.execute_branch_f32_gt(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF32Ge(instr) => self.execute_branch_f32_ge(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF64Eq(instr) => self.execute_branch_f64_eq(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF64Ne(instr) => self.execute_branch_f64_ne(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF64Lt(instr) => self.execute_branch_f64_lt(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF64Le(instr) => self.execute_branch_f64_le(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF64Gt(instr) => self.execute_branch_f64_gt(instr),
+                #[cfg(feature = "float")]
                 Instr::BranchF64Ge(instr) => self.execute_branch_f64_ge(instr),
                 Instr::Copy { result, value } => self.execute_copy(result, value),
                 Instr::Copy2 { results, values } => self.execute_copy_2(results, values),
@@ -843,6 +1021,12 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 Instr::CallImported { results, func } => {
                     forward_call!(self.execute_call_imported(results, func))
                 }
+                Instr::CallIntrinsic0 { results, func } => {
+                    forward_call!(self.execute_call_intrinsic_0(results, func))
+                }
+                Instr::CallIntrinsic { results, func } => {
+                    forward_call!(self.execute_call_intrinsic(results, func))
+                }
                 Instr::CallIndirect0 { results, func_type } => {
                     forward_call!(self.execute_call_indirect_0(results, func_type))
                 }
@@ -871,78 +1055,107 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     result_or_condition,
                     lhs_or_rhs,
                 } => self.execute_select_f64imm32(result_or_condition, lhs_or_rhs),
+                #[cfg(feature = "reference-types")]
                 Instr::RefFunc { result, func } => self.execute_ref_func(result, func),
+                #[cfg(feature = "reference-types")]
                 Instr::TableGet { result, index } => self.execute_table_get(result, index)?,
+                #[cfg(feature = "reference-types")]
                 Instr::TableGetImm { result, index } => {
                     self.execute_table_get_imm(result, index)?
                 }
+                #[cfg(feature = "reference-types")]
                 Instr::TableSize { result, table } => self.execute_table_size(result, table),
+                #[cfg(feature = "reference-types")]
                 Instr::TableSet { index, value } => self.execute_table_set(index, value)?,
+                #[cfg(feature = "reference-types")]
                 Instr::TableSetAt { index, value } => self.execute_table_set_at(index, value)?,
+                #[cfg(feature = "bulk-table")]
                 Instr::TableCopy { dst, src, len } => self.execute_table_copy(dst, src, len)?,
+                #[cfg(feature = "bulk-table")]
                 Instr::TableCopyTo { dst, src, len } => {
                     self.execute_table_copy_to(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableCopyFrom { dst, src, len } => {
                     self.execute_table_copy_from(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableCopyFromTo { dst, src, len } => {
                     self.execute_table_copy_from_to(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableCopyExact { dst, src, len } => {
                     self.execute_table_copy_exact(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableCopyToExact { dst, src, len } => {
                     self.execute_table_copy_to_exact(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableCopyFromExact { dst, src, len } => {
                     self.execute_table_copy_from_exact(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableCopyFromToExact { dst, src, len } => {
                     self.execute_table_copy_from_to_exact(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableInit { dst, src, len } => self.execute_table_init(dst, src, len)?,
+                #[cfg(feature = "bulk-table")]
                 Instr::TableInitTo { dst, src, len } => {
                     self.execute_table_init_to(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableInitFrom { dst, src, len } => {
                     self.execute_table_init_from(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableInitFromTo { dst, src, len } => {
                     self.execute_table_init_from_to(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableInitExact { dst, src, len } => {
                     self.execute_table_init_exact(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableInitToExact { dst, src, len } => {
                     self.execute_table_init_to_exact(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableInitFromExact { dst, src, len } => {
                     self.execute_table_init_from_exact(dst, src, len)?
                 }
+                #[cfg(feature = "bulk-table")]
                 Instr::TableInitFromToExact { dst, src, len } => {
                     self.execute_table_init_from_to_exact(dst, src, len)?
                 }
+                #[cfg(feature = "reference-types")]
                 Instr::TableFill { dst, len, value } => self.execute_table_fill(dst, len, value)?,
+                #[cfg(feature = "reference-types")]
                 Instr::TableFillAt { dst, len, value } => {
                     self.execute_table_fill_at(dst, len, value)?
                 }
+                #[cfg(feature = "reference-types")]
                 Instr::TableFillExact { dst, len, value } => {
                     self.execute_table_fill_exact(dst, len, value)?
                 }
+                #[cfg(feature = "reference-types")]
                 Instr::TableFillAtExact { dst, len, value } => {
                     self.execute_table_fill_at_exact(dst, len, value)?
                 }
+                #[cfg(feature = "reference-types")]
                 Instr::TableGrow {
                     result,
                     delta,
                     value,
                 } => self.execute_table_grow(result, delta, value, &mut *resource_limiter)?,
+                #[cfg(feature = "reference-types")]
                 Instr::TableGrowImm {
                     result,
                     delta,
                     value,
                 } => self.execute_table_grow_imm(result, delta, value, &mut *resource_limiter)?,
+                #[cfg(feature = "bulk-table")]
                 Instr::ElemDrop(element_index) => self.execute_element_drop(element_index),
                 Instr::DataDrop(data_index) => self.execute_data_drop(data_index),
                 Instr::MemorySize { result } => self.execute_memory_size(result),
@@ -1034,11 +1247,17 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 Instr::I64Load(instr) => self.execute_i64_load(instr)?,
                 Instr::I64LoadAt(instr) => self.execute_i64_load_at(instr)?,
                 Instr::I64LoadOffset16(instr) => self.execute_i64_load_offset16(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F32Load(instr) => self.execute_f32_load(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F32LoadAt(instr) => self.execute_f32_load_at(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F32LoadOffset16(instr) => self.execute_f32_load_offset16(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F64Load(instr) => self.execute_f64_load(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F64LoadAt(instr) => self.execute_f64_load_at(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F64LoadOffset16(instr) => self.execute_f64_load_offset16(instr)?,
                 Instr::I32Load8s(instr) => self.execute_i32_load8_s(instr)?,
                 Instr::I32Load8sAt(instr) => self.execute_i32_load8_s_at(instr)?,
@@ -1119,11 +1338,17 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 }
                 Instr::I64Store32At(instr) => self.execute_i64_store32_at(instr)?,
                 Instr::I64Store32AtImm16(instr) => self.execute_i64_store32_at_imm16(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F32Store(instr) => self.execute_f32_store(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F32StoreOffset16(instr) => self.execute_f32_store_offset16(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F32StoreAt(instr) => self.execute_f32_store_at(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F64Store(instr) => self.execute_f64_store(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F64StoreOffset16(instr) => self.execute_f64_store_offset16(instr)?,
+                #[cfg(feature = "float")]
                 Instr::F64StoreAt(instr) => self.execute_f64_store_at(instr)?,
                 Instr::I32Eq(instr) => self.execute_i32_eq(instr),
                 Instr::I32EqImm16(instr) => self.execute_i32_eq_imm16(instr),
@@ -1165,17 +1390,29 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 Instr::I64GeSImm16(instr) => self.execute_i64_ge_s_imm16(instr),
                 Instr::I64GeU(instr) => self.execute_i64_ge_u(instr),
                 Instr::I64GeUImm16(instr) => self.execute_i64_ge_u_imm16(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Eq(instr) => self.execute_f32_eq(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Ne(instr) => self.execute_f32_ne(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Lt(instr) => self.execute_f32_lt(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Le(instr) => self.execute_f32_le(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Gt(instr) => self.execute_f32_gt(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Ge(instr) => self.execute_f32_ge(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Eq(instr) => self.execute_f64_eq(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Ne(instr) => self.execute_f64_ne(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Lt(instr) => self.execute_f64_lt(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Le(instr) => self.execute_f64_le(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Gt(instr) => self.execute_f64_gt(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Ge(instr) => self.execute_f64_ge(instr),
                 Instr::I32Clz(instr) => self.execute_i32_clz(instr),
                 Instr::I64Clz(instr) => self.execute_i64_clz(instr),
@@ -1269,69 +1506,125 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 Instr::I64Rotr(instr) => self.execute_i64_rotr(instr),
                 Instr::I64RotrImm(instr) => self.execute_i64_rotr_imm(instr),
                 Instr::I64RotrImm16Rev(instr) => self.execute_i64_rotr_imm16_rev(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Abs(instr) => self.execute_f32_abs(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Neg(instr) => self.execute_f32_neg(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Ceil(instr) => self.execute_f32_ceil(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Floor(instr) => self.execute_f32_floor(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Trunc(instr) => self.execute_f32_trunc(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Nearest(instr) => self.execute_f32_nearest(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Sqrt(instr) => self.execute_f32_sqrt(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Abs(instr) => self.execute_f64_abs(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Neg(instr) => self.execute_f64_neg(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Ceil(instr) => self.execute_f64_ceil(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Floor(instr) => self.execute_f64_floor(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Trunc(instr) => self.execute_f64_trunc(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Nearest(instr) => self.execute_f64_nearest(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Sqrt(instr) => self.execute_f64_sqrt(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Add(instr) => self.execute_f32_add(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Sub(instr) => self.execute_f32_sub(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Mul(instr) => self.execute_f32_mul(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Div(instr) => self.execute_f32_div(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Min(instr) => self.execute_f32_min(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Max(instr) => self.execute_f32_max(instr),
+                #[cfg(feature = "float")]
                 Instr::F32Copysign(instr) => self.execute_f32_copysign(instr),
+                #[cfg(feature = "float")]
                 Instr::F32CopysignImm(instr) => self.execute_f32_copysign_imm(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Add(instr) => self.execute_f64_add(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Sub(instr) => self.execute_f64_sub(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Mul(instr) => self.execute_f64_mul(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Div(instr) => self.execute_f64_div(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Min(instr) => self.execute_f64_min(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Max(instr) => self.execute_f64_max(instr),
+                #[cfg(feature = "float")]
                 Instr::F64Copysign(instr) => self.execute_f64_copysign(instr),
+                #[cfg(feature = "float")]
                 Instr::F64CopysignImm(instr) => self.execute_f64_copysign_imm(instr),
                 Instr::I32WrapI64(instr) => self.execute_i32_wrap_i64(instr),
                 Instr::I64ExtendI32S(instr) => self.execute_i64_extend_i32_s(instr),
                 Instr::I64ExtendI32U(instr) => self.execute_i64_extend_i32_u(instr),
+                #[cfg(feature = "float")]
                 Instr::I32TruncF32S(instr) => self.execute_i32_trunc_f32_s(instr)?,
+                #[cfg(feature = "float")]
                 Instr::I32TruncF32U(instr) => self.execute_i32_trunc_f32_u(instr)?,
+                #[cfg(feature = "float")]
                 Instr::I32TruncF64S(instr) => self.execute_i32_trunc_f64_s(instr)?,
+                #[cfg(feature = "float")]
                 Instr::I32TruncF64U(instr) => self.execute_i32_trunc_f64_u(instr)?,
+                #[cfg(feature = "float")]
                 Instr::I64TruncF32S(instr) => self.execute_i64_trunc_f32_s(instr)?,
+                #[cfg(feature = "float")]
                 Instr::I64TruncF32U(instr) => self.execute_i64_trunc_f32_u(instr)?,
+                #[cfg(feature = "float")]
                 Instr::I64TruncF64S(instr) => self.execute_i64_trunc_f64_s(instr)?,
+                #[cfg(feature = "float")]
                 Instr::I64TruncF64U(instr) => self.execute_i64_trunc_f64_u(instr)?,
+                #[cfg(feature = "float")]
                 Instr::I32TruncSatF32S(instr) => self.execute_i32_trunc_sat_f32_s(instr),
+                #[cfg(feature = "float")]
                 Instr::I32TruncSatF32U(instr) => self.execute_i32_trunc_sat_f32_u(instr),
+                #[cfg(feature = "float")]
                 Instr::I32TruncSatF64S(instr) => self.execute_i32_trunc_sat_f64_s(instr),
+                #[cfg(feature = "float")]
                 Instr::I32TruncSatF64U(instr) => self.execute_i32_trunc_sat_f64_u(instr),
+                #[cfg(feature = "float")]
                 Instr::I64TruncSatF32S(instr) => self.execute_i64_trunc_sat_f32_s(instr),
+                #[cfg(feature = "float")]
                 Instr::I64TruncSatF32U(instr) => self.execute_i64_trunc_sat_f32_u(instr),
+                #[cfg(feature = "float")]
                 Instr::I64TruncSatF64S(instr) => self.execute_i64_trunc_sat_f64_s(instr),
+                #[cfg(feature = "float")]
                 Instr::I64TruncSatF64U(instr) => self.execute_i64_trunc_sat_f64_u(instr),
                 Instr::I32Extend8S(instr) => self.execute_i32_extend8_s(instr),
                 Instr::I32Extend16S(instr) => self.execute_i32_extend16_s(instr),
                 Instr::I64Extend8S(instr) => self.execute_i64_extend8_s(instr),
                 Instr::I64Extend16S(instr) => self.execute_i64_extend16_s(instr),
                 Instr::I64Extend32S(instr) => self.execute_i64_extend32_s(instr),
+                #[cfg(feature = "float")]
                 Instr::F32DemoteF64(instr) => self.execute_f32_demote_f64(instr),
+                #[cfg(feature = "float")]
                 Instr::F64PromoteF32(instr) => self.execute_f64_promote_f32(instr),
+                #[cfg(feature = "float")]
                 Instr::F32ConvertI32S(instr) => self.execute_f32_convert_i32_s(instr),
+                #[cfg(feature = "float")]
                 Instr::F32ConvertI32U(instr) => self.execute_f32_convert_i32_u(instr),
+                #[cfg(feature = "float")]
                 Instr::F32ConvertI64S(instr) => self.execute_f32_convert_i64_s(instr),
+                #[cfg(feature = "float")]
                 Instr::F32ConvertI64U(instr) => self.execute_f32_convert_i64_u(instr),
+                #[cfg(feature = "float")]
                 Instr::F64ConvertI32S(instr) => self.execute_f64_convert_i32_s(instr),
+                #[cfg(feature = "float")]
                 Instr::F64ConvertI32U(instr) => self.execute_f64_convert_i32_u(instr),
+                #[cfg(feature = "float")]
                 Instr::F64ConvertI64S(instr) => self.execute_f64_convert_i64_s(instr),
+                #[cfg(feature = "float")]
                 Instr::F64ConvertI64U(instr) => self.execute_f64_convert_i64_u(instr),
             }
         }
@@ -1363,6 +1656,57 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.next_instr_at(1)
     }
 
+    /// Notifies the Wasm function hook of the [`StoreInner`] that `func` has been entered or exited.
+    #[inline(always)]
+    fn notify_wasm_func(&self, func: CompiledFunc, event: WasmFuncEvent) {
+        if !self.ctx.has_wasm_func_hook() {
+            return;
+        }
+        if let Some(entity) = self.code_map.get_compiled(func) {
+            self.ctx.notify_wasm_func(entity.func_index(), event);
+        }
+    }
+
+    /// Handles a trap raised within the current [`CallFrame`].
+    ///
+    /// Annotates `error` with the function index and Wasm offset of the current
+    /// [`CallFrame`] and notifies the trap hook of the [`StoreInner`] if any.
+    #[cold]
+    fn handle_trap(&self, error: Error) -> Error {
+        let Some(frame) = self.call_stack.peek() else {
+            return error;
+        };
+        let Some(func) = self.code_map.get_compiled(frame.func()) else {
+            return error;
+        };
+        let wasm_offset = func.address_map().and_then(|address_map| {
+            let instr = self.ip.index_in(func.instrs())?;
+            address_map.wasm_offset(u32::try_from(instr).ok()?)
+        });
+        if error.func_index().is_none() {
+            if let Some(trap_code) = error.as_trap_code() {
+                self.ctx.engine().counters().trapped(trap_code);
+            }
+        }
+        let error = error.with_trap_location(func.func_index(), wasm_offset);
+        if self.ctx.has_trap_hook() {
+            let base = usize::from(frame.base_offset());
+            let len_cells = usize::from(func.len_cells());
+            let registers = self
+                .value_stack
+                .as_slice()
+                .get(base..base + len_cells)
+                .unwrap_or_default();
+            let depth = self.call_stack.len();
+            let backtrace =
+                WasmBacktrace::capture(self.code_map, self.ctx, self.call_stack, Some(self.ip));
+            let trap_frame =
+                TrapFrame::new(&error, *frame.instance(), depth, registers, backtrace);
+            self.ctx.notify_trap(&trap_frame);
+        }
+        error
+    }
+
     /// Shifts the instruction pointer to the next instruction.
     ///
     /// Has a parameter `skip` to denote how many instruction words
@@ -1473,6 +1817,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a fallible generic unary [`Instruction`].
+    #[cfg(feature = "float")]
     fn try_execute_unary(
         &mut self,
         instr: UnaryInstr,
@@ -1642,17 +1987,54 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
 
     /// Executes an [`Instruction::ConsumeFuel`].
     #[inline(always)]
-    fn execute_consume_fuel(&mut self, block_fuel: BlockFuel) -> Result<(), Error> {
+    fn execute_consume_fuel(&mut self, block_fuel: BlockFuel) -> Result<(), TrapCode> {
         // We do not have to check if fuel metering is enabled since
         // [`Instruction::ConsumeFuel`] are only generated if fuel metering
         // is enabled to begin with.
         self.ctx
-            .fuel_mut()
-            .consume_fuel_unchecked(block_fuel.to_u64())?;
+            .consume_block_fuel_unchecked(self.cache.instance(), block_fuel.to_u64())?;
+        self.next_instr();
+        Ok(())
+    }
+
+    /// Suspends the execution at the current instruction with the given `outcome`.
+    ///
+    /// # Note
+    ///
+    /// This is used at [`Instruction::ConsumeFuel`] upon running out of fuel,
+    /// at [`Instruction::ConsumeInstrs`] upon exhausting the time slice and
+    /// at [`Instruction::CheckEpoch`] upon reaching the epoch deadline.
+    /// The current [`CallFrame`] is updated to point to the current instruction
+    /// so that it is executed again when the execution is resumed.
+    #[cold]
+    fn suspend(&mut self, outcome: WasmOutcome) -> WasmOutcome {
+        self.call_stack
+            .peek_mut()
+            .expect("must have call frame on the call stack")
+            .update_instr_ptr(self.ip);
+        outcome
+    }
+
+    /// Executes an [`Instruction::ConsumeInstrs`].
+    #[inline(always)]
+    fn execute_consume_instrs(&mut self, block_fuel: BlockFuel) -> Result<(), Error> {
+        // Same as with [`Instruction::ConsumeFuel`] we do not have to check if
+        // instruction budget metering is enabled.
+        self.ctx
+            .consume_instructions_unchecked(block_fuel.to_u64())?;
+        self.try_next_instr()
+    }
+
+    /// Executes an [`Instruction::CheckEpoch`].
+    #[inline(always)]
+    fn execute_check_epoch(&mut self) -> Result<(), Error> {
+        self.ctx.check_epoch_deadline()?;
+        self.ctx.check_interrupt()?;
         self.try_next_instr()
     }
 
     /// Executes an [`Instruction::RefFunc`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     fn execute_ref_func(&mut self, result: Register, func_index: FuncIdx) {
         let func = self.cache.get_func(self.ctx, func_index);
@@ -1,17 +1,21 @@
 use super::{Executor, UntypedValueExt};
 use crate::{
     core::{TrapCode, UntypedValue},
-    engine::bytecode::{BinInstr, BinInstrImm, BinInstrImm16, Sign},
+    engine::bytecode::{BinInstr, BinInstrImm16},
     Error,
 };
 use core::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
 
+#[cfg(feature = "float")]
+use crate::engine::bytecode::{BinInstrImm, Sign};
+
 #[cfg(doc)]
 use crate::engine::bytecode::Instruction;
 
 macro_rules! impl_binary {
-    ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
+    ( $( $( #[$attr:meta] )* (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
+            $( #[$attr] )*
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
             #[inline(always)]
             pub fn $fn_name(&mut self, instr: BinInstr) {
@@ -51,20 +55,34 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         (Instruction::I64Rotl, execute_i64_rotl, UntypedValue::i64_rotl),
         (Instruction::I64Rotr, execute_i64_rotr, UntypedValue::i64_rotr),
 
+        #[cfg(feature = "float")]
         (Instruction::F32Add, execute_f32_add, UntypedValue::f32_add),
+        #[cfg(feature = "float")]
         (Instruction::F32Sub, execute_f32_sub, UntypedValue::f32_sub),
+        #[cfg(feature = "float")]
         (Instruction::F32Mul, execute_f32_mul, UntypedValue::f32_mul),
+        #[cfg(feature = "float")]
         (Instruction::F32Div, execute_f32_div, UntypedValue::f32_div),
+        #[cfg(feature = "float")]
         (Instruction::F32Min, execute_f32_min, UntypedValue::f32_min),
+        #[cfg(feature = "float")]
         (Instruction::F32Max, execute_f32_max, UntypedValue::f32_max),
+        #[cfg(feature = "float")]
         (Instruction::F32Copysign, execute_f32_copysign, UntypedValue::f32_copysign),
 
+        #[cfg(feature = "float")]
         (Instruction::F64Add, execute_f64_add, UntypedValue::f64_add),
+        #[cfg(feature = "float")]
         (Instruction::F64Sub, execute_f64_sub, UntypedValue::f64_sub),
+        #[cfg(feature = "float")]
         (Instruction::F64Mul, execute_f64_mul, UntypedValue::f64_mul),
+        #[cfg(feature = "float")]
         (Instruction::F64Div, execute_f64_div, UntypedValue::f64_div),
+        #[cfg(feature = "float")]
         (Instruction::F64Min, execute_f64_min, UntypedValue::f64_min),
+        #[cfg(feature = "float")]
         (Instruction::F64Max, execute_f64_max, UntypedValue::f64_max),
+        #[cfg(feature = "float")]
         (Instruction::F64Copysign, execute_f64_copysign, UntypedValue::f64_copysign),
     }
 }
@@ -303,6 +321,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 }
 
+#[cfg(feature = "float")]
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// Executes an [`Instruction::F32CopysignImm`].
     #[inline(always)]
@@ -54,6 +54,35 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.ip.add(normalized_index as usize);
     }
 
+    #[inline(always)]
+    pub fn execute_branch_table_sparse(&mut self, index: Register, len_runs: Const32<u32>) {
+        let index: u32 = self.get_register_as(index);
+        let len_runs = u32::from(len_runs) as usize;
+        // Check if the next instruction is a copy instruction and execute it if so.
+        self.ip.add(1);
+        self.execute_optional_copy_instr();
+        // Binary search for the run containing `index` among the `len_runs - 1` run bounds.
+        // Indices beyond the last bound always fall into the last run holding the default target.
+        let bounds = self.ip;
+        let (mut lo, mut hi) = (0, len_runs - 1);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let mut ip = bounds;
+            ip.add(mid);
+            let bound = match *ip.get() {
+                Instruction::Const32(bound) => u32::from(bound),
+                _ => unreachable!("expected an Instruction::Const32 instruction word"),
+            };
+            if index < bound {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        // Update `pc`:
+        self.ip.add(len_runs - 1 + lo);
+    }
+
     /// Executes an optional copy instruction at `ip`.
     ///
     /// Does nothing if there is no `copy` instruction at `ip`.
@@ -196,9 +225,10 @@ fn cmp_i32_xor_eqz(a: i32, b: i32) -> bool {
 }
 
 macro_rules! impl_execute_branch_binop {
-    ( $( ($ty:ty, Instruction::$op_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
+    ( $( $( #[$attr:meta] )* ($ty:ty, Instruction::$op_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             $(
+                $( #[$attr] )*
                 #[doc = concat!("Executes an [`Instruction::", stringify!($op_name), "`].")]
                 #[inline(always)]
                 pub fn $fn_name(&mut self, instr: BranchBinOpInstr) {
@@ -237,18 +267,30 @@ impl_execute_branch_binop! {
     (i64, Instruction::BranchI64GeS, execute_branch_i64_ge_s, cmp_ge),
     (u64, Instruction::BranchI64GeU, execute_branch_i64_ge_u, cmp_ge),
 
+    #[cfg(feature = "float")]
     (f32, Instruction::BranchF32Eq, execute_branch_f32_eq, cmp_eq),
+    #[cfg(feature = "float")]
     (f32, Instruction::BranchF32Ne, execute_branch_f32_ne, cmp_ne),
+    #[cfg(feature = "float")]
     (f32, Instruction::BranchF32Lt, execute_branch_f32_lt, cmp_lt),
+    #[cfg(feature = "float")]
     (f32, Instruction::BranchF32Le, execute_branch_f32_le, cmp_le),
+    #[cfg(feature = "float")]
     (f32, Instruction::BranchF32Gt, execute_branch_f32_gt, cmp_gt),
+    #[cfg(feature = "float")]
     (f32, Instruction::BranchF32Ge, execute_branch_f32_ge, cmp_ge),
 
+    #[cfg(feature = "float")]
     (f64, Instruction::BranchF64Eq, execute_branch_f64_eq, cmp_eq),
+    #[cfg(feature = "float")]
     (f64, Instruction::BranchF64Ne, execute_branch_f64_ne, cmp_ne),
+    #[cfg(feature = "float")]
     (f64, Instruction::BranchF64Lt, execute_branch_f64_lt, cmp_lt),
+    #[cfg(feature = "float")]
     (f64, Instruction::BranchF64Le, execute_branch_f64_le, cmp_le),
+    #[cfg(feature = "float")]
     (f64, Instruction::BranchF64Gt, execute_branch_f64_gt, cmp_gt),
+    #[cfg(feature = "float")]
     (f64, Instruction::BranchF64Ge, execute_branch_f64_ge, cmp_ge),
 }
 
@@ -331,17 +373,29 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             C::I64GtU => self.execute_branch_binop_raw::<u64>(lhs, rhs, offset, cmp_gt),
             C::I64GeS => self.execute_branch_binop_raw::<i64>(lhs, rhs, offset, cmp_ge),
             C::I64GeU => self.execute_branch_binop_raw::<u64>(lhs, rhs, offset, cmp_ge),
+            #[cfg(feature = "float")]
             C::F32Eq => self.execute_branch_binop_raw::<f32>(lhs, rhs, offset, cmp_eq),
+            #[cfg(feature = "float")]
             C::F32Ne => self.execute_branch_binop_raw::<f32>(lhs, rhs, offset, cmp_ne),
+            #[cfg(feature = "float")]
             C::F32Lt => self.execute_branch_binop_raw::<f32>(lhs, rhs, offset, cmp_lt),
+            #[cfg(feature = "float")]
             C::F32Le => self.execute_branch_binop_raw::<f32>(lhs, rhs, offset, cmp_le),
+            #[cfg(feature = "float")]
             C::F32Gt => self.execute_branch_binop_raw::<f32>(lhs, rhs, offset, cmp_gt),
+            #[cfg(feature = "float")]
             C::F32Ge => self.execute_branch_binop_raw::<f32>(lhs, rhs, offset, cmp_ge),
+            #[cfg(feature = "float")]
             C::F64Eq => self.execute_branch_binop_raw::<f64>(lhs, rhs, offset, cmp_eq),
+            #[cfg(feature = "float")]
             C::F64Ne => self.execute_branch_binop_raw::<f64>(lhs, rhs, offset, cmp_ne),
+            #[cfg(feature = "float")]
             C::F64Lt => self.execute_branch_binop_raw::<f64>(lhs, rhs, offset, cmp_lt),
+            #[cfg(feature = "float")]
             C::F64Le => self.execute_branch_binop_raw::<f64>(lhs, rhs, offset, cmp_le),
+            #[cfg(feature = "float")]
             C::F64Gt => self.execute_branch_binop_raw::<f64>(lhs, rhs, offset, cmp_gt),
+            #[cfg(feature = "float")]
             C::F64Ge => self.execute_branch_binop_raw::<f64>(lhs, rhs, offset, cmp_ge),
         };
     }
@@ -1,19 +1,21 @@
 use super::Executor;
 use crate::{
-    core::TrapCode,
+    core::{TrapCode, UntypedValue},
     engine::{
         bytecode::{FuncIdx, Instruction, Register, RegisterSpan, SignatureIdx, TableIdx},
         code_map::InstructionPtr,
         executor::stack::{CallFrame, FrameRegisters, Stack},
         CompiledFunc,
         CompiledFuncEntity,
+        MAX_INTRINSIC_INOUT,
     },
     func::FuncEntity,
+    store::WasmFuncEvent,
     Error,
     Func,
     FuncRef,
 };
-use core::slice;
+use core::{cmp, slice};
 
 /// Describes whether a `call` instruction has at least one parameter or none.
 #[derive(Debug, Copy, Clone)]
@@ -105,11 +107,12 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     fn dispatch_compiled_func(
         &mut self,
         results: RegisterSpan,
-        func: &CompiledFuncEntity,
+        func: CompiledFunc,
+        entity: &CompiledFuncEntity,
     ) -> Result<CallFrame, Error> {
-        let instrs = func.instrs();
+        let instrs = entity.instrs();
         let instr_ptr = InstructionPtr::new(instrs.as_ptr());
-        let (base_ptr, frame_ptr) = self.value_stack.alloc_call_frame(func)?;
+        let (base_ptr, frame_ptr) = self.value_stack.alloc_call_frame(entity)?;
         // We have to reinstantiate the `self.sp` [`FrameRegisters`] since we just called
         // [`ValueStack::alloc_call_frame`] which might invalidate all live [`FrameRegisters`].
         let caller = self
@@ -119,7 +122,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         // Safety: We use the base offset of a live call frame on the call stack.
         self.sp = unsafe { self.value_stack.stack_ptr_at(caller.base_offset()) };
         let instance = caller.instance();
-        let frame = CallFrame::new(instr_ptr, frame_ptr, base_ptr, results, *instance);
+        let frame = CallFrame::new(func, instr_ptr, frame_ptr, base_ptr, results, *instance);
         Ok(frame)
     }
 
@@ -163,6 +166,36 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         ip
     }
 
+    /// Copies the parameters of an [`Instruction::CallIntrinsic`] into `params`.
+    ///
+    /// Returns the [`InstructionPtr`] pointing to the last parameter [`Instruction`].
+    fn copy_intrinsic_params(&self, params: &mut [UntypedValue]) -> InstructionPtr {
+        let mut params = params.iter_mut();
+        let mut ip = self.ip;
+        let mut copy_params = |values: &[Register]| {
+            for (param, value) in (&mut params).zip(values) {
+                *param = self.get_register(*value);
+            }
+        };
+        ip.add(1);
+        while let Instruction::RegisterList(values) = ip.get() {
+            copy_params(values);
+            ip.add(1);
+        }
+        let values = match ip.get() {
+            Instruction::Register(value) => slice::from_ref(value),
+            Instruction::Register2(values) => values,
+            Instruction::Register3(values) => values,
+            unexpected => {
+                unreachable!(
+                    "unexpected Instruction found while copying intrinsic parameters: {unexpected:?}"
+                )
+            }
+        };
+        copy_params(values);
+        ip
+    }
+
     /// Prepares a [`CompiledFunc`] call with optional [`CallParams`].
     #[inline(always)]
     fn prepare_compiled_func_call(
@@ -172,8 +205,8 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         params: CallParams,
         call_kind: CallKind,
     ) -> Result<(), Error> {
-        let func = self.code_map.get(Some(self.ctx.fuel_mut()), func)?;
-        let mut called = self.dispatch_compiled_func(results, func)?;
+        let entity = self.code_map.get(Some(self.ctx.fuel_mut()), func)?;
+        let mut called = self.dispatch_compiled_func(results, func, entity)?;
         if let CallParams::Some = params {
             let called_sp = self.frame_stack_ptr(&called);
             self.ip = self.copy_call_params(called_sp);
@@ -184,6 +217,9 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 self.update_instr_ptr_at(1);
             }
             CallKind::Tail => {
+                if let Some(caller) = self.call_stack.peek() {
+                    self.notify_wasm_func(caller.func(), WasmFuncEvent::Exit);
+                }
                 // In case of a tail call we have to remove the caller call frame after
                 // allocating the callee call frame. This moves all cells of the callee frame
                 // and may invalidate pointers to it.
@@ -199,6 +235,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         }
         self.init_call_frame(&called);
         self.call_stack.push(called)?;
+        self.notify_wasm_func(func, WasmFuncEvent::Enter);
         Ok(())
     }
 
@@ -282,6 +319,60 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.execute_call_imported_impl(results, &func, params, CallKind::Tail)
     }
 
+    /// Executes an [`Instruction::CallIntrinsic0`].
+    #[inline(always)]
+    pub fn execute_call_intrinsic_0(
+        &mut self,
+        results: RegisterSpan,
+        func: FuncIdx,
+    ) -> Result<CallOutcome, Error> {
+        self.execute_call_intrinsic_impl(results, func, CallParams::None)
+    }
+
+    /// Executes an [`Instruction::CallIntrinsic`].
+    #[inline(always)]
+    pub fn execute_call_intrinsic(
+        &mut self,
+        results: RegisterSpan,
+        func: FuncIdx,
+    ) -> Result<CallOutcome, Error> {
+        self.execute_call_intrinsic_impl(results, func, CallParams::Some)
+    }
+
+    /// Executes an [`Instruction::CallIntrinsic0`] or [`Instruction::CallIntrinsic`].
+    ///
+    /// Falls back to a generic imported call if `func` did not resolve to an intrinsic.
+    fn execute_call_intrinsic_impl(
+        &mut self,
+        results: RegisterSpan,
+        func: FuncIdx,
+        params: CallParams,
+    ) -> Result<CallOutcome, Error> {
+        let func = self.cache.get_func(self.ctx, func);
+        let intrinsic = match self.ctx.resolve_func(&func) {
+            FuncEntity::Host(host_func) => host_func.intrinsic(),
+            FuncEntity::Wasm(_) => None,
+        };
+        let Some(intrinsic) = intrinsic else {
+            // Case: The import resolved to a definition other than the intrinsic.
+            return self.execute_call_imported_impl(results, &func, params, CallKind::Nested);
+        };
+        let intrinsic = self.intrinsics.resolve(intrinsic);
+        let len_params = intrinsic.len_params();
+        let len_results = intrinsic.len_results();
+        let mut buffer = [UntypedValue::default(); MAX_INTRINSIC_INOUT];
+        if matches!(params, CallParams::Some) {
+            self.ip = self.copy_intrinsic_params(&mut buffer[..len_params]);
+        }
+        let len_inout = cmp::max(len_params, len_results);
+        intrinsic.intrinsic().call(&mut buffer[..len_inout])?;
+        for (result, value) in results.iter(len_results).zip(&buffer[..len_results]) {
+            self.set_register(result, *value);
+        }
+        self.try_next_instr()?;
+        Ok(CallOutcome::Continue)
+    }
+
     /// Executes an [`Instruction::CallImported0`].
     #[inline(never)]
     pub fn execute_call_imported_0(
@@ -320,13 +411,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 Ok(CallOutcome::Continue)
             }
             FuncEntity::Host(host_func) => {
-                let (input_types, output_types) = self
-                    .func_types
-                    .resolve_func_type(host_func.ty_dedup())
-                    .params_results();
-                let len_params = input_types.len();
-                let len_results = output_types.len();
-                let max_inout = len_params.max(len_results);
+                let max_inout = host_func.arity().max_inout();
                 self.value_stack.reserve(max_inout)?;
                 // We have to reinstantiate the `self.sp` [`FrameRegisters`] since we just called
                 // [`ValueStack::reserve`] which might invalidate all live [`FrameRegisters`].
@@ -8,8 +8,9 @@ use crate::{
 use crate::engine::bytecode::Instruction;
 
 macro_rules! impl_comparison {
-    ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
+    ( $( $( #[$attr:meta] )* (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
+            $( #[$attr] )*
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
             #[inline(always)]
             pub fn $fn_name(&mut self, instr: BinInstr) {
@@ -43,18 +44,30 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         (Instruction::I64GeS, execute_i64_ge_s, UntypedValue::i64_ge_s),
         (Instruction::I64GeU, execute_i64_ge_u, UntypedValue::i64_ge_u),
 
+        #[cfg(feature = "float")]
         (Instruction::F32Eq, execute_f32_eq, UntypedValue::f32_eq),
+        #[cfg(feature = "float")]
         (Instruction::F32Ne, execute_f32_ne, UntypedValue::f32_ne),
+        #[cfg(feature = "float")]
         (Instruction::F32Lt, execute_f32_lt, UntypedValue::f32_lt),
+        #[cfg(feature = "float")]
         (Instruction::F32Le, execute_f32_le, UntypedValue::f32_le),
+        #[cfg(feature = "float")]
         (Instruction::F32Gt, execute_f32_gt, UntypedValue::f32_gt),
+        #[cfg(feature = "float")]
         (Instruction::F32Ge, execute_f32_ge, UntypedValue::f32_ge),
 
+        #[cfg(feature = "float")]
         (Instruction::F64Eq, execute_f64_eq, UntypedValue::f64_eq),
+        #[cfg(feature = "float")]
         (Instruction::F64Ne, execute_f64_ne, UntypedValue::f64_ne),
+        #[cfg(feature = "float")]
         (Instruction::F64Lt, execute_f64_lt, UntypedValue::f64_lt),
+        #[cfg(feature = "float")]
         (Instruction::F64Le, execute_f64_le, UntypedValue::f64_le),
+        #[cfg(feature = "float")]
         (Instruction::F64Gt, execute_f64_gt, UntypedValue::f64_gt),
+        #[cfg(feature = "float")]
         (Instruction::F64Ge, execute_f64_ge, UntypedValue::f64_ge),
     }
 }
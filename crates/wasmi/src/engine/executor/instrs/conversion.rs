@@ -1,12 +1,16 @@
 use super::Executor;
-use crate::{core::UntypedValue, engine::bytecode::UnaryInstr, Error};
+use crate::{core::UntypedValue, engine::bytecode::UnaryInstr};
+
+#[cfg(feature = "float")]
+use crate::Error;
 
 #[cfg(doc)]
 use crate::engine::bytecode::Instruction;
 
 macro_rules! impl_conversion_impls {
-    ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
+    ( $( $( #[$attr:meta] )* (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
+            $( #[$attr] )*
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
             #[inline(always)]
             pub fn $fn_name(&mut self, instr: UnaryInstr) {
@@ -17,8 +21,9 @@ macro_rules! impl_conversion_impls {
 }
 
 macro_rules! impl_fallible_conversion_impls {
-    ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
+    ( $( $( #[$attr:meta] )* (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
+            $( #[$attr] )*
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
             #[inline(always)]
             pub fn $fn_name(&mut self, instr: UnaryInstr) -> Result<(), Error> {
@@ -34,13 +39,21 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         (Instruction::I64ExtendI32S, execute_i64_extend_i32_s, UntypedValue::i64_extend_i32_s),
         (Instruction::I64ExtendI32U, execute_i64_extend_i32_u, UntypedValue::i64_extend_i32_u),
 
+        #[cfg(feature = "float")]
         (Instruction::I32TruncSatF32S, execute_i32_trunc_sat_f32_s, UntypedValue::i32_trunc_sat_f32_s),
+        #[cfg(feature = "float")]
         (Instruction::I32TruncSatF32U, execute_i32_trunc_sat_f32_u, UntypedValue::i32_trunc_sat_f32_u),
+        #[cfg(feature = "float")]
         (Instruction::I32TruncSatF64S, execute_i32_trunc_sat_f64_s, UntypedValue::i32_trunc_sat_f64_s),
+        #[cfg(feature = "float")]
         (Instruction::I32TruncSatF64U, execute_i32_trunc_sat_f64_u, UntypedValue::i32_trunc_sat_f64_u),
+        #[cfg(feature = "float")]
         (Instruction::I64TruncSatF32S, execute_i64_trunc_sat_f32_s, UntypedValue::i64_trunc_sat_f32_s),
+        #[cfg(feature = "float")]
         (Instruction::I64TruncSatF32U, execute_i64_trunc_sat_f32_u, UntypedValue::i64_trunc_sat_f32_u),
+        #[cfg(feature = "float")]
         (Instruction::I64TruncSatF64S, execute_i64_trunc_sat_f64_s, UntypedValue::i64_trunc_sat_f64_s),
+        #[cfg(feature = "float")]
         (Instruction::I64TruncSatF64U, execute_i64_trunc_sat_f64_u, UntypedValue::i64_trunc_sat_f64_u),
 
         (Instruction::I32Extend8S, execute_i32_extend8_s, UntypedValue::i32_extend8_s),
@@ -49,27 +62,45 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         (Instruction::I64Extend16S, execute_i64_extend16_s, UntypedValue::i64_extend16_s),
         (Instruction::I64Extend32S, execute_i64_extend32_s, UntypedValue::i64_extend32_s),
 
+        #[cfg(feature = "float")]
         (Instruction::F32DemoteF64, execute_f32_demote_f64, UntypedValue::f32_demote_f64),
+        #[cfg(feature = "float")]
         (Instruction::F64PromoteF32, execute_f64_promote_f32, UntypedValue::f64_promote_f32),
 
+        #[cfg(feature = "float")]
         (Instruction::F32ConvertI32S, execute_f32_convert_i32_s, UntypedValue::f32_convert_i32_s),
+        #[cfg(feature = "float")]
         (Instruction::F32ConvertI32U, execute_f32_convert_i32_u, UntypedValue::f32_convert_i32_u),
+        #[cfg(feature = "float")]
         (Instruction::F32ConvertI64S, execute_f32_convert_i64_s, UntypedValue::f32_convert_i64_s),
+        #[cfg(feature = "float")]
         (Instruction::F32ConvertI64U, execute_f32_convert_i64_u, UntypedValue::f32_convert_i64_u),
+        #[cfg(feature = "float")]
         (Instruction::F64ConvertI32S, execute_f64_convert_i32_s, UntypedValue::f64_convert_i32_s),
+        #[cfg(feature = "float")]
         (Instruction::F64ConvertI32U, execute_f64_convert_i32_u, UntypedValue::f64_convert_i32_u),
+        #[cfg(feature = "float")]
         (Instruction::F64ConvertI64S, execute_f64_convert_i64_s, UntypedValue::f64_convert_i64_s),
+        #[cfg(feature = "float")]
         (Instruction::F64ConvertI64U, execute_f64_convert_i64_u, UntypedValue::f64_convert_i64_u),
     }
 
     impl_fallible_conversion_impls! {
+        #[cfg(feature = "float")]
         (Instruction::I32TruncF32S, execute_i32_trunc_f32_s, UntypedValue::i32_trunc_f32_s),
+        #[cfg(feature = "float")]
         (Instruction::I32TruncF32U, execute_i32_trunc_f32_u, UntypedValue::i32_trunc_f32_u),
+        #[cfg(feature = "float")]
         (Instruction::I32TruncF64S, execute_i32_trunc_f64_s, UntypedValue::i32_trunc_f64_s),
+        #[cfg(feature = "float")]
         (Instruction::I32TruncF64U, execute_i32_trunc_f64_u, UntypedValue::i32_trunc_f64_u),
+        #[cfg(feature = "float")]
         (Instruction::I64TruncF32S, execute_i64_trunc_f32_s, UntypedValue::i64_trunc_f32_s),
+        #[cfg(feature = "float")]
         (Instruction::I64TruncF32U, execute_i64_trunc_f32_u, UntypedValue::i64_trunc_f32_u),
+        #[cfg(feature = "float")]
         (Instruction::I64TruncF64S, execute_i64_trunc_f64_s, UntypedValue::i64_trunc_f64_s),
+        #[cfg(feature = "float")]
         (Instruction::I64TruncF64U, execute_i64_trunc_f64_u, UntypedValue::i64_trunc_f64_u),
     }
 }
@@ -66,18 +66,11 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// # Note
     ///
     /// - This instruction assumes that `results` and `values` _do_ overlap
-    ///   and thus requires a costly temporary buffer to avoid overwriting
-    ///   intermediate copy results.
+    ///   which is handled by copying all values with a single `memmove`.
     /// - If `results` and `values` do _not_ overlap [`Instruction::CopySpanNonOverlapping`] is used.
     #[inline(always)]
     pub fn execute_copy_span(&mut self, results: RegisterSpan, values: RegisterSpan, len: u16) {
-        let results = results.iter_u16(len);
-        let values = values.iter_u16(len);
-        let mut tmp = <SmallVec<[UntypedValue; 8]>>::default();
-        tmp.extend(values.into_iter().map(|value| self.get_register(value)));
-        for (result, value) in results.into_iter().zip(tmp) {
-            self.set_register(result, value);
-        }
+        self.copy_span(results, values, len);
         self.next_instr();
     }
 
@@ -86,8 +79,12 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// # Note
     ///
     /// - This instruction assumes that `results` and `values` do _not_ overlap
-    ///   and thus can copy all the elements without a costly temporary buffer.
+    ///   in the sense of [`RegisterSpanIter::has_overlapping_copies`].
+    /// - The underlying memory of `results` and `values` may still overlap,
+    ///   e.g. for `[ 0 <- 1, 1 <- 2 ]`, which is why it also uses a `memmove`.
     /// - If `results` and `values` _do_ overlap [`Instruction::CopySpan`] is used.
+    ///
+    /// [`RegisterSpanIter::has_overlapping_copies`]: crate::engine::bytecode::RegisterSpanIter::has_overlapping_copies
     #[inline(always)]
     pub fn execute_copy_span_non_overlapping(
         &mut self,
@@ -95,15 +92,17 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         values: RegisterSpan,
         len: u16,
     ) {
-        let results = results.iter_u16(len);
-        let values = values.iter_u16(len);
-        for (result, value) in results.into_iter().zip(values.into_iter()) {
-            let value = self.get_register(value);
-            self.set_register(result, value);
-        }
+        self.copy_span(results, values, len);
         self.next_instr();
     }
 
+    /// Copies the `len` values of the `values` span to the `results` span.
+    #[inline(always)]
+    fn copy_span(&mut self, results: RegisterSpan, values: RegisterSpan, len: u16) {
+        // Safety: the translator only emits register spans that are in bounds of the call frame.
+        unsafe { self.sp.copy_span(results, values, len) };
+    }
+
     /// Executes an [`Instruction::CopyMany`].
     #[inline(always)]
     pub fn execute_copy_many(&mut self, results: RegisterSpan, values: [Register; 2]) {
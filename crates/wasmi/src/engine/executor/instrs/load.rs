@@ -78,6 +78,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
 
 macro_rules! impl_execute_load {
     ( $(
+        $( #[$attr:meta] )*
         (
             (Instruction::$var_load:expr, $fn_load:ident),
             (Instruction::$var_load_at:expr, $fn_load_at:ident),
@@ -86,18 +87,23 @@ macro_rules! impl_execute_load {
         )
     ),* $(,)? ) => {
         $(
+            $( #[$attr] )*
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_load), "`].")]
             #[inline(always)]
             pub fn $fn_load(&mut self, instr: LoadInstr) -> Result<(), Error> {
                 self.execute_load_impl(instr, $impl_fn)
             }
 
+
+            $( #[$attr] )*
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_load_at), "`].")]
             #[inline(always)]
             pub fn $fn_load_at(&mut self, instr: LoadAtInstr) -> Result<(), Error> {
                 self.execute_load_at_impl(instr, $impl_fn)
             }
 
+
+            $( #[$attr] )*
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_load_off16), "`].")]
             #[inline(always)]
             pub fn $fn_load_off16(&mut self, instr: LoadOffset16Instr) -> Result<(), Error> {
@@ -121,12 +127,14 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             (Instruction::I64LoadOffset16, execute_i64_load_offset16),
             UntypedValue::i64_load,
         ),
+        #[cfg(feature = "float")]
         (
             (Instruction::F32Load, execute_f32_load),
             (Instruction::F32LoadAt, execute_f32_load_at),
             (Instruction::F32LoadOffset16, execute_f32_load_offset16),
             UntypedValue::f32_load,
         ),
+        #[cfg(feature = "float")]
         (
             (Instruction::F64Load, execute_f64_load),
             (Instruction::F64LoadAt, execute_f64_load_at),
@@ -87,17 +87,18 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             }
         };
         let memory = self.cache.default_memory(self.ctx);
-        let (memory, fuel) = self.ctx.resolve_memory_and_fuel_mut(memory);
-        let return_value = memory
-            .grow(delta, Some(fuel), resource_limiter)
-            .map(u32::from);
+        let (entity, fuel) = self.ctx.resolve_memory_and_fuel_mut(memory);
+        let return_value = entity.grow(delta, Some(fuel), resource_limiter);
         let return_value = match return_value {
-            Ok(return_value) => {
+            Ok(old_pages) => {
+                let new_pages = entity.current_pages();
+                self.ctx.add_pages_grown(u32::from(delta));
+                self.ctx.notify_memory_grow(*memory, old_pages, new_pages);
                 // The `memory.grow` operation might have invalidated the cached
                 // linear memory so we need to reset it in order for the cache to
                 // reload in case it is used again.
                 self.cache.reset_default_memory_bytes();
-                return_value
+                u32::from(old_pages)
             }
             Err(EntityGrowError::InvalidGrow) => EntityGrowError::ERROR_CODE,
             Err(EntityGrowError::TrapCode(trap_code)) => return Err(Error::from(trap_code)),
@@ -227,18 +228,23 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     ) -> Result<(), Error> {
         let src_index = src_index as usize;
         let dst_index = dst_index as usize;
+        let len = len as usize;
         let default_memory = self.cache.default_memory(self.ctx);
         let (memory, fuel) = self.ctx.resolve_memory_and_fuel_mut(default_memory);
-        let data = memory.data_mut();
+        let data = memory.data();
         // These accesses just perform the bounds checks required by the Wasm spec.
         data.get(src_index..)
-            .and_then(|memory| memory.get(..len as usize))
+            .and_then(|memory| memory.get(..len))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
         data.get(dst_index..)
-            .and_then(|memory| memory.get(..len as usize))
+            .and_then(|memory| memory.get(..len))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
-        fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
-        data.copy_within(src_index..src_index.wrapping_add(len as usize), dst_index);
+        memory.check_writable(dst_index, len)?;
+        let data = memory.data_mut();
+        // Note: Fuel is charged for all bytes up front in chunks of bytes per fuel
+        //       so that running out of fuel never leaves a partial copy behind.
+        fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
+        data.copy_within(src_index..src_index.wrapping_add(len), dst_index);
         self.try_next_instr()
     }
 
@@ -356,13 +362,15 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         let len = len as usize;
         let default_memory = self.cache.default_memory(self.ctx);
         let (memory, fuel) = self.ctx.resolve_memory_and_fuel_mut(default_memory);
-        let memory = memory
-            .data_mut()
-            .get_mut(dst..)
-            .and_then(|memory| memory.get_mut(..len))
+        // This access just performs the bounds check required by the Wasm spec.
+        memory
+            .data()
+            .get(dst..)
+            .and_then(|memory| memory.get(..len))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
+        memory.check_writable(dst, len)?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
-        memory.fill(value);
+        memory.data_mut()[dst..][..len].fill(value);
         self.try_next_instr()
     }
 
@@ -485,16 +493,19 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         let len = len as usize;
         let data_index: DataSegmentIdx = self.fetch_data_segment_index(1);
         let (memory, data, fuel) = self.cache.get_memory_init_triplet(self.ctx, data_index);
-        let memory = memory
-            .get_mut(dst_index..)
-            .and_then(|memory| memory.get_mut(..len))
+        // This access just performs the bounds check required by the Wasm spec.
+        memory
+            .data()
+            .get(dst_index..)
+            .and_then(|memory| memory.get(..len))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
         let data = data
             .get(src_index..)
             .and_then(|data| data.get(..len))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
+        memory.check_writable(dst_index, len)?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
-        memory.copy_from_slice(data);
+        memory.data_mut()[dst_index..][..len].copy_from_slice(data);
         self.try_next_instr_at(2)
     }
 }
@@ -5,6 +5,7 @@ use crate::{
         bytecode::{AnyConst32, Const32, Instruction, Register, RegisterSpan, RegisterSpanIter},
         executor::stack::FrameRegisters,
     },
+    store::WasmFuncEvent,
 };
 use core::slice;
 
@@ -29,6 +30,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             .pop()
             .expect("the executing call frame is always on the stack");
         self.value_stack.truncate(returned.frame_offset());
+        self.notify_wasm_func(returned.func(), WasmFuncEvent::Exit);
         match self.call_stack.peek() {
             Some(caller) => {
                 Self::init_call_frame_impl(
@@ -7,6 +7,7 @@ use crate::{
     },
     Error,
 };
+use core::mem;
 
 /// The function signature of Wasm store operations.
 type WasmStoreOp = fn(
@@ -44,19 +45,60 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         offset: u32,
         value: UntypedValue,
         store_wrap: WasmStoreOp,
+        width: usize,
     ) -> Result<(), Error> {
-        let memory = self.cache.default_memory_bytes(self.ctx);
+        let (memory, protected) = self.cache.default_memory_bytes_and_protection(self.ctx);
+        if protected {
+            return self.execute_protected_store_wrap(address, offset, value, store_wrap, width);
+        }
         store_wrap(memory, address, offset, value)?;
         Ok(())
     }
 
-    fn execute_store(&mut self, instr: StoreInstr, store_op: WasmStoreOp) -> Result<(), Error> {
+    /// Executes a generic Wasm `store[N]` operation on a default linear memory with read-only regions.
+    ///
+    /// # Note
+    ///
+    /// Out of bounds stores trap with [`TrapCode::MemoryOutOfBounds`] even if
+    /// they overlap a read-only region as required by the Wasm specification.
+    #[cold]
+    fn execute_protected_store_wrap(
+        &mut self,
+        address: UntypedValue,
+        offset: u32,
+        value: UntypedValue,
+        store_wrap: WasmStoreOp,
+        width: usize,
+    ) -> Result<(), Error> {
+        let default_memory = self.cache.default_memory(self.ctx);
+        let memory = self.ctx.resolve_memory_mut(default_memory);
+        let effective_address = usize::try_from(u64::from(u32::from(address)) + u64::from(offset))
+            .ok()
+            .filter(|&address| {
+                address
+                    .checked_add(width)
+                    .is_some_and(|end| end <= memory.data().len())
+            });
+        if let Some(effective_address) = effective_address {
+            memory.check_writable(effective_address, width)?;
+        }
+        store_wrap(memory.data_mut(), address, offset, value)?;
+        Ok(())
+    }
+
+    fn execute_store(
+        &mut self,
+        instr: StoreInstr,
+        store_op: WasmStoreOp,
+        width: usize,
+    ) -> Result<(), Error> {
         let value = self.fetch_store_value(1);
         self.execute_store_wrap(
             self.get_register(instr.ptr),
             u32::from(instr.offset),
             self.get_register(value),
             store_op,
+            width,
         )?;
         self.try_next_instr_at(2)
     }
@@ -65,12 +107,14 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         &mut self,
         instr: StoreOffset16Instr<Register>,
         store_op: WasmStoreOp,
+        width: usize,
     ) -> Result<(), Error> {
         self.execute_store_wrap(
             self.get_register(instr.ptr),
             u32::from(instr.offset),
             self.get_register(instr.value),
             store_op,
+            width,
         )?;
         self.try_next_instr()
     }
@@ -88,6 +132,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             u32::from(instr.offset),
             T::from(instr.value).into(),
             store_op,
+            mem::size_of::<T>(),
         )?;
         self.try_next_instr()
     }
@@ -96,12 +141,14 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         &mut self,
         instr: StoreAtInstr<Register>,
         store_op: WasmStoreOp,
+        width: usize,
     ) -> Result<(), Error> {
         self.execute_store_wrap(
             UntypedValue::from(0u32),
             u32::from(instr.address),
             self.get_register(instr.value),
             store_op,
+            width,
         )?;
         self.try_next_instr()
     }
@@ -119,6 +166,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             u32::from(instr.address),
             T::from(instr.value).into(),
             store_op,
+            mem::size_of::<T>(),
         )?;
         self.try_next_instr()
     }
@@ -140,7 +188,7 @@ macro_rules! impl_execute_istore {
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store), "`].")]
             #[inline(always)]
             pub fn $fn_store(&mut self, instr: StoreInstr) -> Result<(), Error> {
-                self.execute_store(instr, $impl_fn)
+                self.execute_store(instr, $impl_fn, mem::size_of::<$to_ty>())
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16), "`].")]
@@ -149,7 +197,7 @@ macro_rules! impl_execute_istore {
                 &mut self,
                 instr: StoreOffset16Instr<Register>,
             ) -> Result<(), Error> {
-                self.execute_store_offset16(instr, $impl_fn)
+                self.execute_store_offset16(instr, $impl_fn, mem::size_of::<$to_ty>())
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16_imm16), "`].")]
@@ -164,7 +212,7 @@ macro_rules! impl_execute_istore {
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at), "`].")]
             #[inline(always)]
             pub fn $fn_store_at(&mut self, instr: StoreAtInstr<Register>) -> Result<(), Error> {
-                self.execute_store_at(instr, $impl_fn)
+                self.execute_store_at(instr, $impl_fn, mem::size_of::<$to_ty>())
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at_imm16), "`].")]
@@ -246,9 +294,11 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 }
 
+#[cfg(feature = "float")]
 macro_rules! impl_execute_fstore {
     ( $(
         (
+            ($to_ty:ty),
             (Instruction::$var_store:ident, $fn_store:ident),
             (Instruction::$var_store_off16:ident, $fn_store_off16:ident),
             (Instruction::$var_store_at:ident, $fn_store_at:ident),
@@ -259,7 +309,7 @@ macro_rules! impl_execute_fstore {
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store), "`].")]
             #[inline(always)]
             pub fn $fn_store(&mut self, instr: StoreInstr) -> Result<(), Error> {
-                self.execute_store(instr, $impl_fn)
+                self.execute_store(instr, $impl_fn, mem::size_of::<$to_ty>())
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16), "`].")]
@@ -268,27 +318,30 @@ macro_rules! impl_execute_fstore {
                 &mut self,
                 instr: StoreOffset16Instr<Register>,
             ) -> Result<(), Error> {
-                self.execute_store_offset16(instr, $impl_fn)
+                self.execute_store_offset16(instr, $impl_fn, mem::size_of::<$to_ty>())
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at), "`].")]
             #[inline(always)]
             pub fn $fn_store_at(&mut self, instr: StoreAtInstr<Register>) -> Result<(), Error> {
-                self.execute_store_at(instr, $impl_fn)
+                self.execute_store_at(instr, $impl_fn, mem::size_of::<$to_ty>())
             }
         )*
     }
 }
 
+#[cfg(feature = "float")]
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     impl_execute_fstore! {
         (
+            (f32),
             (Instruction::F32Store, execute_f32_store),
             (Instruction::F32StoreOffset16, execute_f32_store_offset16),
             (Instruction::F32StoreAt, execute_f32_store_at),
             UntypedValue::f32_store,
         ),
         (
+            (f64),
             (Instruction::F64Store, execute_f64_store),
             (Instruction::F64StoreOffset16, execute_f64_store_offset16),
             (Instruction::F64StoreAt, execute_f64_store_at),
@@ -1,18 +1,27 @@
 use super::Executor;
 use crate::{
-    core::TrapCode,
     engine::{
-        bytecode::{Const16, Const32, ElementSegmentIdx, Instruction, Register, TableIdx},
+        bytecode::{Const16, Instruction, Register, TableIdx},
         code_map::InstructionPtr,
     },
+    table::TableEvent,
+    Error,
+};
+
+#[cfg(feature = "reference-types")]
+use crate::{
+    core::TrapCode,
+    engine::bytecode::Const32,
     error::EntityGrowError,
     store::ResourceLimiterRef,
-    table::TableEntity,
-    Error,
 };
 
+#[cfg(feature = "bulk-table")]
+use crate::{engine::bytecode::ElementSegmentIdx, table::TableEntity};
+
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// Returns the [`Instruction::TableIdx`] parameter for an [`Instruction`].
+    #[cfg(not(all(not(feature = "reference-types"), not(feature = "bulk-table"))))]
     fn fetch_table_index(&self, offset: usize) -> TableIdx {
         let mut addr: InstructionPtr = self.ip;
         addr.add(offset);
@@ -23,6 +32,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Returns the [`Instruction::ElementSegmentIdx`] parameter for an [`Instruction`].
+    #[cfg(feature = "bulk-table")]
     fn fetch_element_segment_index(&self, offset: usize) -> ElementSegmentIdx {
         let mut addr: InstructionPtr = self.ip;
         addr.add(offset);
@@ -33,6 +43,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableGet`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     pub fn execute_table_get(&mut self, result: Register, index: Register) -> Result<(), Error> {
         let index: u32 = self.get_register_as(index);
@@ -40,6 +51,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableGetImm`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     pub fn execute_table_get_imm(
         &mut self,
@@ -50,6 +62,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a `table.get` instruction generically.
+    #[cfg(feature = "reference-types")]
     fn execute_table_get_impl(&mut self, result: Register, index: u32) -> Result<(), Error> {
         let table_index = self.fetch_table_index(1);
         let table = self.cache.get_table(self.ctx, table_index);
@@ -63,6 +76,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableSize`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     pub fn execute_table_size(&mut self, result: Register, table_index: TableIdx) {
         self.execute_table_size_impl(result, table_index);
@@ -70,6 +84,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a generic `table.size` instruction.
+    #[cfg(feature = "reference-types")]
     fn execute_table_size_impl(&mut self, result: Register, table_index: TableIdx) {
         let table = self.cache.get_table(self.ctx, table_index);
         let size = self.ctx.resolve_table(&table).size();
@@ -77,6 +92,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableSet`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     pub fn execute_table_set(&mut self, index: Register, value: Register) -> Result<(), Error> {
         let index: u32 = self.get_register_as(index);
@@ -84,6 +100,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableSetAt`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     pub fn execute_table_set_at(
         &mut self,
@@ -95,6 +112,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a generic `table.set` instruction.
+    #[cfg(feature = "reference-types")]
     fn execute_table_set_impl(&mut self, index: u32, value: Register) -> Result<(), Error> {
         let table_index = self.fetch_table_index(1);
         let table = self.cache.get_table(self.ctx, table_index);
@@ -103,10 +121,12 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             .resolve_table_mut(&table)
             .set_untyped(index, value)
             .map_err(|_| TrapCode::TableOutOfBounds)?;
+        self.ctx.notify_table(table, TableEvent::Set { index });
         self.try_next_instr_at(2)
     }
 
     /// Executes an [`Instruction::TableCopy`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_copy(
         &mut self,
@@ -121,6 +141,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableCopyTo`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_copy_to(
         &mut self,
@@ -135,6 +156,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableCopyFrom`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_copy_from(
         &mut self,
@@ -149,6 +171,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableCopyFromTo`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_copy_from_to(
         &mut self,
@@ -163,6 +186,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableCopyExact`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_copy_exact(
         &mut self,
@@ -177,6 +201,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableCopyToExact`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_copy_to_exact(
         &mut self,
@@ -191,6 +216,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableCopyFromExact`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_copy_from_exact(
         &mut self,
@@ -205,6 +231,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableCopyFromToExact`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_copy_from_to_exact(
         &mut self,
@@ -219,6 +246,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a generic `table.copy` instruction.
+    #[cfg(feature = "bulk-table")]
     fn execute_table_copy_impl(
         &mut self,
         dst_index: u32,
@@ -227,24 +255,31 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     ) -> Result<(), Error> {
         let dst_table_index = self.fetch_table_index(1);
         let src_table_index = self.fetch_table_index(2);
+        let dst_table = self.cache.get_table(self.ctx, dst_table_index);
         if dst_table_index == src_table_index {
             // Case: copy within the same table
-            let table = self.cache.get_table(self.ctx, dst_table_index);
-            let (table, fuel) = self.ctx.resolve_table_and_fuel_mut(&table);
+            let (table, fuel) = self.ctx.resolve_table_and_fuel_mut(&dst_table);
             table.copy_within(dst_index, src_index, len, Some(fuel))?;
         } else {
             // Case: copy between two different tables
-            let dst_table = self.cache.get_table(self.ctx, dst_table_index);
             let src_table = self.cache.get_table(self.ctx, src_table_index);
             // Copy from one table to another table:
             let (dst_table, src_table, fuel) =
                 self.ctx.resolve_table_pair_and_fuel(&dst_table, &src_table);
             TableEntity::copy(dst_table, dst_index, src_table, src_index, len, Some(fuel))?;
         }
+        if len != 0 {
+            let event = TableEvent::Copy {
+                dst: dst_index,
+                len,
+            };
+            self.ctx.notify_table(dst_table, event);
+        }
         self.try_next_instr_at(3)
     }
 
     /// Executes an [`Instruction::TableInit`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_init(
         &mut self,
@@ -259,6 +294,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableInitTo`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_init_to(
         &mut self,
@@ -273,6 +309,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableInitFrom`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_init_from(
         &mut self,
@@ -287,6 +324,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableInitFromTo`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_init_from_to(
         &mut self,
@@ -301,6 +339,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableInitExact`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_init_exact(
         &mut self,
@@ -315,6 +354,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableInitToExact`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_init_to_exact(
         &mut self,
@@ -329,6 +369,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableInitFromExact`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_init_from_exact(
         &mut self,
@@ -343,6 +384,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableInitFromToExact`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_table_init_from_to_exact(
         &mut self,
@@ -357,6 +399,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a generic `table.init` instruction.
+    #[cfg(feature = "bulk-table")]
     fn execute_table_init_impl(
         &mut self,
         dst_index: u32,
@@ -380,10 +423,19 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     .unwrap_or_else(|| panic!("missing function at index {func_index}"))
             },
         )?;
+        if len != 0 {
+            let table = self.cache.get_table(self.ctx, table_index);
+            let event = TableEvent::Init {
+                dst: dst_index,
+                len,
+            };
+            self.ctx.notify_table(table, event);
+        }
         self.try_next_instr_at(3)
     }
 
     /// Executes an [`Instruction::TableFill`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     pub fn execute_table_fill(
         &mut self,
@@ -397,6 +449,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableFillAt`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     pub fn execute_table_fill_at(
         &mut self,
@@ -410,6 +463,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableFillExact`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     pub fn execute_table_fill_exact(
         &mut self,
@@ -423,6 +477,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableFillAtExact`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     pub fn execute_table_fill_at_exact(
         &mut self,
@@ -436,6 +491,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a generic `table.fill` instruction.
+    #[cfg(feature = "reference-types")]
     fn execute_table_fill_impl(
         &mut self,
         dst: u32,
@@ -445,12 +501,16 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         let table_index = self.fetch_table_index(1);
         let value = self.get_register(value);
         let table = self.cache.get_table(self.ctx, table_index);
-        let (table, fuel) = self.ctx.resolve_table_and_fuel_mut(&table);
-        table.fill_untyped(dst, value, len, Some(fuel))?;
+        let (entity, fuel) = self.ctx.resolve_table_and_fuel_mut(&table);
+        entity.fill_untyped(dst, value, len, Some(fuel))?;
+        if len != 0 {
+            self.ctx.notify_table(table, TableEvent::Fill { dst, len });
+        }
         self.try_next_instr_at(2)
     }
 
     /// Executes an [`Instruction::TableGrow`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     pub fn execute_table_grow(
         &mut self,
@@ -464,6 +524,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::TableGrowImm`].
+    #[cfg(feature = "reference-types")]
     #[inline(always)]
     pub fn execute_table_grow_imm(
         &mut self,
@@ -477,6 +538,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a generic `table.grow` instruction.
+    #[cfg(feature = "reference-types")]
     fn execute_table_grow_impl(
         &mut self,
         result: Register,
@@ -492,10 +554,15 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         }
         let table = self.cache.get_table(self.ctx, table_index);
         let value = self.get_register(value);
-        let (table, fuel) = self.ctx.resolve_table_and_fuel_mut(&table);
-        let return_value = table.grow_untyped(delta, value, Some(fuel), resource_limiter);
+        let (entity, fuel) = self.ctx.resolve_table_and_fuel_mut(&table);
+        let return_value = entity.grow_untyped(delta, value, Some(fuel), resource_limiter);
         let return_value = match return_value {
-            Ok(return_value) => return_value,
+            Ok(old_size) => {
+                let new_size = entity.size();
+                let event = TableEvent::Grow { old_size, new_size };
+                self.ctx.notify_table(table, event);
+                old_size
+            }
             Err(EntityGrowError::InvalidGrow) => EntityGrowError::ERROR_CODE,
             Err(EntityGrowError::TrapCode(trap_code)) => return Err(Error::from(trap_code)),
         };
@@ -504,6 +571,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::ElemDrop`].
+    #[cfg(feature = "bulk-table")]
     #[inline(always)]
     pub fn execute_element_drop(&mut self, segment_index: ElementSegmentIdx) {
         let segment = self.cache.get_element_segment(self.ctx, segment_index);
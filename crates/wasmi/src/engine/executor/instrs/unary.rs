@@ -5,8 +5,9 @@ use crate::{core::UntypedValue, engine::bytecode::UnaryInstr};
 use crate::engine::bytecode::Instruction;
 
 macro_rules! impl_unary_impls {
-    ( $( (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
+    ( $( $( #[$attr:meta] )* (Instruction::$var_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
         $(
+            $( #[$attr] )*
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_name), "`].")]
             #[inline(always)]
             pub fn $fn_name(&mut self, instr: UnaryInstr) {
@@ -26,20 +27,34 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         (Instruction::I64Ctz, execute_i64_ctz, UntypedValue::i64_ctz),
         (Instruction::I64Popcnt, execute_i64_popcnt, UntypedValue::i64_popcnt),
 
+        #[cfg(feature = "float")]
         (Instruction::F32Abs, execute_f32_abs, UntypedValue::f32_abs),
+        #[cfg(feature = "float")]
         (Instruction::F32Neg, execute_f32_neg, UntypedValue::f32_neg),
+        #[cfg(feature = "float")]
         (Instruction::F32Ceil, execute_f32_ceil, UntypedValue::f32_ceil),
+        #[cfg(feature = "float")]
         (Instruction::F32Floor, execute_f32_floor, UntypedValue::f32_floor),
+        #[cfg(feature = "float")]
         (Instruction::F32Trunc, execute_f32_trunc, UntypedValue::f32_trunc),
+        #[cfg(feature = "float")]
         (Instruction::F32Nearest, execute_f32_nearest, UntypedValue::f32_nearest),
+        #[cfg(feature = "float")]
         (Instruction::F32Sqrt, execute_f32_sqrt, UntypedValue::f32_sqrt),
 
+        #[cfg(feature = "float")]
         (Instruction::F64Abs, execute_f64_abs, UntypedValue::f64_abs),
+        #[cfg(feature = "float")]
         (Instruction::F64Neg, execute_f64_neg, UntypedValue::f64_neg),
+        #[cfg(feature = "float")]
         (Instruction::F64Ceil, execute_f64_ceil, UntypedValue::f64_ceil),
+        #[cfg(feature = "float")]
         (Instruction::F64Floor, execute_f64_floor, UntypedValue::f64_floor),
+        #[cfg(feature = "float")]
         (Instruction::F64Trunc, execute_f64_trunc, UntypedValue::f64_trunc),
+        #[cfg(feature = "float")]
         (Instruction::F64Nearest, execute_f64_nearest, UntypedValue::f64_nearest),
+        #[cfg(feature = "float")]
         (Instruction::F64Sqrt, execute_f64_sqrt, UntypedValue::f64_sqrt),
     }
 }
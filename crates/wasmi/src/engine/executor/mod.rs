@@ -1,31 +1,45 @@
-pub(crate) use self::stack::Stack;
+pub(crate) use self::stack::{CallStack, Stack};
 use self::{
     instrs::{execute_instrs, CallKind, WasmOutcome},
     stack::CallFrame,
     trap::TaggedTrap,
 };
 use crate::{
+    core::TrapCode,
     engine::{
         bytecode::{Register, RegisterSpan},
         cache::InstanceCache,
         code_map::InstructionPtr,
         CallParams,
+        CallReportProbe,
         CallResults,
         EngineInner,
         EngineResources,
         FuncParams,
         ResumableCallBase,
         ResumableInvocation,
+        YieldedInvocation,
     },
     func::HostFuncEntity,
+    store::WasmFuncEvent,
+    value::WithType,
     AsContext,
     AsContextMut,
     Error,
     Func,
     FuncEntity,
+    FuncType,
     Instance,
     StoreContextMut,
+    Value,
 };
+use alloc::boxed::Box;
+
+#[cfg(unix)]
+use core::time::Duration;
+
+#[cfg(all(unix, feature = "std"))]
+use crate::preempt::InterruptBinding;
 
 #[cfg(doc)]
 use crate::{engine::StackLimits, Store};
@@ -44,7 +58,7 @@ impl EngineInner {
     /// If the Wasm execution traps or runs out of resources.
     pub fn execute_func<T, Results>(
         &self,
-        ctx: StoreContextMut<T>,
+        mut ctx: StoreContextMut<T>,
         func: &Func,
         params: impl CallParams,
         results: Results,
@@ -53,14 +67,70 @@ impl EngineInner {
         Results: CallResults,
     {
         let res = self.res.read();
-        let mut stack = self.stacks.lock().reuse_or_new();
+        let mut stack = self.stacks.lock().reuse_or_new(&self.stats);
+        let probe = CallReportProbe::new(&ctx.store.inner);
+        let yieldable = ctx.store.inner.enter_yieldable(false);
         let results = EngineExecutor::new(&res, &mut stack)
-            .execute_root_func(ctx, func, params, results)
+            .execute_root_func(ctx.as_context_mut(), func, params, results)
             .map_err(TaggedTrap::into_error);
+        ctx.store.inner.restore_yieldable(yieldable);
+        probe.finish(&mut ctx.store.inner, stack.peak_call_depth());
         self.stacks.lock().recycle(stack);
         results
     }
 
+    /// Executes the given [`Func`] once for each pair of `params` and `results` of `calls`.
+    ///
+    /// Uses the [`StoreContextMut`] for context information about the Wasm [`Store`].
+    ///
+    /// # Note
+    ///
+    /// All calls share the same [`Stack`] and are recorded as a single [`CallReport`].
+    ///
+    /// # Errors
+    ///
+    /// If any Wasm execution traps or runs out of resources.
+    ///
+    /// [`CallReport`]: crate::CallReport
+    pub fn execute_func_batch<T, Params, Results>(
+        &self,
+        mut ctx: StoreContextMut<T>,
+        func: &Func,
+        calls: impl IntoIterator<Item = (Params, Results)>,
+        mut f: impl FnMut(<Results as CallResults>::Results),
+    ) -> Result<(), Error>
+    where
+        Params: CallParams,
+        Results: CallResults,
+    {
+        let res = self.res.read();
+        let mut stack = self.stacks.lock().reuse_or_new(&self.stats);
+        let probe = CallReportProbe::new(&ctx.store.inner);
+        let yieldable = ctx.store.inner.enter_yieldable(false);
+        let mut peak_call_depth = 0;
+        let mut outcome = Ok(());
+        for (params, results) in calls {
+            let results = EngineExecutor::new(&res, &mut stack).execute_root_func(
+                ctx.as_context_mut(),
+                func,
+                params,
+                results,
+            );
+            peak_call_depth = peak_call_depth.max(stack.peak_call_depth());
+            match results {
+                Ok(results) => f(results),
+                Err(trap) => {
+                    outcome = Err(trap.into_error());
+                    break;
+                }
+            }
+        }
+        ctx.store.inner.restore_yieldable(yieldable);
+        probe.finish(&mut ctx.store.inner, peak_call_depth);
+        self.stacks.lock().recycle(stack);
+        outcome
+    }
+
     /// Executes the given [`Func`] resumably with the given `params` and returns the `results`.
     ///
     /// Uses the [`StoreContextMut`] for context information about the Wasm [`Store`].
@@ -79,34 +149,48 @@ impl EngineInner {
         Results: CallResults,
     {
         let res = self.res.read();
-        let mut stack = self.stacks.lock().reuse_or_new();
+        let mut stack = self.stacks.lock().reuse_or_new(&self.stats);
+        let probe = CallReportProbe::new(&ctx.store.inner);
+        let yieldable = ctx.store.inner.enter_yieldable(true);
         let results = EngineExecutor::new(&res, &mut stack).execute_root_func(
             ctx.as_context_mut(),
             func,
             params,
             results,
         );
+        ctx.store.inner.restore_yieldable(yieldable);
+        probe.finish(&mut ctx.store.inner, stack.peak_call_depth());
         match results {
             Ok(results) => {
                 self.stacks.lock().recycle(stack);
                 Ok(ResumableCallBase::Finished(results))
             }
-            Err(TaggedTrap::Wasm(error)) => {
+            Err(trap @ TaggedTrap::Wasm(_)) => {
                 self.stacks.lock().recycle(stack);
-                Err(error)
+                Err(trap.into_error())
             }
+            Err(TaggedTrap::OutOfFuel(_)) => Ok(ResumableCallBase::OutOfFuel(
+                YieldedInvocation::new(ctx.as_context().store.engine().clone(), *func, stack),
+            )),
             Err(TaggedTrap::Host {
                 host_func,
                 host_error,
+                host_params,
                 caller_results,
             }) => Ok(ResumableCallBase::Resumable(ResumableInvocation::new(
                 ctx.as_context().store.engine().clone(),
                 *func,
                 host_func,
                 host_error,
+                host_params,
                 caller_results,
                 stack,
             ))),
+            Err(TaggedTrap::Yield) => Ok(ResumableCallBase::Yielded(YieldedInvocation::new(
+                ctx.as_context().store.engine().clone(),
+                *func,
+                stack,
+            ))),
         }
     }
 
@@ -119,7 +203,7 @@ impl EngineInner {
     /// If the Wasm execution traps or runs out of resources.
     pub(crate) fn resume_func<T, Results>(
         &self,
-        ctx: StoreContextMut<T>,
+        mut ctx: StoreContextMut<T>,
         mut invocation: ResumableInvocation,
         params: impl CallParams,
         results: Results,
@@ -130,30 +214,87 @@ impl EngineInner {
         let res = self.res.read();
         let host_func = invocation.host_func();
         let caller_results = invocation.caller_results();
+        let probe = CallReportProbe::new(&ctx.store.inner);
+        let yieldable = ctx.store.inner.enter_yieldable(true);
         let results = EngineExecutor::new(&res, &mut invocation.stack).resume_func(
-            ctx,
+            ctx.as_context_mut(),
             host_func,
             params,
             caller_results,
             results,
         );
+        ctx.store.inner.restore_yieldable(yieldable);
+        probe.finish(&mut ctx.store.inner, invocation.stack.peak_call_depth());
         match results {
             Ok(results) => {
                 self.stacks.lock().recycle(invocation.take_stack());
                 Ok(ResumableCallBase::Finished(results))
             }
-            Err(TaggedTrap::Wasm(error)) => {
+            Err(trap @ TaggedTrap::Wasm(_)) => {
                 self.stacks.lock().recycle(invocation.take_stack());
-                Err(error)
+                Err(trap.into_error())
+            }
+            Err(TaggedTrap::OutOfFuel(_)) => {
+                Ok(ResumableCallBase::OutOfFuel(invocation.into_yielded()))
             }
             Err(TaggedTrap::Host {
                 host_func,
                 host_error,
+                host_params,
                 caller_results,
             }) => {
-                invocation.update(host_func, host_error, caller_results);
+                invocation.update(host_func, host_error, host_params, caller_results);
                 Ok(ResumableCallBase::Resumable(invocation))
             }
+            Err(TaggedTrap::Yield) => Ok(ResumableCallBase::Yielded(invocation.into_yielded())),
+        }
+    }
+
+    /// Resumes the `yielded` call for another time slice and returns the `results`.
+    ///
+    /// Uses the [`StoreContextMut`] for context information about the Wasm [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm execution traps or runs out of resources.
+    pub(crate) fn resume_yielded<T, Results>(
+        &self,
+        mut ctx: StoreContextMut<T>,
+        mut yielded: YieldedInvocation,
+        results: Results,
+    ) -> Result<ResumableCallBase<<Results as CallResults>::Results>, Error>
+    where
+        Results: CallResults,
+    {
+        let res = self.res.read();
+        let probe = CallReportProbe::new(&ctx.store.inner);
+        let yieldable = ctx.store.inner.enter_yieldable(true);
+        let results = EngineExecutor::new(&res, &mut yielded.stack)
+            .resume_func_suspended(ctx.as_context_mut(), results);
+        ctx.store.inner.restore_yieldable(yieldable);
+        probe.finish(&mut ctx.store.inner, yielded.stack.peak_call_depth());
+        match results {
+            Ok(results) => {
+                self.stacks.lock().recycle(yielded.take_stack());
+                Ok(ResumableCallBase::Finished(results))
+            }
+            Err(trap @ TaggedTrap::Wasm(_)) => {
+                self.stacks.lock().recycle(yielded.take_stack());
+                Err(trap.into_error())
+            }
+            Err(TaggedTrap::OutOfFuel(_)) => Ok(ResumableCallBase::OutOfFuel(yielded)),
+            Err(TaggedTrap::Host {
+                host_func,
+                host_error,
+                host_params,
+                caller_results,
+            }) => Ok(ResumableCallBase::Resumable(yielded.into_resumable(
+                host_func,
+                host_error,
+                host_params,
+                caller_results,
+            ))),
+            Err(TaggedTrap::Yield) => Ok(ResumableCallBase::Yielded(yielded)),
         }
     }
 }
@@ -195,6 +336,7 @@ impl<'engine> EngineExecutor<'engine> {
         Results: CallResults,
     {
         self.stack.reset();
+        self.enter_host_call_depth(&ctx)?;
         match ctx.as_context().store.inner.resolve_func(func) {
             FuncEntity::Wasm(wasm_func) => {
                 // We reserve space on the stack to write the results of the root function execution.
@@ -202,12 +344,12 @@ impl<'engine> EngineExecutor<'engine> {
                 self.stack.values.reserve(len_results)?;
                 self.stack.values.extend_zeros(len_results);
                 let instance = *wasm_func.instance();
-                let compiled_func = wasm_func.func_body();
+                let func_body = wasm_func.func_body();
                 let ctx = ctx.as_context_mut();
                 let compiled_func = self
                     .res
                     .code_map
-                    .get(Some(ctx.store.inner.fuel_mut()), compiled_func)?;
+                    .get(Some(ctx.store.inner.fuel_mut()), func_body)?;
                 let (base_ptr, frame_ptr) = self.stack.values.alloc_call_frame(compiled_func)?;
                 // Safety: We use the `base_ptr` that we just received upon allocating the new
                 //         call frame which is guaranteed to be valid for this particular operation
@@ -216,27 +358,27 @@ impl<'engine> EngineExecutor<'engine> {
                 //         be exactly the length of the expected function arguments.
                 unsafe { self.stack.values.fill_at(base_ptr, params.call_params()) };
                 self.stack.calls.push(CallFrame::new(
+                    func_body,
                     InstructionPtr::new(compiled_func.instrs().as_ptr()),
                     frame_ptr,
                     base_ptr,
                     RegisterSpan::new(Register::from_i16(0)),
                     instance,
                 ))?;
+                if ctx.store.inner.has_wasm_func_hook() {
+                    let func_index = compiled_func.func_index();
+                    ctx.store
+                        .inner
+                        .notify_wasm_func(func_index, WasmFuncEvent::Enter);
+                }
                 self.execute_func(ctx)?;
             }
             FuncEntity::Host(host_func) => {
-                // The host function signature is required for properly
-                // adjusting, inspecting and manipulating the value stack.
-                let (input_types, output_types) = self
-                    .res
-                    .func_types
-                    .resolve_func_type(host_func.ty_dedup())
-                    .params_results();
                 // In case the host function returns more values than it takes
                 // we are required to extend the value stack.
-                let len_params = input_types.len();
-                let len_results = output_types.len();
-                let max_inout = len_params.max(len_results);
+                let arity = host_func.arity();
+                let len_params = arity.len_params();
+                let max_inout = arity.max_inout();
                 self.stack.values.reserve(max_inout)?;
                 self.stack.values.extend_zeros(max_inout);
                 let values = &mut self.stack.values.as_slice_mut()[..len_params];
@@ -244,7 +386,8 @@ impl<'engine> EngineExecutor<'engine> {
                     *value = param;
                 }
                 let host_func = *host_func;
-                self.dispatch_host_func(ctx.as_context_mut(), host_func, HostFuncCaller::Root)?;
+                self.dispatch_host_func(ctx.as_context_mut(), host_func, HostFuncCaller::Root)
+                    .map_err(|error| error.error)?;
             }
         };
         let results = self.write_results_back(results);
@@ -271,6 +414,7 @@ impl<'engine> EngineExecutor<'engine> {
     where
         Results: CallResults,
     {
+        self.enter_host_call_depth(&ctx)?;
         let caller = self
             .stack
             .calls
@@ -287,6 +431,48 @@ impl<'engine> EngineExecutor<'engine> {
         Ok(results)
     }
 
+    /// Resumes the execution of the top most Wasm function on the [`Stack`] after it was suspended.
+    ///
+    /// # Note
+    ///
+    /// Executions are suspended when running out of fuel or exhausting their time slice.
+    ///
+    /// Stores the execution result into `results` upon a successful execution.
+    ///
+    /// # Errors
+    ///
+    /// - If the given `results` do not match the the length of the expected results of `func`.
+    /// - When encountering a Wasm or host trap during the execution of `func`.
+    pub fn resume_func_suspended<T, Results>(
+        &mut self,
+        ctx: StoreContextMut<T>,
+        results: Results,
+    ) -> Result<<Results as CallResults>::Results, TaggedTrap>
+    where
+        Results: CallResults,
+    {
+        self.enter_host_call_depth(&ctx)?;
+        self.execute_func(ctx)?;
+        let results = self.write_results_back(results);
+        Ok(results)
+    }
+
+    /// Accounts the call frames of the executions that are currently calling host functions.
+    ///
+    /// # Note
+    ///
+    /// This is required for executions started from within host functions so that
+    /// host→Wasm→host→Wasm re-entrancy is bounded by the configured recursion limit.
+    ///
+    /// # Errors
+    ///
+    /// If the maximum re-entrancy depth is exceeded.
+    fn enter_host_call_depth<T>(&mut self, ctx: &StoreContextMut<T>) -> Result<(), TrapCode> {
+        let depth_offset = ctx.store.inner.host_call_depth()?;
+        self.stack.calls.set_depth_offset(depth_offset);
+        Ok(())
+    }
+
     /// Executes the top most Wasm function on the [`Stack`] until the [`Stack`] is empty.
     ///
     /// # Errors
@@ -294,6 +480,16 @@ impl<'engine> EngineExecutor<'engine> {
     /// When encountering a Wasm or host trap during execution.
     #[inline(never)]
     fn execute_func<T>(&mut self, mut ctx: StoreContextMut<T>) -> Result<(), TaggedTrap> {
+        #[cfg(all(unix, feature = "std"))]
+        let _binding = {
+            let store = &ctx.as_context().store.inner;
+            store
+                .engine()
+                .config()
+                .get_signal_preemption()
+                // Safety: The interrupt flag is owned by the store which outlives the execution.
+                .then(|| unsafe { InterruptBinding::new(store.interrupt_flag()) })
+        };
         let mut cache = self
             .stack
             .calls
@@ -302,7 +498,7 @@ impl<'engine> EngineExecutor<'engine> {
             .map(InstanceCache::from)
             .expect("must have frame on the call stack");
         loop {
-            match self.execute_compiled_func(ctx.as_context_mut(), &mut cache)? {
+            match self.execute_compiled_func_accounted(ctx.as_context_mut(), &mut cache)? {
                 WasmOutcome::Return => {
                     // In this case the root function has returned.
                     // Therefore we can return from the entire execution.
@@ -321,6 +517,13 @@ impl<'engine> EngineExecutor<'engine> {
                         .instance();
                     self.execute_host_func(&mut ctx, results, host_func, &instance, call_kind)?;
                 }
+                WasmOutcome::OutOfFuel(error) => return Err(TaggedTrap::OutOfFuel(error)),
+                WasmOutcome::Yield => return Err(TaggedTrap::Yield),
+                WasmOutcome::EpochDeadline => {
+                    if ctx.store.update_epoch_deadline()? {
+                        return Err(TaggedTrap::Yield);
+                    }
+                }
             }
         }
     }
@@ -345,26 +548,42 @@ impl<'engine> EngineExecutor<'engine> {
             HostFuncCaller::wasm(results, instance),
         );
         if matches!(call_kind, CallKind::Tail) {
-            self.stack.calls.pop();
+            let caller = self.stack.calls.pop();
+            if let Some(caller) = caller.filter(|_| ctx.store.inner.has_wasm_func_hook()) {
+                if let Some(entity) = self.res.code_map.get_compiled(caller.func()) {
+                    ctx.store
+                        .inner
+                        .notify_wasm_func(entity.func_index(), WasmFuncEvent::Exit);
+                }
+            }
         }
         if self.stack.calls.peek().is_some() {
             // Case: There is a frame on the call stack.
             //
             // This is the default case and we can easily make host function
             // errors return a resumable call handle.
-            result.map_err(|error| TaggedTrap::host(*func, error, results))?;
+            result.map_err(|error| TaggedTrap::host(*func, error.error, error.params, results))?;
         } else {
             // Case: No frame is on the call stack. (edge case)
             //
             // This can happen if the host function was called by a tail call.
             // In this case we treat host function errors the same as if we called
             // the host function as root and do not allow to resume the call.
-            result.map_err(TaggedTrap::Wasm)?;
+            result.map_err(|error| TaggedTrap::Wasm(error.error))?;
         }
         Ok(())
     }
 }
 
+/// An [`Error`] returned by a host function together with the parameters of the call.
+#[derive(Debug)]
+struct HostFuncError {
+    /// The error returned by the host function.
+    error: Error,
+    /// The parameters with which the host function was called.
+    params: Box<[Value]>,
+}
+
 /// The caller of a host function call.
 #[derive(Debug, Copy, Clone)]
 enum HostFuncCaller<'a> {
@@ -402,51 +621,121 @@ impl<'a> HostFuncCaller<'a> {
     }
 }
 
+/// Calls the host function via `call_host` and converts its panics into an [`Error`].
+#[cfg(feature = "std")]
+fn catch_host_panic(call_host: impl FnOnce() -> Result<(), Error>) -> Result<(), Error> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(call_host)).unwrap_or_else(|payload| {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast_ref::<&'static str>() {
+                Some(message) => String::from(*message),
+                None => String::from("Box<dyn Any>"),
+            },
+        };
+        Err(Error::host_panic(message))
+    })
+}
+
 impl<'engine> EngineExecutor<'engine> {
     /// Dispatches a host function call and returns its result.
     fn dispatch_host_func<T>(
         &mut self,
-        ctx: StoreContextMut<T>,
+        mut ctx: StoreContextMut<T>,
         host_func: HostFuncEntity,
         caller: HostFuncCaller,
-    ) -> Result<(), Error> {
-        // The host function signature is required for properly
+    ) -> Result<(), HostFuncError> {
+        // The host function arity is required for properly
         // adjusting, inspecting and manipulating the value stack.
-        let (input_types, output_types) = self
-            .res
-            .func_types
-            .resolve_func_type(host_func.ty_dedup())
-            .params_results();
+        //
         // In case the host function returns more values than it takes
         // we are required to extend the value stack.
-        let len_inputs = input_types.len();
-        let len_outputs = output_types.len();
-        let max_inout = len_inputs.max(len_outputs);
+        let arity = host_func.arity();
+        let len_inputs = arity.len_params();
+        let len_outputs = arity.len_results();
+        let max_inout = arity.max_inout();
         let values = self.stack.values.as_slice_mut();
-        let params_results = FuncParams::new(
-            values.split_at_mut(values.len() - max_inout).1,
-            len_inputs,
-            len_outputs,
-        );
+        let values = values.split_at_mut(values.len() - max_inout).1;
+        let call_stack = Some(&self.stack.calls);
+        // Host functions may call back into Wasm so we account for our call frames.
+        let host_call_depth = ctx.store.inner.enter_host_call(self.stack.calls.depth());
         // Now we are ready to perform the host function call.
-        // Note: We need to clone the host function due to some borrowing issues.
-        //       This should not be a big deal since host functions usually are cheap to clone.
-        let trampoline = ctx
+        let interceptor = ctx.as_context().store.host_call_interceptor().cloned();
+        #[cfg(feature = "std")]
+        let host_panic_as_trap = ctx
             .as_context()
             .store
-            .resolve_trampoline(host_func.trampoline())
-            .clone();
-        trampoline
-            .call(ctx, caller.instance(), params_results)
-            .map_err(|error| {
-                // Note: We drop the values that have been temporarily added to
-                //       the stack to act as parameter and result buffer for the
-                //       called host function. Since the host function failed we
-                //       need to clean up the temporary buffer values here.
-                //       This is required for resumable calls to work properly.
-                self.stack.values.drop(max_inout);
-                error
-            })?;
+            .engine()
+            .config()
+            .get_host_panic_as_trap();
+        let call_host = || match interceptor {
+            None => {
+                let params_results = FuncParams::new(values, len_inputs, len_outputs);
+                host_func
+                    .trampoline()
+                    .call(
+                        ctx.as_context_mut(),
+                        caller.instance(),
+                        call_stack,
+                        params_results,
+                    )
+                    .map(|_| ())
+            }
+            Some(interceptor) => {
+                // Note: We need to clone the host function due to borrowing issues
+                //       since the interceptor requires access to its import name.
+                //       This is fine since interceptors are an opt-in debugging aid.
+                let trampoline = ctx
+                    .as_context()
+                    .store
+                    .resolve_trampoline(host_func.trampoline())
+                    .clone();
+                let ty = ctx
+                    .as_context()
+                    .store
+                    .engine()
+                    .resolve_func_type(host_func.ty_dedup(), FuncType::clone);
+                interceptor.call(
+                    ctx.as_context_mut(),
+                    caller.instance(),
+                    call_stack,
+                    &trampoline,
+                    ty,
+                    values,
+                )
+            }
+        };
+        #[cfg(feature = "std")]
+        let result = match host_panic_as_trap {
+            true => catch_host_panic(call_host),
+            false => call_host(),
+        };
+        #[cfg(not(feature = "std"))]
+        let result = call_host();
+        ctx.store.inner.restore_host_call_depth(host_call_depth);
+        if let Err(error) = result {
+            // Note: Host functions only write their results upon success
+            //       so the parameter buffer still holds the call parameters.
+            let values = self.stack.values.as_slice();
+            let values = &values[values.len() - max_inout..];
+            let params = ctx.as_context().store.engine().resolve_func_type(
+                host_func.ty_dedup(),
+                |func_type| {
+                    func_type
+                        .params()
+                        .iter()
+                        .zip(values)
+                        .map(|(ty, value)| value.with_type(*ty))
+                        .collect::<Box<[Value]>>()
+                },
+            );
+            // Note: We drop the values that have been temporarily added to
+            //       the stack to act as parameter and result buffer for the
+            //       called host function. Since the host function failed we
+            //       need to clean up the temporary buffer values here.
+            //       This is required for resumable calls to work properly.
+            self.stack.values.drop(max_inout);
+            return Err(HostFuncError { error, params });
+        }
         if let Some(results) = caller.results() {
             // Now the results need to be written back to where the caller expects them.
             let caller_offset = self
@@ -498,18 +787,38 @@ impl<'engine> EngineExecutor<'engine> {
         let value_stack = &mut self.stack.values;
         let call_stack = &mut self.stack.calls;
         let code_map = &self.res.code_map;
-        let func_types = &self.res.func_types;
+        let intrinsics = &self.res.intrinsics;
         execute_instrs(
             store_inner,
             cache,
             value_stack,
             call_stack,
             code_map,
-            func_types,
+            intrinsics,
             &mut resource_limiter,
         )
     }
 
+    /// Executes the compiled Wasm function via [`EngineExecutor::execute_compiled_func`].
+    ///
+    /// Accounts the CPU time of the execution to the [`Store`] if enabled.
+    #[inline(always)]
+    fn execute_compiled_func_accounted<T>(
+        &mut self,
+        mut ctx: StoreContextMut<T>,
+        cache: &mut InstanceCache,
+    ) -> Result<WasmOutcome, Error> {
+        #[cfg(unix)]
+        if ctx.store.engine().config().get_cpu_time_accounting() {
+            let start = thread_cpu_time();
+            let outcome = self.execute_compiled_func(ctx.as_context_mut(), cache);
+            let delta = thread_cpu_time().saturating_sub(start);
+            ctx.store.inner.add_cpu_time(delta);
+            return outcome;
+        }
+        self.execute_compiled_func(ctx, cache)
+    }
+
     /// Writes the results of the function execution back into the `results` buffer.
     ///
     /// # Note
@@ -528,3 +837,15 @@ impl<'engine> EngineExecutor<'engine> {
         results.call_results(&self.stack.values.as_slice()[..len_results])
     }
 }
+
+/// Returns the CPU time consumed by the calling thread so far.
+#[cfg(unix)]
+fn thread_cpu_time() -> Duration {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // Safety: `time` is a valid `timespec` and `CLOCK_THREAD_CPUTIME_ID` is always supported.
+    unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+    Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+}
@@ -1,6 +1,6 @@
 use super::{err_stack_overflow, BaseValueStackOffset, FrameValueStackOffset};
 use crate::{
-    engine::{bytecode::RegisterSpan, code_map::InstructionPtr},
+    engine::{bytecode::RegisterSpan, code_map::InstructionPtr, CompiledFunc},
     Instance,
 };
 use alloc::vec::Vec;
@@ -11,14 +11,13 @@ use crate::{
     engine::bytecode::Instruction,
     engine::bytecode::Register,
     engine::executor::stack::ValueStack,
-    engine::CompiledFunc,
     Global,
     Memory,
     Table,
 };
 
 /// The stack of nested function calls.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct CallStack {
     /// The stack of nested function calls.
     calls: Vec<CallFrame>,
@@ -27,7 +26,17 @@ pub struct CallStack {
     /// # Note
     ///
     /// A [`TrapCode::StackOverflow`] is raised if the recursion limit is exceeded.
+    ///
+    /// This is the configured maximum recursion depth minus the depth offset.
     recursion_limit: usize,
+    /// The configured maximum recursion depth.
+    max_recursion_depth: usize,
+    /// The number of [`CallFrame`]s of outer executions that called into this one.
+    ///
+    /// This is non-zero for executions started from within host functions.
+    depth_offset: usize,
+    /// The maximum number of [`CallFrame`]s on the [`CallStack`] since its last reset.
+    peak: usize,
 }
 
 impl CallStack {
@@ -39,6 +48,9 @@ impl CallStack {
         Self {
             calls: Vec::new(),
             recursion_limit,
+            max_recursion_depth: recursion_limit,
+            depth_offset: 0,
+            peak: 0,
         }
     }
 
@@ -52,25 +64,58 @@ impl CallStack {
     /// provide a clean slate for all executions.
     pub fn reset(&mut self) {
         self.calls.clear();
+        self.peak = 0;
+    }
+
+    /// Returns the maximum number of [`CallFrame`]s on the [`CallStack`] since its last reset.
+    pub fn peak(&self) -> usize {
+        self.peak
     }
 
     /// Returns the number of [`CallFrame`] on the [`CallStack`].
     #[inline]
-    fn len(&self) -> usize {
+    pub fn len(&self) -> usize {
         self.calls.len()
     }
 
+    /// Sets the number of [`CallFrame`]s of outer executions that called into this one.
+    ///
+    /// # Note
+    ///
+    /// The outer [`CallFrame`]s count towards the recursion limit of the [`CallStack`].
+    pub fn set_depth_offset(&mut self, depth_offset: usize) {
+        self.depth_offset = depth_offset;
+        self.recursion_limit = self.max_recursion_depth.saturating_sub(depth_offset);
+    }
+
+    /// Returns the total call depth including the [`CallFrame`]s of outer executions.
+    pub fn depth(&self) -> usize {
+        self.depth_offset + self.len()
+    }
+
+    /// Returns the [`CallFrame`]s of the [`CallStack`] from the outermost to the innermost.
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.calls[..]
+    }
+
     /// Pushes a [`CallFrame`] onto the [`CallStack`].
     ///
     /// # Errors
     ///
-    /// If the recursion limit has been reached.
+    /// - If the recursion limit has been reached.
+    /// - If the allocation of the grown [`CallStack`] fails.
     #[inline]
     pub fn push(&mut self, call: CallFrame) -> Result<(), TrapCode> {
-        if self.len() == self.recursion_limit {
+        if self.len() >= self.recursion_limit {
             return Err(err_stack_overflow());
         }
+        if self.calls.len() == self.calls.capacity() {
+            self.calls
+                .try_reserve(1)
+                .map_err(|_| err_stack_overflow())?;
+        }
         self.calls.push(call);
+        self.peak = self.peak.max(self.len());
         Ok(())
     }
 
@@ -110,6 +155,8 @@ impl CallStack {
 /// A single frame of a called [`CompiledFunc`].
 #[derive(Debug, Copy, Clone)]
 pub struct CallFrame {
+    /// The [`CompiledFunc`] executed by the [`CallFrame`].
+    func: CompiledFunc,
     /// The pointer to the [`Instruction`] that is executed next.
     instr_ptr: InstructionPtr,
     /// Pointer to the first mutable cell of a [`CallFrame`].
@@ -130,6 +177,7 @@ pub struct CallFrame {
 impl CallFrame {
     /// Creates a new [`CallFrame`].
     pub fn new(
+        func: CompiledFunc,
         instr_ptr: InstructionPtr,
         frame_ptr: FrameValueStackOffset,
         base_ptr: BaseValueStackOffset,
@@ -137,6 +185,7 @@ impl CallFrame {
         instance: Instance,
     ) -> Self {
         Self {
+            func,
             instr_ptr,
             base_ptr,
             frame_ptr,
@@ -168,6 +217,11 @@ impl CallFrame {
         self.instr_ptr = new_instr_ptr;
     }
 
+    /// Returns the [`CompiledFunc`] executed by the [`CallFrame`].
+    pub fn func(&self) -> CompiledFunc {
+        self.func
+    }
+
     /// Returns the [`InstructionPtr`] of the [`CallFrame`].
     pub fn instr_ptr(&self) -> InstructionPtr {
         self.instr_ptr
@@ -14,7 +14,7 @@ fn err_stack_overflow() -> TrapCode {
 }
 
 /// Data structure that combines both value stack and call stack.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Stack {
     /// The value stack.
     pub values: ValueStack,
@@ -52,6 +52,11 @@ impl Stack {
         self.calls.reset();
     }
 
+    /// Returns the maximum number of nested calls on the [`Stack`] since its last reset.
+    pub fn peak_call_depth(&self) -> usize {
+        self.calls.peak()
+    }
+
     /// Create an empty [`Stack`].
     ///
     /// # Note
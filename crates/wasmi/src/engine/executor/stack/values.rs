@@ -1,7 +1,11 @@
 use super::err_stack_overflow;
 use crate::{
     core::UntypedValue,
-    engine::{bytecode::Register, CompiledFuncEntity},
+    engine::{
+        bytecode::{Register, RegisterSpan},
+        CompiledFuncEntity,
+        FuncConsts,
+    },
 };
 use alloc::vec::Vec;
 use core::{fmt, fmt::Debug, iter, mem, ptr};
@@ -12,6 +16,7 @@ use super::calls::CallFrame;
 #[cfg(doc)]
 use crate::engine::CompiledFunc;
 
+#[derive(Clone)]
 pub struct ValueStack {
     /// The values on the [`ValueStack`].
     values: Vec<UntypedValue>,
@@ -152,7 +157,8 @@ impl ValueStack {
     ///
     /// # Errors
     ///
-    /// When trying to grow the [`ValueStack`] over its maximum size limit.
+    /// - When trying to grow the [`ValueStack`] over its maximum size limit.
+    /// - When the allocation of the grown [`ValueStack`] fails.
     pub fn reserve(&mut self, additional: usize) -> Result<(), TrapCode> {
         let new_len = self
             .len()
@@ -163,6 +169,9 @@ impl ValueStack {
             // Note: By extending with the new length we effectively double
             // the current value stack length and add the additional flat amount
             // on top. This avoids too many frequent reallocations.
+            self.values
+                .try_reserve_exact(new_len)
+                .map_err(|_| err_stack_overflow())?;
             self.values
                 .extend(iter::repeat(UntypedValue::default()).take(new_len));
         }
@@ -192,27 +201,24 @@ impl ValueStack {
         ValueStackOffset(old_sp)
     }
 
-    /// Extends the [`ValueStack`] by the `values` slice.
+    /// Extends the [`ValueStack`] by the function local constant values `consts`.
     ///
     /// Returns the [`ValueStackOffset`] before this operation.
     /// Use [`ValueStack::truncate`] to undo the [`ValueStack`] state change.
     ///
     /// # Panics
     ///
-    /// If the value stack cannot fit `additional` stack values.
-    pub fn extend_slice(&mut self, values: &[UntypedValue]) -> ValueStackOffset {
-        if values.is_empty() {
-            return ValueStackOffset(self.sp);
-        }
+    /// If the value stack cannot fit `consts`.
+    pub fn extend_consts(&mut self, consts: &FuncConsts) -> ValueStackOffset {
         let old_sp = self.sp;
-        let len_values = values.len();
+        let len_consts = consts.len();
         let cells = self
             .values
             .get_mut(self.sp..)
-            .and_then(|slice| slice.get_mut(..len_values))
+            .and_then(|slice| slice.get_mut(..len_consts))
             .unwrap_or_else(|| panic!("did not reserve enough value stack space"));
-        cells.copy_from_slice(values);
-        self.sp += len_values;
+        consts.copy_to(cells);
+        self.sp += len_consts;
         ValueStackOffset(old_sp)
     }
 
@@ -258,7 +264,7 @@ impl ValueStack {
     ) -> Result<(BaseValueStackOffset, FrameValueStackOffset), TrapCode> {
         let len_registers = func.len_registers();
         self.reserve(len_registers as usize)?;
-        let frame_offset = FrameValueStackOffset(self.extend_slice(func.consts()));
+        let frame_offset = FrameValueStackOffset(self.extend_consts(func.consts()));
         let base_offset = BaseValueStackOffset(self.extend_zeros(func.len_cells() as usize));
         Ok((base_offset, frame_offset))
     }
@@ -429,6 +435,23 @@ impl FrameRegisters {
         ptr::write(self.register_offset(register), value)
     }
 
+    /// Copies the `len` values of the `values` span to the `results` span.
+    ///
+    /// # Note
+    ///
+    /// This is a single `memmove` and thus correct for all kinds of
+    /// overlapping `results` and `values` spans.
+    ///
+    /// # Safety
+    ///
+    /// It is the callers responsibility to provide [`RegisterSpan`]s that
+    /// do not access the underlying [`ValueStack`] out of bounds.
+    pub unsafe fn copy_span(&mut self, results: RegisterSpan, values: RegisterSpan, len: u16) {
+        let src = self.register_offset(values.head());
+        let dst = self.register_offset(results.head());
+        ptr::copy(src, dst, usize::from(len))
+    }
+
     /// Returns the underlying pointer offset by the [`Register`] index.
     unsafe fn register_offset(&self, register: Register) -> *mut UntypedValue {
         unsafe { self.ptr.offset(register.to_i16() as isize) }
@@ -1,4 +1,5 @@
-use crate::{core::TrapCode, engine::bytecode::RegisterSpan, Error, Func};
+use crate::{core::TrapCode, engine::bytecode::RegisterSpan, Error, Func, Value};
+use alloc::boxed::Box;
 
 /// Either a Wasm trap or a host trap with its originating host [`Func`].
 #[derive(Debug)]
@@ -9,16 +10,37 @@ pub enum TaggedTrap {
     Host {
         host_error: Error,
         host_func: Func,
+        host_params: Box<[Value]>,
         caller_results: RegisterSpan,
     },
+    /// The Wasm execution ran out of fuel at the start of a basic block.
+    ///
+    /// # Note
+    ///
+    /// In this case the execution can be resumed after refueling.
+    OutOfFuel(Error),
+    /// The Wasm execution exhausted its time slice at the start of a basic block
+    /// or yielded at its epoch deadline.
+    ///
+    /// # Note
+    ///
+    /// In this case the execution can be resumed.
+    /// This only ever happens for executions that are allowed to yield.
+    Yield,
 }
 
 impl TaggedTrap {
     /// Creates a [`TaggedTrap`] from a host error.
-    pub fn host(host_func: Func, host_error: Error, caller_results: RegisterSpan) -> Self {
+    pub fn host(
+        host_func: Func,
+        host_error: Error,
+        host_params: Box<[Value]>,
+        caller_results: RegisterSpan,
+    ) -> Self {
         Self::Host {
             host_func,
             host_error,
+            host_params,
             caller_results,
         }
     }
@@ -28,6 +50,8 @@ impl TaggedTrap {
         match self {
             TaggedTrap::Wasm(error) => error,
             TaggedTrap::Host { host_error, .. } => host_error,
+            TaggedTrap::OutOfFuel(error) => error,
+            TaggedTrap::Yield => unreachable!("executions that may not yield must never yield"),
         }
     }
 }
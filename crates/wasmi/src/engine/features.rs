@@ -0,0 +1,104 @@
+use super::StackLimits;
+
+/// The set of Wasm proposals and limits an [`Engine`] was configured with.
+///
+/// Query it via [`Engine::features`].
+///
+/// # Note
+///
+/// This allows hosts to advertise the capabilities of an [`Engine`]
+/// and to check them against the expectations of Wasm producers.
+///
+/// [`Engine`]: crate::Engine
+/// [`Engine::features`]: crate::Engine::features
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WasmFeatures {
+    /// Is `true` if the `mutable-global` Wasm proposal is enabled.
+    pub(super) mutable_global: bool,
+    /// Is `true` if the `sign-extension` Wasm proposal is enabled.
+    pub(super) sign_extension: bool,
+    /// Is `true` if the `saturating-float-to-int` Wasm proposal is enabled.
+    pub(super) saturating_float_to_int: bool,
+    /// Is `true` if the `multi-value` Wasm proposal is enabled.
+    pub(super) multi_value: bool,
+    /// Is `true` if the `bulk-memory` Wasm proposal is enabled.
+    pub(super) bulk_memory: bool,
+    /// Is `true` if the `reference-types` Wasm proposal is enabled.
+    pub(super) reference_types: bool,
+    /// Is `true` if the `tail-call` Wasm proposal is enabled.
+    pub(super) tail_call: bool,
+    /// Is `true` if the `extended-const` Wasm proposal is enabled.
+    pub(super) extended_const: bool,
+    /// Is `true` if Wasm instructions on `f32` and `f64` types are allowed.
+    pub(super) floats: bool,
+    /// The limits set on the value stack and call stack.
+    pub(super) stack_limits: StackLimits,
+}
+
+impl WasmFeatures {
+    /// Returns `true` if the [`mutable-global`] Wasm proposal is enabled.
+    ///
+    /// [`mutable-global`]: https://github.com/WebAssembly/mutable-global
+    pub fn mutable_global(&self) -> bool {
+        self.mutable_global
+    }
+
+    /// Returns `true` if the [`sign-extension`] Wasm proposal is enabled.
+    ///
+    /// [`sign-extension`]: https://github.com/WebAssembly/sign-extension-ops
+    pub fn sign_extension(&self) -> bool {
+        self.sign_extension
+    }
+
+    /// Returns `true` if the [`saturating-float-to-int`] Wasm proposal is enabled.
+    ///
+    /// [`saturating-float-to-int`]: https://github.com/WebAssembly/nontrapping-float-to-int-conversions
+    pub fn saturating_float_to_int(&self) -> bool {
+        self.saturating_float_to_int
+    }
+
+    /// Returns `true` if the [`multi-value`] Wasm proposal is enabled.
+    ///
+    /// [`multi-value`]: https://github.com/WebAssembly/multi-value
+    pub fn multi_value(&self) -> bool {
+        self.multi_value
+    }
+
+    /// Returns `true` if the [`bulk-memory`] Wasm proposal is enabled.
+    ///
+    /// [`bulk-memory`]: https://github.com/WebAssembly/bulk-memory-operations
+    pub fn bulk_memory(&self) -> bool {
+        self.bulk_memory
+    }
+
+    /// Returns `true` if the [`reference-types`] Wasm proposal is enabled.
+    ///
+    /// [`reference-types`]: https://github.com/WebAssembly/reference-types
+    pub fn reference_types(&self) -> bool {
+        self.reference_types
+    }
+
+    /// Returns `true` if the [`tail-call`] Wasm proposal is enabled.
+    ///
+    /// [`tail-call`]: https://github.com/WebAssembly/tail-call
+    pub fn tail_call(&self) -> bool {
+        self.tail_call
+    }
+
+    /// Returns `true` if the [`extended-const`] Wasm proposal is enabled.
+    ///
+    /// [`extended-const`]: https://github.com/WebAssembly/extended-const
+    pub fn extended_const(&self) -> bool {
+        self.extended_const
+    }
+
+    /// Returns `true` if Wasm instructions on `f32` and `f64` types are allowed.
+    pub fn floats(&self) -> bool {
+        self.floats
+    }
+
+    /// Returns the [`StackLimits`] of the value stack and call stack.
+    pub fn stack_limits(&self) -> StackLimits {
+        self.stack_limits
+    }
+}
@@ -78,7 +78,7 @@ impl<'a> FuncParams<'a> {
     ///
     /// If the length of hte `params_results` slice does not match the maximum
     /// of the `len_params` and `Len_results`.
-    pub(super) fn new(
+    pub(crate) fn new(
         params_results: &'a mut [UntypedValue],
         len_params: usize,
         len_results: usize,
@@ -1,5 +1,7 @@
 use super::{EngineIdx, Guarded};
 use crate::FuncType;
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::cmp::Ordering;
 use wasmi_arena::{ArenaIndex, DedupArena, GuardedEntity};
 
 /// A raw index to a function signature entity.
@@ -34,19 +36,37 @@ impl ArenaIndex for DedupFuncTypeIdx {
 ///
 /// - Requires another indirection to acquire information such as parameter
 ///   or result types of the underlying [`FuncType`].
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-#[repr(transparent)]
-pub struct DedupFuncType(GuardedEntity<EngineIdx, DedupFuncTypeIdx>);
+///
+/// Parameter and result names do not take part in the comparison of [`DedupFuncType`]s.
+#[derive(Debug, Copy, Clone)]
+pub struct DedupFuncType {
+    /// The deduplicated [`FuncType`] without parameter and result names.
+    ty: GuardedEntity<EngineIdx, DedupFuncTypeIdx>,
+    /// The index of the named variant of `ty` in the [`FuncTypeRegistry`] if any.
+    ///
+    /// # Note
+    ///
+    /// A value of 0 refers to the unnamed `ty` itself.
+    names: u32,
+}
+
+impl PartialEq for DedupFuncType {
+    fn eq(&self, other: &Self) -> bool {
+        self.ty == other.ty
+    }
+}
+
+impl Eq for DedupFuncType {}
 
-impl DedupFuncType {
-    /// Creates a new function signature reference.
-    pub(super) fn from_inner(stored: GuardedEntity<EngineIdx, DedupFuncTypeIdx>) -> Self {
-        Self(stored)
+impl PartialOrd for DedupFuncType {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
+}
 
-    /// Returns the underlying stored representation.
-    pub(super) fn into_inner(self) -> GuardedEntity<EngineIdx, DedupFuncTypeIdx> {
-        self.0
+impl Ord for DedupFuncType {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ty.cmp(&other.ty)
     }
 }
 
@@ -75,6 +95,14 @@ pub struct FuncTypeRegistry {
     /// The engine deduplicates function types to make the equality
     /// comparison very fast. This helps to speed up indirect calls.
     func_types: DedupArena<DedupFuncTypeIdx, FuncType>,
+    /// The named variants of the deduplicated function types.
+    ///
+    /// # Note
+    ///
+    /// Named variants are keyed by their unnamed deduplicated function type so that
+    /// equal function types are deduplicated to equal [`DedupFuncType`]s regardless
+    /// of their parameter and result names.
+    named: BTreeMap<DedupFuncTypeIdx, Vec<FuncType>>,
 }
 
 impl FuncTypeRegistry {
@@ -83,6 +111,7 @@ impl FuncTypeRegistry {
         Self {
             engine_idx,
             func_types: DedupArena::default(),
+            named: BTreeMap::new(),
         }
     }
 
@@ -105,10 +134,30 @@ impl FuncTypeRegistry {
 
     /// Allocates a new function type to the engine.
     pub(crate) fn alloc_func_type(&mut self, func_type: FuncType) -> DedupFuncType {
-        DedupFuncType::from_inner(Guarded::new(
-            self.engine_idx,
-            self.func_types.alloc(func_type),
-        ))
+        let index = self.func_types.alloc(func_type.without_names());
+        let names = match func_type.has_names() {
+            true => {
+                let named = self.named.entry(index).or_default();
+                let position = match named
+                    .iter()
+                    .position(|named| named.cmp_names(&func_type).is_eq())
+                {
+                    Some(position) => position,
+                    None => {
+                        named.push(func_type);
+                        named.len() - 1
+                    }
+                };
+                u32::try_from(position + 1).unwrap_or_else(|error| {
+                    panic!("too many named variants for function type {index:?}: {error}")
+                })
+            }
+            false => 0,
+        };
+        DedupFuncType {
+            ty: Guarded::new(self.engine_idx, index),
+            names,
+        }
     }
 
     /// Resolves a deduplicated function type into a [`FuncType`] entity.
@@ -118,9 +167,33 @@ impl FuncTypeRegistry {
     /// - If the deduplicated function type is not owned by the engine.
     /// - If the deduplicated function type cannot be resolved to its entity.
     pub(crate) fn resolve_func_type(&self, func_type: &DedupFuncType) -> &FuncType {
-        let entity_index = self.unwrap_index(func_type.into_inner());
-        self.func_types
-            .get(entity_index)
+        let entity_index = self.unwrap_index(func_type.ty);
+        let resolved = match func_type.names.checked_sub(1) {
+            Some(position) => self
+                .named
+                .get(&entity_index)
+                .and_then(|named| named.get(position as usize)),
+            None => self.func_types.get(entity_index),
+        };
+        resolved
             .unwrap_or_else(|| panic!("failed to resolve stored function type: {entity_index:?}"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ValueType;
+
+    #[test]
+    fn order_ignores_names() {
+        let mut registry = FuncTypeRegistry::new(EngineIdx::new());
+        let ty = |params: &[ValueType]| FuncType::new(params.iter().copied(), [ValueType::I64]);
+        let a = registry.alloc_func_type(ty(&[ValueType::I32]));
+        let b = registry.alloc_func_type(ty(&[ValueType::I32]).with_param_names(["x"]));
+        let c = registry.alloc_func_type(ty(&[ValueType::F32]));
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+        assert_ne!(a.cmp(&c), Ordering::Equal);
+        assert_eq!(a.cmp(&c), b.cmp(&c));
+    }
+}
@@ -0,0 +1,297 @@
+//! Host intrinsics that are called without the generic host call machinery.
+
+use super::{bytecode::IntrinsicIdx, DedupFuncType};
+use crate::{
+    core::UntypedValue,
+    value::WithType,
+    AsContextMut,
+    Caller,
+    Error,
+    Func,
+    FuncType,
+    Value,
+};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use core::{cmp, fmt, fmt::Display};
+
+/// The maximum number of parameters or results of an [`Intrinsic`].
+pub const MAX_INTRINSIC_INOUT: usize = 16;
+
+/// The closure type of an [`Intrinsic`].
+type IntrinsicFn = dyn Fn(&mut [UntypedValue]) -> Result<(), Error> + Send + Sync;
+
+/// A host function that is called directly by the Wasmi executor.
+///
+/// # Note
+///
+/// Calls to imported functions that match a defined [`Intrinsic`] by import name
+/// and function type are lowered to a dedicated instruction upon translation.
+/// If the import resolves to the [`Intrinsic`] upon instantiation those calls
+/// do not set up a host call frame and have no access to the
+/// [`Store`](crate::Store) but read their parameters from and write their
+/// results to the registers of the calling function directly.
+///
+/// The closure receives a buffer holding the parameters of the call which is
+/// large enough to also hold its results. Results are written to the front of
+/// the buffer overwriting the parameters.
+///
+/// Errors returned by an [`Intrinsic`] are traps and are not resumable.
+#[derive(Clone)]
+pub struct Intrinsic {
+    /// The function type of the [`Intrinsic`].
+    ty: FuncType,
+    /// The closure of the [`Intrinsic`].
+    func: Arc<IntrinsicFn>,
+}
+
+impl fmt::Debug for Intrinsic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Intrinsic")
+            .field("ty", &self.ty)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Intrinsic {
+    /// Creates a new [`Intrinsic`] with the given function type and closure.
+    ///
+    /// # Errors
+    ///
+    /// If `ty` has more than [`MAX_INTRINSIC_INOUT`] parameters or results.
+    pub fn new<F>(ty: FuncType, func: F) -> Result<Self, IntrinsicError>
+    where
+        F: Fn(&mut [UntypedValue]) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        let (params, results) = ty.params_results();
+        if params.len() > MAX_INTRINSIC_INOUT || results.len() > MAX_INTRINSIC_INOUT {
+            return Err(IntrinsicError::TooManyParamsOrResults);
+        }
+        Ok(Self {
+            ty,
+            func: Arc::new(func),
+        })
+    }
+
+    /// Returns the function type of the [`Intrinsic`].
+    pub fn ty(&self) -> &FuncType {
+        &self.ty
+    }
+
+    /// Calls the [`Intrinsic`] with the given in-out `buffer`.
+    pub(crate) fn call(&self, buffer: &mut [UntypedValue]) -> Result<(), Error> {
+        (self.func)(buffer)
+    }
+
+    /// Creates a host [`Func`] that calls the [`Intrinsic`] at `index`.
+    ///
+    /// # Note
+    ///
+    /// Lowered calls to imports resolved to this [`Func`] call the [`Intrinsic`] directly.
+    /// The [`Func`] itself is used wherever the [`Intrinsic`] is not called directly,
+    /// e.g. for indirect calls, tail calls or calls from the host side.
+    pub(crate) fn to_func<T>(
+        &self,
+        ctx: impl AsContextMut<UserState = T>,
+        index: IntrinsicIdx,
+    ) -> Func {
+        let intrinsic = self.clone();
+        let func = move |_caller: Caller<'_, T>, params: &[Value], results: &mut [Value]| {
+            let mut buffer = [UntypedValue::default(); MAX_INTRINSIC_INOUT];
+            let len_inout = cmp::max(params.len(), results.len());
+            for (value, param) in buffer.iter_mut().zip(params) {
+                *value = UntypedValue::from(param.clone());
+            }
+            intrinsic.call(&mut buffer[..len_inout])?;
+            let result_types = intrinsic.ty.results();
+            for ((result, value), ty) in results.iter_mut().zip(buffer).zip(result_types) {
+                *result = value.with_type(*ty);
+            }
+            Ok(())
+        };
+        Func::new_intrinsic(ctx, self.ty.clone(), index, func)
+    }
+}
+
+/// Errors that can occur upon defining an [`Intrinsic`].
+#[derive(Debug)]
+pub enum IntrinsicError {
+    /// The function type of the [`Intrinsic`] has too many parameters or results.
+    TooManyParamsOrResults,
+    /// An [`Intrinsic`] with the same import name has already been defined.
+    DuplicateDefinition {
+        /// The module name of the duplicate [`Intrinsic`].
+        module: Box<str>,
+        /// The field name of the duplicate [`Intrinsic`].
+        name: Box<str>,
+    },
+}
+
+impl Display for IntrinsicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyParamsOrResults => {
+                write!(
+                    f,
+                    "intrinsics must have at most {MAX_INTRINSIC_INOUT} parameters and results"
+                )
+            }
+            Self::DuplicateDefinition { module, name } => {
+                write!(
+                    f,
+                    "encountered duplicate intrinsic definition for {module}::{name}"
+                )
+            }
+        }
+    }
+}
+
+/// An [`Intrinsic`] defined for an [`Engine`](crate::Engine).
+#[derive(Debug)]
+pub struct IntrinsicEntity {
+    /// The deduplicated function type of the [`Intrinsic`].
+    ty: DedupFuncType,
+    /// The number of parameters of the [`Intrinsic`].
+    len_params: u16,
+    /// The number of results of the [`Intrinsic`].
+    len_results: u16,
+    /// The underlying [`Intrinsic`].
+    intrinsic: Intrinsic,
+}
+
+impl IntrinsicEntity {
+    /// Returns the number of parameters of the [`IntrinsicEntity`].
+    pub fn len_params(&self) -> usize {
+        usize::from(self.len_params)
+    }
+
+    /// Returns the number of results of the [`IntrinsicEntity`].
+    pub fn len_results(&self) -> usize {
+        usize::from(self.len_results)
+    }
+
+    /// Returns the underlying [`Intrinsic`].
+    pub fn intrinsic(&self) -> &Intrinsic {
+        &self.intrinsic
+    }
+}
+
+/// The [`Intrinsic`]s defined for an [`Engine`](crate::Engine).
+#[derive(Debug, Default)]
+pub struct Intrinsics {
+    /// All defined intrinsics indexed by [`IntrinsicIdx`].
+    entities: Vec<IntrinsicEntity>,
+    /// The [`IntrinsicIdx`] of all intrinsics by their module and field names.
+    names: BTreeMap<Box<str>, BTreeMap<Box<str>, IntrinsicIdx>>,
+}
+
+impl Intrinsics {
+    /// Defines the [`Intrinsic`] under the import name `module::name`.
+    ///
+    /// # Errors
+    ///
+    /// If an [`Intrinsic`] is already defined under the same import name.
+    pub fn define(
+        &mut self,
+        module: &str,
+        name: &str,
+        ty: DedupFuncType,
+        intrinsic: Intrinsic,
+    ) -> Result<(), IntrinsicError> {
+        let names = self.names.entry(module.into()).or_default();
+        if names.contains_key(name) {
+            return Err(IntrinsicError::DuplicateDefinition {
+                module: module.into(),
+                name: name.into(),
+            });
+        }
+        let Ok(index) = u32::try_from(self.entities.len()) else {
+            panic!("out of bounds intrinsic index: {}", self.entities.len())
+        };
+        let (params, results) = intrinsic.ty().params_results();
+        // Note: cannot overflow since `Intrinsic::new` limits parameters and results.
+        let len_params = params.len() as u16;
+        let len_results = results.len() as u16;
+        names.insert(name.into(), IntrinsicIdx::from(index));
+        self.entities.push(IntrinsicEntity {
+            ty,
+            len_params,
+            len_results,
+            intrinsic,
+        });
+        Ok(())
+    }
+
+    /// Returns the [`IntrinsicIdx`] of the [`Intrinsic`] defined as `module::name` if any.
+    ///
+    /// Returns `None` if the [`Intrinsic`] does not match the function type `ty`.
+    pub fn get(&self, module: &str, name: &str, ty: DedupFuncType) -> Option<IntrinsicIdx> {
+        let index = self.get_by_name(module, name)?;
+        if self.resolve(index).ty != ty {
+            return None;
+        }
+        Some(index)
+    }
+
+    /// Returns the [`IntrinsicIdx`] of the [`Intrinsic`] defined as `module::name` if any.
+    pub fn get_by_name(&self, module: &str, name: &str) -> Option<IntrinsicIdx> {
+        self.names.get(module)?.get(name).copied()
+    }
+
+    /// Returns the [`IntrinsicEntity`] at `index`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` does not refer to an [`Intrinsic`] of the [`Intrinsics`].
+    #[inline]
+    pub fn resolve(&self, index: IntrinsicIdx) -> &IntrinsicEntity {
+        &self.entities[index.to_u32() as usize]
+    }
+}
+
+#[cfg(all(test, feature = "compiler"))]
+mod tests {
+    use super::*;
+    use crate::{
+        core::ValueType,
+        engine::bytecode::{Instruction, Register, RegisterSpan},
+        Engine,
+        Module,
+    };
+
+    #[test]
+    fn call_is_lowered_to_intrinsic() {
+        let engine = Engine::default();
+        let ty = FuncType::new([ValueType::I32], [ValueType::I32]);
+        let intrinsic = Intrinsic::new(ty, |_| Ok(())).unwrap();
+        engine.define_intrinsic("host", "id", intrinsic).unwrap();
+        let wasm = wat::parse_str(
+            r#"
+            (module
+                (import "host" "id" (func $id (param i32) (result i32)))
+                (func (param i32) (result i32)
+                    (call $id (local.get 0))
+                )
+            )
+            "#,
+        )
+        .unwrap();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let (_, func) = module.internal_funcs().next().unwrap();
+        assert_eq!(
+            engine.resolve_instr(func, 0).unwrap(),
+            Some(Instruction::call_intrinsic(
+                RegisterSpan::new(Register::from_i16(1)),
+                0,
+            )),
+        );
+    }
+
+    #[test]
+    fn too_many_params_fails() {
+        let ty = FuncType::new([ValueType::I32; MAX_INTRINSIC_INOUT + 1], []);
+        assert!(matches!(
+            Intrinsic::new(ty, |_| Ok(())),
+            Err(IntrinsicError::TooManyParamsOrResults),
+        ));
+    }
+}
@@ -1,7 +1,9 @@
+use super::TranslationError;
 use crate::core::UntypedValue;
 use core::{
     fmt::{self, Display},
     mem::size_of,
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
 /// Default value for initial value stack height in bytes.
@@ -14,7 +16,7 @@ const DEFAULT_MAX_VALUE_STACK_HEIGHT: usize = 1024 * DEFAULT_MIN_VALUE_STACK_HEI
 const DEFAULT_MAX_RECURSION_DEPTH: usize = 1024;
 
 /// The configured limits of the Wasm stack.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct StackLimits {
     /// The initial value stack height that the Wasm stack prepares.
     pub initial_value_stack_height: usize,
@@ -75,3 +77,47 @@ impl Default for StackLimits {
         }
     }
 }
+
+/// Tracks the compiled code of an [`Engine`] against its configured budget.
+///
+/// [`Engine`]: crate::Engine
+#[derive(Debug)]
+pub struct CodeBudget {
+    /// The maximum number of bytes of compiled code if any.
+    max: Option<usize>,
+    /// The number of bytes of compiled code so far.
+    used: AtomicUsize,
+}
+
+impl CodeBudget {
+    /// Creates a new [`CodeBudget`] with `max` bytes of compiled code if any.
+    pub fn new(max: Option<usize>) -> Self {
+        Self {
+            max,
+            used: AtomicUsize::new(0),
+        }
+    }
+
+    /// Charges `bytes` of compiled code against the [`CodeBudget`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`CodeBudget`] would be exceeded. Nothing is charged in this case.
+    pub fn charge(&self, bytes: usize) -> Result<(), TranslationError> {
+        let Some(max) = self.max else {
+            self.used.fetch_add(bytes, Ordering::Relaxed);
+            return Ok(());
+        };
+        self.used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(bytes).filter(|&used| used <= max)
+            })
+            .map(|_| ())
+            .map_err(|_| TranslationError::CompiledCodeBudgetExceeded)
+    }
+
+    /// Returns the number of bytes of compiled code charged so far.
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+}
@@ -1,31 +1,77 @@
 //! The Wasmi interpreter.
 
+mod address_map;
+#[cfg(feature = "fault-injection")]
+mod alloc_failures;
+mod backtrace;
+#[cfg(feature = "compiler")]
 mod block_type;
 pub mod bytecode;
+mod bytecode_json;
 mod cache;
 mod code_map;
+#[cfg(all(feature = "std", feature = "compiler"))]
+mod compile;
 mod config;
+mod const_pool;
+mod diagnostics;
 mod executor;
+mod features;
 mod func_args;
 mod func_types;
+mod intrinsic;
 mod limits;
+mod report;
 mod resumable;
+mod stats;
+mod symbols;
 mod traits;
 mod translator;
+mod trap_frame;
 
 #[cfg(test)]
 mod tests;
 
-#[cfg(test)]
+use self::bytecode::IntrinsicIdx;
+
+#[cfg(all(test, feature = "compiler"))]
 use self::bytecode::RegisterSpan;
 
+#[cfg(feature = "fault-injection")]
+pub(crate) use self::alloc_failures::AllocFailureInjector;
+#[cfg(feature = "fault-injection")]
+pub use self::alloc_failures::AllocFailures;
+#[cfg(all(feature = "std", feature = "compiler"))]
+pub use self::compile::CompilationHandle;
+pub use self::{
+    address_map::AddressMap,
+    backtrace::{FrameInfo, WasmBacktrace},
+    code_map::CompiledFunc,
+    config::{CompilationMode, Config, SafepointGranularity},
+    diagnostics::{Diagnostic, DiagnosticKind},
+    features::WasmFeatures,
+    intrinsic::{Intrinsic, IntrinsicError, MAX_INTRINSIC_INOUT},
+    limits::StackLimits,
+    report::CallReport,
+    resumable::{
+        ResumableCall,
+        ResumableInvocation,
+        TypedResumableCall,
+        TypedResumableInvocation,
+        TypedYieldedInvocation,
+        YieldedInvocation,
+    },
+    stats::EngineStats,
+    symbols::{Symbol, SymbolProvider, SymbolQuery},
+    traits::{CallParams, CallResults},
+    translator::{Instr, TranslationError},
+    trap_frame::TrapFrame,
+};
+#[cfg(feature = "compiler")]
 pub(crate) use self::{
     block_type::BlockType,
-    config::FuelCosts,
-    executor::Stack,
-    func_args::{FuncFinished, FuncParams, FuncResults},
-    func_types::DedupFuncType,
     translator::{
+        check_denied_operators,
         FuncTranslationDriver,
         FuncTranslator,
         FuncTranslatorAllocations,
@@ -34,34 +80,50 @@ pub(crate) use self::{
         WasmTranslator,
     },
 };
-pub use self::{
-    code_map::CompiledFunc,
-    config::{CompilationMode, Config},
-    limits::StackLimits,
-    resumable::{ResumableCall, ResumableInvocation, TypedResumableCall, TypedResumableInvocation},
-    traits::{CallParams, CallResults},
-    translator::{Instr, TranslationError},
-};
 use self::{
-    code_map::{CodeMap, CompiledFuncEntity},
+    code_map::CodeMap,
+    const_pool::FuncConsts,
     func_types::FuncTypeRegistry,
     resumable::ResumableCallBase,
+    stats::EngineCounters,
+    symbols::SharedSymbolProvider,
+};
+pub(crate) use self::{
+    code_map::CompiledFuncEntity,
+    config::FuelCosts,
+    diagnostics::DiagnosticsSink,
+    executor::{CallStack, Stack},
+    func_args::{FuncFinished, FuncParams, FuncResults},
+    func_types::DedupFuncType,
+    intrinsic::Intrinsics,
+    limits::CodeBudget,
+    report::CallReportProbe,
 };
 use crate::{
-    module::{FuncIdx, ModuleHeader},
+    store::StoreInner,
+    AsContextMut,
     Error,
     Func,
     FuncType,
+    Module,
     StoreContextMut,
 };
 use alloc::{
+    string::String,
     sync::{Arc, Weak},
     vec::Vec,
 };
-use core::sync::atomic::{AtomicU32, Ordering};
+use core::{
+    mem,
+    sync::atomic::{AtomicU32, Ordering},
+};
 use spin::{Mutex, RwLock};
 use wasmi_arena::{ArenaIndex, GuardedEntity};
-use wasmparser::{FuncToValidate, FuncValidatorAllocations, ValidatorResources};
+#[cfg(feature = "compiler")]
+use {
+    crate::module::{FuncIdx, ModuleHeader},
+    wasmparser::{FuncToValidate, FuncValidatorAllocations, ValidatorResources},
+};
 
 #[cfg(test)]
 use self::bytecode::Instruction;
@@ -77,7 +139,7 @@ use crate::Store;
 /// # Note
 ///
 /// Used to protect against invalid entity indices.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct EngineIdx(u32);
 
 impl ArenaIndex for EngineIdx {
@@ -166,6 +228,227 @@ impl Engine {
         self.inner.config()
     }
 
+    /// Returns the [`WasmFeatures`] of the [`Engine`].
+    ///
+    /// These are the Wasm proposals and limits the [`Engine`] was configured with.
+    pub fn features(&self) -> WasmFeatures {
+        self.config().features()
+    }
+
+    /// Compiles the Wasm `bytes` into a [`Module`] on a background thread.
+    ///
+    /// # Note
+    ///
+    /// This performs the same parsing, validation and translation as [`Module::new`]
+    /// but returns immediately. Retrieve the [`Module`] from the returned
+    /// [`CompilationHandle`] via [`CompilationHandle::wait`] or by `.await`ing it.
+    ///
+    /// [`Module`]: crate::Module
+    /// [`Module::new`]: crate::Module::new
+    #[cfg(all(feature = "std", feature = "compiler"))]
+    pub fn compile_async<T>(&self, bytes: T) -> CompilationHandle
+    where
+        T: AsRef<[u8]> + Send + 'static,
+    {
+        CompilationHandle::spawn(self, bytes)
+    }
+
+    /// Increments the epoch of the [`Engine`] by one.
+    ///
+    /// # Note
+    ///
+    /// Executions within a [`Store`] of the [`Engine`] are interrupted once the epoch
+    /// reaches the deadline of the [`Store`] if epoch based interruption is enabled.
+    /// This is cheap and can be called from any thread, e.g. from a timer thread.
+    ///
+    /// [`Store`]: crate::Store
+    pub fn increment_epoch(&self) {
+        self.inner.epoch.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the shared epoch counter of the [`Engine`].
+    pub(crate) fn epoch(&self) -> &Arc<AtomicU32> {
+        &self.inner.epoch
+    }
+
+    /// Releases all cached execution stacks and reusable translation allocations.
+    ///
+    /// # Note
+    ///
+    /// This also drops deduplicated function local constants that are no longer
+    /// used by any compiled function. Caches are refilled on demand by later
+    /// translations and executions which therefore might be slightly slower.
+    /// Use this to return memory to the system between bursts of activity.
+    pub fn clear_caches(&self) {
+        self.inner.clear_caches()
+    }
+
+    /// Releases all caches and shrinks the internal buffers of the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// This does everything [`Engine::clear_caches`] does and additionally
+    /// shrinks the capacity of the [`Engine`]'s internal data structures
+    /// to fit the Wasm functions compiled so far.
+    pub fn shrink_to_fit(&self) {
+        self.inner.shrink_to_fit()
+    }
+
+    /// Returns the [`AddressMap`] of the function at `func_index` of the `module`.
+    ///
+    /// # Note
+    ///
+    /// The index space of `func_index` includes imported functions.
+    ///
+    /// Returns `None` if
+    ///
+    /// - [`Config::generate_address_maps`] was disabled,
+    /// - `func_index` does not refer to an internally defined function of `module` or
+    /// - the function has not yet been translated, e.g. due to lazy compilation.
+    ///
+    /// # Panics
+    ///
+    /// If `module` was not compiled by this [`Engine`].
+    pub fn address_map(&self, module: &Module, func_index: u32) -> Option<AddressMap> {
+        assert!(Engine::same(self, module.engine()));
+        let func = module.compiled_func(func_index)?;
+        self.address_map_of(func)
+    }
+
+    /// Returns the translated Wasmi bytecode of the function at `func_index` of the `module` as JSON.
+    ///
+    /// This allows external analysis and visualization tools to consume the Wasmi
+    /// bytecode without parsing its human readable [`Debug`] representation.
+    ///
+    /// # Format
+    ///
+    /// The returned JSON object has the following fields:
+    ///
+    /// - `func_index`: the index of the function within the `module`.
+    /// - `registers`: the total number of registers `len` of the function, the number
+    ///   of function local constant values `len_consts` and the number of value stack
+    ///   cells `len_cells` used by parameters, locals and temporaries.
+    /// - `consts`: the function local constant values as pairs of their `register`
+    ///   and their raw 64-bit `bits`. Those registers have negative indices starting at `-1`.
+    /// - `instrs`: the instructions in order with their `index`, the name of their
+    ///   operation `op`, their `operands` and the instruction indices of their
+    ///   `branch_targets` if any.
+    ///
+    /// Operands are encoded as JSON objects for named fields and JSON arrays otherwise.
+    /// Named operand values are wrapped in a JSON object keyed by their type name,
+    /// e.g. register `3` is encoded as `{"Register":3}`.
+    ///
+    /// # Note
+    ///
+    /// - The index space of `func_index` includes imported functions.
+    /// - The Wasmi bytecode is not stable and may change between Wasmi versions.
+    ///
+    /// Returns `None` if
+    ///
+    /// - `func_index` does not refer to an internally defined function of `module` or
+    /// - the function has not yet been translated, e.g. due to lazy compilation.
+    ///
+    /// # Panics
+    ///
+    /// If `module` was not compiled by this [`Engine`].
+    pub fn bytecode_json(&self, module: &Module, func_index: u32) -> Option<String> {
+        assert!(Engine::same(self, module.engine()));
+        let func = module.compiled_func(func_index)?;
+        self.inner.bytecode_json_of(func)
+    }
+
+    /// Returns a snapshot of the [`EngineStats`] of the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// The statistics are maintained via cheap atomic counters and
+    /// are shared by all clones of the [`Engine`].
+    pub fn stats(&self) -> EngineStats {
+        self.inner.stats.snapshot()
+    }
+
+    /// Returns the number of bytes of compiled code of all Wasm modules of the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// This is the amount charged against [`Config::max_compiled_code_bytes`].
+    /// Lazily compiled functions are only accounted for once they have been compiled.
+    pub fn compiled_code_bytes(&self) -> usize {
+        self.inner.code_budget.used()
+    }
+
+    /// Returns the number of allocations of the [`Engine`] subject to [`Config::alloc_failures`].
+    ///
+    /// This includes the allocations that failed on purpose.
+    /// Returns `None` if [`Config::alloc_failures`] is not configured.
+    ///
+    /// # Note
+    ///
+    /// This is useful to determine the number of allocations of a workload
+    /// in order to test the failure of each of them via [`AllocFailures::Nth`].
+    #[cfg(feature = "fault-injection")]
+    pub fn alloc_count(&self) -> Option<u64> {
+        self.inner
+            .alloc_failures
+            .as_ref()
+            .map(|alloc_failures| alloc_failures.count())
+    }
+
+    /// Returns the [`AllocFailureInjector`] of the [`Engine`] if any.
+    #[cfg(feature = "fault-injection")]
+    pub(crate) fn alloc_failures(&self) -> Option<&Arc<AllocFailureInjector>> {
+        self.inner.alloc_failures.as_ref()
+    }
+
+    /// Returns the [`EngineCounters`] of the [`Engine`].
+    pub(crate) fn counters(&self) -> &EngineCounters {
+        &self.inner.stats
+    }
+
+    /// Registers the [`SymbolProvider`] of the [`Engine`] replacing any previous one.
+    ///
+    /// # Note
+    ///
+    /// The [`SymbolProvider`] is shared by all clones of the [`Engine`] and is used to
+    /// resolve the [`Symbol`]s of [`WasmBacktrace`]s and of [`Engine::symbolicate`].
+    pub fn set_symbol_provider<P>(&self, provider: P)
+    where
+        P: SymbolProvider + 'static,
+    {
+        self.inner
+            .set_symbol_provider(Some(SharedSymbolProvider::new(provider)))
+    }
+
+    /// Removes the [`SymbolProvider`] of the [`Engine`] if any.
+    pub fn remove_symbol_provider(&self) {
+        self.inner.set_symbol_provider(None)
+    }
+
+    /// Resolves the [`Symbol`] of the function at `func_index` of `module` via the registered [`SymbolProvider`].
+    ///
+    /// The `wasm_offset` is the Wasm bytecode offset within the `module` if known.
+    ///
+    /// Returns `None` if no [`SymbolProvider`] is registered or if it cannot resolve the location.
+    pub fn symbolicate(
+        &self,
+        module: &Module,
+        func_index: u32,
+        wasm_offset: Option<u32>,
+    ) -> Option<Symbol> {
+        let provider = self.inner.symbol_provider()?;
+        provider.symbolicate(&SymbolQuery::new(
+            module.name(),
+            func_index,
+            module.func_name(func_index),
+            wasm_offset,
+        ))
+    }
+
+    /// Captures the [`WasmBacktrace`] of the Wasm call frames on the `calls` stack.
+    pub(crate) fn capture_backtrace(&self, store: &StoreInner, calls: &CallStack) -> WasmBacktrace {
+        self.inner.capture_backtrace(store, calls)
+    }
+
     /// Returns `true` if both [`Engine`] references `a` and `b` refer to the same [`Engine`].
     pub fn same(a: &Engine, b: &Engine) -> bool {
         Arc::ptr_eq(&a.inner, &b.inner)
@@ -196,6 +479,49 @@ impl Engine {
         self.inner.alloc_func()
     }
 
+    /// Defines the [`Intrinsic`] under the import name `module::name` for the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// Calls to imported functions with the same import name and function type are
+    /// lowered to direct calls to the [`Intrinsic`] in all Wasm modules that are
+    /// compiled afterwards. A [`Linker`](crate::Linker) resolves those imports to the
+    /// [`Intrinsic`] only if it has no definition with the same import name itself.
+    /// Imports that resolve to any other definition are called as usual.
+    ///
+    /// # Errors
+    ///
+    /// If an [`Intrinsic`] is already defined under the same import name.
+    pub fn define_intrinsic(
+        &self,
+        module: &str,
+        name: &str,
+        intrinsic: Intrinsic,
+    ) -> Result<(), Error> {
+        self.inner.define_intrinsic(module, name, intrinsic)
+    }
+
+    /// Returns the [`IntrinsicIdx`] of the [`Intrinsic`] defined as `module::name` if any.
+    ///
+    /// Returns `None` if the [`Intrinsic`] does not match the function type `ty`.
+    pub(crate) fn get_intrinsic(
+        &self,
+        module: &str,
+        name: &str,
+        ty: DedupFuncType,
+    ) -> Option<IntrinsicIdx> {
+        self.inner.get_intrinsic(module, name, ty)
+    }
+
+    /// Returns the [`Intrinsic`] defined as `module::name` and its [`IntrinsicIdx`] if any.
+    pub(crate) fn get_intrinsic_by_name(
+        &self,
+        module: &str,
+        name: &str,
+    ) -> Option<(IntrinsicIdx, Intrinsic)> {
+        self.inner.get_intrinsic_by_name(module, name)
+    }
+
     /// Translates the Wasm function using the [`Engine`].
     ///
     /// - Uses the internal [`Config`] to drive the function translation as mandated.
@@ -214,6 +540,8 @@ impl Engine {
     ///
     /// - If function translation fails.
     /// - If function validation fails.
+    /// - If the function uses a Wasm operator denied by the [`Config`].
+    #[cfg(feature = "compiler")]
     pub(crate) fn translate_func(
         &self,
         func_index: FuncIdx,
@@ -223,13 +551,17 @@ impl Engine {
         module: ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) -> Result<(), Error> {
+        let denied_operators = self.config().get_denied_operators();
+        if !denied_operators.is_empty() {
+            check_denied_operators(func_index, offset, bytes, denied_operators)?;
+        }
         match (self.config().get_compilation_mode(), func_to_validate) {
             (CompilationMode::Eager, Some(func_to_validate)) => {
                 let (translation_allocs, validation_allocs) = self.inner.get_allocs();
                 let validator = func_to_validate.into_validator(validation_allocs);
                 let translator = FuncTranslator::new(func_index, module, translation_allocs)?;
                 let translator = ValidatingFuncTranslator::new(validator, translator)?;
-                let allocs = FuncTranslationDriver::new(offset, bytes, translator)?
+                let allocs = FuncTranslationDriver::new(func_index, offset, bytes, translator)?
                     .translate(|func_entity| self.inner.init_func(compiled_func, func_entity))?;
                 self.inner
                     .recycle_allocs(allocs.translation, allocs.validation);
@@ -237,7 +569,7 @@ impl Engine {
             (CompilationMode::Eager, None) => {
                 let allocs = self.inner.get_translation_allocs();
                 let translator = FuncTranslator::new(func_index, module, allocs)?;
-                let allocs = FuncTranslationDriver::new(offset, bytes, translator)?
+                let allocs = FuncTranslationDriver::new(func_index, offset, bytes, translator)?
                     .translate(|func_entity| self.inner.init_func(compiled_func, func_entity))?;
                 self.inner.recycle_translation_allocs(allocs);
             }
@@ -246,36 +578,53 @@ impl Engine {
                 let translator = LazyFuncTranslator::new(func_index, compiled_func, module, None);
                 let validator = func_to_validate.into_validator(allocs);
                 let translator = ValidatingFuncTranslator::new(validator, translator)?;
-                let allocs = FuncTranslationDriver::new(offset, bytes, translator)?
+                let allocs = FuncTranslationDriver::new(func_index, offset, bytes, translator)?
                     .translate(|func_entity| self.inner.init_func(compiled_func, func_entity))?;
                 self.inner.recycle_validation_allocs(allocs.validation);
             }
             (CompilationMode::Lazy | CompilationMode::LazyTranslation, func_to_validate) => {
                 let translator =
                     LazyFuncTranslator::new(func_index, compiled_func, module, func_to_validate);
-                FuncTranslationDriver::new(offset, bytes, translator)?
+                FuncTranslationDriver::new(func_index, offset, bytes, translator)?
                     .translate(|func_entity| self.inner.init_func(compiled_func, func_entity))?;
             }
         }
+        self.inner.emit_diagnostics();
         Ok(())
     }
 
     /// Returns reusable [`FuncTranslatorAllocations`] from the [`Engine`].
+    #[cfg(feature = "compiler")]
     pub(crate) fn get_translation_allocs(&self) -> FuncTranslatorAllocations {
         self.inner.get_translation_allocs()
     }
 
+    /// Returns reusable [`FuncValidatorAllocations`] from the [`Engine`].
+    #[cfg(feature = "compiler")]
+    pub(crate) fn get_validation_allocs(&self) -> FuncValidatorAllocations {
+        self.inner.get_validation_allocs()
+    }
+
     /// Returns reusable [`FuncTranslatorAllocations`] and [`FuncValidatorAllocations`] from the [`Engine`].
+    #[cfg(feature = "compiler")]
     pub(crate) fn get_allocs(&self) -> (FuncTranslatorAllocations, FuncValidatorAllocations) {
         self.inner.get_allocs()
     }
 
     /// Recycles the given [`FuncTranslatorAllocations`] in the [`Engine`].
+    #[cfg(feature = "compiler")]
     pub(crate) fn recycle_translation_allocs(&self, allocs: FuncTranslatorAllocations) {
         self.inner.recycle_translation_allocs(allocs)
     }
 
+    /// Recycles the given [`FuncValidatorAllocations`] in the [`Engine`].
+    #[cfg(feature = "compiler")]
+    pub(crate) fn recycle_validation_allocs(&self, allocs: FuncValidatorAllocations) {
+        self.inner.recycle_validation_allocs(allocs)
+    }
+
     /// Recycles the given [`FuncTranslatorAllocations`] and [`FuncValidatorAllocations`] in the [`Engine`].
+    #[cfg(feature = "compiler")]
     pub(crate) fn recycle_allocs(
         &self,
         translation: FuncTranslatorAllocations,
@@ -284,6 +633,49 @@ impl Engine {
         self.inner.recycle_allocs(translation, validation)
     }
 
+    /// Deduplicates the function local constant values of the compiled `funcs` of a Wasm module.
+    ///
+    /// # Note
+    ///
+    /// This must only be called before any of the `funcs` has been executed.
+    pub(crate) fn pool_consts(&self, funcs: &[CompiledFunc]) {
+        self.inner.pool_consts(funcs)
+    }
+
+    /// Charges `bytes` of compiled code against the budget of the [`Engine`].
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Config::max_compiled_code_bytes`] budget would be exceeded.
+    /// - If the allocation failed on purpose due to [`Config::alloc_failures`].
+    pub(crate) fn charge_compiled_code(&self, bytes: usize) -> Result<(), Error> {
+        self.inner.alloc_compiled_code(bytes).map_err(Error::from)
+    }
+
+    /// Queues the translation `diagnostic` if a [`Config::diagnostics_sink`] is configured.
+    ///
+    /// # Note
+    ///
+    /// Translation may run while engine locks are held, e.g. for lazily compiled
+    /// functions. Therefore queued diagnostics are only emitted once those are released.
+    pub(crate) fn queue_diagnostic(&self, diagnostic: Diagnostic) {
+        if self.config().get_diagnostics_sink().is_some() {
+            self.inner.diagnostics.lock().push(diagnostic);
+        }
+    }
+
+    /// Emits all queued translation diagnostics unless `store` is still executing.
+    ///
+    /// # Note
+    ///
+    /// Nested executions started from host functions are still covered by the
+    /// engine locks of their outer execution which emits their diagnostics instead.
+    fn emit_diagnostics(&self, store: &StoreInner) {
+        if !store.is_executing() {
+            self.inner.emit_diagnostics();
+        }
+    }
+
     /// Initializes the uninitialized [`CompiledFunc`] for the [`Engine`].
     ///
     /// # Note
@@ -295,16 +687,18 @@ impl Engine {
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
     /// - If `func` refers to an already initialized [`CompiledFunc`].
+    #[cfg(feature = "compiler")]
     fn init_lazy_func(
         &self,
         func_idx: FuncIdx,
         func: CompiledFunc,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) {
         self.inner
-            .init_lazy_func(func_idx, func, bytes, module, func_to_validate)
+            .init_lazy_func(func_idx, func, offset, bytes, module, func_to_validate)
     }
 
     /// Resolves the [`CompiledFunc`] to the underlying Wasmi bytecode instructions.
@@ -334,6 +728,45 @@ impl Engine {
         self.inner.resolve_instr(func, index)
     }
 
+    /// Resolves the [`CompiledFunc`] to its [`CompiledFuncEntity`] and applies `f` to it.
+    ///
+    /// # Note
+    ///
+    /// Lazily compiled functions are compiled by this call if necessary.
+    ///
+    /// # Errors
+    ///
+    /// If the `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    ///
+    /// # Panics
+    ///
+    /// If the [`CompiledFunc`] is invalid for the [`Engine`].
+    pub(crate) fn resolve_func<F, R>(&self, func: CompiledFunc, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&CompiledFuncEntity) -> R,
+    {
+        self.inner.resolve_func(func, f)
+    }
+
+    /// Initializes the uninitialized [`CompiledFunc`] with the already compiled `entity`.
+    ///
+    /// # Panics
+    ///
+    /// - If `func` is an invalid [`CompiledFunc`] reference for this [`Engine`].
+    /// - If `func` refers to an already initialized [`CompiledFunc`].
+    ///
+    /// # Errors
+    ///
+    /// - If `entity` exceeds the compiled code budget of the [`Engine`].
+    /// - If the allocation of the compiled code of `entity` failed.
+    pub(crate) fn init_compiled_func(
+        &self,
+        func: CompiledFunc,
+        entity: CompiledFuncEntity,
+    ) -> Result<(), Error> {
+        self.inner.init_func(func, entity)
+    }
+
     /// Resolves the function local constant of [`CompiledFunc`] at `index` if any.
     ///
     /// # Note
@@ -381,7 +814,7 @@ impl Engine {
     #[inline]
     pub(crate) fn execute_func<T, Results>(
         &self,
-        ctx: StoreContextMut<T>,
+        mut ctx: StoreContextMut<T>,
         func: &Func,
         params: impl CallParams,
         results: Results,
@@ -389,7 +822,49 @@ impl Engine {
     where
         Results: CallResults,
     {
-        self.inner.execute_func(ctx, func, params, results)
+        let outcome = self
+            .inner
+            .execute_func(ctx.as_context_mut(), func, params, results);
+        self.emit_diagnostics(&ctx.store.inner);
+        outcome
+    }
+
+    /// Executes the given [`Func`] once for each pair of parameters and results of `calls`.
+    ///
+    /// Hands the execution result of each call to `f` upon a successful execution.
+    ///
+    /// # Note
+    ///
+    /// - Assumes that all `params` and `results` of `calls` are well typed.
+    ///   Type checks are done at the [`Func::call_batch`] API or when creating
+    ///   a new [`TypedFunc`] instance via [`Func::typed`].
+    /// - The execution resources are set up once for all `calls`.
+    ///
+    /// # Errors
+    ///
+    /// - If `params` are overflowing or underflowing the expected amount of parameters.
+    /// - If the given `results` do not match the the length of the expected results of `func`.
+    /// - When encountering a Wasm or host trap during the execution of `func`.
+    ///   In this case the remaining `calls` are not executed.
+    ///
+    /// [`TypedFunc`]: [`crate::TypedFunc`]
+    #[inline]
+    pub(crate) fn execute_func_batch<T, Params, Results>(
+        &self,
+        mut ctx: StoreContextMut<T>,
+        func: &Func,
+        calls: impl IntoIterator<Item = (Params, Results)>,
+        f: impl FnMut(<Results as CallResults>::Results),
+    ) -> Result<(), Error>
+    where
+        Params: CallParams,
+        Results: CallResults,
+    {
+        let outcome = self
+            .inner
+            .execute_func_batch(ctx.as_context_mut(), func, calls, f);
+        self.emit_diagnostics(&ctx.store.inner);
+        outcome
     }
 
     /// Executes the given [`Func`] resumably with parameters `params` and returns.
@@ -417,7 +892,7 @@ impl Engine {
     #[inline]
     pub(crate) fn execute_func_resumable<T, Results>(
         &self,
-        ctx: StoreContextMut<T>,
+        mut ctx: StoreContextMut<T>,
         func: &Func,
         params: impl CallParams,
         results: Results,
@@ -425,8 +900,11 @@ impl Engine {
     where
         Results: CallResults,
     {
-        self.inner
-            .execute_func_resumable(ctx, func, params, results)
+        let outcome =
+            self.inner
+                .execute_func_resumable(ctx.as_context_mut(), func, params, results);
+        self.emit_diagnostics(&ctx.store.inner);
+        outcome
     }
 
     /// Resumes the given `invocation` given the `params`.
@@ -454,7 +932,7 @@ impl Engine {
     #[inline]
     pub(crate) fn resume_func<T, Results>(
         &self,
-        ctx: StoreContextMut<T>,
+        mut ctx: StoreContextMut<T>,
         invocation: ResumableInvocation,
         params: impl CallParams,
         results: Results,
@@ -462,13 +940,67 @@ impl Engine {
     where
         Results: CallResults,
     {
-        self.inner.resume_func(ctx, invocation, params, results)
+        let outcome = self
+            .inner
+            .resume_func(ctx.as_context_mut(), invocation, params, results);
+        self.emit_diagnostics(&ctx.store.inner);
+        outcome
+    }
+
+    /// Resumes the `yielded` call for another time slice.
+    ///
+    /// Stores the execution result into `results` upon a successful execution.
+    ///
+    /// # Note
+    ///
+    /// Assumes that the `results` are well typed.
+    ///
+    /// # Errors
+    ///
+    /// If the execution traps.
+    pub(crate) fn resume_yielded<T, Results>(
+        &self,
+        mut ctx: StoreContextMut<T>,
+        yielded: YieldedInvocation,
+        results: Results,
+    ) -> Result<ResumableCallBase<<Results as CallResults>::Results>, Error>
+    where
+        Results: CallResults,
+    {
+        let outcome = self
+            .inner
+            .resume_yielded(ctx.as_context_mut(), yielded, results);
+        self.emit_diagnostics(&ctx.store.inner);
+        outcome
+    }
+
+    /// Returns the size of the translated Wasmi bytecode of `func` in bytes.
+    ///
+    /// Returns `None` if `func` has not yet been compiled, e.g. due to lazy compilation.
+    pub(crate) fn code_size(&self, func: CompiledFunc) -> Option<usize> {
+        self.inner.code_size(func)
+    }
+
+    /// Returns the number of value stack cells of a call frame of `func` if it has been compiled.
+    pub(crate) fn len_registers(&self, func: CompiledFunc) -> Option<u16> {
+        self.inner.len_registers(func)
+    }
+
+    /// Returns the [`AddressMap`] of `func` if it has been compiled with address maps enabled.
+    pub(crate) fn address_map_of(&self, func: CompiledFunc) -> Option<AddressMap> {
+        self.inner.address_map_of(func)
     }
 
     /// Recycles the given [`Stack`] for reuse in the [`Engine`].
     pub(crate) fn recycle_stack(&self, stack: Stack) {
         self.inner.recycle_stack(stack)
     }
+
+    /// Returns the number of [`Stack`]s cached for reuse by the [`Engine`].
+    #[cfg(test)]
+    pub(crate) fn len_cached_stacks(&self) -> usize {
+        self.inner.len_cached_stacks()
+    }
 }
 
 /// The internal state of the Wasmi [`Engine`].
@@ -479,6 +1011,7 @@ pub struct EngineInner {
     /// Engine resources shared across multiple engine executors.
     res: RwLock<EngineResources>,
     /// Reusable allocation stacks.
+    #[cfg(feature = "compiler")]
     allocs: Mutex<ReusableAllocationStack>,
     /// Reusable engine stacks for Wasm execution.
     ///
@@ -486,9 +1019,29 @@ pub struct EngineInner {
     /// operate on. Therefore a Wasm engine is required to provide stacks and
     /// ideally recycles old ones since creation of a new stack is rather expensive.
     stacks: Mutex<EngineStacks>,
+    /// The current epoch of the engine used for epoch based interruption.
+    ///
+    /// # Note
+    ///
+    /// This is shared with all [`Store`]s of the engine.
+    ///
+    /// [`Store`]: crate::Store
+    epoch: Arc<AtomicU32>,
+    /// The counters of the [`EngineStats`] of the engine.
+    stats: EngineCounters,
+    /// The budget for the compiled code of all Wasm modules of the engine.
+    code_budget: CodeBudget,
+    /// The registered [`SymbolProvider`] of the engine if any.
+    symbols: RwLock<Option<SharedSymbolProvider>>,
+    /// The translation diagnostics not yet emitted to the [`Config::diagnostics_sink`].
+    diagnostics: Mutex<Vec<Diagnostic>>,
+    /// Decides which internal allocations of the engine fail on purpose if any.
+    #[cfg(feature = "fault-injection")]
+    alloc_failures: Option<Arc<AllocFailureInjector>>,
 }
 
 /// Stacks to hold and distribute reusable allocations.
+#[cfg(feature = "compiler")]
 pub struct ReusableAllocationStack {
     /// The maximum height of each of the allocations stacks.
     max_height: usize,
@@ -498,6 +1051,7 @@ pub struct ReusableAllocationStack {
     validation: Vec<FuncValidatorAllocations>,
 }
 
+#[cfg(feature = "compiler")]
 impl Default for ReusableAllocationStack {
     fn default() -> Self {
         Self {
@@ -508,6 +1062,7 @@ impl Default for ReusableAllocationStack {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl core::fmt::Debug for ReusableAllocationStack {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         f.debug_struct("ReusableAllocationStack")
@@ -520,13 +1075,13 @@ impl core::fmt::Debug for ReusableAllocationStack {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl ReusableAllocationStack {
     /// Returns reusable [`FuncTranslatorAllocations`] from the [`Engine`].
-    pub fn get_translation_allocs(&mut self) -> FuncTranslatorAllocations {
-        match self.translation.pop() {
-            Some(allocs) => allocs,
-            None => FuncTranslatorAllocations::default(),
-        }
+    ///
+    /// Returns `None` if there are no cached [`FuncTranslatorAllocations`].
+    pub fn get_translation_allocs(&mut self) -> Option<FuncTranslatorAllocations> {
+        self.translation.pop()
     }
 
     /// Returns reusable [`FuncValidatorAllocations`] from the [`Engine`].
@@ -546,6 +1101,12 @@ impl ReusableAllocationStack {
         self.translation.push(recycled);
     }
 
+    /// Drops all reusable allocations and releases their memory.
+    pub fn clear(&mut self) {
+        self.translation = Vec::new();
+        self.validation = Vec::new();
+    }
+
     /// Recycles the given [`FuncValidatorAllocations`] in the [`Engine`].
     pub fn recycle_validation_allocs(&mut self, recycled: FuncValidatorAllocations) {
         debug_assert!(self.validation.len() <= self.max_height);
@@ -580,14 +1141,29 @@ impl EngineStacks {
     }
 
     /// Reuse or create a new [`Stack`] if none was available.
-    pub fn reuse_or_new(&mut self) -> Stack {
-        match self.stacks.pop() {
+    ///
+    /// Records whether a [`Stack`] was reused in the `stats`.
+    pub fn reuse_or_new(&mut self, stats: &EngineCounters) -> Stack {
+        let stack = self.stacks.pop();
+        stats.stack_reused(stack.is_some());
+        match stack {
             Some(stack) => stack,
             None => Stack::new(self.limits),
         }
     }
 
-    /// Disose and recycle the `stack`.
+    /// Drops all stacks kept for reuse and releases their memory.
+    pub fn clear(&mut self) {
+        self.stacks = Vec::new();
+    }
+
+    /// Returns the number of stacks kept for reuse.
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.stacks.len()
+    }
+
+    /// Dispose and recycle the `stack`.
     pub fn recycle(&mut self, stack: Stack) {
         if !stack.is_empty() && self.stacks.len() < self.keep {
             self.stacks.push(stack);
@@ -599,10 +1175,20 @@ impl EngineInner {
     /// Creates a new [`EngineInner`] with the given [`Config`].
     fn new(config: &Config) -> Self {
         Self {
-            config: *config,
+            config: config.clone(),
             res: RwLock::new(EngineResources::new()),
+            #[cfg(feature = "compiler")]
             allocs: Mutex::new(ReusableAllocationStack::default()),
             stacks: Mutex::new(EngineStacks::new(config)),
+            epoch: Arc::new(AtomicU32::new(0)),
+            stats: EngineCounters::default(),
+            code_budget: CodeBudget::new(config.get_max_compiled_code_bytes()),
+            symbols: RwLock::new(None),
+            diagnostics: Mutex::new(Vec::new()),
+            #[cfg(feature = "fault-injection")]
+            alloc_failures: config
+                .get_alloc_failures()
+                .map(|failures| Arc::new(AllocFailureInjector::new(failures))),
         }
     }
 
@@ -611,6 +1197,29 @@ impl EngineInner {
         &self.config
     }
 
+    /// Releases all cached stacks and reusable allocations of the [`EngineInner`].
+    fn clear_caches(&self) {
+        self.stacks.lock().clear();
+        #[cfg(feature = "compiler")]
+        self.allocs.lock().clear();
+    }
+
+    /// Releases all caches and shrinks the internal buffers of the [`EngineInner`].
+    fn shrink_to_fit(&self) {
+        self.clear_caches();
+        self.res.write().code_map.shrink_to_fit();
+    }
+
+    /// Sets or removes the [`SymbolProvider`] of the [`EngineInner`].
+    fn set_symbol_provider(&self, provider: Option<SharedSymbolProvider>) {
+        *self.symbols.write() = provider;
+    }
+
+    /// Returns the [`SymbolProvider`] of the [`EngineInner`] if any.
+    fn symbol_provider(&self) -> Option<SharedSymbolProvider> {
+        self.symbols.read().clone()
+    }
+
     /// Allocates a new function type to the [`EngineInner`].
     fn alloc_func_type(&self, func_type: FuncType) -> DedupFuncType {
         self.res.write().func_types.alloc_func_type(func_type)
@@ -636,12 +1245,52 @@ impl EngineInner {
         self.res.write().code_map.alloc_func()
     }
 
+    /// Defines the [`Intrinsic`] under the import name `module::name` for the [`EngineInner`].
+    fn define_intrinsic(
+        &self,
+        module: &str,
+        name: &str,
+        intrinsic: Intrinsic,
+    ) -> Result<(), Error> {
+        let mut res = self.res.write();
+        let ty = res.func_types.alloc_func_type(intrinsic.ty().clone());
+        res.intrinsics.define(module, name, ty, intrinsic)?;
+        Ok(())
+    }
+
+    /// Returns the [`IntrinsicIdx`] of the [`Intrinsic`] defined as `module::name` if any.
+    fn get_intrinsic(&self, module: &str, name: &str, ty: DedupFuncType) -> Option<IntrinsicIdx> {
+        self.res.read().intrinsics.get(module, name, ty)
+    }
+
+    /// Returns the [`Intrinsic`] defined as `module::name` and its [`IntrinsicIdx`] if any.
+    fn get_intrinsic_by_name(&self, module: &str, name: &str) -> Option<(IntrinsicIdx, Intrinsic)> {
+        let res = self.res.read();
+        let index = res.intrinsics.get_by_name(module, name)?;
+        Some((index, res.intrinsics.resolve(index).intrinsic().clone()))
+    }
+
     /// Returns reusable [`FuncTranslatorAllocations`] from the [`Engine`].
+    #[cfg(feature = "compiler")]
     fn get_translation_allocs(&self) -> FuncTranslatorAllocations {
-        self.allocs.lock().get_translation_allocs()
+        let allocs = self.allocs.lock().get_translation_allocs();
+        self.translation_allocs_or_default(allocs)
+    }
+
+    /// Returns the cached translation `allocs` or new ones if there are none.
+    ///
+    /// Records whether cached allocations have been reused in the [`EngineStats`].
+    #[cfg(feature = "compiler")]
+    fn translation_allocs_or_default(
+        &self,
+        allocs: Option<FuncTranslatorAllocations>,
+    ) -> FuncTranslatorAllocations {
+        self.stats.translation_allocs_reused(allocs.is_some());
+        allocs.unwrap_or_default()
     }
 
     /// Returns reusable [`FuncValidatorAllocations`] from the [`Engine`].
+    #[cfg(feature = "compiler")]
     fn get_validation_allocs(&self) -> FuncValidatorAllocations {
         self.allocs.lock().get_validation_allocs()
     }
@@ -653,19 +1302,23 @@ impl EngineInner {
     /// This method is a bit more efficient than calling both
     /// - [`EngineInner::get_translation_allocs`]
     /// - [`EngineInner::get_validation_allocs`]
+    #[cfg(feature = "compiler")]
     fn get_allocs(&self) -> (FuncTranslatorAllocations, FuncValidatorAllocations) {
         let mut allocs = self.allocs.lock();
         let translation = allocs.get_translation_allocs();
         let validation = allocs.get_validation_allocs();
-        (translation, validation)
+        drop(allocs);
+        (self.translation_allocs_or_default(translation), validation)
     }
 
     /// Recycles the given [`FuncTranslatorAllocations`] in the [`Engine`].
+    #[cfg(feature = "compiler")]
     fn recycle_translation_allocs(&self, allocs: FuncTranslatorAllocations) {
         self.allocs.lock().recycle_translation_allocs(allocs)
     }
 
     /// Recycles the given [`FuncValidatorAllocations`] in the [`Engine`].
+    #[cfg(feature = "compiler")]
     fn recycle_validation_allocs(&self, allocs: FuncValidatorAllocations) {
         self.allocs.lock().recycle_validation_allocs(allocs)
     }
@@ -677,6 +1330,7 @@ impl EngineInner {
     /// This method is a bit more efficient than calling both
     /// - [`EngineInner::recycle_translation_allocs`]
     /// - [`EngineInner::recycle_validation_allocs`]
+    #[cfg(feature = "compiler")]
     fn recycle_allocs(
         &self,
         translation: FuncTranslatorAllocations,
@@ -687,6 +1341,51 @@ impl EngineInner {
         allocs.recycle_validation_allocs(validation);
     }
 
+    /// Emits all queued translation diagnostics to the [`Config::diagnostics_sink`] if any.
+    ///
+    /// # Note
+    ///
+    /// The sink is called after the queue has been unlocked so that it may use the [`Engine`].
+    fn emit_diagnostics(&self) {
+        let Some(sink) = self.config.get_diagnostics_sink() else {
+            return;
+        };
+        let diagnostics = mem::take(&mut *self.diagnostics.lock());
+        for diagnostic in diagnostics {
+            sink.emit(diagnostic);
+        }
+    }
+
+    /// Deduplicates the function local constant values of the compiled `funcs` of a Wasm module.
+    ///
+    /// # Note
+    ///
+    /// The shared pool is built while only holding a read lock so that
+    /// concurrent executions are blocked only for installing it.
+    fn pool_consts(&self, funcs: &[CompiledFunc]) {
+        let pooled = self.res.read().code_map.pool_consts(funcs);
+        if pooled.is_empty() {
+            return;
+        }
+        self.res.write().code_map.set_pooled_consts(pooled)
+    }
+
+    /// Allocates `bytes` of compiled code of the [`EngineInner`].
+    ///
+    /// # Errors
+    ///
+    /// - If the compiled code budget of the [`EngineInner`] would be exceeded.
+    /// - If the allocation failed on purpose due to [`Config::alloc_failures`].
+    fn alloc_compiled_code(&self, bytes: usize) -> Result<(), TranslationError> {
+        #[cfg(feature = "fault-injection")]
+        if let Some(alloc_failures) = &self.alloc_failures {
+            if alloc_failures.fail_next() {
+                return Err(TranslationError::AllocationFailed);
+            }
+        }
+        self.code_budget.charge(bytes)
+    }
+
     /// Initializes the uninitialized [`CompiledFunc`] for the [`EngineInner`].
     ///
     /// # Note
@@ -697,11 +1396,23 @@ impl EngineInner {
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
     /// - If `func` refers to an already initialized [`CompiledFunc`].
-    fn init_func(&self, compiled_func: CompiledFunc, func_entity: CompiledFuncEntity) {
+    ///
+    /// # Errors
+    ///
+    /// - If `func_entity` exceeds the compiled code budget of the [`EngineInner`].
+    /// - If the allocation of the compiled code of `func_entity` failed.
+    fn init_func(
+        &self,
+        compiled_func: CompiledFunc,
+        func_entity: CompiledFuncEntity,
+    ) -> Result<(), Error> {
+        self.alloc_compiled_code(func_entity.code_size())?;
+        self.stats.func_translated();
         self.res
             .write()
             .code_map
-            .init_func(compiled_func, func_entity)
+            .init_func(compiled_func, func_entity);
+        Ok(())
     }
 
     /// Initializes the uninitialized [`CompiledFunc`] for the [`Engine`].
@@ -715,18 +1426,24 @@ impl EngineInner {
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
     /// - If `func` refers to an already initialized [`CompiledFunc`].
+    #[cfg(feature = "compiler")]
     fn init_lazy_func(
         &self,
         func_idx: FuncIdx,
         func: CompiledFunc,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) {
-        self.res
-            .write()
-            .code_map
-            .init_lazy_func(func, func_idx, bytes, module, func_to_validate)
+        self.res.write().code_map.init_lazy_func(
+            func,
+            func_idx,
+            offset,
+            bytes,
+            module,
+            func_to_validate,
+        )
     }
 
     /// Resolves the [`InternalFuncEntity`] for [`CompiledFunc`] and applies `f` to it.
@@ -734,12 +1451,11 @@ impl EngineInner {
     /// # Panics
     ///
     /// If [`CompiledFunc`] is invalid for [`Engine`].
-    #[cfg(test)]
     pub(super) fn resolve_func<F, R>(&self, func: CompiledFunc, f: F) -> Result<R, Error>
     where
         F: FnOnce(&CompiledFuncEntity) -> R,
     {
-        // Note: We use `None` so this test-only function will never charge for compilation fuel.
+        // Note: We use `None` so that resolving a function never charges compilation fuel.
         Ok(f(self.res.read().code_map.get(None, func)?))
     }
 
@@ -783,7 +1499,50 @@ impl EngineInner {
         // Function local constants are stored in reverse order of their indices since
         // they are allocated in reverse order to their absolute indices during function
         // translation. That is why we need to access them in reverse order.
-        self.resolve_func(func, |func| func.consts().iter().rev().nth(index).copied())
+        self.resolve_func(func, |func| func.consts().iter().rev().nth(index))
+    }
+
+    /// Returns the number of [`Stack`]s cached for reuse.
+    #[cfg(test)]
+    fn len_cached_stacks(&self) -> usize {
+        self.stacks.lock().len()
+    }
+
+    /// Returns the size of the translated Wasmi bytecode of `func` in bytes if compiled.
+    fn code_size(&self, func: CompiledFunc) -> Option<usize> {
+        self.res
+            .read()
+            .code_map
+            .get_compiled(func)
+            .map(CompiledFuncEntity::code_size)
+    }
+
+    /// Returns the number of value stack cells of a call frame of `func` if it has been compiled.
+    fn len_registers(&self, func: CompiledFunc) -> Option<u16> {
+        self.res
+            .read()
+            .code_map
+            .get_compiled(func)
+            .map(CompiledFuncEntity::len_registers)
+    }
+
+    /// Returns the [`AddressMap`] of `func` if compiled with address maps enabled.
+    fn address_map_of(&self, func: CompiledFunc) -> Option<AddressMap> {
+        self.res
+            .read()
+            .code_map
+            .get_compiled(func)
+            .and_then(CompiledFuncEntity::address_map)
+            .cloned()
+    }
+
+    /// Returns the translated Wasmi bytecode of `func` as JSON if it has been translated.
+    fn bytecode_json_of(&self, func: CompiledFunc) -> Option<String> {
+        self.res
+            .read()
+            .code_map
+            .get_compiled(func)
+            .map(bytecode_json::func_to_json)
     }
 
     /// Recycles the given [`Stack`].
@@ -806,6 +1565,8 @@ pub struct EngineResources {
     /// The engine deduplicates function types to make the equality
     /// comparison very fast. This helps to speed up indirect calls.
     func_types: FuncTypeRegistry,
+    /// The host intrinsics defined for the engine.
+    intrinsics: Intrinsics,
 }
 
 impl EngineResources {
@@ -815,6 +1576,7 @@ impl EngineResources {
         Self {
             code_map: CodeMap::default(),
             func_types: FuncTypeRegistry::new(engine_idx),
+            intrinsics: Intrinsics::default(),
         }
     }
 }
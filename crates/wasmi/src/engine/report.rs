@@ -0,0 +1,97 @@
+use crate::StoreInner;
+
+/// The resource usage of the most recent Wasm or host function call of a [`Store`].
+///
+/// Query it via [`Store::last_call_report`] after a call returned.
+///
+/// # Note
+///
+/// For resumable calls the [`CallReport`] covers the execution up to the
+/// most recent suspension or completion of the call.
+///
+/// [`Store`]: crate::Store
+/// [`Store::last_call_report`]: crate::Store::last_call_report
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CallReport {
+    /// The amount of fuel consumed by the call if fuel metering is enabled.
+    fuel_consumed: Option<u64>,
+    /// The amount of executed instructions if instruction budget metering is enabled.
+    instructions_executed: Option<u64>,
+    /// The maximum number of nested Wasm calls on the call stack during the call.
+    peak_stack_depth: usize,
+    /// The total amount of linear memory pages grown by `memory.grow` during the call.
+    pages_grown: u64,
+}
+
+impl CallReport {
+    /// Returns the amount of fuel consumed by the call.
+    ///
+    /// Returns `None` if fuel metering is disabled.
+    pub fn fuel_consumed(&self) -> Option<u64> {
+        self.fuel_consumed
+    }
+
+    /// Returns the amount of instructions executed by the call.
+    ///
+    /// Returns `None` if instruction budget metering via [`Config::consume_instructions`] is disabled.
+    ///
+    /// [`Config::consume_instructions`]: crate::Config::consume_instructions
+    pub fn instructions_executed(&self) -> Option<u64> {
+        self.instructions_executed
+    }
+
+    /// Returns the maximum number of nested Wasm calls on the call stack during the call.
+    ///
+    /// # Note
+    ///
+    /// This is zero if only a host function has been called.
+    pub fn peak_stack_depth(&self) -> usize {
+        self.peak_stack_depth
+    }
+
+    /// Returns the total amount of linear memory pages grown by `memory.grow` during the call.
+    pub fn pages_grown(&self) -> u64 {
+        self.pages_grown
+    }
+}
+
+/// Records the resource counters of a [`StoreInner`] before a call to compute its [`CallReport`].
+#[derive(Debug, Copy, Clone)]
+pub struct CallReportProbe {
+    /// The amount of fuel consumed so far if fuel metering is enabled.
+    fuel_consumed: Option<u64>,
+    /// The remaining instruction budget if instruction budget metering is enabled.
+    instruction_budget: Option<u64>,
+    /// The amount of linear memory pages grown so far.
+    pages_grown: u64,
+}
+
+impl CallReportProbe {
+    /// Creates a new [`CallReportProbe`] recording the current counters of `store`.
+    pub fn new(store: &StoreInner) -> Self {
+        Self {
+            fuel_consumed: store.fuel().fuel_consumed(),
+            instruction_budget: store.instruction_budget(),
+            pages_grown: store.pages_grown(),
+        }
+    }
+
+    /// Stores the [`CallReport`] of the finished call into the `store`.
+    pub fn finish(self, store: &mut StoreInner, peak_stack_depth: usize) {
+        let fuel_consumed = self
+            .fuel_consumed
+            .zip(store.fuel().fuel_consumed())
+            .map(|(before, after)| after.wrapping_sub(before));
+        let instructions_executed = self
+            .instruction_budget
+            .zip(store.instruction_budget())
+            .map(|(before, after)| before.saturating_sub(after));
+        let pages_grown = store.pages_grown().wrapping_sub(self.pages_grown);
+        store.set_last_call_report(CallReport {
+            fuel_consumed,
+            instructions_executed,
+            peak_stack_depth,
+            pages_grown,
+        });
+    }
+}
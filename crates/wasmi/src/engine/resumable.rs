@@ -1,5 +1,6 @@
 use super::{bytecode::RegisterSpan, Func};
 use crate::{
+    core::TrapCode,
     engine::Stack,
     func::CallResultsTuple,
     AsContextMut,
@@ -8,6 +9,7 @@ use crate::{
     Value,
     WasmResults,
 };
+use alloc::boxed::Box;
 use core::{fmt, marker::PhantomData, mem::replace, ops::Deref};
 
 /// Returned by [`Engine`] methods for calling a function in a resumable way.
@@ -25,6 +27,10 @@ pub(crate) enum ResumableCallBase<T> {
     Finished(T),
     /// The resumable call encountered a host error and can be resumed.
     Resumable(ResumableInvocation),
+    /// The resumable call exhausted its time slice and can be resumed.
+    Yielded(YieldedInvocation),
+    /// The resumable call ran out of fuel and can be resumed after refueling.
+    OutOfFuel(YieldedInvocation),
 }
 
 /// Returned by calling a [`Func`] in a resumable way.
@@ -34,6 +40,27 @@ pub enum ResumableCall {
     Finished,
     /// The resumable call encountered a host error and can be resumed.
     Resumable(ResumableInvocation),
+    /// The resumable call exhausted its time slice or yielded at its epoch deadline and can be resumed.
+    ///
+    /// # Note
+    ///
+    /// This only happens if time slicing is enabled via [`Config::time_slice`] or
+    /// if the epoch deadline callback of the [`Store`] returns [`UpdateDeadline::Yield`].
+    ///
+    /// [`Config::time_slice`]: crate::Config::time_slice
+    /// [`Store`]: crate::Store
+    /// [`UpdateDeadline::Yield`]: crate::UpdateDeadline::Yield
+    Yielded(YieldedInvocation),
+    /// The resumable call ran out of fuel and can be resumed after refueling the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// This only happens when running out of fuel at the start of a basic block.
+    /// Running out of fuel in other places, e.g. upon `memory.grow`, is still
+    /// reported as an error.
+    ///
+    /// [`Store`]: crate::Store
+    OutOfFuel(YieldedInvocation),
 }
 
 impl ResumableCall {
@@ -42,6 +69,8 @@ impl ResumableCall {
         match call {
             ResumableCallBase::Finished(()) => Self::Finished,
             ResumableCallBase::Resumable(invocation) => Self::Resumable(invocation),
+            ResumableCallBase::Yielded(invocation) => Self::Yielded(invocation),
+            ResumableCallBase::OutOfFuel(invocation) => Self::OutOfFuel(invocation),
         }
     }
 }
@@ -83,6 +112,8 @@ pub struct ResumableInvocation {
     /// actual host error. This is therefore guaranteed to never
     /// be a Wasm trap.
     host_error: Error,
+    /// The parameters with which the `host_func` was called.
+    host_params: Box<[Value]>,
     /// The registers where to put provided host function results upon resumption.
     ///
     /// # Note
@@ -123,6 +154,7 @@ impl ResumableInvocation {
         func: Func,
         host_func: Func,
         host_error: Error,
+        host_params: Box<[Value]>,
         caller_results: RegisterSpan,
         stack: Stack,
     ) -> Self {
@@ -131,6 +163,7 @@ impl ResumableInvocation {
             func,
             host_func,
             host_error,
+            host_params,
             caller_results,
             stack,
         }
@@ -141,7 +174,43 @@ impl ResumableInvocation {
         replace(&mut self.stack, Stack::empty())
     }
 
-    /// Updates the [`ResumableInvocation`] with the new `host_func`, `host_error` and `caller_results`.
+    /// Returns a copy of the [`ResumableInvocation`] with the new `host_error`.
+    ///
+    /// # Note
+    ///
+    /// Both [`ResumableInvocation`]s can be resumed independently of each other.
+    pub(crate) fn fork(&self, host_error: Error) -> Self {
+        Self {
+            engine: self.engine.clone(),
+            func: self.func,
+            host_func: self.host_func,
+            host_error,
+            host_params: self.host_params.clone(),
+            caller_results: self.caller_results,
+            stack: self.stack.clone(),
+        }
+    }
+
+    /// Consumes the [`ResumableInvocation`] and returns the encountered host error.
+    pub(crate) fn into_host_error(mut self) -> Error {
+        // Note: The placeholder does not heap allocate and is dropped together with `self`.
+        replace(
+            &mut self.host_error,
+            Error::from(TrapCode::UnreachableCodeReached),
+        )
+    }
+
+    /// Converts the [`ResumableInvocation`] into a [`YieldedInvocation`] of the same call.
+    ///
+    /// # Note
+    ///
+    /// This is used when the resumed call exhausted its time slice or ran out of fuel.
+    pub(super) fn into_yielded(mut self) -> YieldedInvocation {
+        let stack = self.take_stack();
+        YieldedInvocation::new(self.engine.clone(), self.func, stack)
+    }
+
+    /// Updates the [`ResumableInvocation`] with the new `host_func`, `host_error`, `host_params` and `caller_results`.
     ///
     /// # Note
     ///
@@ -150,10 +219,12 @@ impl ResumableInvocation {
         &mut self,
         host_func: Func,
         host_error: Error,
+        host_params: Box<[Value]>,
         caller_results: RegisterSpan,
     ) {
         self.host_func = host_func;
         self.host_error = host_error;
+        self.host_params = host_params;
         self.caller_results = caller_results;
     }
 }
@@ -187,6 +258,21 @@ impl ResumableInvocation {
         &self.host_error
     }
 
+    /// Returns the parameters with which the host [`Func`] that returned the host error was called.
+    pub fn host_params(&self) -> &[Value] {
+        &self.host_params
+    }
+
+    /// Returns the number of Wasm call frames on the stack of the suspended invocation.
+    ///
+    /// # Note
+    ///
+    /// This is the depth of the Wasm function that called the host [`Func`]
+    /// and does not include call frames of outer executions.
+    pub fn call_depth(&self) -> usize {
+        self.stack.calls.len()
+    }
+
     /// Returns the caller results [`RegisterSpan`].
     ///
     /// # Note
@@ -235,6 +321,114 @@ impl ResumableInvocation {
     }
 }
 
+/// State required to resume a [`Func`] invocation that exhausted its time slice or ran out of fuel.
+#[derive(Debug)]
+pub struct YieldedInvocation {
+    /// The engine in use for the function invocation.
+    ///
+    /// # Note
+    ///
+    /// This is required to resolve the function type of `func`
+    /// as well as in the `Drop` impl to recycle the stack.
+    engine: Engine,
+    /// The underlying root function to be executed.
+    ///
+    /// # Note
+    ///
+    /// The results of this function must always match with the
+    /// results given when resuming the call.
+    func: Func,
+    /// The value and call stack in use by the [`YieldedInvocation`].
+    ///
+    /// # Note
+    ///
+    /// This stack is borrowed from the engine and needs to be given
+    /// back to the engine when the [`YieldedInvocation`] goes out of scope.
+    pub(super) stack: Stack,
+}
+
+// # Safety
+//
+// Same as with [`ResumableInvocation`] the [`Stack`] is only `!Sync` because of the
+// `InstructionPtr`s pointing to immutable `Instruction` buffers owned by the [`Engine`].
+unsafe impl Sync for YieldedInvocation {}
+
+impl YieldedInvocation {
+    /// Creates a new [`YieldedInvocation`].
+    pub(super) fn new(engine: Engine, func: Func, stack: Stack) -> Self {
+        Self {
+            engine,
+            func,
+            stack,
+        }
+    }
+
+    /// Replaces the internal stack with an empty one that has no heap allocations.
+    pub(super) fn take_stack(&mut self) -> Stack {
+        replace(&mut self.stack, Stack::empty())
+    }
+
+    /// Converts the [`YieldedInvocation`] into a [`ResumableInvocation`] of the same call.
+    ///
+    /// # Note
+    ///
+    /// This is used when the resumed call encountered a host error.
+    pub(super) fn into_resumable(
+        mut self,
+        host_func: Func,
+        host_error: Error,
+        host_params: Box<[Value]>,
+        caller_results: RegisterSpan,
+    ) -> ResumableInvocation {
+        let stack = self.take_stack();
+        ResumableInvocation::new(
+            self.engine.clone(),
+            self.func,
+            host_func,
+            host_error,
+            host_params,
+            caller_results,
+            stack,
+        )
+    }
+
+    /// Resumes the call to the [`Func`] for another time slice or after refueling.
+    ///
+    /// The result is written back into the `outputs` buffer upon success.
+    ///
+    /// Returns a resumable handle to the function invocation upon encountering
+    /// host errors, exhausting the time slice or running out of fuel again.
+    ///
+    /// # Errors
+    ///
+    /// - If the function resumption returned a Wasm [`Error`].
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the called function.
+    pub fn resume<T>(
+        self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        outputs: &mut [Value],
+    ) -> Result<ResumableCall, Error> {
+        self.engine
+            .resolve_func_type(self.func.ty_dedup(ctx.as_context()), |func_type| {
+                func_type.match_results(outputs, false)?;
+                func_type.prepare_outputs(outputs);
+                <Result<(), Error>>::Ok(())
+            })?;
+        self.engine
+            .clone()
+            .resume_yielded(ctx.as_context_mut(), self, outputs)
+            .map(ResumableCall::new)
+    }
+}
+
+impl Drop for YieldedInvocation {
+    fn drop(&mut self) {
+        let stack = self.take_stack();
+        self.engine.recycle_stack(stack);
+    }
+}
+
 /// Returned by calling a [`TypedFunc`] in a resumable way.
 ///
 /// [`TypedFunc`]: [`crate::TypedFunc`]
@@ -244,6 +438,27 @@ pub enum TypedResumableCall<T> {
     Finished(T),
     /// The resumable call encountered a host error and can be resumed.
     Resumable(TypedResumableInvocation<T>),
+    /// The resumable call exhausted its time slice or yielded at its epoch deadline and can be resumed.
+    ///
+    /// # Note
+    ///
+    /// This only happens if time slicing is enabled via [`Config::time_slice`] or
+    /// if the epoch deadline callback of the [`Store`] returns [`UpdateDeadline::Yield`].
+    ///
+    /// [`Config::time_slice`]: crate::Config::time_slice
+    /// [`Store`]: crate::Store
+    /// [`UpdateDeadline::Yield`]: crate::UpdateDeadline::Yield
+    Yielded(TypedYieldedInvocation<T>),
+    /// The resumable call ran out of fuel and can be resumed after refueling the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// This only happens when running out of fuel at the start of a basic block.
+    /// Running out of fuel in other places, e.g. upon `memory.grow`, is still
+    /// reported as an error.
+    ///
+    /// [`Store`]: crate::Store
+    OutOfFuel(TypedYieldedInvocation<T>),
 }
 
 impl<Results> TypedResumableCall<Results> {
@@ -254,6 +469,12 @@ impl<Results> TypedResumableCall<Results> {
             ResumableCallBase::Resumable(invocation) => {
                 Self::Resumable(TypedResumableInvocation::new(invocation))
             }
+            ResumableCallBase::Yielded(invocation) => {
+                Self::Yielded(TypedYieldedInvocation::new(invocation))
+            }
+            ResumableCallBase::OutOfFuel(invocation) => {
+                Self::OutOfFuel(TypedYieldedInvocation::new(invocation))
+            }
         }
     }
 }
@@ -330,3 +551,59 @@ impl<Results> fmt::Debug for TypedResumableInvocation<Results> {
             .finish()
     }
 }
+
+/// State required to resume a [`TypedFunc`] invocation that exhausted its time slice or ran out of fuel.
+///
+/// [`TypedFunc`]: [`crate::TypedFunc`]
+pub struct TypedYieldedInvocation<Results> {
+    invocation: YieldedInvocation,
+    /// The parameter and result typed encoded in Rust type system.
+    results: PhantomData<fn() -> Results>,
+}
+
+impl<Results> TypedYieldedInvocation<Results> {
+    /// Creates a [`TypedYieldedInvocation`] wrapper for the given [`YieldedInvocation`].
+    pub(crate) fn new(invocation: YieldedInvocation) -> Self {
+        Self {
+            invocation,
+            results: PhantomData,
+        }
+    }
+
+    /// Resumes the call to the [`TypedFunc`] for another time slice or after refueling.
+    ///
+    /// Returns a resumable handle to the function invocation upon encountering
+    /// host errors, exhausting the time slice or running out of fuel again.
+    ///
+    /// # Errors
+    ///
+    /// If the function resumption returned a Wasm [`Error`].
+    ///
+    /// [`TypedFunc`]: [`crate::TypedFunc`]
+    pub fn resume<T>(
+        self,
+        mut ctx: impl AsContextMut<UserState = T>,
+    ) -> Result<TypedResumableCall<Results>, Error>
+    where
+        Results: WasmResults,
+    {
+        self.invocation
+            .engine
+            .clone()
+            .resume_yielded(
+                ctx.as_context_mut(),
+                self.invocation,
+                <CallResultsTuple<Results>>::default(),
+            )
+            .map(TypedResumableCall::new)
+    }
+}
+
+impl<Results> fmt::Debug for TypedYieldedInvocation<Results> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedYieldedInvocation")
+            .field("invocation", &self.invocation)
+            .field("results", &self.results)
+            .finish()
+    }
+}
@@ -0,0 +1,203 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+use wasmi_core::TrapCode;
+
+/// The number of distinct [`TrapCode`]s that are counted by the [`EngineStats`].
+///
+/// # Note
+///
+/// Trap counters are indexed by [`TrapCode::code`] which assigns the next
+/// free code to new [`TrapCode`]s, so this is derived from the highest code.
+const MAX_TRAP_CODES: usize = TrapCode::MemoryWriteProtected.code() as usize + 1;
+
+/// Returns the index of the trap counter of `trap_code` if any.
+fn trap_index(trap_code: TrapCode) -> Option<usize> {
+    let index = usize::from(trap_code.code());
+    debug_assert!(
+        index < MAX_TRAP_CODES,
+        "missing trap counter for {trap_code:?}"
+    );
+    (index < MAX_TRAP_CODES).then_some(index)
+}
+
+/// Statistics about the activity of an [`Engine`] since its creation.
+///
+/// Query them via [`Engine::stats`].
+///
+/// # Note
+///
+/// Counters wrap around upon overflow on targets with 32-bit pointers.
+///
+/// [`Engine`]: crate::Engine
+/// [`Engine::stats`]: crate::Engine::stats
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct EngineStats {
+    /// The number of compiled Wasm modules.
+    modules_compiled: u64,
+    /// The number of translated Wasm functions.
+    funcs_translated: u64,
+    /// The number of executions that reused a cached execution stack.
+    stack_cache_hits: u64,
+    /// The number of executions that had to allocate a new execution stack.
+    stack_cache_misses: u64,
+    /// The number of function translations that reused cached translation allocations.
+    translation_allocs_hits: u64,
+    /// The number of function translations that had to create new translation allocations.
+    translation_allocs_misses: u64,
+    /// The number of instantiated Wasm modules.
+    instantiations: u64,
+    /// The number of traps per [`TrapCode`].
+    traps: [u64; MAX_TRAP_CODES],
+}
+
+impl EngineStats {
+    /// Returns the number of Wasm modules compiled by the [`Engine`].
+    ///
+    /// [`Engine`]: crate::Engine
+    pub fn modules_compiled(&self) -> u64 {
+        self.modules_compiled
+    }
+
+    /// Returns the number of Wasm functions translated to Wasmi bytecode.
+    ///
+    /// # Note
+    ///
+    /// With lazy compilation functions are counted once they are translated upon their first call.
+    pub fn funcs_translated(&self) -> u64 {
+        self.funcs_translated
+    }
+
+    /// Returns the number of executions that reused a cached execution stack.
+    pub fn stack_cache_hits(&self) -> u64 {
+        self.stack_cache_hits
+    }
+
+    /// Returns the number of executions that had to allocate a new execution stack.
+    pub fn stack_cache_misses(&self) -> u64 {
+        self.stack_cache_misses
+    }
+
+    /// Returns the number of function translations that reused cached translation allocations.
+    ///
+    /// # Note
+    ///
+    /// The [`Engine`] caches the allocations of its function translators across the
+    /// compilation of all its Wasm modules.
+    ///
+    /// [`Engine`]: crate::Engine
+    pub fn translation_allocs_hits(&self) -> u64 {
+        self.translation_allocs_hits
+    }
+
+    /// Returns the number of function translations that had to create new translation allocations.
+    pub fn translation_allocs_misses(&self) -> u64 {
+        self.translation_allocs_misses
+    }
+
+    /// Returns the number of Wasm module instantiations.
+    pub fn instantiations(&self) -> u64 {
+        self.instantiations
+    }
+
+    /// Returns the number of Wasm executions that trapped with `trap_code`.
+    ///
+    /// # Note
+    ///
+    /// Traps propagating through multiple nested Wasm executions are counted once.
+    pub fn traps(&self, trap_code: TrapCode) -> u64 {
+        trap_index(trap_code)
+            .map(|index| self.traps[index])
+            .unwrap_or_default()
+    }
+
+    /// Returns the total number of Wasm executions that trapped.
+    pub fn total_traps(&self) -> u64 {
+        self.traps.iter().sum()
+    }
+}
+
+/// The atomic counters backing the [`EngineStats`] of an [`Engine`].
+///
+/// [`Engine`]: crate::Engine
+#[derive(Debug, Default)]
+pub struct EngineCounters {
+    /// The number of compiled Wasm modules.
+    modules_compiled: AtomicUsize,
+    /// The number of translated Wasm functions.
+    funcs_translated: AtomicUsize,
+    /// The number of executions that reused a cached execution stack.
+    stack_cache_hits: AtomicUsize,
+    /// The number of executions that had to allocate a new execution stack.
+    stack_cache_misses: AtomicUsize,
+    /// The number of function translations that reused cached translation allocations.
+    translation_allocs_hits: AtomicUsize,
+    /// The number of function translations that had to create new translation allocations.
+    translation_allocs_misses: AtomicUsize,
+    /// The number of instantiated Wasm modules.
+    instantiations: AtomicUsize,
+    /// The number of traps per [`TrapCode`].
+    traps: [AtomicUsize; MAX_TRAP_CODES],
+}
+
+/// Increments the `counter` by one.
+fn increment(counter: &AtomicUsize) {
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Returns the current value of the `counter`.
+fn load(counter: &AtomicUsize) -> u64 {
+    counter.load(Ordering::Relaxed) as u64
+}
+
+impl EngineCounters {
+    /// Records the compilation of a Wasm module.
+    pub fn module_compiled(&self) {
+        increment(&self.modules_compiled)
+    }
+
+    /// Records the translation of a Wasm function.
+    pub fn func_translated(&self) {
+        increment(&self.funcs_translated)
+    }
+
+    /// Records whether an execution reused a cached execution stack.
+    pub fn stack_reused(&self, hit: bool) {
+        match hit {
+            true => increment(&self.stack_cache_hits),
+            false => increment(&self.stack_cache_misses),
+        }
+    }
+
+    /// Records whether a function translation reused cached translation allocations.
+    pub fn translation_allocs_reused(&self, hit: bool) {
+        match hit {
+            true => increment(&self.translation_allocs_hits),
+            false => increment(&self.translation_allocs_misses),
+        }
+    }
+
+    /// Records the instantiation of a Wasm module.
+    pub fn instantiated(&self) {
+        increment(&self.instantiations)
+    }
+
+    /// Records a trap with `trap_code`.
+    pub fn trapped(&self, trap_code: TrapCode) {
+        if let Some(index) = trap_index(trap_code) {
+            increment(&self.traps[index])
+        }
+    }
+
+    /// Returns a snapshot of the current [`EngineStats`].
+    pub fn snapshot(&self) -> EngineStats {
+        EngineStats {
+            modules_compiled: load(&self.modules_compiled),
+            funcs_translated: load(&self.funcs_translated),
+            stack_cache_hits: load(&self.stack_cache_hits),
+            stack_cache_misses: load(&self.stack_cache_misses),
+            translation_allocs_hits: load(&self.translation_allocs_hits),
+            translation_allocs_misses: load(&self.translation_allocs_misses),
+            instantiations: load(&self.instantiations),
+            traps: core::array::from_fn(|index| load(&self.traps[index])),
+        }
+    }
+}
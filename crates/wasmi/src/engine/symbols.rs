@@ -0,0 +1,172 @@
+use alloc::{string::String, sync::Arc};
+use core::{fmt, fmt::Debug};
+
+/// A user provided source of human readable symbols for Wasm code locations.
+///
+/// Register it via [`Engine::set_symbol_provider`] to resolve symbols from
+/// external side files such as split DWARF debug information. The [`Engine`]
+/// queries it for [`WasmBacktrace`]s and [`Engine::symbolicate`].
+///
+/// [`Engine`]: crate::Engine
+/// [`Engine::set_symbol_provider`]: crate::Engine::set_symbol_provider
+/// [`Engine::symbolicate`]: crate::Engine::symbolicate
+/// [`WasmBacktrace`]: crate::WasmBacktrace
+pub trait SymbolProvider: Send + Sync {
+    /// Returns the [`Symbol`] for the Wasm code location described by `query` if known.
+    fn symbolicate(&self, query: &SymbolQuery) -> Option<Symbol>;
+}
+
+impl<F> SymbolProvider for F
+where
+    F: Fn(&SymbolQuery) -> Option<Symbol> + Send + Sync,
+{
+    fn symbolicate(&self, query: &SymbolQuery) -> Option<Symbol> {
+        self(query)
+    }
+}
+
+/// The [`SymbolProvider`] registered at an [`Engine`] that names Wasm functions in backtraces.
+///
+/// [`Engine`]: crate::Engine
+#[derive(Clone)]
+pub struct SharedSymbolProvider(Arc<dyn SymbolProvider>);
+
+impl Debug for SharedSymbolProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SharedSymbolProvider(...)")
+    }
+}
+
+impl SharedSymbolProvider {
+    /// Creates a new [`SharedSymbolProvider`] from the given `provider`.
+    pub fn new<P>(provider: P) -> Self
+    where
+        P: SymbolProvider + 'static,
+    {
+        Self(Arc::new(provider))
+    }
+
+    /// Returns the [`Symbol`] for the Wasm code location described by `query` if known.
+    pub fn symbolicate(&self, query: &SymbolQuery) -> Option<Symbol> {
+        self.0.symbolicate(query)
+    }
+}
+
+/// A Wasm code location to be resolved by a [`SymbolProvider`].
+#[derive(Debug, Copy, Clone)]
+pub struct SymbolQuery<'a> {
+    /// The name of the Wasm module from its `name` custom section if any.
+    module_name: Option<&'a str>,
+    /// The index of the Wasm function within its module.
+    func_index: u32,
+    /// The name of the Wasm function from the `name` custom section if any.
+    func_name: Option<&'a str>,
+    /// The Wasm bytecode offset within the Wasm module if known.
+    wasm_offset: Option<u32>,
+}
+
+impl<'a> SymbolQuery<'a> {
+    /// Creates a new [`SymbolQuery`].
+    pub(crate) fn new(
+        module_name: Option<&'a str>,
+        func_index: u32,
+        func_name: Option<&'a str>,
+        wasm_offset: Option<u32>,
+    ) -> Self {
+        Self {
+            module_name,
+            func_index,
+            func_name,
+            wasm_offset,
+        }
+    }
+
+    /// Returns the name of the Wasm module from its `name` custom section if any.
+    pub fn module_name(&self) -> Option<&'a str> {
+        self.module_name
+    }
+
+    /// Returns the index of the Wasm function within its module.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the name of the Wasm function from the `name` custom section if any.
+    pub fn func_name(&self) -> Option<&'a str> {
+        self.func_name
+    }
+
+    /// Returns the Wasm bytecode offset within the Wasm module if known.
+    ///
+    /// # Note
+    ///
+    /// This is `None` for backtraces unless [`Config::generate_address_maps`] is enabled.
+    ///
+    /// [`Config::generate_address_maps`]: crate::Config::generate_address_maps
+    pub fn wasm_offset(&self) -> Option<u32> {
+        self.wasm_offset
+    }
+}
+
+/// A human readable symbol resolved by a [`SymbolProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// The name of the symbol, usually a (demangled) function name.
+    name: String,
+    /// The source file of the symbol if known.
+    file: Option<String>,
+    /// The line within the source file if known.
+    line: Option<u32>,
+    /// The column within the source line if known.
+    column: Option<u32>,
+}
+
+impl Symbol {
+    /// Creates a new [`Symbol`] with `name` and no source location.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            file: None,
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Sets the source `file` of the [`Symbol`].
+    pub fn with_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Sets the source `line` of the [`Symbol`].
+    pub fn with_line(mut self, line: u32) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Sets the source `column` of the [`Symbol`].
+    pub fn with_column(mut self, column: u32) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Returns the name of the [`Symbol`].
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the source file of the [`Symbol`] if known.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Returns the line within the source file of the [`Symbol`] if known.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// Returns the column within the source line of the [`Symbol`] if known.
+    pub fn column(&self) -> Option<u32> {
+        self.column
+    }
+}
@@ -1 +1,2 @@
 mod host_calls;
+mod stack_pool;
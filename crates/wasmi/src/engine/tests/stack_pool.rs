@@ -0,0 +1,38 @@
+//! Tests for the reuse of execution stacks across function calls.
+
+use crate::{Config, Engine, Func, Store};
+
+/// Calls a host function `n` times from the host side using an [`Engine`] with `config`.
+fn call_n(config: &Config, n: usize) -> Engine {
+    let engine = Engine::new(config);
+    let mut store = Store::new(&engine, ());
+    let func = Func::wrap(&mut store, |value: i32| value + 1);
+    let func = func.typed::<i32, i32>(&store).unwrap();
+    for i in 0..n {
+        let value = i as i32;
+        assert_eq!(func.call(&mut store, value).unwrap(), value + 1);
+    }
+    engine
+}
+
+#[test]
+fn stacks_are_reused_across_calls() {
+    let engine = call_n(&Config::default(), 10);
+    assert_eq!(engine.len_cached_stacks(), 1);
+}
+
+#[test]
+fn stacks_are_not_cached_if_disabled() {
+    let mut config = Config::default();
+    config.set_cached_stacks(0);
+    let engine = call_n(&config, 10);
+    assert_eq!(engine.len_cached_stacks(), 0);
+}
+
+#[test]
+fn clear_caches_releases_cached_stacks() {
+    let engine = call_n(&Config::default(), 1);
+    assert_eq!(engine.len_cached_stacks(), 1);
+    engine.clear_caches();
+    assert_eq!(engine.len_cached_stacks(), 0);
+}
@@ -6,7 +6,7 @@ use wasmi_core::UntypedValue;
 ///
 /// # Note
 ///
-/// - This is generically implemented by `&[Value]` and tuples of `T: WasmType` types.
+/// - This is generically implemented by `&[Value]`, `&[UntypedValue]` and tuples of `T: WasmType` types.
 /// - Using this trait allows to customize the parameters entrypoint for efficient
 ///   function execution via the [`Engine`].
 ///
@@ -52,12 +52,21 @@ impl<'a> Iterator for CallParamsValueIter<'a> {
 
 impl ExactSizeIterator for CallParamsValueIter<'_> {}
 
+impl<'a> CallParams for &'a [UntypedValue] {
+    type Params = iter::Copied<slice::Iter<'a, UntypedValue>>;
+
+    #[inline]
+    fn call_params(self) -> Self::Params {
+        self.iter().copied()
+    }
+}
+
 /// Types implementing this trait may be used as results for function execution.
 ///
 /// # Note
 ///
-/// - This is generically implemented by `&mut [Value]` and indirectly for
-///   tuples of `T: WasmType`.
+/// - This is generically implemented by `&mut [Value]`, `&mut [UntypedValue]`
+///   and indirectly for tuples of `T: WasmType`.
 /// - Using this trait allows to customize the parameters entrypoint for efficient
 ///   function execution via the [`Engine`].
 ///
@@ -91,3 +100,15 @@ impl<'a> CallResults for &'a mut [Value] {
         })
     }
 }
+
+impl CallResults for &mut [UntypedValue] {
+    type Results = ();
+
+    fn len_results(&self) -> usize {
+        self.len()
+    }
+
+    fn call_results(self, results: &[UntypedValue]) -> Self::Results {
+        self.copy_from_slice(results)
+    }
+}
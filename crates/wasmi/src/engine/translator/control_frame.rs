@@ -147,6 +147,21 @@ impl BlockControlFrame {
     pub fn consume_fuel_instr(&self) -> Option<Instr> {
         self.consume_fuel
     }
+
+    /// Updates the [`ConsumeFuel`] instruction of the [`BlockControlFrame`].
+    ///
+    /// # Panics
+    ///
+    /// If the `consume_fuel` field was not already `Some`.
+    ///
+    /// [`ConsumeFuel`]: enum.Instruction.html#variant.ConsumeFuel
+    pub fn update_consume_fuel_instr(&mut self, instr: Instr) {
+        assert!(
+            self.consume_fuel.is_some(),
+            "can only update the consume fuel instruction if it existed before"
+        );
+        self.consume_fuel = Some(instr);
+    }
 }
 
 /// A Wasm `loop` control flow frame.
@@ -243,6 +258,21 @@ impl LoopControlFrame {
     pub fn consume_fuel_instr(&self) -> Option<Instr> {
         self.consume_fuel
     }
+
+    /// Updates the [`ConsumeFuel`] instruction of the [`LoopControlFrame`].
+    ///
+    /// # Panics
+    ///
+    /// If the `consume_fuel` field was not already `Some`.
+    ///
+    /// [`ConsumeFuel`]: enum.Instruction.html#variant.ConsumeFuel
+    pub fn update_consume_fuel_instr(&mut self, instr: Instr) {
+        assert!(
+            self.consume_fuel.is_some(),
+            "can only update the consume fuel instruction if it existed before"
+        );
+        self.consume_fuel = Some(instr);
+    }
 }
 
 /// A Wasm `if` and `else` control flow frames.
@@ -720,4 +750,23 @@ impl ControlFrame {
             ControlFrame::Unreachable(_) => None,
         }
     }
+
+    /// Updates the [`ConsumeFuel`] instruction of the [`ControlFrame`].
+    ///
+    /// # Panics
+    ///
+    /// - If the [`ControlFrame`] is unreachable.
+    /// - If the [`ControlFrame`] had no [`ConsumeFuel`] instruction before.
+    ///
+    /// [`ConsumeFuel`]: enum.Instruction.html#variant.ConsumeFuel
+    pub fn update_consume_fuel_instr(&mut self, instr: Instr) {
+        match self {
+            ControlFrame::Block(frame) => frame.update_consume_fuel_instr(instr),
+            ControlFrame::Loop(frame) => frame.update_consume_fuel_instr(instr),
+            ControlFrame::If(frame) => frame.update_consume_fuel_instr(instr),
+            ControlFrame::Unreachable(_) => {
+                panic!("cannot update the consume fuel instruction of an unreachable control frame")
+            }
+        }
+    }
 }
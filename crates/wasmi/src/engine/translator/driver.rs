@@ -1,11 +1,15 @@
 use crate::{
-    engine::{code_map::CompiledFuncEntity, WasmTranslator},
+    engine::{code_map::CompiledFuncEntity, TranslationError, WasmTranslator},
+    module::FuncIdx,
     Error,
 };
-use wasmparser::FunctionBody;
+use alloc::{boxed::Box, collections::BTreeSet, string::String};
+use wasmparser::{FunctionBody, Operator, OperatorsReader};
 
 /// Translates Wasm bytecode into Wasmi bytecode for a single Wasm function.
 pub struct FuncTranslationDriver<'parser, T> {
+    /// The index of the translated function within its Wasm module.
+    func_index: FuncIdx,
     /// The function body that shall be translated.
     func_body: FunctionBody<'parser>,
     /// The bytes that make up the entirety of the function body.
@@ -15,8 +19,9 @@ pub struct FuncTranslationDriver<'parser, T> {
 }
 
 impl<'parser, T> FuncTranslationDriver<'parser, T> {
-    /// Creates a new Wasm to Wasmi bytecode function translator.
+    /// Creates a new Wasm to Wasmi bytecode function translator for the function at `func_index`.
     pub fn new(
+        func_index: FuncIdx,
         offset: impl Into<Option<usize>>,
         bytes: &'parser [u8],
         translator: T,
@@ -24,6 +29,7 @@ impl<'parser, T> FuncTranslationDriver<'parser, T> {
         let offset = offset.into().unwrap_or(0);
         let func_body = FunctionBody::new(offset, bytes);
         Ok(Self {
+            func_index,
             func_body,
             bytes,
             translator,
@@ -36,17 +42,29 @@ where
     T: WasmTranslator<'parser>,
 {
     /// Starts translation of the Wasm stream into Wasmi bytecode.
+    ///
+    /// # Errors
+    ///
+    /// If translation fails. The returned [`Error`] is annotated with the index
+    /// of the translated function, the Wasm offset at which translation failed
+    /// and the name of the Wasm operator that failed to translate if any.
     pub fn translate(
         mut self,
-        finalize: impl FnOnce(CompiledFuncEntity),
+        finalize: impl FnOnce(CompiledFuncEntity) -> Result<(), Error>,
     ) -> Result<T::Allocations, Error> {
-        if self.translator.setup(self.bytes)? {
-            let allocations = self.translator.finish(finalize)?;
+        let func_index = self.func_index.into_u32();
+        let start = self.func_body.range().start;
+        let in_func = |offset: usize| {
+            move |error: Error| error.with_translation_location(func_index, offset, None)
+        };
+        self.translator.update_pos(start);
+        if self.translator.setup(self.bytes).map_err(in_func(start))? {
+            let allocations = self.translator.finish(finalize).map_err(in_func(start))?;
             return Ok(allocations);
         }
         self.translate_locals()?;
         let offset = self.translate_operators()?;
-        let allocations = self.finish(offset, finalize)?;
+        let allocations = self.finish(offset, finalize).map_err(in_func(offset))?;
         Ok(allocations)
     }
 
@@ -54,23 +72,46 @@ where
     fn finish(
         mut self,
         offset: usize,
-        finalize: impl FnOnce(CompiledFuncEntity),
+        finalize: impl FnOnce(CompiledFuncEntity) -> Result<(), Error>,
     ) -> Result<T::Allocations, Error> {
         self.translator.update_pos(offset);
         self.translator.finish(finalize).map_err(Into::into)
     }
 
+    /// Annotates the `error` of a translation failure at `offset` with its location.
+    fn error_at(
+        &self,
+        error: impl Into<Error>,
+        offset: usize,
+        operator: Option<Box<str>>,
+    ) -> Error {
+        error
+            .into()
+            .with_translation_location(self.func_index.into_u32(), offset, operator)
+    }
+
     /// Translates local variables of the Wasm function.
     fn translate_locals(&mut self) -> Result<(), Error> {
-        let mut reader = self.func_body.get_locals_reader()?;
+        let start = self.func_body.range().start;
+        let mut reader = self
+            .func_body
+            .get_locals_reader()
+            .map_err(|error| self.error_at(error, start, None))?;
         let len_locals = reader.get_count();
         for _ in 0..len_locals {
             let offset = reader.original_position();
-            let (amount, value_type) = reader.read()?;
+            let (amount, value_type) = reader
+                .read()
+                .map_err(|error| self.error_at(error, offset, None))?;
             self.translator.update_pos(offset);
-            self.translator.translate_locals(amount, value_type)?;
+            self.translator
+                .translate_locals(amount, value_type)
+                .map_err(|error| self.error_at(error, offset, None))?;
         }
-        self.translator.finish_translate_locals()?;
+        let offset = reader.original_position();
+        self.translator
+            .finish_translate_locals()
+            .map_err(|error| self.error_at(error, offset, None))?;
         Ok(())
     }
 
@@ -78,13 +119,133 @@ where
     ///
     /// Returns the offset of the `End` Wasm operator.
     fn translate_operators(&mut self) -> Result<usize, Error> {
-        let mut reader = self.func_body.get_operators_reader()?;
+        let mut reader = self
+            .func_body
+            .get_operators_reader()
+            .map_err(|error| self.error_at(error, self.func_body.range().start, None))?;
         while !reader.eof() {
             let pos = reader.original_position();
             self.translator.update_pos(pos);
-            reader.visit_operator(&mut self.translator)??;
+            let operator = reader.clone();
+            self.translator
+                .translate_safepoint()
+                .and_then(|()| {
+                    reader
+                        .visit_operator(&mut self.translator)
+                        .map_err(Error::from)
+                })
+                .and_then(|result| result)
+                .map_err(|error| self.error_at(error, pos, operator_name(operator)))?;
         }
-        reader.ensure_end()?;
+        let pos = reader.original_position();
+        reader
+            .ensure_end()
+            .map_err(|error| self.error_at(error, pos, None))?;
         Ok(reader.original_position())
     }
 }
+
+/// Returns the name of the next Wasm operator of `reader` as used in the Wasm text format.
+///
+/// Returns `None` if the Wasm operator cannot be read.
+fn operator_name(mut reader: OperatorsReader) -> Option<Box<str>> {
+    reader.read().ok().map(|operator| text_name(&operator))
+}
+
+/// Returns the name of the Wasm `operator` as used in the Wasm text format.
+fn text_name(operator: &Operator) -> Box<str> {
+    macro_rules! visit_name {
+        ( $( @$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident )* ) => {
+            match operator {
+                $(
+                    Operator::$op { .. } => stringify!($visit),
+                )*
+            }
+        };
+    }
+    let visit_name = wasmparser::for_each_operator!(visit_name);
+    let name = visit_name.strip_prefix("visit_").unwrap_or(visit_name);
+    let name = match name.split_once('_') {
+        Some((namespace, name)) if is_operator_namespace(namespace) => {
+            let mut text = String::from(namespace);
+            text.push('.');
+            text.push_str(name);
+            text
+        }
+        _ if name == "typed_select" => String::from("select"),
+        _ => String::from(name),
+    };
+    name.into_boxed_str()
+}
+
+/// Returns `true` if the Wasm operator with the text format `name` is contained in `denied`.
+///
+/// A denied name of the form `<prefix>.*` matches all Wasm operators with that prefix.
+fn is_denied(denied: &BTreeSet<Box<str>>, name: &str) -> bool {
+    if denied.contains(name) {
+        return true;
+    }
+    let Some((namespace, _)) = name.split_once('.') else {
+        return false;
+    };
+    let mut wildcard = String::from(namespace);
+    wildcard.push_str(".*");
+    denied.contains(wildcard.as_str())
+}
+
+/// Checks the Wasm function body `bytes` at `offset` for Wasm operators contained in `denied`.
+///
+/// # Note
+///
+/// Malformed Wasm operators are not reported by this check but upon validation.
+///
+/// # Errors
+///
+/// If the Wasm function body uses a denied Wasm operator. The returned [`Error`] is annotated
+/// with the index of the function and the Wasm offset of the denied Wasm operator.
+pub fn check_denied_operators(
+    func_index: FuncIdx,
+    offset: usize,
+    bytes: &[u8],
+    denied: &BTreeSet<Box<str>>,
+) -> Result<(), Error> {
+    let func_body = FunctionBody::new(offset, bytes);
+    let Ok(mut reader) = func_body.get_operators_reader() else {
+        return Ok(());
+    };
+    while !reader.eof() {
+        let pos = reader.original_position();
+        let Ok(operator) = reader.read() else {
+            return Ok(());
+        };
+        let name = text_name(&operator);
+        if is_denied(denied, &name) {
+            return Err(Error::from(TranslationError::DeniedOperator(name.clone()))
+                .with_translation_location(func_index.into_u32(), pos, Some(name)));
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if `prefix` is separated by a `.` from the rest of a Wasm operator name.
+///
+/// # Example
+///
+/// The `i32` of `i32.add` or the `local` of `local.get` are namespaces.
+fn is_operator_namespace(prefix: &str) -> bool {
+    matches!(
+        prefix,
+        "i32"
+            | "i64"
+            | "f32"
+            | "f64"
+            | "v128"
+            | "local"
+            | "global"
+            | "table"
+            | "memory"
+            | "data"
+            | "elem"
+            | "ref"
+    )
+}
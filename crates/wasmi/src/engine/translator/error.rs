@@ -1,11 +1,14 @@
+use alloc::boxed::Box;
 use core::fmt::{self, Display};
 
 /// An error that may occur upon parsing, validating and translating Wasm.
 #[derive(Debug)]
 pub enum TranslationError {
     /// Encountered an unsupported Wasm block type.
+    #[cfg(feature = "compiler")]
     UnsupportedBlockType(wasmparser::BlockType),
     /// Encountered an unsupported Wasm value type.
+    #[cfg(feature = "compiler")]
     UnsupportedValueType(wasmparser::ValType),
     /// When using too many branch table targets.
     BranchTableTargetsOutOfBounds,
@@ -29,26 +32,43 @@ pub enum TranslationError {
     TooManyFunctionParams,
     /// The function failed to compiled lazily.
     LazyCompilationFailed,
+    /// The compiled code would exceed the budget of the `Engine`.
+    CompiledCodeBudgetExceeded,
+    /// The allocation of the compiled code failed.
+    AllocationFailed,
+    /// Encountered a Wasm operator that has been compiled out via crate features.
+    UnsupportedOperator(&'static str),
+    /// Encountered a Wasm operator that has been denied by the `Config`.
+    DeniedOperator(Box<str>),
 }
 
 impl TranslationError {
     /// Creates a new error indicating an unsupported Wasm block type.
+    #[cfg(feature = "compiler")]
     pub fn unsupported_block_type(block_type: wasmparser::BlockType) -> Self {
         Self::UnsupportedBlockType(block_type)
     }
 
     /// Creates a new error indicating an unsupported Wasm value type.
+    #[cfg(feature = "compiler")]
     pub fn unsupported_value_type(value_type: wasmparser::ValType) -> Self {
         Self::UnsupportedValueType(value_type)
     }
+
+    /// Creates a new error indicating an unsupported Wasm operator.
+    pub fn unsupported_operator(name: &'static str) -> Self {
+        Self::UnsupportedOperator(name)
+    }
 }
 
 impl Display for TranslationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            #[cfg(feature = "compiler")]
             Self::UnsupportedBlockType(error) => {
                 write!(f, "encountered unsupported Wasm block type: {error:?}")
             }
+            #[cfg(feature = "compiler")]
             Self::UnsupportedValueType(error) => {
                 write!(f, "encountered unsupported Wasm value type: {error:?}")
             }
@@ -100,6 +120,18 @@ impl Display for TranslationError {
                     "lazy function compilation encountered a Wasm validation or translation error"
                 )
             }
+            Self::CompiledCodeBudgetExceeded => {
+                write!(f, "compiled code exceeds the budget of the engine")
+            }
+            Self::AllocationFailed => {
+                write!(f, "failed to allocate the compiled code")
+            }
+            Self::UnsupportedOperator(name) => {
+                write!(f, "encountered unsupported Wasm operator: {name}")
+            }
+            Self::DeniedOperator(name) => {
+                write!(f, "encountered Wasm operator denied by the config: {name}")
+            }
         }
     }
 }
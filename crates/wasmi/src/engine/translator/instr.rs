@@ -0,0 +1,48 @@
+/// A reference to an instruction of the partially
+/// constructed function body of the [`InstrEncoder`].
+///
+/// [`InstrEncoder`]: super::InstrEncoder
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Instr(u32);
+
+impl Instr {
+    /// Creates an [`Instr`] from the given `usize` value.
+    ///
+    /// # Note
+    ///
+    /// This intentionally is an API intended for test purposes only.
+    ///
+    /// # Panics
+    ///
+    /// If the `value` exceeds limitations for [`Instr`].
+    pub fn from_usize(value: usize) -> Self {
+        let value = value.try_into().unwrap_or_else(|error| {
+            panic!("invalid index {value} for instruction reference: {error}")
+        });
+        Self(value)
+    }
+
+    /// Returns an `usize` representation of the instruction index.
+    pub fn into_usize(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Creates an [`Instr`] form the given `u32` value.
+    pub fn from_u32(value: u32) -> Self {
+        Self(value)
+    }
+
+    /// Returns an `u32` representation of the instruction index.
+    pub fn into_u32(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the absolute distance between `self` and `other`.
+    ///
+    /// - Returns `0` if `self == other`.
+    /// - Returns `1` if `self` is adjacent to `other` in the sequence of instructions.
+    /// - etc..
+    pub fn distance(self, other: Self) -> u32 {
+        self.0.abs_diff(other.0)
+    }
+}
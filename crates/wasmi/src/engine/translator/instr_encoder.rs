@@ -1,6 +1,7 @@
 use super::{
     visit_register::VisitInputRegisters,
     FuelInfo,
+    Instr,
     LabelRef,
     LabelRegistry,
     TypedProvider,
@@ -23,6 +24,7 @@ use crate::{
             RegisterSpanIter,
         },
         translator::{stack::RegisterSpace, ValueStack},
+        AddressMap,
         FuelCosts,
     },
     module::ModuleHeader,
@@ -32,53 +34,6 @@ use alloc::vec::{Drain, Vec};
 use core::mem;
 use wasmi_core::{UntypedValue, ValueType, F32};
 
-/// A reference to an instruction of the partially
-/// constructed function body of the [`InstrEncoder`].
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Instr(u32);
-
-impl Instr {
-    /// Creates an [`Instr`] from the given `usize` value.
-    ///
-    /// # Note
-    ///
-    /// This intentionally is an API intended for test purposes only.
-    ///
-    /// # Panics
-    ///
-    /// If the `value` exceeds limitations for [`Instr`].
-    pub fn from_usize(value: usize) -> Self {
-        let value = value.try_into().unwrap_or_else(|error| {
-            panic!("invalid index {value} for instruction reference: {error}")
-        });
-        Self(value)
-    }
-
-    /// Returns an `usize` representation of the instruction index.
-    pub fn into_usize(self) -> usize {
-        self.0 as usize
-    }
-
-    /// Creates an [`Instr`] form the given `u32` value.
-    pub fn from_u32(value: u32) -> Self {
-        Self(value)
-    }
-
-    /// Returns an `u32` representation of the instruction index.
-    pub fn into_u32(self) -> u32 {
-        self.0
-    }
-
-    /// Returns the absolute distance between `self` and `other`.
-    ///
-    /// - Returns `0` if `self == other`.
-    /// - Returns `1` if `self` is adjacent to `other` in the sequence of instructions.
-    /// - etc..
-    pub fn distance(self, other: Self) -> u32 {
-        self.0.abs_diff(other.0)
-    }
-}
-
 /// Encodes Wasmi bytecode instructions to an [`Instruction`] stream.
 #[derive(Debug, Default)]
 pub struct InstrEncoder {
@@ -103,12 +58,52 @@ pub struct InstrEncoder {
 pub struct InstrSequence {
     /// Already encoded [`Instruction`] words.
     instrs: Vec<Instruction>,
+    /// The Wasm bytecode offsets of the encoded [`Instruction`] words.
+    ///
+    /// # Note
+    ///
+    /// This is only populated if `track_offsets` is `true`.
+    offsets: Vec<u32>,
+    /// Is `true` if the Wasm bytecode offsets of encoded [`Instruction`] words are tracked.
+    track_offsets: bool,
+    /// The Wasm bytecode offset of the currently translated Wasm operator if known.
+    pos: Option<u32>,
 }
 
 impl InstrSequence {
     /// Resets the [`InstrSequence`].
     pub fn reset(&mut self) {
         self.instrs.clear();
+        self.offsets.clear();
+        self.track_offsets = false;
+        self.pos = None;
+    }
+
+    /// Enables tracking of the Wasm bytecode offsets of encoded [`Instruction`] words.
+    pub fn track_offsets(&mut self) {
+        self.track_offsets = true;
+    }
+
+    /// Updates the Wasm bytecode offset of the currently translated Wasm operator.
+    ///
+    /// # Note
+    ///
+    /// Instructions encoded before the first update are associated to the first offset.
+    pub fn update_pos(&mut self, pos: usize) {
+        if !self.track_offsets {
+            return;
+        }
+        let pos = u32::try_from(pos)
+            .unwrap_or_else(|_| panic!("Wasm bytecode offset out of bounds: {pos}"));
+        if self.pos.is_none() {
+            self.offsets.fill(pos);
+        }
+        self.pos = Some(pos);
+    }
+
+    /// Returns the Wasm bytecode offset of the currently translated Wasm operator.
+    fn current_offset(&self) -> u32 {
+        self.pos.unwrap_or(0)
     }
 
     /// Returns the next [`Instr`].
@@ -124,6 +119,9 @@ impl InstrSequence {
     fn push(&mut self, instruction: Instruction) -> Result<Instr, Error> {
         let instr = self.next_instr();
         self.instrs.push(instruction);
+        if self.track_offsets {
+            self.offsets.push(self.current_offset());
+        }
         Ok(instr)
     }
 
@@ -144,6 +142,10 @@ impl InstrSequence {
     /// If there are too many instructions in the instruction sequence.
     fn push_before(&mut self, instr: Instr, instruction: Instruction) -> Result<Instr, Error> {
         self.instrs.insert(instr.into_usize(), instruction);
+        if self.track_offsets {
+            self.offsets
+                .insert(instr.into_usize(), self.current_offset());
+        }
         let shifted_instr = instr
             .into_u32()
             .checked_add(1)
@@ -179,6 +181,18 @@ impl InstrSequence {
         self.instrs.drain(..)
     }
 
+    /// Returns the [`AddressMap`] of the generated [`Instruction`] sequence if offsets are tracked.
+    ///
+    /// # Note
+    ///
+    /// The tracked offsets of the [`InstrSequence`] will be empty after this operation.
+    pub fn take_address_map(&mut self) -> Option<AddressMap> {
+        if !self.track_offsets {
+            return None;
+        }
+        Some(AddressMap::new(self.offsets.drain(..)))
+    }
+
     /// Returns a slice to the sequence of [`Instruction`] starting at `start`.
     ///
     /// # Panics
@@ -231,6 +245,21 @@ impl InstrEncoder {
         self.instrs.drain()
     }
 
+    /// Enables tracking of the Wasm bytecode offsets of encoded [`Instruction`] words.
+    pub fn track_offsets(&mut self) {
+        self.instrs.track_offsets()
+    }
+
+    /// Updates the Wasm bytecode offset of the currently translated Wasm operator.
+    pub fn update_pos(&mut self, pos: usize) {
+        self.instrs.update_pos(pos)
+    }
+
+    /// Returns the [`AddressMap`] of the encoded [`Instruction`] sequence if offsets are tracked.
+    pub fn take_address_map(&mut self) -> Option<AddressMap> {
+        self.instrs.take_address_map()
+    }
+
     /// Creates a new unresolved label and returns its [`LabelRef`].
     pub fn new_label(&mut self) -> LabelRef {
         self.labels.new_label()
@@ -1023,7 +1052,9 @@ impl InstrEncoder {
             I::I64GtU(instr) => fuse(self, stack, last_instr, instr, label, Cmp::I64LeU, I::branch_i64_le_u as _)?,
             I::I64GeS(instr) => fuse(self, stack, last_instr, instr, label, Cmp::I64LtS, I::branch_i64_lt_s as _)?,
             I::I64GeU(instr) => fuse(self, stack, last_instr, instr, label, Cmp::I64LtU, I::branch_i64_lt_u as _)?,
+            #[cfg(feature = "float")]
             I::F32Eq(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32Ne, I::branch_f32_ne as _)?,
+            #[cfg(feature = "float")]
             I::F32Ne(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32Eq, I::branch_f32_eq as _)?,
             // Note: We cannot fuse cmp+branch for float comparison operators due to how NaN values are treated.
             I::I32AndImm16(instr) => fuse_imm::<i32>(self, stack, last_instr, instr, label, Cmp::I32AndEqz, I::branch_i32_and_eqz_imm as _)?,
@@ -1202,17 +1233,29 @@ impl InstrEncoder {
             I::I64GtU(instr) => fuse(self, stack, last_instr, instr, label, Cmp::I64GtU, I::branch_i64_gt_u as _)?,
             I::I64GeS(instr) => fuse(self, stack, last_instr, instr, label, Cmp::I64GeS, I::branch_i64_ge_s as _)?,
             I::I64GeU(instr) => fuse(self, stack, last_instr, instr, label, Cmp::I64GeU, I::branch_i64_ge_u as _)?,
+            #[cfg(feature = "float")]
             I::F32Eq(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32Eq, I::branch_f32_eq as _)?,
+            #[cfg(feature = "float")]
             I::F32Ne(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32Ne, I::branch_f32_ne as _)?,
+            #[cfg(feature = "float")]
             I::F32Lt(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32Lt, I::branch_f32_lt as _)?,
+            #[cfg(feature = "float")]
             I::F32Le(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32Le, I::branch_f32_le as _)?,
+            #[cfg(feature = "float")]
             I::F32Gt(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32Gt, I::branch_f32_gt as _)?,
+            #[cfg(feature = "float")]
             I::F32Ge(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32Ge, I::branch_f32_ge as _)?,
+            #[cfg(feature = "float")]
             I::F64Eq(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F64Eq, I::branch_f64_eq as _)?,
+            #[cfg(feature = "float")]
             I::F64Ne(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F64Ne, I::branch_f64_ne as _)?,
+            #[cfg(feature = "float")]
             I::F64Lt(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F64Lt, I::branch_f64_lt as _)?,
+            #[cfg(feature = "float")]
             I::F64Le(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F64Le, I::branch_f64_le as _)?,
+            #[cfg(feature = "float")]
             I::F64Gt(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F64Gt, I::branch_f64_gt as _)?,
+            #[cfg(feature = "float")]
             I::F64Ge(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F64Ge, I::branch_f64_ge as _)?,
             I::I32AndImm16(instr) => fuse_imm(self, stack, last_instr, instr, label, Cmp::I32And, I::branch_i32_and_imm as _)?,
             I::I32OrImm16(instr) => fuse_imm(self, stack, last_instr, instr, label, Cmp::I32Or, I::branch_i32_or_imm as _)?,
@@ -1315,17 +1358,29 @@ impl Instruction {
             I::BranchI64GtU(instr) => init_offset!(instr, new_offset, Cmp::I64GtU),
             I::BranchI64GeS(instr) => init_offset!(instr, new_offset, Cmp::I64GeS),
             I::BranchI64GeU(instr) => init_offset!(instr, new_offset, Cmp::I64GeU),
+            #[cfg(feature = "float")]
             I::BranchF32Eq(instr) => init_offset!(instr, new_offset, Cmp::F32Eq),
+            #[cfg(feature = "float")]
             I::BranchF32Ne(instr) => init_offset!(instr, new_offset, Cmp::F32Ne),
+            #[cfg(feature = "float")]
             I::BranchF32Lt(instr) => init_offset!(instr, new_offset, Cmp::F32Lt),
+            #[cfg(feature = "float")]
             I::BranchF32Le(instr) => init_offset!(instr, new_offset, Cmp::F32Le),
+            #[cfg(feature = "float")]
             I::BranchF32Gt(instr) => init_offset!(instr, new_offset, Cmp::F32Gt),
+            #[cfg(feature = "float")]
             I::BranchF32Ge(instr) => init_offset!(instr, new_offset, Cmp::F32Ge),
+            #[cfg(feature = "float")]
             I::BranchF64Eq(instr) => init_offset!(instr, new_offset, Cmp::F64Eq),
+            #[cfg(feature = "float")]
             I::BranchF64Ne(instr) => init_offset!(instr, new_offset, Cmp::F64Ne),
+            #[cfg(feature = "float")]
             I::BranchF64Lt(instr) => init_offset!(instr, new_offset, Cmp::F64Lt),
+            #[cfg(feature = "float")]
             I::BranchF64Le(instr) => init_offset!(instr, new_offset, Cmp::F64Le),
+            #[cfg(feature = "float")]
             I::BranchF64Gt(instr) => init_offset!(instr, new_offset, Cmp::F64Gt),
+            #[cfg(feature = "float")]
             I::BranchF64Ge(instr) => init_offset!(instr, new_offset, Cmp::F64Ge),
             I::BranchI32AndImm(instr) => init_offset_imm!(i32, instr, new_offset, Cmp::I32And),
             I::BranchI32OrImm(instr) => init_offset_imm!(i32, instr, new_offset, Cmp::I32Or),
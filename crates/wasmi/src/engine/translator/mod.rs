@@ -1,21 +1,34 @@
 //! Function translation for the register-machine bytecode based Wasmi engine.
 
+#[cfg(feature = "compiler")]
 mod control_frame;
+#[cfg(feature = "compiler")]
 mod control_stack;
+#[cfg(feature = "compiler")]
 mod driver;
 mod error;
+mod instr;
+#[cfg(feature = "compiler")]
 mod instr_encoder;
+#[cfg(feature = "compiler")]
 mod labels;
+#[cfg(feature = "compiler")]
 mod relink_result;
+#[cfg(feature = "compiler")]
 mod stack;
+#[cfg(feature = "compiler")]
 mod typed_value;
+#[cfg(feature = "compiler")]
 mod utils;
+#[cfg(feature = "compiler")]
 mod visit;
+#[cfg(feature = "compiler")]
 mod visit_register;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "compiler"))]
 mod tests;
 
+#[cfg(feature = "compiler")]
 use self::{
     control_frame::{
         BlockControlFrame,
@@ -28,17 +41,20 @@ use self::{
     labels::{LabelRef, LabelRegistry},
     stack::ValueStack,
     typed_value::TypedValue,
-    utils::{WasmFloat, WasmInteger},
+    utils::WasmInteger,
 };
+#[cfg(feature = "compiler")]
 pub use self::{
     control_frame::{ControlFrame, ControlFrameKind},
     control_stack::ControlStack,
-    driver::FuncTranslationDriver,
-    error::TranslationError,
-    instr_encoder::{Instr, InstrEncoder},
+    driver::{check_denied_operators, FuncTranslationDriver},
+    instr_encoder::InstrEncoder,
     stack::TypedProvider,
 };
+pub use self::{error::TranslationError, instr::Instr};
+#[cfg(feature = "compiler")]
 use super::code_map::CompiledFuncEntity;
+#[cfg(feature = "compiler")]
 use crate::{
     engine::{
         bytecode::{
@@ -49,21 +65,26 @@ use crate::{
             Register,
             RegisterSpan,
             RegisterSpanIter,
-            Sign,
             SignatureIdx,
         },
-        config::FuelCosts,
+        config::{FuelCosts, SafepointGranularity},
         BlockType,
         CompiledFunc,
+        Diagnostic,
+        DiagnosticKind,
     },
     module::{FuncIdx, FuncTypeIdx, ModuleHeader},
     Engine,
     Error,
     FuncType,
 };
+#[cfg(feature = "compiler")]
 use alloc::vec::Vec;
+#[cfg(feature = "compiler")]
 use core::fmt;
+#[cfg(feature = "compiler")]
 use wasmi_core::{TrapCode, UntypedValue, ValueType};
+#[cfg(feature = "compiler")]
 use wasmparser::{
     BinaryReaderError,
     FuncToValidate,
@@ -72,8 +93,21 @@ use wasmparser::{
     ValidatorResources,
     VisitOperator,
 };
+#[cfg(feature = "compiler")]
+#[cfg(feature = "float")]
+use {self::utils::WasmFloat, crate::engine::bytecode::Sign};
 
 /// Reusable allocations of a [`FuncTranslator`].
+///
+/// # Note
+///
+/// These allocations serve as the per-function arena of the translator.
+/// They are pooled by the [`Engine`] and reset instead of freed after each
+/// function translation so that their buffers keep their capacity. Once warmed
+/// up, translating further functions causes no allocator traffic.
+/// A bump arena is not used since it cannot back the `Vec` and `SmallVec`
+/// buffers without the unstable `allocator_api` on stable Rust.
+#[cfg(feature = "compiler")]
 #[derive(Debug, Default)]
 pub struct FuncTranslatorAllocations {
     /// The emulated value stack.
@@ -88,6 +122,7 @@ pub struct FuncTranslatorAllocations {
     br_table_targets: Vec<u32>,
 }
 
+#[cfg(feature = "compiler")]
 impl FuncTranslatorAllocations {
     /// Resets the [`FuncTranslatorAllocations`].
     fn reset(&mut self) {
@@ -100,9 +135,11 @@ impl FuncTranslatorAllocations {
 }
 
 /// The used function validator type.
+#[cfg(feature = "compiler")]
 type FuncValidator = wasmparser::FuncValidator<wasmparser::ValidatorResources>;
 
 /// A Wasm to Wasmi IR function translator that also validates its input.
+#[cfg(feature = "compiler")]
 pub struct ValidatingFuncTranslator<T> {
     /// The current position in the Wasm binary while parsing operators.
     pos: usize,
@@ -113,6 +150,7 @@ pub struct ValidatingFuncTranslator<T> {
 }
 
 /// Reusable heap allocations for function validation and translation.
+#[cfg(feature = "compiler")]
 #[derive(Default)]
 pub struct ReusableAllocations<T> {
     pub translation: T,
@@ -120,6 +158,7 @@ pub struct ReusableAllocations<T> {
 }
 
 /// A WebAssembly (Wasm) function translator.
+#[cfg(feature = "compiler")]
 pub trait WasmTranslator<'parser>: VisitOperator<'parser, Output = Result<(), Error>> {
     /// The reusable allocations required by the [`WasmTranslator`].
     ///
@@ -174,15 +213,27 @@ pub trait WasmTranslator<'parser>: VisitOperator<'parser, Output = Result<(), Er
     /// This information is mainly required for properly locating translation errors.
     fn update_pos(&mut self, pos: usize);
 
+    /// Translates the safepoint preceding the Wasm operator at the current position if any.
+    ///
+    /// # Note
+    ///
+    /// This is called before every Wasm operator and only translates safepoints
+    /// if [`SafepointGranularity::Instruction`] is configured.
+    fn translate_safepoint(&mut self) -> Result<(), Error>;
+
     /// Finishes constructing the Wasm function translation.
     ///
     /// # Note
     ///
     /// - Initialized the [`CompiledFunc`] in the [`Engine`].
     /// - Returns the allocations used for translation.
-    fn finish(self, finalize: impl FnOnce(CompiledFuncEntity)) -> Result<Self::Allocations, Error>;
+    fn finish(
+        self,
+        finalize: impl FnOnce(CompiledFuncEntity) -> Result<(), Error>,
+    ) -> Result<Self::Allocations, Error>;
 }
 
+#[cfg(feature = "compiler")]
 impl<T> ValidatingFuncTranslator<T> {
     /// Creates a new [`ValidatingFuncTranslator`].
     pub fn new(validator: FuncValidator, translator: T) -> Result<Self, Error> {
@@ -214,6 +265,7 @@ impl<T> ValidatingFuncTranslator<T> {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl<'parser, T> WasmTranslator<'parser> for ValidatingFuncTranslator<T>
 where
     T: WasmTranslator<'parser>,
@@ -245,11 +297,16 @@ where
 
     fn update_pos(&mut self, pos: usize) {
         self.pos = pos;
+        self.translator.update_pos(pos);
+    }
+
+    fn translate_safepoint(&mut self) -> Result<(), Error> {
+        self.translator.translate_safepoint()
     }
 
     fn finish(
         mut self,
-        finalize: impl FnOnce(CompiledFuncEntity),
+        finalize: impl FnOnce(CompiledFuncEntity) -> Result<(), Error>,
     ) -> Result<Self::Allocations, Error> {
         let pos = self.current_pos();
         self.validator.finish(pos)?;
@@ -263,6 +320,7 @@ where
     }
 }
 
+#[cfg(feature = "compiler")]
 macro_rules! impl_visit_operator {
     ( @mvp BrTable { $arg:ident: $argty:ty } => $visit:ident $($rest:tt)* ) => {
         // We need to special case the `BrTable` operand since its
@@ -316,6 +374,7 @@ macro_rules! impl_visit_operator {
     () => {};
 }
 
+#[cfg(feature = "compiler")]
 impl<'a, T> VisitOperator<'a> for ValidatingFuncTranslator<T>
 where
     T: WasmTranslator<'a>,
@@ -326,6 +385,7 @@ where
 }
 
 /// A lazy Wasm function translator that defers translation when the function is first used.
+#[cfg(feature = "compiler")]
 pub struct LazyFuncTranslator {
     /// The index of the lazily compiled function within its module.
     func_idx: FuncIdx,
@@ -335,8 +395,11 @@ pub struct LazyFuncTranslator {
     module: ModuleHeader,
     /// Optional information about lazy Wasm validation.
     func_to_validate: Option<FuncToValidate<ValidatorResources>>,
+    /// The offset of the function body within the Wasm binary.
+    offset: usize,
 }
 
+#[cfg(feature = "compiler")]
 impl fmt::Debug for LazyFuncTranslator {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("LazyFuncTranslator")
@@ -344,10 +407,12 @@ impl fmt::Debug for LazyFuncTranslator {
             .field("compiled_func", &self.compiled_func)
             .field("module", &self.module)
             .field("validate", &self.func_to_validate.is_some())
+            .field("offset", &self.offset)
             .finish()
     }
 }
 
+#[cfg(feature = "compiler")]
 impl LazyFuncTranslator {
     /// Create a new [`LazyFuncTranslator`].
     pub fn new(
@@ -361,10 +426,12 @@ impl LazyFuncTranslator {
             compiled_func,
             module,
             func_to_validate,
+            offset: 0,
         }
     }
 }
 
+#[cfg(feature = "compiler")]
 impl<'parser> WasmTranslator<'parser> for LazyFuncTranslator {
     type Allocations = ();
 
@@ -381,6 +448,7 @@ impl<'parser> WasmTranslator<'parser> for LazyFuncTranslator {
             .init_lazy_func(
                 self.func_idx,
                 self.compiled_func,
+                self.offset,
                 bytes,
                 &self.module,
                 self.func_to_validate.take(),
@@ -403,17 +471,25 @@ impl<'parser> WasmTranslator<'parser> for LazyFuncTranslator {
     }
 
     #[inline]
-    fn update_pos(&mut self, _pos: usize) {}
+    fn update_pos(&mut self, pos: usize) {
+        self.offset = pos;
+    }
+
+    #[inline]
+    fn translate_safepoint(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
 
     #[inline]
     fn finish(
         self,
-        _finalize: impl FnOnce(CompiledFuncEntity),
+        _finalize: impl FnOnce(CompiledFuncEntity) -> Result<(), Error>,
     ) -> Result<Self::Allocations, Error> {
         Ok(())
     }
 }
 
+#[cfg(feature = "compiler")]
 macro_rules! impl_visit_operator {
     ( @$proposal:ident $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $($rest:tt)* ) => {
         #[inline]
@@ -425,6 +501,7 @@ macro_rules! impl_visit_operator {
     () => {};
 }
 
+#[cfg(feature = "compiler")]
 impl<'a> VisitOperator<'a> for LazyFuncTranslator {
     type Output = Result<(), Error>;
 
@@ -432,6 +509,7 @@ impl<'a> VisitOperator<'a> for LazyFuncTranslator {
 }
 
 /// Type concerned with translating from Wasm bytecode to Wasmi bytecode.
+#[cfg(feature = "compiler")]
 pub struct FuncTranslator {
     /// The reference to the Wasm module function under construction.
     func: FuncIdx,
@@ -458,11 +536,23 @@ pub struct FuncTranslator {
     /// Fuel costs for fuel metering.
     ///
     /// `None` if fuel metering is disabled.
+    ///
+    /// # Note
+    ///
+    /// Instruction budget metering is implemented via fuel metering
+    /// where every instruction costs exactly one unit of fuel.
     fuel_costs: Option<FuelCosts>,
+    /// Is `true` if instruction budget metering is enabled.
+    consume_instrs: bool,
+    /// The placement of the [`Instruction::ConsumeFuel`] if fuel metering is enabled.
+    fuel_safepoints: SafepointGranularity,
+    /// The placement of the [`Instruction::CheckEpoch`] if epoch based interruption is enabled.
+    epoch_safepoints: Option<SafepointGranularity>,
     /// The reusable data structures of the [`FuncTranslator`].
     alloc: FuncTranslatorAllocations,
 }
 
+#[cfg(feature = "compiler")]
 impl<'parser> WasmTranslator<'parser> for FuncTranslator {
     type Allocations = FuncTranslatorAllocations;
 
@@ -483,11 +573,34 @@ impl<'parser> WasmTranslator<'parser> for FuncTranslator {
         Ok(())
     }
 
-    fn update_pos(&mut self, _pos: usize) {}
+    fn update_pos(&mut self, pos: usize) {
+        self.alloc.instr_encoder.update_pos(pos);
+    }
+
+    fn translate_safepoint(&mut self) -> Result<(), Error> {
+        if !self.is_reachable() {
+            return Ok(());
+        }
+        if matches!(self.fuel_safepoints, SafepointGranularity::Instruction) {
+            if let Some(fuel_instr) = self.make_fuel_instr()? {
+                self.alloc
+                    .control_stack
+                    .nth_back_mut(0)
+                    .update_consume_fuel_instr(fuel_instr);
+            }
+        }
+        if matches!(
+            self.epoch_safepoints,
+            Some(SafepointGranularity::Instruction)
+        ) {
+            self.push_epoch_check()?;
+        }
+        Ok(())
+    }
 
     fn finish(
         mut self,
-        finalize: impl FnOnce(CompiledFuncEntity),
+        finalize: impl FnOnce(CompiledFuncEntity) -> Result<(), Error>,
     ) -> Result<Self::Allocations, Error> {
         self.alloc
             .instr_encoder
@@ -512,8 +625,15 @@ impl<'parser> WasmTranslator<'parser> for FuncTranslator {
                 })?;
         }
         let func_consts = self.alloc.stack.func_local_consts();
+        let address_map = self.alloc.instr_encoder.take_address_map();
         let instrs = self.alloc.instr_encoder.drain_instrs();
-        finalize(CompiledFuncEntity::new(len_registers, instrs, func_consts));
+        finalize(CompiledFuncEntity::new(
+            self.func.into_u32(),
+            len_registers,
+            instrs,
+            func_consts,
+            address_map,
+        ))?;
         Ok(self.into_allocations())
     }
 }
@@ -527,6 +647,7 @@ impl<'parser> WasmTranslator<'parser> for FuncTranslator {
 ///   certain control flow operators such as `End` are going through the
 ///   translation process. In particular the `End` operator may end unreachable
 ///   code blocks.
+#[cfg(feature = "compiler")]
 macro_rules! bail_unreachable {
     ($this:ident) => {{
         if !$this.is_reachable() {
@@ -534,9 +655,11 @@ macro_rules! bail_unreachable {
         }
     }};
 }
+#[cfg(feature = "compiler")]
 use bail_unreachable;
 
 /// Fuel metering information for a certain translation state.
+#[cfg(feature = "compiler")]
 #[derive(Debug, Copy, Clone)]
 pub enum FuelInfo {
     /// Fuel metering is disabled.
@@ -550,6 +673,7 @@ pub enum FuelInfo {
     },
 }
 
+#[cfg(feature = "compiler")]
 impl FuelInfo {
     /// Create a new [`FuelInfo`] for enabled fuel metering.
     pub fn some(costs: FuelCosts, instr: Instr) -> Self {
@@ -557,6 +681,7 @@ impl FuelInfo {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl FuncTranslator {
     /// Creates a new [`FuncTranslator`].
     pub fn new(
@@ -571,16 +696,23 @@ impl FuncTranslator {
             )
         };
         let config = engine.config();
-        let fuel_costs = config
-            .get_consume_fuel()
-            .then(|| config.fuel_costs())
-            .copied();
+        let consume_instrs = config.get_consume_instructions() || config.get_time_slice().is_some();
+        let check_epoch = config.get_epoch_interruption() || config.get_signal_preemption();
+        let epoch_safepoints = check_epoch.then(|| config.get_epoch_safepoints());
+        let fuel_safepoints = config.get_fuel_safepoints();
+        let fuel_costs = match config.get_consume_fuel() {
+            true => Some(*config.fuel_costs()),
+            false => consume_instrs.then(FuelCosts::instruction_count),
+        };
         Self {
             func,
             engine,
             module: res,
             reachable: true,
             fuel_costs,
+            consume_instrs,
+            fuel_safepoints,
+            epoch_safepoints,
             alloc,
         }
         .init()
@@ -594,6 +726,9 @@ impl FuncTranslator {
     /// Initializes a newly constructed [`FuncTranslator`].
     fn init(mut self) -> Result<Self, Error> {
         self.alloc.reset();
+        if self.engine().config().get_generate_address_maps() {
+            self.alloc.instr_encoder.track_offsets();
+        }
         self.init_func_body_block()?;
         self.init_func_params()?;
         Ok(self)
@@ -618,6 +753,7 @@ impl FuncTranslator {
             consume_fuel,
         );
         self.alloc.control_stack.push_frame(block_frame);
+        self.push_epoch_check()?;
         Ok(())
     }
 
@@ -668,6 +804,12 @@ impl FuncTranslator {
         self.fuel_costs.as_ref()
     }
 
+    /// Queues a [`Diagnostic`] of `kind` for the translated function if a sink is configured.
+    fn emit_diagnostic(&self, kind: DiagnosticKind) {
+        self.engine()
+            .queue_diagnostic(Diagnostic::new(self.func.into_u32(), kind));
+    }
+
     /// Returns the most recent [`Instruction::ConsumeFuel`] in the translation process.
     ///
     /// Returns `None` if fuel metering is disabled.
@@ -697,12 +839,52 @@ impl FuncTranslator {
             // Fuel metering is disabled so there is no need to create an `Instruction::ConsumeFuel`.
             return Ok(None);
         };
-        let fuel_instr = Instruction::consume_fuel(fuel_costs.base())
-            .expect("base fuel must be valid for creating `Instruction::ConsumeFuel`");
+        let fuel_instr = match self.consume_instrs {
+            true => Instruction::consume_instrs(fuel_costs.base()),
+            false => Instruction::consume_fuel(fuel_costs.base()),
+        }
+        .expect("base fuel must be valid for creating `Instruction::ConsumeFuel`");
         let instr = self.alloc.instr_encoder.push_instr(fuel_instr)?;
         Ok(Some(instr))
     }
 
+    /// Pushes an [`Instruction::CheckEpoch`] if epoch based interruption or preemption is enabled.
+    ///
+    /// # Note
+    ///
+    /// This must be called after the control frame of the function body or loop has been pushed.
+    fn push_epoch_check(&mut self) -> Result<(), Error> {
+        if self.epoch_safepoints.is_some() {
+            self.bump_fuel_consumption(FuelCosts::base)?;
+            self.alloc
+                .instr_encoder
+                .push_instr(Instruction::CheckEpoch)?;
+        }
+        Ok(())
+    }
+
+    /// Pushes an [`Instruction::CheckEpoch`] at the start of an `if` or `else` branch if configured.
+    ///
+    /// # Note
+    ///
+    /// This must be called after the control frame of the `if` has been pushed.
+    fn push_branch_epoch_check(&mut self) -> Result<(), Error> {
+        if matches!(
+            self.epoch_safepoints,
+            Some(SafepointGranularity::Instruction | SafepointGranularity::BasicBlock)
+        ) {
+            self.push_epoch_check()?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the `if` and `else` branches consume fuel on their own.
+    ///
+    /// Otherwise they inherit the [`Instruction::ConsumeFuel`] of their parent control frame.
+    fn has_branch_fuel(&self) -> bool {
+        !matches!(self.fuel_safepoints, SafepointGranularity::LoopsAndCalls)
+    }
+
     /// Bumps fuel consumption of the most recent [`Instruction::ConsumeFuel`] in the translation process.
     ///
     /// Does nothing if gas metering is disabled.
@@ -739,6 +921,48 @@ impl FuncTranslator {
         self.push_fueled_instr(instr, FuelCosts::base)
     }
 
+    /// Returns the number of runs of equal consecutive `br_table` targets.
+    fn len_br_table_runs(&self) -> usize {
+        let targets = &self.alloc.br_table_targets[..];
+        1 + targets
+            .windows(2)
+            .filter(|window| window[0] != window[1])
+            .count()
+    }
+
+    /// Returns `true` if a `br_table` shall be encoded via [`Instruction::BranchTableSparse`].
+    ///
+    /// # Note
+    ///
+    /// Small `br_table`s are always encoded densely since dense dispatch is cheapest.
+    /// Large `br_table`s are encoded sparsely if their targets form few enough runs
+    /// so that the binary search dispatch is both smaller and cheap enough.
+    fn is_sparse_br_table(len_targets: usize, len_runs: usize) -> bool {
+        /// The minimum number of targets for a `br_table` to be encoded sparsely.
+        const MIN_SPARSE_TARGETS: usize = 16;
+        /// The minimum ratio of targets per run for a `br_table` to be encoded sparsely.
+        const MIN_TARGETS_PER_RUN: usize = 4;
+        len_targets >= MIN_SPARSE_TARGETS && len_runs * MIN_TARGETS_PER_RUN <= len_targets
+    }
+
+    /// Encodes the [`Instruction::BranchTableSparse`] run bounds of the `br_table` targets.
+    ///
+    /// Afterwards `br_table_targets` only contains a single target per run.
+    fn encode_br_table_run_bounds(&mut self) -> Result<(), Error> {
+        let targets = &self.alloc.br_table_targets[..];
+        for (n, window) in targets.windows(2).enumerate() {
+            if window[0] != window[1] {
+                // Note: cannot overflow since Wasm `br_table`s have at most `u32::MAX` targets.
+                let bound = (n + 1) as u32;
+                self.alloc
+                    .instr_encoder
+                    .append_instr(Instruction::const32(bound))?;
+            }
+        }
+        self.alloc.br_table_targets.dedup();
+        Ok(())
+    }
+
     /// Convenience function to copy the parameters when branching to a control frame.
     fn translate_copy_branch_params(
         &mut self,
@@ -1324,6 +1548,7 @@ impl FuncTranslator {
     /// Used for translating the following Wasm operators to Wasmi bytecode:
     ///
     /// - `{f32, f64}.{sub, div}`
+    #[cfg(feature = "float")]
     #[allow(clippy::too_many_arguments)]
     fn translate_fbinary<T>(
         &mut self,
@@ -1381,6 +1606,7 @@ impl FuncTranslator {
     ///
     /// - This applies several optimization that are valid for copysign instructions.
     /// - Applies constant evaluation if both operands are constant values.
+    #[cfg(feature = "float")]
     fn translate_fcopysign<T>(
         &mut self,
         make_instr: fn(result: Register, lhs: Register, rhs: Register) -> Instruction,
@@ -1494,6 +1720,7 @@ impl FuncTranslator {
     /// Used for translating the following Wasm operators to Wasmi bytecode:
     ///
     /// - `{f32, f64}.{add, mul, min, max}`
+    #[cfg(feature = "float")]
     #[allow(clippy::too_many_arguments)]
     fn translate_fbinary_commutative<T>(
         &mut self,
@@ -1705,6 +1932,7 @@ impl FuncTranslator {
     }
 
     /// Translates a fallible unary Wasm instruction to Wasmi bytecode.
+    #[cfg(feature = "float")]
     fn translate_unary_fallible(
         &mut self,
         make_instr: fn(result: Register, input: Register) -> Instruction,
@@ -1933,6 +2161,7 @@ impl FuncTranslator {
     /// Used for translating the following Wasm operators to Wasmi bytecode:
     ///
     /// - `{f32, f64}.store`
+    #[cfg(feature = "float")]
     fn translate_fstore(
         &mut self,
         memarg: MemArg,
@@ -2417,6 +2646,7 @@ impl FuncTranslator {
     }
 
     /// Translates a Wasm `reinterpret` instruction.
+    #[cfg(feature = "float")]
     fn translate_reinterpret(&mut self, ty: ValueType) -> Result<(), Error> {
         bail_unreachable!(self);
         if let TypedProvider::Register(_) = self.alloc.stack.peek() {
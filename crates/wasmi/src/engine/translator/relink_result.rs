@@ -31,9 +31,7 @@ impl Instruction {
     ) -> Result<bool, Error> {
         use Instruction as I;
         match self {
-            I::TableIdx(_)
-            | I::DataSegmentIdx(_)
-            | I::ElementSegmentIdx(_)
+            I::DataSegmentIdx(_)
             | I::Const32(_)
             | I::I64Const32(_)
             | I::F64Const32(_)
@@ -45,6 +43,8 @@ impl Instruction {
             | I::CallIndirectParamsImm16(_)
             | I::Trap(_)
             | I::ConsumeFuel(_)
+            | I::ConsumeInstrs(_)
+            | I::CheckEpoch
             | I::Return
             | I::ReturnReg { .. }
             | I::ReturnReg2 { .. }
@@ -77,6 +77,7 @@ impl Instruction {
             | I::BranchI32XorEqz(_)
             | I::BranchI32XorEqzImm(_)
             | I::BranchTable { .. }
+            | I::BranchTableSparse { .. }
             | I::BranchI32Eq(_)
             | I::BranchI32EqImm(_)
             | I::BranchI32Ne(_)
@@ -116,8 +117,13 @@ impl Instruction {
             | I::BranchI64GeS(_)
             | I::BranchI64GeSImm(_)
             | I::BranchI64GeU(_)
-            | I::BranchI64GeUImm(_)
-            | I::BranchF32Eq(_)
+            | I::BranchI64GeUImm(_) => Ok(false),
+            #[cfg(not(all(not(feature = "reference-types"), not(feature = "bulk-table"))))]
+            I::TableIdx(_) => Ok(false),
+            #[cfg(feature = "bulk-table")]
+            I::ElementSegmentIdx(_) => Ok(false),
+            #[cfg(feature = "float")]
+            I::BranchF32Eq(_)
             | I::BranchF32Ne(_)
             | I::BranchF32Lt(_)
             | I::BranchF32Le(_)
@@ -147,7 +153,10 @@ impl Instruction {
             I::CallInternal0 { results, func } | I::CallInternal { results, func } => {
                 relink_call_internal(results, *func, module, new_result, old_result)
             }
-            I::CallImported0 { results, func } | I::CallImported { results, func } => {
+            I::CallImported0 { results, func }
+            | I::CallImported { results, func }
+            | I::CallIntrinsic0 { results, func }
+            | I::CallIntrinsic { results, func } => {
                 relink_call_imported(results, *func, module, new_result, old_result)
             }
             I::CallIndirect0 { results, func_type } | I::CallIndirect { results, func_type } => {
@@ -172,13 +181,13 @@ impl Instruction {
                 //       word is given to this method.
                 relink_simple(result, new_result, old_result)
             }
+            #[cfg(feature = "reference-types")]
             I::RefFunc { result, .. }
             | I::TableGet { result, .. }
             | I::TableGetImm { result, .. }
             | I::TableSize { result, .. } => relink_simple(result, new_result, old_result),
-            I::TableSet { .. }
-            | I::TableSetAt { .. }
-            | I::TableCopy { .. }
+            #[cfg(feature = "bulk-table")]
+            I::TableCopy { .. }
             | I::TableCopyTo { .. }
             | I::TableCopyFrom { .. }
             | I::TableCopyFromTo { .. }
@@ -193,15 +202,21 @@ impl Instruction {
             | I::TableInitExact { .. }
             | I::TableInitToExact { .. }
             | I::TableInitFromExact { .. }
-            | I::TableInitFromToExact { .. }
+            | I::TableInitFromToExact { .. } => Ok(false),
+            #[cfg(feature = "reference-types")]
+            I::TableSet { .. }
+            | I::TableSetAt { .. }
             | I::TableFill { .. }
             | I::TableFillAt { .. }
             | I::TableFillExact { .. }
             | I::TableFillAtExact { .. } => Ok(false),
+            #[cfg(feature = "reference-types")]
             I::TableGrow { result, .. } | I::TableGrowImm { result, .. } => {
                 relink_simple(result, new_result, old_result)
             }
-            I::ElemDrop(_) | I::DataDrop(_) => Ok(false),
+            #[cfg(feature = "bulk-table")]
+            I::ElemDrop(_) => Ok(false),
+            I::DataDrop(_) => Ok(false),
             I::MemorySize { result }
             | I::MemoryGrow { result, .. }
             | I::MemoryGrowBy { result, .. } => relink_simple(result, new_result, old_result),
@@ -235,8 +250,6 @@ impl Instruction {
             }
             I::I32Load(instr) |
             I::I64Load(instr) |
-            I::F32Load(instr) |
-            I::F64Load(instr) |
             I::I32Load8s(instr) |
             I::I32Load8u(instr) |
             I::I32Load16s(instr) |
@@ -247,10 +260,11 @@ impl Instruction {
             I::I64Load16u(instr) |
             I::I64Load32s(instr) |
             I::I64Load32u(instr) => relink_simple(instr, new_result, old_result),
+            #[cfg(feature = "float")]
+            I::F32Load(instr) |
+            I::F64Load(instr) => relink_simple(instr, new_result, old_result),
             I::I32LoadAt(instr) |
             I::I64LoadAt(instr) |
-            I::F32LoadAt(instr) |
-            I::F64LoadAt(instr) |
             I::I32Load8sAt(instr) |
             I::I32Load8uAt(instr) |
             I::I32Load16sAt(instr) |
@@ -261,10 +275,11 @@ impl Instruction {
             I::I64Load16uAt(instr) |
             I::I64Load32sAt(instr) |
             I::I64Load32uAt(instr) => relink_simple(instr, new_result, old_result),
+            #[cfg(feature = "float")]
+            I::F32LoadAt(instr) |
+            I::F64LoadAt(instr) => relink_simple(instr, new_result, old_result),
             I::I32LoadOffset16(instr) |
             I::I64LoadOffset16(instr) |
-            I::F32LoadOffset16(instr) |
-            I::F64LoadOffset16(instr) |
             I::I32Load8sOffset16(instr) |
             I::I32Load8uOffset16(instr) |
             I::I32Load16sOffset16(instr) |
@@ -275,6 +290,9 @@ impl Instruction {
             I::I64Load16uOffset16(instr) |
             I::I64Load32sOffset16(instr) |
             I::I64Load32uOffset16(instr) => relink_simple(instr, new_result, old_result),
+            #[cfg(feature = "float")]
+            I::F32LoadOffset16(instr) |
+            I::F64LoadOffset16(instr) => relink_simple(instr, new_result, old_result),
             I::I32Store(_)
             | I::I32StoreOffset16(_)
             | I::I32StoreOffset16Imm16(_)
@@ -309,8 +327,9 @@ impl Instruction {
             | I::I64Store32Offset16(_)
             | I::I64Store32Offset16Imm16(_)
             | I::I64Store32At(_)
-            | I::I64Store32AtImm16(_)
-            | I::F32Store(_)
+            | I::I64Store32AtImm16(_) => Ok(false),
+            #[cfg(feature = "float")]
+            I::F32Store(_)
             | I::F32StoreOffset16(_)
             | I::F32StoreAt(_)
             | I::F64Store(_)
@@ -335,7 +354,8 @@ impl Instruction {
             I::I32GeS(instr) |
             I::I64GeS(instr) |
             I::I32GeU(instr) |
-            I::I64GeU(instr) |
+            I::I64GeU(instr) => relink_simple(instr, new_result, old_result),
+            #[cfg(feature = "float")]
             I::F32Eq(instr) |
             I::F32Ne(instr) |
             I::F32Lt(instr) |
@@ -410,6 +430,7 @@ impl Instruction {
             I::I64Rotl(instr) |
             I::I64Rotr(instr) => relink_simple(instr, new_result, old_result),
 
+            #[cfg(feature = "float")]
             I::F32Abs(instr) |
             I::F32Neg(instr) |
             I::F32Ceil(instr) |
@@ -425,6 +446,7 @@ impl Instruction {
             I::F64Nearest(instr) |
             I::F64Sqrt(instr) => relink_simple(instr, new_result, old_result),
 
+            #[cfg(feature = "float")]
             I::F32Add(instr) |
             I::F32Sub(instr) |
             I::F32Mul(instr) |
@@ -432,6 +454,7 @@ impl Instruction {
             I::F32Min(instr) |
             I::F32Max(instr) |
             I::F32Copysign(instr) => relink_simple(instr, new_result, old_result),
+            #[cfg(feature = "float")]
             I::F64Add(instr) |
             I::F64Sub(instr) |
             I::F64Mul(instr) |
@@ -440,6 +463,7 @@ impl Instruction {
             I::F64Max(instr) |
             I::F64Copysign(instr) => relink_simple(instr, new_result, old_result),
 
+            #[cfg(feature = "float")]
             I::F32CopysignImm(instr) |
             I::F64CopysignImm(instr) => relink_simple(instr, new_result, old_result),
 
@@ -501,6 +525,12 @@ impl Instruction {
             I::I32WrapI64(instr) |
             I::I64ExtendI32S(instr) |
             I::I64ExtendI32U(instr) |
+            I::I32Extend8S(instr) |
+            I::I32Extend16S(instr) |
+            I::I64Extend8S(instr) |
+            I::I64Extend16S(instr) |
+            I::I64Extend32S(instr) => relink_simple(instr, new_result, old_result),
+            #[cfg(feature = "float")]
             I::I32TruncF32S(instr) |
             I::I32TruncF32U(instr) |
             I::I32TruncF64S(instr) |
@@ -517,11 +547,6 @@ impl Instruction {
             I::I64TruncSatF32U(instr) |
             I::I64TruncSatF64S(instr) |
             I::I64TruncSatF64U(instr) |
-            I::I32Extend8S(instr) |
-            I::I32Extend16S(instr) |
-            I::I64Extend8S(instr) |
-            I::I64Extend16S(instr) |
-            I::I64Extend32S(instr) |
             I::F32DemoteF64(instr) |
             I::F64PromoteF32(instr) |
             I::F32ConvertI32S(instr) |
@@ -289,13 +289,27 @@ type StackIndex = usize;
 #[derive(Debug, Default)]
 pub struct LocalRefs {
     /// The indices of all `local.get` on the [`ProviderStack`] of all local variables.
+    ///
+    /// # Note
+    ///
+    /// Entries beyond `len_locals` are not in use but kept so that their heap
+    /// allocated buffers can be reused when translating the next function.
     locals: Vec<SmallVec<[StackIndex; 2]>>,
+    /// The number of registered function inputs and local variables.
+    len_locals: usize,
 }
 
 impl LocalRefs {
     /// Resets the [`LocalRefs`].
+    ///
+    /// # Note
+    ///
+    /// This keeps the heap allocated buffers of all local variables for reuse.
     pub fn reset(&mut self) {
-        self.locals.clear()
+        for indices in &mut self.locals[..self.len_locals] {
+            indices.clear();
+        }
+        self.len_locals = 0;
     }
 
     /// Registers an `amount` of function inputs or local variables.
@@ -304,8 +318,11 @@ impl LocalRefs {
     ///
     /// If too many registers have been registered.
     pub fn register_locals(&mut self, amount: u32) {
-        self.locals
-            .extend(iter::repeat_with(SmallVec::default).take(amount as StackIndex));
+        self.len_locals += amount as usize;
+        if let Some(additional) = self.len_locals.checked_sub(self.locals.len()) {
+            self.locals
+                .extend(iter::repeat_with(SmallVec::default).take(additional));
+        }
     }
 
     /// Returns the [`ProviderStack`] `local.get` indices of the `local` variable.
@@ -315,7 +332,7 @@ impl LocalRefs {
     /// If the `local` index is out of bounds.
     fn get_indices_mut(&mut self, local: Register) -> &mut SmallVec<[StackIndex; 2]> {
         debug_assert!(!local.is_const());
-        &mut self.locals[local.to_i16().unsigned_abs() as usize]
+        &mut self.locals[..self.len_locals][local.to_i16().unsigned_abs() as usize]
     }
 
     /// Pushes the stack index of a `local.get` on the [`ProviderStack`].
@@ -163,7 +163,9 @@ impl Display for WasmOp {
 pub enum WasmType {
     I32,
     I64,
+    #[cfg(feature = "float")]
     F32,
+    #[cfg(feature = "float")]
     F64,
 }
 
@@ -172,7 +174,9 @@ impl Display for WasmType {
         match self {
             Self::I32 => write!(f, "i32"),
             Self::I64 => write!(f, "i64"),
+            #[cfg(feature = "float")]
             Self::F32 => write!(f, "f32"),
+            #[cfg(feature = "float")]
             Self::F64 => write!(f, "f64"),
         }
     }
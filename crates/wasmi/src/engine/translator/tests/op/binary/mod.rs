@@ -7,19 +7,33 @@
 use super::*;
 use core::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
 
+#[cfg(feature = "float")]
 mod f32_add;
+#[cfg(feature = "float")]
 mod f32_copysign;
+#[cfg(feature = "float")]
 mod f32_div;
+#[cfg(feature = "float")]
 mod f32_max;
+#[cfg(feature = "float")]
 mod f32_min;
+#[cfg(feature = "float")]
 mod f32_mul;
+#[cfg(feature = "float")]
 mod f32_sub;
+#[cfg(feature = "float")]
 mod f64_add;
+#[cfg(feature = "float")]
 mod f64_copysign;
+#[cfg(feature = "float")]
 mod f64_div;
+#[cfg(feature = "float")]
 mod f64_max;
+#[cfg(feature = "float")]
 mod f64_min;
+#[cfg(feature = "float")]
 mod f64_mul;
+#[cfg(feature = "float")]
 mod f64_sub;
 mod i32_add;
 mod i32_and;
@@ -77,6 +91,7 @@ fn nonzero_u64(value: u64) -> NonZeroU64 {
 /// # Panics
 ///
 /// If the `value` cannot be converted into `f32` losslessly.
+#[cfg(feature = "float")]
 fn return_f64imm32_instr(value: f64) -> Instruction {
     let const32 = <Const32<f64>>::try_from(value).expect("value must be 32-bit encodable");
     Instruction::return_f64imm32(const32)
@@ -239,6 +239,7 @@ fn branched_block_1_imm_i64() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn branched_block_1_imm_f32() {
     fn test_for_f32(value: f32) {
         testcase_branched_block_1_imm::<f32>(value)
@@ -259,6 +260,7 @@ fn branched_block_1_imm_f32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn branched_block_1_imm_f64imm32() {
     fn test_for_f64imm32(value: f64) {
         let const32 = <Const32<f64>>::try_from(value)
@@ -284,6 +286,7 @@ fn branched_block_1_imm_f64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn branched_block_1_imm_f64() {
     fn test_for_f64(value: f64) {
         testcase_branched_block_1_imm::<f64>(value)
@@ -107,8 +107,11 @@ fn consteval_return_1_imm() {
     }
     test_for_both::<i64>(i64::MIN, i64::MAX);
     test_for_both::<i64>(i64::from(i32::MIN) - 1, i64::from(i32::MAX) + 1);
-    test_for_both::<f64>(0.3, -0.3);
-    test_for_both::<f64>(0.123456789, -0.987654321);
+    #[cfg(feature = "float")]
+    {
+        test_for_both::<f64>(0.3, -0.3);
+        test_for_both::<f64>(0.123456789, -0.987654321);
+    }
 }
 
 #[test]
@@ -153,6 +156,7 @@ fn consteval_return_1_imm32() {
         test_for::<T>(false, if_true, if_false);
     }
     test_for_both::<i32>(5, 42);
+    #[cfg(feature = "float")]
     test_for_both::<f32>(5.5, -42.25);
 }
 
@@ -195,6 +199,7 @@ fn consteval_return_1_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn consteval_return_1_f64imm32() {
     fn test_for(condition: bool, if_true: f64, if_false: f64) {
         let expected: f64 = match condition {
@@ -356,12 +361,15 @@ fn return_if_results_1_imm() {
     test_for::<i64>(i64::MIN);
     test_for::<i64>(i64::MAX);
 
-    test_for::<f64>(0.3);
-    test_for::<f64>(-0.3);
-    test_for::<f64>(0.123456789);
-    test_for::<f64>(0.987654321);
-    test_for::<f64>(-0.123456789);
-    test_for::<f64>(-0.987654321);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f64>(0.3);
+        test_for::<f64>(-0.3);
+        test_for::<f64>(0.123456789);
+        test_for::<f64>(0.987654321);
+        test_for::<f64>(-0.123456789);
+        test_for::<f64>(-0.987654321);
+    }
 }
 
 #[test]
@@ -396,10 +404,13 @@ fn return_if_results_1_imm32() {
     test_for::<i32>(1);
     test_for::<i32>(-1);
     test_for::<i32>(42);
-    test_for::<f32>(0.0);
-    test_for::<f32>(5.5);
-    test_for::<f32>(42.25);
-    test_for::<f32>(f32::NAN);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f32>(0.0);
+        test_for::<f32>(5.5);
+        test_for::<f32>(42.25);
+        test_for::<f32>(f32::NAN);
+    }
 }
 
 #[test]
@@ -436,6 +447,7 @@ fn return_if_results_1_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn return_if_results_1_f64imm32() {
     fn test_for(returned_value: f64) {
         let display_value = DisplayWasm::from(returned_value);
@@ -455,3 +455,43 @@ fn imm_params_0() {
     test_with(3);
     test_with(1000);
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_params_0_sparse() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param $index i32) (result i32)
+                (block
+                    (block
+                        (block
+                            (br_table
+                                0 0 0 0 0 0 0 0
+                                1 1 1 1 1 1 1 1
+                                2
+                                (local.get $index)
+                            )
+                        )
+                        (return (i32.const 10))
+                    )
+                    (return (i32.const 20))
+                )
+                (return (i32.const 30))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::branch_table_sparse(Register::from_i16(0), 3),
+            Instruction::const32(8_u32),
+            Instruction::const32(16_u32),
+            Instruction::branch(BranchOffset::from(3)),
+            Instruction::branch(BranchOffset::from(3)),
+            Instruction::branch(BranchOffset::from(3)),
+            Instruction::return_imm32(10),
+            Instruction::return_imm32(20),
+            Instruction::return_imm32(30),
+        ])
+        .run()
+}
@@ -15,22 +15,34 @@
 
 use super::*;
 
+#[cfg(feature = "float")]
 mod f32_eq;
+#[cfg(feature = "float")]
 mod f32_ne;
+#[cfg(feature = "float")]
 mod f64_eq;
+#[cfg(feature = "float")]
 mod f64_ne;
 mod i32_eq;
 mod i32_ne;
 mod i64_eq;
 mod i64_ne;
 
+#[cfg(feature = "float")]
 mod f32_ge;
+#[cfg(feature = "float")]
 mod f32_gt;
+#[cfg(feature = "float")]
 mod f32_le;
+#[cfg(feature = "float")]
 mod f32_lt;
+#[cfg(feature = "float")]
 mod f64_ge;
+#[cfg(feature = "float")]
 mod f64_gt;
+#[cfg(feature = "float")]
 mod f64_le;
+#[cfg(feature = "float")]
 mod f64_lt;
 
 mod i32_ge_s;
@@ -67,19 +67,22 @@ fn loop_backward() {
     test_for(ValueType::I64, "ge_s", Instruction::branch_i64_ge_s);
     test_for(ValueType::I64, "ge_u", Instruction::branch_i64_ge_u);
 
-    test_for(ValueType::F32, "eq", Instruction::branch_f32_eq);
-    test_for(ValueType::F32, "ne", Instruction::branch_f32_ne);
-    test_for(ValueType::F32, "lt", Instruction::branch_f32_lt);
-    test_for(ValueType::F32, "le", Instruction::branch_f32_le);
-    test_for(ValueType::F32, "gt", Instruction::branch_f32_gt);
-    test_for(ValueType::F32, "ge", Instruction::branch_f32_ge);
+    #[cfg(feature = "float")]
+    {
+        test_for(ValueType::F32, "eq", Instruction::branch_f32_eq);
+        test_for(ValueType::F32, "ne", Instruction::branch_f32_ne);
+        test_for(ValueType::F32, "lt", Instruction::branch_f32_lt);
+        test_for(ValueType::F32, "le", Instruction::branch_f32_le);
+        test_for(ValueType::F32, "gt", Instruction::branch_f32_gt);
+        test_for(ValueType::F32, "ge", Instruction::branch_f32_ge);
 
-    test_for(ValueType::F64, "eq", Instruction::branch_f64_eq);
-    test_for(ValueType::F64, "ne", Instruction::branch_f64_ne);
-    test_for(ValueType::F64, "lt", Instruction::branch_f64_lt);
-    test_for(ValueType::F64, "le", Instruction::branch_f64_le);
-    test_for(ValueType::F64, "gt", Instruction::branch_f64_gt);
-    test_for(ValueType::F64, "ge", Instruction::branch_f64_ge);
+        test_for(ValueType::F64, "eq", Instruction::branch_f64_eq);
+        test_for(ValueType::F64, "ne", Instruction::branch_f64_ne);
+        test_for(ValueType::F64, "lt", Instruction::branch_f64_lt);
+        test_for(ValueType::F64, "le", Instruction::branch_f64_le);
+        test_for(ValueType::F64, "gt", Instruction::branch_f64_gt);
+        test_for(ValueType::F64, "ge", Instruction::branch_f64_ge);
+    }
 }
 
 #[test]
@@ -234,19 +237,22 @@ fn block_forward() {
     test_for(ValueType::I64, "ge_s", Instruction::branch_i64_ge_s);
     test_for(ValueType::I64, "ge_u", Instruction::branch_i64_ge_u);
 
-    test_for(ValueType::F32, "eq", Instruction::branch_f32_eq);
-    test_for(ValueType::F32, "ne", Instruction::branch_f32_ne);
-    test_for(ValueType::F32, "lt", Instruction::branch_f32_lt);
-    test_for(ValueType::F32, "le", Instruction::branch_f32_le);
-    test_for(ValueType::F32, "gt", Instruction::branch_f32_gt);
-    test_for(ValueType::F32, "ge", Instruction::branch_f32_ge);
+    #[cfg(feature = "float")]
+    {
+        test_for(ValueType::F32, "eq", Instruction::branch_f32_eq);
+        test_for(ValueType::F32, "ne", Instruction::branch_f32_ne);
+        test_for(ValueType::F32, "lt", Instruction::branch_f32_lt);
+        test_for(ValueType::F32, "le", Instruction::branch_f32_le);
+        test_for(ValueType::F32, "gt", Instruction::branch_f32_gt);
+        test_for(ValueType::F32, "ge", Instruction::branch_f32_ge);
 
-    test_for(ValueType::F64, "eq", Instruction::branch_f64_eq);
-    test_for(ValueType::F64, "ne", Instruction::branch_f64_ne);
-    test_for(ValueType::F64, "lt", Instruction::branch_f64_lt);
-    test_for(ValueType::F64, "le", Instruction::branch_f64_le);
-    test_for(ValueType::F64, "gt", Instruction::branch_f64_gt);
-    test_for(ValueType::F64, "ge", Instruction::branch_f64_ge);
+        test_for(ValueType::F64, "eq", Instruction::branch_f64_eq);
+        test_for(ValueType::F64, "ne", Instruction::branch_f64_ne);
+        test_for(ValueType::F64, "lt", Instruction::branch_f64_lt);
+        test_for(ValueType::F64, "le", Instruction::branch_f64_le);
+        test_for(ValueType::F64, "gt", Instruction::branch_f64_gt);
+        test_for(ValueType::F64, "ge", Instruction::branch_f64_ge);
+    }
 }
 
 #[test]
@@ -314,19 +320,22 @@ fn block_forward_nop_copy() {
     test_for(ValueType::I64, "ge_s", Instruction::branch_i64_ge_s);
     test_for(ValueType::I64, "ge_u", Instruction::branch_i64_ge_u);
 
-    test_for(ValueType::F32, "eq", Instruction::branch_f32_eq);
-    test_for(ValueType::F32, "ne", Instruction::branch_f32_ne);
-    test_for(ValueType::F32, "lt", Instruction::branch_f32_lt);
-    test_for(ValueType::F32, "le", Instruction::branch_f32_le);
-    test_for(ValueType::F32, "gt", Instruction::branch_f32_gt);
-    test_for(ValueType::F32, "ge", Instruction::branch_f32_ge);
+    #[cfg(feature = "float")]
+    {
+        test_for(ValueType::F32, "eq", Instruction::branch_f32_eq);
+        test_for(ValueType::F32, "ne", Instruction::branch_f32_ne);
+        test_for(ValueType::F32, "lt", Instruction::branch_f32_lt);
+        test_for(ValueType::F32, "le", Instruction::branch_f32_le);
+        test_for(ValueType::F32, "gt", Instruction::branch_f32_gt);
+        test_for(ValueType::F32, "ge", Instruction::branch_f32_ge);
 
-    test_for(ValueType::F64, "eq", Instruction::branch_f64_eq);
-    test_for(ValueType::F64, "ne", Instruction::branch_f64_ne);
-    test_for(ValueType::F64, "lt", Instruction::branch_f64_lt);
-    test_for(ValueType::F64, "le", Instruction::branch_f64_le);
-    test_for(ValueType::F64, "gt", Instruction::branch_f64_gt);
-    test_for(ValueType::F64, "ge", Instruction::branch_f64_ge);
+        test_for(ValueType::F64, "eq", Instruction::branch_f64_eq);
+        test_for(ValueType::F64, "ne", Instruction::branch_f64_ne);
+        test_for(ValueType::F64, "lt", Instruction::branch_f64_lt);
+        test_for(ValueType::F64, "le", Instruction::branch_f64_le);
+        test_for(ValueType::F64, "gt", Instruction::branch_f64_gt);
+        test_for(ValueType::F64, "ge", Instruction::branch_f64_ge);
+    }
 }
 
 #[test]
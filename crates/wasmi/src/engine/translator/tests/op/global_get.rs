@@ -50,12 +50,14 @@ fn mutable_i64() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn mutable_f32() {
     test_mutable::<f32>(42.5);
 }
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn mutable_f64() {
     test_mutable::<f64>(42.5);
 }
@@ -111,12 +113,14 @@ fn immutable_i64() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn immutable_f32() {
     test_immutable::<f32>(42.5);
 }
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn immutable_f64() {
     test_immutable::<f64>(42.5);
 }
@@ -165,12 +169,14 @@ fn imported_i64() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn imported_f32() {
     test_imported::<f32>();
 }
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn imported_f64() {
     test_imported::<f64>();
 }
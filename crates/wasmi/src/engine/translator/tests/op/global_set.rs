@@ -39,8 +39,11 @@ where
 fn reg() {
     test_reg::<i32>();
     test_reg::<i64>();
-    test_reg::<f32>();
-    test_reg::<f64>();
+    #[cfg(feature = "float")]
+    {
+        test_reg::<f32>();
+        test_reg::<f64>();
+    }
 }
 
 fn test_imm<T>(value: T)
@@ -79,10 +82,13 @@ fn imm() {
     test_imm::<i32>(i32::from(i16::MIN) - 1);
     test_imm::<i64>(i64::from(i16::MAX) + 1);
     test_imm::<i64>(i64::from(i16::MIN) - 1);
-    test_imm::<f32>(0.0);
-    test_imm::<f32>(-1.0);
-    test_imm::<f64>(0.0);
-    test_imm::<f64>(-1.0);
+    #[cfg(feature = "float")]
+    {
+        test_imm::<f32>(0.0);
+        test_imm::<f32>(-1.0);
+        test_imm::<f64>(0.0);
+        test_imm::<f64>(-1.0);
+    }
 }
 
 fn test_i32imm16(value: i32) {
@@ -303,6 +303,7 @@ mod i64_load32_u {
     );
 }
 
+#[cfg(feature = "float")]
 mod f32_load {
     use super::*;
 
@@ -316,6 +317,7 @@ mod f32_load {
     );
 }
 
+#[cfg(feature = "float")]
 mod f64_load {
     use super::*;
 
@@ -1,7 +1,10 @@
 use super::*;
 
+#[cfg(feature = "bulk-table")]
 mod memory_copy;
+#[cfg(feature = "bulk-table")]
 mod memory_fill;
 mod memory_grow;
+#[cfg(feature = "bulk-table")]
 mod memory_init;
 mod memory_size;
@@ -16,6 +16,7 @@ mod loop_;
 mod memory;
 mod return_;
 mod return_call;
+#[cfg(feature = "reference-types")]
 mod select;
 mod store;
 mod table;
@@ -35,9 +36,6 @@ use super::{
     test_binary_reg_imm_with,
     test_binary_reg_reg,
     test_binary_same_reg,
-    testcase_binary_consteval,
-    testcase_binary_imm_reg,
-    testcase_binary_reg_imm,
     wasm_type,
     wat2wasm,
     AnyConst32,
@@ -50,6 +48,8 @@ use super::{
     WasmOp,
     WasmType,
 };
+#[cfg(feature = "float")]
+use super::{testcase_binary_consteval, testcase_binary_imm_reg, testcase_binary_reg_imm};
 
 /// Creates an [`Const32<i32>`] from the given `i32` value.
 ///
@@ -91,6 +91,7 @@ fn i64imm32(value: i64) -> Const32<i64> {
 ///
 /// If the `value` cannot be converted into `i32` losslessly.
 #[track_caller]
+#[cfg(feature = "float")]
 fn f64imm32(value: f64) -> Const32<f64> {
     <Const32<f64>>::try_from(value)
         .unwrap_or_else(|_| panic!("value must be 32-bit encodable: {}", value))
@@ -112,6 +113,7 @@ fn i64imm32_instr(value: i64) -> Instruction {
 ///
 /// If the `value` cannot be converted into `i32` losslessly.
 #[track_caller]
+#[cfg(feature = "float")]
 fn f64imm32_instr(value: f64) -> Instruction {
     Instruction::F64Const32(f64imm32(value))
 }
@@ -142,6 +144,7 @@ fn return_nez_i64imm32_instr(condition: Register, value: i64) -> Instruction {
 ///
 /// If the `value` cannot be converted into `f32` losslessly.
 #[track_caller]
+#[cfg(feature = "float")]
 fn return_f64imm32_instr(value: f64) -> Instruction {
     Instruction::return_f64imm32(f64imm32(value))
 }
@@ -152,6 +155,7 @@ fn return_f64imm32_instr(value: f64) -> Instruction {
 ///
 /// If the `value` cannot be converted into `f32` losslessly.
 #[track_caller]
+#[cfg(feature = "float")]
 fn return_nez_f64imm32_instr(condition: Register, value: f64) -> Instruction {
     Instruction::return_nez_f64imm32(condition, f64imm32(value))
 }
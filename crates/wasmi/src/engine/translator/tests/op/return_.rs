@@ -72,10 +72,13 @@ fn return_1_imm() {
     test_for::<i64>(i64::MAX);
     test_for::<i64>(i64::from(i32::MIN) - 1);
     test_for::<i64>(i64::from(i32::MAX) + 1);
-    test_for::<f64>(0.3);
-    test_for::<f64>(-0.3);
-    test_for::<f64>(0.123456789);
-    test_for::<f64>(0.987654321);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f64>(0.3);
+        test_for::<f64>(-0.3);
+        test_for::<f64>(0.123456789);
+        test_for::<f64>(0.987654321);
+    }
 }
 
 #[test]
@@ -103,8 +106,11 @@ fn return_1_imm32() {
     }
     test_for::<i32>(5);
     test_for::<i32>(42);
-    test_for::<f32>(5.5);
-    test_for::<f32>(-42.25);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f32>(5.5);
+        test_for::<f32>(-42.25);
+    }
 }
 
 #[test]
@@ -134,6 +140,7 @@ fn return_1_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn return_1_f64imm32() {
     fn test_for(value: f64) {
         let display_value = DisplayWasm::from(value);
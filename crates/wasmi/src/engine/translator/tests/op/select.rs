@@ -81,8 +81,11 @@ fn reg() {
     fn test_for(kind: SelectKind) {
         test_reg(kind, ValueType::I32);
         test_reg(kind, ValueType::I64);
-        test_reg(kind, ValueType::F32);
-        test_reg(kind, ValueType::F64);
+        #[cfg(feature = "float")]
+        {
+            test_reg(kind, ValueType::F32);
+            test_reg(kind, ValueType::F64);
+        }
     }
     test_for(SelectKind::Select);
     test_for(SelectKind::TypedSelect);
@@ -115,8 +118,11 @@ fn same_reg() {
     fn test_for(kind: SelectKind) {
         test_same_reg(kind, ValueType::I32);
         test_same_reg(kind, ValueType::I64);
-        test_same_reg(kind, ValueType::F32);
-        test_same_reg(kind, ValueType::F64);
+        #[cfg(feature = "float")]
+        {
+            test_same_reg(kind, ValueType::F32);
+            test_same_reg(kind, ValueType::F64);
+        }
     }
     test_for(SelectKind::Select);
     test_for(SelectKind::TypedSelect);
@@ -179,15 +185,18 @@ fn same_imm32() {
     test_for::<i32>(i32::MIN);
     test_for::<i32>(i32::MAX);
 
-    test_for::<f32>(0.0);
-    test_for::<f32>(0.25);
-    test_for::<f32>(-0.25);
-    test_for::<f32>(1.0);
-    test_for::<f32>(-1.0);
-    test_for::<f32>(f32::NEG_INFINITY);
-    test_for::<f32>(f32::INFINITY);
-    test_for::<f32>(f32::NAN);
-    test_for::<f32>(f32::EPSILON);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f32>(0.0);
+        test_for::<f32>(0.25);
+        test_for::<f32>(-0.25);
+        test_for::<f32>(1.0);
+        test_for::<f32>(-1.0);
+        test_for::<f32>(f32::NEG_INFINITY);
+        test_for::<f32>(f32::INFINITY);
+        test_for::<f32>(f32::NAN);
+        test_for::<f32>(f32::EPSILON);
+    }
 }
 
 #[test]
@@ -214,6 +223,7 @@ fn same_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn same_f64imm32() {
     fn test_for(value: f64) {
         let expected = [return_f64imm32_instr(value)];
@@ -261,12 +271,15 @@ fn same_imm() {
     test_for::<i64>(i64::MAX - 1);
     test_for::<i64>(i64::MAX);
 
-    test_for::<f64>(0.3);
-    test_for::<f64>(-0.3);
-    test_for::<f64>(0.123456789);
-    test_for::<f64>(-0.123456789);
-    test_for::<f64>(9.87654321);
-    test_for::<f64>(-9.87654321);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f64>(0.3);
+        test_for::<f64>(-0.3);
+        test_for::<f64>(0.123456789);
+        test_for::<f64>(-0.123456789);
+        test_for::<f64>(9.87654321);
+        test_for::<f64>(-9.87654321);
+    }
 }
 
 fn test_reg_imm<T>(kind: SelectKind, rhs: T) -> TranslationTest
@@ -331,17 +344,20 @@ fn reg_imm32() {
     test_for::<i32>(i32::MAX - 1);
     test_for::<i32>(i32::MAX);
 
-    test_for::<f32>(0.0);
-    test_for::<f32>(0.25);
-    test_for::<f32>(-0.25);
-    test_for::<f32>(0.3);
-    test_for::<f32>(-0.3);
-    test_for::<f32>(1.0);
-    test_for::<f32>(-1.0);
-    test_for::<f32>(f32::NEG_INFINITY);
-    test_for::<f32>(f32::INFINITY);
-    test_for::<f32>(f32::NAN);
-    test_for::<f32>(f32::EPSILON);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f32>(0.0);
+        test_for::<f32>(0.25);
+        test_for::<f32>(-0.25);
+        test_for::<f32>(0.3);
+        test_for::<f32>(-0.3);
+        test_for::<f32>(1.0);
+        test_for::<f32>(-1.0);
+        test_for::<f32>(f32::NEG_INFINITY);
+        test_for::<f32>(f32::INFINITY);
+        test_for::<f32>(f32::NAN);
+        test_for::<f32>(f32::EPSILON);
+    }
 }
 
 #[test]
@@ -380,12 +396,15 @@ fn reg_imm() {
     test_for::<i64>(i64::MAX - 1);
     test_for::<i64>(i64::MAX);
 
-    test_for::<f64>(0.3);
-    test_for::<f64>(-0.3);
-    test_for::<f64>(0.123456789);
-    test_for::<f64>(-0.123456789);
-    test_for::<f64>(9.87654321);
-    test_for::<f64>(-9.87654321);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f64>(0.3);
+        test_for::<f64>(-0.3);
+        test_for::<f64>(0.123456789);
+        test_for::<f64>(-0.123456789);
+        test_for::<f64>(9.87654321);
+        test_for::<f64>(-9.87654321);
+    }
 }
 
 #[test]
@@ -419,6 +438,7 @@ fn reg_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn reg_f64imm32() {
     fn test_for_kind(kind: SelectKind, value: f64) {
         let result = Register::from_i16(2);
@@ -510,17 +530,20 @@ fn imm32_reg() {
     test_for::<i32>(i32::MAX - 1);
     test_for::<i32>(i32::MAX);
 
-    test_for::<f32>(0.0);
-    test_for::<f32>(0.25);
-    test_for::<f32>(-0.25);
-    test_for::<f32>(0.3);
-    test_for::<f32>(-0.3);
-    test_for::<f32>(1.0);
-    test_for::<f32>(-1.0);
-    test_for::<f32>(f32::NEG_INFINITY);
-    test_for::<f32>(f32::INFINITY);
-    test_for::<f32>(f32::NAN);
-    test_for::<f32>(f32::EPSILON);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f32>(0.0);
+        test_for::<f32>(0.25);
+        test_for::<f32>(-0.25);
+        test_for::<f32>(0.3);
+        test_for::<f32>(-0.3);
+        test_for::<f32>(1.0);
+        test_for::<f32>(-1.0);
+        test_for::<f32>(f32::NEG_INFINITY);
+        test_for::<f32>(f32::INFINITY);
+        test_for::<f32>(f32::NAN);
+        test_for::<f32>(f32::EPSILON);
+    }
 }
 
 #[test]
@@ -559,12 +582,15 @@ fn imm_reg() {
     test_for::<i64>(i64::MAX - 1);
     test_for::<i64>(i64::MAX);
 
-    test_for::<f64>(0.3);
-    test_for::<f64>(-0.3);
-    test_for::<f64>(0.123456789);
-    test_for::<f64>(-0.123456789);
-    test_for::<f64>(9.87654321);
-    test_for::<f64>(-9.87654321);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f64>(0.3);
+        test_for::<f64>(-0.3);
+        test_for::<f64>(0.123456789);
+        test_for::<f64>(-0.123456789);
+        test_for::<f64>(9.87654321);
+        test_for::<f64>(-9.87654321);
+    }
 }
 
 #[test]
@@ -598,6 +624,7 @@ fn i64imm32_reg() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn f64imm32_reg() {
     fn test_for_kind(kind: SelectKind, value: f64) {
         let result = Register::from_i16(2);
@@ -692,10 +719,13 @@ fn both_imm32() {
     test_for::<i32>(i32::MIN + 1, i32::MAX - 1);
     test_for::<i32>(i32::MIN, i32::MAX);
 
-    test_for::<f32>(0.0, 1.0);
-    test_for::<f32>(0.3, -0.3);
-    test_for::<f32>(f32::NEG_INFINITY, f32::INFINITY);
-    test_for::<f32>(f32::NAN, f32::EPSILON);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f32>(0.0, 1.0);
+        test_for::<f32>(0.3, -0.3);
+        test_for::<f32>(f32::NEG_INFINITY, f32::INFINITY);
+        test_for::<f32>(f32::NAN, f32::EPSILON);
+    }
 }
 
 #[test]
@@ -734,8 +764,11 @@ fn both_imm() {
     test_for::<i64>(i64::from(i32::MIN) - 1, i64::from(i32::MAX) + 1);
     test_for::<i64>(i64::MIN, i64::MAX);
 
-    test_for::<f64>(0.3, -0.3);
-    test_for::<f64>(0.123456789, -0.987654321);
+    #[cfg(feature = "float")]
+    {
+        test_for::<f64>(0.3, -0.3);
+        test_for::<f64>(0.123456789, -0.987654321);
+    }
 }
 
 #[test]
@@ -771,6 +804,7 @@ fn both_i64imm32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn both_f64imm32() {
     fn test_for_kind(kind: SelectKind, lhs: f64, rhs: f64) {
         let result = Register::from_i16(1);
@@ -3,7 +3,9 @@
 use super::*;
 use crate::core::UntypedValue;
 
+#[cfg(feature = "float")]
 mod f32_store;
+#[cfg(feature = "float")]
 mod f64_store;
 mod i32_store;
 mod i32_store16;
@@ -1,9 +1,16 @@
 use super::*;
 
+#[cfg(feature = "bulk-table")]
 mod table_copy;
+#[cfg(feature = "reference-types")]
 mod table_fill;
+#[cfg(feature = "reference-types")]
 mod table_get;
+#[cfg(feature = "reference-types")]
 mod table_grow;
+#[cfg(feature = "bulk-table")]
 mod table_init;
+#[cfg(feature = "reference-types")]
 mod table_set;
+#[cfg(feature = "reference-types")]
 mod table_size;
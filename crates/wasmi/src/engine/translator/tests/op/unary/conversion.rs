@@ -75,6 +75,7 @@ mod i64_extend_i32_u {
     }
 }
 
+#[cfg(feature = "float")]
 macro_rules! signed_trunc_tests {
     (
         $(
@@ -117,6 +118,7 @@ macro_rules! signed_trunc_tests {
         )*
     };
 }
+#[cfg(feature = "float")]
 signed_trunc_tests! {
     fn i32_trunc_f32_s("trunc_f32_s", f32, i32);
     fn i32_trunc_f64_s("trunc_f64_s", f64, i32);
@@ -124,6 +126,7 @@ signed_trunc_tests! {
     fn i64_trunc_f64_s("trunc_f64_s", f64, i64);
 }
 
+#[cfg(feature = "float")]
 macro_rules! unsigned_trunc_tests {
     (
         $(
@@ -166,6 +169,7 @@ macro_rules! unsigned_trunc_tests {
         )*
     };
 }
+#[cfg(feature = "float")]
 unsigned_trunc_tests! {
     fn i32_trunc_f32_u("trunc_f32_u", f32, i32);
     fn i32_trunc_f64_u("trunc_f64_u", f64, i32);
@@ -173,6 +177,7 @@ unsigned_trunc_tests! {
     fn i64_trunc_f64_u("trunc_f64_u", f64, i64);
 }
 
+#[cfg(feature = "float")]
 macro_rules! trunc_sat_tests {
     (
         $(
@@ -206,6 +211,7 @@ macro_rules! trunc_sat_tests {
         )*
     };
 }
+#[cfg(feature = "float")]
 trunc_sat_tests! {
     fn i32_trunc_sat_f32_s("trunc_sat_f32_s", f32, i32);
     fn i32_trunc_sat_f32_u("trunc_sat_f32_u", f32, i32);
@@ -217,6 +223,7 @@ trunc_sat_tests! {
     fn i64_trunc_sat_f64_u("trunc_sat_f64_u", f64, i64);
 }
 
+#[cfg(feature = "float")]
 macro_rules! convert_tests {
     (
         $(
@@ -249,6 +256,7 @@ macro_rules! convert_tests {
         )*
     };
 }
+#[cfg(feature = "float")]
 convert_tests! {
     fn f32_convert_i32_s("convert_i32_s", i32, f32);
     fn f32_convert_i32_u("convert_i32_u", i32, f32);
@@ -260,6 +268,7 @@ convert_tests! {
     fn f64_convert_i64_u("convert_i64_u", i64, f64);
 }
 
+#[cfg(feature = "float")]
 mod f32_demote_f64 {
     use super::*;
     const OP: &str = "demote_f64";
@@ -286,6 +295,7 @@ mod f32_demote_f64 {
     }
 }
 
+#[cfg(feature = "float")]
 mod f64_promote_f32 {
     use super::*;
     const OP: &str = "promote_f32";
@@ -312,6 +322,7 @@ mod f64_promote_f32 {
     }
 }
 
+#[cfg(feature = "float")]
 macro_rules! iN_reinterpret_fN_tests {
     ( $( fn $name:ident($op:literal, $input_ty:ty, $output_ty:ty); )* ) => {
         $(
@@ -341,11 +352,13 @@ macro_rules! iN_reinterpret_fN_tests {
         )*
     }
 }
+#[cfg(feature = "float")]
 iN_reinterpret_fN_tests! {
     fn i32_reinterpret_f32("reinterpret_f32", f32, i32);
     fn i64_reinterpret_f64("reinterpret_f64", f64, i64);
 }
 
+#[cfg(feature = "float")]
 macro_rules! fN_reinterpret_iN_tests {
     ( $( fn $name:ident($op:literal, $input_ty:ty, $output_ty:ty); )* ) => {
         $(
@@ -374,6 +387,7 @@ macro_rules! fN_reinterpret_iN_tests {
         )*
     }
 }
+#[cfg(feature = "float")]
 fN_reinterpret_iN_tests! {
     fn f32_reinterpret_i32("reinterpret_i32", i32, f32);
     fn f64_reinterpret_i64("reinterpret_i64", i64, f64);
@@ -7,7 +7,10 @@ use super::*;
 use crate::engine::translator::tests::driver::{ExpectedFunc, TranslationTest};
 use std::fmt::Display;
 use wasm_type::WasmType;
-use wasmi_core::{TrapCode, UntypedValue};
+use wasmi_core::UntypedValue;
+
+#[cfg(feature = "float")]
+use wasmi_core::TrapCode;
 
 /// Asserts that the unary Wasm operator `wasm_op` translates properly to a unary Wasmi instruction.
 fn conversion_reg_with<I, O, E>(wasm_op: &str, expected: E)
@@ -98,6 +101,7 @@ where
 }
 
 /// Asserts that the unary Wasm operator `wasm_op` translates properly to a unary Wasmi instruction.
+#[cfg(feature = "float")]
 fn fallible_conversion_imm_err<I, O>(wasm_op: &str, input: I, eval: fn(input: I) -> TrapCode)
 where
     I: WasmType,
@@ -96,6 +96,7 @@ mod i64_popcnt {
     }
 }
 
+#[cfg(feature = "float")]
 mod f32_abs {
     use super::*;
 
@@ -115,6 +116,7 @@ mod f32_abs {
     }
 }
 
+#[cfg(feature = "float")]
 mod f32_neg {
     use super::*;
 
@@ -135,6 +137,7 @@ mod f32_neg {
     }
 }
 
+#[cfg(feature = "float")]
 mod f32_ceil {
     use super::*;
 
@@ -154,6 +157,7 @@ mod f32_ceil {
     }
 }
 
+#[cfg(feature = "float")]
 mod f32_floor {
     use super::*;
 
@@ -173,6 +177,7 @@ mod f32_floor {
     }
 }
 
+#[cfg(feature = "float")]
 mod f32_trunc {
     use super::*;
 
@@ -192,6 +197,7 @@ mod f32_trunc {
     }
 }
 
+#[cfg(feature = "float")]
 mod f32_nearest {
     use super::*;
     use wasmi_core::UntypedValue;
@@ -226,6 +232,7 @@ mod f32_nearest {
     }
 }
 
+#[cfg(feature = "float")]
 mod f32_sqrt {
     use super::*;
 
@@ -245,6 +252,7 @@ mod f32_sqrt {
     }
 }
 
+#[cfg(feature = "float")]
 mod f64_abs {
     use super::*;
 
@@ -264,6 +272,7 @@ mod f64_abs {
     }
 }
 
+#[cfg(feature = "float")]
 mod f64_neg {
     use super::*;
 
@@ -284,6 +293,7 @@ mod f64_neg {
     }
 }
 
+#[cfg(feature = "float")]
 mod f64_ceil {
     use super::*;
 
@@ -303,6 +313,7 @@ mod f64_ceil {
     }
 }
 
+#[cfg(feature = "float")]
 mod f64_floor {
     use super::*;
 
@@ -322,6 +333,7 @@ mod f64_floor {
     }
 }
 
+#[cfg(feature = "float")]
 mod f64_trunc {
     use super::*;
 
@@ -341,6 +353,7 @@ mod f64_trunc {
     }
 }
 
+#[cfg(feature = "float")]
 mod f64_nearest {
     use super::*;
     use wasmi_core::UntypedValue;
@@ -375,6 +388,7 @@ mod f64_nearest {
     }
 }
 
+#[cfg(feature = "float")]
 mod f64_sqrt {
     use super::*;
 
@@ -6,10 +6,12 @@ use crate::{
         CompiledFunc,
     },
 };
+#[cfg(feature = "float")]
 use wasmi_core::F32;
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn fuzz_regression_0() {
     let wat = include_str!("fuzz_0.wat");
     let wasm = wat2wasm(wat);
@@ -24,6 +26,7 @@ fn fuzz_regression_0() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn fuzz_regression_1() {
     let wat = include_str!("fuzz_1.wat");
     let wasm = wat2wasm(wat);
@@ -235,6 +238,7 @@ fn fuzz_regression_11() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn fuzz_regression_12_f32() {
     let wat = include_str!("fuzz_12_f32.wat");
     let wasm = wat2wasm(wat);
@@ -264,6 +268,7 @@ fn fuzz_regression_12_f32() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn fuzz_regression_12_f64() {
     let wat = include_str!("fuzz_12_f64.wat");
     let wasm = wat2wasm(wat);
@@ -357,6 +362,7 @@ fn fuzz_regression_14() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn fuzz_regression_15_01_codegen() {
     let wat = include_str!("fuzz_15_01.wat");
     let wasm = wat2wasm(wat);
@@ -382,6 +388,7 @@ fn fuzz_regression_15_01_codegen() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn fuzz_regression_15_01_execute() {
     // Note: we can remove this test case once the bug is fixed
     //       since this is a codegen bug and not an executor bug.
@@ -408,6 +415,7 @@ fn fuzz_regression_15_01_execute() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn fuzz_regression_15_02() {
     let wat = include_str!("fuzz_15_02.wat");
     let wasm = wat2wasm(wat);
@@ -493,6 +501,7 @@ fn fuzz_regression_15_03() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn fuzz_regression_16() {
     // The bug in this regression test was a forgotten adjustment
     // for the preserved local value causing the `value` register
@@ -512,6 +521,7 @@ fn fuzz_regression_16() {
 
 #[test]
 #[cfg_attr(miri, ignore)]
+#[cfg(feature = "float")]
 fn fuzz_regression_17() {
     // The bug in this regression test was a forgotten adjustment
     // for the preserved local value causing the `value` register
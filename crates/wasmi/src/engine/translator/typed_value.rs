@@ -182,13 +182,9 @@ impl TypedValue {
 
         fn i32_eq(i32, i32) -> i32;
         fn i64_eq(i64, i64) -> i32;
-        fn f32_eq(f32, f32) -> i32;
-        fn f64_eq(f64, f64) -> i32;
 
         fn i32_ne(i32, i32) -> i32;
         fn i64_ne(i64, i64) -> i32;
-        fn f32_ne(f32, f32) -> i32;
-        fn f64_ne(f64, f64) -> i32;
 
         fn i32_lt_s(i32, i32) -> i32;
         fn i32_lt_u(i32, i32) -> i32;
@@ -208,16 +204,6 @@ impl TypedValue {
         fn i64_ge_s(i64, i64) -> i32;
         fn i64_ge_u(i64, i64) -> i32;
 
-        fn f32_lt(f32, f32) -> i32;
-        fn f32_gt(f32, f32) -> i32;
-        fn f32_le(f32, f32) -> i32;
-        fn f32_ge(f32, f32) -> i32;
-
-        fn f64_lt(f64, f64) -> i32;
-        fn f64_gt(f64, f64) -> i32;
-        fn f64_le(f64, f64) -> i32;
-        fn f64_ge(f64, f64) -> i32;
-
         // Integer Arithmetic Instructions
 
         fn i32_clz(i32) -> i32;
@@ -270,6 +256,41 @@ impl TypedValue {
         fn i64_or(i64, i64) -> i64;
         fn i64_xor(i64, i64) -> i64;
 
+        // Conversions
+
+        fn i32_wrap_i64(i64) -> i32;
+        fn i64_extend_i32_s(i32) -> i64;
+        fn i64_extend_i32_u(i32) -> i64;
+
+        fn i32_extend8_s(i32) -> i32;
+        fn i32_extend16_s(i32) -> i32;
+        fn i64_extend8_s(i64) -> i64;
+        fn i64_extend16_s(i64) -> i64;
+        fn i64_extend32_s(i64) -> i64;
+    }
+}
+
+#[cfg(feature = "float")]
+impl TypedValue {
+    impl_forwarding! {
+        // Comparison Instructions
+
+        fn f32_eq(f32, f32) -> i32;
+        fn f64_eq(f64, f64) -> i32;
+
+        fn f32_ne(f32, f32) -> i32;
+        fn f64_ne(f64, f64) -> i32;
+
+        fn f32_lt(f32, f32) -> i32;
+        fn f32_gt(f32, f32) -> i32;
+        fn f32_le(f32, f32) -> i32;
+        fn f32_ge(f32, f32) -> i32;
+
+        fn f64_lt(f64, f64) -> i32;
+        fn f64_gt(f64, f64) -> i32;
+        fn f64_le(f64, f64) -> i32;
+        fn f64_ge(f64, f64) -> i32;
+
         // Float Arithmetic Instructions
 
         fn f32_abs(f32) -> f32;
@@ -306,10 +327,6 @@ impl TypedValue {
 
         // Conversions
 
-        fn i32_wrap_i64(i64) -> i32;
-        fn i64_extend_i32_s(i32) -> i64;
-        fn i64_extend_i32_u(i32) -> i64;
-
         fn f32_demote_f64(f64) -> f32;
         fn f64_promote_f32(f32) -> f64;
 
@@ -339,11 +356,5 @@ impl TypedValue {
         fn f64_convert_i32_u(i32) -> f64;
         fn f64_convert_i64_s(i64) -> f64;
         fn f64_convert_i64_u(i64) -> f64;
-
-        fn i32_extend8_s(i32) -> i32;
-        fn i32_extend16_s(i32) -> i32;
-        fn i64_extend8_s(i64) -> i64;
-        fn i64_extend16_s(i64) -> i64;
-        fn i64_extend32_s(i64) -> i64;
     }
 }
@@ -1,9 +1,12 @@
 use super::{stack::ValueStack, TypedProvider, TypedValue};
 use crate::{
-    engine::bytecode::{AnyConst16, Const16, Provider, Register, RegisterSpanIter, Sign},
+    engine::bytecode::{AnyConst16, Const16, Provider, Register, RegisterSpanIter},
     Error,
 };
 
+#[cfg(feature = "float")]
+use crate::engine::bytecode::Sign;
+
 /// A WebAssembly integer. Either `i32` or `i64`.
 ///
 /// # Note
@@ -72,6 +75,7 @@ impl WasmInteger for u64 {
 /// # Note
 ///
 /// This trait provides some utility methods useful for translation.
+#[cfg(feature = "float")]
 pub trait WasmFloat: Copy + Into<TypedValue> + From<TypedValue> {
     /// Returns `true` if `self` is any kind of NaN value.
     fn is_nan(self) -> bool;
@@ -80,6 +84,7 @@ pub trait WasmFloat: Copy + Into<TypedValue> + From<TypedValue> {
     fn sign(self) -> Sign;
 }
 
+#[cfg(feature = "float")]
 impl WasmFloat for f32 {
     fn is_nan(self) -> bool {
         self.is_nan()
@@ -93,6 +98,7 @@ impl WasmFloat for f32 {
     }
 }
 
+#[cfg(feature = "float")]
 impl WasmFloat for f64 {
     fn is_nan(self) -> bool {
         self.is_nan()
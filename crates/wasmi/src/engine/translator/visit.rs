@@ -21,19 +21,27 @@ use crate::{
         bytecode::{self, Const16, Instruction, Provider, Register, SignatureIdx},
         translator::AcquiredTarget,
         BlockType,
+        DiagnosticKind,
         FuelCosts,
     },
     module::{self, FuncIdx, WasmiValueType},
     Error,
-    ExternRef,
-    FuncRef,
     Mutability,
 };
 use alloc::collections::BTreeMap;
 use core::num::{NonZeroU32, NonZeroU64};
-use wasmi_core::{TrapCode, ValueType, F32, F64};
+use wasmi_core::{TrapCode, ValueType};
 use wasmparser::VisitOperator;
 
+#[cfg(feature = "float")]
+use wasmi_core::{F32, F64};
+
+#[cfg(feature = "reference-types")]
+use crate::{ExternRef, FuncRef};
+
+#[cfg(not(all(feature = "reference-types", feature = "bulk-table")))]
+use super::TranslationError;
+
 /// Used to swap operands of a `rev` variant [`Instruction`] constructor.
 macro_rules! swap_ops {
     ($fn_name:path) => {
@@ -76,6 +84,41 @@ macro_rules! impl_visit_operator {
     () => {};
 }
 
+/// Implements the visitors of all Wasm `f32` and `f64` operators as unsupported.
+///
+/// # Note
+///
+/// Used if the `float` crate feature is disabled which compiles out all Wasm floating point instructions.
+/// Those Wasm operators are rejected by the Wasm validation before their translation.
+#[cfg(not(feature = "float"))]
+macro_rules! impl_visit_float_operator {
+    ( $( fn $visit:ident($($argty:ty)?); )* ) => {
+        $(
+            fn $visit(&mut self $(, _: $argty)?) -> Self::Output {
+                self.unsupported_operator(stringify!($visit))
+            }
+        )*
+    };
+}
+
+/// Implements the visitors of Wasm operators of proposals that are compiled out.
+///
+/// # Note
+///
+/// Used if the `reference-types` or `bulk-table` crate features are disabled.
+/// Translating any of those Wasm operators fails with [`TranslationError::UnsupportedOperator`].
+#[cfg(not(all(feature = "reference-types", feature = "bulk-table")))]
+macro_rules! impl_visit_disabled_operator {
+    ( $( #[$attr:meta] fn $visit:ident($($argty:ty),*) => $op:literal; )* ) => {
+        $(
+            #[$attr]
+            fn $visit(&mut self $(, _: $argty)*) -> Self::Output {
+                Err(Error::from(TranslationError::unsupported_operator($op)))
+            }
+        )*
+    };
+}
+
 impl FuncTranslator {
     /// Called when translating an unsupported Wasm operator.
     ///
@@ -95,6 +138,112 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     wasmparser::for_each_operator!(impl_visit_operator);
 
+    #[cfg(not(feature = "float"))]
+    impl_visit_float_operator! {
+        fn visit_f32_load(wasmparser::MemArg);
+        fn visit_f64_load(wasmparser::MemArg);
+        fn visit_f32_store(wasmparser::MemArg);
+        fn visit_f64_store(wasmparser::MemArg);
+        fn visit_f32_const(wasmparser::Ieee32);
+        fn visit_f64_const(wasmparser::Ieee64);
+        fn visit_f32_eq();
+        fn visit_f32_ne();
+        fn visit_f32_lt();
+        fn visit_f32_gt();
+        fn visit_f32_le();
+        fn visit_f32_ge();
+        fn visit_f64_eq();
+        fn visit_f64_ne();
+        fn visit_f64_lt();
+        fn visit_f64_gt();
+        fn visit_f64_le();
+        fn visit_f64_ge();
+        fn visit_f32_abs();
+        fn visit_f32_neg();
+        fn visit_f32_ceil();
+        fn visit_f32_floor();
+        fn visit_f32_trunc();
+        fn visit_f32_nearest();
+        fn visit_f32_sqrt();
+        fn visit_f32_add();
+        fn visit_f32_sub();
+        fn visit_f32_mul();
+        fn visit_f32_div();
+        fn visit_f32_min();
+        fn visit_f32_max();
+        fn visit_f32_copysign();
+        fn visit_f64_abs();
+        fn visit_f64_neg();
+        fn visit_f64_ceil();
+        fn visit_f64_floor();
+        fn visit_f64_trunc();
+        fn visit_f64_nearest();
+        fn visit_f64_sqrt();
+        fn visit_f64_add();
+        fn visit_f64_sub();
+        fn visit_f64_mul();
+        fn visit_f64_div();
+        fn visit_f64_min();
+        fn visit_f64_max();
+        fn visit_f64_copysign();
+        fn visit_i32_trunc_f32_s();
+        fn visit_i32_trunc_f32_u();
+        fn visit_i32_trunc_f64_s();
+        fn visit_i32_trunc_f64_u();
+        fn visit_i64_trunc_f32_s();
+        fn visit_i64_trunc_f32_u();
+        fn visit_i64_trunc_f64_s();
+        fn visit_i64_trunc_f64_u();
+        fn visit_f32_convert_i32_s();
+        fn visit_f32_convert_i32_u();
+        fn visit_f32_convert_i64_s();
+        fn visit_f32_convert_i64_u();
+        fn visit_f32_demote_f64();
+        fn visit_f64_convert_i32_s();
+        fn visit_f64_convert_i32_u();
+        fn visit_f64_convert_i64_s();
+        fn visit_f64_convert_i64_u();
+        fn visit_f64_promote_f32();
+        fn visit_i32_reinterpret_f32();
+        fn visit_i64_reinterpret_f64();
+        fn visit_f32_reinterpret_i32();
+        fn visit_f64_reinterpret_i64();
+        fn visit_i32_trunc_sat_f32_s();
+        fn visit_i32_trunc_sat_f32_u();
+        fn visit_i32_trunc_sat_f64_s();
+        fn visit_i32_trunc_sat_f64_u();
+        fn visit_i64_trunc_sat_f32_s();
+        fn visit_i64_trunc_sat_f32_u();
+        fn visit_i64_trunc_sat_f64_s();
+        fn visit_i64_trunc_sat_f64_u();
+    }
+
+    #[cfg(not(all(feature = "reference-types", feature = "bulk-table")))]
+    impl_visit_disabled_operator! {
+        #[cfg(not(feature = "reference-types"))]
+        fn visit_ref_null(wasmparser::ValType) => "ref.null";
+        #[cfg(not(feature = "reference-types"))]
+        fn visit_ref_is_null() => "ref.is_null";
+        #[cfg(not(feature = "reference-types"))]
+        fn visit_ref_func(u32) => "ref.func";
+        #[cfg(not(feature = "reference-types"))]
+        fn visit_table_get(u32) => "table.get";
+        #[cfg(not(feature = "reference-types"))]
+        fn visit_table_set(u32) => "table.set";
+        #[cfg(not(feature = "reference-types"))]
+        fn visit_table_size(u32) => "table.size";
+        #[cfg(not(feature = "reference-types"))]
+        fn visit_table_grow(u32) => "table.grow";
+        #[cfg(not(feature = "reference-types"))]
+        fn visit_table_fill(u32) => "table.fill";
+        #[cfg(not(feature = "bulk-table"))]
+        fn visit_table_copy(u32, u32) => "table.copy";
+        #[cfg(not(feature = "bulk-table"))]
+        fn visit_table_init(u32, u32) => "table.init";
+        #[cfg(not(feature = "bulk-table"))]
+        fn visit_elem_drop(u32) => "elem.drop";
+    }
+
     fn visit_unreachable(&mut self) -> Self::Output {
         bail_unreachable!(self);
         self.push_base_instr(Instruction::Trap(TrapCode::UnreachableCodeReached))?;
@@ -187,6 +336,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             branch_params,
             consume_fuel,
         ));
+        self.push_epoch_check()?;
         Ok(())
     }
 
@@ -267,10 +417,14 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                 //
                 // The [`Instruction::ConsumeFuel`] for the `else` branch is
                 // created on the fly when visiting the `else` block.
-                let fuel_instr = self.make_fuel_instr()?;
+                let fuel_instr = match self.has_branch_fuel() {
+                    true => self.make_fuel_instr()?,
+                    false => self.fuel_instr(),
+                };
                 (reachability, fuel_instr)
             }
         };
+        let is_branch = matches!(reachability, IfReachability::Both { .. });
         self.alloc.control_stack.push_frame(IfControlFrame::new(
             block_type,
             end_label,
@@ -279,6 +433,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             fuel_instr,
             reachability,
         ));
+        if is_branch {
+            self.push_branch_epoch_check()?;
+        }
         Ok(())
     }
 
@@ -302,6 +459,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         if frame.is_then_reachable() {
             frame.update_end_of_then_reachability(self.reachable);
         }
+        let is_branch = frame.else_label().is_some();
         if let Some(else_label) = frame.else_label() {
             // Case: the `if` control frame has reachable `then` and `else` branches.
             debug_assert!(frame.is_then_reachable());
@@ -319,8 +477,10 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             }
             self.reachable = true;
             self.alloc.instr_encoder.pin_label(else_label);
-            if let Some(fuel_instr) = self.make_fuel_instr()? {
-                frame.update_consume_fuel_instr(fuel_instr);
+            if self.has_branch_fuel() {
+                if let Some(fuel_instr) = self.make_fuel_instr()? {
+                    frame.update_consume_fuel_instr(fuel_instr);
+                }
             }
             // At this point we can restore the `else` branch parameters
             // so that the `else` branch translation has the same set of
@@ -362,6 +522,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         }
         // At last we need to push the popped and adjusted [`IfControlFrame`] back.
         self.alloc.control_stack.push_frame(frame);
+        if is_branch {
+            self.push_branch_epoch_check()?;
+        }
         Ok(())
     }
 
@@ -518,6 +681,15 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             self.alloc.br_table_targets.push(target?);
         }
         self.alloc.br_table_targets.push(default_target);
+        let len_targets = self.alloc.br_table_targets.len();
+        let len_runs = self.len_br_table_runs();
+        // Large `br_table`s with few runs of equal consecutive targets are encoded
+        // sparsely to decrease code size while keeping dispatch cheap.
+        let is_sparse = Self::is_sparse_br_table(len_targets, len_runs);
+        let br_table = match is_sparse {
+            true => Instruction::branch_table_sparse(index, len_runs as u32),
+            false => Instruction::branch_table(index, targets.len() + 1),
+        };
         // We check if all `br_table` targets expect their results at the same
         // registers which allows us to encode the `br_table` more efficiently
         // by using a single copy instruction before branching instead of having
@@ -547,8 +719,11 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             // In both cases it is sufficient to copy values to the destination of
             // the default branch target and encode the `br_table` with a series of
             // simple direct branches without any further copy instructions.
-            self.push_base_instr(Instruction::branch_table(index, targets.len() + 1))?;
+            self.push_base_instr(br_table)?;
             self.translate_copy_branch_params(default_branch_params)?;
+            if is_sparse {
+                self.encode_br_table_run_bounds()?;
+            }
             let return_instr = match default_branch_params.len() {
                 0 => Instruction::Return,
                 1 => Instruction::return_reg(default_branch_params.span().head()),
@@ -580,7 +755,11 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         //
         // Since `br_table` target depths are often shared we use a btree-set to
         // share codegen for `br_table` arms that have the same branch target.
-        self.push_base_instr(Instruction::branch_table(index, targets.len() + 1))?;
+        self.emit_diagnostic(DiagnosticKind::BranchTableSlowPath { len_targets });
+        self.push_base_instr(br_table)?;
+        if is_sparse {
+            self.encode_br_table_run_bounds()?;
+        }
         let mut shared_targets = <BTreeMap<u32, LabelRef>>::new();
         for target in self.alloc.br_table_targets.iter().copied() {
             let shared_label = *shared_targets
@@ -651,6 +830,15 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                     _ => Instruction::call_internal(results, compiled_func),
                 }
             }
+            None if self.module.is_intrinsic(func_idx) => {
+                // Case: We are calling an imported host intrinsic which is called
+                //       directly without the generic host call machinery if the
+                //       import resolves to the intrinsic upon instantiation.
+                match params.len() {
+                    0 => Instruction::call_intrinsic_0(results, function_index),
+                    _ => Instruction::call_intrinsic(results, function_index),
+                }
+            }
             None => {
                 // Case: We are calling an imported function and must use the
                 //       general calling operator for it.
@@ -818,6 +1006,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             }
         }
         let preserved = self.alloc.stack.preserve_locals(local_index)?;
+        if preserved.is_some() {
+            self.emit_diagnostic(DiagnosticKind::PreservedLocal { local_index });
+        }
         let fuel_info = self.fuel_info();
         self.alloc.instr_encoder.encode_local_set(
             &mut self.alloc.stack,
@@ -934,6 +1125,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_load(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
         self.translate_load(
             memarg,
@@ -943,6 +1135,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_load(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
         self.translate_load(
             memarg,
@@ -1064,6 +1257,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_store(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
         self.translate_fstore(
             memarg,
@@ -1073,6 +1267,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_store(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
         self.translate_fstore(
             memarg,
@@ -1183,6 +1378,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_const(&mut self, value: wasmparser::Ieee32) -> Self::Output {
         bail_unreachable!(self);
         self.alloc.stack.push_const(F32::from_bits(value.bits()));
@@ -1190,6 +1386,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_const(&mut self, value: wasmparser::Ieee64) -> Self::Output {
         bail_unreachable!(self);
         self.alloc.stack.push_const(F64::from_bits(value.bits()));
@@ -1197,6 +1394,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(feature = "reference-types")]
     fn visit_ref_null(&mut self, ty: wasmparser::ValType) -> Self::Output {
         bail_unreachable!(self);
         let type_hint = WasmiValueType::from(ty).into_inner();
@@ -1210,12 +1408,14 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(feature = "reference-types")]
     fn visit_ref_is_null(&mut self) -> Self::Output {
         // Note: Since `funcref` and `externref` both serialize to `UntypedValue`
         //       as raw `u64` values we can use `i64.eqz` translation for `ref.is_null`.
         self.visit_i64_eqz()
     }
 
+    #[cfg(feature = "reference-types")]
     fn visit_ref_func(&mut self, function_index: u32) -> Self::Output {
         bail_unreachable!(self);
         let result = self.alloc.stack.push_dynamic()?;
@@ -1840,6 +2040,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_eq(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f32>(
             Instruction::f32_eq,
@@ -1856,6 +2057,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_ne(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f32>(
             Instruction::f32_ne,
@@ -1872,6 +2074,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_lt(&mut self) -> Self::Output {
         self.translate_fbinary(
             Instruction::f32_lt,
@@ -1913,6 +2116,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_gt(&mut self) -> Self::Output {
         self.translate_fbinary(
             Instruction::f32_gt,
@@ -1954,6 +2158,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_le(&mut self) -> Self::Output {
         self.translate_fbinary(
             Instruction::f32_le,
@@ -1978,6 +2183,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_ge(&mut self) -> Self::Output {
         self.translate_fbinary(
             Instruction::f32_ge,
@@ -2002,6 +2208,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_eq(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f64>(
             Instruction::f64_eq,
@@ -2018,6 +2225,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_ne(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f64>(
             Instruction::f64_ne,
@@ -2034,6 +2242,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_lt(&mut self) -> Self::Output {
         self.translate_fbinary(
             Instruction::f64_lt,
@@ -2075,6 +2284,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_gt(&mut self) -> Self::Output {
         self.translate_fbinary(
             Instruction::f64_gt,
@@ -2116,6 +2326,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_le(&mut self) -> Self::Output {
         self.translate_fbinary(
             Instruction::f64_le,
@@ -2140,6 +2351,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_ge(&mut self) -> Self::Output {
         self.translate_fbinary(
             Instruction::f64_ge,
@@ -2780,34 +2992,42 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_abs(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f32_abs, TypedValue::f32_abs)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_neg(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f32_neg, TypedValue::f32_neg)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_ceil(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f32_ceil, TypedValue::f32_ceil)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_floor(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f32_floor, TypedValue::f32_floor)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_trunc(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f32_trunc, TypedValue::f32_trunc)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_nearest(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f32_nearest, TypedValue::f32_nearest)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_sqrt(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f32_sqrt, TypedValue::f32_sqrt)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_add(&mut self) -> Self::Output {
         self.translate_fbinary_commutative(
             Instruction::f32_add,
@@ -2817,6 +3037,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_sub(&mut self) -> Self::Output {
         self.translate_fbinary(
             Instruction::f32_sub,
@@ -2830,6 +3051,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_mul(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f32>(
             Instruction::f32_mul,
@@ -2842,6 +3064,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_div(&mut self) -> Self::Output {
         self.translate_fbinary::<f32>(
             Instruction::f32_div,
@@ -2852,6 +3075,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_min(&mut self) -> Self::Output {
         self.translate_fbinary_commutative(
             Instruction::f32_min,
@@ -2868,6 +3092,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_max(&mut self) -> Self::Output {
         self.translate_fbinary_commutative(
             Instruction::f32_max,
@@ -2884,6 +3109,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_copysign(&mut self) -> Self::Output {
         self.translate_fcopysign::<f32>(
             Instruction::f32_copysign,
@@ -2892,34 +3118,42 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_abs(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f64_abs, TypedValue::f64_abs)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_neg(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f64_neg, TypedValue::f64_neg)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_ceil(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f64_ceil, TypedValue::f64_ceil)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_floor(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f64_floor, TypedValue::f64_floor)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_trunc(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f64_trunc, TypedValue::f64_trunc)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_nearest(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f64_nearest, TypedValue::f64_nearest)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_sqrt(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f64_sqrt, TypedValue::f64_sqrt)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_add(&mut self) -> Self::Output {
         self.translate_fbinary_commutative(
             Instruction::f64_add,
@@ -2929,6 +3163,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_sub(&mut self) -> Self::Output {
         self.translate_fbinary(
             Instruction::f64_sub,
@@ -2942,6 +3177,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_mul(&mut self) -> Self::Output {
         self.translate_fbinary_commutative::<f64>(
             Instruction::f64_mul,
@@ -2954,6 +3190,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_div(&mut self) -> Self::Output {
         self.translate_fbinary::<f64>(
             Instruction::f64_div,
@@ -2964,6 +3201,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_min(&mut self) -> Self::Output {
         self.translate_fbinary_commutative(
             Instruction::f64_min,
@@ -2980,6 +3218,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_max(&mut self) -> Self::Output {
         self.translate_fbinary_commutative(
             Instruction::f64_max,
@@ -2996,6 +3235,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_copysign(&mut self) -> Self::Output {
         self.translate_fcopysign::<f64>(
             Instruction::f64_copysign,
@@ -3008,18 +3248,22 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.translate_unary(Instruction::i32_wrap_i64, TypedValue::i32_wrap_i64)
     }
 
+    #[cfg(feature = "float")]
     fn visit_i32_trunc_f32_s(&mut self) -> Self::Output {
         self.translate_unary_fallible(Instruction::i32_trunc_f32_s, TypedValue::i32_trunc_f32_s)
     }
 
+    #[cfg(feature = "float")]
     fn visit_i32_trunc_f32_u(&mut self) -> Self::Output {
         self.translate_unary_fallible(Instruction::i32_trunc_f32_u, TypedValue::i32_trunc_f32_u)
     }
 
+    #[cfg(feature = "float")]
     fn visit_i32_trunc_f64_s(&mut self) -> Self::Output {
         self.translate_unary_fallible(Instruction::i32_trunc_f64_s, TypedValue::i32_trunc_f64_s)
     }
 
+    #[cfg(feature = "float")]
     fn visit_i32_trunc_f64_u(&mut self) -> Self::Output {
         self.translate_unary_fallible(Instruction::i32_trunc_f64_u, TypedValue::i32_trunc_f64_u)
     }
@@ -3032,22 +3276,27 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.translate_unary(Instruction::i64_extend_i32_u, TypedValue::i64_extend_i32_u)
     }
 
+    #[cfg(feature = "float")]
     fn visit_i64_trunc_f32_s(&mut self) -> Self::Output {
         self.translate_unary_fallible(Instruction::i64_trunc_f32_s, TypedValue::i64_trunc_f32_s)
     }
 
+    #[cfg(feature = "float")]
     fn visit_i64_trunc_f32_u(&mut self) -> Self::Output {
         self.translate_unary_fallible(Instruction::i64_trunc_f32_u, TypedValue::i64_trunc_f32_u)
     }
 
+    #[cfg(feature = "float")]
     fn visit_i64_trunc_f64_s(&mut self) -> Self::Output {
         self.translate_unary_fallible(Instruction::i64_trunc_f64_s, TypedValue::i64_trunc_f64_s)
     }
 
+    #[cfg(feature = "float")]
     fn visit_i64_trunc_f64_u(&mut self) -> Self::Output {
         self.translate_unary_fallible(Instruction::i64_trunc_f64_u, TypedValue::i64_trunc_f64_u)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_convert_i32_s(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::f32_convert_i32_s,
@@ -3055,6 +3304,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_convert_i32_u(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::f32_convert_i32_u,
@@ -3062,6 +3312,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_convert_i64_s(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::f32_convert_i64_s,
@@ -3069,6 +3320,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_convert_i64_u(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::f32_convert_i64_u,
@@ -3076,10 +3328,12 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_demote_f64(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f32_demote_f64, TypedValue::f32_demote_f64)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_convert_i32_s(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::f64_convert_i32_s,
@@ -3087,6 +3341,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_convert_i32_u(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::f64_convert_i32_u,
@@ -3094,6 +3349,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_convert_i64_s(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::f64_convert_i64_s,
@@ -3101,6 +3357,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_convert_i64_u(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::f64_convert_i64_u,
@@ -3108,22 +3365,27 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_promote_f32(&mut self) -> Self::Output {
         self.translate_unary(Instruction::f64_promote_f32, TypedValue::f64_promote_f32)
     }
 
+    #[cfg(feature = "float")]
     fn visit_i32_reinterpret_f32(&mut self) -> Self::Output {
         self.translate_reinterpret(ValueType::I32)
     }
 
+    #[cfg(feature = "float")]
     fn visit_i64_reinterpret_f64(&mut self) -> Self::Output {
         self.translate_reinterpret(ValueType::I64)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f32_reinterpret_i32(&mut self) -> Self::Output {
         self.translate_reinterpret(ValueType::F32)
     }
 
+    #[cfg(feature = "float")]
     fn visit_f64_reinterpret_i64(&mut self) -> Self::Output {
         self.translate_reinterpret(ValueType::F64)
     }
@@ -3148,6 +3410,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.translate_unary(Instruction::i64_extend32_s, TypedValue::i64_extend32_s)
     }
 
+    #[cfg(feature = "float")]
     fn visit_i32_trunc_sat_f32_s(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::i32_trunc_sat_f32_s,
@@ -3155,6 +3418,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_i32_trunc_sat_f32_u(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::i32_trunc_sat_f32_u,
@@ -3162,6 +3426,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_i32_trunc_sat_f64_s(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::i32_trunc_sat_f64_s,
@@ -3169,6 +3434,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_i32_trunc_sat_f64_u(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::i32_trunc_sat_f64_u,
@@ -3176,6 +3442,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_i64_trunc_sat_f32_s(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::i64_trunc_sat_f32_s,
@@ -3183,6 +3450,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_i64_trunc_sat_f32_u(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::i64_trunc_sat_f32_u,
@@ -3190,6 +3458,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_i64_trunc_sat_f64_s(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::i64_trunc_sat_f64_s,
@@ -3197,6 +3466,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
+    #[cfg(feature = "float")]
     fn visit_i64_trunc_sat_f64_u(&mut self) -> Self::Output {
         self.translate_unary(
             Instruction::i64_trunc_sat_f64_u,
@@ -3321,6 +3591,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(feature = "bulk-table")]
     fn visit_table_init(&mut self, elem_index: u32, table: u32) -> Self::Output {
         bail_unreachable!(self);
         let (dst, src, len) = self.alloc.stack.pop3();
@@ -3363,12 +3634,14 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(feature = "bulk-table")]
     fn visit_elem_drop(&mut self, elem_index: u32) -> Self::Output {
         bail_unreachable!(self);
         self.push_fueled_instr(Instruction::ElemDrop(elem_index.into()), FuelCosts::entity)?;
         Ok(())
     }
 
+    #[cfg(feature = "bulk-table")]
     fn visit_table_copy(&mut self, dst_table: u32, src_table: u32) -> Self::Output {
         bail_unreachable!(self);
         let (dst, src, len) = self.alloc.stack.pop3();
@@ -3411,6 +3684,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(feature = "reference-types")]
     fn visit_table_fill(&mut self, table: u32) -> Self::Output {
         bail_unreachable!(self);
         let (dst, value, len) = self.alloc.stack.pop3();
@@ -3441,6 +3715,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(feature = "reference-types")]
     fn visit_table_get(&mut self, table: u32) -> Self::Output {
         bail_unreachable!(self);
         let index = self.alloc.stack.pop();
@@ -3462,6 +3737,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(feature = "reference-types")]
     fn visit_table_set(&mut self, table: u32) -> Self::Output {
         bail_unreachable!(self);
         let (index, value) = self.alloc.stack.pop2();
@@ -3480,6 +3756,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(feature = "reference-types")]
     fn visit_table_grow(&mut self, table: u32) -> Self::Output {
         bail_unreachable!(self);
         let (value, delta) = self.alloc.stack.pop2();
@@ -3512,6 +3789,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         Ok(())
     }
 
+    #[cfg(feature = "reference-types")]
     fn visit_table_size(&mut self, table: u32) -> Self::Output {
         bail_unreachable!(self);
         let result = self.alloc.stack.push_dynamic()?;
@@ -35,18 +35,22 @@ impl VisitInputRegisters for Instruction {
     #[rustfmt::skip]
     fn visit_input_registers(&mut self, mut f: impl FnMut(&mut Register)) {
         match self {
-            Instruction::TableIdx(_) |
             Instruction::DataSegmentIdx(_) |
-            Instruction::ElementSegmentIdx(_) |
             Instruction::Const32(_) |
             Instruction::I64Const32(_) |
             Instruction::F64Const32(_) => {},
+            #[cfg(not(all(not(feature = "reference-types"), not(feature = "bulk-table"))))]
+            Instruction::TableIdx(_) => {},
+            #[cfg(feature = "bulk-table")]
+            Instruction::ElementSegmentIdx(_) => {},
             Instruction::Register(register) => f(register),
             Instruction::Register2(registers) => registers.visit_input_registers(f),
             Instruction::Register3(registers) |
             Instruction::RegisterList(registers) => registers.visit_input_registers(f),
             Instruction::Trap(_) |
             Instruction::ConsumeFuel(_) |
+            Instruction::ConsumeInstrs(_) |
+            Instruction::CheckEpoch |
             Instruction::Return => {},
             Instruction::ReturnReg { value } => f(value),
             Instruction::ReturnReg2 { values } => values.visit_input_registers(f),
@@ -79,6 +83,7 @@ impl VisitInputRegisters for Instruction {
             }
             Instruction::Branch { .. } => {},
             Instruction::BranchTable { index, .. } => f(index),
+            Instruction::BranchTableSparse { index, .. } => f(index),
 
             Instruction::BranchCmpFallback { lhs, rhs, .. } => visit_registers!(f, lhs, rhs),
             Instruction::BranchI32And(instr) => instr.visit_input_registers(f),
@@ -133,17 +138,29 @@ impl VisitInputRegisters for Instruction {
             Instruction::BranchI64GeSImm(instr) => instr.visit_input_registers(f),
             Instruction::BranchI64GeU(instr) => instr.visit_input_registers(f),
             Instruction::BranchI64GeUImm(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF32Eq(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF32Ne(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF32Lt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF32Le(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF32Gt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF32Ge(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF64Eq(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF64Ne(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF64Lt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF64Le(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF64Gt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::BranchF64Ge(instr) => instr.visit_input_registers(f),
 
             Instruction::Copy { result, value } => {
@@ -187,6 +204,8 @@ impl VisitInputRegisters for Instruction {
             Instruction::CallInternal { .. } |
             Instruction::CallImported0 { .. } |
             Instruction::CallImported { .. } |
+            Instruction::CallIntrinsic0 { .. } |
+            Instruction::CallIntrinsic { .. } |
             Instruction::CallIndirect0 { .. } |
             Instruction::CallIndirect { .. } => {},
             Instruction::Select { condition, lhs, .. } => visit_registers!(f, condition, lhs),
@@ -194,34 +213,60 @@ impl VisitInputRegisters for Instruction {
             Instruction::SelectImm32 { result_or_condition, .. } |
             Instruction::SelectI64Imm32 { result_or_condition, .. } |
             Instruction::SelectF64Imm32 { result_or_condition, .. } => f(result_or_condition),
+            #[cfg(feature = "reference-types")]
             Instruction::RefFunc { .. } |
             Instruction::TableGet { .. } |
             Instruction::TableGetImm { .. } |
             Instruction::TableSize { .. } => {},
+            #[cfg(feature = "reference-types")]
             Instruction::TableSet { index, value } => visit_registers!(f, index, value),
+            #[cfg(feature = "reference-types")]
             Instruction::TableSetAt { value, .. } => f(value),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableCopy { dst, src, len } => visit_registers!(f, dst, src, len),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableCopyTo { dst: _, src, len } => visit_registers!(f, src, len),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableCopyFrom { dst, src: _, len } => visit_registers!(f, dst, len),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableCopyFromTo { dst: _, src: _, len } => f(len),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableCopyExact { dst, src, len: _ } => visit_registers!(f, dst, src),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableCopyToExact { dst: _, src, len: _ } => f(src),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableCopyFromExact { dst, src: _, len: _ } => f(dst),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableCopyFromToExact { dst: _, src: _, len: _ } => {},
+            #[cfg(feature = "bulk-table")]
             Instruction::TableInit { dst, src, len } => visit_registers!(f, dst, src, len),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableInitTo { dst: _, src, len } => visit_registers!(f, src, len),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableInitFrom { dst, src: _, len } => visit_registers!(f, dst, len),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableInitFromTo { dst: _, src: _, len } => f(len),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableInitExact { dst, src, len: _ } => visit_registers!(f, dst, src),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableInitToExact { dst: _, src, len: _ } => f(src),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableInitFromExact { dst, src: _, len: _ } => f(dst),
+            #[cfg(feature = "bulk-table")]
             Instruction::TableInitFromToExact { dst: _, src: _, len: _ } => {},
+            #[cfg(feature = "reference-types")]
             Instruction::TableFill { dst, len, value } => visit_registers!(f, dst, len, value),
+            #[cfg(feature = "reference-types")]
             Instruction::TableFillAt { dst: _, len, value } => visit_registers!(f, len, value),
+            #[cfg(feature = "reference-types")]
             Instruction::TableFillExact { dst, len: _, value } => visit_registers!(f, dst, value),
+            #[cfg(feature = "reference-types")]
             Instruction::TableFillAtExact { dst: _, len: _, value } => f(value),
+            #[cfg(feature = "reference-types")]
             Instruction::TableGrow { result: _, delta, value } => visit_registers!(f, delta, value),
+            #[cfg(feature = "reference-types")]
             Instruction::TableGrowImm { result: _, delta: _, value } => f(value),
+            #[cfg(feature = "bulk-table")]
             Instruction::ElemDrop(_) => {}
             Instruction::DataDrop(_) => {}
             Instruction::MemorySize { result: _ } => {},
@@ -261,11 +306,17 @@ impl VisitInputRegisters for Instruction {
             Instruction::I64Load(instr) => instr.visit_input_registers(f),
             Instruction::I64LoadAt(instr) => instr.visit_input_registers(f),
             Instruction::I64LoadOffset16(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Load(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32LoadAt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32LoadOffset16(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Load(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64LoadAt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64LoadOffset16(instr) => instr.visit_input_registers(f),
             Instruction::I32Load8s(instr) => instr.visit_input_registers(f),
             Instruction::I32Load8sAt(instr) => instr.visit_input_registers(f),
@@ -332,11 +383,17 @@ impl VisitInputRegisters for Instruction {
             Instruction::I64Store32Offset16Imm16(instr) => instr.visit_input_registers(f),
             Instruction::I64Store32At(instr) => instr.visit_input_registers(f),
             Instruction::I64Store32AtImm16(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Store(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32StoreOffset16(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32StoreAt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Store(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64StoreOffset16(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64StoreAt(instr) => instr.visit_input_registers(f),
             Instruction::I32Eq(instr) => instr.visit_input_registers(f),
             Instruction::I32EqImm16(instr) => instr.visit_input_registers(f),
@@ -378,17 +435,29 @@ impl VisitInputRegisters for Instruction {
             Instruction::I64GeU(instr) => instr.visit_input_registers(f),
             Instruction::I64GeSImm16(instr) => instr.visit_input_registers(f),
             Instruction::I64GeUImm16(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Eq(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Eq(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Ne(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Ne(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Lt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Lt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Le(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Le(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Gt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Gt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Ge(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Ge(instr) => instr.visit_input_registers(f),
             Instruction::I32Clz(instr) => instr.visit_input_registers(f),
             Instruction::I64Clz(instr) => instr.visit_input_registers(f),
@@ -482,69 +551,125 @@ impl VisitInputRegisters for Instruction {
             Instruction::I64RotrImm(instr) => instr.visit_input_registers(f),
             Instruction::I32RotrImm16Rev(instr) => instr.visit_input_registers(f),
             Instruction::I64RotrImm16Rev(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Abs(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Abs(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Neg(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Neg(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Ceil(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Ceil(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Floor(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Floor(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Trunc(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Trunc(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Nearest(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Nearest(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Sqrt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Sqrt(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Add(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Add(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Sub(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Sub(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Mul(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Mul(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Div(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Div(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Min(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Min(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Max(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Max(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32Copysign(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64Copysign(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32CopysignImm(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64CopysignImm(instr) => instr.visit_input_registers(f),
             Instruction::I32WrapI64(instr) => instr.visit_input_registers(f),
             Instruction::I64ExtendI32S(instr) => instr.visit_input_registers(f),
             Instruction::I64ExtendI32U(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I32TruncF32S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I32TruncF32U(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I32TruncF64S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I32TruncF64U(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I64TruncF32S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I64TruncF32U(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I64TruncF64S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I64TruncF64U(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I32TruncSatF32S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I32TruncSatF32U(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I32TruncSatF64S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I32TruncSatF64U(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I64TruncSatF32S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I64TruncSatF32U(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I64TruncSatF64S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::I64TruncSatF64U(instr) => instr.visit_input_registers(f),
             Instruction::I32Extend8S(instr) => instr.visit_input_registers(f),
             Instruction::I32Extend16S(instr) => instr.visit_input_registers(f),
             Instruction::I64Extend8S(instr) => instr.visit_input_registers(f),
             Instruction::I64Extend16S(instr) => instr.visit_input_registers(f),
             Instruction::I64Extend32S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32DemoteF64(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64PromoteF32(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32ConvertI32S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32ConvertI32U(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32ConvertI64S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F32ConvertI64U(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64ConvertI32S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64ConvertI32U(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64ConvertI64S(instr) => instr.visit_input_registers(f),
+            #[cfg(feature = "float")]
             Instruction::F64ConvertI64U(instr) => instr.visit_input_registers(f),
         }
     }
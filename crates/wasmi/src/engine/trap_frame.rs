@@ -0,0 +1,87 @@
+use super::WasmBacktrace;
+use crate::{core::UntypedValue, Error, Instance};
+
+/// The Wasm call frame in which a trap has been raised.
+///
+/// Passed to the hook installed via [`Store::set_trap_hook`].
+///
+/// # Note
+///
+/// The [`TrapFrame`] is observed before the call stack is torn down
+/// and therefore allows to inspect the state of the trapping function.
+///
+/// [`Store::set_trap_hook`]: crate::Store::set_trap_hook
+#[derive(Debug)]
+pub struct TrapFrame<'a> {
+    /// The raised trap.
+    error: &'a Error,
+    /// The instance of the trapping function.
+    instance: Instance,
+    /// The number of Wasm call frames on the call stack including the trapping one.
+    depth: usize,
+    /// The registers of the trapping function.
+    registers: &'a [UntypedValue],
+    /// The Wasm call frames at the time of the trap.
+    backtrace: WasmBacktrace,
+}
+
+impl<'a> TrapFrame<'a> {
+    /// Creates a new [`TrapFrame`].
+    pub(crate) fn new(
+        error: &'a Error,
+        instance: Instance,
+        depth: usize,
+        registers: &'a [UntypedValue],
+        backtrace: WasmBacktrace,
+    ) -> Self {
+        Self {
+            error,
+            instance,
+            depth,
+            registers,
+            backtrace,
+        }
+    }
+
+    /// Returns the raised trap.
+    ///
+    /// # Note
+    ///
+    /// Use [`Error::func_index`] and [`Error::wasm_offset`] to query
+    /// the location of the trap within its Wasm module.
+    pub fn error(&self) -> &'a Error {
+        self.error
+    }
+
+    /// Returns the [`Instance`] of the trapping function.
+    pub fn instance(&self) -> Instance {
+        self.instance
+    }
+
+    /// Returns the number of Wasm call frames on the call stack including the trapping one.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the values of the parameters, locals and temporaries of the trapping function.
+    ///
+    /// # Note
+    ///
+    /// The layout of the registers is an implementation detail of the
+    /// Wasmi bytecode and may change between versions. The parameters of
+    /// the trapping function always occupy the first registers.
+    pub fn registers(&self) -> &'a [UntypedValue] {
+        self.registers
+    }
+
+    /// Returns the [`WasmBacktrace`] of the Wasm call frames at the time of the trap.
+    ///
+    /// # Note
+    ///
+    /// The innermost frame is the trapping function and its Wasm offset is the one
+    /// of the trapping instruction. Wasm offsets are only available if
+    /// [`Config::generate_address_maps`](crate::Config::generate_address_maps) is enabled.
+    pub fn backtrace(&self) -> &WasmBacktrace {
+        &self.backtrace
+    }
+}
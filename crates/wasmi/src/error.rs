@@ -1,39 +1,238 @@
 use super::errors::{
+    ArtifactError,
+    ConstExprError,
     FuelError,
     FuncError,
     GlobalError,
     InstantiationError,
+    IntrinsicError,
     LinkerError,
     MemoryError,
+    PreinitError,
     TableError,
+    ValueError,
 };
 use crate::{
-    core::{HostError, TrapCode},
+    core::{HostError, TrapCode, ValueType},
     engine::TranslationError,
     module::ReadError,
 };
 use alloc::{boxed::Box, string::String};
 use core::{fmt, fmt::Display};
+#[cfg(feature = "compiler")]
 use wasmparser::BinaryReaderError as WasmError;
 
 /// The generic Wasmi root error type.
 #[derive(Debug)]
 pub struct Error {
     /// The underlying kind of the error and its specific information.
-    kind: Box<ErrorKind>,
+    repr: ErrorRepr,
+}
+
+/// The representation of an [`Error`].
+#[derive(Debug)]
+enum ErrorRepr {
+    /// A [`TrapCode`] error that is stored inline and thus never heap allocates.
+    ///
+    /// # Note
+    ///
+    /// Its [`ErrorKind`] is one of the shared [`TRAP_CODE_ERRORS`].
+    TrapCode {
+        /// The trap code of the error.
+        trap_code: TrapCode,
+        /// The location within Wasm code at which the trap occurred if any.
+        location: Option<TrapLocation>,
+    },
+    /// Any other error with its heap allocated contents.
+    Boxed(Box<ErrorInner>),
+}
+
+/// The contents of a heap allocated [`Error`].
+#[derive(Debug)]
+struct ErrorInner {
+    /// The underlying kind of the error and its specific information.
+    kind: ErrorKind,
+    /// The location within Wasm code at which the error occurred if any.
+    location: Option<TrapLocation>,
+    /// Additional information about the failed operation if any.
+    context: Option<ErrorContext>,
+}
+
+/// The shared [`ErrorKind`] of all inline [`TrapCode`] errors indexed by their discriminant.
+static TRAP_CODE_ERRORS: [ErrorKind; 14] = [
+    ErrorKind::TrapCode(TrapCode::UnreachableCodeReached),
+    ErrorKind::TrapCode(TrapCode::MemoryOutOfBounds),
+    ErrorKind::TrapCode(TrapCode::TableOutOfBounds),
+    ErrorKind::TrapCode(TrapCode::IndirectCallToNull),
+    ErrorKind::TrapCode(TrapCode::IntegerDivisionByZero),
+    ErrorKind::TrapCode(TrapCode::IntegerOverflow),
+    ErrorKind::TrapCode(TrapCode::BadConversionToInteger),
+    ErrorKind::TrapCode(TrapCode::StackOverflow),
+    ErrorKind::TrapCode(TrapCode::BadSignature),
+    ErrorKind::TrapCode(TrapCode::OutOfFuel),
+    ErrorKind::TrapCode(TrapCode::OutOfInstructions),
+    ErrorKind::TrapCode(TrapCode::Interrupted),
+    ErrorKind::TrapCode(TrapCode::GrowthOperationLimited),
+    ErrorKind::TrapCode(TrapCode::MemoryWriteProtected),
+];
+
+/// The location within Wasm code at which a trap occurred.
+#[derive(Debug, Copy, Clone)]
+struct TrapLocation {
+    /// The index of the trapping function within its Wasm module.
+    func_index: u32,
+    /// The offset of the trapping Wasm instruction within the Wasm module if known.
+    wasm_offset: Option<u32>,
+}
+
+/// Additional information about the operation that failed with an [`Error`].
+#[derive(Debug)]
+enum ErrorContext {
+    /// The Wasm function translation that failed with the error.
+    Translation(TranslationLocation),
+    /// The mismatching function parameter or result of a function type check.
+    TypeMismatch(TypeMismatch),
+}
+
+/// The location within Wasm code at which a Wasm function translation failed.
+#[derive(Debug)]
+struct TranslationLocation {
+    /// The index of the Wasm function that failed to translate within its Wasm module.
+    func_index: u32,
+    /// The offset within the Wasm module at which the translation failed.
+    wasm_offset: u32,
+    /// The name of the Wasm operator that failed to translate if any.
+    operator: Option<Box<str>>,
+}
+
+/// A function parameter or result whose type did not match its [`FuncType`].
+///
+/// [`FuncType`]: crate::FuncType
+#[derive(Debug)]
+struct TypeMismatch {
+    /// The index of the mismatching parameter or result.
+    index: usize,
+    /// The name of the mismatching parameter or result if any.
+    name: Option<Box<str>>,
+    /// The type required by the function type.
+    expected: ValueType,
+    /// The type that was provided.
+    actual: ValueType,
 }
 
 #[test]
 fn error_size() {
     use core::mem;
-    assert_eq!(mem::size_of::<Error>(), 8);
+    assert_eq!(mem::size_of::<Error>(), 16);
+    assert_eq!(mem::size_of::<Result<(), Error>>(), 16);
+}
+
+#[test]
+fn trap_code_errors_do_not_allocate() {
+    let trap_codes = [
+        TrapCode::UnreachableCodeReached,
+        TrapCode::MemoryOutOfBounds,
+        TrapCode::TableOutOfBounds,
+        TrapCode::IndirectCallToNull,
+        TrapCode::IntegerDivisionByZero,
+        TrapCode::IntegerOverflow,
+        TrapCode::BadConversionToInteger,
+        TrapCode::StackOverflow,
+        TrapCode::BadSignature,
+        TrapCode::OutOfFuel,
+        TrapCode::OutOfInstructions,
+        TrapCode::Interrupted,
+        TrapCode::GrowthOperationLimited,
+        TrapCode::MemoryWriteProtected,
+    ];
+    assert_eq!(trap_codes.len(), TRAP_CODE_ERRORS.len());
+    for trap_code in trap_codes {
+        let error = Error::from(trap_code).with_trap_location(1, Some(2));
+        assert!(matches!(error.repr, ErrorRepr::TrapCode { .. }));
+        assert_eq!(error.as_trap_code(), Some(trap_code));
+        assert_eq!(error.func_index(), Some(1));
+        assert_eq!(error.wasm_offset(), Some(2));
+    }
 }
 
 impl Error {
     /// Creates a new [`Error`] from the [`ErrorKind`].
     fn from_kind(kind: ErrorKind) -> Self {
+        Self::from_inner(ErrorInner {
+            kind,
+            location: None,
+            context: None,
+        })
+    }
+
+    /// Creates a new heap allocated [`Error`] from its `inner` contents.
+    fn from_inner(inner: ErrorInner) -> Self {
         Self {
-            kind: Box::new(kind),
+            repr: ErrorRepr::Boxed(Box::new(inner)),
+        }
+    }
+
+    /// Creates a new [`Error`] from the [`TrapCode`].
+    ///
+    /// # Note
+    ///
+    /// This does not heap allocate if the [`TrapCode`] is one of the shared [`TRAP_CODE_ERRORS`].
+    fn from_trap_code(trap_code: TrapCode) -> Self {
+        match TRAP_CODE_ERRORS.get(trap_code as usize) {
+            Some(kind) if kind.as_trap_code() == Some(trap_code) => Self {
+                repr: ErrorRepr::TrapCode {
+                    trap_code,
+                    location: None,
+                },
+            },
+            _ => Self::from_kind(ErrorKind::TrapCode(trap_code)),
+        }
+    }
+
+    /// Returns an exclusive reference to the heap allocated contents of the [`Error`].
+    ///
+    /// # Note
+    ///
+    /// Inline [`TrapCode`] errors are moved to the heap first.
+    fn inner_mut(&mut self) -> &mut ErrorInner {
+        if let ErrorRepr::TrapCode {
+            trap_code,
+            location,
+        } = self.repr
+        {
+            *self = Self::from_inner(ErrorInner {
+                kind: ErrorKind::TrapCode(trap_code),
+                location,
+                context: None,
+            });
+        }
+        match &mut self.repr {
+            ErrorRepr::Boxed(inner) => inner,
+            ErrorRepr::TrapCode { .. } => unreachable!("inline trap codes have been boxed above"),
+        }
+    }
+
+    /// Returns the location within Wasm code at which the [`Error`] occurred if any.
+    fn location(&self) -> Option<TrapLocation> {
+        match &self.repr {
+            ErrorRepr::TrapCode { location, .. } => *location,
+            ErrorRepr::Boxed(inner) => inner.location,
+        }
+    }
+
+    /// Returns the additional information about the failed operation if any.
+    fn context(&self) -> Option<&ErrorContext> {
+        match &self.repr {
+            ErrorRepr::TrapCode { .. } => None,
+            ErrorRepr::Boxed(inner) => inner.context.as_ref(),
+        }
+    }
+
+    /// Returns the Wasm function translation that failed with the [`Error`] if any.
+    fn translation(&self) -> Option<&TranslationLocation> {
+        match self.context()? {
+            ErrorContext::Translation(translation) => Some(translation),
+            ErrorContext::TypeMismatch(_) => None,
         }
     }
 
@@ -68,9 +267,138 @@ impl Error {
         Self::from_kind(ErrorKind::I32ExitStatus(status))
     }
 
+    /// Creates a new [`Error`] for a caught panic of a host function described by `message`.
+    #[cold]
+    #[cfg(feature = "std")]
+    pub(crate) fn host_panic<T>(message: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::from_kind(ErrorKind::HostPanic(message.into().into_boxed_str()))
+    }
+
     /// Returns the [`ErrorKind`] of the [`Error`].
     pub fn kind(&self) -> &ErrorKind {
-        &self.kind
+        match &self.repr {
+            ErrorRepr::TrapCode { trap_code, .. } => &TRAP_CODE_ERRORS[*trap_code as usize],
+            ErrorRepr::Boxed(inner) => &inner.kind,
+        }
+    }
+
+    /// Annotates the [`Error`] with the Wasm location at which it occurred.
+    ///
+    /// # Note
+    ///
+    /// - Does nothing if the [`Error`] already has a location.
+    /// - This does not heap allocate for [`TrapCode`] errors.
+    pub(crate) fn with_trap_location(mut self, func_index: u32, wasm_offset: Option<u32>) -> Self {
+        let location = match &mut self.repr {
+            ErrorRepr::TrapCode { location, .. } => location,
+            ErrorRepr::Boxed(inner) => &mut inner.location,
+        };
+        if location.is_none() {
+            *location = Some(TrapLocation {
+                func_index,
+                wasm_offset,
+            });
+        }
+        self
+    }
+
+    /// Annotates the [`Error`] with the Wasm function translation that failed with it.
+    ///
+    /// # Note
+    ///
+    /// - Does nothing if the [`Error`] already has a translation location.
+    /// - The `operator` is the name of the Wasm operator that failed to translate if any.
+    pub(crate) fn with_translation_location(
+        mut self,
+        func_index: u32,
+        wasm_offset: usize,
+        operator: Option<Box<str>>,
+    ) -> Self {
+        let inner = self.inner_mut();
+        if inner.context.is_none() {
+            inner.context = Some(ErrorContext::Translation(TranslationLocation {
+                func_index,
+                wasm_offset: u32::try_from(wasm_offset).unwrap_or(u32::MAX),
+                operator,
+            }));
+        }
+        self
+    }
+
+    /// Annotates the [`Error`] with the function parameter or result that failed a type check.
+    ///
+    /// # Note
+    ///
+    /// The `name` is the name of the parameter or result in its [`FuncType`] if any.
+    ///
+    /// [`FuncType`]: crate::FuncType
+    pub(crate) fn with_type_mismatch(
+        mut self,
+        index: usize,
+        name: Option<&str>,
+        expected: ValueType,
+        actual: ValueType,
+    ) -> Self {
+        self.inner_mut().context = Some(ErrorContext::TypeMismatch(TypeMismatch {
+            index,
+            name: name.map(Into::into),
+            expected,
+            actual,
+        }));
+        self
+    }
+
+    /// Returns the index of the Wasm function in which the [`Error`] occurred if any.
+    ///
+    /// # Note
+    ///
+    /// - The index refers to the function index space of the Wasm module
+    ///   that defines the trapping function and includes imported functions.
+    /// - This is also available for errors of failed Wasm function translations.
+    pub fn func_index(&self) -> Option<u32> {
+        match self.translation() {
+            Some(translation) => Some(translation.func_index),
+            None => self.location().map(|location| location.func_index),
+        }
+    }
+
+    /// Returns the offset of the trapping Wasm instruction within its Wasm module if any.
+    ///
+    /// # Note
+    ///
+    /// - For traps this is only available if [`Config::generate_address_maps`]
+    ///   was enabled for the [`Engine`] that compiled the trapping function.
+    /// - For errors of failed Wasm function translations this is the offset
+    ///   of the Wasm operator that failed to translate.
+    ///
+    /// [`Config::generate_address_maps`]: crate::Config::generate_address_maps
+    /// [`Engine`]: crate::Engine
+    pub fn wasm_offset(&self) -> Option<u32> {
+        match self.translation() {
+            Some(translation) => Some(translation.wasm_offset),
+            None => self.location().and_then(|location| location.wasm_offset),
+        }
+    }
+
+    /// Returns the name of the Wasm operator that failed to translate if any.
+    ///
+    /// # Note
+    ///
+    /// This is only available for errors of failed Wasm function translations
+    /// that occurred while translating a Wasm operator, e.g. `i32.add`.
+    pub fn wasm_operator(&self) -> Option<&str> {
+        self.translation()
+            .and_then(|translation| translation.operator.as_deref())
+    }
+
+    /// Returns the stable numeric code of the [`Error`].
+    ///
+    /// See [`ErrorKind::code`] for more information.
+    pub fn code(&self) -> u16 {
+        self.kind().code()
     }
 
     /// Returns a reference to [`TrapCode`] if [`Error`] is a [`TrapCode`].
@@ -93,7 +421,7 @@ impl Error {
     where
         T: HostError,
     {
-        self.kind
+        self.kind()
             .as_host()
             .and_then(<(dyn HostError + 'static)>::downcast_ref)
     }
@@ -106,7 +434,11 @@ impl Error {
     where
         T: HostError,
     {
-        self.kind
+        let ErrorRepr::Boxed(inner) = &mut self.repr else {
+            return None;
+        };
+        inner
+            .kind
             .as_host_mut()
             .and_then(<(dyn HostError + 'static)>::downcast_mut)
     }
@@ -119,7 +451,11 @@ impl Error {
     where
         T: HostError,
     {
-        self.kind
+        let ErrorRepr::Boxed(inner) = self.repr else {
+            return None;
+        };
+        inner
+            .kind
             .into_host()
             .and_then(|error| error.downcast().ok())
             .map(|boxed| *boxed)
@@ -131,7 +467,36 @@ impl std::error::Error for Error {}
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Display::fmt(&self.kind, f)
+        if let Some(translation) = self.translation() {
+            let TranslationLocation {
+                func_index,
+                wasm_offset,
+                operator,
+            } = translation;
+            write!(
+                f,
+                "failed to translate Wasm function {func_index} at offset 0x{wasm_offset:x}"
+            )?;
+            if let Some(operator) = operator {
+                write!(f, " ({operator})")?;
+            }
+            write!(f, ": ")?;
+        }
+        Display::fmt(self.kind(), f)?;
+        if let Some(ErrorContext::TypeMismatch(mismatch)) = self.context() {
+            let TypeMismatch {
+                index,
+                name,
+                expected,
+                actual,
+            } = mismatch;
+            write!(f, " at index {index}")?;
+            if let Some(name) = name {
+                write!(f, " (`{name}`)")?;
+            }
+            write!(f, ": expected {expected:?} but found {actual:?}")?;
+        }
+        Ok(())
     }
 }
 
@@ -147,6 +512,14 @@ pub enum ErrorKind {
     I32ExitStatus(i32),
     /// A trap as defined by the WebAssembly specification.
     Host(Box<dyn HostError>),
+    /// A panic of a host function that has been caught and converted into a trap.
+    ///
+    /// # Note
+    ///
+    /// This is only raised if [`Config::host_panic_as_trap`] is enabled.
+    ///
+    /// [`Config::host_panic_as_trap`]: crate::Config::host_panic_as_trap
+    HostPanic(Box<str>),
     /// A global variable error.
     Global(GlobalError),
     /// A linear memory error.
@@ -164,12 +537,228 @@ pub enum ErrorKind {
     /// Encountered when there is a problem with the Wasm input stream.
     Read(ReadError),
     /// Encountered when there is a Wasm parsing or validation error.
+    #[cfg(feature = "compiler")]
     Wasm(WasmError),
     /// Encountered when there is a Wasm to Wasmi translation error.
     Translation(TranslationError),
+    /// Encountered when a precompiled artifact is malformed or incompatible.
+    Artifact(ArtifactError),
+    /// Encountered when defining a host intrinsic fails.
+    Intrinsic(IntrinsicError),
+    /// Encountered when pre-initializing a module fails.
+    Preinit(PreinitError),
+    /// Encountered when converting [`Value`]s into Rust types fails.
+    ///
+    /// [`Value`]: crate::Value
+    Value(ValueError),
+    /// Encountered when parsing a [`ConstExpr`] fails.
+    ///
+    /// [`ConstExpr`]: crate::ConstExpr
+    ConstExpr(ConstExprError),
 }
 
 impl ErrorKind {
+    /// Returns the stable numeric code of the [`ErrorKind`].
+    ///
+    /// # Note
+    ///
+    /// The high byte of the code identifies the category of the error
+    /// and the low byte identifies the specific error within its category:
+    ///
+    /// | Category | Code |
+    /// |:--|:--|
+    /// | [`ErrorKind::Message`] | `0x0001` |
+    /// | [`ErrorKind::I32ExitStatus`] | `0x0002` |
+    /// | [`ErrorKind::Host`] | `0x0003` |
+    /// | [`ErrorKind::HostPanic`] | `0x0004` |
+    /// | [`ErrorKind::TrapCode`] | `0x01XX` |
+    /// | [`ErrorKind::Global`] | `0x02XX` |
+    /// | [`ErrorKind::Memory`] | `0x03XX` |
+    /// | [`ErrorKind::Table`] | `0x04XX` |
+    /// | [`ErrorKind::Linker`] | `0x05XX` |
+    /// | [`ErrorKind::Instantiation`] | `0x06XX` |
+    /// | [`ErrorKind::Fuel`] | `0x07XX` |
+    /// | [`ErrorKind::Func`] | `0x08XX` |
+    /// | [`ErrorKind::Read`] | `0x09XX` |
+    /// | [`ErrorKind::Wasm`] | `0x0A00` |
+    /// | [`ErrorKind::Translation`] | `0x0BXX` |
+    /// | [`ErrorKind::Artifact`] | `0x0CXX` |
+    /// | [`ErrorKind::Intrinsic`] | `0x0DXX` |
+    /// | [`ErrorKind::Preinit`] | `0x0EXX` |
+    /// | [`ErrorKind::Value`] | `0x0FXX` |
+    /// | [`ErrorKind::ConstExpr`] | `0x10XX` |
+    ///
+    /// Codes are stable across Wasmi versions and are never reused.
+    /// This allows `no_std` embedders and FFI layers to match on errors
+    /// without formatting them.
+    pub fn code(&self) -> u16 {
+        let (category, code): (u8, u8) = match self {
+            Self::Message(_) => (0x00, 0x01),
+            Self::I32ExitStatus(_) => (0x00, 0x02),
+            Self::Host(_) => (0x00, 0x03),
+            Self::HostPanic(_) => (0x00, 0x04),
+            Self::TrapCode(error) => (0x01, error.code()),
+            Self::Global(error) => (
+                0x02,
+                match error {
+                    GlobalError::ImmutableWrite => 0x00,
+                    GlobalError::TypeMismatch { .. } => 0x01,
+                    GlobalError::UnsatisfyingGlobalType { .. } => 0x02,
+                },
+            ),
+            Self::Memory(error) => (
+                0x03,
+                match error {
+                    MemoryError::OutOfBoundsAllocation => 0x00,
+                    MemoryError::OutOfBoundsGrowth => 0x01,
+                    MemoryError::OutOfBoundsAccess => 0x02,
+                    MemoryError::InvalidMemoryType => 0x03,
+                    MemoryError::InvalidSubtype { .. } => 0x04,
+                    MemoryError::TooManyMemories => 0x05,
+                    MemoryError::WriteProtected => 0x06,
+                    MemoryError::ResetDisabled => 0x07,
+                },
+            ),
+            Self::Table(error) => (
+                0x04,
+                match error {
+                    TableError::GrowOutOfBounds { .. } => 0x00,
+                    TableError::ElementTypeMismatch { .. } => 0x01,
+                    TableError::AccessOutOfBounds { .. } => 0x02,
+                    TableError::CopyOutOfBounds => 0x03,
+                    TableError::InvalidSubtype { .. } => 0x04,
+                    TableError::TooManyTables => 0x05,
+                },
+            ),
+            Self::Linker(error) => (
+                0x05,
+                match error {
+                    LinkerError::DuplicateDefinition { .. } => 0x00,
+                    LinkerError::MissingDefinition { .. } => 0x01,
+                    LinkerError::InvalidTypeDefinition { .. } => 0x02,
+                    LinkerError::FuncTypeMismatch { .. } => 0x03,
+                    LinkerError::InvalidTableSubtype { .. } => 0x04,
+                    LinkerError::InvalidMemorySubtype { .. } => 0x05,
+                    LinkerError::GlobalTypeMismatch { .. } => 0x06,
+                },
+            ),
+            Self::Instantiation(error) => (
+                0x06,
+                match error {
+                    InstantiationError::ImportsExternalsLenMismatch => 0x00,
+                    InstantiationError::ImportsExternalsMismatch { .. } => 0x01,
+                    InstantiationError::SignatureMismatch { .. } => 0x02,
+                    InstantiationError::Table(_) => 0x03,
+                    InstantiationError::Memory(_) => 0x04,
+                    InstantiationError::Global(_) => 0x05,
+                    InstantiationError::ElementSegmentDoesNotFit { .. } => 0x06,
+                    InstantiationError::FoundStartFn { .. } => 0x07,
+                    InstantiationError::TooManyInstances => 0x08,
+                    InstantiationError::ImportDenied { .. } => 0x09,
+                    InstantiationError::IncompatibleReload => 0x0A,
+                    InstantiationError::ReloadWhileExecuting => 0x0B,
+                    InstantiationError::ResetDisabled => 0x0C,
+                },
+            ),
+            Self::Fuel(error) => (
+                0x07,
+                match error {
+                    FuelError::FuelMeteringDisabled => 0x00,
+                    FuelError::OutOfFuel => 0x01,
+                },
+            ),
+            Self::Func(error) => (
+                0x08,
+                match error {
+                    FuncError::ExportedFuncNotFound => 0x00,
+                    FuncError::MismatchingParameterType => 0x01,
+                    FuncError::MismatchingParameterLen => 0x02,
+                    FuncError::MismatchingResultType => 0x03,
+                    FuncError::MismatchingResultLen => 0x04,
+                    FuncError::MismatchingBatchLen => 0x05,
+                },
+            ),
+            Self::Read(error) => (
+                0x09,
+                match error {
+                    ReadError::EndOfStream => 0x00,
+                    ReadError::UnknownError => 0x01,
+                },
+            ),
+            #[cfg(feature = "compiler")]
+            Self::Wasm(_) => (0x0A, 0x00),
+            Self::Translation(error) => (
+                0x0B,
+                match error {
+                    #[cfg(feature = "compiler")]
+                    TranslationError::UnsupportedBlockType(_) => 0x00,
+                    #[cfg(feature = "compiler")]
+                    TranslationError::UnsupportedValueType(_) => 0x01,
+                    TranslationError::BranchTableTargetsOutOfBounds => 0x02,
+                    TranslationError::BranchOffsetOutOfBounds => 0x03,
+                    TranslationError::BlockFuelOutOfBounds => 0x04,
+                    TranslationError::AllocatedTooManyRegisters => 0x05,
+                    TranslationError::RegisterOutOfBounds => 0x06,
+                    TranslationError::EmulatedValueStackOverflow => 0x07,
+                    TranslationError::ProviderSliceOverflow => 0x08,
+                    TranslationError::TooManyFuncLocalConstValues => 0x09,
+                    TranslationError::TooManyFunctionResults => 0x0A,
+                    TranslationError::TooManyFunctionParams => 0x0B,
+                    TranslationError::LazyCompilationFailed => 0x0C,
+                    TranslationError::CompiledCodeBudgetExceeded => 0x0D,
+                    TranslationError::AllocationFailed => 0x0E,
+                    TranslationError::UnsupportedOperator(_) => 0x0F,
+                    TranslationError::DeniedOperator(_) => 0x10,
+                },
+            ),
+            Self::Artifact(error) => (
+                0x0C,
+                match error {
+                    ArtifactError::InvalidMagic => 0x00,
+                    ArtifactError::Truncated => 0x01,
+                    ArtifactError::FormatMismatch { .. } => 0x02,
+                    ArtifactError::VersionMismatch { .. } => 0x03,
+                    ArtifactError::FeaturesMismatch { .. } => 0x04,
+                    ArtifactError::TargetMismatch { .. } => 0x05,
+                    ArtifactError::Malformed => 0x06,
+                    ArtifactError::ConfigMismatch => 0x07,
+                    ArtifactError::RequiresCompiler => 0x08,
+                    ArtifactError::InvalidPayloadOffset(_) => 0x09,
+                    ArtifactError::InvalidVersion => 0x0A,
+                },
+            ),
+            Self::Intrinsic(error) => (
+                0x0D,
+                match error {
+                    IntrinsicError::TooManyParamsOrResults => 0x00,
+                    IntrinsicError::DuplicateDefinition { .. } => 0x01,
+                },
+            ),
+            Self::Preinit(error) => (
+                0x0E,
+                match error {
+                    PreinitError::UnsupportedGlobalValue { .. } => 0x00,
+                },
+            ),
+            Self::Value(error) => (
+                0x0F,
+                match error {
+                    ValueError::TypeMismatch { .. } => 0x00,
+                    ValueError::LenMismatch { .. } => 0x01,
+                },
+            ),
+            Self::ConstExpr(error) => (
+                0x10,
+                match error {
+                    ConstExprError::UnsupportedOperator { .. } => 0x00,
+                    ConstExprError::TypeMismatch { .. } => 0x01,
+                    ConstExprError::InvalidStackHeight { .. } => 0x02,
+                },
+            ),
+        };
+        u16::from_be_bytes([category, code])
+    }
+
     /// Returns a reference to [`TrapCode`] if [`ErrorKind`] is a [`TrapCode`].
     pub fn as_trap_code(&self) -> Option<TrapCode> {
         match self {
@@ -186,6 +775,14 @@ impl ErrorKind {
         }
     }
 
+    /// Returns the panic message if [`ErrorKind`] is an [`ErrorKind::HostPanic`].
+    pub fn as_host_panic(&self) -> Option<&str> {
+        match self {
+            Self::HostPanic(message) => Some(message),
+            _ => None,
+        }
+    }
+
     /// Returns a dynamic reference to [`HostError`] if [`ErrorKind`] is a [`HostError`].
     pub fn as_host(&self) -> Option<&dyn HostError> {
         match self {
@@ -221,6 +818,7 @@ impl Display for ErrorKind {
             Self::I32ExitStatus(status) => writeln!(f, "Exited with i32 exit status {status}"),
             Self::Message(message) => Display::fmt(message, f),
             Self::Host(error) => Display::fmt(error, f),
+            Self::HostPanic(message) => write!(f, "host function panicked: {message}"),
             Self::Global(error) => Display::fmt(error, f),
             Self::Memory(error) => Display::fmt(error, f),
             Self::Table(error) => Display::fmt(error, f),
@@ -229,12 +827,27 @@ impl Display for ErrorKind {
             Self::Instantiation(error) => Display::fmt(error, f),
             Self::Fuel(error) => Display::fmt(error, f),
             Self::Read(error) => Display::fmt(error, f),
+            #[cfg(feature = "compiler")]
             Self::Wasm(error) => Display::fmt(error, f),
             Self::Translation(error) => Display::fmt(error, f),
+            Self::Artifact(error) => Display::fmt(error, f),
+            Self::Intrinsic(error) => Display::fmt(error, f),
+            Self::Preinit(error) => Display::fmt(error, f),
+            Self::Value(error) => Display::fmt(error, f),
+            Self::ConstExpr(error) => Display::fmt(error, f),
         }
     }
 }
 
+#[cfg(feature = "compiler")]
+impl From<WasmError> for Error {
+    #[inline]
+    #[cold]
+    fn from(error: WasmError) -> Self {
+        Self::from_kind(ErrorKind::Wasm(error))
+    }
+}
+
 macro_rules! impl_from {
     ( $( impl From<$from:ident> for Error::$name:ident );* $(;)? ) => {
         $(
@@ -249,17 +862,28 @@ macro_rules! impl_from {
     }
 }
 impl_from! {
-    impl From<TrapCode> for Error::TrapCode;
     impl From<GlobalError> for Error::Global;
     impl From<MemoryError> for Error::Memory;
     impl From<TableError> for Error::Table;
     impl From<LinkerError> for Error::Linker;
     impl From<InstantiationError> for Error::Instantiation;
     impl From<TranslationError> for Error::Translation;
-    impl From<WasmError> for Error::Wasm;
     impl From<ReadError> for Error::Read;
     impl From<FuelError> for Error::Fuel;
     impl From<FuncError> for Error::Func;
+    impl From<ArtifactError> for Error::Artifact;
+    impl From<IntrinsicError> for Error::Intrinsic;
+    impl From<PreinitError> for Error::Preinit;
+    impl From<ValueError> for Error::Value;
+    impl From<ConstExprError> for Error::ConstExpr;
+}
+
+impl From<TrapCode> for Error {
+    #[inline]
+    #[cold]
+    fn from(error: TrapCode) -> Self {
+        Self::from_trap_code(error)
+    }
 }
 
 /// An error that can occur upon `memory.grow` or `table.grow`.
@@ -1,9 +1,36 @@
 use super::super::{AsContext, AsContextMut, StoreContext, StoreContextMut};
-use crate::{store::FuelError, Engine, Extern, Instance};
+use crate::{
+    engine::{CallStack, WasmBacktrace},
+    store::FuelError,
+    Engine,
+    Extern,
+    Instance,
+};
 
 /// Represents the caller’s context when creating a host function via [`Func::wrap`].
 ///
+/// # Re-entrant Calls
+///
+/// Host functions may call back into Wasm by calling a [`Func`] with their [`Caller`],
+/// for example an export of the calling [`Instance`] found via [`Caller::get_export`].
+///
+/// - **Stack:** The Wasm call frames of all executions that are currently calling host
+///   functions count towards the recursion limit of the re-entrant execution.
+///   Therefore host→Wasm→host→Wasm re-entrancy traps with [`TrapCode::StackOverflow`]
+///   at the same total depth as plain Wasm recursion. Additionally, the number of nested
+///   executions is limited by [`Config::max_reentrancy_depth`] to protect the native stack.
+/// - **Fuel:** Re-entrant executions consume fuel of the same [`Store`]. Running out of
+///   fuel in a re-entrant execution is reported to the calling host function as error.
+/// - **Resumability:** Re-entrant executions via [`Func::call`] never yield or suspend.
+///   Errors returned by the calling host function suspend an outer resumable call as usual
+///   and it can be resumed even if the error originated in a re-entrant execution.
+///
+/// [`Config::max_reentrancy_depth`]: crate::Config::max_reentrancy_depth
+/// [`Func`]: crate::Func
+/// [`Func::call`]: crate::Func::call
 /// [`Func::wrap`]: struct.Func.html#method.wrap
+/// [`Store`]: crate::Store
+/// [`TrapCode::StackOverflow`]: crate::core::TrapCode::StackOverflow
 pub struct Caller<'a, T> {
     ctx: StoreContextMut<'a, T>,
     /// The module instance associated to the call.
@@ -11,17 +38,51 @@ pub struct Caller<'a, T> {
     /// since all Wasm function are associated to a module instance.
     /// This usually is `None` if the host function was called from the host side.
     instance: Option<Instance>,
+    /// The call stack of the Wasm execution that called the host function if any.
+    call_stack: Option<&'a CallStack>,
 }
 
 impl<'a, T> Caller<'a, T> {
     /// Creates a new [`Caller`] from the given store context and [`Instance`] handle.
-    pub(crate) fn new<C>(ctx: &'a mut C, instance: Option<&Instance>) -> Self
+    pub(crate) fn new<C>(
+        ctx: &'a mut C,
+        instance: Option<&Instance>,
+        call_stack: Option<&'a CallStack>,
+    ) -> Self
     where
         C: AsContextMut<UserState = T>,
     {
         Self {
             ctx: ctx.as_context_mut(),
             instance: instance.copied(),
+            call_stack,
+        }
+    }
+
+    /// Returns the [`Instance`] associated to the call if any.
+    pub(crate) fn instance(&self) -> Option<&Instance> {
+        self.instance.as_ref()
+    }
+
+    /// Returns the [`CallStack`] of the Wasm execution that called the host function if any.
+    pub(crate) fn call_stack(&self) -> Option<&'a CallStack> {
+        self.call_stack
+    }
+
+    /// Captures the [`WasmBacktrace`] of the Wasm functions that led to the host function call.
+    ///
+    /// # Note
+    ///
+    /// - The backtrace is empty if the host function was called from the host side.
+    /// - Wasm bytecode offsets are only available if [`Config::generate_address_maps`] is enabled.
+    ///
+    /// [`Config::generate_address_maps`]: crate::Config::generate_address_maps
+    pub fn backtrace(&self) -> WasmBacktrace {
+        match self.call_stack {
+            Some(call_stack) => self
+                .engine()
+                .capture_backtrace(&self.ctx.store.inner, call_stack),
+            None => WasmBacktrace::default(),
         }
     }
 
@@ -108,6 +169,7 @@ impl<'a, T: AsContextMut> From<&'a mut T> for Caller<'a, T::UserState> {
         Self {
             ctx: ctx.as_context_mut(),
             instance: None,
+            call_stack: None,
         }
     }
 }
@@ -13,6 +13,8 @@ pub enum FuncError {
     MismatchingResultType,
     /// Specified an incorrect number of results.
     MismatchingResultLen,
+    /// Specified a different number of inputs and outputs for a batch of calls.
+    MismatchingBatchLen,
 }
 
 impl Display for FuncError {
@@ -33,6 +35,12 @@ impl Display for FuncError {
             FuncError::MismatchingResultLen => {
                 write!(f, "encountered an incorrect number of results")
             }
+            FuncError::MismatchingBatchLen => {
+                write!(
+                    f,
+                    "encountered a different number of inputs and outputs for a batch call"
+                )
+            }
         }
     }
 }
@@ -1,13 +1,15 @@
-use crate::{core::ValueType, func::FuncError, Value};
-use alloc::{sync::Arc, vec::Vec};
-use core::fmt;
+use crate::{core::ValueType, func::FuncError, Error, Value};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{cmp::Ordering, fmt};
 
 /// A function type representing a function's parameter and result types.
 ///
 /// # Note
 ///
-/// Can be cloned cheaply.
-#[derive(Clone, PartialOrd, Ord, PartialEq, Eq)]
+/// - Can be cloned cheaply.
+/// - Parameters and results can optionally be named for better diagnostics.
+///   Names do not take part in comparisons of function types.
+#[derive(Clone)]
 pub struct FuncType {
     /// The number of function parameters.
     len_params: usize,
@@ -21,14 +23,93 @@ pub struct FuncType {
     /// The `len_params` field denotes how many parameters there are in
     /// the head of the vector before the results.
     params_results: Arc<[ValueType]>,
+    /// The optional names of the parameters and results if any.
+    ///
+    /// # Note
+    ///
+    /// - The names are ordered and merged the same way as `params_results`.
+    /// - The names are double boxed to keep [`FuncType`] small.
+    names: Option<Arc<Names>>,
+}
+
+/// The optional names of the parameters and results of a [`FuncType`].
+type Names = Box<[Option<Box<str>>]>;
+
+impl PartialEq for FuncType {
+    fn eq(&self, other: &Self) -> bool {
+        self.len_params == other.len_params && self.params_results == other.params_results
+    }
+}
+
+impl Eq for FuncType {}
+
+impl PartialOrd for FuncType {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FuncType {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.len_params
+            .cmp(&other.len_params)
+            .then_with(|| self.params_results.cmp(&other.params_results))
+    }
 }
 
 impl fmt::Debug for FuncType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("FuncType")
+        let mut debug = f.debug_struct("FuncType");
+        debug
             .field("params", &self.params())
-            .field("results", &self.results())
-            .finish()
+            .field("results", &self.results());
+        if let Some(names) = &self.names {
+            debug.field("names", names);
+        }
+        debug.finish()
+    }
+}
+
+impl fmt::Display for FuncType {
+    /// Formats the [`FuncType`] as in `fn(lhs: i32, i32) -> (sum: i32)`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "fn(")?;
+        for index in 0..self.len_params {
+            if index != 0 {
+                write!(f, ", ")?;
+            }
+            self.fmt_item(f, index)?;
+        }
+        write!(f, ")")?;
+        match self.len_results() {
+            0 => Ok(()),
+            1 if self.result_name(0).is_none() => {
+                write!(f, " -> ")?;
+                self.fmt_item(f, self.len_params)
+            }
+            len_results => {
+                write!(f, " -> (")?;
+                for index in 0..len_results {
+                    if index != 0 {
+                        write!(f, ", ")?;
+                    }
+                    self.fmt_item(f, self.len_params + index)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Returns the name of the [`ValueType`] in the Wasm text format.
+fn value_type_name(ty: ValueType) -> &'static str {
+    match ty {
+        ValueType::I32 => "i32",
+        ValueType::I64 => "i64",
+        ValueType::F32 => "f32",
+        ValueType::F64 => "f64",
+        ValueType::FuncRef => "funcref",
+        ValueType::ExternRef => "externref",
     }
 }
 
@@ -45,6 +126,123 @@ impl FuncType {
         Self {
             params_results: params_results.into(),
             len_params,
+            names: None,
+        }
+    }
+
+    /// Names the parameters of the [`FuncType`] in order.
+    ///
+    /// # Note
+    ///
+    /// - Parameters without a given name stay unnamed, as do parameters named by an empty string.
+    /// - Names are used in diagnostics, e.g. in errors about mismatching parameter types.
+    ///
+    /// # Panics
+    ///
+    /// If more names are given than the [`FuncType`] has parameters.
+    pub fn with_param_names<I>(self, names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let len_params = self.len_params;
+        self.with_names(0..len_params, names)
+    }
+
+    /// Names the results of the [`FuncType`] in order.
+    ///
+    /// # Note
+    ///
+    /// - Results without a given name stay unnamed, as do results named by an empty string.
+    /// - Names are used in diagnostics, e.g. in errors about mismatching result types.
+    ///
+    /// # Panics
+    ///
+    /// If more names are given than the [`FuncType`] has results.
+    pub fn with_result_names<I>(self, names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let range = self.len_params..self.params_results.len();
+        self.with_names(range, names)
+    }
+
+    /// Sets the names of the items of `params_results` at the indices in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If more names are given than `range` contains indices.
+    fn with_names<I>(mut self, range: core::ops::Range<usize>, names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut new_names = match &self.names {
+            Some(names) => names.to_vec(),
+            None => (0..self.params_results.len()).map(|_| None).collect(),
+        };
+        let mut indices = range.clone();
+        for name in names {
+            let index = indices.next().unwrap_or_else(|| {
+                panic!(
+                    "encountered more names than the {} items of the function type",
+                    range.len()
+                )
+            });
+            let name = name.as_ref();
+            new_names[index] = (!name.is_empty()).then(|| name.into());
+        }
+        self.names = new_names
+            .iter()
+            .any(Option::is_some)
+            .then(|| Arc::new(new_names.into_boxed_slice()));
+        self
+    }
+
+    /// Returns the name of the item of `params_results` at `index` if any.
+    fn name(&self, index: usize) -> Option<&str> {
+        self.names.as_ref()?.get(index)?.as_deref()
+    }
+
+    /// Returns the name of the parameter at `index` if any.
+    pub fn param_name(&self, index: usize) -> Option<&str> {
+        if index >= self.len_params {
+            return None;
+        }
+        self.name(index)
+    }
+
+    /// Returns the name of the result at `index` if any.
+    pub fn result_name(&self, index: usize) -> Option<&str> {
+        self.name(self.len_params.checked_add(index)?)
+    }
+
+    /// Returns `true` if any parameter or result of the [`FuncType`] is named.
+    pub(crate) fn has_names(&self) -> bool {
+        self.names.is_some()
+    }
+
+    /// Returns a clone of the [`FuncType`] without parameter and result names.
+    pub(crate) fn without_names(&self) -> Self {
+        Self {
+            len_params: self.len_params,
+            params_results: self.params_results.clone(),
+            names: None,
+        }
+    }
+
+    /// Compares the names of `self` and `other`.
+    pub(crate) fn cmp_names(&self, other: &Self) -> Ordering {
+        self.names.as_deref().cmp(&other.names.as_deref())
+    }
+
+    /// Formats the item of `params_results` at `index` with its name if any.
+    fn fmt_item(&self, f: &mut fmt::Formatter, index: usize) -> fmt::Result {
+        let ty = value_type_name(self.params_results[index]);
+        match self.name(index) {
+            Some(name) => write!(f, "{name}: {ty}"),
+            None => write!(f, "{ty}"),
         }
     }
 
@@ -74,20 +272,22 @@ impl FuncType {
     ///
     /// - If the number of items in `params` does not match the number of parameters of the function type.
     /// - If any type of an item in `params` does not match the expected type of the function type.
-    pub(crate) fn match_params<T>(&self, params: &[T]) -> Result<(), FuncError>
+    pub(crate) fn match_params<T>(&self, params: &[T]) -> Result<(), Error>
     where
         T: Ty,
     {
         if self.params().len() != params.len() {
-            return Err(FuncError::MismatchingParameterLen);
+            return Err(Error::from(FuncError::MismatchingParameterLen));
         }
-        if self
-            .params()
-            .iter()
-            .copied()
-            .ne(params.iter().map(<T as Ty>::ty))
-        {
-            return Err(FuncError::MismatchingParameterType);
+        if let Some((index, expected, actual)) = find_mismatch(self.params(), params) {
+            return Err(
+                Error::from(FuncError::MismatchingParameterType).with_type_mismatch(
+                    index,
+                    self.param_name(index),
+                    expected,
+                    actual,
+                ),
+            );
         }
         Ok(())
     }
@@ -102,21 +302,25 @@ impl FuncType {
     ///
     /// - If the number of items in `results` does not match the number of results of the function type.
     /// - If any type of an item in `results` does not match the expected type of the function type.
-    pub(crate) fn match_results<T>(&self, results: &[T], check_type: bool) -> Result<(), FuncError>
+    pub(crate) fn match_results<T>(&self, results: &[T], check_type: bool) -> Result<(), Error>
     where
         T: Ty,
     {
         if self.results().len() != results.len() {
-            return Err(FuncError::MismatchingResultLen);
+            return Err(Error::from(FuncError::MismatchingResultLen));
         }
-        if check_type
-            && self
-                .results()
-                .iter()
-                .copied()
-                .ne(results.iter().map(<T as Ty>::ty))
-        {
-            return Err(FuncError::MismatchingResultType);
+        if !check_type {
+            return Ok(());
+        }
+        if let Some((index, expected, actual)) = find_mismatch(self.results(), results) {
+            return Err(
+                Error::from(FuncError::MismatchingResultType).with_type_mismatch(
+                    index,
+                    self.result_name(index),
+                    expected,
+                    actual,
+                ),
+            );
         }
         Ok(())
     }
@@ -145,6 +349,19 @@ impl FuncType {
     }
 }
 
+/// Returns the index, expected and actual type of the first item of `actual` mismatching `expected`.
+fn find_mismatch<T>(expected: &[ValueType], actual: &[T]) -> Option<(usize, ValueType, ValueType)>
+where
+    T: Ty,
+{
+    expected
+        .iter()
+        .zip(actual.iter().map(<T as Ty>::ty))
+        .enumerate()
+        .find(|(_, (expected, actual))| *expected != actual)
+        .map(|(index, (expected, actual))| (index, *expected, actual))
+}
+
 /// Types that have a [`ValueType`].
 ///
 /// # Note
@@ -222,4 +439,58 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn names_work() {
+        let ft = FuncType::new([ValueType::I32, ValueType::I64], [ValueType::F32])
+            .with_param_names(["lhs", ""])
+            .with_result_names(["sum"]);
+        assert_eq!(ft.param_name(0), Some("lhs"));
+        assert_eq!(ft.param_name(1), None);
+        assert_eq!(ft.param_name(2), None);
+        assert_eq!(ft.result_name(0), Some("sum"));
+        assert_eq!(ft.result_name(1), None);
+        let unnamed = ft.clone().with_param_names([""]).with_result_names([""]);
+        assert!(unnamed.names.is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_names_panics() {
+        let _ = FuncType::new([ValueType::I32], []).with_param_names(["a", "b"]);
+    }
+
+    #[test]
+    fn names_do_not_affect_equality() {
+        let ft = FuncType::new([ValueType::I32], [ValueType::I32]);
+        let named = ft.clone().with_param_names(["x"]);
+        assert_eq!(ft, named);
+        assert_eq!(ft.cmp(&named), Ordering::Equal);
+        assert_ne!(ft.cmp_names(&named), Ordering::Equal);
+    }
+
+    #[test]
+    fn display_works() {
+        let display = |ft: FuncType| alloc::format!("{ft}");
+        assert_eq!(display(FuncType::new([], [])), "fn()");
+        assert_eq!(
+            display(FuncType::new(
+                [ValueType::I32, ValueType::FuncRef],
+                [ValueType::F64]
+            )),
+            "fn(i32, funcref) -> f64"
+        );
+        assert_eq!(
+            display(
+                FuncType::new([ValueType::I32, ValueType::I32], [ValueType::I32])
+                    .with_param_names(["lhs"])
+                    .with_result_names(["sum"])
+            ),
+            "fn(lhs: i32, i32) -> (sum: i32)"
+        );
+        assert_eq!(
+            display(FuncType::new([], [ValueType::I64, ValueType::ExternRef])),
+            "fn() -> (i64, externref)"
+        );
+    }
 }
@@ -1,5 +1,5 @@
-use super::Func;
-use crate::{core::UntypedValue, reftype::Transposer};
+use super::{Func, TypedFunc, WasmParams, WasmResults};
+use crate::{core::UntypedValue, reftype::Transposer, AsContext, Error};
 
 /// A nullable [`Func`] reference.
 #[derive(Debug, Default, Copy, Clone)]
@@ -105,6 +105,30 @@ impl FuncRef {
         self.inner.as_ref()
     }
 
+    /// Creates a new [`TypedFunc`] from the inner [`Func`] if [`FuncRef`] is not `null`.
+    ///
+    /// Returns `Ok(None)` if [`FuncRef`] is `null`.
+    ///
+    /// # Note
+    ///
+    /// This allows to call function references that have been taken out of a table
+    /// or returned by Wasm with static typing. See [`Func::typed`] for more details.
+    ///
+    /// # Errors
+    ///
+    /// If the function signature of the inner [`Func`] does not match `Params` and
+    /// `Results` as parameter types and result types respectively.
+    pub fn typed<Params, Results>(
+        &self,
+        ctx: impl AsContext,
+    ) -> Result<Option<TypedFunc<Params, Results>>, Error>
+    where
+        Params: WasmParams,
+        Results: WasmResults,
+    {
+        self.func().map(|func| func.typed(ctx)).transpose()
+    }
+
     /// Creates a `null` [`FuncRef`].
     pub fn null() -> Self {
         Self::new(None).canonicalize()
@@ -0,0 +1,220 @@
+use super::{Caller, FuncType, TrampolineEntity};
+use crate::{
+    core::UntypedValue,
+    engine::{CallStack, FuncParams},
+    module::ImportName,
+    value::WithType,
+    AsContextMut,
+    Error,
+    Instance,
+    Value,
+};
+use alloc::sync::Arc;
+use core::{fmt, fmt::Debug};
+
+/// The closure type of a [`HostCallInterceptor`].
+type HostCallInterceptorFn<T> =
+    dyn Fn(Caller<'_, T>, &mut HostCall<'_, T>) -> Result<(), Error> + Send + Sync + 'static;
+
+/// A user provided interceptor that wraps all host function calls of a [`Store`].
+///
+/// This wrapper exists to provide a `Debug` impl so that `#[derive(Debug)]`
+/// works on structs that contain it.
+///
+/// [`Store`]: crate::Store
+pub struct HostCallInterceptor<T>(Arc<HostCallInterceptorFn<T>>);
+
+impl<T> Debug for HostCallInterceptor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HostCallInterceptor(...)")
+    }
+}
+
+impl<T> Clone for HostCallInterceptor<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> HostCallInterceptor<T> {
+    /// Creates a new [`HostCallInterceptor`] from the given closure.
+    pub fn new<F>(interceptor: F) -> Self
+    where
+        F: Fn(Caller<'_, T>, &mut HostCall<'_, T>) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        Self(Arc::new(interceptor))
+    }
+
+    /// Calls the host function `trampoline` through the [`HostCallInterceptor`].
+    ///
+    /// # Errors
+    ///
+    /// - If the interceptor or the intercepted host function fails.
+    /// - If the interceptor returns successfully without proceeding the host function call.
+    pub(crate) fn call(
+        &self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        instance: Option<&Instance>,
+        call_stack: Option<&CallStack>,
+        trampoline: &TrampolineEntity<T>,
+        ty: FuncType,
+        params_results: &mut [UntypedValue],
+    ) -> Result<(), Error> {
+        let mut call = HostCall {
+            name: trampoline.name(),
+            ty,
+            params_results,
+            trampoline,
+            proceeded: false,
+        };
+        let caller = <Caller<T>>::new(&mut ctx, instance, call_stack);
+        (self.0)(caller, &mut call)?;
+        if !call.proceeded {
+            return Err(Error::new(
+                "host call interceptor returned without proceeding the host function call",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A host function call intercepted by a [`Store`] interceptor.
+///
+/// # Note
+///
+/// The interceptor must call [`HostCall::proceed`] exactly once to call
+/// the intercepted host function unless it returns an error.
+///
+/// [`Store`]: crate::Store
+pub struct HostCall<'a, T> {
+    /// The import name of the host function if any.
+    name: Option<&'a ImportName>,
+    /// The function type of the host function.
+    ty: FuncType,
+    /// The buffer holding the parameters and after proceeding the results.
+    params_results: &'a mut [UntypedValue],
+    /// The trampoline of the intercepted host function.
+    trampoline: &'a TrampolineEntity<T>,
+    /// Flag, `true` if the host function call has already been proceeded.
+    proceeded: bool,
+}
+
+impl<T> Debug for HostCall<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HostCall")
+            .field("name", &self.name)
+            .field("ty", &self.ty)
+            .field("proceeded", &self.proceeded)
+            .finish()
+    }
+}
+
+impl<'a, T> HostCall<'a, T> {
+    /// Returns the module name of the intercepted host function.
+    ///
+    /// Returns `None` if the host function was not defined by a [`Linker`].
+    ///
+    /// [`Linker`]: crate::Linker
+    pub fn module(&self) -> Option<&str> {
+        self.name.map(ImportName::module)
+    }
+
+    /// Returns the name of the intercepted host function within its module.
+    ///
+    /// Returns `None` if the host function was not defined by a [`Linker`].
+    ///
+    /// [`Linker`]: crate::Linker
+    pub fn name(&self) -> Option<&str> {
+        self.name.map(ImportName::name)
+    }
+
+    /// Returns the function type of the intercepted host function.
+    pub fn ty(&self) -> &FuncType {
+        &self.ty
+    }
+
+    /// Returns the parameters of the host function call.
+    ///
+    /// # Panics
+    ///
+    /// If the host function call has already been proceeded.
+    pub fn params(&self) -> impl ExactSizeIterator<Item = Value> + '_ {
+        assert!(
+            !self.proceeded,
+            "cannot query parameters of a proceeded host function call"
+        );
+        self.ty
+            .params()
+            .iter()
+            .zip(&self.params_results[..])
+            .map(|(ty, value)| value.with_type(*ty))
+    }
+
+    /// Returns the results of the host function call.
+    ///
+    /// # Panics
+    ///
+    /// If the host function call has not yet been proceeded.
+    pub fn results(&self) -> impl ExactSizeIterator<Item = Value> + '_ {
+        assert!(
+            self.proceeded,
+            "cannot query results of a host function call that has not yet been proceeded"
+        );
+        self.ty
+            .results()
+            .iter()
+            .zip(&self.params_results[..])
+            .map(|(ty, value)| value.with_type(*ty))
+    }
+
+    /// Proceeds the intercepted host function call using the `caller`.
+    ///
+    /// # Errors
+    ///
+    /// - If the host function call has already been proceeded.
+    /// - If the intercepted host function returns an error.
+    pub fn proceed(&mut self, caller: &mut Caller<'_, T>) -> Result<(), Error> {
+        if self.proceeded {
+            return Err(Error::new("host function call has already been proceeded"));
+        }
+        self.proceeded = true;
+        let params = FuncParams::new(
+            self.params_results,
+            self.ty.params().len(),
+            self.ty.results().len(),
+        );
+        let instance = caller.instance().copied();
+        let call_stack = caller.call_stack();
+        self.trampoline
+            .call(caller, instance.as_ref(), call_stack, params)?;
+        Ok(())
+    }
+
+    /// Completes the intercepted host function call with `results` without calling the host function.
+    ///
+    /// # Errors
+    ///
+    /// - If the host function call has already been proceeded.
+    /// - If `results` do not match the result types of the host function.
+    pub fn complete(&mut self, results: &[Value]) -> Result<(), Error> {
+        if self.proceeded {
+            return Err(Error::new("host function call has already been proceeded"));
+        }
+        let expected = self.ty.results();
+        if expected.len() != results.len()
+            || expected
+                .iter()
+                .zip(results)
+                .any(|(ty, value)| *ty != value.ty())
+        {
+            return Err(Error::new(
+                "results do not match the result types of the host function",
+            ));
+        }
+        self.proceeded = true;
+        for (cell, value) in self.params_results.iter_mut().zip(results) {
+            *cell = value.clone().into();
+        }
+        Ok(())
+    }
+}
@@ -2,7 +2,9 @@ mod caller;
 mod error;
 mod func_type;
 mod funcref;
+mod interceptor;
 mod into_func;
+mod replay;
 mod typed_func;
 
 pub(crate) use self::typed_func::CallResultsTuple;
@@ -11,19 +13,36 @@ pub use self::{
     error::FuncError,
     func_type::FuncType,
     funcref::FuncRef,
+    interceptor::{HostCall, HostCallInterceptor},
     into_func::{IntoFunc, WasmRet, WasmType, WasmTypeList},
+    replay::{
+        HostCallLog,
+        HostCallOutcome,
+        HostCallRecord,
+        HostCallRecorder,
+        HostCallReplayer,
+        MemoryWrite,
+    },
     typed_func::{TypedFunc, WasmParams, WasmResults},
 };
 use super::{
-    engine::{CompiledFunc, DedupFuncType, FuncFinished, FuncParams},
+    engine::{
+        bytecode::IntrinsicIdx,
+        CallStack,
+        CompiledFunc,
+        DedupFuncType,
+        FuncFinished,
+        FuncParams,
+    },
     AsContext,
     AsContextMut,
     Instance,
     StoreContext,
+    StoreContextMut,
     Stored,
 };
-use crate::{engine::ResumableCall, Engine, Error, Value};
-use alloc::{boxed::Box, sync::Arc};
+use crate::{core::UntypedValue, engine::ResumableCall, module::ImportName, Engine, Error, Value};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use core::{fmt, fmt::Debug, num::NonZeroU32};
 use wasmi_arena::ArenaIndex;
 
@@ -76,6 +95,39 @@ impl Trampoline {
     pub(super) fn as_inner(&self) -> &Stored<TrampolineIdx> {
         &self.0
     }
+
+    /// Calls the host function of the [`Trampoline`] stored in `ctx` with the given inputs.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`TrampolineEntity::call`] this does not clone the resolved
+    /// [`TrampolineEntity`] to release the borrow on the [`Store`] of `ctx`.
+    ///
+    /// [`Store`]: crate::Store
+    pub(crate) fn call<T>(
+        &self,
+        mut ctx: StoreContextMut<T>,
+        instance: Option<&Instance>,
+        call_stack: Option<&CallStack>,
+        params: FuncParams,
+    ) -> Result<FuncFinished, Error> {
+        // Safety: The trampolines of a `Store` are never removed or replaced
+        //         while the `Store` is alive. Therefore the closures behind the
+        //         `Arc`s outlive the host function call even if the host function
+        //         stores new trampolines and thereby moves the `Arc`s themselves.
+        match &ctx.store.resolve_trampoline(self).closure {
+            HostClosure::Typed(closure) => {
+                let closure = unsafe { &*Arc::as_ptr(closure) };
+                let caller = <Caller<T>>::new(&mut ctx, instance, call_stack);
+                closure(caller, params)
+            }
+            HostClosure::Dynamic { ty, func } => {
+                let ty = *ty;
+                let func = unsafe { &*Arc::as_ptr(func) };
+                call_dynamic(ctx, &ty, func, instance, call_stack, params)
+            }
+        }
+    }
 }
 
 /// A Wasm or host function instance.
@@ -99,19 +151,86 @@ impl From<HostFuncEntity> for FuncEntity {
     }
 }
 
+/// The number of parameters and results of a host function.
+///
+/// # Note
+///
+/// This is cached alongside host functions so that host calls do not
+/// have to resolve the [`FuncType`] of the called host function.
+#[derive(Debug, Copy, Clone)]
+pub struct HostFuncArity {
+    /// The number of parameters of the host function.
+    len_params: u32,
+    /// The number of results of the host function.
+    len_results: u32,
+}
+
+impl HostFuncArity {
+    /// Creates the [`HostFuncArity`] of the given [`FuncType`].
+    ///
+    /// # Panics
+    ///
+    /// If `ty` has more than `u32::MAX` parameters or results.
+    pub fn from_func_type(ty: &FuncType) -> Self {
+        let len = |len: usize| {
+            u32::try_from(len)
+                .unwrap_or_else(|_| panic!("host function arity out of bounds: {len}"))
+        };
+        Self {
+            len_params: len(ty.params().len()),
+            len_results: len(ty.results().len()),
+        }
+    }
+
+    /// Returns the number of parameters of the host function.
+    pub fn len_params(&self) -> usize {
+        self.len_params as usize
+    }
+
+    /// Returns the number of results of the host function.
+    pub fn len_results(&self) -> usize {
+        self.len_results as usize
+    }
+
+    /// Returns the number of cells required to hold either all parameters or all results.
+    pub fn max_inout(&self) -> usize {
+        self.len_params().max(self.len_results())
+    }
+}
+
 /// A host function reference and its function type.
 #[derive(Debug, Copy, Clone)]
 pub struct HostFuncEntity {
     /// The function type of the host function.
     ty: DedupFuncType,
+    /// The number of parameters and results of the host function.
+    arity: HostFuncArity,
     /// A reference to the trampoline of the host function.
     func: Trampoline,
+    /// The [`Intrinsic`](crate::Intrinsic) called by the host function if any.
+    intrinsic: Option<IntrinsicIdx>,
 }
 
 impl HostFuncEntity {
     /// Creates a new [`HostFuncEntity`].
-    pub fn new(ty: DedupFuncType, func: Trampoline) -> Self {
-        Self { ty, func }
+    pub fn new(ty: DedupFuncType, arity: HostFuncArity, func: Trampoline) -> Self {
+        Self {
+            ty,
+            arity,
+            func,
+            intrinsic: None,
+        }
+    }
+
+    /// Marks the [`HostFuncEntity`] as calling the [`Intrinsic`](crate::Intrinsic) at `index`.
+    pub fn with_intrinsic(mut self, index: IntrinsicIdx) -> Self {
+        self.intrinsic = Some(index);
+        self
+    }
+
+    /// Returns the [`IntrinsicIdx`] if the host function calls an [`Intrinsic`](crate::Intrinsic).
+    pub fn intrinsic(&self) -> Option<IntrinsicIdx> {
+        self.intrinsic
     }
 
     /// Returns the signature of the host function.
@@ -119,6 +238,11 @@ impl HostFuncEntity {
         &self.ty
     }
 
+    /// Returns the [`HostFuncArity`] of the host function.
+    pub fn arity(&self) -> HostFuncArity {
+        self.arity
+    }
+
     /// Returns the [`Trampoline`] of the host function.
     pub fn trampoline(&self) -> &Trampoline {
         &self.func
@@ -176,6 +300,8 @@ impl WasmFuncEntity {
 pub struct HostFuncTrampolineEntity<T> {
     /// The type of the associated host function.
     ty: DedupFuncType,
+    /// The number of parameters and results of the associated host function.
+    arity: HostFuncArity,
     /// The trampoline of the associated host function.
     trampoline: TrampolineEntity<T>,
 }
@@ -184,6 +310,7 @@ impl<T> Clone for HostFuncTrampolineEntity<T> {
     fn clone(&self) -> Self {
         Self {
             ty: self.ty,
+            arity: self.arity,
             trampoline: self.trampoline.clone(),
         }
     }
@@ -197,6 +324,10 @@ impl<T> Debug for HostFuncTrampolineEntity<T> {
 
 impl<T> HostFuncTrampolineEntity<T> {
     /// Creates a new host function trampoline from the given dynamically typed closure.
+    ///
+    /// # Panics
+    ///
+    /// If `ty` has more than `u32::MAX` parameters or results.
     pub fn new(
         engine: &Engine,
         ty: FuncType,
@@ -205,34 +336,34 @@ impl<T> HostFuncTrampolineEntity<T> {
             + Sync
             + 'static,
     ) -> Self {
-        // Preprocess parameters and results buffers so that we can reuse those
-        // computations within the closure implementation. We put both parameters
-        // and results into a single buffer which we can split to minimize the
-        // amount of allocations per trampoline invokation.
-        let params_iter = ty.params().iter().copied().map(Value::default);
-        let results_iter = ty.results().iter().copied().map(Value::default);
-        let len_params = ty.params().len();
-        let params_results: Box<[Value]> = params_iter.chain(results_iter).collect();
-        let trampoline = <TrampolineEntity<T>>::new(move |caller, args| {
-            // We are required to clone the buffer because we are operating within a `Fn`.
-            // This way the trampoline closure only has to own a single slice buffer.
-            // Note: An alternative solution is to use interior mutability but that solution
-            //       comes with its own downsides.
-            let mut params_results = params_results.clone();
-            let (params, results) = params_results.split_at_mut(len_params);
-            let func_results = args.decode_params_into_slice(params).unwrap();
-            func(caller, params, results)?;
-            Ok(func_results.encode_results_from_slice(results).unwrap())
-        });
-        let ty = engine.alloc_func_type(ty.clone());
-        Self { ty, trampoline }
+        let arity = HostFuncArity::from_func_type(&ty);
+        let ty = engine.alloc_func_type(ty);
+        let trampoline = <TrampolineEntity<T>>::new_dynamic(ty, func);
+        Self {
+            ty,
+            arity,
+            trampoline,
+        }
     }
 
     /// Creates a new host function trampoline from the given statically typed closure.
     pub fn wrap<Params, Results>(engine: &Engine, func: impl IntoFunc<T, Params, Results>) -> Self {
         let (signature, trampoline) = func.into_func();
+        let arity = HostFuncArity::from_func_type(&signature);
         let ty = engine.alloc_func_type(signature);
-        Self { ty, trampoline }
+        Self {
+            ty,
+            arity,
+            trampoline,
+        }
+    }
+
+    /// Names the host function with its `module` and `name` in a [`Linker`].
+    ///
+    /// [`Linker`]: crate::Linker
+    pub fn with_name(mut self, module: &str, name: &str) -> Self {
+        self.trampoline.name = Some(Arc::new(ImportName::new(module, name)));
+        self
     }
 
     /// Returns the signature of the host function.
@@ -240,6 +371,11 @@ impl<T> HostFuncTrampolineEntity<T> {
         &self.ty
     }
 
+    /// Returns the [`HostFuncArity`] of the host function.
+    pub fn arity(&self) -> HostFuncArity {
+        self.arity
+    }
+
     /// Returns the trampoline of the host function.
     pub fn trampoline(&self) -> &TrampolineEntity<T> {
         &self.trampoline
@@ -249,8 +385,48 @@ impl<T> HostFuncTrampolineEntity<T> {
 type TrampolineFn<T> =
     dyn Fn(Caller<T>, FuncParams) -> Result<FuncFinished, Error> + Send + Sync + 'static;
 
+/// The closure of a dynamically typed host function as created by [`Func::new`].
+type HostFn<T> =
+    dyn Fn(Caller<'_, T>, &[Value], &mut [Value]) -> Result<(), Error> + Send + Sync + 'static;
+
 pub struct TrampolineEntity<T> {
-    closure: Arc<TrampolineFn<T>>,
+    closure: HostClosure<T>,
+    /// The import name of the host function if it has been defined by a [`Linker`].
+    ///
+    /// [`Linker`]: crate::Linker
+    name: Option<Arc<ImportName>>,
+}
+
+/// The closure of a host function stored in a [`TrampolineEntity`].
+enum HostClosure<T> {
+    /// A statically typed host function that decodes its parameters itself.
+    Typed(Arc<TrampolineFn<T>>),
+    /// A dynamically typed host function.
+    ///
+    /// # Note
+    ///
+    /// It is called through the [`DynamicTrampoline`] of its function type `ty`
+    /// that is shared by all dynamically typed host functions of a [`Store`].
+    ///
+    /// [`Store`]: crate::Store
+    Dynamic {
+        /// The function type of the host function.
+        ty: DedupFuncType,
+        /// The host function closure.
+        func: Arc<HostFn<T>>,
+    },
+}
+
+impl<T> Clone for HostClosure<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Typed(closure) => Self::Typed(closure.clone()),
+            Self::Dynamic { ty, func } => Self::Dynamic {
+                ty: *ty,
+                func: func.clone(),
+            },
+        }
+    }
 }
 
 impl<T> Debug for TrampolineEntity<T> {
@@ -266,7 +442,38 @@ impl<T> TrampolineEntity<T> {
         F: Fn(Caller<T>, FuncParams) -> Result<FuncFinished, Error> + Send + Sync + 'static,
     {
         Self {
-            closure: Arc::new(trampoline),
+            closure: HostClosure::Typed(Arc::new(trampoline)),
+            name: None,
+        }
+    }
+
+    /// Creates a new [`TrampolineEntity`] from the given dynamically typed host function of type `ty`.
+    fn new_dynamic(
+        ty: DedupFuncType,
+        func: impl Fn(Caller<'_, T>, &[Value], &mut [Value]) -> Result<(), Error>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self {
+            closure: HostClosure::Dynamic {
+                ty,
+                func: Arc::new(func),
+            },
+            name: None,
+        }
+    }
+
+    /// Returns the import name of the host function if any.
+    pub fn name(&self) -> Option<&ImportName> {
+        self.name.as_deref()
+    }
+
+    /// Returns the function type if the host function is dynamically typed.
+    pub fn dynamic_ty(&self) -> Option<&DedupFuncType> {
+        match &self.closure {
+            HostClosure::Typed(_) => None,
+            HostClosure::Dynamic { ty, .. } => Some(ty),
         }
     }
 
@@ -277,10 +484,23 @@ impl<T> TrampolineEntity<T> {
         &self,
         mut ctx: impl AsContextMut<UserState = T>,
         instance: Option<&Instance>,
+        call_stack: Option<&CallStack>,
         params: FuncParams,
     ) -> Result<FuncFinished, Error> {
-        let caller = <Caller<T>>::new(&mut ctx, instance);
-        (self.closure)(caller, params)
+        match &self.closure {
+            HostClosure::Typed(closure) => {
+                let caller = <Caller<T>>::new(&mut ctx, instance, call_stack);
+                closure(caller, params)
+            }
+            HostClosure::Dynamic { ty, func } => call_dynamic(
+                ctx.as_context_mut(),
+                ty,
+                &**func,
+                instance,
+                call_stack,
+                params,
+            ),
+        }
     }
 }
 
@@ -288,8 +508,86 @@ impl<T> Clone for TrampolineEntity<T> {
     fn clone(&self) -> Self {
         Self {
             closure: self.closure.clone(),
+            name: self.name.clone(),
+        }
+    }
+}
+
+/// Calls the dynamically typed host function `func` of type `ty` with the given inputs.
+///
+/// # Note
+///
+/// The parameters and results are decoded into and encoded from a buffer of the
+/// [`DynamicTrampoline`] of `ty` so that host function calls do not allocate.
+fn call_dynamic<T>(
+    mut ctx: StoreContextMut<T>,
+    ty: &DedupFuncType,
+    func: &HostFn<T>,
+    instance: Option<&Instance>,
+    call_stack: Option<&CallStack>,
+    params: FuncParams,
+) -> Result<FuncFinished, Error> {
+    let (len_params, mut params_results) = ctx.store.take_host_func_buffer(ty);
+    let (func_params, func_results) = params_results.split_at_mut(len_params);
+    let results = params.decode_params_into_slice(func_params).unwrap();
+    let caller = <Caller<T>>::new(&mut ctx, instance, call_stack);
+    let outcome = func(caller, func_params, func_results)
+        .map(|()| results.encode_results_from_slice(func_results).unwrap());
+    ctx.store.recycle_host_func_buffer(ty, params_results);
+    outcome
+}
+
+/// The trampoline shared by all dynamically typed host functions of the same function type.
+///
+/// # Note
+///
+/// A [`Store`] holds a single [`DynamicTrampoline`] per function type of its dynamically
+/// typed host functions, e.g. when a [`Linker`] defines many host functions with only a
+/// few distinct function types. Its buffers are reused by all host function calls.
+///
+/// [`Store`]: crate::Store
+/// [`Linker`]: crate::Linker
+#[derive(Debug)]
+pub struct DynamicTrampoline {
+    /// The number of parameters of the function type.
+    len_params: usize,
+    /// The default values of all parameters followed by all results of the function type.
+    defaults: Box<[Value]>,
+    /// The parameter and result buffers that are currently not in use by a host function call.
+    buffers: Vec<Box<[Value]>>,
+}
+
+impl DynamicTrampoline {
+    /// Creates a new [`DynamicTrampoline`] for host functions of type `ty`.
+    pub fn new(ty: &FuncType) -> Self {
+        let defaults = ty
+            .params()
+            .iter()
+            .chain(ty.results())
+            .copied()
+            .map(Value::default)
+            .collect();
+        Self {
+            len_params: ty.params().len(),
+            defaults,
+            buffers: Vec::new(),
         }
     }
+
+    /// Returns the number of parameters and a parameter and result buffer for a host function call.
+    ///
+    /// # Note
+    ///
+    /// Only allocates a new buffer if all buffers are in use by re-entrant host function calls.
+    pub fn take_buffer(&mut self) -> (usize, Box<[Value]>) {
+        let buffer = self.buffers.pop().unwrap_or_else(|| self.defaults.clone());
+        (self.len_params, buffer)
+    }
+
+    /// Returns the `buffer` taken via [`DynamicTrampoline::take_buffer`] for reuse.
+    pub fn recycle_buffer(&mut self, buffer: Box<[Value]>) {
+        self.buffers.push(buffer);
+    }
 }
 
 /// A Wasm or host function reference.
@@ -308,6 +606,17 @@ impl Func {
         &self.0
     }
 
+    /// Returns `true` if `lhs` and `rhs` [`Func`] refer to the same entity.
+    ///
+    /// # Note
+    ///
+    /// We do not implement `Eq` and `PartialEq` and
+    /// intentionally keep this API hidden from users.
+    #[inline]
+    pub(crate) fn eq(lhs: &Self, rhs: &Self) -> bool {
+        lhs.as_inner() == rhs.as_inner()
+    }
+
     /// Creates a new [`Func`] with the given arguments.
     ///
     /// This is typically used to create a host-defined function to pass as an import to a Wasm module.
@@ -331,6 +640,10 @@ impl Func {
     /// - Prefer using [`Func::wrap`] over this method if possible since [`Func`] instances
     ///   created using this constructor have runtime overhead for every invocation that
     ///   can be avoided by using [`Func::wrap`].
+    ///
+    /// # Panics
+    ///
+    /// If `ty` has more than `u32::MAX` parameters or results.
     pub fn new<T>(
         mut ctx: impl AsContextMut<UserState = T>,
         ty: FuncType,
@@ -342,12 +655,39 @@ impl Func {
         let engine = ctx.as_context().store.engine();
         let host_func = HostFuncTrampolineEntity::new(engine, ty, func);
         let ty_dedup = *host_func.ty_dedup();
+        let arity = host_func.arity();
         let trampoline = host_func.trampoline().clone();
         let func = ctx.as_context_mut().store.alloc_trampoline(trampoline);
         ctx.as_context_mut()
             .store
             .inner
-            .alloc_func(HostFuncEntity::new(ty_dedup, func).into())
+            .alloc_func(HostFuncEntity::new(ty_dedup, arity, func).into())
+    }
+
+    /// Creates a new host function that calls the [`Intrinsic`](crate::Intrinsic) at `index`.
+    ///
+    /// # Note
+    ///
+    /// Lowered calls to the imported function are only dispatched to the
+    /// [`Intrinsic`](crate::Intrinsic) directly if the import resolves to
+    /// a [`Func`] created by this constructor.
+    pub(crate) fn new_intrinsic<T>(
+        mut ctx: impl AsContextMut<UserState = T>,
+        ty: FuncType,
+        index: IntrinsicIdx,
+        func: impl Fn(Caller<'_, T>, &[Value], &mut [Value]) -> Result<(), Error>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        let engine = ctx.as_context().store.engine();
+        let host_func = HostFuncTrampolineEntity::new(engine, ty, func);
+        let ty_dedup = *host_func.ty_dedup();
+        let arity = host_func.arity();
+        let trampoline = host_func.trampoline().clone();
+        let func = ctx.as_context_mut().store.alloc_trampoline(trampoline);
+        let entity = HostFuncEntity::new(ty_dedup, arity, func).with_intrinsic(index);
+        ctx.as_context_mut().store.inner.alloc_func(entity.into())
     }
 
     /// Creates a new host function from the given closure.
@@ -358,12 +698,13 @@ impl Func {
         let engine = ctx.as_context().store.engine();
         let host_func = HostFuncTrampolineEntity::wrap(engine, func);
         let ty_dedup = *host_func.ty_dedup();
+        let arity = host_func.arity();
         let trampoline = host_func.trampoline().clone();
         let func = ctx.as_context_mut().store.alloc_trampoline(trampoline);
         ctx.as_context_mut()
             .store
             .inner
-            .alloc_func(HostFuncEntity::new(ty_dedup, func).into())
+            .alloc_func(HostFuncEntity::new(ty_dedup, arity, func).into())
     }
 
     /// Returns the signature of the function.
@@ -412,13 +753,113 @@ impl Func {
         Ok(())
     }
 
+    /// Calls the Wasm or host function with the given untyped `params`.
+    ///
+    /// The results are written back into the `results` buffer.
+    ///
+    /// # Note
+    ///
+    /// - Unlike [`Func::call`] this neither constructs nor type checks [`Value`]s
+    ///   which is useful for embedders that track the types of values themselves.
+    /// - It is the caller's responsibility to provide `params` that match the
+    ///   parameter types of the function signature of `self`. Otherwise the
+    ///   behavior of the call is unspecified, however, it is still memory safe.
+    ///
+    /// # Errors
+    ///
+    /// - If the function returned a [`Error`].
+    /// - If the number of `params` does not match the number of parameters
+    ///   of the function signature of `self`.
+    /// - If the number of `results` does not match the number of results
+    ///   of the function signature of `self`.
+    pub fn call_untyped<T>(
+        &self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        params: &[UntypedValue],
+        results: &mut [UntypedValue],
+    ) -> Result<(), Error> {
+        let fn_type = self.ty_dedup(ctx.as_context());
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_func_type_with(fn_type, |func_type| {
+                if func_type.params().len() != params.len() {
+                    return Err(FuncError::MismatchingParameterLen);
+                }
+                if func_type.len_results() != results.len() {
+                    return Err(FuncError::MismatchingResultLen);
+                }
+                Ok(())
+            })?;
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context().store.engine().clone().execute_func(
+            ctx.as_context_mut(),
+            self,
+            params,
+            results,
+        )
+    }
+
+    /// Calls the Wasm or host function once for each of the given inputs.
+    ///
+    /// The results of the call with `inputs[n]` are written back into the `outputs[n]` buffer.
+    ///
+    /// # Note
+    ///
+    /// This is more efficient than calling [`Func::call`] in a loop since the
+    /// execution resources are set up only once for the whole batch of calls.
+    /// The [`CallReport`] of the [`Store`] accounts for all calls of the batch.
+    ///
+    /// # Errors
+    ///
+    /// - If any call returned a [`Error`].
+    ///   The remaining calls of the batch are not executed in this case.
+    /// - If the number of `inputs` does not match the number of `outputs`.
+    /// - If the types or number of any `inputs` or `outputs` do not match
+    ///   the function signature of `self`.
+    ///
+    /// [`CallReport`]: crate::CallReport
+    /// [`Store`]: crate::Store
+    pub fn call_batch<T>(
+        &self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        inputs: &[&[Value]],
+        outputs: &mut [&mut [Value]],
+    ) -> Result<(), Error> {
+        if inputs.len() != outputs.len() {
+            return Err(Error::from(FuncError::MismatchingBatchLen));
+        }
+        let fn_type = self.ty_dedup(ctx.as_context());
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_func_type_with(fn_type, |func_type| {
+                for (inputs, outputs) in inputs.iter().zip(outputs.iter_mut()) {
+                    func_type.match_params(inputs)?;
+                    func_type.match_results(outputs, false)?;
+                    func_type.prepare_outputs(outputs);
+                }
+                Ok::<(), Error>(())
+            })?;
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context().store.engine().clone().execute_func_batch(
+            ctx.as_context_mut(),
+            self,
+            inputs
+                .iter()
+                .copied()
+                .zip(outputs.iter_mut().map(|outputs| &mut **outputs)),
+            |()| {},
+        )
+    }
+
     /// Calls the Wasm or host function with the given inputs.
     ///
     /// The result is written back into the `outputs` buffer.
     ///
     /// Returns a resumable handle to the function invocation upon
-    /// encountering host errors with which it is possible to handle
-    /// the error and continue the execution as if no error occurred.
+    /// encountering host errors or running out of fuel with which it is possible
+    /// to handle the error and continue the execution as if no error occurred.
     ///
     /// # Note
     ///
@@ -470,7 +911,7 @@ impl Func {
         ctx: impl AsContext,
         inputs: &[Value],
         outputs: &mut [Value],
-    ) -> Result<(), FuncError> {
+    ) -> Result<(), Error> {
         let fn_type = self.ty_dedup(ctx.as_context());
         ctx.as_context()
             .store
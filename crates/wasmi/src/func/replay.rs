@@ -0,0 +1,406 @@
+use super::{Caller, HostCall};
+use crate::{
+    core::{Pages, TrapCode, UntypedValue},
+    AsContextMut,
+    Error,
+    Extern,
+    Memory,
+    Store,
+    Value,
+};
+use alloc::{
+    collections::VecDeque,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use spin::Mutex;
+
+/// The name of the linear memory export whose host side modifications are recorded.
+const MEMORY_EXPORT: &str = "memory";
+
+/// A log of recorded host function calls.
+///
+/// Created by a [`HostCallRecorder`] and fed back to the guest by a [`HostCallReplayer`].
+#[derive(Debug, Default, Clone)]
+pub struct HostCallLog {
+    /// The recorded host function calls in the order of their execution.
+    records: Vec<HostCallRecord>,
+}
+
+impl HostCallLog {
+    /// Returns the recorded host function calls in the order of their execution.
+    pub fn records(&self) -> &[HostCallRecord] {
+        &self.records
+    }
+
+    /// Returns the number of recorded host function calls.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no host function calls have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+impl FromIterator<HostCallRecord> for HostCallLog {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = HostCallRecord>,
+    {
+        Self {
+            records: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// A single recorded host function call.
+#[derive(Debug, Clone)]
+pub struct HostCallRecord {
+    /// The module name of the host function if any.
+    module: Option<String>,
+    /// The name of the host function if any.
+    name: Option<String>,
+    /// The parameters of the host function call.
+    params: Vec<Value>,
+    /// The outcome of the host function call.
+    outcome: HostCallOutcome,
+    /// The size of the linear memory of the caller after the host function call if any.
+    memory_pages: Option<Pages>,
+    /// The modifications of the linear memory of the caller by the host function.
+    memory_writes: Vec<MemoryWrite>,
+}
+
+impl HostCallRecord {
+    /// Creates a new [`HostCallRecord`].
+    ///
+    /// # Note
+    ///
+    /// This allows to construct or edit [`HostCallLog`]s by hand, e.g. to
+    /// replay host function calls that have been persisted in a custom format.
+    pub fn new(
+        module: Option<String>,
+        name: Option<String>,
+        params: Vec<Value>,
+        outcome: HostCallOutcome,
+    ) -> Self {
+        Self {
+            module,
+            name,
+            params,
+            outcome,
+            memory_pages: None,
+            memory_writes: Vec::new(),
+        }
+    }
+
+    /// Adds the modifications of the linear memory of the caller to the [`HostCallRecord`].
+    ///
+    /// The linear memory is grown to `pages` before `writes` are applied upon replay.
+    pub fn with_memory(mut self, pages: Pages, writes: Vec<MemoryWrite>) -> Self {
+        self.memory_pages = Some(pages);
+        self.memory_writes = writes;
+        self
+    }
+
+    /// Returns the module name of the host function if any.
+    pub fn module(&self) -> Option<&str> {
+        self.module.as_deref()
+    }
+
+    /// Returns the name of the host function if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the parameters of the host function call.
+    pub fn params(&self) -> &[Value] {
+        &self.params
+    }
+
+    /// Returns the outcome of the host function call.
+    pub fn outcome(&self) -> &HostCallOutcome {
+        &self.outcome
+    }
+
+    /// Returns the size of the linear memory of the caller after the host function call if any.
+    pub fn memory_pages(&self) -> Option<Pages> {
+        self.memory_pages
+    }
+
+    /// Returns the modifications of the linear memory of the caller by the host function.
+    pub fn memory_writes(&self) -> &[MemoryWrite] {
+        &self.memory_writes
+    }
+    /// Returns an error if the host function call to `module::name` with `params` diverges from `self`.
+    pub(crate) fn check_call(
+        &self,
+        module: Option<&str>,
+        name: Option<&str>,
+        params: impl ExactSizeIterator<Item = Value>,
+    ) -> Result<(), Error> {
+        if module != self.module() || name != self.name() {
+            return Err(Error::new(format!(
+                "host call replay diverged: expected call to {:?}::{:?} but found {:?}::{:?}",
+                self.module(),
+                self.name(),
+                module,
+                name,
+            )));
+        }
+        let expected = &self.params;
+        let params_match = params.len() == expected.len()
+            && params.zip(expected).all(|(actual, expected)| {
+                actual.ty() == expected.ty()
+                    && UntypedValue::from(actual) == UntypedValue::from(expected.clone())
+            });
+        if !params_match {
+            return Err(Error::new(format!(
+                "host call replay diverged: mismatching parameters for call to {:?}::{:?}",
+                self.module(),
+                self.name(),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reapplies the recorded modifications of the linear `memory` of the caller.
+    ///
+    /// # Errors
+    ///
+    /// - If modifications have been recorded but `memory` is `None`.
+    /// - If `memory` cannot be grown to the recorded size.
+    pub(crate) fn replay_memory(
+        &self,
+        mut ctx: impl AsContextMut,
+        memory: Option<Memory>,
+    ) -> Result<(), Error> {
+        let Some(pages) = self.memory_pages else {
+            return Ok(());
+        };
+        let memory = memory
+            .ok_or_else(|| Error::new("host call replay diverged: missing linear memory export"))?;
+        let current = memory.current_pages(&ctx);
+        if let Some(delta) = pages.checked_sub(current) {
+            memory.grow(&mut ctx, delta)?;
+        }
+        let data = memory.data_mut(&mut ctx);
+        for write in &self.memory_writes {
+            data.get_mut(write.offset..)
+                .and_then(|data| data.get_mut(..write.bytes.len()))
+                .ok_or_else(|| Error::from(TrapCode::MemoryOutOfBounds))?
+                .copy_from_slice(&write.bytes);
+        }
+        Ok(())
+    }
+}
+
+/// The outcome of a recorded host function call.
+#[derive(Debug, Clone)]
+pub enum HostCallOutcome {
+    /// The host function returned the results.
+    Return(Vec<Value>),
+    /// The host function trapped with the [`TrapCode`].
+    Trap(TrapCode),
+    /// The host function returned an error with the message.
+    Error(String),
+}
+
+/// A contiguous modification of linear memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryWrite {
+    /// The byte offset of the modification.
+    offset: usize,
+    /// The bytes written at the `offset`.
+    bytes: Vec<u8>,
+}
+
+impl MemoryWrite {
+    /// Creates a new [`MemoryWrite`] of `bytes` at `offset`.
+    pub fn new(offset: usize, bytes: Vec<u8>) -> Self {
+        Self { offset, bytes }
+    }
+
+    /// Returns the byte offset of the modification.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the bytes written at the offset.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Returns the linear memory exported as `"memory"` by the caller if any.
+fn caller_memory<T>(caller: &Caller<'_, T>) -> Option<Memory> {
+    match caller.get_export(MEMORY_EXPORT)? {
+        Extern::Memory(memory) => Some(memory),
+        _ => None,
+    }
+}
+
+/// Returns the [`MemoryWrite`]s that turn `before` into `after`.
+///
+/// Bytes beyond the end of `before` are zero since linear memory only grows.
+fn diff_memory(before: &[u8], after: &[u8]) -> Vec<MemoryWrite> {
+    let changed = |offset: usize| before.get(offset).copied().unwrap_or(0) != after[offset];
+    let mut writes = Vec::new();
+    let mut offset = 0;
+    while offset < after.len() {
+        if !changed(offset) {
+            offset += 1;
+            continue;
+        }
+        let start = offset;
+        while offset < after.len() && changed(offset) {
+            offset += 1;
+        }
+        writes.push(MemoryWrite::new(start, after[start..offset].to_vec()));
+    }
+    writes
+}
+
+/// Records host function calls of a [`Store`] into a [`HostCallLog`].
+///
+/// # Note
+///
+/// Besides parameters and results the recorder captures all modifications of the
+/// linear memory exported as `"memory"` by the calling instance, such as the
+/// effects of WASI host functions. For this the linear memory is compared before
+/// and after every host function call. Therefore recording is an expensive
+/// debugging aid and should not be enabled in production.
+#[derive(Debug, Default, Clone)]
+pub struct HostCallRecorder {
+    /// The shared log of recorded host function calls.
+    log: Arc<Mutex<HostCallLog>>,
+}
+
+impl HostCallRecorder {
+    /// Creates a new [`HostCallRecorder`] with an empty [`HostCallLog`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Installs the [`HostCallRecorder`] as host call interceptor of the `store`.
+    ///
+    /// # Note
+    ///
+    /// This replaces the previously installed host call interceptor of the `store` if any.
+    pub fn install<T>(&self, store: &mut Store<T>) {
+        let log = self.log.clone();
+        store.set_host_call_interceptor(move |mut caller, call| {
+            let (record, result) = Self::record(&mut caller, call);
+            log.lock().records.push(record);
+            result
+        });
+    }
+
+    /// Proceeds the host function `call` and records it.
+    ///
+    /// Returns the [`HostCallRecord`] and the result of the host function call.
+    fn record<T>(
+        caller: &mut Caller<'_, T>,
+        call: &mut HostCall<'_, T>,
+    ) -> (HostCallRecord, Result<(), Error>) {
+        let module = call.module().map(String::from);
+        let name = call.name().map(String::from);
+        let params = call.params().collect();
+        let memory = caller_memory(caller);
+        let before = memory.map(|memory| memory.data(&*caller).to_vec());
+        let result = call.proceed(caller);
+        let outcome = match &result {
+            Ok(()) => HostCallOutcome::Return(call.results().collect()),
+            Err(error) => match error.as_trap_code() {
+                Some(trap_code) => HostCallOutcome::Trap(trap_code),
+                None => HostCallOutcome::Error(error.to_string()),
+            },
+        };
+        let record = HostCallRecord::new(module, name, params, outcome);
+        let record = match (memory, before) {
+            (Some(memory), Some(before)) => {
+                let writes = diff_memory(&before, memory.data(&*caller));
+                record.with_memory(memory.current_pages(&*caller), writes)
+            }
+            _ => record,
+        };
+        (record, result)
+    }
+
+    /// Returns a copy of the [`HostCallLog`] recorded so far.
+    pub fn log(&self) -> HostCallLog {
+        self.log.lock().clone()
+    }
+
+    /// Takes the [`HostCallLog`] recorded so far, leaving behind an empty one.
+    pub fn take_log(&self) -> HostCallLog {
+        core::mem::take(&mut *self.log.lock())
+    }
+}
+
+/// Replays a [`HostCallLog`] instead of calling the host functions of a [`Store`].
+///
+/// # Note
+///
+/// Every host function call is answered by the next [`HostCallRecord`] of the log
+/// which also reapplies the recorded modifications of the linear memory exported
+/// as `"memory"` by the calling instance. The host functions themselves are never
+/// called which allows to deterministically reproduce a recorded execution offline.
+///
+/// A host function call fails if it diverges from the next [`HostCallRecord`],
+/// i.e. if its name or parameters differ, or if the log has been exhausted.
+#[derive(Debug, Clone)]
+pub struct HostCallReplayer {
+    /// The remaining host function calls to be replayed.
+    records: Arc<Mutex<VecDeque<HostCallRecord>>>,
+}
+
+impl HostCallReplayer {
+    /// Creates a new [`HostCallReplayer`] for the `log`.
+    pub fn new(log: HostCallLog) -> Self {
+        Self {
+            records: Arc::new(Mutex::new(log.records.into())),
+        }
+    }
+
+    /// Installs the [`HostCallReplayer`] as host call interceptor of the `store`.
+    ///
+    /// # Note
+    ///
+    /// This replaces the previously installed host call interceptor of the `store` if any.
+    pub fn install<T>(&self, store: &mut Store<T>) {
+        let records = self.records.clone();
+        store.set_host_call_interceptor(move |mut caller, call| {
+            let Some(record) = records.lock().pop_front() else {
+                return Err(Error::new(
+                    "host call replay diverged: the host call log has been exhausted",
+                ));
+            };
+            Self::replay(&mut caller, call, record)
+        });
+    }
+
+    /// Answers the host function `call` with the `record`.
+    fn replay<T>(
+        caller: &mut Caller<'_, T>,
+        call: &mut HostCall<'_, T>,
+        record: HostCallRecord,
+    ) -> Result<(), Error> {
+        record.check_call(call.module(), call.name(), call.params())?;
+        let memory = caller_memory(caller);
+        record.replay_memory(&mut *caller, memory)?;
+        match record.outcome {
+            HostCallOutcome::Return(results) => call.complete(&results),
+            HostCallOutcome::Trap(trap_code) => Err(Error::from(trap_code)),
+            HostCallOutcome::Error(message) => Err(Error::new(message)),
+        }
+    }
+
+    /// Returns the number of host function calls that remain to be replayed.
+    pub fn remaining(&self) -> usize {
+        self.records.lock().len()
+    }
+}
@@ -7,6 +7,7 @@ use crate::{
     Error,
     TypedResumableCall,
 };
+use alloc::vec::Vec;
 use core::{fmt, fmt::Debug, marker::PhantomData};
 
 /// A typed [`Func`] instance.
@@ -103,11 +104,98 @@ where
         )
     }
 
+    /// Calls this Wasm or host function once for each of the specified parameters.
+    ///
+    /// Returns either the results of all calls in order, or a [`Error`] if one happened.
+    ///
+    /// # Note
+    ///
+    /// This is more efficient than calling [`TypedFunc::call`] in a loop since the
+    /// execution resources are set up only once for the whole batch of calls.
+    /// The [`CallReport`] of the [`Store`] accounts for all calls of the batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`TypedFunc`].
+    ///
+    /// # Errors
+    ///
+    /// If the execution of any of the called Wasm functions traps.
+    /// The remaining calls of the batch are not executed in this case.
+    ///
+    /// [`CallReport`]: crate::CallReport
+    /// [`Store`]: crate::Store
+    pub fn call_batch(
+        &self,
+        mut ctx: impl AsContextMut,
+        params: &[Params],
+    ) -> Result<Vec<Results>, Error>
+    where
+        Params: Clone,
+    {
+        let mut results = Vec::with_capacity(params.len());
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context().store.engine().clone().execute_func_batch(
+            ctx.as_context_mut(),
+            &self.func,
+            params
+                .iter()
+                .cloned()
+                .map(|params| (params, <CallResultsTuple<Results>>::default())),
+            |call_results| results.push(call_results),
+        )?;
+        Ok(results)
+    }
+
+    /// Calls this Wasm or host function with the specified parameters and a fuel budget of `max_fuel`.
+    ///
+    /// Returns either the results of the call, or a [`Error`] if one happened.
+    ///
+    /// # Note
+    ///
+    /// - The call may consume at most `max_fuel` fuel regardless of the remaining
+    ///   fuel of the [`Store`] which allows to run a sequence of calls with
+    ///   independent fuel budgets using the same [`Store`].
+    /// - The remaining fuel of the [`Store`] is restored after the call while the
+    ///   fuel consumed by the call still counts towards [`Store::fuel_consumed`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`TypedFunc`].
+    ///
+    /// # Errors
+    ///
+    /// - If fuel metering is disabled.
+    /// - If the execution of the called Wasm function traps, e.g. because it ran out of fuel.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::fuel_consumed`]: crate::Store::fuel_consumed
+    pub fn call_with_fuel(
+        &self,
+        mut ctx: impl AsContextMut,
+        params: Params,
+        max_fuel: u64,
+    ) -> Result<Results, Error> {
+        let remaining = ctx
+            .as_context_mut()
+            .store
+            .inner
+            .fuel_mut()
+            .replace_remaining(max_fuel)?;
+        let result = self.call(&mut ctx, params);
+        ctx.as_context_mut()
+            .store
+            .inner
+            .fuel_mut()
+            .replace_remaining(remaining)?;
+        result
+    }
+
     /// Calls this Wasm or host function with the specified parameters.
     ///
     /// Returns a resumable handle to the function invocation upon
-    /// encountering host errors with which it is possible to handle
-    /// the error and continue the execution as if no error occurred.
+    /// encountering host errors or running out of fuel with which it is possible
+    /// to handle the error and continue the execution as if no error occurred.
     ///
     /// # Note
     ///
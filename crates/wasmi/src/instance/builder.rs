@@ -1,8 +1,8 @@
-use super::InstanceEntity;
+use super::{InstanceEntity, InstanceInit};
 use crate::{
     engine::DedupFuncType,
     memory::DataSegment,
-    module::FuncIdx,
+    module::{FuncIdx, ModuleNames},
     ElementSegment,
     Extern,
     ExternType,
@@ -26,6 +26,8 @@ pub struct InstanceEntityBuilder {
     exports: BTreeMap<Box<str>, Extern>,
     data_segments: Vec<DataSegment>,
     elem_segments: Vec<ElementSegment>,
+    names: Arc<ModuleNames>,
+    init: InstanceInit,
 }
 
 impl InstanceEntityBuilder {
@@ -66,6 +68,8 @@ impl InstanceEntityBuilder {
             exports: BTreeMap::default(),
             data_segments: Vec::new(),
             elem_segments: Vec::new(),
+            names: module.names_cloned(),
+            init: InstanceInit::default(),
         }
     }
 
@@ -136,6 +140,16 @@ impl InstanceEntityBuilder {
             .unwrap_or_else(|| panic!("missing `Func` at index: {index}"))
     }
 
+    /// Returns the [`DataSegment`]s pushed to the [`InstanceEntity`] under construction.
+    pub fn data_segments(&self) -> &[DataSegment] {
+        &self.data_segments[..]
+    }
+
+    /// Returns the [`ElementSegment`]s pushed to the [`InstanceEntity`] under construction.
+    pub fn element_segments(&self) -> &[ElementSegment] {
+        &self.elem_segments[..]
+    }
+
     /// Pushes a new [`Memory`] to the [`InstanceEntity`] under construction.
     pub fn push_memory(&mut self, memory: Memory) {
         self.memories.push(memory);
@@ -181,6 +195,11 @@ impl InstanceEntityBuilder {
         self.elem_segments.push(segment);
     }
 
+    /// Sets the state of the [`InstanceEntity`] right after its instantiation.
+    pub fn set_init(&mut self, init: InstanceInit) {
+        self.init = init;
+    }
+
     /// Finishes constructing the [`InstanceEntity`].
     pub fn finish(self) -> InstanceEntity {
         InstanceEntity {
@@ -192,7 +211,10 @@ impl InstanceEntityBuilder {
             globals: self.globals.into(),
             exports: self.exports,
             data_segments: self.data_segments.into(),
+            #[cfg(feature = "bulk-table")]
             elem_segments: self.elem_segments.into(),
+            names: self.names,
+            init: Arc::new(self.init),
         }
     }
 }
@@ -98,6 +98,26 @@ impl Extern {
             Extern::Func(func) => func.ty(ctx).into(),
         }
     }
+
+    /// Returns the dynamic type associated with this [`Extern`].
+    ///
+    /// # Note
+    ///
+    /// This respects the current size of tables and linear memories as their minimum
+    /// size the same as instantiation does when checking imports. Use this together
+    /// with [`ExternType::matches`] to check if this [`Extern`] satisfies an import.
+    ///
+    /// # Panics
+    ///
+    /// If this item does not belong to the `store` provided.
+    pub fn dynamic_ty(&self, ctx: impl AsContext) -> ExternType {
+        match self {
+            Extern::Global(global) => global.ty(ctx).into(),
+            Extern::Table(table) => table.dynamic_ty(ctx).into(),
+            Extern::Memory(memory) => memory.dynamic_ty(ctx).into(),
+            Extern::Func(func) => func.ty(ctx).into(),
+        }
+    }
 }
 
 /// The type of an [`Extern`] item.
@@ -171,6 +191,37 @@ impl ExternType {
             _ => None,
         }
     }
+
+    /// Returns `true` if an item of type `self` can be used to satisfy an import of type `required`.
+    ///
+    /// # Note
+    ///
+    /// This implements the [import matching] rules of the WebAssembly specification
+    /// and performs exactly the checks that instantiation performs on imports:
+    ///
+    /// - [`FuncType`]s match if they are equal.
+    /// - [`TableType`]s match if their element types are equal and their limits are subtypes.
+    /// - [`MemoryType`]s match if their limits are subtypes.
+    /// - [`GlobalType`]s match if their content types and mutability are equal.
+    /// - Items of different kinds never match.
+    ///
+    /// Limits of `self` are subtypes of the `required` limits if their minimum is greater than
+    /// or equal to the `required` minimum and if their maximum is less than or equal to the
+    /// `required` maximum. A missing maximum is treated as unbounded.
+    ///
+    /// Use [`Extern::dynamic_ty`] to query the type of an [`Extern`] for this check since
+    /// instantiation uses the current size of tables and linear memories as their minimum.
+    ///
+    /// [import matching]: https://webassembly.github.io/spec/core/valid/types.html#import-subtyping
+    pub fn matches(&self, required: &ExternType) -> bool {
+        match (self, required) {
+            (Self::Func(ty), Self::Func(required)) => ty == required,
+            (Self::Table(ty), Self::Table(required)) => ty.is_subtype_of(required),
+            (Self::Memory(ty), Self::Memory(required)) => ty.is_subtype_of(required),
+            (Self::Global(ty), Self::Global(required)) => required.satisfies(ty).is_ok(),
+            _ => false,
+        }
+    }
 }
 
 /// An exported WebAssembly value.
@@ -0,0 +1,96 @@
+use crate::{
+    memory::{DataSegment, DataSegmentEntity},
+    AsContextMut,
+    ElementSegment,
+    ElementSegmentEntity,
+    Error,
+    Global,
+    Memory,
+    Table,
+};
+use alloc::{boxed::Box, vec::Vec};
+use wasmi_core::UntypedValue;
+
+/// The state of an instance right after its instantiation.
+///
+/// Restored by [`Instance::reset`](crate::Instance::reset).
+#[derive(Debug, Default)]
+pub struct InstanceInit {
+    /// The internal mutable global variables and their initial values.
+    globals: Vec<(Global, UntypedValue)>,
+    /// The internal tables.
+    tables: Vec<Table>,
+    /// The elements written by the active element segments at their table offsets.
+    elements: Vec<(Table, u32, Box<[UntypedValue]>)>,
+    /// The internal linear memories.
+    memories: Vec<Memory>,
+    /// The data segments and their state after instantiation.
+    data_segments: Vec<(DataSegment, DataSegmentEntity)>,
+    /// The element segments and their state after instantiation.
+    elem_segments: Vec<(ElementSegment, ElementSegmentEntity)>,
+}
+
+impl InstanceInit {
+    /// Records the internal mutable `global` and its initial `value`.
+    pub fn push_global(&mut self, global: Global, value: UntypedValue) {
+        self.globals.push((global, value));
+    }
+
+    /// Records the internal `table`.
+    pub fn push_table(&mut self, table: Table) {
+        self.tables.push(table);
+    }
+
+    /// Records the `elements` written to `table` at `offset` by an active element segment.
+    pub fn push_elements(&mut self, table: Table, offset: u32, elements: Box<[UntypedValue]>) {
+        self.elements.push((table, offset, elements));
+    }
+
+    /// Records the internal `memory`.
+    pub fn push_memory(&mut self, memory: Memory) {
+        self.memories.push(memory);
+    }
+
+    /// Records the data `segment` and its `entity` after instantiation.
+    pub fn push_data_segment(&mut self, segment: DataSegment, entity: DataSegmentEntity) {
+        self.data_segments.push((segment, entity));
+    }
+
+    /// Records the element `segment` and its `entity` after instantiation.
+    pub fn push_element_segment(&mut self, segment: ElementSegment, entity: ElementSegmentEntity) {
+        self.elem_segments.push((segment, entity));
+    }
+
+    /// Restores the recorded state of the instance within `ctx`.
+    ///
+    /// # Errors
+    ///
+    /// If an internal linear memory cannot be reset in which case nothing else is restored.
+    pub fn restore(&self, mut ctx: impl AsContextMut) -> Result<(), Error> {
+        let mut ctx = ctx.as_context_mut();
+        // Note: Memories are reset first since they are the only fallible part.
+        for memory in &self.memories {
+            memory.reset(&mut ctx)?;
+        }
+        let store = &mut ctx.store.inner;
+        for (global, value) in &self.globals {
+            store.resolve_global_mut(global).set_untyped(*value);
+        }
+        for table in &self.tables {
+            store.resolve_table_mut(table).reset();
+        }
+        for (table, offset, elements) in &self.elements {
+            let table = store.resolve_table_mut(table);
+            for (index, element) in (*offset..).zip(&elements[..]) {
+                table.set_untyped(index, *element)?;
+            }
+        }
+        for (segment, entity) in &self.data_segments {
+            *store.resolve_data_segment_mut(segment) = entity.clone();
+        }
+        for (segment, entity) in &self.elem_segments {
+            *store.resolve_element_segment_mut(segment) = entity.clone();
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
-pub(crate) use self::builder::InstanceEntityBuilder;
 pub use self::exports::{Export, ExportsIter, Extern, ExternType};
+pub(crate) use self::{builder::InstanceEntityBuilder, init::InstanceInit};
 use super::{
     engine::DedupFuncType,
     AsContext,
+    AsContextMut,
     Func,
     Global,
     Memory,
@@ -14,7 +15,7 @@ use super::{
 use crate::{
     func::FuncError,
     memory::DataSegment,
-    ElementSegment,
+    module::{InstantiationError, ModuleNames},
     Error,
     TypedFunc,
     WasmParams,
@@ -23,8 +24,12 @@ use crate::{
 use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
 use wasmi_arena::ArenaIndex;
 
+#[cfg(feature = "bulk-table")]
+use crate::ElementSegment;
+
 mod builder;
 mod exports;
+mod init;
 
 /// A raw index to a module instance entity.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -54,7 +59,10 @@ pub struct InstanceEntity {
     globals: Box<[Global]>,
     exports: BTreeMap<Box<str>, Extern>,
     data_segments: Box<[DataSegment]>,
+    #[cfg(feature = "bulk-table")]
     elem_segments: Box<[ElementSegment]>,
+    names: Arc<ModuleNames>,
+    init: Arc<InstanceInit>,
 }
 
 impl InstanceEntity {
@@ -69,7 +77,10 @@ impl InstanceEntity {
             globals: [].into(),
             exports: BTreeMap::new(),
             data_segments: [].into(),
+            #[cfg(feature = "bulk-table")]
             elem_segments: [].into(),
+            names: Arc::default(),
+            init: Arc::default(),
         }
     }
 
@@ -103,6 +114,16 @@ impl InstanceEntity {
         self.funcs.get(index as usize).copied()
     }
 
+    /// Returns the name of the module of the instance if any.
+    pub fn get_module_name(&self) -> Option<&str> {
+        self.names.module()
+    }
+
+    /// Returns the name of the function at the `index` if any.
+    pub fn get_func_name(&self, index: u32) -> Option<&str> {
+        self.names.func(index)
+    }
+
     /// Returns the signature at the `index` if any.
     pub fn get_signature(&self, index: u32) -> Option<&DedupFuncType> {
         self.func_types.get(index as usize)
@@ -114,6 +135,7 @@ impl InstanceEntity {
     }
 
     /// Returns the [`ElementSegment`] at the `index` if any.
+    #[cfg(feature = "bulk-table")]
     pub fn get_element_segment(&self, index: u32) -> Option<ElementSegment> {
         self.elem_segments.get(index as usize).copied()
     }
@@ -123,6 +145,11 @@ impl InstanceEntity {
         self.exports.get(name).copied()
     }
 
+    /// Returns the state of the [`InstanceEntity`] right after its instantiation.
+    pub fn init(&self) -> &Arc<InstanceInit> {
+        &self.init
+    }
+
     /// Returns an iterator over the exports of the [`Instance`].
     ///
     /// The order of the yielded exports is not specified.
@@ -268,6 +295,48 @@ impl Instance {
         self.get_export(store, name)?.into_memory()
     }
 
+    /// Resets the [`Instance`] to its state right after instantiation.
+    ///
+    /// # Note
+    ///
+    /// This allows to recycle a pooled [`Instance`] for the next request
+    /// without instantiating its [`Module`] again. Resetting
+    ///
+    /// - resets its internal linear memories via [`Memory::reset`],
+    /// - restores the initial values of its internal mutable global variables,
+    /// - shrinks its internal tables back to their initial size,
+    /// - re-applies its active element segments to their tables and
+    /// - restores its dropped passive data and element segments.
+    ///
+    /// Imported entities are not reset apart from the tables written to by its
+    /// active element segments. The restored state is the one before the `start`
+    /// function was executed which is not executed again.
+    ///
+    /// # Errors
+    ///
+    /// - If [`Config::instance_reset`] is disabled for the [`Engine`] of the `store`.
+    /// - If an internal linear memory cannot be reset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    ///
+    /// [`Config::instance_reset`]: crate::Config::instance_reset
+    /// [`Engine`]: crate::Engine
+    pub fn reset(&self, store: impl AsContextMut) -> Result<(), Error> {
+        if !store.as_context().engine().config().get_instance_reset() {
+            return Err(Error::from(InstantiationError::ResetDisabled));
+        }
+        let init = store
+            .as_context()
+            .store
+            .inner
+            .resolve_instance(self)
+            .init()
+            .clone();
+        init.restore(store)
+    }
+
     /// Returns an iterator over the exports of the [`Instance`].
     ///
     /// The order of the yielded exports is not specified.
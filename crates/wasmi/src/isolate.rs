@@ -0,0 +1,464 @@
+//! Multi-tenant isolation of Wasm executions.
+//!
+//! # Note
+//!
+//! An [`Isolate`] bundles a [`Store`] with the quotas of its tenant:
+//!
+//! - The resource limits enforced upon instantiation and growth via [`StoreLimits`].
+//! - The fuel budget that is charged by executions of the [`Isolate`].
+//! - The epoch deadline after which executions of the [`Isolate`] are interrupted.
+//!
+//! Every [`Isolate`] is registered at an [`IsolateRegistry`] for its lifetime.
+//! The [`IsolateRegistry`] can be shared across threads in order to enumerate
+//! the live isolates and to terminate them, for example all isolates of a tenant.
+//!
+//! # Example
+//!
+//! ```
+//! use wasmi::{
+//!     isolate::{Isolate, IsolateQuota, IsolateRegistry},
+//!     Config, Engine, Linker, Module, StoreLimitsBuilder,
+//! };
+//!
+//! let mut config = Config::default();
+//! config.consume_fuel(true);
+//! config.epoch_interruption(true);
+//! let engine = Engine::new(&config);
+//! let registry = IsolateRegistry::new();
+//! let quota = IsolateQuota::new()
+//!     .limits(StoreLimitsBuilder::new().memory_size(1 << 20).build())
+//!     .fuel(10_000);
+//! let mut isolate = Isolate::new(&registry, &engine, "tenant-a", quota, ()).unwrap();
+//! let wasm = wat::parse_str(r#"(module (func (export "run")))"#).unwrap();
+//! let module = Module::new(&engine, &wasm[..]).unwrap();
+//! let instance = Linker::new(&engine)
+//!     .instantiate(&mut isolate, &module)
+//!     .unwrap()
+//!     .start(&mut isolate)
+//!     .unwrap();
+//! let run = instance.get_typed_func::<(), ()>(&isolate, "run").unwrap();
+//! run.call(&mut isolate, ()).unwrap();
+//! assert_eq!(registry.len(), 1);
+//! registry.terminate_tenant("tenant-a");
+//! assert!(run.call(&mut isolate, ()).is_err());
+//! ```
+
+use crate::{
+    store::{InterruptHandle, StoreContext, StoreContextMut},
+    AsContext,
+    AsContextMut,
+    Engine,
+    Error,
+    Store,
+    StoreLimits,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+        Mutex,
+        MutexGuard,
+        PoisonError,
+    },
+    vec::Vec,
+};
+
+/// Identifies an [`Isolate`] within its [`IsolateRegistry`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IsolateId(u64);
+
+/// The per-tenant quotas of an [`Isolate`].
+#[derive(Debug, Default, Clone)]
+pub struct IsolateQuota {
+    /// The resource limits of the [`Isolate`].
+    limits: StoreLimits,
+    /// The fuel budget of the [`Isolate`] if any.
+    fuel: Option<u64>,
+    /// The epoch deadline of the [`Isolate`] in ticks if any.
+    epoch_deadline: Option<u32>,
+}
+
+impl IsolateQuota {
+    /// Creates a new [`IsolateQuota`] with default resource limits, no fuel budget and no deadline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the resource `limits` of the [`Isolate`].
+    pub fn limits(mut self, limits: StoreLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets the fuel budget of the [`Isolate`].
+    ///
+    /// # Note
+    ///
+    /// Requires [`Config::consume_fuel`](crate::Config::consume_fuel) to be enabled.
+    /// Without a fuel budget the [`Store`] of the [`Isolate`] starts without any fuel.
+    pub fn fuel(mut self, fuel: u64) -> Self {
+        self.fuel = Some(fuel);
+        self
+    }
+
+    /// Sets the epoch deadline of the [`Isolate`] in `ticks`.
+    ///
+    /// # Note
+    ///
+    /// Requires [`Config::epoch_interruption`](crate::Config::epoch_interruption) to be enabled.
+    pub fn epoch_deadline(mut self, ticks: u32) -> Self {
+        self.epoch_deadline = Some(ticks);
+        self
+    }
+
+    /// Returns the fuel budget of the [`IsolateQuota`] if any.
+    pub fn get_fuel(&self) -> Option<u64> {
+        self.fuel
+    }
+
+    /// Returns the epoch deadline of the [`IsolateQuota`] in ticks if any.
+    pub fn get_epoch_deadline(&self) -> Option<u32> {
+        self.epoch_deadline
+    }
+}
+
+/// The user state of the [`Store`] of an [`Isolate`].
+///
+/// Host functions called by an [`Isolate`] access the user state `T` via
+/// [`IsolateData::data`] and [`IsolateData::data_mut`].
+#[derive(Debug)]
+pub struct IsolateData<T> {
+    /// The resource limiter of the [`Isolate`].
+    limits: StoreLimits,
+    /// The user state of the [`Isolate`].
+    data: T,
+}
+
+impl<T> IsolateData<T> {
+    /// Returns a shared reference to the user state of the [`Isolate`].
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// Returns an exclusive reference to the user state of the [`Isolate`].
+    pub fn data_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
+/// A [`Store`] bundled with the quotas of a tenant.
+///
+/// # Note
+///
+/// - The [`Isolate`] is used as context wherever a [`Store`] is expected.
+/// - Once terminated via its [`IsolateRegistry`] all executions of the [`Isolate`]
+///   trap with [`TrapCode::Interrupted`] at their next safepoint. This requires
+///   [`Config::epoch_interruption`] or [`Config::signal_preemption`] to be enabled.
+/// - The [`Isolate`] is removed from its [`IsolateRegistry`] when dropped.
+///
+/// [`TrapCode::Interrupted`]: crate::core::TrapCode::Interrupted
+/// [`Config::epoch_interruption`]: crate::Config::epoch_interruption
+/// [`Config::signal_preemption`]: crate::Config::signal_preemption
+#[derive(Debug)]
+pub struct Isolate<T> {
+    /// The identifier of the [`Isolate`] within its registry.
+    id: IsolateId,
+    /// The tenant owning the [`Isolate`].
+    tenant: Arc<str>,
+    /// The quotas of the [`Isolate`].
+    quota: IsolateQuota,
+    /// The store of the [`Isolate`].
+    store: Store<IsolateData<T>>,
+    /// Is `true` once the [`Isolate`] has been terminated.
+    terminated: Arc<AtomicBool>,
+    /// The registry of the [`Isolate`].
+    registry: IsolateRegistry,
+}
+
+impl<T> Isolate<T> {
+    /// Creates a new [`Isolate`] for `tenant` with its `quota` and registers it at `registry`.
+    ///
+    /// # Errors
+    ///
+    /// If the `quota` has a fuel budget but fuel metering is disabled for `engine`.
+    pub fn new(
+        registry: &IsolateRegistry,
+        engine: &Engine,
+        tenant: &str,
+        quota: IsolateQuota,
+        data: T,
+    ) -> Result<Self, Error> {
+        let data = IsolateData {
+            limits: quota.limits.clone(),
+            data,
+        };
+        let mut store = Store::new(engine, data);
+        store.limiter(|data| &mut data.limits);
+        if let Some(fuel) = quota.fuel {
+            store.add_fuel(fuel)?;
+        }
+        if let Some(ticks) = quota.epoch_deadline {
+            store.set_epoch_deadline(ticks);
+        }
+        let tenant = Arc::<str>::from(tenant);
+        let terminated = Arc::new(AtomicBool::new(false));
+        let id = registry.register(IsolateEntry {
+            tenant: tenant.clone(),
+            interrupt: store.interrupt_handle(),
+            terminated: terminated.clone(),
+        });
+        Ok(Self {
+            id,
+            tenant,
+            quota,
+            store,
+            terminated,
+            registry: registry.clone(),
+        })
+    }
+
+    /// Returns the [`IsolateId`] of the [`Isolate`].
+    pub fn id(&self) -> IsolateId {
+        self.id
+    }
+
+    /// Returns the tenant owning the [`Isolate`].
+    pub fn tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    /// Returns the [`IsolateQuota`] of the [`Isolate`].
+    pub fn quota(&self) -> &IsolateQuota {
+        &self.quota
+    }
+
+    /// Returns a shared reference to the user state of the [`Isolate`].
+    pub fn data(&self) -> &T {
+        &self.store.data().data
+    }
+
+    /// Returns an exclusive reference to the user state of the [`Isolate`].
+    pub fn data_mut(&mut self) -> &mut T {
+        &mut self.store.data_mut().data
+    }
+
+    /// Returns a shared reference to the [`Store`] of the [`Isolate`].
+    pub fn store(&self) -> &Store<IsolateData<T>> {
+        &self.store
+    }
+
+    /// Returns an exclusive reference to the [`Store`] of the [`Isolate`].
+    pub fn store_mut(&mut self) -> &mut Store<IsolateData<T>> {
+        self.rearm();
+        &mut self.store
+    }
+
+    /// Returns `true` if the [`Isolate`] has been terminated.
+    pub fn is_terminated(&self) -> bool {
+        self.terminated.load(Ordering::Acquire)
+    }
+
+    /// Replenishes the fuel budget and restarts the epoch deadline of the [`Isolate`].
+    ///
+    /// # Note
+    ///
+    /// The fuel consumed so far still counts towards [`Store::fuel_consumed`].
+    ///
+    /// # Errors
+    ///
+    /// If the quota has a fuel budget but fuel metering is disabled.
+    pub fn renew(&mut self) -> Result<(), Error> {
+        if let Some(fuel) = self.quota.fuel {
+            let remaining = self.store.consume_fuel(0)?;
+            self.store.add_fuel(fuel.saturating_sub(remaining))?;
+        }
+        if let Some(ticks) = self.quota.epoch_deadline {
+            self.store.set_epoch_deadline(ticks);
+        }
+        Ok(())
+    }
+
+    /// Requests an interruption if the [`Isolate`] has been terminated.
+    ///
+    /// # Note
+    ///
+    /// Executions clear the interruption request once they trap. Therefore the
+    /// request is renewed before every access that may execute Wasm.
+    fn rearm(&self) {
+        if self.is_terminated() {
+            self.store.interrupt_handle().interrupt();
+        }
+    }
+}
+
+impl<T> Drop for Isolate<T> {
+    fn drop(&mut self) {
+        self.registry.unregister(self.id);
+    }
+}
+
+impl<T> AsContext for Isolate<T> {
+    type UserState = IsolateData<T>;
+
+    #[inline]
+    fn as_context(&self) -> StoreContext<'_, Self::UserState> {
+        self.store.as_context()
+    }
+}
+
+impl<T> AsContextMut for Isolate<T> {
+    #[inline]
+    fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::UserState> {
+        self.store_mut().as_context_mut()
+    }
+}
+
+/// The registry entry of a live [`Isolate`].
+#[derive(Debug)]
+struct IsolateEntry {
+    /// The tenant owning the [`Isolate`].
+    tenant: Arc<str>,
+    /// Interrupts executions of the [`Isolate`].
+    interrupt: InterruptHandle,
+    /// Is `true` once the [`Isolate`] has been terminated.
+    terminated: Arc<AtomicBool>,
+}
+
+impl IsolateEntry {
+    /// Terminates the [`Isolate`] of the entry.
+    fn terminate(&self) {
+        self.terminated.store(true, Ordering::Release);
+        self.interrupt.interrupt();
+    }
+}
+
+/// Information about a live [`Isolate`] returned by [`IsolateRegistry::isolates`].
+#[derive(Debug, Clone)]
+pub struct IsolateInfo {
+    /// The identifier of the [`Isolate`].
+    id: IsolateId,
+    /// The tenant owning the [`Isolate`].
+    tenant: Arc<str>,
+    /// Is `true` if the [`Isolate`] has been terminated.
+    terminated: bool,
+}
+
+impl IsolateInfo {
+    /// Returns the [`IsolateId`] of the [`Isolate`].
+    pub fn id(&self) -> IsolateId {
+        self.id
+    }
+
+    /// Returns the tenant owning the [`Isolate`].
+    pub fn tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    /// Returns `true` if the [`Isolate`] has been terminated.
+    pub fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+/// The shared state of an [`IsolateRegistry`].
+#[derive(Debug, Default)]
+struct RegistryInner {
+    /// The identifier of the next registered [`Isolate`].
+    next_id: AtomicU64,
+    /// The live isolates.
+    entries: Mutex<BTreeMap<IsolateId, IsolateEntry>>,
+}
+
+/// Keeps track of all live [`Isolate`]s registered at it.
+///
+/// # Note
+///
+/// Cloning an [`IsolateRegistry`] yields a handle to the same registry
+/// which may be used from any thread.
+#[derive(Debug, Default, Clone)]
+pub struct IsolateRegistry {
+    inner: Arc<RegistryInner>,
+}
+
+impl IsolateRegistry {
+    /// Creates a new empty [`IsolateRegistry`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Locks the live isolates ignoring poisoning.
+    ///
+    /// # Note
+    ///
+    /// Poisoning can be ignored since the entries are always in a valid state.
+    fn entries(&self) -> MutexGuard<'_, BTreeMap<IsolateId, IsolateEntry>> {
+        self.inner
+            .entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Registers the `entry` and returns the [`IsolateId`] of its [`Isolate`].
+    fn register(&self, entry: IsolateEntry) -> IsolateId {
+        let id = IsolateId(self.inner.next_id.fetch_add(1, Ordering::Relaxed));
+        self.entries().insert(id, entry);
+        id
+    }
+
+    /// Removes the [`Isolate`] identified by `id` from the [`IsolateRegistry`].
+    fn unregister(&self, id: IsolateId) {
+        self.entries().remove(&id);
+    }
+
+    /// Returns the number of live [`Isolate`]s.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Returns `true` if there are no live [`Isolate`]s.
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    /// Returns information about all live [`Isolate`]s ordered by their [`IsolateId`].
+    pub fn isolates(&self) -> Vec<IsolateInfo> {
+        self.entries()
+            .iter()
+            .map(|(id, entry)| IsolateInfo {
+                id: *id,
+                tenant: entry.tenant.clone(),
+                terminated: entry.terminated.load(Ordering::Acquire),
+            })
+            .collect()
+    }
+
+    /// Terminates the [`Isolate`] identified by `id`.
+    ///
+    /// Returns `false` if no such [`Isolate`] is live.
+    ///
+    /// # Note
+    ///
+    /// An ongoing execution of the [`Isolate`] traps at its next safepoint.
+    /// All further executions of the [`Isolate`] trap at their first safepoint.
+    pub fn terminate(&self, id: IsolateId) -> bool {
+        match self.entries().get(&id) {
+            Some(entry) => {
+                entry.terminate();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Terminates all live [`Isolate`]s of `tenant` and returns their number.
+    pub fn terminate_tenant(&self, tenant: &str) -> usize {
+        let entries = self.entries();
+        let mut terminated = 0;
+        for entry in entries.values().filter(|entry| &*entry.tenant == tenant) {
+            entry.terminate();
+            terminated += 1;
+        }
+        terminated
+    }
+}
@@ -0,0 +1,408 @@
+//! Compatibility layer for the `ImportsBuilder` and `Externals` API of Wasmi `0.x`.
+//!
+//! # Note
+//!
+//! This module eases the migration of embedders that were written against the
+//! `ModuleInstance::new(&module, &imports)` style API of Wasmi `0.11` and earlier.
+//! It is implemented on top of [`Engine`], [`Store`] and [`Module`] and therefore
+//! shares their execution semantics.
+//!
+//! - Only function imports can be resolved via [`ModuleImportResolver`].
+//! - Host functions are dispatched through [`Externals::invoke_index`] which is
+//!   provided anew upon every invocation, just like in Wasmi `0.x`.
+//! - Functions returning more than a single value cannot be invoked.
+//!
+//! New code should use [`Linker`] and [`Func::wrap`] instead.
+//!
+//! # Example
+//!
+//! ```
+//! use wasmi::{
+//!     legacy::{Externals, FuncInstance, ImportsBuilder, ModuleImportResolver, ModuleInstance},
+//!     Engine, Error, FuncType, Module, Value,
+//! };
+//!
+//! struct Env;
+//!
+//! impl ModuleImportResolver for Env {
+//!     fn resolve_func(&self, field_name: &str, signature: &FuncType) -> Result<FuncInstance, Error> {
+//!         match field_name {
+//!             "double" => Ok(FuncInstance::alloc_host(signature.clone(), 0)),
+//!             _ => Err(Error::new(format!("unknown host function: {field_name}"))),
+//!         }
+//!     }
+//! }
+//!
+//! struct Host;
+//!
+//! impl Externals for Host {
+//!     fn invoke_index(&mut self, index: usize, args: &[Value]) -> Result<Option<Value>, Error> {
+//!         assert_eq!(index, 0);
+//!         Ok(Some(Value::I32(args[0].i32().unwrap() * 2)))
+//!     }
+//! }
+//!
+//! let wasm = wat::parse_str(r#"
+//!     (module
+//!         (import "env" "double" (func $double (param i32) (result i32)))
+//!         (func (export "run") (param i32) (result i32)
+//!             (call $double (local.get 0))
+//!         )
+//!     )
+//! "#).unwrap();
+//! let module = Module::new(&Engine::default(), &wasm[..]).unwrap();
+//! let imports = ImportsBuilder::new().with_resolver("env", &Env);
+//! let mut instance = ModuleInstance::new(&module, &imports)
+//!     .unwrap()
+//!     .run_start(&mut Host)
+//!     .unwrap();
+//! let result = instance.invoke_export("run", &[Value::I32(21)], &mut Host).unwrap();
+//! assert_eq!(result.and_then(|value| value.i32()), Some(42));
+//! ```
+//!
+//! [`Engine`]: crate::Engine
+//! [`Linker`]: crate::Linker
+//! [`Func::wrap`]: crate::Func::wrap
+
+use crate::{
+    core::{HostError, TrapCode},
+    errors::LinkerError,
+    Error,
+    Extern,
+    ExternType,
+    Func,
+    FuncType,
+    Instance,
+    InstancePre,
+    Module,
+    ResumableCall,
+    Store,
+    Value,
+};
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+/// Dispatches calls to host functions by their index.
+///
+/// This mirrors the `Externals` trait of Wasmi `0.x`.
+pub trait Externals {
+    /// Calls the host function with the given `index` and `args`.
+    ///
+    /// The `index` is the one given to [`FuncInstance::alloc_host`] when resolving the import.
+    ///
+    /// # Errors
+    ///
+    /// If the host function traps.
+    fn invoke_index(&mut self, index: usize, args: &[Value]) -> Result<Option<Value>, Error>;
+}
+
+/// [`Externals`] that trap upon every host function call.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NopExternals;
+
+impl Externals for NopExternals {
+    fn invoke_index(&mut self, _index: usize, _args: &[Value]) -> Result<Option<Value>, Error> {
+        Err(Error::from(TrapCode::UnreachableCodeReached))
+    }
+}
+
+/// A host function resolved by a [`ModuleImportResolver`].
+#[derive(Debug, Clone)]
+pub struct FuncInstance {
+    /// The signature of the host function.
+    signature: FuncType,
+    /// The index passed to [`Externals::invoke_index`] upon calling the host function.
+    index: usize,
+}
+
+impl FuncInstance {
+    /// Allocates a new host function with `signature` that is dispatched using `index`.
+    pub fn alloc_host(signature: FuncType, index: usize) -> Self {
+        Self { signature, index }
+    }
+
+    /// Returns the signature of the host function.
+    pub fn signature(&self) -> &FuncType {
+        &self.signature
+    }
+
+    /// Returns the index passed to [`Externals::invoke_index`].
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// Resolves the function imports of a single module namespace.
+///
+/// This mirrors the `ModuleImportResolver` trait of Wasmi `0.x`.
+pub trait ModuleImportResolver {
+    /// Resolves the imported function `field_name` with the expected `signature`.
+    ///
+    /// # Errors
+    ///
+    /// If there is no host function named `field_name`.
+    fn resolve_func(&self, field_name: &str, signature: &FuncType) -> Result<FuncInstance, Error>;
+}
+
+/// Maps module namespaces to their [`ModuleImportResolver`].
+#[derive(Default)]
+pub struct ImportsBuilder<'a> {
+    /// The registered resolvers by their module namespace.
+    resolvers: BTreeMap<String, &'a dyn ModuleImportResolver>,
+}
+
+impl<'a> fmt::Debug for ImportsBuilder<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.resolvers.keys()).finish()
+    }
+}
+
+impl<'a> ImportsBuilder<'a> {
+    /// Creates a new [`ImportsBuilder`] without any resolvers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `resolver` for all imports from the `name` module namespace.
+    pub fn with_resolver(mut self, name: &str, resolver: &'a dyn ModuleImportResolver) -> Self {
+        self.push_resolver(name, resolver);
+        self
+    }
+
+    /// Registers `resolver` for all imports from the `name` module namespace.
+    pub fn push_resolver(&mut self, name: &str, resolver: &'a dyn ModuleImportResolver) {
+        self.resolvers.insert(name.to_string(), resolver);
+    }
+
+    /// Returns the resolver registered for the `name` module namespace if any.
+    pub fn resolver(&self, name: &str) -> Option<&dyn ModuleImportResolver> {
+        self.resolvers.get(name).copied()
+    }
+}
+
+/// A host function call that suspends execution in order to be dispatched via [`Externals`].
+#[derive(Debug)]
+struct HostCall {
+    /// The index of the called host function.
+    index: usize,
+    /// The arguments of the host function call.
+    args: Vec<Value>,
+}
+
+impl fmt::Display for HostCall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "legacy host function call with index {}", self.index)
+    }
+}
+
+impl HostError for HostCall {}
+
+/// Entry point to instantiate a [`Module`] using an [`ImportsBuilder`].
+#[derive(Debug)]
+pub struct ModuleInstance;
+
+impl ModuleInstance {
+    /// Instantiates `module` in a new [`Store`] resolving its imports with `imports`.
+    ///
+    /// # Errors
+    ///
+    /// - If an import is not a function import.
+    /// - If no resolver is registered for the module namespace of an import.
+    /// - If a resolver fails to resolve an import or resolves it with a mismatching signature.
+    /// - If the instantiation of `module` fails.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(module: &Module, imports: &ImportsBuilder) -> Result<NotStartedModuleRef, Error> {
+        let mut store = Store::new(module.engine(), ());
+        let mut externals = Vec::with_capacity(module.imports().len());
+        for import in module.imports() {
+            let (ExternType::Func(func_type), Some(resolver)) =
+                (import.ty(), imports.resolver(import.module()))
+            else {
+                return Err(LinkerError::missing_definition(&import).into());
+            };
+            let host_func = resolver.resolve_func(import.name(), func_type)?;
+            if host_func.signature() != func_type {
+                return Err(LinkerError::func_type_mismatch(
+                    import.import_name(),
+                    func_type,
+                    host_func.signature(),
+                )
+                .into());
+            }
+            let index = host_func.index();
+            let func = Func::new(
+                &mut store,
+                func_type.clone(),
+                move |_caller, args, _results| {
+                    Err(Error::host(HostCall {
+                        index,
+                        args: args.to_vec(),
+                    }))
+                },
+            );
+            externals.push(Extern::Func(func));
+        }
+        let pre = module.instantiate_resolved(&mut store, externals)?;
+        Ok(NotStartedModuleRef { store, pre })
+    }
+}
+
+/// An instantiated [`Module`] whose `start` function has not yet been executed.
+#[derive(Debug)]
+pub struct NotStartedModuleRef {
+    /// The [`Store`] owning the instance.
+    store: Store<()>,
+    /// The partially instantiated instance.
+    pre: InstancePre,
+}
+
+impl NotStartedModuleRef {
+    /// Executes the `start` function if any, dispatching host calls to `externals`.
+    ///
+    /// # Errors
+    ///
+    /// If executing the `start` function traps.
+    pub fn run_start<E>(self, externals: &mut E) -> Result<ModuleRef, Error>
+    where
+        E: Externals,
+    {
+        let Self { mut store, pre } = self;
+        let start_fn = pre.start_fn();
+        let instance = pre.initialize(&mut store);
+        if let Some(index) = start_fn {
+            let start = instance
+                .get_func_by_index(&store, index)
+                .unwrap_or_else(|| {
+                    panic!("encountered invalid start function after validation: {index}")
+                });
+            invoke(&mut store, start, &[], externals)?;
+        }
+        Ok(ModuleRef { store, instance })
+    }
+
+    /// Finishes instantiation assuming that there is no `start` function.
+    ///
+    /// # Panics
+    ///
+    /// If the instantiated [`Module`] has a `start` function.
+    pub fn assert_no_start(self) -> ModuleRef {
+        let Self { mut store, pre } = self;
+        let instance = pre
+            .ensure_no_start(&mut store)
+            .unwrap_or_else(|error| panic!("{error}"));
+        ModuleRef { store, instance }
+    }
+}
+
+/// A fully instantiated [`Module`] together with the [`Store`] owning it.
+#[derive(Debug)]
+pub struct ModuleRef {
+    /// The [`Store`] owning the instance.
+    store: Store<()>,
+    /// The instance.
+    instance: Instance,
+}
+
+impl ModuleRef {
+    /// Returns the underlying [`Instance`].
+    pub fn instance(&self) -> Instance {
+        self.instance
+    }
+
+    /// Returns a shared reference to the [`Store`] owning the [`Instance`].
+    pub fn store(&self) -> &Store<()> {
+        &self.store
+    }
+
+    /// Returns an exclusive reference to the [`Store`] owning the [`Instance`].
+    pub fn store_mut(&mut self) -> &mut Store<()> {
+        &mut self.store
+    }
+
+    /// Returns the export named `name` if any.
+    pub fn export_by_name(&self, name: &str) -> Option<Extern> {
+        self.instance.get_export(&self.store, name)
+    }
+
+    /// Calls the exported function `name` with `args`, dispatching host calls to `externals`.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no exported function named `name`.
+    /// - If `args` do not match the parameters of the exported function.
+    /// - If the exported function returns more than a single value.
+    /// - If the execution traps.
+    pub fn invoke_export<E>(
+        &mut self,
+        name: &str,
+        args: &[Value],
+        externals: &mut E,
+    ) -> Result<Option<Value>, Error>
+    where
+        E: Externals,
+    {
+        let func = self
+            .instance
+            .get_func(&self.store, name)
+            .ok_or_else(|| Error::new(format!("missing exported function: {name}")))?;
+        invoke(&mut self.store, func, args, externals)
+    }
+}
+
+/// Calls `func` with `args` in `store`, dispatching host calls to `externals`.
+///
+/// # Errors
+///
+/// - If `args` do not match the parameters of `func`.
+/// - If `func` returns more than a single value.
+/// - If the execution traps.
+fn invoke<E>(
+    store: &mut Store<()>,
+    func: Func,
+    args: &[Value],
+    externals: &mut E,
+) -> Result<Option<Value>, Error>
+where
+    E: Externals,
+{
+    let func_type = func.ty(&*store);
+    if func_type.results().len() > 1 {
+        return Err(Error::new(
+            "cannot invoke functions with multiple results via the legacy API",
+        ));
+    }
+    let mut results = func_type
+        .results()
+        .iter()
+        .copied()
+        .map(Value::default)
+        .collect::<Vec<_>>();
+    let mut call = func.call_resumable(&mut *store, args, &mut results)?;
+    loop {
+        let invocation = match call {
+            ResumableCall::Finished => break,
+            ResumableCall::Yielded(yielded) => {
+                call = yielded.resume(&mut *store, &mut results)?;
+                continue;
+            }
+            ResumableCall::Resumable(invocation) => invocation,
+            ResumableCall::OutOfFuel(_) => return Err(Error::from(TrapCode::OutOfFuel)),
+        };
+        let host_call = invocation
+            .host_error()
+            .downcast_ref::<HostCall>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "encountered unexpected host error: {}",
+                    invocation.host_error()
+                )
+            });
+        let result = externals.invoke_index(host_call.index, &host_call.args)?;
+        call = invocation.resume(&mut *store, result.as_slice(), &mut results)?;
+    }
+    Ok(results.pop())
+}
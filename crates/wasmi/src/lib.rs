@@ -76,6 +76,8 @@
     clippy::default_trait_access,
     clippy::items_after_statements
 )]
+// The runtime-only build keeps many helpers shared with the Wasm translator around.
+#![cfg_attr(not(feature = "compiler"), allow(dead_code))]
 #![recursion_limit = "750"]
 
 #[cfg(not(feature = "std"))]
@@ -87,20 +89,38 @@ extern crate std as alloc;
 #[macro_use]
 mod foreach_tuple;
 
+pub mod debug;
 mod engine;
 mod error;
 mod externref;
 mod func;
 mod global;
 mod instance;
+#[cfg(feature = "std")]
+pub mod isolate;
+#[cfg(feature = "legacy")]
+pub mod legacy;
 mod limits;
 mod linker;
+#[cfg(feature = "macros")]
+mod macro_support;
+#[cfg(feature = "serde")]
+pub mod marshal;
 mod memory;
 mod module;
+mod policy;
+#[cfg(all(unix, feature = "std"))]
+pub mod preempt;
 mod reftype;
+mod scheduler;
 mod store;
 mod table;
+mod teardown;
+#[cfg(feature = "std")]
+pub mod threading;
 mod value;
+#[cfg(feature = "std")]
+pub mod watchdog;
 
 /// Definitions from the `wasmi_core` crate.
 #[doc(inline)]
@@ -109,27 +129,65 @@ pub use wasmi_core as core;
 /// Defines some errors that may occur upon interaction with Wasmi.
 pub mod errors {
     pub use super::{
+        engine::{IntrinsicError, TranslationError},
         error::ErrorKind,
         func::FuncError,
         global::GlobalError,
         linker::LinkerError,
         memory::MemoryError,
-        module::InstantiationError,
+        module::{
+            ArtifactError,
+            ArtifactFeatures,
+            ArtifactTarget,
+            ConstExprError,
+            InstantiationError,
+            PreinitError,
+        },
         store::FuelError,
         table::TableError,
+        value::ValueError,
     };
 }
 
+#[cfg(feature = "fault-injection")]
+pub use self::engine::AllocFailures;
+#[cfg(all(feature = "std", feature = "compiler"))]
+pub use self::engine::CompilationHandle;
+#[cfg(feature = "compiler")]
+pub use self::module::{
+    CoverageProbe,
+    CoverageProbes,
+    FuncBody,
+    FuncTransform,
+    InstructionCounter,
+};
 pub use self::{
     engine::{
+        AddressMap,
+        CallReport,
         CompilationMode,
         Config,
+        Diagnostic,
+        DiagnosticKind,
         Engine,
+        EngineStats,
+        FrameInfo,
+        Intrinsic,
+        MAX_INTRINSIC_INOUT,
         ResumableCall,
         ResumableInvocation,
+        SafepointGranularity,
         StackLimits,
+        Symbol,
+        SymbolProvider,
+        SymbolQuery,
+        TrapFrame,
         TypedResumableCall,
         TypedResumableInvocation,
+        TypedYieldedInvocation,
+        WasmBacktrace,
+        WasmFeatures,
+        YieldedInvocation,
     },
     error::Error,
     externref::ExternRef,
@@ -138,7 +196,14 @@ pub use self::{
         Func,
         FuncRef,
         FuncType,
+        HostCall,
+        HostCallLog,
+        HostCallOutcome,
+        HostCallRecord,
+        HostCallRecorder,
+        HostCallReplayer,
         IntoFunc,
+        MemoryWrite,
         TypedFunc,
         WasmParams,
         WasmResults,
@@ -149,25 +214,59 @@ pub use self::{
     global::{Global, GlobalType, Mutability},
     instance::{Export, ExportsIter, Extern, ExternType, Instance},
     limits::{ResourceLimiter, StoreLimits, StoreLimitsBuilder},
-    linker::Linker,
-    memory::{Memory, MemoryType},
+    linker::{Linker, ResolvedModule},
+    memory::{Memory, MemoryProtection, MemoryType},
     module::{
+        ArtifactHash,
+        CompileStats,
+        ConstExpr,
         ExportType,
+        FuncAnalysis,
+        FuncCodeSize,
         ImportType,
         InstancePre,
         Module,
+        ModuleAnalysis,
         ModuleExportsIter,
         ModuleImportsIter,
         Read,
     },
-    store::{AsContext, AsContextMut, Store, StoreContext, StoreContextMut},
-    table::{Table, TableType},
-    value::Value,
+    policy::ImportPolicy,
+    scheduler::{Scheduler, TaskId, TaskTurn},
+    store::{
+        AsContext,
+        AsContextMut,
+        InterruptHandle,
+        Store,
+        StoreContext,
+        StoreContextMut,
+        UpdateDeadline,
+        WasmFuncEvent,
+    },
+    table::{Table, TableEvent, TableType},
+    teardown::StoreTeardown,
+    value::{FromValues, IntoValues, Value},
 };
+#[cfg(feature = "macros")]
+pub use wasmi_macros::{bindgen, host_module};
+
+/// Runtime support for the code generated by the [`host_module`] and [`bindgen`] macros.
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod __private {
+    pub use super::macro_support::{
+        exported_memory,
+        guest_bool,
+        guest_bytes,
+        guest_int,
+        guest_str,
+        IntoError,
+    };
+}
 use self::{
     func::{FuncEntity, FuncIdx},
     global::{GlobalEntity, GlobalIdx},
-    instance::{InstanceEntity, InstanceEntityBuilder, InstanceIdx},
+    instance::{InstanceEntity, InstanceEntityBuilder, InstanceIdx, InstanceInit},
     memory::{DataSegmentEntity, DataSegmentIdx, MemoryEntity, MemoryIdx},
     store::{StoreInner, Stored},
     table::{ElementSegment, ElementSegmentEntity, ElementSegmentIdx, TableEntity, TableIdx},
@@ -1,4 +1,5 @@
 use crate::{
+    engine::{bytecode::IntrinsicIdx, DedupFuncType, Intrinsic},
     func::{FuncEntity, HostFuncEntity, HostFuncTrampolineEntity},
     module::{ImportName, ImportType},
     AsContext,
@@ -11,6 +12,7 @@ use crate::{
     Func,
     FuncType,
     GlobalType,
+    Instance,
     InstancePre,
     IntoFunc,
     MemoryType,
@@ -18,11 +20,7 @@ use crate::{
     TableType,
     Value,
 };
-use alloc::{
-    collections::{btree_map::Entry, BTreeMap},
-    sync::Arc,
-    vec::Vec,
-};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use core::{
     fmt,
     fmt::{Debug, Display},
@@ -30,6 +28,13 @@ use core::{
     ops::Deref,
 };
 
+// Note: Hashed lookups keep import resolution fast for Wasm modules with
+//       thousands of imports. Without `std` we fall back to ordered maps.
+#[cfg(not(feature = "std"))]
+use alloc::collections::{btree_map::Entry, BTreeMap as Map};
+#[cfg(feature = "std")]
+use std::collections::{hash_map::Entry, HashMap as Map};
+
 /// An error that may occur upon operating with [`Linker`] instances.
 #[derive(Debug)]
 pub enum LinkerError {
@@ -94,7 +99,7 @@ pub enum LinkerError {
 
 impl LinkerError {
     /// Creates a new [`LinkerError`] for when an imported definition was not found.
-    fn missing_definition(import: &ImportType) -> Self {
+    pub(crate) fn missing_definition(import: &ImportType) -> Self {
         Self::MissingDefinition {
             name: import.import_name().clone(),
             ty: import.ty().clone(),
@@ -111,7 +116,11 @@ impl LinkerError {
     }
 
     /// Create a new [`LinkerError`] for when a [`FuncType`] mismatched.
-    fn func_type_mismatch(name: &ImportName, expected: &FuncType, found: &FuncType) -> Self {
+    pub(crate) fn func_type_mismatch(
+        name: &ImportName,
+        expected: &FuncType,
+        found: &FuncType,
+    ) -> Self {
         Self::FuncTypeMismatch {
             name: name.clone(),
             expected: expected.clone(),
@@ -180,7 +189,7 @@ impl Display for LinkerError {
                 write!(
                     f,
                     "function type mismatch for import {name}: \
-                    expected {expected:?} but found {found:?}",
+                    expected {expected} but found {found}",
                 )
             }
             Self::InvalidTableSubtype { name, ty, other } => {
@@ -223,7 +232,7 @@ impl Display for LinkerError {
 /// Internally we use [`NonZeroUsize`] so that `Option<Symbol>` can
 /// be space optimized easily by the compiler. This is important since
 /// in [`ImportKey`] we are making extensive use of `Option<Symbol>`.
-#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct Symbol(NonZeroUsize);
 
@@ -250,7 +259,7 @@ impl Symbol {
 /// Efficiently interns strings and distributes symbols.
 #[derive(Debug, Default, Clone)]
 pub struct StringInterner {
-    string2idx: BTreeMap<Arc<str>, Symbol>,
+    string2idx: Map<Arc<str>, Symbol>,
     strings: Vec<Arc<str>>,
 }
 
@@ -286,7 +295,7 @@ impl StringInterner {
 }
 
 /// Wasm import keys.
-#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
 struct ImportKey {
     /// The name of the module for the definition.
     module: Symbol,
@@ -337,6 +346,17 @@ impl<T> Definition<T> {
         }
     }
 
+    /// Returns the [`DedupFuncType`] of the [`Definition`] if it is a function.
+    ///
+    /// Returns `None` otherwise.
+    fn func_ty_dedup(&self, ctx: impl AsContext) -> Option<DedupFuncType> {
+        match self {
+            Definition::Extern(Extern::Func(func)) => Some(*func.ty_dedup(&ctx)),
+            Definition::HostFunc(host_func) => Some(*host_func.ty_dedup()),
+            _ => None,
+        }
+    }
+
     /// Returns the [`Func`] of the [`Definition`] if it is a function.
     ///
     /// Returns `None` otherwise.
@@ -356,7 +376,7 @@ impl<T> Definition<T> {
                     .store
                     .alloc_trampoline(host_func.trampoline().clone());
                 let ty_dedup = host_func.ty_dedup();
-                let entity = HostFuncEntity::new(*ty_dedup, trampoline);
+                let entity = HostFuncEntity::new(*ty_dedup, host_func.arity(), trampoline);
                 let func = ctx
                     .as_context_mut()
                     .store
@@ -374,7 +394,7 @@ pub struct DebugDefinitions<'a, T> {
     /// The [`Engine`] of the [`Linker`].
     engine: &'a Engine,
     /// The definitions of the [`Linker`].
-    definitions: &'a BTreeMap<ImportKey, Definition<T>>,
+    definitions: &'a Map<ImportKey, Definition<T>>,
 }
 
 impl<'a, T> DebugDefinitions<'a, T> {
@@ -390,7 +410,10 @@ impl<'a, T> DebugDefinitions<'a, T> {
 impl<'a, T> Debug for DebugDefinitions<'a, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut map = f.debug_map();
-        for (name, definition) in self.definitions {
+        // Note: Sorting the definitions by their keys orders them by definition.
+        let mut definitions = self.definitions.iter().collect::<Vec<_>>();
+        definitions.sort_unstable_by_key(|(key, _)| **key);
+        for (name, definition) in definitions {
             match definition {
                 Definition::Extern(definition) => {
                     map.entry(name, definition);
@@ -440,7 +463,7 @@ pub struct Linker<T> {
     /// Allows to efficiently store strings and deduplicate them..
     strings: StringInterner,
     /// Stores the definitions given their names.
-    definitions: BTreeMap<ImportKey, Definition<T>>,
+    definitions: Map<ImportKey, Definition<T>>,
 }
 
 impl<T> Debug for Linker<T> {
@@ -474,7 +497,7 @@ impl<T> Linker<T> {
         Self {
             engine: engine.clone(),
             strings: StringInterner::default(),
-            definitions: BTreeMap::default(),
+            definitions: Map::default(),
         }
     }
 
@@ -506,6 +529,10 @@ impl<T> Linker<T> {
     /// # Errors
     ///
     /// If there already is a definition under the same name for this [`Linker`].
+    ///
+    /// # Panics
+    ///
+    /// If `ty` has more than `u32::MAX` parameters or results.
     pub fn func_new(
         &mut self,
         module: &str,
@@ -516,7 +543,7 @@ impl<T> Linker<T> {
             + Sync
             + 'static,
     ) -> Result<&mut Self, LinkerError> {
-        let func = HostFuncTrampolineEntity::new(&self.engine, ty, func);
+        let func = HostFuncTrampolineEntity::new(&self.engine, ty, func).with_name(module, name);
         let key = self.import_key(module, name);
         self.insert(key, Definition::HostFunc(func))?;
         Ok(self)
@@ -549,7 +576,7 @@ impl<T> Linker<T> {
         name: &str,
         func: impl IntoFunc<T, Params, Args>,
     ) -> Result<&mut Self, LinkerError> {
-        let func = HostFuncTrampolineEntity::wrap(&self.engine, func);
+        let func = HostFuncTrampolineEntity::wrap(&self.engine, func).with_name(module, name);
         let key = self.import_key(module, name);
         self.insert(key, Definition::HostFunc(func))?;
         Ok(self)
@@ -650,14 +677,79 @@ impl<T> Linker<T> {
         mut context: impl AsContextMut<UserState = T>,
         module: &Module,
     ) -> Result<InstancePre, Error> {
+        self.resolve(&mut context, module)?.instantiate(context)
+    }
+
+    /// Resolves the imports of `module` using the definitions in the [`Linker`].
+    ///
+    /// # Note
+    ///
+    /// The returned [`ResolvedModule`] can be instantiated many times without looking
+    /// up and type checking the definitions of its imports again. This speeds up the
+    /// repeated instantiation of Wasm modules with many imports.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `context` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// - If the linker does not define imports of the [`Module`].
+    /// - If any imported item does not satisfy its type requirements.
+    pub fn resolve<'module>(
+        &self,
+        context: impl AsContext<UserState = T>,
+        module: &'module Module,
+    ) -> Result<ResolvedModule<'module, T>, Error> {
         assert!(Engine::same(self.engine(), context.as_context().engine()));
         // TODO: possibly add further resource limtation here on number of externals.
         // Not clear that user can't import the same external lots of times to inflate this.
+        let imports = module
+            .imports()
+            .map(|import| self.resolve_import(&context, &import))
+            .collect::<Result<Box<[_]>, Error>>()?;
+        Ok(ResolvedModule { module, imports })
+    }
+
+    /// Hot-reloads `instance` with the code of `module` using the definitions in the [`Linker`].
+    ///
+    /// # Note
+    ///
+    /// - The linear memories and global variables of `instance` are preserved and
+    ///   reused for `module` which therefore must have a compatible layout.
+    /// - Functions and tables are replaced by the ones of `module` and its element
+    ///   segments are applied again. Its data segments and start function are ignored.
+    /// - Existing [`Func`] and [`Table`] handles keep referring to the old entities.
+    ///   Use the exports of `instance` to access the reloaded entities.
+    ///
+    /// This allows hosts to live-reload guest code without losing its state.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `context` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Store`] is currently executing, e.g. when called from a host function.
+    /// - If the linker does not define imports of the reloaded [`Module`].
+    /// - If any imported item does not satisfy its type requirements.
+    /// - If the linear memories or global variables of `instance` are incompatible with `module`.
+    ///
+    /// [`Func`]: crate::Func
+    /// [`Table`]: crate::Table
+    /// [`Store`]: crate::Store
+    pub fn reload(
+        &self,
+        mut context: impl AsContextMut<UserState = T>,
+        instance: &Instance,
+        module: &Module,
+    ) -> Result<(), Error> {
+        assert!(Engine::same(self.engine(), context.as_context().engine()));
         let externals = module
             .imports()
             .map(|import| self.process_import(&mut context, import))
             .collect::<Result<Vec<Extern>, Error>>()?;
-        module.instantiate(context, externals)
+        module.reload(context, instance, externals)
     }
 
     /// Processes a single [`Module`] import.
@@ -671,35 +763,62 @@ impl<T> Linker<T> {
     /// If the imported item does not satisfy constraints set by the [`Module`].
     fn process_import(
         &self,
-        mut context: impl AsContextMut<UserState = T>,
+        context: impl AsContextMut<UserState = T>,
         import: ImportType,
     ) -> Result<Extern, Error> {
+        context.as_context().store.check_import(&import)?;
+        let resolved = self.resolve_import(&context, &import)?;
+        Ok(resolved.to_extern(context))
+    }
+
+    /// Resolves a single [`Module`] import to its definition.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `context` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// If the imported item does not satisfy constraints set by the [`Module`].
+    fn resolve_import(
+        &self,
+        context: impl AsContext<UserState = T>,
+        import: &ImportType,
+    ) -> Result<ResolvedImport<T>, Error> {
         assert!(Engine::same(self.engine(), context.as_context().engine()));
         let import_name = import.import_name();
         let module_name = import.module();
         let field_name = import.name();
-        let resolved = self
-            .get_definition(context.as_context(), module_name, field_name)
-            .ok_or_else(|| LinkerError::missing_definition(&import))?;
-        let invalid_type = || LinkerError::invalid_type_definition(&import, &resolved.ty(&context));
+        let Some(resolved) = self.get_definition(context.as_context(), module_name, field_name)
+        else {
+            // Note: Intrinsics are only used for imports without a definition
+            //       in the linker so that explicit definitions always take precedence.
+            if let ExternType::Func(expected_type) = import.ty() {
+                if let Some((index, intrinsic)) =
+                    self.engine().get_intrinsic_by_name(module_name, field_name)
+                {
+                    if intrinsic.ty() == expected_type {
+                        return Ok(ResolvedImport::Intrinsic(index, intrinsic));
+                    }
+                }
+            }
+            return Err(Error::from(LinkerError::missing_definition(import)));
+        };
+        let invalid_type = || LinkerError::invalid_type_definition(import, &resolved.ty(&context));
         match import.ty() {
             ExternType::Func(expected_type) => {
-                let found_type = resolved
-                    .ty(&context)
-                    .func()
-                    .cloned()
-                    .ok_or_else(invalid_type)?;
-                if &found_type != expected_type {
+                let found_type = resolved.func_ty_dedup(&context).ok_or_else(invalid_type)?;
+                let engine = context.as_context().store.engine();
+                // Note: Comparing in place avoids cloning the found function type.
+                if !engine.resolve_func_type(&found_type, |found| found == expected_type) {
+                    let found_type = engine.resolve_func_type(&found_type, FuncType::clone);
                     return Err(Error::from(LinkerError::func_type_mismatch(
                         import_name,
                         expected_type,
                         &found_type,
                     )));
                 }
-                let func = resolved
-                    .as_func(&mut context)
-                    .expect("already asserted that `resolved` is a function");
-                Ok(Extern::Func(func))
+                Ok(ResolvedImport::Definition(resolved.clone()))
             }
             ExternType::Table(expected_type) => {
                 let table = resolved
@@ -707,11 +826,13 @@ impl<T> Linker<T> {
                     .copied()
                     .and_then(Extern::into_table)
                     .ok_or_else(invalid_type)?;
-                let found_type = table.dynamic_ty(context);
+                let found_type = table.dynamic_ty(&context);
                 found_type.is_subtype_or_err(expected_type).map_err(|_| {
                     LinkerError::table_type_mismatch(import_name, expected_type, &found_type)
                 })?;
-                Ok(Extern::Table(table))
+                Ok(ResolvedImport::Definition(Definition::Extern(
+                    Extern::Table(table),
+                )))
             }
             ExternType::Memory(expected_type) => {
                 let memory = resolved
@@ -719,11 +840,13 @@ impl<T> Linker<T> {
                     .copied()
                     .and_then(Extern::into_memory)
                     .ok_or_else(invalid_type)?;
-                let found_type = memory.dynamic_ty(context);
+                let found_type = memory.dynamic_ty(&context);
                 found_type.is_subtype_or_err(expected_type).map_err(|_| {
                     LinkerError::invalid_memory_subtype(import_name, expected_type, &found_type)
                 })?;
-                Ok(Extern::Memory(memory))
+                Ok(ResolvedImport::Definition(Definition::Extern(
+                    Extern::Memory(memory),
+                )))
             }
             ExternType::Global(expected_type) => {
                 let global = resolved
@@ -731,7 +854,7 @@ impl<T> Linker<T> {
                     .copied()
                     .and_then(Extern::into_global)
                     .ok_or_else(invalid_type)?;
-                let found_type = global.ty(context);
+                let found_type = global.ty(&context);
                 if &found_type != expected_type {
                     return Err(Error::from(LinkerError::global_type_mismatch(
                         import_name,
@@ -739,13 +862,97 @@ impl<T> Linker<T> {
                         &found_type,
                     )));
                 }
-                Ok(Extern::Global(global))
+                Ok(ResolvedImport::Definition(Definition::Extern(
+                    Extern::Global(global),
+                )))
             }
         }
     }
 }
 
-#[cfg(test)]
+/// A [`Module`] import resolved by a [`Linker`].
+enum ResolvedImport<T> {
+    /// The import is satisfied by a [`Linker`] definition.
+    Definition(Definition<T>),
+    /// The import is satisfied by an [`Intrinsic`] of the [`Engine`].
+    Intrinsic(IntrinsicIdx, Intrinsic),
+}
+
+impl<T> ResolvedImport<T> {
+    /// Returns the [`Extern`] satisfying the import within `ctx`.
+    ///
+    /// # Note
+    ///
+    /// This allocates a new [`Func`] on the `ctx` if the import is satisfied
+    /// by a [`Linker`] defined host function or an [`Intrinsic`].
+    fn to_extern(&self, ctx: impl AsContextMut<UserState = T>) -> Extern {
+        match self {
+            Self::Definition(Definition::Extern(item)) => *item,
+            Self::Definition(definition) => Extern::Func(
+                definition
+                    .as_func(ctx)
+                    .expect("already asserted that `definition` is a function"),
+            ),
+            Self::Intrinsic(index, intrinsic) => Extern::Func(intrinsic.to_func(ctx, *index)),
+        }
+    }
+}
+
+/// A [`Module`] with imports resolved by a [`Linker`].
+///
+/// Created via [`Linker::resolve`] and instantiated via [`ResolvedModule::instantiate`].
+pub struct ResolvedModule<'module, T> {
+    /// The [`Module`] with resolved imports.
+    module: &'module Module,
+    /// The resolved imports of the [`Module`] in the order of their declaration.
+    imports: Box<[ResolvedImport<T>]>,
+}
+
+impl<T> Debug for ResolvedModule<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResolvedModule")
+            .field("module", &self.module)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'module, T> ResolvedModule<'module, T> {
+    /// Returns the [`Module`] with resolved imports.
+    pub fn module(&self) -> &'module Module {
+        self.module
+    }
+
+    /// Instantiates the [`Module`] with its resolved imports.
+    ///
+    /// # Panics
+    ///
+    /// - If the [`Engine`] of the [`Module`] and `context` are not the same.
+    /// - If `context` does not own the [`Extern`] definitions of the resolved imports.
+    ///
+    /// # Errors
+    ///
+    /// - If an import is denied by the import policy of the [`Store`].
+    /// - If the instantiation of the [`Module`] fails.
+    ///
+    /// [`Store`]: crate::Store
+    pub fn instantiate(
+        &self,
+        mut context: impl AsContextMut<UserState = T>,
+    ) -> Result<InstancePre, Error> {
+        assert!(Engine::same(
+            self.module.engine(),
+            context.as_context().engine()
+        ));
+        let mut externals = Vec::with_capacity(self.imports.len());
+        for (import, resolved) in self.module.imports().zip(&self.imports[..]) {
+            context.as_context().store.check_import(&import)?;
+            externals.push(resolved.to_extern(&mut context));
+        }
+        self.module.instantiate_resolved(context, externals)
+    }
+}
+
+#[cfg(all(test, feature = "compiler"))]
 mod tests {
     use wasmi_core::ValueType;
 
@@ -851,4 +1058,47 @@ mod tests {
         wasm_set_b.call(&mut store, 200).unwrap();
         assert_eq!(wasm_get_b.call(&mut store, ()).unwrap(), 200);
     }
+
+    #[test]
+    fn linker_shares_trampolines_by_func_type() {
+        let engine = Engine::default();
+        let mut linker = <Linker<()>>::new(&engine);
+        let ty = |param| FuncType::new([param], [param]);
+        let identity = |_: Caller<()>, params: &[Value], results: &mut [Value]| {
+            results[0] = params[0].clone();
+            Ok(())
+        };
+        linker
+            .func_new("host", "f0", ty(ValueType::I32), identity)
+            .unwrap();
+        linker
+            .func_new("host", "f1", ty(ValueType::I32), identity)
+            .unwrap();
+        linker
+            .func_new("host", "f2", ty(ValueType::I64), identity)
+            .unwrap();
+        let mut store = <Store<()>>::new(&engine, ());
+        let wat = r#"
+                (module
+                    (import "host" "f0" (func $f0 (param i32) (result i32)))
+                    (import "host" "f1" (func $f1 (param i32) (result i32)))
+                    (import "host" "f2" (func $f2 (param i64) (result i64)))
+                    (func (export "run") (param i32) (result i32)
+                        (call $f1 (call $f0 (local.get 0)))
+                    )
+                )
+            "#;
+        let wasm = wat::parse_str(wat).unwrap();
+        let module = Module::new(&engine, &mut &wasm[..]).unwrap();
+        for _ in 0..3 {
+            let instance = linker
+                .instantiate(&mut store, &module)
+                .unwrap()
+                .start(&mut store)
+                .unwrap();
+            let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+            assert_eq!(run.call(&mut store, 42).unwrap(), 42);
+        }
+        assert_eq!(store.len_dynamic_trampolines(), 2);
+    }
 }
@@ -0,0 +1,99 @@
+//! Runtime support for the code generated by the [`host_module`] and [`bindgen`] macros.
+//!
+//! [`host_module`]: crate::host_module
+//! [`bindgen`]: crate::bindgen
+
+use crate::{core::HostError, Caller, Error, Extern, Memory};
+use core::str;
+
+/// Converts the error of a host function returned `Result` into an [`Error`].
+pub trait IntoError {
+    /// Converts `self` into an [`Error`].
+    fn into_error(self) -> Error;
+}
+
+impl IntoError for Error {
+    fn into_error(self) -> Error {
+        self
+    }
+}
+
+impl<E> IntoError for E
+where
+    E: HostError,
+{
+    fn into_error(self) -> Error {
+        Error::host(self)
+    }
+}
+
+/// Returns the linear memory exported as `"memory"` by the instance of the `caller`.
+///
+/// # Errors
+///
+/// If the instance of the `caller` does not export a linear memory named `"memory"`.
+pub fn exported_memory<T>(caller: &Caller<T>) -> Result<Memory, Error> {
+    match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => Ok(memory),
+        _ => Err(Error::new(
+            "host function requires a linear memory exported as `memory`",
+        )),
+    }
+}
+
+/// Converts the Wasm `i32` parameter `value` into a `bool`.
+///
+/// # Errors
+///
+/// If `value` is neither `0` nor `1`.
+pub fn guest_bool(value: i32) -> Result<bool, Error> {
+    match value {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(Error::new(format!(
+            "invalid host function parameter: {value} is not a `bool`"
+        ))),
+    }
+}
+
+/// Converts the Wasm `i32` parameter `value` into an integer of type `T`.
+///
+/// # Errors
+///
+/// If `value` is out of bounds for `T`.
+pub fn guest_int<T>(value: i32) -> Result<T, Error>
+where
+    T: TryFrom<i32>,
+{
+    T::try_from(value).map_err(|_| {
+        Error::new(format!(
+            "invalid host function parameter: {value} is out of bounds for `{}`",
+            core::any::type_name::<T>()
+        ))
+    })
+}
+
+/// Returns the bytes of `memory` at `ptr..ptr+len`.
+///
+/// # Errors
+///
+/// If `ptr..ptr+len` is out of bounds for `memory`.
+pub fn guest_bytes(memory: &[u8], ptr: u32, len: u32) -> Result<&[u8], Error> {
+    let start = ptr as usize;
+    start
+        .checked_add(len as usize)
+        .and_then(|end| memory.get(start..end))
+        .ok_or_else(|| Error::from(crate::core::TrapCode::MemoryOutOfBounds))
+}
+
+/// Returns the UTF-8 string of `memory` at `ptr..ptr+len`.
+///
+/// # Errors
+///
+/// - If `ptr..ptr+len` is out of bounds for `memory`.
+/// - If the bytes at `ptr..ptr+len` are not valid UTF-8.
+pub fn guest_str(memory: &[u8], ptr: u32, len: u32) -> Result<&str, Error> {
+    let bytes = guest_bytes(memory, ptr, len)?;
+    str::from_utf8(bytes)
+        .map_err(|error| Error::new(format!("invalid host function parameter: {error}")))
+}
@@ -0,0 +1,263 @@
+//! Helpers to pass serializable Rust values to and from guest linear memory.
+//!
+//! # Note
+//!
+//! Values are encoded via a configurable [`Format`] such as [`Json`] or [`Postcard`]
+//! and placed into memory that is allocated by the guest. For this the guest must
+//! export an allocator that follows the canonical `malloc` and `free` convention:
+//!
+//! - `malloc(size: i32) -> i32` returns a pointer to `size` bytes or `0` upon failure.
+//! - `free(ptr: i32)` deallocates memory returned by `malloc`.
+//!
+//! # Example
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use wasmi::{marshal::{Json, Marshal}, Engine, Linker, Module, Store};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! let wasm = wat::parse_str(r#"
+//!     (module
+//!         (memory (export "memory") 1)
+//!         (global $next (mut i32) (i32.const 1024))
+//!         (func (export "malloc") (param i32) (result i32)
+//!             (global.get $next)
+//!             (global.set $next (i32.add (global.get $next) (local.get 0)))
+//!         )
+//!         (func (export "free") (param i32))
+//!     )
+//! "#).unwrap();
+//! let engine = Engine::default();
+//! let module = Module::new(&engine, &wasm[..]).unwrap();
+//! let mut store = Store::new(&engine, ());
+//! let instance = <Linker<()>>::new(&engine)
+//!     .instantiate(&mut store, &module)
+//!     .unwrap()
+//!     .start(&mut store)
+//!     .unwrap();
+//! let marshal = Marshal::new(&store, &instance, Json).unwrap();
+//! let slice = marshal.write(&mut store, &Point { x: 1, y: 2 }).unwrap();
+//! let point: Point = marshal.take(&mut store, slice).unwrap();
+//! assert_eq!(point, Point { x: 1, y: 2 });
+//! ```
+
+use crate::{core::TrapCode, AsContext, AsContextMut, Error, Extern, Instance, Memory, TypedFunc};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{format, vec::Vec};
+
+/// A serialization format used to encode values in guest memory.
+pub trait Format {
+    /// Serializes `value` into bytes.
+    ///
+    /// # Errors
+    ///
+    /// If `value` cannot be serialized.
+    fn serialize<T>(&self, value: &T) -> Result<Vec<u8>, Error>
+    where
+        T: Serialize + ?Sized;
+
+    /// Deserializes a value of type `T` from `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// If `bytes` do not encode a valid `T`.
+    fn deserialize<T>(&self, bytes: &[u8]) -> Result<T, Error>
+    where
+        T: DeserializeOwned;
+}
+
+/// The JSON [`Format`].
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Json;
+
+impl Format for Json {
+    fn serialize<T>(&self, value: &T) -> Result<Vec<u8>, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        serde_json::to_vec(value)
+            .map_err(|error| Error::new(format!("failed to serialize guest data: {error}")))
+    }
+
+    fn deserialize<T>(&self, bytes: &[u8]) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_slice(bytes)
+            .map_err(|error| Error::new(format!("failed to deserialize guest data: {error}")))
+    }
+}
+
+/// The [postcard] [`Format`].
+///
+/// This compact binary format is well suited for `no_std` guests.
+///
+/// [postcard]: https://docs.rs/postcard
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Postcard;
+
+impl Format for Postcard {
+    fn serialize<T>(&self, value: &T) -> Result<Vec<u8>, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        postcard::to_allocvec(value)
+            .map_err(|error| Error::new(format!("failed to serialize guest data: {error}")))
+    }
+
+    fn deserialize<T>(&self, bytes: &[u8]) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        postcard::from_bytes(bytes)
+            .map_err(|error| Error::new(format!("failed to deserialize guest data: {error}")))
+    }
+}
+
+/// A region of guest linear memory holding an encoded value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GuestSlice {
+    /// The address of the first byte of the region.
+    pub ptr: u32,
+    /// The length of the region in bytes.
+    pub len: u32,
+}
+
+/// Writes and reads serializable values to and from guest linear memory.
+#[derive(Debug)]
+pub struct Marshal<F> {
+    /// The linear memory of the guest.
+    memory: Memory,
+    /// The exported `malloc` function of the guest.
+    malloc: TypedFunc<u32, u32>,
+    /// The exported `free` function of the guest.
+    free: TypedFunc<u32, ()>,
+    /// The format used to encode values.
+    format: F,
+}
+
+impl<F> Marshal<F>
+where
+    F: Format,
+{
+    /// Creates a new [`Marshal`] for the `instance` using its `memory`, `malloc` and `free` exports.
+    ///
+    /// # Errors
+    ///
+    /// If the `instance` is missing one of the exports or if their types mismatch.
+    pub fn new(store: impl AsContext, instance: &Instance, format: F) -> Result<Self, Error> {
+        Self::with_exports(store, instance, format, "memory", "malloc", "free")
+    }
+
+    /// Creates a new [`Marshal`] for the `instance` using custom export names.
+    ///
+    /// # Errors
+    ///
+    /// If the `instance` is missing one of the exports or if their types mismatch.
+    pub fn with_exports(
+        store: impl AsContext,
+        instance: &Instance,
+        format: F,
+        memory: &str,
+        malloc: &str,
+        free: &str,
+    ) -> Result<Self, Error> {
+        let Some(Extern::Memory(memory)) = instance.get_export(&store, memory) else {
+            return Err(Error::new(format!(
+                "missing linear memory export named `{memory}`"
+            )));
+        };
+        let malloc = instance.get_typed_func::<u32, u32>(&store, malloc)?;
+        let free = instance.get_typed_func::<u32, ()>(&store, free)?;
+        Ok(Self {
+            memory,
+            malloc,
+            free,
+            format,
+        })
+    }
+
+    /// Returns the [`Format`] used to encode values.
+    pub fn format(&self) -> &F {
+        &self.format
+    }
+
+    /// Serializes `value` into guest memory allocated via `malloc`.
+    ///
+    /// The returned [`GuestSlice`] is owned by the caller and must be deallocated
+    /// either by the guest or via [`Marshal::free`] or [`Marshal::take`].
+    ///
+    /// # Errors
+    ///
+    /// - If `value` cannot be serialized.
+    /// - If the guest `malloc` traps or fails to allocate.
+    /// - If the allocated guest memory is out of bounds.
+    ///   In this case the allocation is deallocated via `free` again.
+    pub fn write<T>(&self, mut store: impl AsContextMut, value: &T) -> Result<GuestSlice, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let bytes = self.format.serialize(value)?;
+        let len = u32::try_from(bytes.len())
+            .map_err(|_| Error::new("guest data exceeds the 32-bit address space"))?;
+        let ptr = self.malloc.call(&mut store, len)?;
+        if ptr == 0 && len != 0 {
+            return Err(Error::new(format!("guest failed to allocate {len} bytes")));
+        }
+        let slice = GuestSlice { ptr, len };
+        if let Err(error) = self.memory.write(&mut store, ptr as usize, &bytes) {
+            // Note: The original error is more useful to the caller than a `free` trap.
+            let _ = self.free(&mut store, slice);
+            return Err(error.into());
+        }
+        Ok(slice)
+    }
+
+    /// Deserializes a value of type `T` from the guest memory at `slice`.
+    ///
+    /// # Errors
+    ///
+    /// - If `slice` is out of bounds of the guest memory.
+    /// - If the bytes at `slice` do not encode a valid `T`.
+    pub fn read<T>(&self, store: impl AsContext, slice: GuestSlice) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let start = slice.ptr as usize;
+        let bytes = start
+            .checked_add(slice.len as usize)
+            .and_then(|end| self.memory.data(store.as_context()).get(start..end))
+            .ok_or_else(|| Error::from(TrapCode::MemoryOutOfBounds))?;
+        self.format.deserialize(bytes)
+    }
+
+    /// Deallocates the guest memory at `slice` via `free`.
+    ///
+    /// # Errors
+    ///
+    /// If the guest `free` traps.
+    pub fn free(&self, store: impl AsContextMut, slice: GuestSlice) -> Result<(), Error> {
+        self.free.call(store, slice.ptr)
+    }
+
+    /// Deserializes a value of type `T` from the guest memory at `slice` and deallocates it.
+    ///
+    /// # Errors
+    ///
+    /// - If `slice` is out of bounds of the guest memory.
+    /// - If the bytes at `slice` do not encode a valid `T`.
+    /// - If the guest `free` traps.
+    pub fn take<T>(&self, mut store: impl AsContextMut, slice: GuestSlice) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let value = self.read(&store, slice)?;
+        self.free(&mut store, slice)?;
+        Ok(value)
+    }
+}
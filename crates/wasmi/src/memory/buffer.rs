@@ -1,4 +1,5 @@
-use alloc::{vec, vec::Vec};
+use super::MemoryError;
+use alloc::vec::Vec;
 
 /// A `Vec`-based byte buffer implementation.
 ///
@@ -7,7 +8,7 @@ use alloc::{vec, vec::Vec};
 /// This is less efficient than the byte buffer implementation that is
 /// based on actual OS provided virtual memory but it is a safe fallback
 /// solution fitting any platform.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ByteBuffer {
     bytes: Vec<u8>,
 }
@@ -17,22 +18,41 @@ impl ByteBuffer {
     ///
     /// # Errors
     ///
-    /// - If the initial length is 0.
-    /// - If the initial length exceeds the maximum supported limit.
-    pub fn new(initial_len: usize) -> Self {
-        Self {
-            bytes: vec![0x00_u8; initial_len],
-        }
+    /// If the allocation of the byte buffer fails.
+    pub fn new(initial_len: usize) -> Result<Self, MemoryError> {
+        let mut buffer = Self { bytes: Vec::new() };
+        buffer.grow(initial_len)?;
+        Ok(buffer)
     }
 
     /// Grows the byte buffer to the given `new_size`.
     ///
+    /// # Errors
+    ///
+    /// If the allocation of the grown byte buffer fails.
+    ///
     /// # Panics
     ///
     /// If the current size of the [`ByteBuffer`] is larger than `new_size`.
-    pub fn grow(&mut self, new_size: usize) {
+    pub fn grow(&mut self, new_size: usize) -> Result<(), MemoryError> {
         assert!(new_size >= self.len());
+        self.bytes
+            .try_reserve_exact(new_size - self.len())
+            .map_err(|_| MemoryError::OutOfBoundsAllocation)?;
         self.bytes.resize(new_size, 0x00_u8);
+        Ok(())
+    }
+
+    /// Resets the byte buffer to `len` zeroed bytes and frees the excess capacity.
+    ///
+    /// # Panics
+    ///
+    /// If the current size of the [`ByteBuffer`] is smaller than `len`.
+    pub fn reset(&mut self, len: usize) {
+        assert!(len <= self.len());
+        self.bytes.truncate(len);
+        self.bytes.fill(0x00_u8);
+        self.bytes.shrink_to_fit();
     }
 
     /// Returns the length of the byte buffer in bytes.
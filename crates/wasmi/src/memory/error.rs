@@ -22,6 +22,12 @@ pub enum MemoryError {
     },
     /// Tried to create too many memories
     TooManyMemories,
+    /// Tried to write to a read-only region of linear memory.
+    WriteProtected,
+    /// Tried to reset a linear memory without [`Config::memory_reset`] enabled.
+    ///
+    /// [`Config::memory_reset`]: crate::Config::memory_reset
+    ResetDisabled,
 }
 
 impl Display for MemoryError {
@@ -45,6 +51,12 @@ impl Display for MemoryError {
             Self::TooManyMemories => {
                 write!(f, "too many memories")
             }
+            Self::WriteProtected => {
+                write!(f, "write to read-only linear memory")
+            }
+            Self::ResetDisabled => {
+                write!(f, "linear memory reset is disabled by the engine config")
+            }
         }
     }
 }
@@ -0,0 +1,181 @@
+use super::MemoryError;
+use core::{ptr, ptr::NonNull, slice};
+
+/// A byte buffer implementation based on OS provided virtual memory.
+///
+/// # Note
+///
+/// Growing the byte buffer remaps its virtual memory via `mremap` instead of
+/// allocating a new buffer, copying over the bytes and freeing the old buffer.
+/// The kernel moves the page mappings if required and never copies the bytes.
+/// This keeps `memory.grow` cheap even for linear memories of several hundred
+/// MiB and avoids transiently doubling their memory consumption.
+///
+/// Newly mapped pages are lazily committed and zeroed by the kernel.
+#[derive(Debug)]
+pub struct ByteBuffer {
+    /// The start of the mapped virtual memory.
+    ///
+    /// This is dangling if `capacity` is zero since then nothing is mapped.
+    ptr: NonNull<u8>,
+    /// The length of the byte buffer in bytes.
+    len: usize,
+    /// The length of the mapped virtual memory in bytes.
+    ///
+    /// # Note
+    ///
+    /// This is at least `len` and all bytes beyond `len` are zero.
+    capacity: usize,
+}
+
+// Safety: The `ByteBuffer` exclusively owns its mapped virtual memory.
+unsafe impl Send for ByteBuffer {}
+// Safety: The `ByteBuffer` only hands out shared slices via shared references.
+unsafe impl Sync for ByteBuffer {}
+
+impl ByteBuffer {
+    /// Creates a new byte buffer with the given initial length.
+    ///
+    /// # Errors
+    ///
+    /// If the allocation of the byte buffer fails.
+    pub fn new(initial_len: usize) -> Result<Self, MemoryError> {
+        let mut buffer = Self {
+            ptr: NonNull::dangling(),
+            len: 0,
+            capacity: 0,
+        };
+        buffer.grow(initial_len)?;
+        Ok(buffer)
+    }
+
+    /// Grows the byte buffer to the given `new_size`.
+    ///
+    /// # Errors
+    ///
+    /// If the allocation of the grown byte buffer fails.
+    ///
+    /// # Panics
+    ///
+    /// If the current size of the [`ByteBuffer`] is larger than `new_size`.
+    pub fn grow(&mut self, new_size: usize) -> Result<(), MemoryError> {
+        assert!(new_size >= self.len());
+        if new_size <= self.capacity {
+            self.len = new_size;
+            return Ok(());
+        }
+        let ptr = match self.capacity {
+            // Safety: Anonymous private mappings do not alias any other memory.
+            0 => unsafe {
+                libc::mmap(
+                    ptr::null_mut(),
+                    new_size,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                    -1,
+                    0,
+                )
+            },
+            // Safety: `ptr` and `capacity` describe the current mapping of the byte buffer
+            //         which is no longer used if remapping succeeds.
+            capacity => unsafe {
+                libc::mremap(
+                    self.ptr.as_ptr().cast(),
+                    capacity,
+                    new_size,
+                    libc::MREMAP_MAYMOVE,
+                )
+            },
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(MemoryError::OutOfBoundsAllocation);
+        }
+        self.ptr = NonNull::new(ptr.cast()).ok_or(MemoryError::OutOfBoundsAllocation)?;
+        self.len = new_size;
+        self.capacity = new_size;
+        Ok(())
+    }
+
+    /// Resets the byte buffer to `len` zeroed bytes and releases all pages back to the OS.
+    ///
+    /// # Note
+    ///
+    /// The pages are decommitted via `madvise(MADV_DONTNEED)` which lets the kernel
+    /// provide fresh zeroed pages upon their next access. The mapping beyond `len`
+    /// is shrunk in place if possible.
+    ///
+    /// # Panics
+    ///
+    /// If the current size of the [`ByteBuffer`] is smaller than `len`.
+    pub fn reset(&mut self, len: usize) {
+        assert!(len <= self.len());
+        if self.capacity == 0 {
+            return;
+        }
+        // Safety: `ptr` and `capacity` describe the current mapping of the byte buffer.
+        let result =
+            unsafe { libc::madvise(self.ptr.as_ptr().cast(), self.capacity, libc::MADV_DONTNEED) };
+        if result != 0 {
+            // Safety: `ptr` is valid for writes of `capacity` bytes.
+            unsafe { ptr::write_bytes(self.ptr.as_ptr(), 0x00_u8, self.capacity) };
+        }
+        self.len = len;
+        if len == 0 {
+            // Note: Dropping the old buffer unmaps its virtual memory.
+            *self = Self {
+                ptr: NonNull::dangling(),
+                len: 0,
+                capacity: 0,
+            };
+            return;
+        }
+        // Safety: Shrinking a mapping without `MREMAP_MAYMOVE` happens in place
+        //         and unmaps the pages beyond `len` which are no longer used.
+        let ptr = unsafe { libc::mremap(self.ptr.as_ptr().cast(), self.capacity, len, 0) };
+        if ptr != libc::MAP_FAILED {
+            self.capacity = len;
+        }
+    }
+
+    /// Returns the length of the byte buffer in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a shared slice to the bytes underlying to the byte buffer.
+    pub fn data(&self) -> &[u8] {
+        // Safety: `ptr` is valid for reads of `len` bytes or dangling if `capacity` is zero.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Returns an exclusive slice to the bytes underlying to the byte buffer.
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        // Safety: `ptr` is valid for writes of `len` bytes or dangling if `capacity` is zero.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Clone for ByteBuffer {
+    fn clone(&self) -> Self {
+        let mut buffer = Self::new(self.len).unwrap_or_else(|error| {
+            panic!(
+                "failed to allocate {} bytes for cloned byte buffer: {error}",
+                self.len
+            )
+        });
+        buffer.data_mut().copy_from_slice(self.data());
+        buffer
+    }
+}
+
+impl Drop for ByteBuffer {
+    fn drop(&mut self) {
+        if self.capacity == 0 {
+            return;
+        }
+        // Safety: `ptr` and `capacity` describe the current mapping of the byte buffer.
+        unsafe {
+            libc::munmap(self.ptr.as_ptr().cast(), self.capacity);
+        }
+    }
+}
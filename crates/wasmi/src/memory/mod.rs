@@ -1,20 +1,31 @@
+#[cfg(not(all(target_os = "linux", feature = "std")))]
 mod buffer;
 mod data;
 mod error;
+#[cfg(all(target_os = "linux", feature = "std"))]
+mod mmap_buffer;
+mod protect;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(not(all(target_os = "linux", feature = "std")))]
 use self::buffer::ByteBuffer;
+#[cfg(all(target_os = "linux", feature = "std"))]
+use self::mmap_buffer::ByteBuffer;
+use self::protect::ProtectedRanges;
 pub use self::{
     data::{DataSegment, DataSegmentEntity, DataSegmentIdx},
     error::MemoryError,
+    protect::MemoryProtection,
 };
 use super::{AsContext, AsContextMut, StoreContext, StoreContextMut, Stored};
 use crate::{
     error::EntityGrowError,
     store::{Fuel, ResourceLimiterRef},
 };
+use alloc::{sync::Arc, vec::Vec};
+use core::ops::Range;
 use wasmi_arena::ArenaIndex;
 use wasmi_core::{Pages, TrapCode};
 
@@ -122,11 +133,17 @@ impl MemoryType {
 }
 
 /// A linear memory entity.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MemoryEntity {
     bytes: ByteBuffer,
     memory_type: MemoryType,
     current_pages: Pages,
+    /// The active data segments applied to the linear memory upon instantiation.
+    ///
+    /// These are re-applied by [`MemoryEntity::reset`].
+    init_segments: Vec<(usize, Arc<[u8]>)>,
+    /// The read-only regions of the linear memory.
+    protected: ProtectedRanges,
 }
 
 impl MemoryEntity {
@@ -149,19 +166,27 @@ impl MemoryEntity {
             }
         }
 
-        if let Some(initial_len) = initial_len {
-            let memory = Self {
-                bytes: ByteBuffer::new(initial_len),
-                memory_type,
-                current_pages: initial_pages,
-            };
-            Ok(memory)
-        } else {
-            let err = MemoryError::OutOfBoundsAllocation;
-            if let Some(limiter) = limiter.as_resource_limiter() {
-                limiter.memory_grow_failed(&err)
+        let bytes = initial_len
+            .filter(|_| !limiter.fail_allocation())
+            .ok_or(MemoryError::OutOfBoundsAllocation)
+            .and_then(ByteBuffer::new);
+        match bytes {
+            Ok(bytes) => {
+                let memory = Self {
+                    bytes,
+                    memory_type,
+                    current_pages: initial_pages,
+                    init_segments: Vec::new(),
+                    protected: ProtectedRanges::default(),
+                };
+                Ok(memory)
+            }
+            Err(err) => {
+                if let Some(limiter) = limiter.as_resource_limiter() {
+                    limiter.memory_grow_failed(&err)
+                }
+                Err(err)
             }
-            Err(err)
         }
     }
 
@@ -259,11 +284,96 @@ impl MemoryEntity {
         // 1. The resource limiter validated the memory consumption.
         // 2. The growth is within bounds.
         // 3. There is enough fuel for the operation.
-        self.bytes.grow(new_size);
+        //
+        // The allocation of the grown linear memory may still fail
+        // in which case the growth fails instead of aborting the process.
+        if limiter.fail_allocation() || self.bytes.grow(new_size).is_err() {
+            return notify_limiter(limiter, EntityGrowError::InvalidGrow);
+        }
         self.current_pages = new_pages;
         Ok(current_pages)
     }
 
+    /// Records the active data segment `bytes` applied at `offset` upon instantiation.
+    pub fn push_init_segment(&mut self, offset: usize, bytes: Arc<[u8]>) {
+        self.init_segments.push((offset, bytes));
+    }
+
+    /// Resets the linear memory to its contents after instantiation.
+    ///
+    /// # Note
+    ///
+    /// This shrinks the linear memory back to its initial size, releases all its
+    /// pages back to the OS if possible and then re-applies the active data segments
+    /// recorded via [`MemoryEntity::push_init_segment`].
+    ///
+    /// # Errors
+    ///
+    /// If a recorded data segment does not fit into the initial linear memory
+    /// in which case the linear memory is left untouched.
+    pub fn reset(&mut self) -> Result<(), MemoryError> {
+        let initial_pages = self.memory_type.initial_pages();
+        let initial_len = initial_pages
+            .to_bytes()
+            .unwrap_or_else(|| panic!("initial pages must fit into memory due to invariants"));
+        let fits = |(offset, bytes): &(usize, Arc<[u8]>)| {
+            offset
+                .checked_add(bytes.len())
+                .is_some_and(|end| end <= initial_len)
+        };
+        if !self.init_segments.iter().all(fits) {
+            return Err(MemoryError::OutOfBoundsAccess);
+        }
+        self.bytes.reset(initial_len);
+        self.current_pages = initial_pages;
+        let data = self.bytes.data_mut();
+        for (offset, bytes) in &self.init_segments {
+            data[*offset..][..bytes.len()].copy_from_slice(bytes);
+        }
+        Ok(())
+    }
+
+    /// Sets the `protection` of the bytes of the linear memory within `range`.
+    ///
+    /// # Errors
+    ///
+    /// If `range` is out of bounds of the linear memory.
+    pub fn protect(
+        &mut self,
+        range: Range<usize>,
+        protection: MemoryProtection,
+    ) -> Result<(), MemoryError> {
+        if range.start > range.end || range.end > self.data().len() {
+            return Err(MemoryError::OutOfBoundsAccess);
+        }
+        self.protected.set(range, protection);
+        Ok(())
+    }
+
+    /// Returns the read-only byte ranges of the linear memory in ascending order.
+    pub fn protected_ranges(&self) -> &[Range<usize>] {
+        self.protected.ranges()
+    }
+
+    /// Returns `true` if the linear memory has read-only regions.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        !self.protected.ranges().is_empty()
+    }
+
+    /// Returns `Ok` if none of the `len` bytes starting at `offset` is read-only.
+    ///
+    /// # Errors
+    ///
+    /// If any of the bytes is read-only.
+    #[inline]
+    pub fn check_writable(&self, offset: usize, len: usize) -> Result<(), TrapCode> {
+        if self.is_protected() && self.protected.overlaps(offset, len) {
+            return Err(TrapCode::MemoryWriteProtected);
+        }
+        Ok(())
+    }
+
     /// Returns a shared slice to the bytes underlying to the byte buffer.
     pub fn data(&self) -> &[u8] {
         self.bytes.data()
@@ -295,9 +405,13 @@ impl MemoryEntity {
     ///
     /// # Errors
     ///
-    /// If this operation accesses out of bounds linear memory.
+    /// - If this operation accesses out of bounds linear memory.
+    /// - If this operation writes to a read-only region of the linear memory.
     pub fn write(&mut self, offset: usize, buffer: &[u8]) -> Result<(), MemoryError> {
         let len_buffer = buffer.len();
+        if self.protected.overlaps(offset, len_buffer) {
+            return Err(MemoryError::WriteProtected);
+        }
         let slice = self
             .data_mut()
             .get_mut(offset..(offset + len_buffer))
@@ -305,6 +419,58 @@ impl MemoryEntity {
         slice.copy_from_slice(buffer);
         Ok(())
     }
+
+    /// Returns the bounds checked and writable destination range for a copy of `len` bytes to `offset`.
+    ///
+    /// # Errors
+    ///
+    /// - If the destination range is out of bounds of the linear memory.
+    /// - If the destination range overlaps a read-only region of the linear memory.
+    fn copy_dst_range(&self, offset: usize, len: usize) -> Result<Range<usize>, MemoryError> {
+        let end = offset
+            .checked_add(len)
+            .filter(|&end| end <= self.data().len())
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        if self.protected.overlaps(offset, len) {
+            return Err(MemoryError::WriteProtected);
+        }
+        Ok(offset..end)
+    }
+
+    /// Copies the bytes of `src` within the linear memory to `dst_offset`.
+    ///
+    /// The source and destination ranges may overlap.
+    ///
+    /// # Errors
+    ///
+    /// - If this operation accesses out of bounds linear memory.
+    /// - If this operation writes to a read-only region of the linear memory.
+    pub fn copy_within(&mut self, src: Range<usize>, dst_offset: usize) -> Result<(), MemoryError> {
+        if self.data().get(src.clone()).is_none() {
+            return Err(MemoryError::OutOfBoundsAccess);
+        }
+        self.copy_dst_range(dst_offset, src.len())?;
+        self.data_mut().copy_within(src, dst_offset);
+        Ok(())
+    }
+
+    /// Copies the bytes of `src` of this linear memory to `dst_offset` of the `dst` linear memory.
+    ///
+    /// # Errors
+    ///
+    /// - If this operation accesses out of bounds linear memory.
+    /// - If this operation writes to a read-only region of the `dst` linear memory.
+    pub fn copy_to(
+        &self,
+        dst: &mut MemoryEntity,
+        src: Range<usize>,
+        dst_offset: usize,
+    ) -> Result<(), MemoryError> {
+        let src = self.data().get(src).ok_or(MemoryError::OutOfBoundsAccess)?;
+        let dst_range = dst.copy_dst_range(dst_offset, src.len())?;
+        dst.data_mut()[dst_range].copy_from_slice(src);
+        Ok(())
+    }
 }
 
 /// A Wasm linear memory reference.
@@ -400,10 +566,15 @@ impl Memory {
             .as_context_mut()
             .store
             .store_inner_and_resource_limiter_ref();
-        inner
-            .resolve_memory_mut(self)
+        let entity = inner.resolve_memory_mut(self);
+        let old_pages = entity
             .grow(additional, None, &mut limiter)
-            .map_err(|_| MemoryError::OutOfBoundsGrowth)
+            .map_err(|_| MemoryError::OutOfBoundsGrowth)?;
+        let new_pages = entity.current_pages();
+        if u32::from(additional) != 0 {
+            inner.notify_memory_grow(*self, old_pages, new_pages);
+        }
+        Ok(old_pages)
     }
 
     /// Returns a shared slice to the bytes underlying the [`Memory`].
@@ -438,6 +609,129 @@ impl Memory {
         (memory.data_mut(), store)
     }
 
+    /// Returns a shared slice to the bytes underlying the [`Memory`], and a shared
+    /// reference to the user provided state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn data_and_store<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContext<'a, T>>,
+    ) -> (&'a [u8], &'a T) {
+        let store = ctx.into().store;
+        (store.inner.resolve_memory(self).data(), store.data())
+    }
+
+    /// Returns exclusive slices to the bytes underlying the [`Memory`] and the `other`
+    /// [`Memory`], and an exclusive reference to the user provided state.
+    ///
+    /// # Note
+    ///
+    /// This allows host functions to copy between two linear memories while
+    /// accessing their host state without intermediate buffers.
+    ///
+    /// # Panics
+    ///
+    /// - If `ctx` does not own this [`Memory`] or the `other` [`Memory`].
+    /// - If this [`Memory`] and the `other` [`Memory`] are the same.
+    pub fn data_pair_and_store_mut<'a, T: 'a>(
+        &self,
+        other: &Memory,
+        ctx: impl Into<StoreContextMut<'a, T>>,
+    ) -> (&'a mut [u8], &'a mut [u8], &'a mut T) {
+        let (fst, snd, store) = ctx
+            .into()
+            .store
+            .resolve_memory_pair_and_state_mut(self, other);
+        (fst.data_mut(), snd.data_mut(), store)
+    }
+
+    /// Resets the [`Memory`] to its contents right after instantiation.
+    ///
+    /// # Note
+    ///
+    /// - The [`Memory`] is shrunk back to its initial size and all its pages are
+    ///   released back to the operating system where supported before the active
+    ///   data segments of the instantiations that initialized it are re-applied.
+    /// - A [`Memory`] that was not initialized by active data segments is zeroed.
+    /// - This allows to recycle an instance for another request instead of
+    ///   instantiating its module again.
+    ///
+    /// # Errors
+    ///
+    /// - If [`Config::memory_reset`] is disabled for the [`Engine`] of the [`Store`].
+    /// - If an active data segment does not fit into the [`Memory`] at its initial size,
+    ///   e.g. if the [`Memory`] was imported after it had been grown. In this case the
+    ///   [`Memory`] is left untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    ///
+    /// [`Config::memory_reset`]: crate::Config::memory_reset
+    /// [`Engine`]: crate::Engine
+    /// [`Store`]: crate::Store
+    pub fn reset(&self, mut ctx: impl AsContextMut) -> Result<(), MemoryError> {
+        let store = &mut ctx.as_context_mut().store.inner;
+        if !store.engine().config().get_memory_reset() {
+            return Err(MemoryError::ResetDisabled);
+        }
+        store.resolve_memory_mut(self).reset()
+    }
+
+    /// Sets the `protection` of the bytes of the [`Memory`] within `range`.
+    ///
+    /// # Note
+    ///
+    /// - Writes to read-only regions by Wasm `store` instructions, `memory.fill`,
+    ///   `memory.copy` and `memory.init` trap with [`TrapCode::MemoryWriteProtected`].
+    /// - [`Memory::write`] fails with [`MemoryError::WriteProtected`] for read-only regions.
+    /// - Slices returned by [`Memory::data_mut`] and similar APIs are not protected.
+    /// - Protections are checked by Wasmi instead of being enforced via `mprotect`
+    ///   so that violations trap instead of faulting and work on all platforms.
+    ///   Once a region has been made read-only all writes of executions within
+    ///   the [`Store`] to linear memory are checked which slightly slows them down.
+    /// - Protections are kept if the [`Memory`] is grown or reset.
+    ///
+    /// # Errors
+    ///
+    /// If `range` is out of bounds of the [`Memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    ///
+    /// [`Store`]: crate::Store
+    pub fn protect(
+        &self,
+        mut ctx: impl AsContextMut,
+        range: Range<usize>,
+        protection: MemoryProtection,
+    ) -> Result<(), MemoryError> {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .resolve_memory_mut(self)
+            .protect(range, protection)
+    }
+
+    /// Returns the read-only byte ranges of the [`Memory`] in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn protected_ranges<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContext<'a, T>>,
+    ) -> &'a [Range<usize>] {
+        ctx.into()
+            .store
+            .inner
+            .resolve_memory(self)
+            .protected_ranges()
+    }
+
     /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
     /// where `n` is the length of `buffer`.
     ///
@@ -466,7 +760,8 @@ impl Memory {
     ///
     /// # Errors
     ///
-    /// If this operation accesses out of bounds linear memory.
+    /// - If this operation accesses out of bounds linear memory.
+    /// - If this operation writes to a read-only region of the [`Memory`].
     ///
     /// # Panics
     ///
@@ -483,4 +778,41 @@ impl Memory {
             .resolve_memory_mut(self)
             .write(offset, buffer)
     }
+
+    /// Copies the bytes of this [`Memory`] within `src` to `dst_offset` of the `dst` [`Memory`].
+    ///
+    /// # Note
+    ///
+    /// - This allows hosts to move data between the linear memories of different
+    ///   instances of the same [`Store`] without an intermediate host buffer.
+    /// - The `dst` [`Memory`] may be this [`Memory`] in which case the source and
+    ///   destination ranges may overlap the same way as with Wasm `memory.copy`.
+    /// - Either both memories are accessed in bounds and all bytes are copied
+    ///   or nothing is copied at all.
+    ///
+    /// # Errors
+    ///
+    /// - If `src` is out of bounds of this [`Memory`].
+    /// - If the destination range is out of bounds of the `dst` [`Memory`].
+    /// - If the destination range overlaps a read-only region of the `dst` [`Memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`] or the `dst` [`Memory`].
+    ///
+    /// [`Store`]: crate::Store
+    pub fn copy_to(
+        &self,
+        mut ctx: impl AsContextMut,
+        dst: &Memory,
+        src: Range<usize>,
+        dst_offset: usize,
+    ) -> Result<(), MemoryError> {
+        let inner = &mut ctx.as_context_mut().store.inner;
+        if self.as_inner() == dst.as_inner() {
+            return inner.resolve_memory_mut(self).copy_within(src, dst_offset);
+        }
+        let (src_memory, dst_memory) = inner.resolve_memory_pair_mut(self, dst);
+        src_memory.copy_to(dst_memory, src, dst_offset)
+    }
 }
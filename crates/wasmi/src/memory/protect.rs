@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The access protection of a region of a linear memory.
+///
+/// Set via [`Memory::protect`](crate::Memory::protect).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryProtection {
+    /// The region may be read but writes to it trap.
+    ReadOnly,
+    /// The region may be read and written.
+    ReadWrite,
+}
+
+/// The read-only regions of a linear memory.
+#[derive(Debug, Default, Clone)]
+pub struct ProtectedRanges {
+    /// The read-only byte ranges.
+    ///
+    /// # Note
+    ///
+    /// The ranges are non-empty, sorted and neither overlap nor touch each other.
+    ranges: Vec<Range<usize>>,
+}
+
+impl ProtectedRanges {
+    /// Returns the read-only byte ranges in ascending order.
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    /// Sets the `protection` of all bytes within `range`.
+    pub fn set(&mut self, range: Range<usize>, protection: MemoryProtection) {
+        if range.is_empty() {
+            return;
+        }
+        let mut merged = range.clone();
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 2);
+        for current in self.ranges.drain(..) {
+            if current.end < range.start || current.start > range.end {
+                ranges.push(current);
+                continue;
+            }
+            match protection {
+                MemoryProtection::ReadOnly => {
+                    merged.start = merged.start.min(current.start);
+                    merged.end = merged.end.max(current.end);
+                }
+                MemoryProtection::ReadWrite => {
+                    if current.start < range.start {
+                        ranges.push(current.start..range.start);
+                    }
+                    if current.end > range.end {
+                        ranges.push(range.end..current.end);
+                    }
+                }
+            }
+        }
+        if let MemoryProtection::ReadOnly = protection {
+            ranges.push(merged);
+        }
+        ranges.sort_unstable_by_key(|range| range.start);
+        self.ranges = ranges;
+    }
+
+    /// Returns `true` if any of the `len` bytes starting at `start` is read-only.
+    pub fn overlaps(&self, start: usize, len: usize) -> bool {
+        if len == 0 {
+            return false;
+        }
+        let end = start.saturating_add(len);
+        let index = self.ranges.partition_point(|range| range.end <= start);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start < end)
+    }
+}
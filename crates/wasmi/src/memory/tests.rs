@@ -14,3 +14,81 @@ fn subtyping_works() {
     assert!(memory_type(0, 1).is_subtype_of(&memory_type(0, None)));
     assert!(!memory_type(0, None).is_subtype_of(&memory_type(0, 1)));
 }
+
+#[test]
+fn byte_buffer_allocation_failure_is_fallible() {
+    assert!(matches!(
+        ByteBuffer::new(usize::MAX),
+        Err(MemoryError::OutOfBoundsAllocation)
+    ));
+    let mut buffer = ByteBuffer::new(10).unwrap();
+    assert!(matches!(
+        buffer.grow(usize::MAX),
+        Err(MemoryError::OutOfBoundsAllocation)
+    ));
+    assert_eq!(buffer.len(), 10);
+    buffer.grow(20).unwrap();
+    assert_eq!(buffer.data(), &[0x00_u8; 20]);
+}
+
+#[test]
+fn byte_buffer_grow_preserves_contents() {
+    let page = Pages::new(1).unwrap().to_bytes().unwrap();
+    let mut buffer = ByteBuffer::new(page).unwrap();
+    buffer.data_mut()[0] = 0x11;
+    buffer.data_mut()[page - 1] = 0x22;
+    // Note: Interleave a second buffer so that growing cannot always happen in place.
+    let _other = ByteBuffer::new(page).unwrap();
+    buffer.grow(64 * page).unwrap();
+    assert_eq!(buffer.len(), 64 * page);
+    assert_eq!(buffer.data()[0], 0x11);
+    assert_eq!(buffer.data()[page - 1], 0x22);
+    assert!(buffer.data()[page..].iter().all(|&byte| byte == 0x00));
+    let cloned = buffer.clone();
+    buffer.data_mut()[0] = 0x33;
+    assert_eq!(cloned.data()[0], 0x11);
+    assert_eq!(cloned.len(), buffer.len());
+}
+
+#[test]
+fn byte_buffer_reset() {
+    let page = Pages::new(1).unwrap().to_bytes().unwrap();
+    let mut buffer = ByteBuffer::new(2 * page).unwrap();
+    buffer.data_mut().fill(0xFF);
+    buffer.reset(page);
+    assert_eq!(buffer.len(), page);
+    assert!(buffer.data().iter().all(|&byte| byte == 0x00));
+    // Note: Bytes regained by growing after a reset must be zeroed as well.
+    buffer.grow(2 * page).unwrap();
+    assert!(buffer.data().iter().all(|&byte| byte == 0x00));
+    buffer.reset(0);
+    assert_eq!(buffer.len(), 0);
+    buffer.grow(page).unwrap();
+    assert!(buffer.data().iter().all(|&byte| byte == 0x00));
+}
+
+#[test]
+fn protected_ranges_merge_and_split() {
+    let mut protected = ProtectedRanges::default();
+    assert!(protected.ranges().is_empty());
+    protected.set(10..20, MemoryProtection::ReadOnly);
+    protected.set(30..40, MemoryProtection::ReadOnly);
+    // Touching and overlapping ranges are merged.
+    protected.set(20..25, MemoryProtection::ReadOnly);
+    protected.set(5..12, MemoryProtection::ReadOnly);
+    assert_eq!(protected.ranges(), &[5..25, 30..40]);
+    // Making the middle of a range writable splits it.
+    protected.set(10..15, MemoryProtection::ReadWrite);
+    assert_eq!(protected.ranges(), &[5..10, 15..25, 30..40]);
+    protected.set(20..35, MemoryProtection::ReadWrite);
+    assert_eq!(protected.ranges(), &[5..10, 15..20, 35..40]);
+    assert!(protected.overlaps(9, 1));
+    assert!(protected.overlaps(0, 6));
+    assert!(protected.overlaps(12, 8));
+    assert!(!protected.overlaps(10, 5));
+    assert!(!protected.overlaps(20, 15));
+    assert!(!protected.overlaps(19, 0));
+    assert!(!protected.overlaps(40, usize::MAX));
+    protected.set(0..50, MemoryProtection::ReadWrite);
+    assert!(protected.ranges().is_empty());
+}
@@ -0,0 +1,296 @@
+use alloc::vec::Vec;
+#[cfg(feature = "compiler")]
+use {
+    alloc::boxed::Box,
+    wasmparser::{FunctionBody, Operator},
+};
+
+/// The static analysis results of a function of a [`Module`].
+///
+/// Returned by [`ModuleAnalysis::funcs`].
+///
+/// [`Module`]: crate::Module
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FuncAnalysis {
+    /// The index of the function within the Wasm module including imported functions.
+    func_index: u32,
+    /// The number of value stack cells of a call frame of the function if translated already.
+    stack_cells: Option<u16>,
+    /// The maximum number of nested call frames of a call to the function if bounded.
+    max_call_depth: Option<u32>,
+    /// The maximum number of linear memory pages grown by a call to the function if bounded.
+    max_memory_growth: Option<u64>,
+}
+
+impl FuncAnalysis {
+    /// Returns the index of the function within the Wasm module.
+    ///
+    /// # Note
+    ///
+    /// The index space includes imported functions.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the number of value stack cells that a call frame of the function occupies.
+    ///
+    /// # Note
+    ///
+    /// - This includes the registers and the function local constant values of the function.
+    /// - Returns `None` if the function has not yet been translated which is the
+    ///   case for functions that have not yet been called with lazy compilation.
+    pub fn stack_cells(&self) -> Option<u16> {
+        self.stack_cells
+    }
+
+    /// Returns the maximum number of nested Wasm call frames of a call to the function.
+    ///
+    /// # Note
+    ///
+    /// - A function that does not call other Wasm functions has a call depth of 1.
+    /// - Calls to imported functions are not accounted for.
+    /// - Returns `None` if the call depth is unbounded or unknown which is the case
+    ///   for functions that may recurse or that perform indirect calls.
+    pub fn max_call_depth(&self) -> Option<u32> {
+        self.max_call_depth
+    }
+
+    /// Returns the maximum number of linear memory pages grown by the function itself.
+    ///
+    /// # Note
+    ///
+    /// - This is the sum of the constant deltas of all `memory.grow` instructions of the function.
+    /// - Growth performed by called functions is not accounted for.
+    /// - Returns `None` if the growth is unknown which is the case for functions with
+    ///   `memory.grow` instructions with non-constant deltas or within loops.
+    pub fn max_memory_growth(&self) -> Option<u64> {
+        self.max_memory_growth
+    }
+
+    /// Returns the [`FuncAnalysis`] with the given number of `stack_cells`.
+    pub(crate) fn with_stack_cells(mut self, stack_cells: Option<u16>) -> Self {
+        self.stack_cells = stack_cells;
+        self
+    }
+}
+
+/// The static analysis results of a [`Module`].
+///
+/// Returned by [`Module::analysis`].
+///
+/// [`Module`]: crate::Module
+/// [`Module::analysis`]: crate::Module::analysis
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleAnalysis {
+    /// The analysis results of all internally defined functions.
+    funcs: Vec<FuncAnalysis>,
+}
+
+impl ModuleAnalysis {
+    /// Creates a new [`ModuleAnalysis`] from the analysis results of its `funcs`.
+    pub(crate) fn new(funcs: Vec<FuncAnalysis>) -> Self {
+        Self { funcs }
+    }
+
+    /// Returns the analysis results of all internally defined functions.
+    pub fn funcs(&self) -> &[FuncAnalysis] {
+        &self.funcs
+    }
+
+    /// Returns the analysis results of the internally defined function at `func_index`.
+    ///
+    /// Returns `None` if `func_index` refers to an imported or non-existing function.
+    pub fn func(&self, func_index: u32) -> Option<&FuncAnalysis> {
+        let first = self.funcs.first()?.func_index;
+        let index = func_index.checked_sub(first)?;
+        self.funcs.get(index as usize)
+    }
+}
+
+/// The facts about a single Wasm function body gathered by [`ModuleAnalyzer`].
+#[cfg(feature = "compiler")]
+#[derive(Debug, Default)]
+struct FuncFacts {
+    /// The indices of the functions called via `call`.
+    calls: Vec<u32>,
+    /// The indices of the functions called via `return_call`.
+    tail_calls: Vec<u32>,
+    /// Is `true` if the function performs indirect calls.
+    indirect_calls: bool,
+    /// The sum of the constant deltas of all `memory.grow` instructions if known.
+    memory_growth: Option<u64>,
+}
+
+#[cfg(feature = "compiler")]
+impl FuncFacts {
+    /// Gathers the [`FuncFacts`] of the Wasm function body `bytes` at `offset`.
+    ///
+    /// # Note
+    ///
+    /// Scanning stops at the first malformed Wasm operator
+    /// since those are reported upon validation and translation.
+    fn scan(offset: usize, bytes: &[u8]) -> Self {
+        let mut facts = Self {
+            memory_growth: Some(0),
+            ..Self::default()
+        };
+        let Ok(mut reader) = FunctionBody::new(offset, bytes).get_operators_reader() else {
+            return facts;
+        };
+        // The stack of control frames where `true` indicates a loop.
+        let mut control = Vec::new();
+        let mut len_loops = 0_usize;
+        let mut last_const = None;
+        while !reader.eof() {
+            let Ok(operator) = reader.read() else {
+                break;
+            };
+            let mut const_value = None;
+            match operator {
+                Operator::Block { .. } | Operator::If { .. } | Operator::Try { .. } => {
+                    control.push(false)
+                }
+                Operator::Loop { .. } => {
+                    control.push(true);
+                    len_loops += 1;
+                }
+                Operator::End => {
+                    if let Some(true) = control.pop() {
+                        len_loops -= 1;
+                    }
+                }
+                Operator::Call { function_index } => facts.calls.push(function_index),
+                Operator::ReturnCall { function_index } => facts.tail_calls.push(function_index),
+                Operator::CallIndirect { .. } | Operator::ReturnCallIndirect { .. } => {
+                    facts.indirect_calls = true
+                }
+                Operator::I32Const { value } => const_value = Some(value),
+                Operator::MemoryGrow { .. } => {
+                    facts.memory_growth = match (facts.memory_growth, last_const) {
+                        (Some(growth), Some(delta)) if len_loops == 0 => {
+                            Some(growth + u64::from(delta as u32))
+                        }
+                        _ => None,
+                    };
+                }
+                _ => {}
+            }
+            last_const = const_value;
+        }
+        facts
+    }
+}
+
+/// Gathers the facts of the Wasm function bodies of a Wasm module for its [`ModuleAnalysis`].
+#[cfg(feature = "compiler")]
+#[derive(Debug, Default)]
+pub struct ModuleAnalyzer {
+    /// The number of imported functions of the Wasm module.
+    len_imported: u32,
+    /// The facts of the internally defined functions in the order of their definition.
+    funcs: Vec<FuncFacts>,
+}
+
+/// The state of a function during the call depth computation of [`ModuleAnalyzer::finish`].
+#[cfg(feature = "compiler")]
+#[derive(Debug, Copy, Clone)]
+enum Visit {
+    /// The function has not yet been visited.
+    Pending,
+    /// The function is on the stack of currently visited functions.
+    Active,
+    /// The maximum call depth of the function has been computed.
+    Done(Option<u32>),
+}
+
+#[cfg(feature = "compiler")]
+impl ModuleAnalyzer {
+    /// Gathers the facts of the next internally defined Wasm function body `bytes` at `offset`.
+    pub fn push_func(&mut self, offset: usize, bytes: &[u8]) {
+        self.funcs.push(FuncFacts::scan(offset, bytes));
+    }
+
+    /// Returns the internal index of the function at `func_index` or `None` if it is imported.
+    fn internal_index(&self, func_index: u32) -> Option<usize> {
+        let index = func_index.checked_sub(self.len_imported)? as usize;
+        (index < self.funcs.len()).then_some(index)
+    }
+
+    /// Returns the maximum call depth of `func` given the results of its callees so far.
+    ///
+    /// Returns `Err(callee)` with the internal index of a pending callee to visit first.
+    fn call_depth(&self, visits: &[Visit], func: usize) -> Result<Option<u32>, usize> {
+        let facts = &self.funcs[func];
+        if facts.indirect_calls {
+            return Ok(None);
+        }
+        let mut depth = 1_u32;
+        let callees = facts
+            .calls
+            .iter()
+            .map(|callee| (callee, 1))
+            .chain(facts.tail_calls.iter().map(|callee| (callee, 0)));
+        for (callee, frames) in callees {
+            let Some(callee) = self.internal_index(*callee) else {
+                continue;
+            };
+            match visits[callee] {
+                Visit::Pending => return Err(callee),
+                Visit::Active | Visit::Done(None) => return Ok(None),
+                Visit::Done(Some(callee_depth)) => {
+                    depth = depth.max(callee_depth.saturating_add(frames));
+                }
+            }
+        }
+        Ok(Some(depth))
+    }
+
+    /// Finishes the analysis and returns the [`FuncAnalysis`] of all internally defined functions.
+    ///
+    /// The Wasm module has `len_imported` imported functions.
+    ///
+    /// # Note
+    ///
+    /// The value stack cells of the functions are not known at this point and
+    /// must be filled in via [`FuncAnalysis::with_stack_cells`] upon query.
+    pub fn finish(mut self, len_imported: u32) -> Box<[FuncAnalysis]> {
+        self.len_imported = len_imported;
+        let mut visits = alloc::vec![Visit::Pending; self.funcs.len()];
+        let mut stack = Vec::new();
+        for root in 0..self.funcs.len() {
+            if !matches!(visits[root], Visit::Pending) {
+                continue;
+            }
+            visits[root] = Visit::Active;
+            stack.push(root);
+            while let Some(&func) = stack.last() {
+                match self.call_depth(&visits, func) {
+                    Ok(depth) => {
+                        visits[func] = Visit::Done(depth);
+                        stack.pop();
+                    }
+                    Err(callee) => {
+                        visits[callee] = Visit::Active;
+                        stack.push(callee);
+                    }
+                }
+            }
+        }
+        self.funcs
+            .iter()
+            .zip(visits)
+            .zip(self.len_imported..)
+            .map(|((facts, visit), func_index)| {
+                let Visit::Done(max_call_depth) = visit else {
+                    unreachable!("all functions have been visited")
+                };
+                FuncAnalysis {
+                    func_index,
+                    stack_cells: None,
+                    max_call_depth,
+                    max_memory_growth: facts.memory_growth,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,616 @@
+use super::sha256::Sha256;
+use crate::{Engine, Error, WasmFeatures};
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, fmt::Display};
+
+/// The magic bytes that start every precompiled Wasmi artifact.
+const MAGIC: [u8; 8] = *b"\0wasmiar";
+
+/// The version of the precompiled artifact header encoding.
+///
+/// # Note
+///
+/// This must be bumped whenever the encoding of the header changes.
+const FORMAT_VERSION: u16 = 2;
+
+/// The alignment of the Wasm payload within a precompiled artifact in bytes.
+///
+/// # Note
+///
+/// The Wasm payload starts at a page boundary so that it can be mapped read-only
+/// into memory independently of the artifact header and shared by multiple processes.
+const PAYLOAD_ALIGNMENT: usize = 4096;
+
+/// The version of the Wasmi crate that produced a precompiled artifact.
+const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// An error that may occur upon deserializing a precompiled Wasmi artifact.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArtifactError {
+    /// The bytes do not start with the magic bytes of a Wasmi artifact.
+    InvalidMagic,
+    /// The bytes ended before the artifact header was fully decoded.
+    Truncated,
+    /// The artifact was encoded with an unsupported header format.
+    FormatMismatch {
+        /// The header format supported by this Wasmi version.
+        expected: u16,
+        /// The header format found in the artifact.
+        found: u16,
+    },
+    /// The Wasmi version encoded in the artifact header is not valid UTF-8.
+    InvalidVersion,
+    /// The artifact was produced by a different Wasmi version.
+    VersionMismatch {
+        /// The version of the Wasmi crate that tried to load the artifact.
+        expected: Box<str>,
+        /// The version of the Wasmi crate that produced the artifact.
+        found: Box<str>,
+    },
+    /// The artifact was produced with a different set of enabled Wasm features.
+    FeaturesMismatch {
+        /// The Wasm features enabled by the [`Engine`] that tried to load the artifact.
+        expected: ArtifactFeatures,
+        /// The Wasm features enabled by the [`Engine`] that produced the artifact.
+        found: ArtifactFeatures,
+    },
+    /// The artifact was produced for a different target.
+    TargetMismatch {
+        /// The target of the host that tried to load the artifact.
+        expected: ArtifactTarget,
+        /// The target of the host that produced the artifact.
+        found: ArtifactTarget,
+    },
+    /// The compiled payload of the artifact is malformed.
+    Malformed,
+    /// The compiled payload was produced with different code generation settings.
+    ///
+    /// This happens if the [`Config`] of the [`Engine`] that tried to load the artifact
+    /// differs in fuel metering, epoch interruption or denied operators.
+    ///
+    /// [`Config`]: crate::Config
+    ConfigMismatch,
+    /// The artifact carries Wasm bytecode that requires the Wasm compiler to load.
+    ///
+    /// This happens if Wasmi was built without the `compiler` crate feature.
+    RequiresCompiler,
+    /// The Wasm payload offset of the artifact is not aligned or overlaps the header.
+    InvalidPayloadOffset(u32),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArtifactError {}
+
+impl Display for ArtifactError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidMagic => write!(f, "not a precompiled Wasmi artifact: invalid magic bytes"),
+            Self::Truncated => write!(f, "precompiled Wasmi artifact is truncated"),
+            Self::FormatMismatch { expected, found } => write!(
+                f,
+                "incompatible precompiled Wasmi artifact: expected header format {expected} but found {found}",
+            ),
+            Self::InvalidVersion => write!(
+                f,
+                "precompiled Wasmi artifact is corrupt: Wasmi version is not valid UTF-8",
+            ),
+            Self::VersionMismatch { expected, found } => write!(
+                f,
+                "incompatible precompiled Wasmi artifact: produced by Wasmi {found} but loaded by Wasmi {expected}",
+            ),
+            Self::FeaturesMismatch { expected, found } => {
+                write!(
+                    f,
+                    "incompatible precompiled Wasmi artifact: Wasm features do not match (",
+                )?;
+                let mut first = true;
+                for (flag, name) in ArtifactFeatures::NAMES {
+                    let enabled = expected.contains(*flag);
+                    if enabled == found.contains(*flag) {
+                        continue;
+                    }
+                    if !first {
+                        write!(f, ", ")?;
+                    }
+                    first = false;
+                    let state = if enabled { "disabled" } else { "enabled" };
+                    write!(f, "`{name}` is {state} in the artifact")?;
+                }
+                write!(f, ")")
+            }
+            Self::TargetMismatch { expected, found } => write!(
+                f,
+                "incompatible precompiled Wasmi artifact: produced for target {found} but loaded on target {expected}",
+            ),
+            Self::Malformed => write!(f, "precompiled Wasmi artifact is malformed"),
+            Self::ConfigMismatch => write!(
+                f,
+                "incompatible precompiled Wasmi artifact: compiled with different code generation settings",
+            ),
+            Self::RequiresCompiler => write!(
+                f,
+                "precompiled Wasmi artifact requires the Wasm compiler which is not available in this build",
+            ),
+            Self::InvalidPayloadOffset(offset) => write!(
+                f,
+                "precompiled Wasmi artifact has an invalid payload offset: {offset}",
+            ),
+        }
+    }
+}
+
+/// The set of Wasm features that were enabled when a precompiled artifact was produced.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ArtifactFeatures(u32);
+
+impl ArtifactFeatures {
+    const MUTABLE_GLOBAL: u32 = 1 << 0;
+    const SIGN_EXTENSION: u32 = 1 << 1;
+    const SATURATING_FLOAT_TO_INT: u32 = 1 << 2;
+    const MULTI_VALUE: u32 = 1 << 3;
+    const BULK_MEMORY: u32 = 1 << 4;
+    const REFERENCE_TYPES: u32 = 1 << 5;
+    const TAIL_CALL: u32 = 1 << 6;
+    const EXTENDED_CONST: u32 = 1 << 7;
+    const FLOATS: u32 = 1 << 8;
+
+    /// The human readable names of all encoded Wasm features.
+    const NAMES: &'static [(u32, &'static str)] = &[
+        (Self::MUTABLE_GLOBAL, "mutable-global"),
+        (Self::SIGN_EXTENSION, "sign-extension"),
+        (Self::SATURATING_FLOAT_TO_INT, "saturating-float-to-int"),
+        (Self::MULTI_VALUE, "multi-value"),
+        (Self::BULK_MEMORY, "bulk-memory"),
+        (Self::REFERENCE_TYPES, "reference-types"),
+        (Self::TAIL_CALL, "tail-call"),
+        (Self::EXTENDED_CONST, "extended-const"),
+        (Self::FLOATS, "floats"),
+    ];
+
+    /// Creates the [`ArtifactFeatures`] from the given [`WasmFeatures`].
+    fn from_wasm_features(features: &WasmFeatures) -> Self {
+        let mut bits = 0;
+        let mut set = |flag: u32, enabled: bool| {
+            if enabled {
+                bits |= flag;
+            }
+        };
+        set(Self::MUTABLE_GLOBAL, features.mutable_global());
+        set(Self::SIGN_EXTENSION, features.sign_extension());
+        set(
+            Self::SATURATING_FLOAT_TO_INT,
+            features.saturating_float_to_int(),
+        );
+        set(Self::MULTI_VALUE, features.multi_value());
+        set(Self::BULK_MEMORY, features.bulk_memory());
+        set(Self::REFERENCE_TYPES, features.reference_types());
+        set(Self::TAIL_CALL, features.tail_call());
+        set(Self::EXTENDED_CONST, features.extended_const());
+        set(Self::FLOATS, features.floats());
+        Self(bits)
+    }
+
+    /// Returns `true` if `flag` is enabled.
+    fn contains(&self, flag: u32) -> bool {
+        self.0 & flag != 0
+    }
+
+    /// Returns the raw bit representation of the [`ArtifactFeatures`].
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+}
+
+/// The target metadata of the host that produced a precompiled artifact.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ArtifactTarget {
+    /// The pointer width of the target in bits.
+    pointer_width: u8,
+    /// Is `true` if the target is little endian.
+    little_endian: bool,
+}
+
+impl Display for ArtifactTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let endianness = if self.little_endian { "little" } else { "big" };
+        write!(f, "{}-bit {endianness}-endian", self.pointer_width)
+    }
+}
+
+impl ArtifactTarget {
+    /// Returns the [`ArtifactTarget`] of the current host.
+    fn host() -> Self {
+        Self {
+            pointer_width: usize::BITS as u8,
+            little_endian: cfg!(target_endian = "little"),
+        }
+    }
+
+    /// Returns the pointer width of the target in bits.
+    pub fn pointer_width(&self) -> u8 {
+        self.pointer_width
+    }
+
+    /// Returns `true` if the target is little endian.
+    pub fn is_little_endian(&self) -> bool {
+        self.little_endian
+    }
+}
+
+/// The compatibility header that is embedded into every precompiled artifact.
+#[derive(Debug)]
+struct ArtifactHeader<'a> {
+    /// The version of the Wasmi crate that produced the artifact.
+    version: &'a str,
+    /// The Wasm features enabled by the [`Engine`] that produced the artifact.
+    features: ArtifactFeatures,
+    /// The target of the host that produced the artifact.
+    target: ArtifactTarget,
+}
+
+impl ArtifactHeader<'static> {
+    /// Returns the [`ArtifactHeader`] expected by the given [`Engine`].
+    fn for_engine(engine: &Engine) -> Self {
+        Self {
+            version: ENGINE_VERSION,
+            features: ArtifactFeatures::from_wasm_features(&engine.features()),
+            target: ArtifactTarget::host(),
+        }
+    }
+}
+
+impl<'a> ArtifactHeader<'a> {
+    /// Encodes `self` into the `buffer`.
+    ///
+    /// # Note
+    ///
+    /// The encoded header is padded with zeros up to the offset of the Wasm payload.
+    /// The payload offset is relative to the start of the artifact.
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(&MAGIC);
+        buffer.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        let len_version = u8::try_from(self.version.len())
+            .unwrap_or_else(|_| panic!("Wasmi version string is too long: {}", self.version));
+        buffer.push(len_version);
+        buffer.extend_from_slice(self.version.as_bytes());
+        buffer.extend_from_slice(&self.features.bits().to_le_bytes());
+        buffer.push(self.target.pointer_width);
+        buffer.push(u8::from(self.target.little_endian));
+        let len_header = buffer.len() + core::mem::size_of::<u32>();
+        let payload_offset = len_header.next_multiple_of(PAYLOAD_ALIGNMENT);
+        let encoded_offset = u32::try_from(payload_offset)
+            .unwrap_or_else(|_| panic!("artifact payload offset is too large: {payload_offset}"));
+        buffer.extend_from_slice(&encoded_offset.to_le_bytes());
+        buffer.resize(payload_offset, 0x00);
+    }
+
+    /// Decodes an [`ArtifactHeader`] from `bytes`.
+    ///
+    /// Returns the decoded [`ArtifactHeader`] and the remaining payload bytes.
+    ///
+    /// # Errors
+    ///
+    /// - If `bytes` do not start with the magic bytes of a Wasmi artifact.
+    /// - If `bytes` end before the header is fully decoded.
+    /// - If the encoded Wasmi version is not valid UTF-8.
+    /// - If the header was encoded with an unsupported format.
+    /// - If the payload offset is not aligned or overlaps the header.
+    fn decode(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), ArtifactError> {
+        let artifact = bytes;
+        let mut reader = ByteReader { bytes };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(ArtifactError::InvalidMagic);
+        }
+        let format = u16::from_le_bytes(reader.take_array()?);
+        if format != FORMAT_VERSION {
+            return Err(ArtifactError::FormatMismatch {
+                expected: FORMAT_VERSION,
+                found: format,
+            });
+        }
+        let [len_version] = reader.take_array()?;
+        let version = core::str::from_utf8(reader.take(usize::from(len_version))?)
+            .map_err(|_| ArtifactError::InvalidVersion)?;
+        let features = ArtifactFeatures(u32::from_le_bytes(reader.take_array()?));
+        let [pointer_width, little_endian] = reader.take_array()?;
+        let target = ArtifactTarget {
+            pointer_width,
+            little_endian: little_endian != 0,
+        };
+        let encoded_offset = u32::from_le_bytes(reader.take_array()?);
+        let payload_offset = encoded_offset as usize;
+        let len_header = artifact.len() - reader.bytes.len();
+        if payload_offset < len_header || !payload_offset.is_multiple_of(PAYLOAD_ALIGNMENT) {
+            return Err(ArtifactError::InvalidPayloadOffset(encoded_offset));
+        }
+        let payload = artifact
+            .get(payload_offset..)
+            .ok_or(ArtifactError::Truncated)?;
+        let header = Self {
+            version,
+            features,
+            target,
+        };
+        Ok((header, payload))
+    }
+
+    /// Checks that `self` is compatible with the `expected` [`ArtifactHeader`].
+    ///
+    /// # Errors
+    ///
+    /// If `self` and `expected` differ in Wasmi version, enabled Wasm features or target.
+    fn check(&self, expected: &ArtifactHeader) -> Result<(), ArtifactError> {
+        if self.version != expected.version {
+            return Err(ArtifactError::VersionMismatch {
+                expected: expected.version.into(),
+                found: self.version.into(),
+            });
+        }
+        if self.features != expected.features {
+            return Err(ArtifactError::FeaturesMismatch {
+                expected: expected.features,
+                found: self.features,
+            });
+        }
+        if self.target != expected.target {
+            return Err(ArtifactError::TargetMismatch {
+                expected: expected.target,
+                found: self.target,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A minimal reader over a byte slice used to decode artifact headers.
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    /// Takes the next `len` bytes from the reader.
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ArtifactError> {
+        if self.bytes.len() < len {
+            return Err(ArtifactError::Truncated);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    /// Takes the next `N` bytes from the reader as array.
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], ArtifactError> {
+        let mut array = [0x00_u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+}
+
+/// The SHA-256 hash of a precompiled Wasmi artifact.
+///
+/// Query it via [`Module::artifact_hash`] or compute it via [`ArtifactHash::of`].
+///
+/// [`Module::artifact_hash`]: crate::Module::artifact_hash
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArtifactHash([u8; 32]);
+
+impl ArtifactHash {
+    /// Returns the [`ArtifactHash`] of the precompiled `artifact`.
+    ///
+    /// # Note
+    ///
+    /// This is the SHA-256 hash of the `artifact` bytes and does not check if the
+    /// `artifact` is a well-formed precompiled Wasmi artifact.
+    pub fn of(artifact: &[u8]) -> Self {
+        let mut hasher = Sha256::default();
+        hasher.update(artifact);
+        Self(hasher.finish())
+    }
+
+    /// Returns the bytes of the SHA-256 hash.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Display for ArtifactHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Incrementally computes the [`ArtifactHash`] of a Wasm module while it is parsed.
+///
+/// The hash is computed as if the Wasm module was precompiled via [`encode_artifact`].
+#[derive(Debug)]
+pub struct ArtifactHasher {
+    /// The hasher of the artifact bytes.
+    hasher: Sha256,
+}
+
+impl ArtifactHasher {
+    /// Creates a new [`ArtifactHasher`] for artifacts of the `engine`.
+    pub fn new(engine: &Engine) -> Self {
+        let mut header = Vec::new();
+        ArtifactHeader::for_engine(engine).encode(&mut header);
+        let mut hasher = Sha256::default();
+        hasher.update(&header);
+        Self { hasher }
+    }
+
+    /// Hashes the next `wasm` bytes of the Wasm module.
+    pub fn update(&mut self, wasm: &[u8]) {
+        self.hasher.update(wasm);
+    }
+
+    /// Returns the [`ArtifactHash`] of all hashed Wasm bytes.
+    pub fn finish(self) -> ArtifactHash {
+        ArtifactHash(self.hasher.finish())
+    }
+}
+
+/// Encodes the validated `wasm` into a precompiled artifact for the `engine`.
+///
+/// # Note
+///
+/// The artifact only depends on the `wasm` bytes, the Wasmi version, the Wasm
+/// features enabled by the `engine` and the target. Therefore the same `wasm`
+/// always yields byte-identical artifacts for the same configuration.
+///
+/// The `wasm` payload is placed at a page aligned offset after the header.
+/// It is addressed solely via offsets relative to the start of the artifact and
+/// the artifact contains no mutable state so that the payload can be shared read-only.
+pub fn encode_artifact(engine: &Engine, wasm: &[u8]) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    ArtifactHeader::for_engine(engine).encode(&mut buffer);
+    buffer.extend_from_slice(wasm);
+    buffer
+}
+
+/// Decodes the precompiled `artifact` and returns its Wasm payload.
+///
+/// # Errors
+///
+/// If the `artifact` is malformed or incompatible with the `engine`.
+pub fn decode_artifact<'a>(engine: &Engine, artifact: &'a [u8]) -> Result<&'a [u8], Error> {
+    let (header, wasm) = ArtifactHeader::decode(artifact)?;
+    header.check(&ArtifactHeader::for_engine(engine))?;
+    Ok(wasm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+    use core::mem::size_of;
+
+    fn artifact_for(config: &Config) -> Vec<u8> {
+        encode_artifact(&Engine::new(config), b"\0asm\x01\0\0\0")
+    }
+
+    fn decode_error(config: &Config, artifact: &[u8]) -> ArtifactError {
+        let (header, _) = ArtifactHeader::decode(artifact).unwrap();
+        header
+            .check(&ArtifactHeader::for_engine(&Engine::new(config)))
+            .unwrap_err()
+    }
+
+    #[test]
+    fn roundtrip_works() {
+        let engine = Engine::default();
+        let artifact = encode_artifact(&engine, b"payload");
+        assert_eq!(decode_artifact(&engine, &artifact).unwrap(), b"payload");
+    }
+
+    #[test]
+    fn hasher_matches_artifact_hash() {
+        let engine = Engine::default();
+        let artifact = encode_artifact(&engine, b"payload");
+        let mut hasher = ArtifactHasher::new(&engine);
+        hasher.update(b"pay");
+        hasher.update(b"load");
+        assert_eq!(hasher.finish(), ArtifactHash::of(&artifact));
+    }
+
+    #[test]
+    fn invalid_magic() {
+        let mut artifact = artifact_for(&Config::default());
+        artifact[1] = b'x';
+        assert_eq!(
+            ArtifactHeader::decode(&artifact).unwrap_err(),
+            ArtifactError::InvalidMagic
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let artifact = artifact_for(&Config::default());
+        assert_eq!(
+            ArtifactHeader::decode(&artifact[..MAGIC.len() + 1]).unwrap_err(),
+            ArtifactError::Truncated
+        );
+    }
+
+    #[test]
+    fn version_mismatch() {
+        let mut artifact = artifact_for(&Config::default());
+        // Overwrite the first character of the encoded version string.
+        let version_start = MAGIC.len() + size_of::<u16>() + 1;
+        artifact[version_start] = b'x';
+        assert!(matches!(
+            decode_error(&Config::default(), &artifact),
+            ArtifactError::VersionMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn invalid_version() {
+        let mut artifact = artifact_for(&Config::default());
+        let version_start = MAGIC.len() + size_of::<u16>() + 1;
+        artifact[version_start] = 0xFF;
+        assert_eq!(
+            ArtifactHeader::decode(&artifact).unwrap_err(),
+            ArtifactError::InvalidVersion
+        );
+    }
+
+    #[test]
+    fn features_mismatch() {
+        let mut config = Config::default();
+        config.wasm_tail_call(true);
+        let artifact = artifact_for(&config);
+        let error = decode_error(&Config::default(), &artifact);
+        assert!(matches!(error, ArtifactError::FeaturesMismatch { .. }));
+        assert!(error
+            .to_string()
+            .contains("`tail-call` is enabled in the artifact"));
+    }
+
+    /// Returns the position of the encoded pointer width within the artifact header.
+    fn pointer_width_pos() -> usize {
+        MAGIC.len() + size_of::<u16>() + 1 + ENGINE_VERSION.len() + size_of::<u32>()
+    }
+
+    #[test]
+    fn payload_is_page_aligned() {
+        let artifact = artifact_for(&Config::default());
+        let (_, payload) = ArtifactHeader::decode(&artifact).unwrap();
+        assert_eq!(payload, b"\0asm\x01\0\0\0");
+        let payload_offset = artifact.len() - payload.len();
+        assert_eq!(payload_offset, PAYLOAD_ALIGNMENT);
+        assert!(artifact[pointer_width_pos() + 6..payload_offset]
+            .iter()
+            .all(|byte| *byte == 0x00));
+    }
+
+    #[test]
+    fn invalid_payload_offset() {
+        let mut artifact = artifact_for(&Config::default());
+        let offset_pos = pointer_width_pos() + 2;
+        artifact[offset_pos..offset_pos + 4].copy_from_slice(&8_u32.to_le_bytes());
+        assert_eq!(
+            ArtifactHeader::decode(&artifact).unwrap_err(),
+            ArtifactError::InvalidPayloadOffset(8)
+        );
+        let beyond = (2 * PAYLOAD_ALIGNMENT) as u32;
+        artifact[offset_pos..offset_pos + 4].copy_from_slice(&beyond.to_le_bytes());
+        assert_eq!(
+            ArtifactHeader::decode(&artifact).unwrap_err(),
+            ArtifactError::Truncated
+        );
+    }
+
+    #[test]
+    fn target_mismatch() {
+        let mut artifact = artifact_for(&Config::default());
+        artifact[pointer_width_pos()] ^= 0xFF;
+        assert!(matches!(
+            decode_error(&Config::default(), &artifact),
+            ArtifactError::TargetMismatch { .. }
+        ));
+    }
+}
@@ -14,6 +14,7 @@ use super::{
     ModuleHeader,
     ModuleHeaderInner,
     ModuleImports,
+    ModuleNames,
 };
 use crate::{
     engine::{CompiledFunc, DedupFuncType},
@@ -24,13 +25,19 @@ use crate::{
     MemoryType,
     TableType,
 };
-use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec::Vec,
+};
 
 /// A builder for a WebAssembly [`Module`].
 #[derive(Debug)]
 pub struct ModuleBuilder {
     pub header: ModuleHeader,
     pub data_segments: Vec<DataSegment>,
+    pub names: ModuleNames,
 }
 
 /// A builder for a WebAssembly [`Module`] header.
@@ -48,7 +55,9 @@ pub struct ModuleHeaderBuilder {
     pub start: Option<FuncIdx>,
     pub compiled_funcs: Vec<CompiledFunc>,
     pub compiled_funcs_idx: BTreeMap<CompiledFunc, FuncIdx>,
+    pub intrinsics: BTreeSet<FuncIdx>,
     pub element_segments: Vec<ElementSegment>,
+    pub shared_wasm: Option<&'static [u8]>,
 }
 
 impl ModuleHeaderBuilder {
@@ -67,7 +76,9 @@ impl ModuleHeaderBuilder {
             start: None,
             compiled_funcs: Vec::new(),
             compiled_funcs_idx: BTreeMap::new(),
+            intrinsics: BTreeSet::new(),
             element_segments: Vec::new(),
+            shared_wasm: None,
         }
     }
 
@@ -87,7 +98,9 @@ impl ModuleHeaderBuilder {
                 start: self.start,
                 compiled_funcs: self.compiled_funcs.into(),
                 compiled_funcs_idx: self.compiled_funcs_idx,
+                intrinsics: self.intrinsics,
                 element_segments: self.element_segments.into(),
+                shared_wasm: self.shared_wasm,
             }),
         }
     }
@@ -134,6 +147,7 @@ impl ModuleBuilder {
         Self {
             header,
             data_segments: Vec::new(),
+            names: ModuleNames::default(),
         }
     }
 }
@@ -182,8 +196,17 @@ impl ModuleHeaderBuilder {
             let (name, kind) = import.into_name_and_type();
             match kind {
                 ExternTypeIdx::Func(func_type_idx) => {
-                    self.imports.funcs.push(name);
                     let func_type = self.func_types[func_type_idx.into_u32() as usize];
+                    if self
+                        .engine
+                        .get_intrinsic(name.module(), name.name(), func_type)
+                        .is_some()
+                    {
+                        // Note: imported function indices always fit into `u32`.
+                        let func_idx = FuncIdx::from(self.funcs.len() as u32);
+                        self.intrinsics.insert(func_idx);
+                    }
+                    self.imports.funcs.push(name);
                     self.funcs.push(func_type);
                 }
                 ExternTypeIdx::Table(table_type) => {
@@ -390,12 +413,23 @@ impl ModuleBuilder {
         Ok(())
     }
 
+    /// Sets the [`ModuleNames`] of the `name` custom section of the [`Module`] under construction.
+    pub fn set_names(&mut self, names: ModuleNames) {
+        self.names = names;
+    }
+
     /// Finishes construction of the WebAssembly [`Module`].
     pub fn finish(self, engine: &Engine) -> Module {
+        engine.pool_consts(&self.header.inner.compiled_funcs);
+        engine.counters().module_compiled();
         Module {
             engine: engine.clone(),
             header: self.header,
             data_segments: self.data_segments.into(),
+            names: Arc::new(self.names),
+            compile_stats: None,
+            artifact_hash: None,
+            analysis: None,
         }
     }
 }
@@ -0,0 +1,40 @@
+/// The size of the translated Wasmi bytecode of a function of a [`Module`].
+///
+/// Returned by [`Module::func_code_sizes`].
+///
+/// [`Module`]: crate::Module
+/// [`Module::func_code_sizes`]: crate::Module::func_code_sizes
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FuncCodeSize {
+    /// The index of the function within the Wasm module including imported functions.
+    func_index: u32,
+    /// The size of the translated function in bytes if it has been translated already.
+    size: Option<usize>,
+}
+
+impl FuncCodeSize {
+    /// Creates a new [`FuncCodeSize`].
+    pub(crate) fn new(func_index: u32, size: Option<usize>) -> Self {
+        Self { func_index, size }
+    }
+
+    /// Returns the index of the function within the Wasm module.
+    ///
+    /// # Note
+    ///
+    /// The index space includes imported functions.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the size of the translated Wasmi bytecode of the function in bytes.
+    ///
+    /// # Note
+    ///
+    /// - This includes the instructions and the function local constant values.
+    /// - Returns `None` if the function has not yet been translated which is the
+    ///   case for functions that have not yet been called with lazy compilation.
+    pub fn size(&self) -> Option<usize> {
+        self.size
+    }
+}
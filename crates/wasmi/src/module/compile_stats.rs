@@ -0,0 +1,128 @@
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// The wall time spent in the phases of compiling a [`Module`].
+///
+/// Returned by [`Module::compile_stats`] if [`Config::compile_stats`] is enabled.
+///
+/// # Note
+///
+/// - Wasm function bodies are validated while they are translated.
+///   Therefore their validation time is accounted as translation time.
+/// - With lazy [`CompilationMode`]s function bodies are translated upon their first
+///   call which is not accounted in the [`CompileStats`] of the [`Module`].
+///
+/// [`Module`]: crate::Module
+/// [`Module::compile_stats`]: crate::Module::compile_stats
+/// [`Config::compile_stats`]: crate::Config::compile_stats
+/// [`CompilationMode`]: crate::CompilationMode
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CompileStats {
+    /// The time spent parsing the Wasm binary and constructing the module.
+    parsing: Duration,
+    /// The time spent validating the Wasm module.
+    validation: Duration,
+    /// The time spent translating Wasm function bodies to Wasmi bytecode.
+    translation: Duration,
+}
+
+impl CompileStats {
+    /// Returns the time spent parsing the Wasm binary and constructing the [`Module`].
+    ///
+    /// This includes reading from the Wasm byte stream.
+    ///
+    /// [`Module`]: crate::Module
+    pub fn parsing(&self) -> Duration {
+        self.parsing
+    }
+
+    /// Returns the time spent validating the Wasm module structure.
+    pub fn validation(&self) -> Duration {
+        self.validation
+    }
+
+    /// Returns the time spent translating Wasm function bodies to Wasmi bytecode.
+    pub fn translation(&self) -> Duration {
+        self.translation
+    }
+
+    /// Returns the total time spent compiling the [`Module`].
+    ///
+    /// [`Module`]: crate::Module
+    pub fn total(&self) -> Duration {
+        self.parsing + self.validation + self.translation
+    }
+}
+
+/// A phase of Wasm module compilation that is measured separately.
+#[derive(Debug, Copy, Clone)]
+pub enum CompilePhase {
+    /// Validation of the Wasm module structure.
+    Validation,
+    /// Translation of Wasm function bodies to Wasmi bytecode.
+    Translation,
+}
+
+/// Measures the [`CompileStats`] of a single Wasm module compilation.
+#[derive(Debug)]
+pub struct CompileTimer {
+    /// The start of the compilation or `None` if measurement is disabled.
+    #[cfg(feature = "std")]
+    start: Option<Instant>,
+    /// The [`CompileStats`] measured so far.
+    #[cfg(feature = "std")]
+    stats: CompileStats,
+}
+
+impl CompileTimer {
+    /// Creates a new [`CompileTimer`] that starts measuring if `enabled`.
+    ///
+    /// # Note
+    ///
+    /// Measurement requires the `std` crate feature and is disabled otherwise.
+    pub fn new(enabled: bool) -> Self {
+        #[cfg(not(feature = "std"))]
+        let _ = enabled;
+        Self {
+            #[cfg(feature = "std")]
+            start: enabled.then(Instant::now),
+            #[cfg(feature = "std")]
+            stats: CompileStats::default(),
+        }
+    }
+
+    /// Runs `f` and accounts its wall time to the compilation `phase`.
+    pub fn measure<R>(&mut self, phase: CompilePhase, f: impl FnOnce() -> R) -> R {
+        #[cfg(feature = "std")]
+        if self.start.is_some() {
+            let start = Instant::now();
+            let result = f();
+            let elapsed = start.elapsed();
+            match phase {
+                CompilePhase::Validation => self.stats.validation += elapsed,
+                CompilePhase::Translation => self.stats.translation += elapsed,
+            }
+            return result;
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = phase;
+        f()
+    }
+
+    /// Finishes measurement and returns the [`CompileStats`] if enabled.
+    ///
+    /// The time not spent in any measured phase is accounted as parsing time.
+    pub fn finish(self) -> Option<CompileStats> {
+        #[cfg(feature = "std")]
+        if let Some(start) = self.start {
+            let mut stats = self.stats;
+            stats.parsing = start
+                .elapsed()
+                .saturating_sub(stats.validation)
+                .saturating_sub(stats.translation);
+            return Some(stats);
+        }
+        None
+    }
+}
@@ -1,14 +1,22 @@
-use super::{ConstExpr, MemoryIdx};
-use alloc::sync::Arc;
+use super::{ArtifactError, ConstExpr, Decode, Decoder, Encode, Encoder, MemoryIdx};
+use crate::Value;
+use alloc::{boxed::Box, sync::Arc};
 
 /// A Wasm [`Module`] data segment.
 ///
-/// [`Module`]: [`super::Module`]
+/// [`Module`]: super::Module
 #[derive(Debug)]
 pub struct DataSegment {
     /// The kind of the data segment.
     kind: DataSegmentKind,
     /// The bytes of the data segment.
+    ///
+    /// # Note
+    ///
+    /// The bytes are shared with all instances of the [`Module`] so that
+    /// instantiation does not copy the bytes of passive data segments.
+    ///
+    /// [`Module`]: super::Module
     bytes: Arc<[u8]>,
 }
 
@@ -42,6 +50,7 @@ impl ActiveDataSegment {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl From<wasmparser::DataKind<'_>> for DataSegmentKind {
     fn from(data_kind: wasmparser::DataKind<'_>) -> Self {
         match data_kind {
@@ -61,6 +70,7 @@ impl From<wasmparser::DataKind<'_>> for DataSegmentKind {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl From<wasmparser::Data<'_>> for DataSegment {
     fn from(data: wasmparser::Data<'_>) -> Self {
         let kind = DataSegmentKind::from(data.kind);
@@ -70,6 +80,25 @@ impl From<wasmparser::Data<'_>> for DataSegment {
 }
 
 impl DataSegment {
+    /// Creates a new passive [`DataSegment`] with the given `bytes`.
+    pub fn passive(bytes: Arc<[u8]>) -> Self {
+        Self {
+            kind: DataSegmentKind::Passive,
+            bytes,
+        }
+    }
+
+    /// Creates a new active [`DataSegment`] initializing `memory_index` at `offset` with `bytes`.
+    pub fn active(memory_index: MemoryIdx, offset: u32, bytes: Arc<[u8]>) -> Self {
+        Self {
+            kind: DataSegmentKind::Active(ActiveDataSegment {
+                memory_index,
+                offset: ConstExpr::new_const(Value::I32(offset as i32)),
+            }),
+            bytes,
+        }
+    }
+
     /// Returns the [`DataSegmentKind`] of the [`DataSegment`].
     pub fn kind(&self) -> &DataSegmentKind {
         &self.kind
@@ -81,7 +110,42 @@ impl DataSegment {
     }
 
     /// Clone the underlying bytes of the [`DataSegment`].
+    ///
+    /// # Note
+    ///
+    /// This only clones the reference to the shared bytes and not the bytes themselves.
     pub fn clone_bytes(&self) -> Arc<[u8]> {
         self.bytes.clone()
     }
 }
+
+impl Encode for DataSegment {
+    fn encode(&self, encoder: &mut Encoder) {
+        match &self.kind {
+            DataSegmentKind::Passive => encoder.encode(&0_u8),
+            DataSegmentKind::Active(segment) => {
+                encoder.encode(&1_u8);
+                encoder.encode(&segment.memory_index.into_u32());
+                encoder.encode(&segment.offset);
+            }
+        }
+        encoder.encode_len(self.bytes.len());
+        encoder.write(&self.bytes[..]);
+    }
+}
+
+impl Decode for DataSegment {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let kind = match decoder.decode::<u8>()? {
+            0 => DataSegmentKind::Passive,
+            1 => DataSegmentKind::Active(ActiveDataSegment {
+                memory_index: MemoryIdx::from(decoder.decode::<u32>()?),
+                offset: decoder.decode()?,
+            }),
+            _ => return Err(ArtifactError::Malformed),
+        };
+        let len = decoder.decode_len()?;
+        let bytes = Box::<[u8]>::from(decoder.read(len)?).into();
+        Ok(Self { kind, bytes })
+    }
+}
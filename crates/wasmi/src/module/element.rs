@@ -1,11 +1,12 @@
-use super::{ConstExpr, TableIdx};
+use super::{ArtifactError, ConstExpr, Decode, Decoder, Encode, Encoder, TableIdx};
+#[cfg(feature = "compiler")]
 use crate::module::utils::WasmiValueType;
 use alloc::sync::Arc;
 use wasmi_core::ValueType;
 
 /// A table element segment within a [`Module`].
 ///
-/// [`Module`]: [`super::Module`]
+/// [`Module`]: super::Module
 #[derive(Debug)]
 pub struct ElementSegment {
     /// The kind of the [`ElementSegment`].
@@ -17,6 +18,13 @@ pub struct ElementSegment {
 }
 
 /// The items of an [`ElementSegment`].
+///
+/// # Note
+///
+/// The items are shared with all instances of the [`Module`] so that
+/// instantiation does not copy the items of passive element segments.
+///
+/// [`Module`]: super::Module
 #[derive(Debug, Clone)]
 pub struct ElementSegmentItems {
     exprs: Arc<[ConstExpr]>,
@@ -28,6 +36,7 @@ impl ElementSegmentItems {
     /// # Panics
     ///
     /// If the given [`wasmparser::ElementItems`] is invalid.
+    #[cfg(feature = "compiler")]
     fn new(items: &wasmparser::ElementItems) -> Self {
         let exprs = match items {
             wasmparser::ElementItems::Functions(items) => items
@@ -88,6 +97,7 @@ impl ActiveElementSegment {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl From<wasmparser::ElementKind<'_>> for ElementSegmentKind {
     fn from(element_kind: wasmparser::ElementKind<'_>) -> Self {
         match element_kind {
@@ -108,6 +118,7 @@ impl From<wasmparser::ElementKind<'_>> for ElementSegmentKind {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl From<wasmparser::Element<'_>> for ElementSegment {
     fn from(element: wasmparser::Element<'_>) -> Self {
         assert!(
@@ -134,7 +145,48 @@ impl ElementSegment {
     }
 
     /// Returns the element items of the [`ElementSegment`].
+    ///
+    /// # Note
+    ///
+    /// This only clones the reference to the shared items and not the items themselves.
     pub fn items_cloned(&self) -> ElementSegmentItems {
         self.items.clone()
     }
 }
+
+impl Encode for ElementSegment {
+    fn encode(&self, encoder: &mut Encoder) {
+        match &self.kind {
+            ElementSegmentKind::Passive => encoder.encode(&0_u8),
+            ElementSegmentKind::Active(segment) => {
+                encoder.encode(&1_u8);
+                encoder.encode(&segment.table_index.into_u32());
+                encoder.encode(&segment.offset);
+            }
+            ElementSegmentKind::Declared => encoder.encode(&2_u8),
+        }
+        encoder.encode(&self.ty);
+        encoder.encode_all(self.items.items());
+    }
+}
+
+impl Decode for ElementSegment {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let kind = match decoder.decode::<u8>()? {
+            0 => ElementSegmentKind::Passive,
+            1 => ElementSegmentKind::Active(ActiveElementSegment {
+                table_index: TableIdx::from(decoder.decode::<u32>()?),
+                offset: decoder.decode()?,
+            }),
+            2 => ElementSegmentKind::Declared,
+            _ => return Err(ArtifactError::Malformed),
+        };
+        let ty = decoder.decode::<ValueType>()?;
+        if !ty.is_ref() {
+            return Err(ArtifactError::Malformed);
+        }
+        let exprs = decoder.decode_all::<ConstExpr>()?.into();
+        let items = ElementSegmentItems { exprs };
+        Ok(Self { kind, ty, items })
+    }
+}
@@ -1,11 +1,13 @@
 use super::GlobalIdx;
-use crate::{Error, ExternType, Module};
+#[cfg(feature = "compiler")]
+use crate::Error;
+use crate::{ExternType, Module};
 use alloc::{boxed::Box, collections::btree_map::Iter as BTreeIter};
 
 /// The index of a function declaration within a [`Module`].
 ///
 /// [`Module`]: [`super::Module`]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct FuncIdx(u32);
 
 impl From<u32> for FuncIdx {
@@ -88,6 +90,7 @@ impl ExternIdx {
     /// # Errors
     ///
     /// If an unsupported external definition is encountered.
+    #[cfg(feature = "compiler")]
     pub fn new(kind: wasmparser::ExternalKind, index: u32) -> Result<Self, Error> {
         match kind {
             wasmparser::ExternalKind::Func => Ok(ExternIdx::Func(FuncIdx(index))),
@@ -36,6 +36,7 @@ pub struct Global {
     init_expr: ConstExpr,
 }
 
+#[cfg(feature = "compiler")]
 impl From<wasmparser::Global<'_>> for Global {
     fn from(global: wasmparser::Global<'_>) -> Self {
         let global_type = GlobalType::from_wasmparser(global.ty);
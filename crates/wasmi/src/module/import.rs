@@ -1,6 +1,7 @@
 use crate::{GlobalType, MemoryType, TableType};
 use alloc::boxed::Box;
 use core::fmt::{self, Display};
+#[cfg(feature = "compiler")]
 use wasmparser::TypeRef;
 
 /// A [`Module`] import item.
@@ -59,6 +60,7 @@ impl ImportName {
     }
 }
 
+#[cfg(feature = "compiler")]
 impl From<wasmparser::Import<'_>> for Import {
     fn from(import: wasmparser::Import) -> Self {
         let kind = match import.ty {
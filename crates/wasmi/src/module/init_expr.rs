@@ -6,12 +6,67 @@
 //!
 //! [`s1vm`]: https://github.com/Neopallium/s1vm
 
-use super::FuncIdx;
-use crate::{ExternRef, FuncRef, Value};
+use super::{ArtifactError, Decode, Decoder, Encode, Encoder, FuncIdx};
+use crate::{value::WithType, FuncRef, Value};
 use alloc::boxed::Box;
-use core::fmt;
-use smallvec::SmallVec;
-use wasmi_core::{UntypedValue, F32, F64};
+use core::{fmt, fmt::Display};
+use wasmi_core::{UntypedValue, ValueType};
+#[cfg(feature = "compiler")]
+use {
+    crate::{Error, ExternRef},
+    smallvec::SmallVec,
+    wasmi_core::{F32, F64},
+};
+
+/// An error that may occur upon parsing a [`ConstExpr`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConstExprError {
+    /// Encountered an operator that is not allowed in constant expressions.
+    UnsupportedOperator {
+        /// The byte offset of the operator within the constant expression.
+        offset: usize,
+    },
+    /// Encountered an operand of unexpected type.
+    TypeMismatch {
+        /// The byte offset of the operator within the constant expression.
+        offset: usize,
+        /// The expected type of the operand.
+        expected: ValueType,
+        /// The actual type of the operand.
+        actual: ValueType,
+    },
+    /// Encountered too few or too many values on the operand stack.
+    InvalidStackHeight {
+        /// The byte offset of the operator within the constant expression.
+        offset: usize,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConstExprError {}
+
+impl Display for ConstExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedOperator { offset } => {
+                write!(f, "unsupported constant expression operator at offset {offset}")
+            }
+            Self::TypeMismatch {
+                offset,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "type mismatch in constant expression at offset {offset}: expected {expected:?} but found {actual:?}",
+            ),
+            Self::InvalidStackHeight { offset } => write!(
+                f,
+                "invalid operand stack height in constant expression at offset {offset}",
+            ),
+        }
+    }
+}
 
 /// Types that allow evluation given an evaluation context.
 pub trait Eval {
@@ -114,21 +169,46 @@ impl Eval for FuncRefOp {
 /// - `i64.add`
 /// - `i64.sub`
 /// - `i64.mul`
-#[allow(clippy::type_complexity)]
+#[derive(Debug)]
 pub struct ExprOp {
-    /// The underlying closure that implements the expression.
-    expr: Box<dyn Fn(&dyn EvalContext) -> Option<UntypedValue> + Send + Sync>,
+    /// The binary operator applied to the operands.
+    kind: ExprKind,
+    /// The left-hand side operand.
+    lhs: Box<Op>,
+    /// The right-hand side operand.
+    rhs: Box<Op>,
 }
 
-impl fmt::Debug for ExprOp {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("ExprOp").finish()
+/// The binary operator of an [`ExprOp`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExprKind {
+    I32Add,
+    I32Sub,
+    I32Mul,
+    I64Add,
+    I64Sub,
+    I64Mul,
+}
+
+impl ExprKind {
+    /// Applies the [`ExprKind`] operator to `lhs` and `rhs`.
+    fn apply(self, lhs: UntypedValue, rhs: UntypedValue) -> UntypedValue {
+        match self {
+            Self::I32Add => UntypedValue::i32_add(lhs, rhs),
+            Self::I32Sub => UntypedValue::i32_sub(lhs, rhs),
+            Self::I32Mul => UntypedValue::i32_mul(lhs, rhs),
+            Self::I64Add => UntypedValue::i64_add(lhs, rhs),
+            Self::I64Sub => UntypedValue::i64_sub(lhs, rhs),
+            Self::I64Mul => UntypedValue::i64_mul(lhs, rhs),
+        }
     }
 }
 
 impl Eval for ExprOp {
     fn eval(&self, ctx: &dyn EvalContext) -> Option<UntypedValue> {
-        (self.expr)(ctx)
+        let lhs = self.lhs.eval(ctx)?;
+        let rhs = self.rhs.eval(ctx)?;
+        Some(self.kind.apply(lhs, rhs))
     }
 }
 
@@ -153,13 +233,12 @@ impl Op {
         Self::FuncRef(FuncRefOp { function_index })
     }
 
-    /// Creates a new expression operator for the given `expr`.
-    pub fn expr<T>(expr: T) -> Self
-    where
-        T: Fn(&dyn EvalContext) -> Option<UntypedValue> + Send + Sync + 'static,
-    {
+    /// Creates a new expression operator applying `kind` to `lhs` and `rhs`.
+    pub fn expr(kind: ExprKind, lhs: Op, rhs: Op) -> Self {
         Self::Expr(ExprOp {
-            expr: Box::new(expr),
+            kind,
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
         })
     }
 }
@@ -175,11 +254,70 @@ impl Eval for Op {
     }
 }
 
+impl Encode for Op {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            Op::Const(op) => {
+                encoder.encode(&0_u8);
+                encoder.encode(&op.value);
+            }
+            Op::Global(op) => {
+                encoder.encode(&1_u8);
+                encoder.encode(&op.global_index);
+            }
+            Op::FuncRef(op) => {
+                encoder.encode(&2_u8);
+                encoder.encode(&op.function_index);
+            }
+            Op::Expr(op) => {
+                let kind: u8 = match op.kind {
+                    ExprKind::I32Add => 3,
+                    ExprKind::I32Sub => 4,
+                    ExprKind::I32Mul => 5,
+                    ExprKind::I64Add => 6,
+                    ExprKind::I64Sub => 7,
+                    ExprKind::I64Mul => 8,
+                };
+                encoder.encode(&kind);
+                encoder.encode(&*op.lhs);
+                encoder.encode(&*op.rhs);
+            }
+        }
+    }
+}
+
+impl Decode for Op {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let kind = match decoder.decode::<u8>()? {
+            0 => {
+                let value = decoder.decode()?;
+                return Ok(Op::Const(ConstOp { value }));
+            }
+            1 => return decoder.decode().map(Op::global),
+            2 => return decoder.decode().map(Op::funcref),
+            3 => ExprKind::I32Add,
+            4 => ExprKind::I32Sub,
+            5 => ExprKind::I32Mul,
+            6 => ExprKind::I64Add,
+            7 => ExprKind::I64Sub,
+            8 => ExprKind::I64Mul,
+            _ => return Err(ArtifactError::Malformed),
+        };
+        let lhs = decoder.decode()?;
+        let rhs = decoder.decode()?;
+        Ok(Op::expr(kind, lhs, rhs))
+    }
+}
+
 /// A Wasm constant expression.
 ///
 /// These are used to determine the offsets of memory data
 /// and table element segments as well as the initial value
 /// of global variables.
+///
+/// Use [`ConstExpr::parse`] and [`ConstExpr::eval`] to evaluate
+/// constant expressions the same way as Wasmi does, e.g. when
+/// resolving data and element segments in custom module loaders.
 #[derive(Debug)]
 pub struct ConstExpr {
     /// The root operator of the [`ConstExpr`].
@@ -192,16 +330,6 @@ impl Eval for ConstExpr {
     }
 }
 
-macro_rules! def_expr {
-    ($lhs:ident, $rhs:ident, $expr:expr) => {{
-        Op::expr(move |ctx: &dyn EvalContext| -> Option<UntypedValue> {
-            let lhs = $lhs.eval(ctx)?;
-            let rhs = $rhs.eval(ctx)?;
-            Some($expr(lhs, rhs))
-        })
-    }};
-}
-
 impl ConstExpr {
     /// Creates a new [`ConstExpr`] from the given Wasm [`ConstExpr`].
     ///
@@ -209,39 +337,19 @@ impl ConstExpr {
     ///
     /// The constructor assumes that Wasm validation already succeeded
     /// on the input Wasm [`ConstExpr`].
-    pub fn new(expr: wasmparser::ConstExpr<'_>) -> Self {
+    #[cfg(feature = "compiler")]
+    pub(crate) fn new(expr: wasmparser::ConstExpr<'_>) -> Self {
         /// A buffer required for translation of Wasm const expressions.
         type TranslationBuffer = SmallVec<[Op; 3]>;
         /// Convenience function to create the various expression operators.
-        fn expr_op(
-            stack: &mut TranslationBuffer,
-            expr: fn(UntypedValue, UntypedValue) -> UntypedValue,
-        ) {
+        fn expr_op(stack: &mut TranslationBuffer, kind: ExprKind) {
             let rhs = stack
                 .pop()
                 .expect("must have rhs operator on the stack due to Wasm validation");
             let lhs = stack
                 .pop()
                 .expect("must have lhs operator on the stack due to Wasm validation");
-            let op = match (lhs, rhs) {
-                (Op::Const(lhs), Op::Const(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::Const(lhs), Op::Global(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::Const(lhs), Op::FuncRef(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::Const(lhs), Op::Expr(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::Global(lhs), Op::Const(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::Global(lhs), Op::Global(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::Global(lhs), Op::FuncRef(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::Global(lhs), Op::Expr(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::FuncRef(lhs), Op::Const(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::FuncRef(lhs), Op::Global(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::FuncRef(lhs), Op::FuncRef(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::FuncRef(lhs), Op::Expr(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::Expr(lhs), Op::Const(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::Expr(lhs), Op::Global(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::Expr(lhs), Op::FuncRef(rhs)) => def_expr!(lhs, rhs, expr),
-                (Op::Expr(lhs), Op::Expr(rhs)) => def_expr!(lhs, rhs, expr),
-            };
-            stack.push(op);
+            stack.push(Op::expr(kind, lhs, rhs));
         }
 
         let mut reader = expr.get_operators_reader();
@@ -277,12 +385,12 @@ impl ConstExpr {
                 wasmparser::Operator::RefFunc { function_index } => {
                     stack.push(Op::funcref(function_index));
                 }
-                wasmparser::Operator::I32Add => expr_op(&mut stack, UntypedValue::i32_add),
-                wasmparser::Operator::I32Sub => expr_op(&mut stack, UntypedValue::i32_sub),
-                wasmparser::Operator::I32Mul => expr_op(&mut stack, UntypedValue::i32_mul),
-                wasmparser::Operator::I64Add => expr_op(&mut stack, UntypedValue::i64_add),
-                wasmparser::Operator::I64Sub => expr_op(&mut stack, UntypedValue::i64_sub),
-                wasmparser::Operator::I64Mul => expr_op(&mut stack, UntypedValue::i64_mul),
+                wasmparser::Operator::I32Add => expr_op(&mut stack, ExprKind::I32Add),
+                wasmparser::Operator::I32Sub => expr_op(&mut stack, ExprKind::I32Sub),
+                wasmparser::Operator::I32Mul => expr_op(&mut stack, ExprKind::I32Mul),
+                wasmparser::Operator::I64Add => expr_op(&mut stack, ExprKind::I64Add),
+                wasmparser::Operator::I64Sub => expr_op(&mut stack, ExprKind::I64Sub),
+                wasmparser::Operator::I64Mul => expr_op(&mut stack, ExprKind::I64Mul),
                 wasmparser::Operator::End => break,
                 op => panic!("encountered invalid Wasm const expression operator: {op:?}"),
             };
@@ -300,12 +408,147 @@ impl ConstExpr {
         Self { op }
     }
 
+    /// Parses a [`ConstExpr`] from the `bytes` of a Wasm constant expression.
+    ///
+    /// The `bytes` are a sequence of Wasm operators terminated by the `end` operator,
+    /// e.g. as found in the offsets of data and element segments or in the initializers
+    /// of global variables. The following operators are supported:
+    ///
+    /// - `i32.const`, `i64.const`, `f32.const` and `f64.const`
+    /// - `ref.null` and `ref.func`
+    /// - `global.get`
+    /// - `i32.add`, `i32.sub`, `i32.mul`, `i64.add`, `i64.sub` and `i64.mul`
+    ///   as defined by the Wasm `extended-const` proposal.
+    ///
+    /// # Note
+    ///
+    /// Since the types of global variables are unknown to the [`ConstExpr`]
+    /// operands of `global.get` operators are not type checked.
+    ///
+    /// # Errors
+    ///
+    /// - If the `bytes` are not a sequence of Wasm operators terminated by `end`.
+    /// - If an operator is not supported in constant expressions.
+    /// - If the operators are ill-typed or do not produce exactly one value.
+    #[cfg(feature = "compiler")]
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let expr = wasmparser::ConstExpr::new(bytes, 0);
+        Self::validate(expr)?;
+        Ok(Self::new(expr))
+    }
+
+    /// Validates the Wasm [`ConstExpr`] so that it can be translated via [`ConstExpr::new`].
+    ///
+    /// [`ConstExpr`]: wasmparser::ConstExpr
+    #[cfg(feature = "compiler")]
+    fn validate(expr: wasmparser::ConstExpr<'_>) -> Result<(), Error> {
+        /// The operand stack with the types of the operands.
+        ///
+        /// The type of operands produced by `global.get` is unknown and therefore `None`.
+        type TypeStack = SmallVec<[Option<ValueType>; 3]>;
+        /// Pops an operand of type `expected` from the `stack`.
+        fn pop(
+            stack: &mut TypeStack,
+            offset: usize,
+            expected: ValueType,
+        ) -> Result<(), ConstExprError> {
+            match stack.pop() {
+                None => Err(ConstExprError::InvalidStackHeight { offset }),
+                Some(Some(actual)) if actual != expected => Err(ConstExprError::TypeMismatch {
+                    offset,
+                    expected,
+                    actual,
+                }),
+                Some(_) => Ok(()),
+            }
+        }
+        let mut reader = expr.get_operators_reader();
+        let mut stack = TypeStack::new();
+        loop {
+            let offset = reader.original_position();
+            let ty = match reader.read()? {
+                wasmparser::Operator::I32Const { .. } => ValueType::I32,
+                wasmparser::Operator::I64Const { .. } => ValueType::I64,
+                wasmparser::Operator::F32Const { .. } => ValueType::F32,
+                wasmparser::Operator::F64Const { .. } => ValueType::F64,
+                wasmparser::Operator::RefNull {
+                    ty: wasmparser::ValType::FuncRef,
+                }
+                | wasmparser::Operator::RefFunc { .. } => ValueType::FuncRef,
+                wasmparser::Operator::RefNull {
+                    ty: wasmparser::ValType::ExternRef,
+                } => ValueType::ExternRef,
+                wasmparser::Operator::GlobalGet { .. } => {
+                    stack.push(None);
+                    continue;
+                }
+                wasmparser::Operator::I32Add
+                | wasmparser::Operator::I32Sub
+                | wasmparser::Operator::I32Mul => {
+                    pop(&mut stack, offset, ValueType::I32)?;
+                    pop(&mut stack, offset, ValueType::I32)?;
+                    ValueType::I32
+                }
+                wasmparser::Operator::I64Add
+                | wasmparser::Operator::I64Sub
+                | wasmparser::Operator::I64Mul => {
+                    pop(&mut stack, offset, ValueType::I64)?;
+                    pop(&mut stack, offset, ValueType::I64)?;
+                    ValueType::I64
+                }
+                wasmparser::Operator::End => {
+                    if stack.len() != 1 {
+                        return Err(ConstExprError::InvalidStackHeight { offset }.into());
+                    }
+                    break;
+                }
+                _ => return Err(ConstExprError::UnsupportedOperator { offset }.into()),
+            };
+            stack.push(Some(ty));
+        }
+        reader.ensure_end()?;
+        Ok(())
+    }
+
+    /// Evaluates the [`ConstExpr`] to a [`Value`] of type `ty`.
+    ///
+    /// Uses `global_get` and `func_get` to resolve the values of `global.get`
+    /// and `ref.func` operators given their global variable and function indices.
+    ///
+    /// Returns `None` if `global_get` or `func_get` return `None`.
+    ///
+    /// # Note
+    ///
+    /// - This evaluates the [`ConstExpr`] the same way as Wasmi evaluates the
+    ///   offsets of data and element segments and the initializers of global
+    ///   variables upon instantiation.
+    /// - The `ty` is the type of the evaluated value, e.g. the type of
+    ///   the initialized global variable or `i32` for segment offsets.
+    pub fn eval<G, F>(&self, ty: ValueType, global_get: G, func_get: F) -> Option<Value>
+    where
+        G: Fn(u32) -> Option<Value>,
+        F: Fn(u32) -> Option<FuncRef>,
+    {
+        let ctx = WrappedEvalContext {
+            global_get,
+            func_get,
+        };
+        Eval::eval(self, &ctx).map(|value| value.with_type(ty))
+    }
+
+    /// Create a new [`ConstExpr`] evaluating to the constant `value`.
+    pub(crate) fn new_const(value: Value) -> Self {
+        Self {
+            op: Op::constant(value),
+        }
+    }
+
     /// Create a new `ref.func x` [`ConstExpr`].
     ///
     /// # Note
     ///
     /// Required for setting up table elements.
-    pub fn new_funcref(function_index: u32) -> Self {
+    pub(crate) fn new_funcref(function_index: u32) -> Self {
         Self {
             op: Op::FuncRef(FuncRefOp { function_index }),
         }
@@ -314,7 +557,7 @@ impl ConstExpr {
     /// Returns `Some(index)` if the [`ConstExpr`] is a `funcref(index)`.
     ///
     /// Otherwise returns `None`.
-    pub fn funcref(&self) -> Option<FuncIdx> {
+    pub(crate) fn funcref(&self) -> Option<FuncIdx> {
         if let Op::FuncRef(op) = &self.op {
             return Some(FuncIdx::from(op.function_index));
         }
@@ -327,8 +570,8 @@ impl ConstExpr {
     ///
     /// This is useful for evaluations during Wasm translation to
     /// perform optimizations on the translated bytecode.
-    pub fn eval_const(&self) -> Option<UntypedValue> {
-        self.eval(&EmptyEvalContext)
+    pub(crate) fn eval_const(&self) -> Option<UntypedValue> {
+        Eval::eval(self, &EmptyEvalContext)
     }
 
     /// Evaluates the [`ConstExpr`] given a context for globals and functions.
@@ -339,34 +582,49 @@ impl ConstExpr {
     /// # Note
     ///
     /// This is useful for evaluation of [`ConstExpr`] during bytecode execution.
-    pub fn eval_with_context<G, F>(&self, global_get: G, func_get: F) -> Option<UntypedValue>
+    pub(crate) fn eval_with_context<G, F>(&self, global_get: G, func_get: F) -> Option<UntypedValue>
     where
         G: Fn(u32) -> Value,
         F: Fn(u32) -> FuncRef,
     {
-        /// Context that wraps closures representing partial evaluation contexts.
-        struct WrappedEvalContext<G, F> {
-            /// Wrapped context for global variables.
-            global_get: G,
-            /// Wrapped context for functions.
-            func_get: F,
-        }
-        impl<G, F> EvalContext for WrappedEvalContext<G, F>
-        where
-            G: Fn(u32) -> Value,
-            F: Fn(u32) -> FuncRef,
-        {
-            fn get_global(&self, index: u32) -> Option<Value> {
-                Some((self.global_get)(index))
-            }
+        let ctx = WrappedEvalContext {
+            global_get: |index| Some(global_get(index)),
+            func_get: |index| Some(func_get(index)),
+        };
+        Eval::eval(self, &ctx)
+    }
+}
 
-            fn get_func(&self, index: u32) -> Option<FuncRef> {
-                Some((self.func_get)(index))
-            }
-        }
-        self.eval(&WrappedEvalContext::<G, F> {
-            global_get,
-            func_get,
-        })
+impl Encode for ConstExpr {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&self.op);
+    }
+}
+
+impl Decode for ConstExpr {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        decoder.decode().map(|op| Self { op })
+    }
+}
+
+/// Context that wraps closures representing partial evaluation contexts.
+struct WrappedEvalContext<G, F> {
+    /// Wrapped context for global variables.
+    global_get: G,
+    /// Wrapped context for functions.
+    func_get: F,
+}
+
+impl<G, F> EvalContext for WrappedEvalContext<G, F>
+where
+    G: Fn(u32) -> Option<Value>,
+    F: Fn(u32) -> Option<FuncRef>,
+{
+    fn get_global(&self, index: u32) -> Option<Value> {
+        (self.global_get)(index)
+    }
+
+    fn get_func(&self, index: u32) -> Option<FuncRef> {
+        (self.func_get)(index)
     }
 }
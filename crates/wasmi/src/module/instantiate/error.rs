@@ -1,6 +1,7 @@
 use crate::{
     errors::{MemoryError, TableError},
     global::GlobalError,
+    module::ImportName,
     Extern,
     ExternType,
     FuncType,
@@ -50,6 +51,30 @@ pub enum InstantiationError {
         index: u32,
     },
     TooManyInstances,
+    /// Caused when an import is denied by the [`ImportPolicy`] of the [`Store`].
+    ///
+    /// [`ImportPolicy`]: crate::ImportPolicy
+    /// [`Store`]: crate::Store
+    ImportDenied {
+        /// The name of the denied import.
+        name: ImportName,
+    },
+    /// Caused when the linear memories or global variables of an [`Instance`] do not
+    /// match the layout of the [`Module`] it is hot-reloaded with.
+    ///
+    /// [`Instance`]: crate::Instance
+    /// [`Module`]: crate::Module
+    IncompatibleReload,
+    /// Caused when an [`Instance`] is hot-reloaded while the [`Store`] is executing.
+    ///
+    /// [`Instance`]: crate::Instance
+    /// [`Store`]: crate::Store
+    ReloadWhileExecuting,
+    /// Caused when an [`Instance`] is reset without [`Config::instance_reset`] enabled.
+    ///
+    /// [`Instance`]: crate::Instance
+    /// [`Config::instance_reset`]: crate::Config::instance_reset
+    ResetDisabled,
 }
 
 #[cfg(feature = "std")]
@@ -69,7 +94,7 @@ impl Display for InstantiationError {
             Self::SignatureMismatch { expected, actual } => {
                 write!(
                     f,
-                    "expected {expected:?} function signature but found {actual:?}",
+                    "expected {expected} function signature but found {actual}",
                 )
             }
             Self::ElementSegmentDoesNotFit {
@@ -86,7 +111,20 @@ impl Display for InstantiationError {
             Self::Table(error) => Display::fmt(error, f),
             Self::Memory(error) => Display::fmt(error, f),
             Self::Global(error) => Display::fmt(error, f),
-            Self::TooManyInstances => write!(f, "too many instances")
+            Self::TooManyInstances => write!(f, "too many instances"),
+            Self::ImportDenied { name } => {
+                write!(f, "import {name} is denied by the import policy")
+            }
+            Self::IncompatibleReload => write!(
+                f,
+                "the linear memories or global variables of the instance do not match the reloaded module",
+            ),
+            Self::ReloadWhileExecuting => {
+                write!(f, "cannot reload an instance while the store is executing")
+            }
+            Self::ResetDisabled => {
+                write!(f, "instance reset is disabled by the engine config")
+            }
         }
     }
 }
@@ -1,7 +1,8 @@
 mod error;
 mod pre;
+mod reload;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "compiler"))]
 mod tests;
 
 pub use self::{error::InstantiationError, pre::InstancePre};
@@ -22,37 +23,49 @@ use crate::{
     Instance,
     InstanceEntity,
     InstanceEntityBuilder,
+    InstanceInit,
     Memory,
     Table,
     Value,
 };
+use alloc::vec::Vec;
 use wasmi_core::UntypedValue;
 
 impl Module {
     /// Instantiates a new [`Instance`] from the given compiled [`Module`].
     ///
-    /// Uses the given `context` to store the instance data to.
     /// The given `externals` are joined with the imports in the same order in which they occurred.
     ///
     /// # Note
     ///
-    /// This is a very low-level API. For a more high-level API users should use the
-    /// corresponding instantiation methods provided by the [`Linker`].
-    ///
-    /// # Errors
-    ///
-    /// If the given `externals` do not satisfy the required imports, e.g. if an externally
-    /// provided [`Func`] has a different function signature than required by the module import.
+    /// This does not check the `externals` against the imports of the [`Module`]
+    /// since the [`Linker`] already did so while resolving them.
     ///
     /// [`Linker`]: struct.Linker.html
-    /// [`Func`]: [`crate::Func`]
-    pub(crate) fn instantiate<I>(
+    pub(crate) fn instantiate_resolved(
         &self,
-        mut context: impl AsContextMut,
-        externals: I,
-    ) -> Result<InstancePre, Error>
+        context: impl AsContextMut,
+        externals: Vec<Extern>,
+    ) -> Result<InstancePre, Error> {
+        debug_assert_eq!(externals.len(), self.imports().len());
+        self.instantiate_with(context, |_, builder| {
+            for external in externals {
+                match external {
+                    Extern::Func(func) => builder.push_func(func),
+                    Extern::Table(table) => builder.push_table(table),
+                    Extern::Memory(memory) => builder.push_memory(memory),
+                    Extern::Global(global) => builder.push_global(global),
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Instantiates a new [`Instance`] with imports provided by `push_imports`.
+    fn instantiate_with<C, F>(&self, mut context: C, push_imports: F) -> Result<InstancePre, Error>
     where
-        I: IntoIterator<Item = Extern>,
+        C: AsContextMut,
+        F: FnOnce(&C, &mut InstanceEntityBuilder) -> Result<(), InstantiationError>,
     {
         context
             .as_context_mut()
@@ -61,7 +74,7 @@ impl Module {
         let handle = context.as_context_mut().store.inner.alloc_instance();
         let mut builder = InstanceEntity::build(self);
 
-        self.extract_imports(&context, &mut builder, externals)?;
+        push_imports(&context, &mut builder)?;
         self.extract_functions(&mut context, &mut builder, handle);
         self.extract_tables(&mut context, &mut builder)?;
         self.extract_memories(&mut context, &mut builder)?;
@@ -71,9 +84,13 @@ impl Module {
 
         self.initialize_table_elements(&mut context, &mut builder)?;
         self.initialize_memory_data(&mut context, &mut builder)?;
+        if context.as_context().engine().config().get_instance_reset() {
+            self.record_init(&context, &mut builder);
+        }
 
         // At this point the module instantiation is nearly done.
         // The only thing that is missing is to run the `start` function.
+        self.engine().counters().instantiated();
         Ok(InstancePre::new(handle, builder))
     }
 
@@ -114,6 +131,7 @@ impl Module {
                     return Err(InstantiationError::ImportsExternalsLenMismatch)
                 }
             };
+            context.as_context().store.check_import(&import)?;
             match (import.ty(), external) {
                 (ExternType::Func(expected_signature), Extern::Func(func)) => {
                     let actual_signature = func.ty_dedup(context.as_context());
@@ -352,15 +370,64 @@ impl Module {
     ) -> Result<(), Error> {
         for segment in &self.data_segments[..] {
             let bytes = segment.bytes();
-            if let DataSegmentKind::Active(segment) = segment.kind() {
-                let offset_expr = segment.offset();
+            if let DataSegmentKind::Active(active) = segment.kind() {
+                let offset_expr = active.offset();
                 let offset =
                     u32::from(Self::eval_init_expr(&mut *context, builder, offset_expr)) as usize;
-                let memory = builder.get_memory(segment.memory_index().into_u32());
+                let memory = builder.get_memory(active.memory_index().into_u32());
                 memory.write(&mut *context, offset, bytes)?;
+                let store = &mut context.as_context_mut().store.inner;
+                if store.engine().config().get_memory_reset() {
+                    // Note: Recorded so that `Memory::reset` can restore the initialized contents.
+                    store
+                        .resolve_memory_mut(&memory)
+                        .push_init_segment(offset, segment.clone_bytes());
+                }
             }
             builder.push_data_segment(DataSegment::new(context.as_context_mut(), segment));
         }
         Ok(())
     }
+
+    /// Records the state of the [`Instance`] under construction for [`Instance::reset`].
+    fn record_init(&self, context: &impl AsContext, builder: &mut InstanceEntityBuilder) {
+        let imports = &self.header.inner.imports;
+        let store = &context.as_context().store.inner;
+        let mut init = InstanceInit::default();
+        for (index, (global_type, _)) in (imports.len_globals..).zip(self.internal_globals()) {
+            if global_type.mutability().is_mut() {
+                let global = builder.get_global(index as u32);
+                init.push_global(global, store.resolve_global(&global).get_untyped());
+            }
+        }
+        for index in imports.len_tables..self.len_tables() {
+            init.push_table(builder.get_table(index as u32));
+        }
+        for segment in &self.header.inner.element_segments[..] {
+            if let ElementSegmentKind::Active(active) = segment.kind() {
+                let offset = u32::from(Self::eval_init_expr(context, builder, active.offset()));
+                let table = builder.get_table(active.table_index().into_u32());
+                let entity = store.resolve_table(&table);
+                let elements = (offset..)
+                    .take(segment.items_cloned().items().len())
+                    .map(|index| {
+                        entity.get_untyped(index).unwrap_or_else(|| {
+                            panic!("element segment must fit into table due to instantiation")
+                        })
+                    })
+                    .collect();
+                init.push_elements(table, offset, elements);
+            }
+        }
+        for index in imports.len_memories..self.len_memories() {
+            init.push_memory(builder.get_memory(index as u32));
+        }
+        for segment in builder.data_segments() {
+            init.push_data_segment(*segment, store.resolve_data_segment(segment).clone());
+        }
+        for segment in builder.element_segments() {
+            init.push_element_segment(*segment, store.resolve_element_segment(segment).clone());
+        }
+        builder.set_init(init);
+    }
 }
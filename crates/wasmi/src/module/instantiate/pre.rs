@@ -23,7 +23,7 @@ impl InstancePre {
     /// Returns the index of the `start` function if any.
     ///
     /// Returns `None` if the Wasm module does not have a `start` function.
-    fn start_fn(&self) -> Option<u32> {
+    pub(crate) fn start_fn(&self) -> Option<u32> {
         self.builder.get_start().map(FuncIdx::into_u32)
     }
 
@@ -42,21 +42,16 @@ impl InstancePre {
     /// If the `start` function is invalid albeit successful validation.
     pub fn start(self, mut context: impl AsContextMut) -> Result<Instance, Error> {
         let opt_start_index = self.start_fn();
-        context
-            .as_context_mut()
-            .store
-            .inner
-            .initialize_instance(self.handle, self.builder.finish());
+        let handle = self.initialize(&mut context);
         if let Some(start_index) = opt_start_index {
-            let start_func = self
-                .handle
+            let start_func = handle
                 .get_func_by_index(&mut context, start_index)
                 .unwrap_or_else(|| {
                     panic!("encountered invalid start function after validation: {start_index}")
                 });
             start_func.call(context.as_context_mut(), &[], &mut [])?
         }
-        Ok(self.handle)
+        Ok(handle)
     }
 
     /// Finishes instantiation ensuring that no `start` function exists.
@@ -66,16 +61,25 @@ impl InstancePre {
     /// If a `start` function exists that needs to be called for conformant module instantiation.
     pub fn ensure_no_start(
         self,
-        mut context: impl AsContextMut,
+        context: impl AsContextMut,
     ) -> Result<Instance, InstantiationError> {
         if let Some(index) = self.start_fn() {
             return Err(InstantiationError::FoundStartFn { index });
         }
+        Ok(self.initialize(context))
+    }
+
+    /// Initializes the [`Instance`] without executing its `start` function.
+    ///
+    /// # Note
+    ///
+    /// Callers are responsible for executing the `start` function if any.
+    pub(crate) fn initialize(self, mut context: impl AsContextMut) -> Instance {
         context
             .as_context_mut()
             .store
             .inner
             .initialize_instance(self.handle, self.builder.finish());
-        Ok(self.handle)
+        self.handle
     }
 }
@@ -0,0 +1,140 @@
+use super::InstantiationError;
+use crate::{
+    memory::DataSegment,
+    module::Module,
+    AsContext,
+    AsContextMut,
+    Error,
+    Extern,
+    Instance,
+    InstanceEntity,
+    InstanceEntityBuilder,
+};
+
+impl Module {
+    /// Hot-reloads `instance` with the code of this [`Module`].
+    ///
+    /// The given `externals` are joined with the imports in the same order in which they occurred.
+    ///
+    /// # Note
+    ///
+    /// This is a very low-level API. For a more high-level API users should use
+    /// [`Linker::reload`].
+    ///
+    /// # Errors
+    ///
+    /// - If the [`Store`] is currently executing, e.g. when called from a host function.
+    /// - If the given `externals` do not satisfy the required imports.
+    /// - If the linear memories or global variables of `instance` are incompatible with this [`Module`].
+    ///
+    /// [`Linker::reload`]: crate::Linker::reload
+    /// [`Store`]: crate::Store
+    pub(crate) fn reload<I>(
+        &self,
+        mut context: impl AsContextMut,
+        instance: &Instance,
+        externals: I,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = Extern>,
+    {
+        // Note: Reloading replaces the functions and tables of `instance` which
+        //       must not happen while its call frames are on the call stack.
+        if context.as_context().store.inner.is_executing() {
+            return Err(Error::from(InstantiationError::ReloadWhileExecuting));
+        }
+        let mut builder = InstanceEntity::build(self);
+
+        self.extract_imports(&context, &mut builder, externals)?;
+        // Note: We check the compatibility of `instance` before allocating
+        //       any new entities so that incompatible reloads fail early.
+        self.reuse_memories(&context, &mut builder, instance)?;
+        self.reuse_globals(&context, &mut builder, instance)?;
+        self.extract_functions(&mut context, &mut builder, *instance);
+        self.extract_tables(&mut context, &mut builder)?;
+        self.extract_exports(&mut builder);
+
+        self.initialize_table_elements(&mut context, &mut builder)?;
+        self.reload_data_segments(&mut context, &mut builder);
+
+        context
+            .as_context_mut()
+            .store
+            .inner
+            .reinitialize_instance(*instance, builder.finish());
+        Ok(())
+    }
+
+    /// Reuses the internal linear memories of `instance` for the reloaded [`Instance`].
+    ///
+    /// # Errors
+    ///
+    /// - If `instance` has a different number of linear memories than this [`Module`].
+    /// - If a linear memory of `instance` does not satisfy the required memory type.
+    fn reuse_memories(
+        &self,
+        context: &impl AsContext,
+        builder: &mut InstanceEntityBuilder,
+        instance: &Instance,
+    ) -> Result<(), InstantiationError> {
+        let entity = context.as_context().store.inner.resolve_instance(instance);
+        let len_imported = self.header.inner.imports.len_memories as u32;
+        let len_memories = self.len_memories() as u32;
+        if entity.get_memory(len_memories).is_some() {
+            return Err(InstantiationError::IncompatibleReload);
+        }
+        for (index, required) in (len_imported..).zip(self.internal_memories()) {
+            let memory = entity
+                .get_memory(index)
+                .ok_or(InstantiationError::IncompatibleReload)?;
+            memory
+                .dynamic_ty(context.as_context())
+                .is_subtype_or_err(required)?;
+            builder.push_memory(memory);
+        }
+        Ok(())
+    }
+
+    /// Reuses the internal global variables of `instance` for the reloaded [`Instance`].
+    ///
+    /// # Errors
+    ///
+    /// - If `instance` has a different number of global variables than this [`Module`].
+    /// - If a global variable of `instance` does not have the required global type.
+    fn reuse_globals(
+        &self,
+        context: &impl AsContext,
+        builder: &mut InstanceEntityBuilder,
+        instance: &Instance,
+    ) -> Result<(), InstantiationError> {
+        let entity = context.as_context().store.inner.resolve_instance(instance);
+        let len_imported = self.header.inner.imports.len_globals as u32;
+        let len_globals = self.len_globals() as u32;
+        if entity.get_global(len_globals).is_some() {
+            return Err(InstantiationError::IncompatibleReload);
+        }
+        for (index, (required, _)) in (len_imported..).zip(self.internal_globals()) {
+            let global = entity
+                .get_global(index)
+                .ok_or(InstantiationError::IncompatibleReload)?;
+            global.ty(context.as_context()).satisfies(required)?;
+            builder.push_global(global);
+        }
+        Ok(())
+    }
+
+    /// Registers the Wasm data segments of the [`Module`] without initializing linear memories.
+    ///
+    /// # Note
+    ///
+    /// Active data segments are dropped as if they had been applied upon instantiation.
+    fn reload_data_segments(
+        &self,
+        context: &mut impl AsContextMut,
+        builder: &mut InstanceEntityBuilder,
+    ) {
+        for segment in &self.data_segments[..] {
+            builder.push_data_segment(DataSegment::new(context.as_context_mut(), segment));
+        }
+    }
+}
@@ -0,0 +1,449 @@
+use super::{FuncBody, FuncTransform};
+use crate::Error;
+use alloc::vec::Vec;
+use wasmparser::{
+    BinaryReader,
+    CodeSectionReader,
+    FunctionBody,
+    GlobalSectionReader,
+    ImportSectionReader,
+    Operator,
+    Parser,
+    TypeRef,
+};
+
+/// Injects an instruction counter into Wasm function bodies.
+///
+/// At the start of every basic block the counter is incremented by the
+/// number of Wasm operators of the basic block. The counter is a mutable
+/// `i64` global variable of the Wasm module given by its index.
+///
+/// # Note
+///
+/// - This embeds metering into the Wasm module itself instead of relying on
+///   the fuel metering of the [`Engine`] so that it is preserved if the
+///   instrumented Wasm module is executed elsewhere.
+/// - Use [`InstructionCounter::instrument`] to add the counter to a Wasm module.
+/// - Use [`Module::new_with_transform`] to instrument a Wasm module that
+///   already declares the counter upon its creation.
+///
+/// [`Engine`]: crate::Engine
+/// [`Module::new_with_transform`]: crate::Module::new_with_transform
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InstructionCounter {
+    /// The index of the mutable `i64` global variable used as counter.
+    global_index: u32,
+}
+
+impl InstructionCounter {
+    /// Creates a new [`InstructionCounter`] incrementing the global variable at `global_index`.
+    pub fn new(global_index: u32) -> Self {
+        Self { global_index }
+    }
+
+    /// Returns the index of the global variable used as counter.
+    pub fn global_index(&self) -> u32 {
+        self.global_index
+    }
+
+    /// Adds an instruction counter to all function bodies of the Wasm module `wasm`.
+    ///
+    /// The counter is a new mutable `i64` global variable initialized to zero
+    /// which is exported under `export_name` so that hosts can read it.
+    ///
+    /// # Errors
+    ///
+    /// If `wasm` is malformed.
+    pub fn instrument(wasm: &[u8], export_name: &str) -> Result<Vec<u8>, Error> {
+        let mut len_globals = 0;
+        for section in sections(wasm)? {
+            let (id, offset, payload) = section?;
+            match id {
+                SECTION_IMPORT => {
+                    for import in ImportSectionReader::new(payload, offset)? {
+                        if let TypeRef::Global(_) = import?.ty {
+                            len_globals += 1;
+                        }
+                    }
+                }
+                SECTION_GLOBAL => {
+                    len_globals += GlobalSectionReader::new(payload, offset)?.count();
+                }
+                _ => {}
+            }
+        }
+        let mut counter = Self::new(len_globals);
+        rewrite_module(wasm, &mut counter, Some(export_name))
+    }
+}
+
+impl FuncTransform for InstructionCounter {
+    fn transform(&mut self, body: FuncBody) -> Result<Option<Vec<u8>>, Error> {
+        let global_index = self.global_index;
+        let bytes = instrument_body(body, |buffer, block| {
+            buffer.push(0x23); // global.get
+            write_u32(buffer, global_index);
+            buffer.push(0x42); // i64.const
+            write_i64(buffer, i64::from(block.len_ops));
+            buffer.push(0x7C); // i64.add
+            buffer.push(0x24); // global.set
+            write_u32(buffer, global_index);
+        })?;
+        Ok(Some(bytes))
+    }
+}
+
+/// A probe injected by [`CoverageProbes`] at the start of a basic block.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CoverageProbe {
+    /// The index of the instrumented function including imported functions.
+    func_index: u32,
+    /// The offset of the basic block within the original Wasm module.
+    offset: usize,
+}
+
+impl CoverageProbe {
+    /// Returns the index of the instrumented function within the Wasm module.
+    ///
+    /// # Note
+    ///
+    /// The index space includes imported functions.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the offset of the first Wasm operator of the probed basic block within the Wasm module.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Injects coverage probes into Wasm function bodies.
+///
+/// At the start of every basic block the function at the given index is
+/// called with the `i32` identifier of the probe. The function must have
+/// the type `(func (param i32))` and usually is a host function imported
+/// by the Wasm module that records which probes have been hit.
+///
+/// The identifier of a probe is its index in [`CoverageProbes::probes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageProbes {
+    /// The index of the function called by the probes.
+    func_index: u32,
+    /// The probes injected so far.
+    probes: Vec<CoverageProbe>,
+}
+
+impl CoverageProbes {
+    /// Creates a new [`CoverageProbes`] calling the function at `func_index`.
+    pub fn new(func_index: u32) -> Self {
+        Self {
+            func_index,
+            probes: Vec::new(),
+        }
+    }
+
+    /// Returns the probes injected so far indexed by their identifiers.
+    pub fn probes(&self) -> &[CoverageProbe] {
+        &self.probes
+    }
+}
+
+impl FuncTransform for CoverageProbes {
+    fn transform(&mut self, body: FuncBody) -> Result<Option<Vec<u8>>, Error> {
+        let func_index = self.func_index;
+        let probes = &mut self.probes;
+        let bytes = instrument_body(body, |buffer, block| {
+            let id = probes.len() as u32;
+            probes.push(CoverageProbe {
+                func_index: body.func_index(),
+                offset: body.offset() + block.start,
+            });
+            buffer.push(0x41); // i32.const
+            write_i64(buffer, i64::from(id as i32));
+            buffer.push(0x10); // call
+            write_u32(buffer, func_index);
+        })?;
+        Ok(Some(bytes))
+    }
+}
+
+/// A basic block of a Wasm function body.
+#[derive(Debug, Copy, Clone)]
+struct BasicBlock {
+    /// The position of the first operator of the basic block within the function body.
+    start: usize,
+    /// The number of operators of the basic block.
+    len_ops: u32,
+}
+
+/// Returns `true` if the Wasm operator `op` ends a basic block.
+fn ends_basic_block(op: &Operator) -> bool {
+    matches!(
+        op,
+        Operator::Block { .. }
+            | Operator::Loop { .. }
+            | Operator::If { .. }
+            | Operator::Else
+            | Operator::End
+            | Operator::Br { .. }
+            | Operator::BrIf { .. }
+            | Operator::BrTable { .. }
+            | Operator::Return
+            | Operator::ReturnCall { .. }
+            | Operator::ReturnCallIndirect { .. }
+            | Operator::Unreachable
+    )
+}
+
+/// Rewrites the Wasm function `body` with code emitted by `probe` at the start of all basic blocks.
+///
+/// # Note
+///
+/// The code emitted by `probe` must not alter the value stack.
+///
+/// # Errors
+///
+/// If the function `body` is malformed.
+fn instrument_body(
+    body: FuncBody,
+    mut probe: impl FnMut(&mut Vec<u8>, BasicBlock),
+) -> Result<Vec<u8>, Error> {
+    let bytes = body.bytes();
+    let mut reader = FunctionBody::new(0, bytes).get_operators_reader()?;
+    let mut blocks = Vec::new();
+    let mut block = BasicBlock {
+        start: reader.original_position(),
+        len_ops: 0,
+    };
+    while !reader.eof() {
+        let op = reader.read()?;
+        block.len_ops += 1;
+        if ends_basic_block(&op) && !reader.eof() {
+            blocks.push(block);
+            block = BasicBlock {
+                start: reader.original_position(),
+                len_ops: 0,
+            };
+        }
+    }
+    blocks.push(block);
+    let mut buffer = Vec::with_capacity(bytes.len() + blocks.len() * 16);
+    let mut copied = 0;
+    for block in blocks {
+        buffer.extend_from_slice(&bytes[copied..block.start]);
+        copied = block.start;
+        probe(&mut buffer, block);
+    }
+    buffer.extend_from_slice(&bytes[copied..]);
+    Ok(buffer)
+}
+
+/// The identifier of the Wasm import section.
+const SECTION_IMPORT: u8 = 2;
+/// The identifier of the Wasm global section.
+const SECTION_GLOBAL: u8 = 6;
+/// The identifier of the Wasm export section.
+const SECTION_EXPORT: u8 = 7;
+/// The identifier of the Wasm code section.
+const SECTION_CODE: u8 = 10;
+
+/// Returns the position of the Wasm section with identifier `id` in the order of sections.
+///
+/// Returns `None` for custom sections which may appear anywhere.
+fn section_order(id: u8) -> Option<u8> {
+    let order = match id {
+        1..=5 => id,
+        13 => 6,
+        6..=9 => id + 1,
+        12 => 11,
+        10 | 11 => id + 2,
+        _ => return None,
+    };
+    Some(order)
+}
+
+/// A section of a Wasm module given by its identifier, offset and payload.
+type Section<'a> = (u8, usize, &'a [u8]);
+
+/// Returns an iterator over the identifiers, offsets and payloads of the sections of `wasm`.
+///
+/// # Errors
+///
+/// If `wasm` does not start with a valid Wasm header.
+fn sections(wasm: &[u8]) -> Result<impl Iterator<Item = Result<Section<'_>, Error>>, Error> {
+    // Note: Parses the header of the Wasm module only.
+    Parser::new(0).parse(wasm, true)?;
+    let mut reader = BinaryReader::new(wasm);
+    reader.read_bytes(8)?;
+    Ok(core::iter::from_fn(move || {
+        if reader.eof() {
+            return None;
+        }
+        let mut read_section = || -> Result<_, Error> {
+            let id = reader.read_u8()?;
+            let len = reader.read_var_u32()?;
+            let offset = reader.original_position();
+            let payload = reader.read_bytes(len as usize)?;
+            Ok((id, offset, payload))
+        };
+        Some(read_section())
+    }))
+}
+
+/// Applies `transform` to all function bodies of the Wasm module `wasm` and returns the rewritten module.
+///
+/// If `export_counter` is `Some` a mutable `i64` global variable is appended
+/// to the Wasm module and exported under the given name.
+///
+/// # Errors
+///
+/// - If `wasm` is malformed.
+/// - If `transform` fails for any function body.
+pub(super) fn rewrite_module(
+    wasm: &[u8],
+    transform: &mut dyn FuncTransform,
+    export_counter: Option<&str>,
+) -> Result<Vec<u8>, Error> {
+    let mut buffer = Vec::with_capacity(wasm.len());
+    buffer.extend_from_slice(&wasm[..8.min(wasm.len())]);
+    let mut len_imported_funcs = 0;
+    let mut len_globals = 0;
+    let mut pending_global = export_counter.is_some();
+    let mut pending_export = export_counter.is_some();
+    for section in sections(wasm)? {
+        let (id, offset, payload) = section?;
+        let order = section_order(id);
+        if let Some(order) = order {
+            if pending_global && order > section_order(SECTION_GLOBAL).unwrap() {
+                write_section(&mut buffer, SECTION_GLOBAL, &append_global(&[0x00])?);
+                pending_global = false;
+            }
+            if pending_export && order > section_order(SECTION_EXPORT).unwrap() {
+                let export = append_export(&[0x00], export_counter, len_globals)?;
+                write_section(&mut buffer, SECTION_EXPORT, &export);
+                pending_export = false;
+            }
+        }
+        match id {
+            SECTION_IMPORT => {
+                for import in ImportSectionReader::new(payload, offset)? {
+                    match import?.ty {
+                        TypeRef::Func(_) => len_imported_funcs += 1,
+                        TypeRef::Global(_) => len_globals += 1,
+                        _ => {}
+                    }
+                }
+                write_section(&mut buffer, id, payload);
+            }
+            SECTION_GLOBAL if pending_global => {
+                len_globals += GlobalSectionReader::new(payload, offset)?.count();
+                write_section(&mut buffer, id, &append_global(payload)?);
+                pending_global = false;
+            }
+            SECTION_GLOBAL => {
+                len_globals += GlobalSectionReader::new(payload, offset)?.count();
+                write_section(&mut buffer, id, payload);
+            }
+            SECTION_EXPORT if pending_export => {
+                let export = append_export(payload, export_counter, len_globals)?;
+                write_section(&mut buffer, id, &export);
+                pending_export = false;
+            }
+            SECTION_CODE => {
+                let reader = CodeSectionReader::new(payload, offset)?;
+                let mut code = Vec::with_capacity(payload.len());
+                write_u32(&mut code, reader.count());
+                for (func_index, body) in (len_imported_funcs..).zip(reader) {
+                    let range = body?.range();
+                    let bytes = &wasm[range.clone()];
+                    let body = FuncBody::new(func_index, range.start, bytes);
+                    match transform.transform(body)? {
+                        Some(transformed) => write_bytes(&mut code, &transformed),
+                        None => write_bytes(&mut code, bytes),
+                    }
+                }
+                write_section(&mut buffer, id, &code);
+            }
+            _ => write_section(&mut buffer, id, payload),
+        }
+    }
+    if pending_global {
+        write_section(&mut buffer, SECTION_GLOBAL, &append_global(&[0x00])?);
+    }
+    if pending_export {
+        let export = append_export(&[0x00], export_counter, len_globals)?;
+        write_section(&mut buffer, SECTION_EXPORT, &export);
+    }
+    Ok(buffer)
+}
+
+/// Returns the global section `payload` with an appended mutable `i64` global variable.
+fn append_global(payload: &[u8]) -> Result<Vec<u8>, Error> {
+    let (count, items) = split_count(payload)?;
+    let mut buffer = Vec::with_capacity(payload.len() + 8);
+    write_u32(&mut buffer, count + 1);
+    buffer.extend_from_slice(items);
+    // (global (mut i64) (i64.const 0))
+    buffer.extend_from_slice(&[0x7E, 0x01, 0x42, 0x00, 0x0B]);
+    Ok(buffer)
+}
+
+/// Returns the export section `payload` with an appended export of the global variable at `global_index`.
+fn append_export(payload: &[u8], name: Option<&str>, global_index: u32) -> Result<Vec<u8>, Error> {
+    let name = name.unwrap_or_default();
+    let (count, items) = split_count(payload)?;
+    let mut buffer = Vec::with_capacity(payload.len() + name.len() + 8);
+    write_u32(&mut buffer, count + 1);
+    buffer.extend_from_slice(items);
+    write_bytes(&mut buffer, name.as_bytes());
+    buffer.push(0x03); // global export
+    write_u32(&mut buffer, global_index);
+    Ok(buffer)
+}
+
+/// Splits the section `payload` into its number of items and the encoded items.
+fn split_count(payload: &[u8]) -> Result<(u32, &[u8]), Error> {
+    let mut reader = BinaryReader::new(payload);
+    let count = reader.read_var_u32()?;
+    Ok((count, &payload[reader.current_position()..]))
+}
+
+/// Writes the section with identifier `id` and `payload` to `buffer`.
+fn write_section(buffer: &mut Vec<u8>, id: u8, payload: &[u8]) {
+    buffer.push(id);
+    write_bytes(buffer, payload);
+}
+
+/// Writes the length prefixed `bytes` to `buffer`.
+fn write_bytes(buffer: &mut Vec<u8>, bytes: &[u8]) {
+    write_u32(buffer, bytes.len() as u32);
+    buffer.extend_from_slice(bytes);
+}
+
+/// Writes the LEB-128 encoded unsigned `value` to `buffer`.
+fn write_u32(buffer: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            buffer.push(byte);
+            return;
+        }
+        buffer.push(byte | 0x80);
+    }
+}
+
+/// Writes the LEB-128 encoded signed `value` to `buffer`.
+fn write_i64(buffer: &mut Vec<u8>, mut value: i64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+        if done {
+            buffer.push(byte);
+            return;
+        }
+        buffer.push(byte | 0x80);
+    }
+}
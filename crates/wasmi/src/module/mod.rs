@@ -1,4 +1,8 @@
+mod analysis;
+mod artifact;
 mod builder;
+mod code_size;
+mod compile_stats;
 mod data;
 mod element;
 mod export;
@@ -6,30 +10,58 @@ mod global;
 mod import;
 mod init_expr;
 mod instantiate;
+#[cfg(feature = "compiler")]
+mod instrument;
+mod names;
+#[cfg(feature = "compiler")]
 mod parser;
+mod preinit;
 mod read;
+mod serialize;
+mod sha256;
+#[cfg(feature = "compiler")]
+mod transform;
+#[cfg(feature = "compiler")]
 pub(crate) mod utils;
 
+#[cfg(feature = "compiler")]
+pub(crate) use self::utils::WasmiValueType;
 use self::{
-    builder::ModuleBuilder,
+    artifact::{decode_artifact, encode_artifact},
     export::ExternIdx,
     global::Global,
     import::{ExternTypeIdx, Import},
-    parser::{parse, parse_unchecked},
-};
-pub(crate) use self::{
-    data::{DataSegment, DataSegmentKind},
-    element::{ElementSegment, ElementSegmentItems, ElementSegmentKind},
-    init_expr::ConstExpr,
-    utils::WasmiValueType,
+    serialize::{deserialize_module, serialize_module, CODE_MAGIC},
 };
 pub use self::{
+    analysis::{FuncAnalysis, ModuleAnalysis},
+    artifact::{ArtifactError, ArtifactFeatures, ArtifactHash, ArtifactTarget},
+    code_size::FuncCodeSize,
+    compile_stats::CompileStats,
     export::{ExportType, FuncIdx, MemoryIdx, ModuleExportsIter, TableIdx},
     global::GlobalIdx,
     import::{FuncTypeIdx, ImportName},
+    init_expr::{ConstExpr, ConstExprError},
     instantiate::{InstancePre, InstantiationError},
+    preinit::PreinitError,
     read::{Read, ReadError},
 };
+#[cfg(feature = "compiler")]
+use self::{
+    builder::ModuleBuilder,
+    parser::{parse, parse_shared, parse_unchecked, parse_with_transform},
+};
+pub(crate) use self::{
+    data::{DataSegment, DataSegmentKind},
+    element::{ElementSegment, ElementSegmentItems, ElementSegmentKind},
+    names::ModuleNames,
+    serialize::{Decode, Decoder, Encode, Encoder},
+};
+#[cfg(feature = "compiler")]
+pub use self::{
+    instrument::{CoverageProbe, CoverageProbes, InstructionCounter},
+    transform::{FuncBody, FuncTransform},
+};
 use crate::{
     engine::{CompiledFunc, DedupFuncType, EngineWeak},
     Engine,
@@ -40,9 +72,18 @@ use crate::{
     MemoryType,
     TableType,
 };
-use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec::Vec,
+};
 use core::{iter, slice::Iter as SliceIter};
-use wasmparser::{FuncValidatorAllocations, Parser, ValidPayload, Validator};
+#[cfg(feature = "compiler")]
+use {
+    core::mem,
+    wasmparser::{Parser, ValidPayload, Validator},
+};
 
 /// A parsed and validated WebAssembly module.
 #[derive(Debug)]
@@ -50,6 +91,13 @@ pub struct Module {
     engine: Engine,
     header: ModuleHeader,
     data_segments: Box<[DataSegment]>,
+    names: Arc<ModuleNames>,
+    /// The [`CompileStats`] of the [`Module`] if recorded.
+    compile_stats: Option<CompileStats>,
+    /// The [`ArtifactHash`] of the [`Module`] if computed.
+    artifact_hash: Option<ArtifactHash>,
+    /// The static analysis of the internally defined functions of the [`Module`] if computed.
+    analysis: Option<Box<[FuncAnalysis]>>,
 }
 
 /// A parsed and validated WebAssembly module header.
@@ -72,7 +120,12 @@ struct ModuleHeaderInner {
     start: Option<FuncIdx>,
     compiled_funcs: Box<[CompiledFunc]>,
     compiled_funcs_idx: BTreeMap<CompiledFunc, FuncIdx>,
-    element_segments: Box<[ElementSegment]>,
+    intrinsics: BTreeSet<FuncIdx>,
+    element_segments: Arc<[ElementSegment]>,
+    /// The read-only Wasm binary shared with other processes if any.
+    ///
+    /// Lazily compiled function bodies reference this instead of copying their bytes.
+    shared_wasm: Option<&'static [u8]>,
 }
 
 impl ModuleHeader {
@@ -113,6 +166,20 @@ impl ModuleHeader {
         self.inner.compiled_funcs_idx.get(&func).copied()
     }
 
+    /// Returns the sub-slice of the shared Wasm binary that `bytes` refers to if any.
+    ///
+    /// Returns `None` if the Wasm binary is not shared or does not contain `bytes`.
+    pub fn get_shared_bytes(&self, bytes: &[u8]) -> Option<&'static [u8]> {
+        let shared = self.inner.shared_wasm?;
+        let start = (bytes.as_ptr() as usize).checked_sub(shared.as_ptr() as usize)?;
+        shared.get(start..start.checked_add(bytes.len())?)
+    }
+
+    /// Returns `true` if the [`FuncIdx`] refers to an imported function that matches an intrinsic.
+    pub fn is_intrinsic(&self, func_idx: FuncIdx) -> bool {
+        self.inner.intrinsics.contains(&func_idx)
+    }
+
     /// Returns the global variable type and optional initial value.
     pub fn get_global(&self, global_idx: GlobalIdx) -> (&GlobalType, Option<&ConstExpr>) {
         let index = global_idx.into_u32() as usize;
@@ -133,7 +200,7 @@ impl ModuleHeader {
 pub(crate) const DEFAULT_MEMORY_INDEX: u32 = 0;
 
 /// An imported item declaration in the [`Module`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Imported {
     /// The name of an imported [`Func`].
     ///
@@ -152,7 +219,7 @@ pub enum Imported {
 }
 
 /// The import names of the [`Module`] imports.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ModuleImports {
     /// All names and types of all imported items.
     items: Box<[Imported]>,
@@ -200,6 +267,7 @@ impl Module {
     /// - If Wasmi cannot translate the Wasm bytecode yielded by `stream`.
     ///
     /// [`Config`]: crate::Config
+    #[cfg(feature = "compiler")]
     pub fn new(engine: &Engine, stream: impl Read) -> Result<Self, Error> {
         parse(engine, stream).map_err(Into::into)
     }
@@ -226,15 +294,234 @@ impl Module {
     /// If the `stream` cannot be parsed as a valid Wasm module.
     ///
     /// [`Config`]: crate::Config
+    #[cfg(feature = "compiler")]
     pub unsafe fn new_unchecked(engine: &Engine, stream: impl Read) -> Result<Self, Error> {
         unsafe { parse_unchecked(engine, stream).map_err(Into::into) }
     }
 
+    /// Creates a new Wasm [`Module`] from the given byte stream using `transform`.
+    ///
+    /// # Note
+    ///
+    /// - This parses, validates and translates the Wasm bytecode yielded by `stream`.
+    /// - Every Wasm function body is handed to `transform` before its validation
+    ///   and translation which may observe or rewrite it.
+    ///
+    /// # Errors
+    ///
+    /// - If the `stream` cannot be parsed as a valid Wasm module.
+    /// - If the Wasm bytecode yielded by `stream` or returned by `transform` is not valid.
+    /// - If the Wasm bytecode yielded by `stream` violates restrictions
+    ///   set in the [`Config`] used by the `engine`.
+    /// - If Wasmi cannot translate the Wasm bytecode yielded by `stream`.
+    /// - If `transform` fails for any Wasm function body.
+    ///
+    /// [`Config`]: crate::Config
+    #[cfg(feature = "compiler")]
+    pub fn new_with_transform(
+        engine: &Engine,
+        stream: impl Read,
+        mut transform: impl FuncTransform,
+    ) -> Result<Self, Error> {
+        parse_with_transform(engine, stream, &mut transform)
+    }
+
+    /// Validates `wasm` and encodes it into a precompiled artifact for the `engine`.
+    ///
+    /// The returned artifact embeds the Wasmi version, the enabled Wasm features
+    /// of the `engine` and metadata about the current target. Use [`Module::deserialize`]
+    /// to create a [`Module`] from the artifact without validating it again.
+    ///
+    /// # Reproducibility
+    ///
+    /// Precompiling the same `wasm` with the same Wasmi version, [`Config`] and target
+    /// always yields byte-identical artifacts. This holds independently of the state
+    /// of the `engine`, e.g. previously compiled Wasm modules, and of the thread that
+    /// precompiles the `wasm`. Use [`ArtifactHash::of`] or [`Module::artifact_hash`]
+    /// to cache and verify precompiled artifacts.
+    ///
+    /// # Layout
+    ///
+    /// The Wasm payload of the artifact starts at a 4 KiB page aligned offset that is
+    /// recorded relative to the start of the artifact. The artifact contains no pointers
+    /// and no mutable state so that it can be mapped read-only and shared by multiple
+    /// processes via [`Module::deserialize_shared`].
+    ///
+    /// # Errors
+    ///
+    /// If Wasm validation for `wasm` fails for the [`Config`] of the `engine`.
+    ///
+    /// [`Config`]: crate::Config
+    #[cfg(feature = "compiler")]
+    pub fn precompile(engine: &Engine, wasm: &[u8]) -> Result<Vec<u8>, Error> {
+        Self::validate(engine, wasm)?;
+        Ok(encode_artifact(engine, wasm))
+    }
+
+    /// Creates a new Wasm [`Module`] from a precompiled `artifact`.
+    ///
+    /// # Note
+    ///
+    /// The `artifact` must have been produced by [`Module::precompile`] or [`Module::serialize`]
+    /// using the same Wasmi version, the same enabled Wasm features and the same target as the
+    /// `engine`. Since the `artifact` has been validated upon precompilation its function bodies
+    /// are not validated again.
+    ///
+    /// # Safety
+    ///
+    /// - The `artifact` must have been produced by [`Module::precompile`] or [`Module::serialize`].
+    /// - It is the caller's responsibility to make sure that the `artifact` has not
+    ///   been tampered with since Wasmi cannot fully validate its contents.
+    ///
+    /// # Errors
+    ///
+    /// - If the `artifact` is not a precompiled Wasmi artifact.
+    /// - If the `artifact` was produced by another Wasmi version, with other enabled
+    ///   Wasm features or for another target than the `engine`.
+    /// - If the Wasm payload of the `artifact` cannot be parsed or translated.
+    /// - If the compiled payload of the `artifact` was produced with other code
+    ///   generation settings than the `engine`, see [`Module::serialize`].
+    pub unsafe fn deserialize(engine: &Engine, artifact: &[u8]) -> Result<Self, Error> {
+        let payload = decode_artifact(engine, artifact)?;
+        if payload.starts_with(&CODE_MAGIC) {
+            return Self::deserialize_compiled(engine, artifact, payload);
+        }
+        #[cfg(not(feature = "compiler"))]
+        return Err(ArtifactError::RequiresCompiler.into());
+        #[cfg(feature = "compiler")]
+        unsafe {
+            Self::new_unchecked(engine, payload)
+        }
+    }
+
+    /// Creates a new Wasm [`Module`] from a precompiled `artifact` shared with other processes.
+    ///
+    /// # Note
+    ///
+    /// This is the same as [`Module::deserialize`] except that the function bodies of
+    /// the [`Module`] refer to the `artifact` instead of copying it. Use this with a lazy
+    /// [`CompilationMode`] and an `artifact` that is mapped read-only into memory, e.g.
+    /// via `mmap`, so that multiple processes keep a single physical copy of the function
+    /// bodies in the page cache. Functions are translated into process local Wasmi
+    /// bytecode upon their first use.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for [`Module::deserialize`] apply.
+    ///
+    /// # Errors
+    ///
+    /// The same errors as for [`Module::deserialize`] may occur.
+    ///
+    /// [`CompilationMode`]: crate::CompilationMode
+    pub unsafe fn deserialize_shared(
+        engine: &Engine,
+        artifact: &'static [u8],
+    ) -> Result<Self, Error> {
+        let payload = decode_artifact(engine, artifact)?;
+        if payload.starts_with(&CODE_MAGIC) {
+            return Self::deserialize_compiled(engine, artifact, payload);
+        }
+        #[cfg(not(feature = "compiler"))]
+        return Err(ArtifactError::RequiresCompiler.into());
+        #[cfg(feature = "compiler")]
+        unsafe {
+            parse_shared(engine, payload)
+        }
+    }
+
+    /// Creates a new Wasm [`Module`] from the compiled `payload` of a precompiled `artifact`.
+    ///
+    /// # Errors
+    ///
+    /// - If the `payload` is malformed.
+    /// - If the `payload` was compiled with other code generation settings than the `engine`.
+    fn deserialize_compiled(
+        engine: &Engine,
+        artifact: &[u8],
+        payload: &[u8],
+    ) -> Result<Self, Error> {
+        let mut module = deserialize_module(engine, payload)?;
+        if engine.config().get_artifact_hashes() {
+            module.artifact_hash = Some(ArtifactHash::of(artifact));
+        }
+        Ok(module)
+    }
+
+    /// Encodes the compiled [`Module`] into a precompiled artifact.
+    ///
+    /// Unlike [`Module::precompile`] the returned artifact contains the already translated
+    /// Wasmi bytecode of all functions of the [`Module`] instead of its Wasm bytecode.
+    /// Therefore [`Module::deserialize`] neither parses nor translates the artifact which
+    /// also allows to load it with Wasmi builds without the `compiler` crate feature.
+    ///
+    /// # Note
+    ///
+    /// - Functions of the [`Module`] that have not yet been compiled, e.g. due to lazy
+    ///   compilation, are compiled by this call.
+    /// - The artifact can only be loaded by an [`Engine`] with the same code generation
+    ///   settings, i.e. fuel metering, epoch interruption and denied operators, as the
+    ///   [`Engine`] of the [`Module`].
+    ///
+    /// # Errors
+    ///
+    /// If a function of the [`Module`] fails to compile.
+    pub fn serialize(&self) -> Result<Vec<u8>, Error> {
+        let payload = serialize_module(self)?;
+        Ok(encode_artifact(&self.engine, &payload))
+    }
+
     /// Returns the [`Engine`] used during creation of the [`Module`].
     pub fn engine(&self) -> &Engine {
         &self.engine
     }
 
+    /// Returns the [`CompileStats`] of the [`Module`].
+    ///
+    /// Returns `None` if [`Config::compile_stats`] was disabled when the [`Module`] was
+    /// compiled or if the [`Module`] was not compiled from a Wasm binary, e.g. via [`Module::preinitialize`].
+    ///
+    /// [`Config::compile_stats`]: crate::Config::compile_stats
+    pub fn compile_stats(&self) -> Option<CompileStats> {
+        self.compile_stats
+    }
+
+    /// Returns the [`ModuleAnalysis`] of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// The value stack usage of functions that have not yet been translated
+    /// due to lazy compilation is `None`.
+    ///
+    /// Returns `None` if [`Config::module_analysis`] was disabled when the [`Module`] was
+    /// compiled or if the [`Module`] was not compiled from a Wasm binary, e.g. via [`Module::preinitialize`].
+    ///
+    /// [`Config::module_analysis`]: crate::Config::module_analysis
+    pub fn analysis(&self) -> Option<ModuleAnalysis> {
+        let funcs = self.analysis.as_deref()?;
+        let funcs = funcs
+            .iter()
+            .zip(self.header.inner.compiled_funcs.iter())
+            .map(|(analysis, func)| analysis.with_stack_cells(self.engine.len_registers(*func)))
+            .collect();
+        Some(ModuleAnalysis::new(funcs))
+    }
+
+    /// Returns the [`ArtifactHash`] of the [`Module`].
+    ///
+    /// This is the hash of the artifact that [`Module::precompile`] yields for the
+    /// Wasm binary of the [`Module`] and its [`Engine`]. For a [`Module`] created
+    /// via [`Module::deserialize`] this is the hash of the deserialized artifact.
+    ///
+    /// Returns `None` if [`Config::artifact_hashes`] was disabled when the [`Module`] was
+    /// compiled or if the [`Module`] was not compiled from an unmodified Wasm binary,
+    /// e.g. via [`Module::new_with_transform`] or [`Module::preinitialize`].
+    ///
+    /// [`Config::artifact_hashes`]: crate::Config::artifact_hashes
+    pub fn artifact_hash(&self) -> Option<ArtifactHash> {
+        self.artifact_hash
+    }
+
     /// Validates `wasm` as a WebAssembly binary given the configuration (via [`Config`]) in `engine`.
     ///
     /// This function performs Wasm validation of the binary input WebAssembly module and
@@ -255,16 +542,19 @@ impl Module {
     /// If Wasm validation for `wasm` fails for the given [`Config`] provided via `engine`.
     ///
     /// [`Config`]: crate::Config
+    #[cfg(feature = "compiler")]
     pub fn validate(engine: &Engine, wasm: &[u8]) -> Result<(), Error> {
         let mut validator = Validator::new_with_features(engine.config().wasm_features());
+        let mut allocs = engine.get_validation_allocs();
         for payload in Parser::new(0).parse_all(wasm) {
             let payload = payload?;
             if let ValidPayload::Func(func_to_validate, func_body) = validator.payload(&payload)? {
-                func_to_validate
-                    .into_validator(FuncValidatorAllocations::default())
-                    .validate(&func_body)?;
+                let mut func_validator = func_to_validate.into_validator(mem::take(&mut allocs));
+                func_validator.validate(&func_body)?;
+                allocs = func_validator.into_allocations();
             }
         }
+        engine.recycle_validation_allocs(allocs);
         Ok(())
     }
 
@@ -294,6 +584,33 @@ impl Module {
         self.header.inner.func_types.clone()
     }
 
+    /// Returns the name of the [`Module`] if any.
+    ///
+    /// # Note
+    ///
+    /// The module name is taken from the `name` custom section of the Wasm binary.
+    pub fn name(&self) -> Option<&str> {
+        self.names.module()
+    }
+
+    /// Returns the name of the function at `func_index` if any.
+    ///
+    /// # Note
+    ///
+    /// Function names are taken from the `name` custom section of the Wasm binary.
+    pub fn func_name(&self, func_index: u32) -> Option<&str> {
+        self.names.func(func_index)
+    }
+
+    /// Returns the [`ModuleNames`] of the `name` custom section.
+    ///
+    /// # Note
+    ///
+    /// The names are stored in a `Arc` so that this operation is very cheap.
+    pub(crate) fn names_cloned(&self) -> Arc<ModuleNames> {
+        self.names.clone()
+    }
+
     /// Returns an iterator over the imports of the [`Module`].
     pub fn imports(&self) -> ModuleImportsIter {
         let len_imported_funcs = self.header.inner.imports.len_funcs;
@@ -308,6 +625,34 @@ impl Module {
         }
     }
 
+    /// Returns the sizes of the translated Wasmi bytecode of all internally defined functions.
+    ///
+    /// # Note
+    ///
+    /// This allows to identify the functions that contribute most to the size of
+    /// the translated [`Module`], e.g. when fitting it into constrained memory.
+    /// Sizes of functions that have not yet been translated due to lazy
+    /// compilation are `None`.
+    pub fn func_code_sizes(&self) -> Vec<FuncCodeSize> {
+        let len_imported = self.header.inner.imports.len_funcs as u32;
+        self.header
+            .inner
+            .compiled_funcs
+            .iter()
+            .zip(len_imported..)
+            .map(|(func, func_index)| FuncCodeSize::new(func_index, self.engine.code_size(*func)))
+            .collect()
+    }
+
+    /// Returns the [`CompiledFunc`] of the internally defined function at `func_index`.
+    ///
+    /// Returns `None` if `func_index` refers to an imported or non-existing function.
+    pub(crate) fn compiled_func(&self, func_index: u32) -> Option<CompiledFunc> {
+        let len_imported = self.header.inner.imports.len_funcs;
+        let index = (func_index as usize).checked_sub(len_imported)?;
+        self.header.inner.compiled_funcs.get(index).copied()
+    }
+
     /// Returns an iterator over the internally defined [`Func`].
     ///
     /// [`Func`]: [`crate::Func`]
@@ -0,0 +1,73 @@
+use super::{ArtifactError, Decode, Decoder, Encode, Encoder};
+use alloc::{boxed::Box, collections::BTreeMap};
+#[cfg(feature = "compiler")]
+use wasmparser::{CustomSectionReader, Name, NameSectionReader};
+
+/// The names of a Wasm module and its functions from the `name` custom section.
+#[derive(Debug, Default)]
+pub struct ModuleNames {
+    /// The name of the Wasm module if any.
+    module: Option<Box<str>>,
+    /// The names of the Wasm functions by function index.
+    funcs: BTreeMap<u32, Box<str>>,
+}
+
+impl ModuleNames {
+    /// Parses the [`ModuleNames`] from the `name` custom section `reader`.
+    ///
+    /// # Note
+    ///
+    /// Malformed subsections are ignored as mandated by the Wasm specification.
+    #[cfg(feature = "compiler")]
+    pub fn parse(reader: &CustomSectionReader) -> Self {
+        let mut names = Self::default();
+        for subsection in NameSectionReader::new(reader.data(), reader.data_offset()) {
+            match subsection {
+                Ok(Name::Module { name, .. }) => {
+                    names.module = Some(name.into());
+                }
+                Ok(Name::Function(naming)) => {
+                    let funcs = naming
+                        .into_iter()
+                        .map(|naming| naming.map(|naming| (naming.index, naming.name.into())))
+                        .collect::<Result<BTreeMap<_, _>, _>>();
+                    if let Ok(funcs) = funcs {
+                        names.funcs = funcs;
+                    }
+                }
+                _ => {}
+            }
+        }
+        names
+    }
+
+    /// Returns the name of the Wasm module if any.
+    pub fn module(&self) -> Option<&str> {
+        self.module.as_deref()
+    }
+
+    /// Returns the name of the Wasm function at `func_index` if any.
+    pub fn func(&self, func_index: u32) -> Option<&str> {
+        self.funcs.get(&func_index).map(|name| &name[..])
+    }
+}
+
+impl Encode for ModuleNames {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&self.module);
+        encoder.encode_len(self.funcs.len());
+        for (index, name) in &self.funcs {
+            encoder.encode(index);
+            encoder.encode(name);
+        }
+    }
+}
+
+impl Decode for ModuleNames {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let module = decoder.decode()?;
+        let funcs = decoder.decode_all::<(u32, Box<str>)>()?;
+        let funcs = funcs.into_iter().collect::<BTreeMap<_, _>>();
+        Ok(Self { module, funcs })
+    }
+}
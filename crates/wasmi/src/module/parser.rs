@@ -1,21 +1,28 @@
 use super::{
+    analysis::ModuleAnalyzer,
+    artifact::ArtifactHasher,
     builder::ModuleHeaderBuilder,
+    compile_stats::{CompilePhase, CompileTimer},
     export::ExternIdx,
     global::Global,
     import::{FuncTypeIdx, Import},
     DataSegment,
     ElementSegment,
+    FuncBody,
     FuncIdx,
+    FuncTransform,
     Module,
     ModuleBuilder,
     ModuleHeader,
+    ModuleNames,
     Read,
 };
 use crate::{engine::CompiledFunc, Engine, Error, FuncType, MemoryType, TableType};
 use alloc::{boxed::Box, vec::Vec};
-use core::ops::Range;
+use core::{mem, ops::Range};
 use wasmparser::{
     Chunk,
+    CustomSectionReader,
     DataSectionReader,
     ElementSectionReader,
     Encoding,
@@ -56,6 +63,42 @@ pub unsafe fn parse_unchecked(engine: &Engine, stream: impl Read) -> Result<Modu
     unsafe { ModuleParser::new(engine).parse_unchecked(stream) }
 }
 
+/// Parse and translate the shared Wasm bytecode `wasm` into Wasm IR bytecode.
+///
+/// - Returns the fully compiled Wasm [`Module`] upon success.
+/// - Uses the given [`Engine`] as the translation target of the process.
+/// - Lazily compiled function bodies reference `wasm` instead of copying it.
+///
+/// # Errors
+///
+/// If the Wasm bytecode stream fails to parse or translate.
+pub unsafe fn parse_shared(engine: &Engine, wasm: &'static [u8]) -> Result<Module, Error> {
+    let mut parser = ModuleParser::new(engine);
+    parser.shared = Some(wasm);
+    parser.parse_impl(ValidationMode::HeaderOnly, None, wasm)
+}
+
+/// Parse, validate and translate the Wasm bytecode stream into Wasm IR bytecode.
+///
+/// - Returns the fully compiled and validated Wasm [`Module`] upon success.
+/// - Uses the given [`Engine`] as the translation target of the process.
+/// - Applies `transform` to all Wasm function bodies before their validation and translation.
+///
+/// # Errors
+///
+/// - If the Wasm bytecode stream fails to parse, validate or translate.
+/// - If `transform` fails for any Wasm function body.
+pub fn parse_with_transform(
+    engine: &Engine,
+    stream: impl Read,
+    transform: &mut dyn FuncTransform,
+) -> Result<Module, Error> {
+    let mut parser = ModuleParser::new(engine);
+    // The transformed Wasm module cannot be precompiled into an artifact.
+    parser.hasher = None;
+    parser.parse_impl(ValidationMode::All, Some(transform), stream)
+}
+
 /// Context used to construct a WebAssembly module from a stream of bytes.
 pub struct ModuleParser {
     /// The engine used for translation.
@@ -68,6 +111,18 @@ pub struct ModuleParser {
     compiled_funcs: u32,
     /// Flag, `true` when `stream` is at the end.
     eof: bool,
+    /// The names of the `name` custom section if any.
+    names: ModuleNames,
+    /// Measures the time spent in the phases of the compilation.
+    timer: CompileTimer,
+    /// Computes the [`ArtifactHash`] of the Wasm module if enabled.
+    ///
+    /// [`ArtifactHash`]: super::ArtifactHash
+    hasher: Option<ArtifactHasher>,
+    /// Gathers the static analysis of the Wasm module if enabled.
+    analyzer: Option<ModuleAnalyzer>,
+    /// The read-only Wasm binary that is parsed if it is shared with other processes.
+    shared: Option<&'static [u8]>,
 }
 
 /// The mode of Wasm validation when parsing a Wasm module.
@@ -90,6 +145,17 @@ impl ModuleParser {
             parser,
             compiled_funcs: 0,
             eof: false,
+            names: ModuleNames::default(),
+            timer: CompileTimer::new(engine.config().get_compile_stats()),
+            hasher: engine
+                .config()
+                .get_artifact_hashes()
+                .then(|| ArtifactHasher::new(engine)),
+            analyzer: engine
+                .config()
+                .get_module_analysis()
+                .then(ModuleAnalyzer::default),
+            shared: None,
         }
     }
 
@@ -101,7 +167,7 @@ impl ModuleParser {
     ///
     /// If the Wasm bytecode stream fails to validate.
     pub fn parse(self, stream: impl Read) -> Result<Module, Error> {
-        self.parse_impl(ValidationMode::All, stream)
+        self.parse_impl(ValidationMode::All, None, stream)
     }
 
     /// Starts parsing and validating the Wasm bytecode stream.
@@ -112,7 +178,7 @@ impl ModuleParser {
     ///
     /// If the Wasm bytecode stream fails to validate.
     pub unsafe fn parse_unchecked(self, stream: impl Read) -> Result<Module, Error> {
-        self.parse_impl(ValidationMode::HeaderOnly, stream)
+        self.parse_impl(ValidationMode::HeaderOnly, None, stream)
     }
 
     /// Starts parsing and validating the Wasm bytecode stream.
@@ -125,13 +191,24 @@ impl ModuleParser {
     fn parse_impl(
         mut self,
         validation_mode: ValidationMode,
+        transform: Option<&mut dyn FuncTransform>,
         mut stream: impl Read,
     ) -> Result<Module, Error> {
         let mut buffer = Vec::new();
         let header = Self::parse_header(&mut self, &mut stream, &mut buffer)?;
-        let builder =
-            Self::parse_code(&mut self, validation_mode, &mut stream, &mut buffer, header)?;
-        let module = Self::parse_data(&mut self, &mut stream, &mut buffer, builder)?;
+        let builder = Self::parse_code(
+            &mut self,
+            validation_mode,
+            transform,
+            &mut stream,
+            &mut buffer,
+            header,
+        )?;
+        let mut module = Self::parse_data(&mut self, &mut stream, &mut buffer, builder)?;
+        module.compile_stats = self.timer.finish();
+        module.artifact_hash = self.hasher.map(ArtifactHasher::finish);
+        let len_imported = module.header.inner.imports.len_funcs as u32;
+        module.analysis = self.analyzer.map(|analyzer| analyzer.finish(len_imported));
         Ok(module)
     }
 
@@ -151,10 +228,11 @@ impl ModuleParser {
         buffer: &mut Vec<u8>,
     ) -> Result<ModuleHeader, Error> {
         let mut header = ModuleHeaderBuilder::new(&self.engine);
+        header.shared_wasm = self.shared;
         loop {
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
-                    self.eof = Self::pull_bytes(buffer, hint, stream)?;
+                    self.eof = self.pull_bytes(buffer, hint, stream)?;
                     if self.eof {
                         break;
                     }
@@ -201,7 +279,10 @@ impl ModuleParser {
                         }
                         Payload::DataSection(_) => break,
                         Payload::End(_) => break,
-                        Payload::CustomSection { .. } => Ok(()),
+                        Payload::CustomSection(reader) => {
+                            self.process_custom(reader);
+                            Ok(())
+                        }
                         Payload::UnknownSection { id, range, .. } => {
                             self.process_unknown(id, range)
                         }
@@ -229,6 +310,7 @@ impl ModuleParser {
     fn parse_code(
         &mut self,
         validation_mode: ValidationMode,
+        mut transform: Option<&mut dyn FuncTransform>,
         stream: &mut impl Read,
         buffer: &mut Vec<u8>,
         header: ModuleHeader,
@@ -236,7 +318,7 @@ impl ModuleParser {
         loop {
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
-                    self.eof = Self::pull_bytes(buffer, hint, stream)?;
+                    self.eof = self.pull_bytes(buffer, hint, stream)?;
                 }
                 Chunk::Parsed { consumed, payload } => {
                     match payload {
@@ -247,10 +329,21 @@ impl ModuleParser {
                             //       such an API becomes available.
                             let remaining = func_body.get_binary_reader().bytes_remaining();
                             let start = consumed - remaining;
-                            let bytes = &buffer[start..consumed];
-                            self.process_code_entry(func_body, validation_mode, bytes, &header)?;
+                            let bytes = match self.shared {
+                                // Refer to the shared Wasm binary so that lazily compiled
+                                // function bodies do not need to copy their bytes.
+                                Some(wasm) => &wasm[func_body.range()],
+                                None => &buffer[start..consumed],
+                            };
+                            self.process_code_entry(
+                                func_body,
+                                validation_mode,
+                                transform.as_deref_mut(),
+                                bytes,
+                                &header,
+                            )?;
                         }
-                        Payload::CustomSection { .. } => {}
+                        Payload::CustomSection(reader) => self.process_custom(reader),
                         Payload::UnknownSection { id, range, .. } => {
                             self.process_unknown(id, range)?
                         }
@@ -273,7 +366,7 @@ impl ModuleParser {
         loop {
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
-                    self.eof = Self::pull_bytes(buffer, hint, stream)?;
+                    self.eof = self.pull_bytes(buffer, hint, stream)?;
                 }
                 Chunk::Parsed { consumed, payload } => {
                     match payload {
@@ -285,7 +378,7 @@ impl ModuleParser {
                             buffer.drain(..consumed);
                             break;
                         }
-                        Payload::CustomSection { .. } => {}
+                        Payload::CustomSection(reader) => self.process_custom(reader),
                         Payload::UnknownSection { id, range, .. } => {
                             self.process_unknown(id, range)?
                         }
@@ -298,6 +391,7 @@ impl ModuleParser {
                 }
             }
         }
+        builder.set_names(mem::take(&mut self.names));
         Ok(builder.finish(&self.engine))
     }
 
@@ -308,7 +402,12 @@ impl ModuleParser {
     /// # Note
     ///
     /// Uses `hint` to efficiently preallocate enough space for the next payload.
-    fn pull_bytes(buffer: &mut Vec<u8>, hint: u64, stream: &mut impl Read) -> Result<bool, Error> {
+    fn pull_bytes(
+        &mut self,
+        buffer: &mut Vec<u8>,
+        hint: u64,
+        stream: &mut impl Read,
+    ) -> Result<bool, Error> {
         // Use the hint to preallocate more space, then read
         // some more data into the buffer.
         //
@@ -318,13 +417,33 @@ impl ModuleParser {
         buffer.extend((0..hint).map(|_| 0u8));
         let read_bytes = stream.read(&mut buffer[len..])?;
         buffer.truncate(len + read_bytes);
+        if let Some(hasher) = &mut self.hasher {
+            hasher.update(&buffer[len..]);
+        }
         let reached_end = read_bytes == 0;
         Ok(reached_end)
     }
 
+    /// Runs `f` on the Wasm module validator and accounts its time as validation time.
+    fn validate<R>(&mut self, f: impl FnOnce(&mut Validator) -> R) -> R {
+        self.timer
+            .measure(CompilePhase::Validation, || f(&mut self.validator))
+    }
+
+    /// Processes a Wasm custom section.
+    ///
+    /// # Note
+    ///
+    /// This extracts the module and function names of the `name` custom section.
+    fn process_custom(&mut self, reader: CustomSectionReader) {
+        if reader.name() == "name" {
+            self.names = ModuleNames::parse(&reader);
+        }
+    }
+
     /// Processes the end of the Wasm binary.
     fn process_end(&mut self, offset: usize) -> Result<(), Error> {
-        self.validator.end(offset)?;
+        self.validate(|validator| validator.end(offset))?;
         Ok(())
     }
 
@@ -335,8 +454,7 @@ impl ModuleParser {
         encoding: Encoding,
         range: Range<usize>,
     ) -> Result<(), Error> {
-        self.validator
-            .version(num, encoding, &range)
+        self.validate(|validator| validator.version(num, encoding, &range))
             .map_err(Into::into)
     }
 
@@ -354,7 +472,7 @@ impl ModuleParser {
         section: TypeSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
-        self.validator.type_section(&section)?;
+        self.validate(|validator| validator.type_section(&section))?;
         let func_types = section.into_iter().map(|result| match result? {
             wasmparser::Type::Func(ty) => Ok(FuncType::from_wasmparser(ty)),
         });
@@ -377,7 +495,7 @@ impl ModuleParser {
         section: ImportSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
-        self.validator.import_section(&section)?;
+        self.validate(|validator| validator.import_section(&section))?;
         let imports = section
             .into_iter()
             .map(|import| import.map(Import::from).map_err(Error::from));
@@ -395,8 +513,7 @@ impl ModuleParser {
         &mut self,
         section: wasmparser::InstanceSectionReader,
     ) -> Result<(), Error> {
-        self.validator
-            .instance_section(&section)
+        self.validate(|validator| validator.instance_section(&section))
             .map_err(Into::into)
     }
 
@@ -414,7 +531,7 @@ impl ModuleParser {
         section: FunctionSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
-        self.validator.function_section(&section)?;
+        self.validate(|validator| validator.function_section(&section))?;
         let funcs = section
             .into_iter()
             .map(|func| func.map(FuncTypeIdx::from).map_err(Error::from));
@@ -436,7 +553,7 @@ impl ModuleParser {
         section: TableSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
-        self.validator.table_section(&section)?;
+        self.validate(|validator| validator.table_section(&section))?;
         let tables = section
             .into_iter()
             .map(|table| table.map(TableType::from_wasmparser).map_err(Error::from));
@@ -458,7 +575,7 @@ impl ModuleParser {
         section: MemorySectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
-        self.validator.memory_section(&section)?;
+        self.validate(|validator| validator.memory_section(&section))?;
         let memories = section
             .into_iter()
             .map(|memory| memory.map(MemoryType::from_wasmparser).map_err(Error::from));
@@ -473,7 +590,8 @@ impl ModuleParser {
     /// This is part of the module linking Wasm proposal and not yet supported
     /// by Wasmi.
     fn process_tags(&mut self, section: wasmparser::TagSectionReader) -> Result<(), Error> {
-        self.validator.tag_section(&section).map_err(Into::into)
+        self.validate(|validator| validator.tag_section(&section))
+            .map_err(Into::into)
     }
 
     /// Process module global variable declarations.
@@ -490,7 +608,7 @@ impl ModuleParser {
         section: GlobalSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
-        self.validator.global_section(&section)?;
+        self.validate(|validator| validator.global_section(&section))?;
         let globals = section
             .into_iter()
             .map(|global| global.map(Global::from).map_err(Error::from));
@@ -512,7 +630,7 @@ impl ModuleParser {
         section: ExportSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
-        self.validator.export_section(&section)?;
+        self.validate(|validator| validator.export_section(&section))?;
         let exports = section.into_iter().map(|export| {
             let export = export?;
             let field: Box<str> = export.name.into();
@@ -538,7 +656,7 @@ impl ModuleParser {
         range: Range<usize>,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
-        self.validator.start_section(func, &range)?;
+        self.validate(|validator| validator.start_section(func, &range))?;
         header.set_start(FuncIdx::from(func));
         Ok(())
     }
@@ -557,7 +675,7 @@ impl ModuleParser {
         section: ElementSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
-        self.validator.element_section(&section)?;
+        self.validate(|validator| validator.element_section(&section))?;
         let segments = section
             .into_iter()
             .map(|segment| segment.map(ElementSegment::from).map_err(Error::from));
@@ -572,8 +690,7 @@ impl ModuleParser {
     /// This is part of the bulk memory operations Wasm proposal and not yet supported
     /// by Wasmi.
     fn process_data_count(&mut self, count: u32, range: Range<usize>) -> Result<(), Error> {
-        self.validator
-            .data_count_section(count, &range)
+        self.validate(|validator| validator.data_count_section(count, &range))
             .map_err(Into::into)
     }
 
@@ -591,7 +708,7 @@ impl ModuleParser {
         section: DataSectionReader,
         builder: &mut ModuleBuilder,
     ) -> Result<(), Error> {
-        self.validator.data_section(&section)?;
+        self.validate(|validator| validator.data_section(&section))?;
         let segments = section
             .into_iter()
             .map(|segment| segment.map(DataSegment::from).map_err(Error::from));
@@ -611,7 +728,7 @@ impl ModuleParser {
     ///
     /// If the code start section fails to validate.
     fn process_code_start(&mut self, count: u32, range: Range<usize>) -> Result<(), Error> {
-        self.validator.code_section_start(count, &range)?;
+        self.validate(|validator| validator.code_section_start(count, &range))?;
         Ok(())
     }
 
@@ -638,23 +755,42 @@ impl ModuleParser {
     ///
     /// # Errors
     ///
-    /// If the function body fails to validate.
+    /// - If the function body fails to validate.
+    /// - If `transform` fails for the function body.
     fn process_code_entry(
         &mut self,
         func_body: FunctionBody,
         validation_mode: ValidationMode,
+        transform: Option<&mut (dyn FuncTransform + '_)>,
         bytes: &[u8],
         header: &ModuleHeader,
     ) -> Result<(), Error> {
         let (func, compiled_func) = self.next_func(header);
         let module = header.clone();
         let offset = func_body.get_binary_reader().original_position();
+        let transformed = match transform {
+            Some(transform) => {
+                transform.transform(FuncBody::new(func.into_u32(), offset, bytes))?
+            }
+            None => None,
+        };
+        let (func_body, bytes) = match &transformed {
+            Some(transformed) => (FunctionBody::new(offset, transformed), &transformed[..]),
+            None => (func_body, bytes),
+        };
         let func_to_validate = match validation_mode {
-            ValidationMode::All => Some(self.validator.code_section_entry(&func_body)?),
+            ValidationMode::All => {
+                Some(self.validate(|validator| validator.code_section_entry(&func_body))?)
+            }
             ValidationMode::HeaderOnly => None,
         };
-        self.engine
-            .translate_func(func, compiled_func, offset, bytes, module, func_to_validate)?;
+        if let Some(analyzer) = &mut self.analyzer {
+            analyzer.push_func(offset, bytes);
+        }
+        self.timer.measure(CompilePhase::Translation, || {
+            self.engine
+                .translate_func(func, compiled_func, offset, bytes, module, func_to_validate)
+        })?;
         Ok(())
     }
 
@@ -664,8 +800,7 @@ impl ModuleParser {
     ///
     /// This generally will be treated as an error for now.
     fn process_unknown(&mut self, id: u8, range: Range<usize>) -> Result<(), Error> {
-        self.validator
-            .unknown_section(id, &range)
+        self.validate(|validator| validator.unknown_section(id, &range))
             .map_err(Into::into)
     }
 }
@@ -0,0 +1,222 @@
+use super::{
+    ConstExpr,
+    DataSegment,
+    DataSegmentKind,
+    MemoryIdx,
+    Module,
+    ModuleHeader,
+    ModuleHeaderInner,
+};
+use crate::{
+    AsContext,
+    AsContextMut,
+    Error,
+    ExternRef,
+    Func,
+    FuncRef,
+    Instance,
+    Linker,
+    MemoryType,
+    Value,
+};
+use alloc::{sync::Arc, vec::Vec};
+use core::{fmt, fmt::Display};
+
+/// An error that may occur upon pre-initializing a [`Module`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PreinitError {
+    /// The value of an internal global variable cannot be snapshotted.
+    ///
+    /// This is the case for non-null `externref` values and `funcref`
+    /// values that do not refer to functions of the pre-initialized instance.
+    UnsupportedGlobalValue {
+        /// The index of the global variable within the [`Module`].
+        global_index: u32,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PreinitError {}
+
+impl Display for PreinitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedGlobalValue { global_index } => write!(
+                f,
+                "cannot snapshot the value of global variable {global_index} upon pre-initialization",
+            ),
+        }
+    }
+}
+
+/// Contiguous runs of zero bytes shorter than this are kept within snapshotted data segments.
+///
+/// # Note
+///
+/// This avoids splitting the snapshot of a linear memory into too many tiny data segments.
+const MIN_ZERO_GAP: usize = 64;
+
+impl Module {
+    /// Pre-initializes the [`Module`] by running its `entry` export and snapshotting the result.
+    ///
+    /// This instantiates the [`Module`] using `linker`, calls the exported `entry`
+    /// function of type `[] -> []` and returns a new [`Module`] whose instances start
+    /// in the state of the pre-initialized instance, similar to what Wizer does.
+    ///
+    /// # Note
+    ///
+    /// - The contents and sizes of all internal linear memories and the values of all
+    ///   internal global variables are snapshotted into the returned [`Module`].
+    /// - The returned [`Module`] has no start function since it already ran.
+    /// - Imported entities and tables are not snapshotted and passive data and element
+    ///   segments of the returned [`Module`] are not dropped even if the `entry` dropped them.
+    /// - The returned [`Module`] shares its compiled functions with this [`Module`].
+    ///
+    /// # Errors
+    ///
+    /// - If the instantiation of the [`Module`] fails or its start function traps.
+    /// - If the [`Module`] has no exported `entry` function of type `[] -> []`.
+    /// - If the call to `entry` fails.
+    /// - If the value of an internal global variable cannot be snapshotted.
+    pub fn preinitialize<T>(
+        &self,
+        mut store: impl AsContextMut<UserState = T>,
+        linker: &Linker<T>,
+        entry: &str,
+    ) -> Result<Module, Error> {
+        let instance = linker.instantiate(&mut store, self)?.start(&mut store)?;
+        instance
+            .get_typed_func::<(), ()>(&store, entry)?
+            .call(&mut store, ())?;
+        self.snapshot(&store, &instance)
+    }
+
+    /// Returns a new [`Module`] that starts in the state of `instance` of this [`Module`].
+    fn snapshot(&self, store: impl AsContext, instance: &Instance) -> Result<Module, Error> {
+        let store = store.as_context();
+        let entity = store.store.inner.resolve_instance(instance);
+        let header = &self.header.inner;
+        let len_imported_memories = header.imports.len_memories;
+        let len_imported_globals = header.imports.len_globals;
+        let mut memories = header.memories.to_vec();
+        let mut data_segments = self
+            .data_segments
+            .iter()
+            .map(|segment| match segment.kind() {
+                DataSegmentKind::Passive => DataSegment::passive(segment.clone_bytes()),
+                // Active data segments are dropped after instantiation and therefore
+                // can be replaced with empty passive data segments without changing
+                // the indices of the remaining data segments.
+                DataSegmentKind::Active(_) => DataSegment::passive(Arc::from([])),
+            })
+            .collect::<Vec<_>>();
+        for (index, ty) in memories.iter_mut().enumerate().skip(len_imported_memories) {
+            let index = index as u32;
+            let memory = entity
+                .get_memory(index)
+                .unwrap_or_else(|| panic!("missing linear memory at index {index}"));
+            let current = memory.current_pages(&store);
+            *ty = MemoryType::new(u32::from(current), ty.maximum_pages().map(u32::from))?;
+            let bytes = memory.data(&store);
+            for (start, end) in nonzero_runs(bytes) {
+                data_segments.push(DataSegment::active(
+                    MemoryIdx::from(index),
+                    start as u32,
+                    Arc::from(&bytes[start..end]),
+                ));
+            }
+        }
+        let globals_init = header
+            .globals
+            .iter()
+            .enumerate()
+            .skip(len_imported_globals)
+            .map(|(index, _)| {
+                let index = index as u32;
+                let global = entity
+                    .get_global(index)
+                    .unwrap_or_else(|| panic!("missing global variable at index {index}"));
+                let value = global.get(&store);
+                let func_index = match &value {
+                    Value::FuncRef(funcref) => funcref.func().and_then(|func| {
+                        (0..header.funcs.len() as u32).find(|index| {
+                            entity.get_func(*index).is_some_and(|f| Func::eq(&f, func))
+                        })
+                    }),
+                    _ => None,
+                };
+                snapshot_value(func_index, value).ok_or(PreinitError::UnsupportedGlobalValue {
+                    global_index: index,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let header = ModuleHeader {
+            inner: Arc::new(ModuleHeaderInner {
+                engine: header.engine.clone(),
+                func_types: header.func_types.clone(),
+                imports: header.imports.clone(),
+                funcs: header.funcs.clone(),
+                tables: header.tables.clone(),
+                memories: memories.into(),
+                globals: header.globals.clone(),
+                globals_init: globals_init.into(),
+                exports: header.exports.clone(),
+                start: None,
+                compiled_funcs: header.compiled_funcs.clone(),
+                compiled_funcs_idx: header.compiled_funcs_idx.clone(),
+                intrinsics: header.intrinsics.clone(),
+                element_segments: header.element_segments.clone(),
+                shared_wasm: header.shared_wasm,
+            }),
+        };
+        Ok(Module {
+            engine: self.engine.clone(),
+            header,
+            data_segments: data_segments.into(),
+            names: self.names.clone(),
+            compile_stats: None,
+            artifact_hash: None,
+            analysis: None,
+        })
+    }
+}
+
+/// Returns a [`ConstExpr`] evaluating to `value` if possible.
+///
+/// The `func_index` is the index of the function referenced by `value` if any.
+fn snapshot_value(func_index: Option<u32>, value: Value) -> Option<ConstExpr> {
+    let value = match value {
+        Value::FuncRef(funcref) if !funcref.is_null() => {
+            return func_index.map(ConstExpr::new_funcref);
+        }
+        Value::FuncRef(_) => Value::from(FuncRef::null()),
+        Value::ExternRef(externref) if !externref.is_null() => return None,
+        Value::ExternRef(_) => Value::from(ExternRef::null()),
+        value => value,
+    };
+    Some(ConstExpr::new_const(value))
+}
+
+/// Returns the `(start, end)` byte ranges of `bytes` that are not just zeros.
+///
+/// Ranges are only split at runs of at least [`MIN_ZERO_GAP`] zero bytes.
+fn nonzero_runs(bytes: &[u8]) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut current: Option<(usize, usize)> = None;
+    for (index, byte) in bytes.iter().enumerate() {
+        if *byte == 0 {
+            continue;
+        }
+        current = match current {
+            Some((start, end)) if index - end < MIN_ZERO_GAP => Some((start, index + 1)),
+            Some(run) => {
+                runs.push(run);
+                Some((index, index + 1))
+            }
+            None => Some((index, index + 1)),
+        };
+    }
+    runs.extend(current);
+    runs
+}
@@ -0,0 +1,764 @@
+//! Serialization of compiled Wasm modules into precompiled artifacts.
+//!
+//! # Note
+//!
+//! A compiled payload contains the already translated Wasmi bytecode of all
+//! functions of a [`Module`] so that it can be loaded without the Wasm parser,
+//! validator and translator. This is what allows builds of Wasmi without the `compiler` crate feature.
+
+use super::{
+    builder::{ModuleBuilder, ModuleHeaderBuilder},
+    export::{ExternIdx, MemoryIdx, TableIdx},
+    ArtifactError,
+    ConstExpr,
+    DataSegment,
+    ElementSegment,
+    ExternTypeIdx,
+    FuncIdx,
+    FuncTypeIdx,
+    GlobalIdx,
+    Import,
+    Imported,
+    Module,
+    ModuleHeader,
+    ModuleNames,
+};
+use crate::{
+    core::{TrapCode, UntypedValue, ValueType},
+    engine::{CompiledFunc, CompiledFuncEntity},
+    Config,
+    Engine,
+    Error,
+    FuncType,
+    GlobalType,
+    MemoryType,
+    Mutability,
+    TableType,
+};
+use alloc::{boxed::Box, vec::Vec};
+
+/// The magic bytes that start the compiled payload of a precompiled Wasmi artifact.
+///
+/// # Note
+///
+/// This distinguishes compiled payloads produced by [`Module::serialize`] from
+/// the Wasm payloads produced by [`Module::precompile`] that start with `\0asm`.
+pub const CODE_MAGIC: [u8; 4] = *b"\0wbc";
+
+/// All [`TrapCode`]s that may be encoded into Wasmi bytecode in the order of their encoding.
+const TRAP_CODES: &[TrapCode] = &[
+    TrapCode::UnreachableCodeReached,
+    TrapCode::MemoryOutOfBounds,
+    TrapCode::TableOutOfBounds,
+    TrapCode::IndirectCallToNull,
+    TrapCode::IntegerDivisionByZero,
+    TrapCode::IntegerOverflow,
+    TrapCode::BadConversionToInteger,
+    TrapCode::StackOverflow,
+    TrapCode::BadSignature,
+    TrapCode::OutOfFuel,
+    TrapCode::OutOfInstructions,
+    TrapCode::Interrupted,
+    TrapCode::GrowthOperationLimited,
+    TrapCode::MemoryWriteProtected,
+];
+
+/// Types that can be encoded into the compiled payload of a precompiled artifact.
+pub trait Encode {
+    /// Encodes `self` via the `encoder`.
+    fn encode(&self, encoder: &mut Encoder);
+}
+
+/// Types that can be decoded from the compiled payload of a precompiled artifact.
+pub trait Decode: Sized {
+    /// Decodes a value of type `Self` via the `decoder`.
+    ///
+    /// # Errors
+    ///
+    /// If the `decoder` runs out of bytes or encounters malformed bytes.
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError>;
+}
+
+/// Encodes the compiled payload of a precompiled artifact.
+#[derive(Debug)]
+pub struct Encoder<'a> {
+    /// The encoded bytes.
+    bytes: Vec<u8>,
+    /// The header of the encoded [`Module`].
+    header: &'a ModuleHeader,
+}
+
+impl<'a> Encoder<'a> {
+    /// Creates a new [`Encoder`] for the [`Module`] with `header`.
+    fn new(header: &'a ModuleHeader) -> Self {
+        Self {
+            bytes: Vec::new(),
+            header,
+        }
+    }
+
+    /// Writes the raw `bytes` to the [`Encoder`].
+    pub fn write(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Encodes `value` via the [`Encoder`].
+    pub fn encode<T>(&mut self, value: &T)
+    where
+        T: Encode + ?Sized,
+    {
+        value.encode(self)
+    }
+
+    /// Encodes the length `len` of a sequence.
+    ///
+    /// # Panics
+    ///
+    /// If `len` does not fit into a `u32`.
+    pub fn encode_len(&mut self, len: usize) {
+        let Ok(len) = u32::try_from(len) else {
+            panic!("sequence is too long to be encoded: {len}")
+        };
+        self.encode(&len);
+    }
+
+    /// Encodes all `items` prefixed by their number.
+    pub fn encode_all<'b, T, I>(&mut self, items: I)
+    where
+        T: Encode + 'b,
+        I: IntoIterator<Item = &'b T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let items = items.into_iter();
+        self.encode_len(items.len());
+        for item in items {
+            self.encode(item);
+        }
+    }
+
+    /// Returns the index of `func` within the compiled functions of the encoded [`Module`].
+    ///
+    /// # Panics
+    ///
+    /// If `func` is not a compiled function of the encoded [`Module`].
+    pub fn compiled_func_index(&self, func: CompiledFunc) -> u32 {
+        let Some(func_idx) = self.header.get_func_index(func) else {
+            panic!("encountered compiled function of another module: {func:?}")
+        };
+        // Note: compiled function indices are always smaller than their function indices.
+        func_idx.into_u32() - self.header.inner.imports.len_funcs() as u32
+    }
+
+    /// Finishes encoding and returns the encoded bytes.
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Decodes the compiled payload of a precompiled artifact.
+#[derive(Debug)]
+pub struct Decoder<'a> {
+    /// The remaining bytes to decode.
+    bytes: &'a [u8],
+    /// The header of the decoded [`Module`] once it has been decoded.
+    header: Option<ModuleHeader>,
+}
+
+impl<'a> Decoder<'a> {
+    /// Creates a new [`Decoder`] for the `bytes`.
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            header: None,
+        }
+    }
+
+    /// Reads the next `len` raw bytes from the [`Decoder`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`Decoder`] has less than `len` bytes left.
+    pub fn read(&mut self, len: usize) -> Result<&'a [u8], ArtifactError> {
+        if self.bytes.len() < len {
+            return Err(ArtifactError::Truncated);
+        }
+        let (read, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(read)
+    }
+
+    /// Reads the next `N` raw bytes from the [`Decoder`] as array.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Decoder`] has less than `N` bytes left.
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ArtifactError> {
+        let mut array = [0x00_u8; N];
+        array.copy_from_slice(self.read(N)?);
+        Ok(array)
+    }
+
+    /// Decodes a value of type `T` via the [`Decoder`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`Decoder`] runs out of bytes or encounters malformed bytes.
+    pub fn decode<T>(&mut self) -> Result<T, ArtifactError>
+    where
+        T: Decode,
+    {
+        T::decode(self)
+    }
+
+    /// Decodes the length of a sequence.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Decoder`] runs out of bytes.
+    pub fn decode_len(&mut self) -> Result<usize, ArtifactError> {
+        let len = self.decode::<u32>()?;
+        Ok(len as usize)
+    }
+
+    /// Decodes all items prefixed by their number.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Decoder`] runs out of bytes or encounters malformed bytes.
+    pub fn decode_all<T>(&mut self) -> Result<Vec<T>, ArtifactError>
+    where
+        T: Decode,
+    {
+        let len = self.decode_len()?;
+        // Note: every item is encoded by at least one byte which
+        //       bounds the allocation for malformed lengths.
+        let mut items = Vec::with_capacity(len.min(self.bytes.len()));
+        for _ in 0..len {
+            items.push(self.decode()?);
+        }
+        Ok(items)
+    }
+
+    /// Returns the [`CompiledFunc`] at `index` within the compiled functions of the decoded [`Module`].
+    ///
+    /// # Errors
+    ///
+    /// - If `index` is out of bounds for the compiled functions of the decoded [`Module`].
+    /// - If the header of the decoded [`Module`] has not yet been decoded.
+    pub fn compiled_func(&self, index: u32) -> Result<CompiledFunc, ArtifactError> {
+        self.header
+            .as_ref()
+            .and_then(|header| header.inner.compiled_funcs.get(index as usize))
+            .copied()
+            .ok_or(ArtifactError::Malformed)
+    }
+
+    /// Finishes decoding.
+    ///
+    /// # Errors
+    ///
+    /// If there are bytes left to decode.
+    fn finish(self) -> Result<(), ArtifactError> {
+        if !self.bytes.is_empty() {
+            return Err(ArtifactError::Malformed);
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_codec_for_int {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self, encoder: &mut Encoder) {
+                    encoder.write(&self.to_le_bytes());
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+                    decoder.read_array().map(<$ty>::from_le_bytes)
+                }
+            }
+        )*
+    };
+}
+impl_codec_for_int!(u8, u16, u32, u64, i8, i16, i32);
+
+impl Encode for bool {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&u8::from(*self));
+    }
+}
+
+impl Decode for bool {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        match decoder.decode::<u8>()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ArtifactError::Malformed),
+        }
+    }
+}
+
+impl<T> Encode for Option<T>
+where
+    T: Encode,
+{
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&self.is_some());
+        if let Some(value) = self {
+            encoder.encode(value);
+        }
+    }
+}
+
+impl<T> Decode for Option<T>
+where
+    T: Decode,
+{
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        match decoder.decode::<bool>()? {
+            true => decoder.decode().map(Some),
+            false => Ok(None),
+        }
+    }
+}
+
+impl<T, const N: usize> Encode for [T; N]
+where
+    T: Encode,
+{
+    fn encode(&self, encoder: &mut Encoder) {
+        for item in self {
+            encoder.encode(item);
+        }
+    }
+}
+
+impl<T, const N: usize> Decode for [T; N]
+where
+    T: Decode,
+{
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let mut items = Vec::with_capacity(N);
+        for _ in 0..N {
+            items.push(decoder.decode()?);
+        }
+        match <[T; N]>::try_from(items) {
+            Ok(items) => Ok(items),
+            Err(_) => unreachable!("decoded exactly {N} items"),
+        }
+    }
+}
+
+impl<A, B> Encode for (A, B)
+where
+    A: Encode,
+    B: Encode,
+{
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&self.0);
+        encoder.encode(&self.1);
+    }
+}
+
+impl<A, B> Decode for (A, B)
+where
+    A: Decode,
+    B: Decode,
+{
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        Ok((decoder.decode()?, decoder.decode()?))
+    }
+}
+
+impl Encode for str {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode_len(self.len());
+        encoder.write(self.as_bytes());
+    }
+}
+
+impl Encode for Box<str> {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&**self);
+    }
+}
+
+impl Decode for Box<str> {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let len = decoder.decode_len()?;
+        let bytes = decoder.read(len)?;
+        core::str::from_utf8(bytes)
+            .map(Box::from)
+            .map_err(|_| ArtifactError::Malformed)
+    }
+}
+
+impl Encode for UntypedValue {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.write(&self.to_le_bytes());
+    }
+}
+
+impl Decode for UntypedValue {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        decoder.read_array().map(UntypedValue::from_le_bytes)
+    }
+}
+
+impl Encode for ValueType {
+    fn encode(&self, encoder: &mut Encoder) {
+        let tag: u8 = match self {
+            ValueType::I32 => 0,
+            ValueType::I64 => 1,
+            ValueType::F32 => 2,
+            ValueType::F64 => 3,
+            ValueType::FuncRef => 4,
+            ValueType::ExternRef => 5,
+        };
+        encoder.encode(&tag);
+    }
+}
+
+impl Decode for ValueType {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        match decoder.decode::<u8>()? {
+            0 => Ok(ValueType::I32),
+            1 => Ok(ValueType::I64),
+            2 => Ok(ValueType::F32),
+            3 => Ok(ValueType::F64),
+            4 => Ok(ValueType::FuncRef),
+            5 => Ok(ValueType::ExternRef),
+            _ => Err(ArtifactError::Malformed),
+        }
+    }
+}
+
+impl Encode for TrapCode {
+    fn encode(&self, encoder: &mut Encoder) {
+        let Some(tag) = TRAP_CODES.iter().position(|trap_code| trap_code == self) else {
+            panic!("encountered trap code that cannot be encoded: {self:?}")
+        };
+        encoder.encode(&(tag as u8));
+    }
+}
+
+impl Decode for TrapCode {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let tag = decoder.decode::<u8>()?;
+        TRAP_CODES
+            .get(usize::from(tag))
+            .copied()
+            .ok_or(ArtifactError::Malformed)
+    }
+}
+
+impl Encode for CompiledFunc {
+    fn encode(&self, encoder: &mut Encoder) {
+        let index = encoder.compiled_func_index(*self);
+        encoder.encode(&index);
+    }
+}
+
+impl Decode for CompiledFunc {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let index = decoder.decode::<u32>()?;
+        decoder.compiled_func(index)
+    }
+}
+
+impl Encode for FuncType {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode_all(self.params());
+        encoder.encode_all(self.results());
+    }
+}
+
+impl Decode for FuncType {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let params = decoder.decode_all::<ValueType>()?;
+        let results = decoder.decode_all::<ValueType>()?;
+        Ok(FuncType::new(params, results))
+    }
+}
+
+impl Encode for TableType {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&self.element());
+        encoder.encode(&self.minimum());
+        encoder.encode(&self.maximum());
+    }
+}
+
+impl Decode for TableType {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let element = decoder.decode::<ValueType>()?;
+        let minimum = decoder.decode()?;
+        let maximum = decoder.decode::<Option<u32>>()?;
+        if !element.is_ref() || maximum.is_some_and(|maximum| maximum < minimum) {
+            return Err(ArtifactError::Malformed);
+        }
+        Ok(TableType::new(element, minimum, maximum))
+    }
+}
+
+impl Encode for MemoryType {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&u32::from(self.initial_pages()));
+        encoder.encode(&self.maximum_pages().map(u32::from));
+    }
+}
+
+impl Decode for MemoryType {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let initial = decoder.decode()?;
+        let maximum = decoder.decode()?;
+        MemoryType::new(initial, maximum).map_err(|_| ArtifactError::Malformed)
+    }
+}
+
+impl Encode for GlobalType {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.encode(&self.content());
+        encoder.encode(&self.mutability().is_mut());
+    }
+}
+
+impl Decode for GlobalType {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let content = decoder.decode()?;
+        let mutability = match decoder.decode::<bool>()? {
+            true => Mutability::Var,
+            false => Mutability::Const,
+        };
+        Ok(GlobalType::new(content, mutability))
+    }
+}
+
+impl Encode for ExternIdx {
+    fn encode(&self, encoder: &mut Encoder) {
+        let (tag, index): (u8, u32) = match self {
+            ExternIdx::Func(index) => (0, index.into_u32()),
+            ExternIdx::Table(index) => (1, index.into_u32()),
+            ExternIdx::Memory(index) => (2, index.into_u32()),
+            ExternIdx::Global(index) => (3, index.into_u32()),
+        };
+        encoder.encode(&tag);
+        encoder.encode(&index);
+    }
+}
+
+impl Decode for ExternIdx {
+    fn decode(decoder: &mut Decoder) -> Result<Self, ArtifactError> {
+        let tag = decoder.decode::<u8>()?;
+        let index = decoder.decode::<u32>()?;
+        match tag {
+            0 => Ok(ExternIdx::Func(FuncIdx::from(index))),
+            1 => Ok(ExternIdx::Table(TableIdx::from(index))),
+            2 => Ok(ExternIdx::Memory(MemoryIdx::from(index))),
+            3 => Ok(ExternIdx::Global(GlobalIdx::from(index))),
+            _ => Err(ArtifactError::Malformed),
+        }
+    }
+}
+
+/// Returns the encoded [`Config`] settings that affect the Wasmi bytecode of compiled functions.
+///
+/// # Note
+///
+/// Compiled functions of a precompiled artifact can only be loaded by an [`Engine`]
+/// for which this yields the same bytes as for the [`Engine`] that compiled them.
+fn codegen_settings(config: &Config) -> Vec<u8> {
+    let mut settings = Vec::new();
+    let consume_fuel = config.get_consume_fuel();
+    settings.push(u8::from(consume_fuel));
+    if consume_fuel {
+        let costs = config.fuel_costs();
+        settings.extend_from_slice(&costs.base().to_le_bytes());
+        settings.extend_from_slice(&costs.copies_per_fuel().get().to_le_bytes());
+        settings.extend_from_slice(&costs.bytes_per_fuel().get().to_le_bytes());
+    }
+    let consume_instrs = config.get_consume_instructions() || config.get_time_slice().is_some();
+    settings.push(u8::from(consume_instrs));
+    let check_epoch = config.get_epoch_interruption() || config.get_signal_preemption();
+    settings.push(u8::from(check_epoch));
+    settings.push(config.get_fuel_safepoints() as u8);
+    settings.push(config.get_epoch_safepoints() as u8);
+    for operator in config.get_denied_operators() {
+        settings.extend_from_slice(operator.as_bytes());
+        settings.push(0x00);
+    }
+    settings
+}
+
+/// Encodes the compiled payload of a precompiled artifact for the `module`.
+///
+/// # Note
+///
+/// Compiles all functions of the `module` that have not yet been compiled, e.g. due to lazy compilation.
+///
+/// # Errors
+///
+/// If a function of the `module` fails to compile.
+pub fn serialize_module(module: &Module) -> Result<Vec<u8>, Error> {
+    let engine = module.engine();
+    let header = &module.header;
+    let inner = &*header.inner;
+    let mut encoder = Encoder::new(header);
+    encoder.write(&CODE_MAGIC);
+    let settings = codegen_settings(engine.config());
+    encoder.encode_len(settings.len());
+    encoder.write(&settings);
+    encoder.encode_len(inner.func_types.len());
+    for func_type in &inner.func_types[..] {
+        engine.resolve_func_type(func_type, |func_type| encoder.encode(func_type));
+    }
+    let func_type_index = |func_type| {
+        let Some(index) = inner.func_types.iter().position(|ty| ty == func_type) else {
+            panic!("encountered function type of another module: {func_type:?}")
+        };
+        index as u32
+    };
+    let imports = &inner.imports;
+    encoder.encode_len(imports.items.len());
+    let (mut funcs, mut tables, mut memories, mut globals) = (0, 0, 0, 0);
+    for import in &imports.items[..] {
+        let name = match import {
+            Imported::Func(name)
+            | Imported::Table(name)
+            | Imported::Memory(name)
+            | Imported::Global(name) => name,
+        };
+        encoder.encode(name.module());
+        encoder.encode(name.name());
+        match import {
+            Imported::Func(_) => {
+                encoder.encode(&0_u8);
+                encoder.encode(&func_type_index(&inner.funcs[funcs]));
+                funcs += 1;
+            }
+            Imported::Table(_) => {
+                encoder.encode(&1_u8);
+                encoder.encode(&inner.tables[tables]);
+                tables += 1;
+            }
+            Imported::Memory(_) => {
+                encoder.encode(&2_u8);
+                encoder.encode(&inner.memories[memories]);
+                memories += 1;
+            }
+            Imported::Global(_) => {
+                encoder.encode(&3_u8);
+                encoder.encode(&inner.globals[globals]);
+                globals += 1;
+            }
+        }
+    }
+    let internal_funcs = &inner.funcs[imports.len_funcs..];
+    encoder.encode_len(internal_funcs.len());
+    for func_type in internal_funcs {
+        encoder.encode(&func_type_index(func_type));
+    }
+    encoder.encode_all(&inner.tables[imports.len_tables..]);
+    encoder.encode_all(&inner.memories[imports.len_memories..]);
+    let internal_globals = &inner.globals[imports.len_globals..];
+    encoder.encode_len(internal_globals.len());
+    for (global_type, global_init) in internal_globals.iter().zip(&inner.globals_init[..]) {
+        encoder.encode(global_type);
+        encoder.encode(global_init);
+    }
+    encoder.encode_len(inner.exports.len());
+    for (name, idx) in &inner.exports {
+        encoder.encode(&**name);
+        encoder.encode(idx);
+    }
+    encoder.encode(&inner.start.map(FuncIdx::into_u32));
+    encoder.encode_all(&inner.element_segments[..]);
+    encoder.encode_all(&module.data_segments[..]);
+    encoder.encode(&*module.names);
+    for func in &inner.compiled_funcs[..] {
+        engine.resolve_func(*func, |func| encoder.encode(func))?;
+    }
+    Ok(encoder.finish())
+}
+
+/// Decodes the compiled `payload` of a precompiled artifact into a [`Module`] for the `engine`.
+///
+/// # Errors
+///
+/// - If the `payload` is malformed.
+/// - If the `payload` was compiled with other code generation settings than the [`Config`] of the `engine`.
+/// - If the compiled functions exceed the compiled code budget of the `engine`.
+pub fn deserialize_module(engine: &Engine, payload: &[u8]) -> Result<Module, Error> {
+    let mut decoder = Decoder::new(payload);
+    if decoder.read(CODE_MAGIC.len())? != CODE_MAGIC {
+        return Err(ArtifactError::Malformed.into());
+    }
+    let len_settings = decoder.decode_len()?;
+    if decoder.read(len_settings)? != codegen_settings(engine.config()) {
+        return Err(ArtifactError::ConfigMismatch.into());
+    }
+    let mut header = ModuleHeaderBuilder::new(engine);
+    header.push_func_types(decoder.decode_all::<FuncType>()?.into_iter().map(Ok))?;
+    let len_func_types = header.func_types.len();
+    let func_type_idx = |decoder: &mut Decoder| -> Result<FuncTypeIdx, ArtifactError> {
+        let index = decoder.decode::<u32>()?;
+        if index as usize >= len_func_types {
+            return Err(ArtifactError::Malformed);
+        }
+        Ok(FuncTypeIdx::from(index))
+    };
+    let len_imports = decoder.decode_len()?;
+    let mut imports = Vec::new();
+    for _ in 0..len_imports {
+        let module = decoder.decode::<Box<str>>()?;
+        let name = decoder.decode::<Box<str>>()?;
+        let kind = match decoder.decode::<u8>()? {
+            0 => ExternTypeIdx::Func(func_type_idx(&mut decoder)?),
+            1 => ExternTypeIdx::Table(decoder.decode()?),
+            2 => ExternTypeIdx::Memory(decoder.decode()?),
+            3 => ExternTypeIdx::Global(decoder.decode()?),
+            _ => return Err(ArtifactError::Malformed.into()),
+        };
+        imports.push(Ok(Import::new(&module, &name, kind)));
+    }
+    header.push_imports(imports)?;
+    let len_funcs = decoder.decode_len()?;
+    let mut funcs = Vec::new();
+    for _ in 0..len_funcs {
+        funcs.push(Ok(func_type_idx(&mut decoder)?));
+    }
+    header.push_funcs(funcs)?;
+    header.push_tables(decoder.decode_all::<TableType>()?.into_iter().map(Ok))?;
+    header.push_memories(decoder.decode_all::<MemoryType>()?.into_iter().map(Ok))?;
+    let len_globals = decoder.decode_len()?;
+    for _ in 0..len_globals {
+        header.globals.push(decoder.decode::<GlobalType>()?);
+        header.globals_init.push(decoder.decode::<ConstExpr>()?);
+    }
+    let len_exports = decoder.decode_len()?;
+    let mut exports = Vec::new();
+    for _ in 0..len_exports {
+        let name = decoder.decode::<Box<str>>()?;
+        let idx = decoder.decode::<ExternIdx>()?;
+        exports.push(Ok((name, idx)));
+    }
+    header.push_exports(exports)?;
+    if let Some(start) = decoder.decode::<Option<u32>>()? {
+        header.set_start(FuncIdx::from(start));
+    }
+    header.push_element_segments(decoder.decode_all::<ElementSegment>()?.into_iter().map(Ok))?;
+    let header = header.finish();
+    let mut builder = ModuleBuilder::new(header.clone());
+    builder.push_data_segments(decoder.decode_all::<DataSegment>()?.into_iter().map(Ok))?;
+    builder.set_names(decoder.decode::<ModuleNames>()?);
+    decoder.header = Some(header.clone());
+    for func in &header.inner.compiled_funcs[..] {
+        let entity = decoder.decode::<CompiledFuncEntity>()?;
+        engine.init_compiled_func(*func, entity)?;
+    }
+    decoder.finish()?;
+    Ok(builder.finish(engine))
+}
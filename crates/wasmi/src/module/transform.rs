@@ -0,0 +1,101 @@
+use super::instrument::rewrite_module;
+use crate::Error;
+use alloc::vec::Vec;
+
+/// A Wasm function body handed to a [`FuncTransform`] before its translation.
+#[derive(Debug, Copy, Clone)]
+pub struct FuncBody<'a> {
+    /// The index of the function within the Wasm module including imported functions.
+    func_index: u32,
+    /// The offset of the function body within the Wasm module.
+    offset: usize,
+    /// The Wasm encoded function body.
+    bytes: &'a [u8],
+}
+
+impl<'a> FuncBody<'a> {
+    /// Creates a new [`FuncBody`].
+    pub(crate) fn new(func_index: u32, offset: usize, bytes: &'a [u8]) -> Self {
+        Self {
+            func_index,
+            offset,
+            bytes,
+        }
+    }
+
+    /// Returns the index of the function within the Wasm module.
+    ///
+    /// # Note
+    ///
+    /// The index space includes imported functions.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the offset of the function body within the Wasm module.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the Wasm encoded function body.
+    ///
+    /// # Note
+    ///
+    /// This contains the local variable declarations followed by the
+    /// operators of the function body as found in the Wasm code section.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+/// Observes and optionally rewrites Wasm function bodies before their translation.
+///
+/// # Note
+///
+/// This allows to instrument Wasm function bodies, e.g. with counters or
+/// security checks, without the need for an external Wasm to Wasm tool.
+/// Rewritten function bodies are validated before they are translated.
+///
+/// This is implemented for all `FnMut(FuncBody) -> Result<Option<Vec<u8>>, Error>` closures.
+pub trait FuncTransform {
+    /// Observes and optionally rewrites the Wasm function `body`.
+    ///
+    /// - Returns `Ok(None)` to translate the original function `body`.
+    /// - Returns `Ok(Some(bytes))` to translate `bytes` instead which must be
+    ///   Wasm encoded the same way as [`FuncBody::bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Errors are forwarded and abort the creation of the [`Module`](crate::Module).
+    fn transform(&mut self, body: FuncBody) -> Result<Option<Vec<u8>>, Error>;
+
+    /// Applies the [`FuncTransform`] to all function bodies of the Wasm module `wasm`.
+    ///
+    /// Returns the Wasm module with the rewritten function bodies.
+    ///
+    /// # Note
+    ///
+    /// This allows to use a [`FuncTransform`] as standalone Wasm to Wasm transform,
+    /// e.g. to store instrumented Wasm modules. All sections except for the code
+    /// section are kept as is. Neither `wasm` nor the result are validated.
+    ///
+    /// # Errors
+    ///
+    /// - If `wasm` is malformed.
+    /// - If the [`FuncTransform`] fails for any function body.
+    fn transform_module(&mut self, wasm: &[u8]) -> Result<Vec<u8>, Error>
+    where
+        Self: Sized,
+    {
+        rewrite_module(wasm, self, None)
+    }
+}
+
+impl<F> FuncTransform for F
+where
+    F: FnMut(FuncBody) -> Result<Option<Vec<u8>>, Error>,
+{
+    fn transform(&mut self, body: FuncBody) -> Result<Option<Vec<u8>>, Error> {
+        self(body)
+    }
+}
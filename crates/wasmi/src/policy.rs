@@ -0,0 +1,79 @@
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+};
+
+/// A policy that denies specific imports upon instantiation within a [`Store`].
+///
+/// # Note
+///
+/// The [`ImportPolicy`] is consulted for every import of a Wasm module that is
+/// instantiated within the [`Store`] regardless of the externals provided to it.
+/// This allows a single [`Linker`] to serve [`Store`]s of multiple trust levels.
+///
+/// By default all imports are allowed.
+///
+/// [`Store`]: crate::Store
+/// [`Linker`]: crate::Linker
+#[derive(Debug, Default, Clone)]
+pub struct ImportPolicy {
+    /// All module namespaces of which every import is denied.
+    denied_modules: BTreeSet<Box<str>>,
+    /// All denied imports by their module namespace and field name.
+    denied_imports: BTreeMap<Box<str>, BTreeSet<Box<str>>>,
+}
+
+impl ImportPolicy {
+    /// Creates a new [`ImportPolicy`] that allows all imports.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Denies the import `module::name`.
+    pub fn deny(mut self, module: &str, name: &str) -> Self {
+        self.denied_imports
+            .entry(module.into())
+            .or_default()
+            .insert(name.into());
+        self
+    }
+
+    /// Denies all imports of the `module` namespace.
+    pub fn deny_module(mut self, module: &str) -> Self {
+        self.denied_modules.insert(module.into());
+        self
+    }
+
+    /// Returns `true` if the import `module::name` is denied by the [`ImportPolicy`].
+    pub fn is_denied(&self, module: &str, name: &str) -> bool {
+        if self.denied_modules.contains(module) {
+            return true;
+        }
+        self.denied_imports
+            .get(module)
+            .is_some_and(|names| names.contains(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_all_by_default() {
+        let policy = ImportPolicy::new();
+        assert!(!policy.is_denied("env", "foo"));
+    }
+
+    #[test]
+    fn denies_imports_and_modules() {
+        let policy = ImportPolicy::new()
+            .deny("env", "exit")
+            .deny_module("wasi_snapshot_preview1");
+        assert!(policy.is_denied("env", "exit"));
+        assert!(!policy.is_denied("env", "print"));
+        assert!(!policy.is_denied("host", "exit"));
+        assert!(policy.is_denied("wasi_snapshot_preview1", "fd_write"));
+        assert!(policy.is_denied("wasi_snapshot_preview1", "proc_exit"));
+    }
+}
@@ -0,0 +1,212 @@
+//! Signal based preemption of Wasm executions on Unix.
+//!
+//! # Note
+//!
+//! A POSIX signal delivered to a thread that executes Wasm sets the interrupt
+//! flag of the executing [`Store`]. The execution then traps with
+//! [`TrapCode::Interrupted`] at its next safepoint which is either a function
+//! entry or a loop header. This allows to forcibly stop guests without the
+//! overhead of fuel metering and without a shared [`Engine`] epoch.
+//!
+//! Requires [`Config::signal_preemption`] to be enabled.
+//!
+//! # Example
+//!
+//! ```
+//! use std::{sync::mpsc, thread};
+//! use wasmi::{
+//!     core::TrapCode,
+//!     preempt::{install_signal_handler, PreemptibleThread},
+//!     Config, Engine, Linker, Module, Store,
+//! };
+//!
+//! install_signal_handler(libc::SIGUSR1).unwrap();
+//! let mut config = Config::default();
+//! config.signal_preemption(true);
+//! let engine = Engine::new(&config);
+//! let wasm = wat::parse_str(r#"
+//!     (module
+//!         (func (export "spin")
+//!             (loop $continue (br $continue))
+//!         )
+//!     )
+//! "#).unwrap();
+//! let module = Module::new(&engine, &wasm[..]).unwrap();
+//! let (sender, receiver) = mpsc::channel();
+//! let guest = thread::spawn(move || {
+//!     let mut store = Store::new(&engine, ());
+//!     let instance = <Linker<()>>::new(&engine)
+//!         .instantiate(&mut store, &module)
+//!         .unwrap()
+//!         .start(&mut store)
+//!         .unwrap();
+//!     let spin = instance.get_typed_func::<(), ()>(&store, "spin").unwrap();
+//!     sender.send(PreemptibleThread::current()).unwrap();
+//!     spin.call(&mut store, ()).unwrap_err()
+//! });
+//! let target = receiver.recv().unwrap();
+//! // Note: Signal repeatedly since the guest might not have started executing yet.
+//! while !guest.is_finished() {
+//!     // Safety: The guest thread has not yet been joined.
+//!     unsafe { target.preempt(libc::SIGUSR1) }.unwrap();
+//!     thread::sleep(std::time::Duration::from_millis(1));
+//! }
+//! let error = guest.join().unwrap();
+//! assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+//! ```
+//!
+//! [`Store`]: crate::Store
+//! [`Engine`]: crate::Engine
+//! [`Config::signal_preemption`]: crate::Config::signal_preemption
+//! [`TrapCode::Interrupted`]: crate::core::TrapCode::Interrupted
+
+use crate::Error;
+use core::{
+    cell::Cell,
+    mem,
+    ptr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use std::{format, io};
+
+std::thread_local! {
+    /// The interrupt flag of the [`Store`] that is executing Wasm on the current thread if any.
+    ///
+    /// # Note
+    ///
+    /// This is initialized via `const` and has no destructor so that it can be
+    /// accessed from within a signal handler without lazy initialization.
+    ///
+    /// [`Store`]: crate::Store
+    static CURRENT_INTERRUPT: Cell<*const AtomicBool> = const { Cell::new(ptr::null()) };
+}
+
+/// The signal handler setting the interrupt flag bound to the signalled thread if any.
+extern "C" fn handle_signal(_signal: libc::c_int) {
+    let _ = CURRENT_INTERRUPT.try_with(|current| {
+        let interrupt = current.get();
+        if !interrupt.is_null() {
+            // Safety: The pointer is bound by a live `InterruptBinding` which
+            //         guarantees that the interrupt flag outlives the binding.
+            unsafe { (*interrupt).store(true, Ordering::Relaxed) }
+        }
+    });
+}
+
+/// Installs the preemption signal handler for `signal` for the whole process.
+///
+/// # Note
+///
+/// This replaces any previously installed handler for `signal`.
+/// Use a signal that is otherwise unused by the process, e.g. `SIGUSR1`.
+///
+/// # Errors
+///
+/// If the signal handler cannot be installed for `signal`.
+pub fn install_signal_handler(signal: libc::c_int) -> Result<(), Error> {
+    // Safety: `sigaction` is a plain C struct for which all zeroes is a valid value.
+    let mut action: libc::sigaction = unsafe { mem::zeroed() };
+    action.sa_sigaction = handle_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    action.sa_flags = libc::SA_RESTART;
+    // Safety: `action` is properly initialized and `handle_signal` is async-signal-safe.
+    let result = unsafe {
+        libc::sigemptyset(&mut action.sa_mask);
+        libc::sigaction(signal, &action, ptr::null_mut())
+    };
+    if result != 0 {
+        return Err(Error::new(format!(
+            "failed to install preemption signal handler for signal {signal}: {}",
+            io::Error::last_os_error()
+        )));
+    }
+    Ok(())
+}
+
+/// A thread that can be preempted while it executes Wasm.
+///
+/// # Note
+///
+/// This is a plain POSIX thread identifier that is not tied to the lifetime
+/// of the thread. See [`PreemptibleThread::preempt`] for the requirements
+/// of delivering signals to the thread.
+#[derive(Debug, Clone)]
+pub struct PreemptibleThread {
+    /// The POSIX thread handle.
+    thread: libc::pthread_t,
+}
+
+// Safety: A `pthread_t` is a plain thread identifier that may be used from any thread.
+unsafe impl Send for PreemptibleThread {}
+// Safety: A `pthread_t` is a plain thread identifier that may be used from any thread.
+unsafe impl Sync for PreemptibleThread {}
+
+impl PreemptibleThread {
+    /// Returns the [`PreemptibleThread`] of the calling thread.
+    pub fn current() -> Self {
+        // Safety: `pthread_self` is always safe to call.
+        let thread = unsafe { libc::pthread_self() };
+        Self { thread }
+    }
+
+    /// Delivers `signal` to the [`PreemptibleThread`].
+    ///
+    /// # Note
+    ///
+    /// If the thread currently executes Wasm the execution traps at its next
+    /// safepoint given that the handler of `signal` has been installed via
+    /// [`install_signal_handler`]. Otherwise the signal has no effect.
+    ///
+    /// # Safety
+    ///
+    /// The thread identifier must still be valid. This is the case while the thread
+    /// is running and after it has finished as long as it has neither been joined
+    /// nor detached, e.g. while its [`JoinHandle`] is alive. Using the identifier
+    /// of a thread that has been joined or detached is undefined behavior.
+    ///
+    /// # Errors
+    ///
+    /// If `signal` cannot be delivered, e.g. because `signal` is invalid.
+    ///
+    /// [`JoinHandle`]: std::thread::JoinHandle
+    pub unsafe fn preempt(&self, signal: libc::c_int) -> Result<(), Error> {
+        // Safety: The caller guarantees that the thread identifier is still valid.
+        let result = unsafe { libc::pthread_kill(self.thread, signal) };
+        if result != 0 {
+            return Err(Error::new(format!(
+                "failed to deliver preemption signal {signal}: {}",
+                io::Error::from_raw_os_error(result)
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Binds an interrupt flag to the current thread until dropped.
+///
+/// # Note
+///
+/// The previously bound interrupt flag is restored upon drop so
+/// that nested executions of different stores are supported.
+#[derive(Debug)]
+pub(crate) struct InterruptBinding {
+    /// The previously bound interrupt flag.
+    previous: *const AtomicBool,
+}
+
+impl InterruptBinding {
+    /// Binds `interrupt` to the current thread.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `interrupt` outlives the returned [`InterruptBinding`].
+    pub unsafe fn new(interrupt: &AtomicBool) -> Self {
+        let previous = CURRENT_INTERRUPT.with(|current| current.replace(interrupt));
+        Self { previous }
+    }
+}
+
+impl Drop for InterruptBinding {
+    fn drop(&mut self) {
+        CURRENT_INTERRUPT.with(|current| current.set(self.previous));
+    }
+}
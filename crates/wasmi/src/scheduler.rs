@@ -0,0 +1,233 @@
+use crate::{
+    core::TrapCode,
+    store::FuelError,
+    AsContext,
+    AsContextMut,
+    Error,
+    Func,
+    ResumableCall,
+    Value,
+    YieldedInvocation,
+};
+use alloc::{collections::VecDeque, vec::Vec};
+
+/// Identifies a task spawned on a [`Scheduler`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskId(u64);
+
+/// The outcome of a single turn of a task of a [`Scheduler`].
+#[derive(Debug)]
+pub enum TaskTurn {
+    /// The task used up its fuel slice and has been rescheduled.
+    Yielded(TaskId),
+    /// The task finished and returned its results.
+    Finished(TaskId, Vec<Value>),
+    /// The task failed with an error and has been removed.
+    Failed(TaskId, Error),
+}
+
+/// The execution state of a task.
+#[derive(Debug)]
+enum TaskState {
+    /// The task has not yet been started and holds its call parameters.
+    Pending(Vec<Value>),
+    /// The task ran out of fuel or yielded during its last turn.
+    Suspended(YieldedInvocation),
+}
+
+/// A task of a [`Scheduler`].
+#[derive(Debug)]
+struct Task {
+    /// The identifier of the task.
+    id: TaskId,
+    /// The called root function of the task.
+    func: Func,
+    /// The execution state of the task.
+    state: TaskState,
+    /// The buffer for the results of the task.
+    outputs: Vec<Value>,
+}
+
+/// Cooperatively runs many function calls of the same [`Store`] on a single thread.
+///
+/// # Note
+///
+/// - Tasks are run in a round-robin fashion where every task may consume at
+///   most a fuel slice per turn before it is suspended and rescheduled.
+/// - The remaining fuel of the [`Store`] is restored after every turn while the
+///   fuel consumed by all tasks still counts towards [`Store::fuel_consumed`].
+/// - Tasks are suspended at the start of a basic block. Running out of fuel in
+///   other places, for example upon `memory.grow`, makes the task fail instead.
+/// - Tasks that yield their execution, e.g. due to [`Config::time_slice`], are rescheduled as well.
+/// - Host function errors make the task fail.
+/// - Tasks that cannot make progress within a single fuel slice fail with
+///   [`TrapCode::OutOfFuel`].
+/// - All tasks of a [`Scheduler`] must belong to the same [`Store`].
+///
+/// This requires fuel metering to be enabled via [`Config::consume_fuel`].
+///
+/// [`Store`]: crate::Store
+/// [`Store::fuel_consumed`]: crate::Store::fuel_consumed
+/// [`Config::consume_fuel`]: crate::Config::consume_fuel
+/// [`Config::time_slice`]: crate::Config::time_slice
+#[derive(Debug)]
+pub struct Scheduler {
+    /// The queue of tasks in the order of their next turns.
+    tasks: VecDeque<Task>,
+    /// The maximum amount of fuel a task may consume per turn.
+    fuel_slice: u64,
+    /// The identifier of the next spawned task.
+    next_id: u64,
+}
+
+impl Scheduler {
+    /// Creates a new [`Scheduler`] granting tasks `fuel_slice` fuel per turn.
+    pub fn new(fuel_slice: u64) -> Self {
+        Self {
+            tasks: VecDeque::new(),
+            fuel_slice,
+            next_id: 0,
+        }
+    }
+
+    /// Returns the maximum amount of fuel a task may consume per turn.
+    pub fn fuel_slice(&self) -> u64 {
+        self.fuel_slice
+    }
+
+    /// Returns the number of unfinished tasks of the [`Scheduler`].
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if the [`Scheduler`] has no unfinished tasks.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Spawns a task that calls `func` with `params` and returns its [`TaskId`].
+    ///
+    /// # Note
+    ///
+    /// The task is not executed before its first turn.
+    ///
+    /// # Errors
+    ///
+    /// - If fuel metering is disabled.
+    /// - If the types or the number of `params` do not match the parameters of `func`.
+    pub fn spawn(
+        &mut self,
+        ctx: impl AsContext,
+        func: Func,
+        params: &[Value],
+    ) -> Result<TaskId, Error> {
+        if ctx
+            .as_context()
+            .store
+            .inner
+            .fuel()
+            .fuel_consumed()
+            .is_none()
+        {
+            return Err(Error::from(FuelError::fuel_metering_disabled()));
+        }
+        let func_type = func.ty(&ctx);
+        func_type.match_params(params)?;
+        let mut outputs = func_type
+            .results()
+            .iter()
+            .copied()
+            .map(Value::default)
+            .collect::<Vec<_>>();
+        func_type.prepare_outputs(&mut outputs);
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+        self.tasks.push_back(Task {
+            id,
+            func,
+            state: TaskState::Pending(params.to_vec()),
+            outputs,
+        });
+        Ok(id)
+    }
+
+    /// Removes the task identified by `id` from the [`Scheduler`].
+    ///
+    /// Returns `true` if the task has been found and removed.
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        let Some(index) = self.tasks.iter().position(|task| task.id == id) else {
+            return false;
+        };
+        self.tasks.remove(index);
+        true
+    }
+
+    /// Runs a single turn of the next task of the [`Scheduler`].
+    ///
+    /// Returns `None` if the [`Scheduler`] has no unfinished tasks.
+    pub fn step(&mut self, mut ctx: impl AsContextMut) -> Option<TaskTurn> {
+        let Task {
+            id,
+            func,
+            state,
+            mut outputs,
+        } = self.tasks.pop_front()?;
+        let mut ctx = ctx.as_context_mut();
+        let remaining = ctx
+            .store
+            .inner
+            .fuel_mut()
+            .replace_remaining(self.fuel_slice)
+            .expect("fuel metering must be enabled since it was checked upon spawning");
+        let result = match state {
+            TaskState::Pending(params) => {
+                func.call_resumable(ctx.as_context_mut(), &params[..], &mut outputs[..])
+            }
+            TaskState::Suspended(invocation) => {
+                invocation.resume(ctx.as_context_mut(), &mut outputs[..])
+            }
+        };
+        let unused = ctx
+            .store
+            .inner
+            .fuel_mut()
+            .replace_remaining(remaining)
+            .expect("fuel metering must be enabled since it was checked upon spawning");
+        let turn = match result {
+            Ok(ResumableCall::Finished) => TaskTurn::Finished(id, outputs),
+            Ok(ResumableCall::OutOfFuel(_)) if unused == self.fuel_slice => {
+                // The task did not make any progress during its turn and never would.
+                TaskTurn::Failed(id, Error::from(TrapCode::OutOfFuel))
+            }
+            Ok(ResumableCall::OutOfFuel(invocation) | ResumableCall::Yielded(invocation)) => {
+                self.tasks.push_back(Task {
+                    id,
+                    func,
+                    state: TaskState::Suspended(invocation),
+                    outputs,
+                });
+                TaskTurn::Yielded(id)
+            }
+            Ok(ResumableCall::Resumable(invocation)) => {
+                TaskTurn::Failed(id, invocation.into_host_error())
+            }
+            Err(error) => TaskTurn::Failed(id, error),
+        };
+        Some(turn)
+    }
+
+    /// Runs all tasks of the [`Scheduler`] until they are finished or failed.
+    ///
+    /// Returns the results of all tasks in the order in which they finished or failed.
+    pub fn run(&mut self, mut ctx: impl AsContextMut) -> Vec<(TaskId, Result<Vec<Value>, Error>)> {
+        let mut results = Vec::new();
+        while let Some(turn) = self.step(&mut ctx) {
+            match turn {
+                TaskTurn::Yielded(_) => {}
+                TaskTurn::Finished(id, outputs) => results.push((id, Ok(outputs))),
+                TaskTurn::Failed(id, error) => results.push((id, Err(error))),
+            }
+        }
+        results
+    }
+}
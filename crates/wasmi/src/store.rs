@@ -1,22 +1,33 @@
 use crate::{
-    engine::{DedupFuncType, FuelCosts},
+    engine::{CallReport, DedupFuncType, FuelCosts, TrapFrame},
     externref::{ExternObject, ExternObjectEntity, ExternObjectIdx},
-    func::{Trampoline, TrampolineEntity, TrampolineIdx},
+    func::{
+        DynamicTrampoline,
+        HostCall,
+        HostCallInterceptor,
+        Trampoline,
+        TrampolineEntity,
+        TrampolineIdx,
+    },
     memory::{DataSegment, MemoryError},
     module::InstantiationError,
-    table::TableError,
-    DataSegmentEntity, DataSegmentIdx, ElementSegment, ElementSegmentEntity, ElementSegmentIdx,
-    Engine, Func, FuncEntity, FuncIdx, FuncType, Global, GlobalEntity, GlobalIdx, Instance,
-    InstanceEntity, InstanceIdx, Memory, MemoryEntity, MemoryIdx, ResourceLimiter, Table,
-    TableEntity, TableIdx,
+    table::{TableError, TableEvent},
+    Caller, DataSegmentEntity, DataSegmentIdx, ElementSegment, ElementSegmentEntity,
+    ElementSegmentIdx, Engine, Error, Func, FuncEntity, FuncIdx, FuncType, Global, GlobalEntity,
+    GlobalIdx, ImportPolicy, ImportType, Instance, InstanceEntity, InstanceIdx, Memory,
+    MemoryEntity, MemoryIdx, ResourceLimiter, StoreTeardown, Table, TableEntity, TableIdx, Value,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
 use core::{
     fmt::{self, Debug},
-    sync::atomic::{AtomicU32, Ordering},
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+    time::Duration,
 };
 use wasmi_arena::{Arena, ArenaIndex, GuardedEntity};
-use wasmi_core::TrapCode;
+use wasmi_core::{Pages, TrapCode};
+
+#[cfg(feature = "fault-injection")]
+use crate::engine::AllocFailureInjector;
 
 /// A unique store index.
 ///
@@ -55,7 +66,13 @@ pub type Stored<Idx> = GuardedEntity<StoreIdx, Idx>;
 /// A wrapper around an optional `&mut dyn` [`ResourceLimiter`], that exists
 /// both to make types a little easier to read and to provide a `Debug` impl so
 /// that `#[derive(Debug)]` works on structs that contain it.
-pub struct ResourceLimiterRef<'a>(Option<&'a mut (dyn ResourceLimiter)>);
+pub struct ResourceLimiterRef<'a> {
+    /// The [`ResourceLimiter`] of the [`Store`] if any.
+    limiter: Option<&'a mut (dyn ResourceLimiter)>,
+    /// Decides which allocations of linear memories and tables fail on purpose if any.
+    #[cfg(feature = "fault-injection")]
+    alloc_failures: Option<Arc<AllocFailureInjector>>,
+}
 impl<'a> Debug for ResourceLimiterRef<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "ResourceLimiterRef(...)")
@@ -64,7 +81,20 @@ impl<'a> Debug for ResourceLimiterRef<'a> {
 
 impl<'a> ResourceLimiterRef<'a> {
     pub fn as_resource_limiter(&mut self) -> &mut Option<&'a mut dyn ResourceLimiter> {
-        &mut self.0
+        &mut self.limiter
+    }
+
+    /// Returns `true` if the next allocation of a linear memory or table shall fail.
+    ///
+    /// This always returns `false` unless [`Config::alloc_failures`] is configured.
+    ///
+    /// [`Config::alloc_failures`]: crate::Config::alloc_failures
+    pub fn fail_allocation(&self) -> bool {
+        #[cfg(feature = "fault-injection")]
+        if let Some(alloc_failures) = &self.alloc_failures {
+            return alloc_failures.fail_next();
+        }
+        false
     }
 }
 
@@ -82,6 +112,109 @@ impl<T> Debug for ResourceLimiterQuery<T> {
     }
 }
 
+/// The hook called after every successful growth of a linear [`Memory`] of the [`Store`].
+struct MemoryGrowHook(Box<dyn Fn(Memory, Pages, Pages) + Send + Sync>);
+impl Debug for MemoryGrowHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MemoryGrowHook(...)")
+    }
+}
+
+/// The hook called after every mutation of a [`Table`] of the [`Store`].
+struct TableHook(Box<dyn Fn(Table, TableEvent) + Send + Sync>);
+impl Debug for TableHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TableHook(...)")
+    }
+}
+
+/// The hook called whenever a Wasm execution of the [`Store`] traps.
+struct TrapHook(Box<dyn Fn(&TrapFrame) + Send + Sync>);
+impl Debug for TrapHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TrapHook(...)")
+    }
+}
+
+/// The hook called whenever a Wasm execution of the [`Store`] enters or exits a Wasm function.
+struct WasmFuncHook(Box<dyn Fn(u32, WasmFuncEvent) + Send + Sync>);
+impl Debug for WasmFuncHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WasmFuncHook(...)")
+    }
+}
+
+/// The entry into or exit from a Wasm function reported to the Wasm function hook of a [`Store`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WasmFuncEvent {
+    /// The Wasm function has been called and its execution starts.
+    Enter,
+    /// The Wasm function has returned or has been replaced by a tail call.
+    Exit,
+}
+
+/// How to proceed once the epoch deadline of a [`Store`] has been reached.
+///
+/// Returned by the epoch deadline callback of a [`Store`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum UpdateDeadline {
+    /// Continues the execution with a new epoch deadline `ticks` epochs from now.
+    Continue(u32),
+    /// Yields the execution with a new epoch deadline `ticks` epochs from now.
+    ///
+    /// # Note
+    ///
+    /// Only resumable calls yield, for example via [`Func::call_resumable`], which
+    /// then return a yielded invocation that can be resumed later. This allows
+    /// epochs to drive fair scheduling on async executors. All other executions
+    /// continue as with [`UpdateDeadline::Continue`].
+    Yield(u32),
+}
+
+/// The callback deciding how to continue once the epoch deadline of the [`Store`] is reached.
+#[allow(clippy::type_complexity)]
+struct EpochDeadlineCallback<T>(
+    Box<dyn FnMut(StoreContextMut<T>) -> Result<UpdateDeadline, Error> + Send + Sync>,
+);
+impl<T> Debug for EpochDeadlineCallback<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EpochDeadlineCallback(...)")
+    }
+}
+
+/// A handle to interrupt the executions of a [`Store`] from any thread.
+///
+/// Created via [`Store::interrupt_handle`].
+#[derive(Debug, Clone)]
+pub struct InterruptHandle {
+    /// The interrupt flag shared with the [`Store`].
+    interrupt: Arc<AtomicBool>,
+}
+
+impl InterruptHandle {
+    /// Requests the interruption of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// The next safepoint of an execution of the [`Store`] clears the request
+    /// and traps with [`TrapCode::Interrupted`]. Safepoints are function entries
+    /// and loop headers. If no execution is in progress the request stays
+    /// pending until the next execution of the [`Store`] reaches a safepoint.
+    pub fn interrupt(&self) {
+        self.interrupt.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if an interruption of the [`Store`] is pending.
+    pub fn is_interrupted(&self) -> bool {
+        self.interrupt.load(Ordering::Relaxed)
+    }
+
+    /// Withdraws a pending interruption request of the [`Store`].
+    pub fn clear(&self) {
+        self.interrupt.store(false, Ordering::Relaxed);
+    }
+}
+
 /// The store that owns all data associated to Wasm modules.
 #[derive(Debug)]
 pub struct Store<T> {
@@ -94,10 +227,26 @@ pub struct Store<T> {
     pub(crate) inner: StoreInner,
     /// Stored host function trampolines.
     trampolines: Arena<TrampolineIdx, TrampolineEntity<T>>,
+    /// The trampolines of dynamically typed host functions deduplicated by their function type.
+    ///
+    /// # Note
+    ///
+    /// All dynamically typed host functions of the same function type share a single
+    /// [`DynamicTrampoline`], e.g. when a [`Linker`] defines many host functions with
+    /// only a few distinct function types.
+    ///
+    /// [`Linker`]: crate::Linker
+    trampolines_dedup: BTreeMap<DedupFuncType, DynamicTrampoline>,
     /// User provided host data owned by the [`Store`].
     data: T,
     /// User provided hook to retrieve a [`ResourceLimiter`].
     limiter: Option<ResourceLimiterQuery<T>>,
+    /// The [`ImportPolicy`] consulted upon instantiation.
+    import_policy: ImportPolicy,
+    /// User provided interceptor wrapping all host function calls.
+    host_call_interceptor: Option<HostCallInterceptor<T>>,
+    /// User provided callback deciding how to proceed once the epoch deadline has been reached.
+    epoch_deadline_callback: Option<EpochDeadlineCallback<T>>,
 }
 
 /// The inner store that owns all data not associated to the host state.
@@ -131,6 +280,64 @@ pub struct StoreInner {
     engine: Engine,
     /// The fuel of the [`Store`].
     fuel: Fuel,
+    /// The fuel accounts of [`Instance`]s that are billed separately.
+    ///
+    /// # Note
+    ///
+    /// Executions of Wasm functions of an [`Instance`] with a fuel account
+    /// are charged to both the fuel of the [`Store`] and the fuel account.
+    fuel_accounts: BTreeMap<InstanceIdx, Fuel>,
+    /// The total amount of linear memory pages grown by `memory.grow` instructions.
+    pages_grown: u64,
+    /// The resource usage of the most recent call.
+    last_call_report: CallReport,
+    /// The remaining instruction budget of the [`Store`].
+    ///
+    /// Only used if instruction budget metering is enabled.
+    instruction_budget: u64,
+    /// Is `true` if executed instructions are counted against the instruction budget.
+    consume_instructions: bool,
+    /// The number of executed instructions after which resumable calls yield if any.
+    time_slice: Option<u64>,
+    /// The remaining number of instructions of the current time slice.
+    time_slice_remaining: u64,
+    /// Is `true` if the current execution may yield, e.g. once its time slice is exhausted.
+    yieldable: bool,
+    /// The number of Wasm call frames of all executions that are currently calling host functions.
+    ///
+    /// Executions started by host functions (re-entrant calls) share the recursion
+    /// limit with the executions that called the host functions.
+    host_call_depth: usize,
+    /// The number of host functions that are currently called by Wasm executions.
+    host_call_nesting: usize,
+    /// The epoch counter shared with the [`Engine`].
+    epoch: Arc<AtomicU32>,
+    /// The epoch deadline of the [`Store`] as `(start, ticks)` if any.
+    ///
+    /// Executions are interrupted once the epoch of the [`Engine`] advanced
+    /// by `ticks` since `start`. Only used if epoch interruption is enabled.
+    epoch_deadline: Option<(u32, u32)>,
+    /// Is `true` if the [`Store`] has an epoch deadline callback.
+    ///
+    /// In this case executions are suspended instead of interrupted once the
+    /// epoch deadline has been reached so that the callback can be called.
+    epoch_deadline_callback: bool,
+    /// The interrupt flag of the [`Store`] shared with its [`InterruptHandle`]s.
+    ///
+    /// Only checked if epoch interruption or signal based preemption is enabled.
+    interrupt: Arc<AtomicBool>,
+    /// The CPU time consumed by executions of Wasm code of the [`Store`].
+    ///
+    /// Only used if CPU time accounting is enabled.
+    cpu_time: Duration,
+    /// User provided hook called after every successful linear memory growth.
+    memory_grow_hook: Option<MemoryGrowHook>,
+    /// User provided hook called after every table mutation.
+    table_hook: Option<TableHook>,
+    /// User provided hook called whenever Wasm execution raises a trap.
+    trap_hook: Option<TrapHook>,
+    /// User provided hook called whenever Wasm execution enters or exits a Wasm function.
+    wasm_func_hook: Option<WasmFuncHook>,
     /// The runtime_signature of the [`Store`].
     runtime_signature: u64,
 }
@@ -257,6 +464,30 @@ impl Fuel {
         Ok(())
     }
 
+    /// Replaces the remaining [`Fuel`] with `remaining` and returns the previously remaining [`Fuel`].
+    ///
+    /// # Note
+    ///
+    /// This does not affect the amount of [`Fuel`] consumed so far.
+    ///
+    /// # Panics
+    ///
+    /// If this overflows the [`Fuel`] counter.
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled.
+    pub(crate) fn replace_remaining(&mut self, remaining: u64) -> Result<u64, FuelError> {
+        self.check_fuel_metering_enabled()?;
+        let consumed = self.total.wrapping_sub(self.remaining);
+        self.total = consumed.checked_add(remaining).unwrap_or_else(|| {
+            panic!(
+                "encountered total fuel overflow: consumed = {consumed}, remaining = {remaining}"
+            )
+        });
+        Ok(core::mem::replace(&mut self.remaining, remaining))
+    }
+
     /// Returns the amount of [`Fuel`] consumed by executions of the [`Store`] so far.
     pub fn fuel_consumed(&self) -> Option<u64> {
         self.check_fuel_metering_enabled().ok()?;
@@ -337,6 +568,25 @@ impl StoreInner {
             elems: Arena::new(),
             extern_objects: Arena::new(),
             fuel,
+            fuel_accounts: BTreeMap::new(),
+            pages_grown: 0,
+            last_call_report: CallReport::default(),
+            instruction_budget: 0,
+            consume_instructions: engine.config().get_consume_instructions(),
+            time_slice: engine.config().get_time_slice(),
+            time_slice_remaining: 0,
+            yieldable: false,
+            host_call_depth: 0,
+            host_call_nesting: 0,
+            epoch: engine.epoch().clone(),
+            epoch_deadline: None,
+            epoch_deadline_callback: false,
+            interrupt: Arc::new(AtomicBool::new(false)),
+            cpu_time: Duration::ZERO,
+            memory_grow_hook: None,
+            table_hook: None,
+            trap_hook: None,
+            wasm_func_hook: None,
             runtime_signature: 0x97b69fcae66984bf,
         }
     }
@@ -346,11 +596,288 @@ impl StoreInner {
         &self.engine
     }
 
+    /// Returns a shared reference to the [`Fuel`] counters.
+    pub fn fuel(&self) -> &Fuel {
+        &self.fuel
+    }
+
     /// Returns an exclusive reference to the [`Fuel`] counters.
     pub fn fuel_mut(&mut self) -> &mut Fuel {
         &mut self.fuel
     }
 
+    /// Returns the remaining instruction budget if instruction budget metering is enabled.
+    pub(crate) fn instruction_budget(&self) -> Option<u64> {
+        self.engine
+            .config()
+            .get_consume_instructions()
+            .then_some(self.instruction_budget)
+    }
+
+    /// Returns the total amount of linear memory pages grown by `memory.grow` instructions.
+    pub(crate) fn pages_grown(&self) -> u64 {
+        self.pages_grown
+    }
+
+    /// Records that `delta` linear memory pages have been grown by a `memory.grow` instruction.
+    pub(crate) fn add_pages_grown(&mut self, delta: u32) {
+        self.pages_grown = self.pages_grown.wrapping_add(u64::from(delta));
+    }
+
+    /// Notifies the memory grow hook that `memory` has grown from `old` to `new` pages.
+    pub(crate) fn notify_memory_grow(&self, memory: Memory, old: Pages, new: Pages) {
+        if let Some(hook) = &self.memory_grow_hook {
+            hook.0(memory, old, new)
+        }
+    }
+
+    /// Notifies the table hook that `table` has been mutated as described by `event`.
+    pub(crate) fn notify_table(&self, table: Table, event: TableEvent) {
+        if let Some(hook) = &self.table_hook {
+            hook.0(table, event)
+        }
+    }
+
+    /// Returns `true` if the [`Store`] has a trap hook installed.
+    pub(crate) fn has_trap_hook(&self) -> bool {
+        self.trap_hook.is_some()
+    }
+
+    /// Notifies the trap hook that a trap has been raised within `frame`.
+    pub(crate) fn notify_trap(&self, frame: &TrapFrame) {
+        if let Some(hook) = &self.trap_hook {
+            hook.0(frame)
+        }
+    }
+
+    /// Returns `true` if Wasm function enter and exit events of the [`Store`] are reported.
+    pub(crate) fn has_wasm_func_hook(&self) -> bool {
+        self.wasm_func_hook.is_some() && self.engine.config().get_wasm_func_hooks()
+    }
+
+    /// Notifies the Wasm function hook that the Wasm function at `func_index` has been entered or exited.
+    pub(crate) fn notify_wasm_func(&self, func_index: u32, event: WasmFuncEvent) {
+        if let Some(hook) = &self.wasm_func_hook {
+            hook.0(func_index, event)
+        }
+    }
+
+    /// Sets the [`CallReport`] of the most recent call.
+    pub(crate) fn set_last_call_report(&mut self, report: CallReport) {
+        self.last_call_report = report;
+    }
+
+    /// Consumes `delta` fuel from the [`Store`] and the fuel account of the `instance` if any.
+    ///
+    /// # Note
+    ///
+    /// - This does not check if fuel metering is enabled.
+    /// - No fuel is consumed at all if either the [`Store`] or the fuel
+    ///   account of the `instance` has not enough fuel left.
+    ///
+    /// # Errors
+    ///
+    /// If either the [`Store`] or the fuel account of the `instance` is out of fuel.
+    #[inline]
+    pub(crate) fn consume_block_fuel_unchecked(
+        &mut self,
+        instance: &Instance,
+        delta: u64,
+    ) -> Result<(), TrapCode> {
+        if self.fuel_accounts.is_empty() {
+            self.fuel.consume_fuel_unchecked(delta)?;
+            return Ok(());
+        }
+        let instance = self.unwrap_stored(instance.as_inner());
+        match self.fuel_accounts.get_mut(&instance) {
+            Some(account) => {
+                if account.remaining < delta || self.fuel.remaining < delta {
+                    return Err(TrapCode::OutOfFuel);
+                }
+                account.consume_fuel_unchecked(delta)?;
+                self.fuel.consume_fuel_unchecked(delta)?;
+            }
+            None => {
+                self.fuel.consume_fuel_unchecked(delta)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes `delta` executed instructions from the instruction budget.
+    ///
+    /// # Note
+    ///
+    /// This does not check if instruction budget metering is enabled since
+    /// it is only used by instructions that exist only if it is enabled.
+    ///
+    /// # Errors
+    ///
+    /// If the instruction budget is exhausted.
+    ///
+    /// Also consumes `delta` instructions from the current time slice.
+    pub(crate) fn consume_instructions_unchecked(&mut self, delta: u64) -> Result<(), TrapCode> {
+        self.time_slice_remaining = self.time_slice_remaining.saturating_sub(delta);
+        if !self.consume_instructions {
+            return Ok(());
+        }
+        self.instruction_budget = self
+            .instruction_budget
+            .checked_sub(delta)
+            .ok_or(TrapCode::OutOfInstructions)?;
+        Ok(())
+    }
+
+    /// Returns `true` if the current execution shall yield since its time slice is exhausted.
+    #[inline]
+    pub(crate) fn time_slice_exhausted(&self) -> bool {
+        self.yieldable && self.time_slice_remaining == 0
+    }
+
+    /// Configures whether the upcoming execution may yield and returns the previous state.
+    ///
+    /// # Note
+    ///
+    /// - Executions only ever yield due to their time slice if time slicing is
+    ///   enabled via [`Config::time_slice`](crate::Config::time_slice).
+    /// - Starts a new time slice if the upcoming execution may yield.
+    /// - Use [`StoreInner::restore_yieldable`] with the returned state after the execution.
+    pub(crate) fn enter_yieldable(&mut self, yieldable: bool) -> bool {
+        if yieldable {
+            // Note: without time slicing the time slice is practically never exhausted.
+            self.time_slice_remaining = self.time_slice.unwrap_or(u64::MAX);
+        }
+        core::mem::replace(&mut self.yieldable, yieldable)
+    }
+
+    /// Returns `true` if the current execution may yield.
+    pub(crate) fn is_yieldable(&self) -> bool {
+        self.yieldable
+    }
+
+    /// Restores the `yieldable` state returned by [`StoreInner::enter_yieldable`].
+    pub(crate) fn restore_yieldable(&mut self, yieldable: bool) {
+        self.yieldable = yieldable;
+    }
+
+    /// Returns the number of Wasm call frames of all executions that are currently calling host functions.
+    ///
+    /// Executions started from within host functions must not exceed the
+    /// recursion limit minus this depth.
+    ///
+    /// # Errors
+    ///
+    /// If starting another execution exceeds the configured maximum re-entrancy depth.
+    pub(crate) fn host_call_depth(&self) -> Result<usize, TrapCode> {
+        if self.host_call_nesting > self.engine.config().get_max_reentrancy_depth() {
+            return Err(TrapCode::StackOverflow);
+        }
+        Ok(self.host_call_depth)
+    }
+
+    /// Returns `true` if the [`Store`] is currently executing, i.e. calling a host function.
+    ///
+    /// # Note
+    ///
+    /// Since executions borrow the [`Store`] exclusively it can only be
+    /// accessed during an execution from within the host functions it calls.
+    pub(crate) fn is_executing(&self) -> bool {
+        self.host_call_nesting > 0
+    }
+
+    /// Sets the host call depth to `depth` before calling a host function and returns the previous one.
+    ///
+    /// Use [`StoreInner::restore_host_call_depth`] with the returned depth after the host function call.
+    pub(crate) fn enter_host_call(&mut self, depth: usize) -> usize {
+        self.host_call_nesting += 1;
+        core::mem::replace(&mut self.host_call_depth, depth)
+    }
+
+    /// Restores the host call `depth` returned by [`StoreInner::enter_host_call`].
+    pub(crate) fn restore_host_call_depth(&mut self, depth: usize) {
+        self.host_call_nesting -= 1;
+        self.host_call_depth = depth;
+    }
+
+    /// Checks if the epoch deadline of the [`Store`] has been reached.
+    ///
+    /// # Errors
+    ///
+    /// If the epoch deadline has been reached.
+    #[inline]
+    pub(crate) fn check_epoch_deadline(&self) -> Result<(), TrapCode> {
+        if let Some((start, ticks)) = self.epoch_deadline {
+            let elapsed = self.epoch.load(Ordering::Relaxed).wrapping_sub(start);
+            if elapsed >= ticks {
+                return Err(TrapCode::Interrupted);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of entities allocated by the [`StoreInner`].
+    pub(crate) fn len_entities(&self) -> usize {
+        self.funcs.len()
+            + self.memories.len()
+            + self.tables.len()
+            + self.globals.len()
+            + self.instances.len()
+            + self.datas.len()
+            + self.elems.len()
+            + self.extern_objects.len()
+    }
+
+    /// Deallocates the most recently allocated entity of the [`StoreInner`] if any.
+    ///
+    /// Returns `false` if the [`StoreInner`] has no more entities.
+    ///
+    /// # Note
+    ///
+    /// This invalidates the indices of the deallocated entities and therefore
+    /// must only be used upon tearing down the [`Store`].
+    pub(crate) fn pop_entity(&mut self) -> bool {
+        // Note: linear memories and tables usually own most of the memory
+        //       and are therefore deallocated first.
+        self.memories.pop().is_some()
+            || self.tables.pop().is_some()
+            || self.datas.pop().is_some()
+            || self.elems.pop().is_some()
+            || self.instances.pop().is_some()
+            || self.extern_objects.pop().is_some()
+            || self.globals.pop().is_some()
+            || self.funcs.pop().is_some()
+    }
+
+    /// Returns `true` if the epoch deadline has been reached and the epoch deadline callback must be called.
+    #[inline]
+    pub(crate) fn epoch_deadline_callback_due(&self) -> bool {
+        self.epoch_deadline_callback && self.check_epoch_deadline().is_err()
+    }
+
+    /// Checks if the interrupt flag of the [`Store`] has been set and clears it.
+    ///
+    /// # Errors
+    ///
+    /// If the interrupt flag has been set.
+    #[inline]
+    pub(crate) fn check_interrupt(&self) -> Result<(), TrapCode> {
+        if self.interrupt.load(Ordering::Relaxed) && self.interrupt.swap(false, Ordering::Relaxed) {
+            return Err(TrapCode::Interrupted);
+        }
+        Ok(())
+    }
+
+    /// Adds `delta` to the CPU time consumed by executions of the [`Store`].
+    pub(crate) fn add_cpu_time(&mut self, delta: Duration) {
+        self.cpu_time = self.cpu_time.saturating_add(delta);
+    }
+
+    /// Returns the interrupt flag of the [`Store`].
+    #[cfg(all(unix, feature = "std"))]
+    pub(crate) fn interrupt_flag(&self) -> &AtomicBool {
+        &self.interrupt
+    }
+
     /// Wraps an entity `Idx` (index type) as a [`Stored<Idx>`] type.
     ///
     /// # Note
@@ -460,6 +987,35 @@ impl StoreInner {
         *uninit = init;
     }
 
+    /// Replaces the [`InstanceEntity`] of the initialized [`Instance`] with `init`.
+    ///
+    /// # Note
+    ///
+    /// This is used to hot-reload the code of an [`Instance`].
+    ///
+    /// # Panics
+    ///
+    /// - If the [`Instance`] does not belong to the [`Store`].
+    /// - If the [`Instance`] is unknown to the [`Store`].
+    /// - If the [`Instance`] has not yet been initialized.
+    /// - If the given [`InstanceEntity`] is itself not initialized, yet.
+    pub fn reinitialize_instance(&mut self, instance: Instance, init: InstanceEntity) {
+        assert!(
+            init.is_initialized(),
+            "encountered an uninitialized new instance entity: {init:?}",
+        );
+        let idx = self.unwrap_stored(instance.as_inner());
+        let entity = self
+            .instances
+            .get_mut(idx)
+            .unwrap_or_else(|| panic!("missing entity for the given instance: {instance:?}"));
+        assert!(
+            entity.is_initialized(),
+            "encountered an uninitialized instance: {entity:?}",
+        );
+        *entity = init;
+    }
+
     /// Returns a shared reference to the entity indexed by the given `idx`.
     ///
     /// # Panics
@@ -574,6 +1130,7 @@ impl StoreInner {
     ///
     /// - If the [`Table`] does not originate from this [`Store`].
     /// - If the [`Table`] cannot be resolved to its entity.
+    #[cfg(not(all(not(feature = "reference-types"), not(feature = "bulk-table"))))]
     pub fn resolve_table_and_fuel_mut(&mut self, table: &Table) -> (&mut TableEntity, &mut Fuel) {
         let idx = self.unwrap_stored(table.as_inner());
         let table = Self::resolve_mut(idx, &mut self.tables);
@@ -649,6 +1206,7 @@ impl StoreInner {
     /// - If the [`Table`] cannot be resolved to its entity.
     /// - If the [`ElementSegment`] does not originate from this [`Store`].
     /// - If the [`ElementSegment`] cannot be resolved to its entity.
+    #[cfg(feature = "bulk-table")]
     pub(super) fn resolve_table_init_params(
         &mut self,
         instance: &Instance,
@@ -716,6 +1274,25 @@ impl StoreInner {
         Self::resolve_mut(idx, &mut self.memories)
     }
 
+    /// Returns exclusive references to the [`MemoryEntity`] pair associated to `fst` and `snd`.
+    ///
+    /// # Panics
+    ///
+    /// - If either [`Memory`] does not originate from this [`Store`].
+    /// - If either [`Memory`] cannot be resolved to its entity.
+    /// - If `fst` and `snd` refer to the same [`Memory`].
+    pub fn resolve_memory_pair_mut(
+        &mut self,
+        fst: &Memory,
+        snd: &Memory,
+    ) -> (&mut MemoryEntity, &mut MemoryEntity) {
+        let fst = self.unwrap_stored(fst.as_inner());
+        let snd = self.unwrap_stored(snd.as_inner());
+        self.memories.get_pair_mut(fst, snd).unwrap_or_else(|| {
+            panic!("failed to resolve stored pair of entities: {fst:?} and {snd:?}")
+        })
+    }
+
     /// Returns an exclusive reference to the [`MemoryEntity`] associated to the given [`Memory`].
     ///
     /// # Panics
@@ -763,6 +1340,16 @@ impl StoreInner {
         (mem, data, fuel)
     }
 
+    /// Returns a shared reference to the [`DataSegmentEntity`] associated to the given [`DataSegment`].
+    ///
+    /// # Panics
+    ///
+    /// - If the [`DataSegment`] does not originate from this [`Store`].
+    /// - If the [`DataSegment`] cannot be resolved to its entity.
+    pub fn resolve_data_segment(&self, segment: &DataSegment) -> &DataSegmentEntity {
+        self.resolve(segment.as_inner(), &self.datas)
+    }
+
     /// Returns an exclusive reference to the [`DataSegmentEntity`] associated to the given [`DataSegment`].
     ///
     /// # Panics
@@ -828,8 +1415,12 @@ impl<T> Store<T> {
         Self {
             inner: StoreInner::new(engine),
             trampolines: Arena::new(),
+            trampolines_dedup: BTreeMap::new(),
             data,
             limiter: None,
+            import_policy: ImportPolicy::default(),
+            host_call_interceptor: None,
+            epoch_deadline_callback: None,
         }
     }
 
@@ -853,6 +1444,49 @@ impl<T> Store<T> {
         self.data
     }
 
+    /// Consumes `self` and returns a [`StoreTeardown`] to deallocate its entities incrementally.
+    ///
+    /// # Note
+    ///
+    /// This avoids long pauses upon dropping a [`Store`] that owns thousands
+    /// of instances or linear memories. Read [`StoreTeardown`] for more information.
+    pub fn into_teardown(self) -> StoreTeardown<T> {
+        StoreTeardown::new(self.inner, self.trampolines, self.data)
+    }
+
+    /// Returns an iterator over all fully initialized [`Instance`]s of the [`Store`].
+    pub fn instances(&self) -> impl Iterator<Item = Instance> + '_ {
+        self.inner
+            .instances
+            .iter()
+            .filter(|(_, entity)| entity.is_initialized())
+            .map(|(idx, _)| Instance::from_inner(self.inner.wrap_stored(idx)))
+    }
+
+    /// Returns an iterator over all linear [`Memory`]s of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// Use [`Memory::ty`] and [`Memory::current_pages`] to query their type and size.
+    pub fn memories(&self) -> impl ExactSizeIterator<Item = Memory> + '_ {
+        self.inner
+            .memories
+            .iter()
+            .map(|(idx, _)| Memory::from_inner(self.inner.wrap_stored(idx)))
+    }
+
+    /// Returns an iterator over all [`Table`]s of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// Use [`Table::ty`] and [`Table::size`] to query their type and size.
+    pub fn tables(&self) -> impl ExactSizeIterator<Item = Table> + '_ {
+        self.inner
+            .tables
+            .iter()
+            .map(|(idx, _)| Table::from_inner(self.inner.wrap_stored(idx)))
+    }
+
     /// Installs a function into the [`Store`] that will be called with the user
     /// data type `T` to retrieve a [`ResourceLimiter`] any time a limited,
     /// growable resource such as a linear memory or table is grown.
@@ -863,6 +1497,57 @@ impl<T> Store<T> {
         self.limiter = Some(ResourceLimiterQuery(Box::new(limiter)))
     }
 
+    /// Sets the [`ImportPolicy`] that is consulted upon instantiation within the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// Instantiating a Wasm module with an import denied by the [`ImportPolicy`]
+    /// fails with [`InstantiationError::ImportDenied`].
+    pub fn set_import_policy(&mut self, policy: ImportPolicy) {
+        self.import_policy = policy;
+    }
+
+    /// Returns the [`ImportPolicy`] of the [`Store`].
+    pub fn import_policy(&self) -> &ImportPolicy {
+        &self.import_policy
+    }
+
+    /// Installs an interceptor into the [`Store`] that wraps all host function calls.
+    ///
+    /// # Note
+    ///
+    /// The interceptor is called instead of the host function with the [`Caller`]
+    /// and the intercepted [`HostCall`]. It may inspect the call before and after
+    /// proceeding it via [`HostCall::proceed`] or reject it by returning an error.
+    /// This allows to audit, rate limit or trace host API usage without wrapping
+    /// every individual host function.
+    ///
+    /// [`Caller`]: crate::Caller
+    pub fn set_host_call_interceptor(
+        &mut self,
+        interceptor: impl Fn(Caller<'_, T>, &mut HostCall<'_, T>) -> Result<(), Error>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.host_call_interceptor = Some(HostCallInterceptor::new(interceptor));
+    }
+
+    /// Returns the host call interceptor of the [`Store`] if any.
+    pub(crate) fn host_call_interceptor(&self) -> Option<&HostCallInterceptor<T>> {
+        self.host_call_interceptor.as_ref()
+    }
+
+    /// Returns an error if the [`ImportPolicy`] of the [`Store`] denies `import`.
+    pub(crate) fn check_import(&self, import: &ImportType) -> Result<(), InstantiationError> {
+        if self.import_policy.is_denied(import.module(), import.name()) {
+            return Err(InstantiationError::ImportDenied {
+                name: import.import_name().clone(),
+            });
+        }
+        Ok(())
+    }
+
     pub(crate) fn check_new_instances_limit(
         &mut self,
         num_new_instances: usize,
@@ -905,10 +1590,14 @@ impl<T> Store<T> {
     pub(crate) fn store_inner_and_resource_limiter_ref(
         &mut self,
     ) -> (&mut StoreInner, ResourceLimiterRef) {
-        let resource_limiter = ResourceLimiterRef(match &mut self.limiter {
-            Some(q) => Some(q.0(&mut self.data)),
-            None => None,
-        });
+        let resource_limiter = ResourceLimiterRef {
+            limiter: match &mut self.limiter {
+                Some(q) => Some(q.0(&mut self.data)),
+                None => None,
+            },
+            #[cfg(feature = "fault-injection")]
+            alloc_failures: self.inner.engine.alloc_failures().cloned(),
+        };
         (&mut self.inner, resource_limiter)
     }
 
@@ -932,6 +1621,21 @@ impl<T> Store<T> {
         self.inner.fuel.fuel_consumed()
     }
 
+    /// Returns the CPU time consumed by executions of Wasm code of the [`Store`] so far.
+    ///
+    /// Returns `None` if [`Config::cpu_time_accounting`] is disabled or unsupported on the platform.
+    ///
+    /// # Note
+    ///
+    /// This is the thread CPU time spent in Wasm code and excludes the time spent in
+    /// host functions. Unlike fuel it is not deterministic but reflects the actual costs.
+    ///
+    /// [`Config::cpu_time_accounting`]: crate::Config::cpu_time_accounting
+    pub fn cpu_time(&self) -> Option<Duration> {
+        let enabled = cfg!(unix) && self.engine().config().get_cpu_time_accounting();
+        enabled.then_some(self.inner.cpu_time)
+    }
+
     /// Synthetically consumes an amount of fuel for the [`Store`].
     ///
     /// Returns the remaining amount of fuel after this operation.
@@ -948,12 +1652,284 @@ impl<T> Store<T> {
         self.inner.fuel.consume_fuel(|_| delta)
     }
 
+    /// Adds `delta` quantity of fuel to the fuel account of the `instance`.
+    ///
+    /// # Note
+    ///
+    /// - A fuel account is created for the `instance` if it has none, yet.
+    /// - Executions of Wasm functions of the `instance` are charged to its
+    ///   fuel account in addition to the fuel of the [`Store`].
+    ///   Executions trap with [`TrapCode::OutOfFuel`] if either runs out of fuel.
+    /// - This allows to bill and limit multiple [`Instance`]s of the same [`Store`] independently.
+    /// - Fuel for bulk operations such as `memory.copy` is charged to the [`Store`] only.
+    ///
+    /// # Panics
+    ///
+    /// - If the `instance` does not originate from this [`Store`].
+    /// - If this overflows the fuel counter of the fuel account.
+    ///
+    /// # Errors
+    ///
+    /// If fuel metering is disabled.
+    pub fn add_instance_fuel(&mut self, instance: &Instance, delta: u64) -> Result<(), FuelError> {
+        let idx = self.inner.unwrap_stored(instance.as_inner());
+        let engine = &self.inner.engine;
+        let account = self
+            .inner
+            .fuel_accounts
+            .entry(idx)
+            .or_insert_with(|| Fuel::new(engine));
+        account.add_fuel(delta)
+    }
+
+    /// Returns the amount of fuel consumed by executions of the `instance` so far.
+    ///
+    /// Returns `None` if fuel metering is disabled or if the `instance` has no fuel account.
+    ///
+    /// # Panics
+    ///
+    /// If the `instance` does not originate from this [`Store`].
+    pub fn instance_fuel_consumed(&self, instance: &Instance) -> Option<u64> {
+        let idx = self.inner.unwrap_stored(instance.as_inner());
+        self.inner.fuel_accounts.get(&idx)?.fuel_consumed()
+    }
+
+    /// Removes the fuel account of the `instance` and returns the amount of fuel it consumed.
+    ///
+    /// Returns `None` if fuel metering is disabled or if the `instance` has no fuel account.
+    ///
+    /// # Panics
+    ///
+    /// If the `instance` does not originate from this [`Store`].
+    pub fn remove_instance_fuel(&mut self, instance: &Instance) -> Option<u64> {
+        let idx = self.inner.unwrap_stored(instance.as_inner());
+        self.inner.fuel_accounts.remove(&idx)?.fuel_consumed()
+    }
+
+    /// Returns the [`CallReport`] of the most recent Wasm or host function call of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// This is updated after every call from the host regardless of its success.
+    /// Calls from within host functions overwrite the [`CallReport`] of the
+    /// enclosing call until the enclosing call returns.
+    pub fn last_call_report(&self) -> CallReport {
+        self.inner.last_call_report
+    }
+
+    /// Sets the remaining instruction budget of the [`Store`] to `budget`.
+    ///
+    /// # Note
+    ///
+    /// The instruction budget is only consumed if instruction budget metering
+    /// is enabled via [`Config::consume_instructions`]. Executions trap with
+    /// [`TrapCode::OutOfInstructions`] once the budget is exhausted.
+    ///
+    /// [`Config::consume_instructions`]: crate::Config::consume_instructions
+    pub fn set_instruction_budget(&mut self, budget: u64) {
+        self.inner.instruction_budget = budget;
+    }
+
+    /// Returns the remaining instruction budget of the [`Store`].
+    pub fn instruction_budget(&self) -> u64 {
+        self.inner.instruction_budget
+    }
+
+    /// Sets the epoch deadline of the [`Store`] to `ticks` epochs from now.
+    ///
+    /// # Note
+    ///
+    /// The deadline is only checked if epoch interruption is enabled via
+    /// [`Config::epoch_interruption`]. Executions trap with [`TrapCode::Interrupted`]
+    /// at the next function entry or loop header once [`Engine::increment_epoch`]
+    /// has been called at least `ticks` times since unless an epoch deadline
+    /// callback has been installed via [`Store::set_epoch_deadline_callback`].
+    ///
+    /// [`Config::epoch_interruption`]: crate::Config::epoch_interruption
+    pub fn set_epoch_deadline(&mut self, ticks: u32) {
+        let start = self.inner.epoch.load(Ordering::Relaxed);
+        self.inner.epoch_deadline = Some((start, ticks));
+    }
+
+    /// Clears the epoch deadline of the [`Store`] so that executions are never interrupted.
+    pub fn clear_epoch_deadline(&mut self) {
+        self.inner.epoch_deadline = None;
+    }
+
+    /// Installs a callback into the [`Store`] that is called once the epoch deadline has been reached.
+    ///
+    /// # Note
+    ///
+    /// Instead of trapping with [`TrapCode::Interrupted`] the execution calls
+    /// the `callback` which decides how to proceed via the returned [`UpdateDeadline`]:
+    ///
+    /// - [`UpdateDeadline::Continue`] re-arms the epoch deadline and continues the execution.
+    /// - [`UpdateDeadline::Yield`] re-arms the epoch deadline and yields resumable calls.
+    /// - Returning an error makes the execution fail with the error.
+    ///
+    /// This allows epochs to drive fair scheduling of resumable calls
+    /// on async executors rather than only hard interruptions.
+    pub fn set_epoch_deadline_callback(
+        &mut self,
+        callback: impl FnMut(StoreContextMut<T>) -> Result<UpdateDeadline, Error>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.epoch_deadline_callback = Some(EpochDeadlineCallback(Box::new(callback)));
+        self.inner.epoch_deadline_callback = true;
+    }
+
+    /// Removes the epoch deadline callback of the [`Store`] if any.
+    ///
+    /// Executions trap with [`TrapCode::Interrupted`] once the epoch deadline has been reached.
+    pub fn clear_epoch_deadline_callback(&mut self) {
+        self.epoch_deadline_callback = None;
+        self.inner.epoch_deadline_callback = false;
+    }
+
+    /// Calls the epoch deadline callback and re-arms the epoch deadline accordingly.
+    ///
+    /// Returns `true` if the execution shall yield.
+    ///
+    /// # Errors
+    ///
+    /// If the epoch deadline callback returned an error.
+    pub(crate) fn update_epoch_deadline(&mut self) -> Result<bool, Error> {
+        let Some(mut callback) = self.epoch_deadline_callback.take() else {
+            return Err(Error::from(TrapCode::Interrupted));
+        };
+        let update = (callback.0)(StoreContextMut { store: self });
+        if self.epoch_deadline_callback.is_none() && self.inner.epoch_deadline_callback {
+            // Note: the callback might have replaced or cleared itself.
+            self.epoch_deadline_callback = Some(callback);
+        }
+        let (ticks, yields) = match update? {
+            UpdateDeadline::Continue(ticks) => (ticks, false),
+            UpdateDeadline::Yield(ticks) => (ticks, true),
+        };
+        self.set_epoch_deadline(ticks);
+        Ok(yields && self.inner.is_yieldable())
+    }
+
+    /// Returns an [`InterruptHandle`] to interrupt executions of the [`Store`] from other threads.
+    ///
+    /// # Note
+    ///
+    /// The interrupt flag is only checked if epoch interruption or signal based
+    /// preemption is enabled via [`Config::epoch_interruption`] or [`Config::signal_preemption`].
+    ///
+    /// [`Config::epoch_interruption`]: crate::Config::epoch_interruption
+    /// [`Config::signal_preemption`]: crate::Config::signal_preemption
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        InterruptHandle {
+            interrupt: self.inner.interrupt.clone(),
+        }
+    }
+
+    /// Installs a hook into the [`Store`] that is called after every successful linear memory growth.
+    ///
+    /// # Note
+    ///
+    /// The hook is called with the grown [`Memory`] as well as its amount of
+    /// pages before and after the growth. This includes growth by Wasm `memory.grow`
+    /// instructions as well as by [`Memory::grow`]. This allows hosts that mirror
+    /// linear memories to react immediately instead of polling their sizes.
+    pub fn set_memory_grow_hook(
+        &mut self,
+        hook: impl Fn(Memory, Pages, Pages) + Send + Sync + 'static,
+    ) {
+        self.inner.memory_grow_hook = Some(MemoryGrowHook(Box::new(hook)));
+    }
+
+    /// Installs a hook into the [`Store`] that is called after every table mutation.
+    ///
+    /// # Note
+    ///
+    /// The hook is called with the mutated [`Table`] and a [`TableEvent`] describing
+    /// the mutation. This includes mutations by the Wasm `table.grow`, `table.set`,
+    /// `table.init`, `table.fill` and `table.copy` instructions as well as growth by
+    /// [`Table::grow`]. Mutations that do not affect any elements are not reported.
+    /// This allows hosts that cache function references to invalidate their caches.
+    pub fn set_table_hook(&mut self, hook: impl Fn(Table, TableEvent) + Send + Sync + 'static) {
+        self.inner.table_hook = Some(TableHook(Box::new(hook)));
+    }
+
+    /// Installs a hook into the [`Store`] that is called whenever Wasm execution raises a trap.
+    ///
+    /// # Note
+    ///
+    /// The hook is called with the [`TrapFrame`] of the trapping Wasm function at the
+    /// moment the trap is raised and before the call stack is torn down. This allows
+    /// hosts to log rich context or to capture custom dumps exactly at the failure point.
+    /// Errors returned by host functions do not invoke the hook.
+    pub fn set_trap_hook(&mut self, hook: impl Fn(&TrapFrame) + Send + Sync + 'static) {
+        self.inner.trap_hook = Some(TrapHook(Box::new(hook)));
+    }
+
+    /// Installs a hook into the [`Store`] that is called whenever Wasm execution enters or exits a Wasm function.
+    ///
+    /// # Note
+    ///
+    /// The hook is called with the index of the Wasm function within its Wasm module
+    /// and a [`WasmFuncEvent`]. Tail calls exit the calling function before entering the
+    /// called one. Wasm functions whose execution is aborted by a trap are not exited.
+    /// Calls to host functions are not reported.
+    ///
+    /// The hook is only called if [`Config::wasm_func_hooks`] is enabled.
+    ///
+    /// [`Config::wasm_func_hooks`]: crate::Config::wasm_func_hooks
+    pub fn set_wasm_func_hook(
+        &mut self,
+        hook: impl Fn(u32, WasmFuncEvent) + Send + Sync + 'static,
+    ) {
+        self.inner.wasm_func_hook = Some(WasmFuncHook(Box::new(hook)));
+    }
+
     /// Allocates a new [`TrampolineEntity`] and returns a [`Trampoline`] reference to it.
+    ///
+    /// # Note
+    ///
+    /// Dynamically typed host functions are called through the [`DynamicTrampoline`]
+    /// of their function type which is created if it does not yet exist.
     pub(super) fn alloc_trampoline(&mut self, func: TrampolineEntity<T>) -> Trampoline {
+        if let Some(ty) = func.dynamic_ty() {
+            if !self.trampolines_dedup.contains_key(ty) {
+                let trampoline = self.engine().resolve_func_type(ty, DynamicTrampoline::new);
+                self.trampolines_dedup.insert(*ty, trampoline);
+            }
+        }
         let idx = self.trampolines.alloc(func);
         Trampoline::from_inner(self.inner.wrap_stored(idx))
     }
 
+    /// Takes a parameter and result buffer from the [`DynamicTrampoline`] of `ty`.
+    ///
+    /// Returns the number of parameters of `ty` alongside the buffer.
+    ///
+    /// # Panics
+    ///
+    /// If no dynamically typed host function of type `ty` has been allocated to the [`Store`].
+    pub(super) fn take_host_func_buffer(&mut self, ty: &DedupFuncType) -> (usize, Box<[Value]>) {
+        self.trampolines_dedup
+            .get_mut(ty)
+            .unwrap_or_else(|| panic!("missing trampoline for host function type: {ty:?}"))
+            .take_buffer()
+    }
+
+    /// Returns the `buffer` taken via [`Store::take_host_func_buffer`] to the [`DynamicTrampoline`] of `ty`.
+    pub(super) fn recycle_host_func_buffer(&mut self, ty: &DedupFuncType, buffer: Box<[Value]>) {
+        if let Some(trampoline) = self.trampolines_dedup.get_mut(ty) {
+            trampoline.recycle_buffer(buffer);
+        }
+    }
+
+    /// Returns the number of [`DynamicTrampoline`]s stored in the [`Store`].
+    #[cfg(test)]
+    pub(crate) fn len_dynamic_trampolines(&self) -> usize {
+        self.trampolines_dedup.len()
+    }
+
     /// Returns an exclusive reference to the [`MemoryEntity`] associated to the given [`Memory`]
     /// and an exclusive reference to the user provided host state.
     ///
@@ -973,6 +1949,31 @@ impl<T> Store<T> {
         (self.inner.resolve_memory_mut(memory), &mut self.data)
     }
 
+    /// Returns a triple of:
+    ///
+    /// - An exclusive reference to the [`MemoryEntity`] associated to `fst`.
+    /// - An exclusive reference to the [`MemoryEntity`] associated to `snd`.
+    /// - An exclusive reference to the user provided host state.
+    ///
+    /// # Note
+    ///
+    /// This method exists to properly handle use cases where
+    /// otherwise the Rust borrow-checker would not accept.
+    ///
+    /// # Panics
+    ///
+    /// - If either [`Memory`] does not originate from this [`Store`].
+    /// - If either [`Memory`] cannot be resolved to its entity.
+    /// - If `fst` and `snd` refer to the same [`Memory`].
+    pub(super) fn resolve_memory_pair_and_state_mut(
+        &mut self,
+        fst: &Memory,
+        snd: &Memory,
+    ) -> (&mut MemoryEntity, &mut MemoryEntity, &mut T) {
+        let (fst, snd) = self.inner.resolve_memory_pair_mut(fst, snd);
+        (fst, snd, &mut self.data)
+    }
+
     /// Returns a shared reference to the associated entity of the host function trampoline.
     ///
     /// # Panics
@@ -40,6 +40,23 @@ impl ArenaIndex for TableIdx {
     }
 }
 
+/// A mutation of a [`Table`] reported to the table hook of a [`Store`].
+///
+/// [`Store`]: crate::Store
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TableEvent {
+    /// The [`Table`] has grown from `old_size` to `new_size` elements.
+    Grow { old_size: u32, new_size: u32 },
+    /// The element at `index` has been replaced by a `table.set` instruction.
+    Set { index: u32 },
+    /// The `len` elements starting at `dst` have been replaced by a `table.init` instruction.
+    Init { dst: u32, len: u32 },
+    /// The `len` elements starting at `dst` have been replaced by a `table.fill` instruction.
+    Fill { dst: u32, len: u32 },
+    /// The `len` elements starting at `dst` have been replaced by a `table.copy` instruction.
+    Copy { dst: u32, len: u32 },
+}
+
 /// A descriptor for a [`Table`] instance.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct TableType {
@@ -141,7 +158,7 @@ impl TableType {
 }
 
 /// A Wasm table entity.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TableEntity {
     ty: TableType,
     elements: Vec<UntypedValue>,
@@ -173,10 +190,29 @@ impl TableEntity {
             }
         }
 
-        let elements = vec![init.into(); ty.minimum() as usize];
+        let mut elements = Vec::new();
+        if limiter.fail_allocation() || elements.try_reserve_exact(ty.minimum() as usize).is_err() {
+            let err = TableError::GrowOutOfBounds {
+                maximum: ty.maximum().unwrap_or(u32::MAX),
+                current: 0,
+                delta: ty.minimum(),
+            };
+            if let Some(limiter) = limiter.as_resource_limiter() {
+                limiter.table_grow_failed(&err)
+            }
+            return Err(err);
+        }
+        elements.resize(ty.minimum() as usize, init.into());
         Ok(Self { ty, elements })
     }
 
+    /// Resets the table to its minimum size with all elements set to `null`.
+    pub fn reset(&mut self) {
+        let init = Value::default(self.ty.element());
+        self.elements.truncate(self.ty.minimum() as usize);
+        self.elements.fill(init.into());
+    }
+
     /// Returns the resizable limits of the table.
     pub fn ty(&self) -> TableType {
         self.ty
@@ -279,6 +315,11 @@ impl TableEntity {
                 Err(FuelError::OutOfFuel) => return notify_limiter(limiter),
             }
         }
+        // The allocation of the grown table may still fail
+        // in which case the growth fails instead of aborting the process.
+        if limiter.fail_allocation() || self.elements.try_reserve_exact(delta as usize).is_err() {
+            return notify_limiter(limiter);
+        }
         self.elements.resize(desired as usize, init);
         Ok(current)
     }
@@ -337,6 +378,32 @@ impl TableEntity {
         Ok(())
     }
 
+    /// Sets the elements of this [`Table`] starting at `dst_index` to the `values`.
+    ///
+    /// # Errors
+    ///
+    /// - If any of the `values` does not match the [`Table`] element type.
+    /// - If the range of elements to be set is out of bounds.
+    pub fn init_from_slice(&mut self, dst_index: u32, values: &[Value]) -> Result<(), TableError> {
+        let table_type = self.ty();
+        for value in values {
+            table_type.matches_element_type(value.ty())?;
+        }
+        let current = self.size();
+        let dst = self
+            .elements
+            .get_mut(dst_index as usize..)
+            .and_then(|elements| elements.get_mut(..values.len()))
+            .ok_or(TableError::AccessOutOfBounds {
+                current,
+                offset: dst_index,
+            })?;
+        for (dst, value) in dst.iter_mut().zip(values) {
+            *dst = value.clone().into();
+        }
+        Ok(())
+    }
+
     /// Initialize `len` elements from `src_element[src_index..]` into
     /// `dst_table[dst_index..]`.
     ///
@@ -641,13 +708,18 @@ impl Table {
         let table = inner.resolve_table_mut(self);
         let current = table.size();
         let maximum = table.ty().maximum().unwrap_or(u32::MAX);
-        table
-            .grow(delta, init, None, &mut limiter)
-            .map_err(|_| TableError::GrowOutOfBounds {
+        let old_size = table.grow(delta, init, None, &mut limiter).map_err(|_| {
+            TableError::GrowOutOfBounds {
                 maximum,
                 current,
                 delta,
-            })
+            }
+        })?;
+        let new_size = table.size();
+        if delta != 0 {
+            inner.notify_table(*self, TableEvent::Grow { old_size, new_size });
+        }
+        Ok(old_size)
     }
 
     /// Returns the [`Table`] element value at `index`.
@@ -684,6 +756,36 @@ impl Table {
             .set(index, value)
     }
 
+    /// Sets the elements of this [`Table`] starting at `dst_index` to the `values`.
+    ///
+    /// # Note
+    ///
+    /// - This is the host-side counterpart of the Wasm `table.init` instruction
+    ///   and more efficient than calling [`Table::set`] for each element,
+    ///   e.g. when populating large function tables for dynamic linking.
+    /// - Either all `values` are set or none of them.
+    ///
+    /// # Errors
+    ///
+    /// - If any of the `values` does not match the [`Table`] element type.
+    /// - If the range of elements to be set is out of bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub fn init_from_slice(
+        &self,
+        mut ctx: impl AsContextMut,
+        dst_index: u32,
+        values: &[Value],
+    ) -> Result<(), TableError> {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .resolve_table_mut(self)
+            .init_from_slice(dst_index, values)
+    }
+
     /// Returns `true` if `lhs` and `rhs` [`Table`] refer to the same entity.
     ///
     /// # Note
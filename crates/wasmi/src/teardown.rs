@@ -0,0 +1,82 @@
+use crate::{
+    func::{TrampolineEntity, TrampolineIdx},
+    store::StoreInner,
+};
+use wasmi_arena::Arena;
+
+/// Incrementally deallocates the entities of a [`Store`] with bounded latency.
+///
+/// Created via [`Store::into_teardown`].
+///
+/// # Note
+///
+/// - Dropping a [`Store`] deallocates all of its instances, linear memories, tables
+///   and other entities at once which may take milliseconds for large [`Store`]s.
+/// - Instead [`StoreTeardown::step`] deallocates at most a given number of entities
+///   per call so that the teardown can be spread over multiple calls, for example
+///   in between requests. Alternatively a [`StoreTeardown`] can be moved to a
+///   background thread for reclamation if its user provided data is [`Send`].
+/// - Linear memories and tables are deallocated first since they usually own most memory.
+/// - Dropping a [`StoreTeardown`] deallocates all of its remaining entities at once.
+///
+/// [`Store`]: crate::Store
+/// [`Store::into_teardown`]: crate::Store::into_teardown
+#[derive(Debug)]
+pub struct StoreTeardown<T> {
+    /// The remaining data of the torn down [`Store`] not associated to the host state.
+    ///
+    /// [`Store`]: crate::Store
+    inner: StoreInner,
+    /// The remaining host function trampolines of the torn down [`Store`].
+    ///
+    /// [`Store`]: crate::Store
+    trampolines: Arena<TrampolineIdx, TrampolineEntity<T>>,
+    /// The user provided host data of the torn down [`Store`] if not yet dropped.
+    ///
+    /// [`Store`]: crate::Store
+    data: Option<T>,
+}
+
+impl<T> StoreTeardown<T> {
+    /// Creates a new [`StoreTeardown`] from the parts of a [`Store`].
+    ///
+    /// [`Store`]: crate::Store
+    pub(crate) fn new(
+        inner: StoreInner,
+        trampolines: Arena<TrampolineIdx, TrampolineEntity<T>>,
+        data: T,
+    ) -> Self {
+        Self {
+            inner,
+            trampolines,
+            data: Some(data),
+        }
+    }
+
+    /// Returns the number of entities that have not yet been deallocated.
+    ///
+    /// # Note
+    ///
+    /// The user provided host data counts as a single entity.
+    pub fn remaining(&self) -> usize {
+        self.inner.len_entities() + self.trampolines.len() + usize::from(self.data.is_some())
+    }
+
+    /// Returns `true` if all entities have been deallocated.
+    pub fn is_finished(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Deallocates at most `max_entities` entities and returns `true` once all have been deallocated.
+    pub fn step(&mut self, max_entities: usize) -> bool {
+        for _ in 0..max_entities {
+            let progressed = self.data.take().is_some()
+                || self.inner.pop_entity()
+                || self.trampolines.pop().is_some();
+            if !progressed {
+                break;
+            }
+        }
+        self.is_finished()
+    }
+}
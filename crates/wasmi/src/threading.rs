@@ -0,0 +1,70 @@
+//! Helpers to run Wasm instances on native threads.
+//!
+//! # Note
+//!
+//! Wasmi does not support the Wasm `threads` proposal and thus has no shared
+//! linear memories. Instances spawned via [`spawn_instance`] share state with
+//! each other only through the host functions defined in their [`Linker`].
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//! use wasmi::{threading::spawn_instance, Engine, Linker, Module};
+//!
+//! let engine = Engine::default();
+//! let wasm = wat::parse_str(r#"
+//!     (module
+//!         (func (export "run") (param i32) (result i32)
+//!             (i32.add (local.get 0) (i32.const 1))
+//!         )
+//!     )
+//! "#).unwrap();
+//! let module = Arc::new(Module::new(&engine, &wasm[..]).unwrap());
+//! let linker = <Linker<()>>::new(&engine);
+//! let thread = spawn_instance::<_, i32, i32>(&linker, &module, (), "run", 41);
+//! assert_eq!(thread.join().unwrap().unwrap(), 42);
+//! ```
+
+use crate::{Error, Linker, Module, Store, WasmParams, WasmResults};
+use std::{
+    string::String,
+    sync::Arc,
+    thread::{self, JoinHandle},
+};
+
+/// Instantiates the shared `module` in a new [`Store`] on a new native thread and calls its `entry` export.
+///
+/// # Note
+///
+/// - The new [`Store`] is created with `data` as its host state and uses the [`Engine`] of `module`.
+/// - The imports of `module` are resolved using a clone of `linker`.
+/// - The `entry` export must be a function of type `Params -> Results`.
+///
+/// Returns a [`JoinHandle`] that yields the results of the `entry` call or the
+/// [`Error`] of the instantiation or call once joined.
+///
+/// [`Engine`]: crate::Engine
+pub fn spawn_instance<T, Params, Results>(
+    linker: &Linker<T>,
+    module: &Arc<Module>,
+    data: T,
+    entry: &str,
+    params: Params,
+) -> JoinHandle<Result<Results, Error>>
+where
+    T: Send + 'static,
+    Params: WasmParams + Send + 'static,
+    Results: WasmResults + Send + 'static,
+{
+    let linker = linker.clone();
+    let module = module.clone();
+    let entry = String::from(entry);
+    thread::spawn(move || {
+        let mut store = Store::new(module.engine(), data);
+        let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+        instance
+            .get_typed_func::<Params, Results>(&store, &entry)?
+            .call(&mut store, params)
+    })
+}
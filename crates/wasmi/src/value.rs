@@ -1,4 +1,6 @@
 use crate::{ExternRef, Func, FuncRef};
+use alloc::vec::Vec;
+use core::{fmt, fmt::Display};
 use wasmi_core::{UntypedValue, ValueType, F32, F64};
 
 /// Untyped instances that allow to be typed.
@@ -185,3 +187,235 @@ impl From<ExternRef> for Value {
         Self::ExternRef(externref)
     }
 }
+
+impl From<u32> for Value {
+    #[inline]
+    fn from(val: u32) -> Self {
+        Self::I32(val as i32)
+    }
+}
+
+impl From<u64> for Value {
+    #[inline]
+    fn from(val: u64) -> Self {
+        Self::I64(val as i64)
+    }
+}
+
+impl From<f32> for Value {
+    #[inline]
+    fn from(val: f32) -> Self {
+        Self::F32(val.into())
+    }
+}
+
+impl From<f64> for Value {
+    #[inline]
+    fn from(val: f64) -> Self {
+        Self::F64(val.into())
+    }
+}
+
+/// An error that may occur upon converting [`Value`]s into Rust types.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValueError {
+    /// The [`Value`] has a different [`ValueType`] than the Rust type.
+    TypeMismatch {
+        /// The [`ValueType`] of the Rust type.
+        expected: ValueType,
+        /// The [`ValueType`] of the converted [`Value`].
+        actual: ValueType,
+    },
+    /// The number of [`Value`]s does not match the number of elements of the Rust type.
+    LenMismatch {
+        /// The number of elements of the Rust type.
+        expected: usize,
+        /// The number of converted [`Value`]s.
+        actual: usize,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValueError {}
+
+impl Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TypeMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "expected a value of type {expected:?} but found {actual:?}"
+                )
+            }
+            Self::LenMismatch { expected, actual } => {
+                write!(f, "expected {expected} values but found {actual}")
+            }
+        }
+    }
+}
+
+macro_rules! impl_try_from_value {
+    ( $( $rust_type:ty = $variant:ident );* $(;)? ) => {
+        $(
+            impl TryFrom<Value> for $rust_type {
+                type Error = ValueError;
+
+                #[inline]
+                fn try_from(value: Value) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::$variant(value) => Ok(value.into()),
+                        value => Err(ValueError::TypeMismatch {
+                            expected: ValueType::$variant,
+                            actual: value.ty(),
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+impl_try_from_value! {
+    i32 = I32;
+    i64 = I64;
+    F32 = F32;
+    f32 = F32;
+    F64 = F64;
+    f64 = F64;
+    FuncRef = FuncRef;
+    ExternRef = ExternRef;
+}
+
+impl TryFrom<Value> for u32 {
+    type Error = ValueError;
+
+    #[inline]
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        i32::try_from(value).map(|value| value as u32)
+    }
+}
+
+impl TryFrom<Value> for u64 {
+    type Error = ValueError;
+
+    #[inline]
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        i64::try_from(value).map(|value| value as u64)
+    }
+}
+
+/// Rust types that can be converted into a sequence of [`Value`]s.
+///
+/// # Note
+///
+/// This is implemented for tuples and arrays of types that convert into [`Value`]
+/// and allows to call [`Func::call`] without manually constructing [`Value`]s.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{IntoValues, Value};
+/// let values = (1_i32, 2.0_f64).into_values();
+/// assert_eq!(values[0].i32(), Some(1));
+/// assert_eq!(values[1].f64().map(f64::from), Some(2.0));
+/// ```
+pub trait IntoValues {
+    /// Converts `self` into a sequence of [`Value`]s.
+    fn into_values(self) -> Vec<Value>;
+}
+
+/// Rust types that can be created from a sequence of [`Value`]s.
+///
+/// # Note
+///
+/// This is implemented for tuples and arrays of types that can be converted from
+/// [`Value`] and allows to read the results of [`Func::call`] with static types.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{FromValues, Value};
+/// let values = [Value::I32(1), Value::I64(2)];
+/// let (a, b) = <(i32, i64)>::from_values(&values).unwrap();
+/// assert_eq!((a, b), (1, 2));
+/// ```
+pub trait FromValues: Sized {
+    /// Creates `Self` from the sequence of `values`.
+    ///
+    /// # Errors
+    ///
+    /// - If the number of `values` does not match the number of elements of `Self`.
+    /// - If any of the `values` has a mismatching [`ValueType`].
+    fn from_values(values: &[Value]) -> Result<Self, ValueError>;
+}
+
+impl<T, const N: usize> IntoValues for [T; N]
+where
+    T: Into<Value>,
+{
+    fn into_values(self) -> Vec<Value> {
+        self.into_iter().map(Into::into).collect()
+    }
+}
+
+impl<T, const N: usize> FromValues for [T; N]
+where
+    T: TryFrom<Value, Error = ValueError>,
+{
+    fn from_values(values: &[Value]) -> Result<Self, ValueError> {
+        if values.len() != N {
+            return Err(ValueError::LenMismatch {
+                expected: N,
+                actual: values.len(),
+            });
+        }
+        let values = values
+            .iter()
+            .cloned()
+            .map(T::try_from)
+            .collect::<Result<Vec<T>, ValueError>>()?;
+        Ok(values
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("the number of values has already been checked")))
+    }
+}
+
+macro_rules! impl_values_for_tuple {
+    ( $n:literal $( $tuple:ident )* ) => {
+        impl<$($tuple),*> IntoValues for ($($tuple,)*)
+        where
+            $(
+                $tuple: Into<Value>
+            ),*
+        {
+            #[allow(non_snake_case)]
+            fn into_values(self) -> Vec<Value> {
+                let ($($tuple,)*) = self;
+                vec![$(
+                    <$tuple as Into<Value>>::into($tuple)
+                ),*]
+            }
+        }
+
+        impl<$($tuple),*> FromValues for ($($tuple,)*)
+        where
+            $(
+                $tuple: TryFrom<Value, Error = ValueError>
+            ),*
+        {
+            #[allow(non_snake_case)]
+            fn from_values(values: &[Value]) -> Result<Self, ValueError> {
+                if let [$($tuple),*] = values {
+                    return Ok((
+                        $( <$tuple as TryFrom<Value>>::try_from($tuple.clone())?, )*
+                    ));
+                }
+                Err(ValueError::LenMismatch {
+                    expected: $n,
+                    actual: values.len(),
+                })
+            }
+        }
+    };
+}
+for_each_tuple!(impl_values_for_tuple);
@@ -0,0 +1,85 @@
+//! A timer thread that drives epoch based interruption.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//! use wasmi::{watchdog::Watchdog, Config, Engine, Store};
+//!
+//! let mut config = Config::default();
+//! config.epoch_interruption(true);
+//! let engine = Engine::new(&config);
+//! let _watchdog = Watchdog::new(&engine, Duration::from_millis(10));
+//! let mut store = Store::new(&engine, ());
+//! // Calls into `store` now trap after roughly 100ms.
+//! store.set_epoch_deadline(10);
+//! ```
+
+use crate::Engine;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// A timer thread that increments the epoch of an [`Engine`] every tick.
+///
+/// # Note
+///
+/// Combined with [`Config::epoch_interruption`] and [`Store::set_epoch_deadline`]
+/// this provides call timeouts for all [`Store`]s of the [`Engine`].
+/// The timer thread is stopped and joined when the [`Watchdog`] is dropped.
+///
+/// [`Config::epoch_interruption`]: crate::Config::epoch_interruption
+/// [`Store`]: crate::Store
+/// [`Store::set_epoch_deadline`]: crate::Store::set_epoch_deadline
+#[derive(Debug)]
+pub struct Watchdog {
+    /// The duration between two epoch increments.
+    tick: Duration,
+    /// Set to `true` to stop the timer thread.
+    stop: Arc<AtomicBool>,
+    /// The handle to the timer thread.
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+    /// Spawns a timer thread that calls [`Engine::increment_epoch`] every `tick`.
+    pub fn new(engine: &Engine, tick: Duration) -> Self {
+        let engine = engine.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = thread::spawn({
+            let stop = stop.clone();
+            move || {
+                while !stop.load(Ordering::Acquire) {
+                    thread::park_timeout(tick);
+                    engine.increment_epoch();
+                }
+            }
+        });
+        Self {
+            tick,
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Returns the duration between two epoch increments of the [`Watchdog`].
+    pub fn tick(&self) -> Duration {
+        self.tick
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            // The timer thread never panics so we can ignore the result.
+            let _ = handle.join();
+        }
+    }
+}
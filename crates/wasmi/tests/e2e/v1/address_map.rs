@@ -0,0 +1,86 @@
+//! Tests for mapping Wasmi bytecode back to Wasm bytecode offsets via `Engine::address_map`.
+
+use wasmi::{AddressMap, CompilationMode, Config, Engine, Linker, Module, Store};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "f" (func))
+        (func (export "add") (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+/// Returns the Wasm binary of [`WAT`] and the offsets of `i32.add` and its function `end`.
+fn wasm_and_offsets() -> (Vec<u8>, u32, u32) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    // Note: the body of the `add` function ends with `i32.add` (0x6A) followed by `end` (0x0B).
+    let add = wasm
+        .windows(2)
+        .rposition(|window| window == [0x6A, 0x0B])
+        .unwrap() as u32;
+    (wasm, add, add + 1)
+}
+
+fn engine(mode: CompilationMode, address_maps: bool) -> Engine {
+    let mut config = Config::default();
+    config
+        .compilation_mode(mode)
+        .generate_address_maps(address_maps);
+    Engine::new(&config)
+}
+
+fn assert_address_map(map: &AddressMap, add: u32, end: u32) {
+    let offsets = map.iter().map(|(_, offset)| offset).collect::<Vec<_>>();
+    assert_eq!(offsets.first(), Some(&add));
+    assert_eq!(offsets.last(), Some(&end));
+    assert_eq!(map.wasm_offset(0), Some(add));
+    let (last_instr, _) = map.iter().last().unwrap();
+    assert_eq!(map.wasm_offset(last_instr + 10), Some(end));
+}
+
+#[test]
+fn eager_address_map() {
+    let (wasm, add, end) = wasm_and_offsets();
+    let engine = engine(CompilationMode::Eager, true);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let map = engine.address_map(&module, 1).unwrap();
+    assert_address_map(&map, add, end);
+    // Imported and non-existing functions have no address map.
+    assert!(engine.address_map(&module, 0).is_none());
+    assert!(engine.address_map(&module, 2).is_none());
+}
+
+#[test]
+fn disabled_address_map() {
+    let (wasm, _, _) = wasm_and_offsets();
+    let engine = engine(CompilationMode::Eager, false);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    assert!(engine.address_map(&module, 1).is_none());
+}
+
+#[test]
+fn lazy_address_map() {
+    let (wasm, add, end) = wasm_and_offsets();
+    let engine = engine(CompilationMode::Lazy, true);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    assert!(engine.address_map(&module, 1).is_none());
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "f", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    assert_eq!(func.call(&mut store, (1, 2)).unwrap(), 3);
+    let map = engine.address_map(&module, 1).unwrap();
+    assert_address_map(&map, add, end);
+    // Lazily and eagerly compiled functions have equal address maps.
+    let eager_engine = self::engine(CompilationMode::Eager, true);
+    let eager_module = Module::new(&eager_engine, &wasm[..]).unwrap();
+    assert_eq!(eager_engine.address_map(&eager_module, 1), Some(map));
+}
@@ -0,0 +1,162 @@
+//! Tests for injected allocation failures via `Config::alloc_failures`.
+
+use wasmi::{
+    core::ValueType,
+    errors::{ErrorKind, TranslationError},
+    AllocFailures,
+    CompilationMode,
+    Config,
+    Engine,
+    Error,
+    Linker,
+    Module,
+    Store,
+    Table,
+    TableType,
+    Value,
+};
+
+/// Wasm module with two functions, a linear memory and a table.
+const WAT: &str = r#"
+    (module
+        (memory 1)
+        (table 1 funcref)
+        (func (export "grow_memory") (param i32) (result i32)
+            (memory.grow (local.get 0))
+        )
+        (func (export "grow_table") (param i32) (result i32)
+            (table.grow (ref.null func) (local.get 0))
+        )
+    )
+"#;
+
+/// Creates an [`Engine`] that fails allocations as determined by `failures`.
+fn engine(failures: AllocFailures) -> Engine {
+    let mut config = Config::default();
+    config.alloc_failures(failures);
+    Engine::new(&config)
+}
+
+/// Compiles and instantiates [`WAT`] and calls `grow_memory` and `grow_table`.
+///
+/// Returns the results of both calls.
+fn run(engine: &Engine) -> Result<(i32, i32), Error> {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(engine, &wasm[..])?;
+    let mut store = Store::new(engine, ());
+    let instance = <Linker<()>>::new(engine)
+        .instantiate(&mut store, &module)?
+        .start(&mut store)?;
+    let grow_memory = instance.get_typed_func::<i32, i32>(&store, "grow_memory")?;
+    let grow_table = instance.get_typed_func::<i32, i32>(&store, "grow_table")?;
+    let memory = grow_memory.call(&mut store, 1)?;
+    let table = grow_table.call(&mut store, 1)?;
+    Ok((memory, table))
+}
+
+/// Returns the number of allocations subject to failure injection of [`run`].
+fn count_allocs() -> u64 {
+    let engine = engine(AllocFailures::Nth(u64::MAX));
+    assert_eq!(run(&engine).unwrap(), (1, 1));
+    engine.alloc_count().unwrap()
+}
+
+#[test]
+fn disabled_by_default() {
+    assert_eq!(Engine::default().alloc_count(), None);
+}
+
+#[test]
+fn translation_fails() {
+    let error = run(&engine(AllocFailures::Nth(1))).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Translation(TranslationError::AllocationFailed)
+    ));
+    assert_eq!(error.func_index(), Some(1));
+}
+
+#[test]
+fn lazy_translation_fails() {
+    let mut config = Config::default();
+    // The functions are compiled upon their first call after the creation
+    // of the table and linear memory upon instantiation.
+    config.alloc_failures(AllocFailures::Nth(2));
+    config.compilation_mode(CompilationMode::Lazy);
+    let engine = Engine::new(&config);
+    let error = run(&engine).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Translation(TranslationError::AllocationFailed)
+    ));
+    assert_eq!(error.func_index(), Some(0));
+    assert_eq!(engine.alloc_count(), Some(3));
+}
+
+#[test]
+fn every_allocation_fails_gracefully() {
+    let count = count_allocs();
+    // 2 compiled functions, table and memory creation as well as their growth.
+    assert_eq!(count, 6);
+    let results = (0..count)
+        .map(|n| run(&engine(AllocFailures::Nth(n))))
+        .collect::<Vec<_>>();
+    for result in &results[..2] {
+        assert!(matches!(
+            result.as_ref().unwrap_err().kind(),
+            ErrorKind::Translation(TranslationError::AllocationFailed)
+        ));
+    }
+    // The creation of the table and linear memory fails upon instantiation.
+    assert!(matches!(
+        results[2].as_ref().unwrap_err().kind(),
+        ErrorKind::Instantiation(_)
+    ));
+    assert!(matches!(
+        results[3].as_ref().unwrap_err().kind(),
+        ErrorKind::Memory(_)
+    ));
+    // Wasm observes failed growth as `-1`.
+    assert_eq!(results[4].as_ref().unwrap(), &(-1, 1));
+    assert_eq!(results[5].as_ref().unwrap(), &(1, -1));
+}
+
+#[test]
+fn host_table_grow_fails() {
+    let engine = engine(AllocFailures::Nth(1));
+    let mut store = Store::new(&engine, ());
+    let ty = TableType::new(ValueType::ExternRef, 0, None);
+    let init = Value::default(ValueType::ExternRef);
+    let table = Table::new(&mut store, ty, init.clone()).unwrap();
+    assert!(table.grow(&mut store, 1, init.clone()).is_err());
+    assert_eq!(table.grow(&mut store, 1, init).unwrap(), 0);
+    assert_eq!(engine.alloc_count(), Some(3));
+}
+
+#[test]
+fn random_is_deterministic() {
+    let count = count_allocs();
+    let outcomes = |seed: u64| {
+        let engine = engine(AllocFailures::Random { seed, one_in: 2 });
+        let result = run(&engine).map_err(|error| error.to_string());
+        (result, engine.alloc_count())
+    };
+    for seed in 0..10 {
+        assert_eq!(outcomes(seed), outcomes(seed));
+    }
+    // With a probability of 1/2 some seeds fail and some succeed.
+    assert!((0..10).any(|seed| outcomes(seed).0.is_err()));
+    assert!((0..10).any(|seed| outcomes(seed).1 == Some(count)));
+}
+
+#[test]
+fn random_always_or_never() {
+    let never = engine(AllocFailures::Random { seed: 0, one_in: 0 });
+    assert_eq!(run(&never).unwrap(), (1, 1));
+    let always = engine(AllocFailures::Random { seed: 0, one_in: 1 });
+    assert!(matches!(
+        run(&always).unwrap_err().kind(),
+        ErrorKind::Translation(TranslationError::AllocationFailed)
+    ));
+    assert_eq!(always.alloc_count(), Some(1));
+}
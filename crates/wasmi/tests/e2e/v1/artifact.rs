@@ -0,0 +1,281 @@
+//! Tests for precompiled Wasmi artifacts.
+
+use std::thread;
+use wasmi::{
+    errors::{ArtifactError, ErrorKind},
+    ArtifactHash,
+    CompilationMode,
+    Config,
+    Engine,
+    FuncBody,
+    Linker,
+    Module,
+    Store,
+};
+
+fn wasm() -> Vec<u8> {
+    wat::parse_str(
+        r#"
+        (module
+            (func (export "add") (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )
+        "#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn deserialize_works() {
+    let engine = Engine::default();
+    let artifact = Module::precompile(&engine, &wasm()).unwrap();
+    let module = unsafe { Module::deserialize(&engine, &artifact) }.unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+}
+
+#[test]
+fn deserialize_shared_works() {
+    for mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        let mut config = Config::default();
+        config.compilation_mode(mode);
+        let engine = Engine::new(&config);
+        let artifact = Module::precompile(&engine, &wasm()).unwrap();
+        // Emulates an artifact that is mapped read-only for the lifetime of the process.
+        let artifact: &'static [u8] = Box::leak(artifact.into_boxed_slice());
+        let module = unsafe { Module::deserialize_shared(&engine, artifact) }.unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = <Linker<()>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let add = instance
+            .get_typed_func::<(i32, i32), i32>(&store, "add")
+            .unwrap();
+        assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+    }
+}
+
+#[test]
+fn deserialize_rejects_mismatching_features() {
+    let mut config = Config::default();
+    config.wasm_bulk_memory(false);
+    let artifact = Module::precompile(&Engine::new(&config), &wasm()).unwrap();
+    let error = unsafe { Module::deserialize(&Engine::default(), &artifact) }.unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Artifact(_)));
+}
+
+#[test]
+fn deserialize_rejects_plain_wasm() {
+    let engine = Engine::default();
+    let error = unsafe { Module::deserialize(&engine, &wasm()) }.unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Artifact(_)));
+}
+
+/// Returns a [`Config`] that computes artifact hashes.
+fn hashing_config() -> Config {
+    let mut config = Config::default();
+    config.artifact_hashes(true);
+    config
+}
+
+#[test]
+fn precompile_is_reproducible() {
+    let config = hashing_config();
+    let expected = Module::precompile(&Engine::new(&config), &wasm()).unwrap();
+    // The state of the engine does not influence the artifact.
+    let engine = Engine::new(&config);
+    Module::new(&engine, &wasm()[..]).unwrap();
+    assert_eq!(Module::precompile(&engine, &wasm()).unwrap(), expected);
+    // Precompiling concurrently yields the same artifacts.
+    let artifacts = (0..4)
+        .map(|_| {
+            let engine = engine.clone();
+            thread::spawn(move || Module::precompile(&engine, &wasm()).unwrap())
+        })
+        .collect::<Vec<_>>();
+    for artifact in artifacts {
+        assert_eq!(artifact.join().unwrap(), expected);
+    }
+}
+
+#[test]
+fn artifact_hash_matches_precompile() {
+    for mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        let mut config = hashing_config();
+        config.compilation_mode(mode);
+        let engine = Engine::new(&config);
+        let artifact = Module::precompile(&engine, &wasm()).unwrap();
+        let expected = ArtifactHash::of(&artifact);
+        let module = Module::new(&engine, &wasm()[..]).unwrap();
+        assert_eq!(module.artifact_hash(), Some(expected));
+        let module = unsafe { Module::new_unchecked(&engine, &wasm()[..]) }.unwrap();
+        assert_eq!(module.artifact_hash(), Some(expected));
+        let module = unsafe { Module::deserialize(&engine, &artifact) }.unwrap();
+        assert_eq!(module.artifact_hash(), Some(expected));
+    }
+}
+
+#[test]
+fn artifact_hash_depends_on_config() {
+    let wasm = wasm();
+    let hash = |config: &Config| {
+        Module::new(&Engine::new(config), &wasm[..])
+            .unwrap()
+            .artifact_hash()
+            .unwrap()
+    };
+    let mut config = hashing_config();
+    assert_eq!(hash(&config), hash(&config));
+    config.wasm_tail_call(true);
+    assert_ne!(hash(&config), hash(&hashing_config()));
+}
+
+#[test]
+fn artifact_hash_is_sha256() {
+    let artifact = b"abc";
+    let hash = ArtifactHash::of(artifact);
+    assert_eq!(
+        hash.to_string(),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(hash.as_bytes()[0], 0xBA);
+}
+
+#[test]
+fn artifact_hash_disabled() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm()[..]).unwrap();
+    assert_eq!(module.artifact_hash(), None);
+    let engine = Engine::new(&hashing_config());
+    let module = Module::new_with_transform(&engine, &wasm()[..], |_: FuncBody| Ok(None)).unwrap();
+    assert_eq!(module.artifact_hash(), None);
+}
+
+/// Returns a Wasm module that uses most kinds of Wasm module items.
+fn rich_wasm() -> Vec<u8> {
+    wat::parse_str(
+        r#"
+        (module $rich
+            (import "env" "double" (func $double (param i32) (result i32)))
+            (import "env" "base" (global $base i32))
+            (memory 1 2)
+            (table 2 funcref)
+            (global $offset (mut i32) (i32.add (global.get $base) (i32.const 10)))
+            (elem (i32.const 0) $double $inc)
+            (data (i32.const 16) "\2A\00\00\00")
+            (type $unop (func (param i32) (result i32)))
+            (func $inc (param i32) (result i32)
+                (i32.add (local.get 0) (i32.const 1))
+            )
+            (func $init
+                (global.set $offset (i32.add (global.get $offset) (i32.const 1)))
+            )
+            (func (export "run") (param i32) (result i64)
+                (i64.add
+                    (i64.extend_i32_u
+                        (call_indirect (type $unop) (local.get 0) (i32.const 0))
+                    )
+                    (i64.const 0x1_0000_0000)
+                )
+            )
+            (func (export "load") (result i32)
+                (i32.add (i32.load (i32.const 16)) (global.get $offset))
+            )
+            (start $init)
+        )
+        "#,
+    )
+    .unwrap()
+}
+
+/// Instantiates the [`rich_wasm`] `module` and checks its exports.
+fn assert_rich_module(engine: &Engine, module: &Module) {
+    let mut store = Store::new(engine, ());
+    let mut linker = <Linker<()>>::new(engine);
+    linker
+        .func_wrap("env", "double", |value: i32| value * 2)
+        .unwrap();
+    linker
+        .define(
+            "env",
+            "base",
+            wasmi::Global::new(&mut store, wasmi::Value::I32(100), wasmi::Mutability::Const),
+        )
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i64>(&store, "run").unwrap();
+    let load = instance.get_typed_func::<(), i32>(&store, "load").unwrap();
+    assert_eq!(run.call(&mut store, 5).unwrap(), 0x1_0000_000A);
+    assert_eq!(load.call(&mut store, ()).unwrap(), 42 + 111);
+}
+
+#[test]
+fn serialize_works() {
+    for mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        let mut config = Config::default();
+        config.compilation_mode(mode);
+        config.wasm_extended_const(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &rich_wasm()[..]).unwrap();
+        let artifact = module.serialize().unwrap();
+        let module = unsafe { Module::deserialize(&engine, &artifact) }.unwrap();
+        assert_eq!(module.name(), Some("rich"));
+        assert_rich_module(&engine, &module);
+        // Serializing a deserialized module yields the same artifact.
+        assert_eq!(module.serialize().unwrap(), artifact);
+        // Compiled artifacts can be loaded by other engines with the same settings.
+        let other = Engine::new(&config);
+        let module = unsafe { Module::deserialize(&other, &artifact) }.unwrap();
+        assert_rich_module(&other, &module);
+    }
+}
+
+#[test]
+fn serialize_rejects_mismatching_codegen() {
+    let module = Module::new(&Engine::default(), &wasm()[..]).unwrap();
+    let artifact = module.serialize().unwrap();
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let error = unsafe { Module::deserialize(&Engine::new(&config), &artifact) }.unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Artifact(ArtifactError::ConfigMismatch)
+    ));
+}
+
+#[test]
+fn deserialize_rejects_truncated_compiled_artifact() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm()[..]).unwrap();
+    let artifact = module.serialize().unwrap();
+    let error =
+        unsafe { Module::deserialize(&engine, &artifact[..artifact.len() - 1]) }.unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Artifact(_)));
+}
@@ -0,0 +1,120 @@
+//! Tests for capturing Wasm backtraces from within host functions via `Caller::backtrace`.
+
+use wasmi::{Caller, Config, Engine, Func, Linker, Module, Store, WasmBacktrace};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "trace" (func $trace))
+        (func $inner
+            (call $trace)
+        )
+        (func $outer (export "outer")
+            (call $inner)
+        )
+    )
+"#;
+
+/// Runs the exported `outer` function of `wat` and returns the backtrace captured by `trace`.
+fn capture(wat: &str, address_maps: bool) -> (Vec<u8>, WasmBacktrace) {
+    let wasm = wat::parse_str(wat).unwrap();
+    let mut config = Config::default();
+    config.generate_address_maps(address_maps);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = <Store<Option<WasmBacktrace>>>::new(&engine, None);
+    let mut linker = <Linker<Option<WasmBacktrace>>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "trace",
+            |mut caller: Caller<Option<WasmBacktrace>>| {
+                *caller.data_mut() = Some(caller.backtrace());
+            },
+        )
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<(), ()>(&store, "outer")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    let backtrace = store.data_mut().take().unwrap();
+    (wasm, backtrace)
+}
+
+#[test]
+fn backtrace_frames() {
+    let (wasm, backtrace) = capture(WAT, true);
+    let frames = backtrace.frames();
+    assert_eq!(backtrace.len(), 2);
+    assert_eq!(frames[0].func_index(), 1);
+    assert_eq!(frames[0].func_name(), Some("inner"));
+    assert_eq!(frames[1].func_index(), 2);
+    assert_eq!(frames[1].func_name(), Some("outer"));
+    // The offsets point to the pending `call` instructions of each frame.
+    let inner = frames[0].wasm_offset().unwrap() as usize;
+    assert_eq!(&wasm[inner..inner + 2], &[0x10, 0x00]);
+    let outer = frames[1].wasm_offset().unwrap() as usize;
+    assert_eq!(&wasm[outer..outer + 2], &[0x10, 0x01]);
+    assert_eq!(
+        backtrace.to_string(),
+        format!("   0: inner @ {inner:#x}\n   1: outer @ {outer:#x}\n"),
+    );
+}
+
+#[test]
+fn backtrace_without_address_maps() {
+    let (_, backtrace) = capture(WAT, false);
+    assert_eq!(backtrace.len(), 2);
+    assert!(backtrace
+        .frames()
+        .iter()
+        .all(|frame| frame.wasm_offset().is_none()));
+    assert_eq!(backtrace.to_string(), "   0: inner\n   1: outer\n");
+}
+
+#[test]
+fn backtrace_without_names() {
+    let wat = r#"
+        (module
+            (import "env" "trace" (func))
+            (func
+                (call 0)
+            )
+            (func (export "outer")
+                (call 1)
+            )
+        )
+    "#;
+    let (_, backtrace) = capture(wat, false);
+    let frames = backtrace.frames();
+    assert_eq!(backtrace.len(), 2);
+    assert!(frames.iter().all(|frame| frame.func_name().is_none()));
+    assert_eq!(backtrace.to_string(), "   0: <func 1>\n   1: <func 2>\n");
+}
+
+#[test]
+fn backtrace_from_host() {
+    let engine = Engine::default();
+    let mut store = <Store<Option<WasmBacktrace>>>::new(&engine, None);
+    let trace = Func::wrap(&mut store, |mut caller: Caller<Option<WasmBacktrace>>| {
+        *caller.data_mut() = Some(caller.backtrace());
+    });
+    trace.call(&mut store, &[], &mut []).unwrap();
+    let backtrace = store.data_mut().take().unwrap();
+    assert!(backtrace.is_empty());
+}
+
+#[test]
+fn module_func_names() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&Engine::default(), &wasm[..]).unwrap();
+    assert_eq!(module.func_name(0), Some("trace"));
+    assert_eq!(module.func_name(1), Some("inner"));
+    assert_eq!(module.func_name(2), Some("outer"));
+    assert_eq!(module.func_name(3), None);
+}
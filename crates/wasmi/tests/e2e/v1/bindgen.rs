@@ -0,0 +1,126 @@
+//! Tests for the `bindgen!` macro.
+
+use wasmi::{bindgen, core::F64, Engine, Instance, Linker, Module, Store};
+
+mod calc {
+    wasmi::bindgen!(
+        world = "calculator",
+        inline = "
+            package test:calc@1.0.0;
+
+            interface host {
+                log: func(message: string, bytes: list<u8>);
+                check: func(flag: bool, small: s16) -> s32;
+            }
+
+            interface ops {
+                scale: func(value: f64, factor: f64) -> f64;
+            }
+
+            world calculator {
+                import host;
+                import next-id: func(type: u8) -> u64;
+                export ops;
+                export add-all: func(a: u32, b: u32, c: u32) -> u32;
+                export nop: func();
+            }
+
+            world other {
+                export noop: func();
+            }
+        ",
+    );
+}
+
+use calc::{Calculator, CalculatorImports, Host as CalcHost};
+
+#[derive(Debug, Default)]
+struct Host {
+    log: Vec<(String, Vec<u8>)>,
+    next_id: u64,
+}
+
+impl CalcHost for Host {
+    fn log(&mut self, message: &str, bytes: &[u8]) {
+        self.log.push((message.into(), bytes.into()));
+    }
+
+    fn check(&mut self, flag: bool, small: i16) -> i32 {
+        match flag {
+            true => i32::from(small),
+            false => -1,
+        }
+    }
+}
+
+impl CalculatorImports for Host {
+    fn next_id(&mut self, r#type: u8) -> u64 {
+        self.next_id += 1;
+        self.next_id * 1000 + u64::from(r#type)
+    }
+}
+
+const WAT: &str = r#"
+    (module
+        (import "test:calc/host@1.0.0" "log" (func $log (param i32 i32 i32 i32)))
+        (import "test:calc/host@1.0.0" "check" (func $check (param i32 i32) (result i32)))
+        (import "$root" "next-id" (func $next_id (param i32) (result i64)))
+        (memory (export "memory") 1)
+        (data (i32.const 0) "sum\01\02")
+        (global $id (export "id") (mut i64) (i64.const 0))
+        (global $check (export "check") (mut i32) (i32.const 0))
+        (func (export "test:calc/ops@1.0.0#scale") (param f64 f64) (result f64)
+            (f64.mul (local.get 0) (local.get 1))
+        )
+        (func (export "add-all") (param i32 i32 i32) (result i32)
+            (call $log (i32.const 0) (i32.const 3) (i32.const 3) (i32.const 2))
+            (global.set $id (call $next_id (i32.const 7)))
+            (global.set $check (call $check (i32.const 1) (i32.const -5)))
+            (i32.add (i32.add (local.get 0) (local.get 1)) (local.get 2))
+        )
+        (func (export "nop"))
+    )
+"#;
+
+fn setup() -> (Store<Host>, Instance) {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, Host::default());
+    let mut linker = <Linker<Host>>::new(&engine);
+    Calculator::add_to_linker(&mut linker).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn bindgen_works() {
+    let (mut store, instance) = setup();
+    let calc = Calculator::new(&store, &instance).unwrap();
+    assert_eq!(calc.add_all(&mut store, 1, 2, 3).unwrap(), 6);
+    assert_eq!(
+        calc.ops_scale(&mut store, F64::from(1.5), F64::from(4.0))
+            .unwrap(),
+        F64::from(6.0)
+    );
+    calc.nop(&mut store).unwrap();
+    assert_eq!(store.data().log, [(String::from("sum"), vec![1, 2])]);
+    let global =
+        |store: &Store<Host>, name: &str| instance.get_global(store, name).unwrap().get(store);
+    assert_eq!(global(&store, "id").i64(), Some(1007));
+    assert_eq!(global(&store, "check").i32(), Some(-5));
+}
+
+#[test]
+fn bindgen_missing_exports() {
+    bindgen!(inline = "world empty { export missing: func(); }");
+    let (store, instance) = setup();
+    assert!(Empty::new(&store, &instance).is_err());
+    // Worlds without imports define nothing in the `Linker`.
+    let mut linker = <Linker<()>>::new(store.engine());
+    Empty::add_to_linker(&mut linker).unwrap();
+}
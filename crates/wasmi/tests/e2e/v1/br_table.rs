@@ -0,0 +1,85 @@
+//! Tests for the execution of Wasm `br_table` instructions.
+
+use wasmi::{Engine, Linker, Module, Store, TypedFunc};
+
+/// Instantiates the Wasm `wat` and returns its exported `test` function.
+fn setup(wat: &str) -> (Store<()>, TypedFunc<i32, i32>) {
+    let wasm = wat::parse_str(wat).unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let test = instance.get_typed_func::<i32, i32>(&store, "test").unwrap();
+    (store, test)
+}
+
+#[test]
+fn sparse_br_table_works() {
+    let (mut store, test) = setup(
+        r#"
+        (module
+            (func (export "test") (param $index i32) (result i32)
+                (block
+                    (block
+                        (block
+                            (br_table
+                                0 0 0 0 0 0 0 0 0 0
+                                1 1 1 1 1 1 1 1 1 1
+                                0 0 0 0 0 0 0 0 0 0
+                                2
+                                (local.get $index)
+                            )
+                        )
+                        (return (i32.const 10))
+                    )
+                    (return (i32.const 20))
+                )
+                (return (i32.const 30))
+            )
+        )
+        "#,
+    );
+    let expected = |index: i32| match index {
+        0..=9 | 20..=29 => 10,
+        10..=19 => 20,
+        _ => 30,
+    };
+    for index in (-2..40).chain([i32::MIN, i32::MAX]) {
+        assert_eq!(test.call(&mut store, index).unwrap(), expected(index));
+    }
+}
+
+#[test]
+fn sparse_br_table_with_params_works() {
+    let (mut store, test) = setup(
+        r#"
+        (module
+            (func (export "test") (param $index i32) (result i32)
+                (block (result i32)
+                    (block (result i32)
+                        (i32.add (local.get $index) (i32.const 100))
+                        (br_table
+                            0 0 0 0 0 0 0 0
+                            1 1 1 1 1 1 1 1
+                            0
+                            (local.get $index)
+                        )
+                    )
+                    (return (i32.const 10))
+                )
+            )
+        )
+        "#,
+    );
+    for index in 0..20 {
+        let expected = match index {
+            8..=15 => index + 100,
+            _ => 10,
+        };
+        assert_eq!(test.call(&mut store, index).unwrap(), expected);
+    }
+}
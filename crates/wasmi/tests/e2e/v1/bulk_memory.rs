@@ -0,0 +1,107 @@
+//! Tests for the execution of Wasm bulk memory instructions.
+
+use wasmi::{core::TrapCode, Config, Engine, Instance, Linker, Module, Store};
+
+/// The Wasm module used by all tests.
+///
+/// Memory is initialized with the bytes `0..16` at address 0.
+const WAT: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (data (i32.const 0) "\00\01\02\03\04\05\06\07\08\09\0a\0b\0c\0d\0e\0f")
+        (data $passive "\aa\bb\cc\dd")
+        (func (export "copy") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.copy (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "fill") (param $dst i32) (param $value i32) (param $len i32)
+            (memory.fill (local.get $dst) (local.get $value) (local.get $len))
+        )
+        (func (export "init") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.init $passive (local.get $dst) (local.get $src) (local.get $len))
+        )
+    )
+"#;
+
+/// Instantiates [`WAT`] with the given [`Config`].
+fn setup(config: &Config) -> (Store<()>, Instance) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `name` of `instance` with `params`.
+fn call(
+    store: &mut Store<()>,
+    instance: Instance,
+    name: &str,
+    params: (i32, i32, i32),
+) -> Result<(), wasmi::Error> {
+    instance
+        .get_typed_func::<(i32, i32, i32), ()>(&*store, name)
+        .unwrap()
+        .call(store, params)
+}
+
+/// Returns the first 16 bytes of the linear memory of `instance`.
+fn bytes(store: &Store<()>, instance: Instance) -> [u8; 16] {
+    let memory = instance.get_memory(store, "memory").unwrap();
+    memory.data(store)[..16].try_into().unwrap()
+}
+
+#[test]
+fn memory_copy_overlapping_works() {
+    let (mut store, instance) = setup(&Config::default());
+    // Forward overlapping copy.
+    call(&mut store, instance, "copy", (2, 0, 8)).unwrap();
+    assert_eq!(
+        bytes(&store, instance),
+        [0, 1, 0, 1, 2, 3, 4, 5, 6, 7, 10, 11, 12, 13, 14, 15]
+    );
+    // Backward overlapping copy.
+    call(&mut store, instance, "copy", (0, 2, 8)).unwrap();
+    assert_eq!(
+        bytes(&store, instance),
+        [0, 1, 2, 3, 4, 5, 6, 7, 6, 7, 10, 11, 12, 13, 14, 15]
+    );
+}
+
+#[test]
+fn memory_fill_and_init_works() {
+    let (mut store, instance) = setup(&Config::default());
+    call(&mut store, instance, "fill", (4, 0xFF, 4)).unwrap();
+    call(&mut store, instance, "init", (12, 1, 3)).unwrap();
+    assert_eq!(
+        bytes(&store, instance),
+        [0, 1, 2, 3, 255, 255, 255, 255, 8, 9, 10, 11, 0xbb, 0xcc, 0xdd, 15]
+    );
+}
+
+#[test]
+fn memory_copy_out_of_bounds_has_no_effect() {
+    let (mut store, instance) = setup(&Config::default());
+    let before = bytes(&store, instance);
+    let error = call(&mut store, instance, "copy", (0, 65530, 8)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    let error = call(&mut store, instance, "copy", (65530, 0, 8)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    assert_eq!(bytes(&store, instance), before);
+}
+
+#[test]
+fn memory_copy_out_of_fuel_has_no_effect() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let (mut store, instance) = setup(&config);
+    store.add_fuel(10).unwrap();
+    let before = bytes(&store, instance);
+    let error = call(&mut store, instance, "copy", (0, 4096, 65536 - 4096)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    assert_eq!(bytes(&store, instance), before);
+}
@@ -0,0 +1,47 @@
+//! Tests for the JSON representation of Wasmi bytecode via `Engine::bytecode_json`.
+
+use wasmi::{CompilationMode, Config, Engine, Module};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "f" (func))
+        (func (export "count") (param i32) (result i32)
+            (local i32)
+            (loop $continue
+                (local.set 1 (i32.add (local.get 1) (i32.const 100_000)))
+                (br_if $continue (i32.lt_u (local.get 1) (local.get 0)))
+            )
+            (local.get 1)
+        )
+    )
+"#;
+
+fn module(mode: CompilationMode) -> Module {
+    let mut config = Config::default();
+    config.compilation_mode(mode);
+    let engine = Engine::new(&config);
+    Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap()
+}
+
+#[test]
+fn bytecode_json_works() {
+    let module = module(CompilationMode::Eager);
+    let engine = module.engine();
+    let json = engine.bytecode_json(&module, 1).unwrap();
+    assert!(json.starts_with(r#"{"func_index":1,"registers":{"len":"#));
+    assert!(json.contains(r#"{"register":-1,"bits":100000}"#));
+    assert!(json.contains(r#"{"index":0,"op":"#));
+    assert!(json.contains(r#""result":{"Register":1}"#));
+    // The conditional branch jumps backwards to the start of the loop.
+    assert!(json.contains(r#""branch_targets":[0]"#));
+    assert!(json.ends_with("]}"));
+    // Imported and non-existing functions have no bytecode.
+    assert!(engine.bytecode_json(&module, 0).is_none());
+    assert!(engine.bytecode_json(&module, 2).is_none());
+}
+
+#[test]
+fn lazy_bytecode_json() {
+    let module = module(CompilationMode::Lazy);
+    assert!(module.engine().bytecode_json(&module, 1).is_none());
+}
@@ -0,0 +1,145 @@
+//! Tests for batched calls via `TypedFunc::call_batch` and `Func::call_batch`.
+
+use wasmi::{
+    errors::{ErrorKind, FuncError},
+    Engine,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+    Value,
+};
+
+/// Instantiates `wat` in a new [`Store`].
+fn test_setup(wat: &str) -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// A module with a kernel that traps on division by zero and counts its calls.
+const WAT: &str = r#"
+    (module
+        (global $calls (export "calls") (mut i32) (i32.const 0))
+        (memory 0)
+        (func $recurse (param $n i32)
+            (if (local.get $n)
+                (then
+                    (drop (memory.grow (i32.const 1)))
+                    (call $recurse (i32.sub (local.get $n) (i32.const 1)))
+                )
+            )
+        )
+        (func (export "kernel") (param $lhs i32) (param $rhs i32) (result i32)
+            (global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+            (call $recurse (local.get $lhs))
+            (i32.div_s (local.get $lhs) (local.get $rhs))
+        )
+    )
+"#;
+
+/// Returns the exported `kernel` function of the `instance`.
+fn kernel(store: &Store<()>, instance: &Instance) -> TypedFunc<(i32, i32), i32> {
+    instance
+        .get_typed_func::<(i32, i32), i32>(store, "kernel")
+        .unwrap()
+}
+
+/// Returns the number of calls to the `kernel` function so far.
+fn calls(store: &Store<()>, instance: &Instance) -> i32 {
+    instance
+        .get_global(store, "calls")
+        .unwrap()
+        .get(store)
+        .i32()
+        .unwrap()
+}
+
+#[test]
+fn typed_call_batch_works() {
+    let (mut store, instance) = test_setup(WAT);
+    let kernel = kernel(&store, &instance);
+    let results = kernel
+        .call_batch(&mut store, &[(1, 1), (3, 2), (2, -1)])
+        .unwrap();
+    assert_eq!(results, [1, 1, -2]);
+    assert_eq!(calls(&store, &instance), 3);
+    // The call report accounts for all calls of the batch.
+    let report = store.last_call_report();
+    assert_eq!(report.peak_stack_depth(), 5);
+    assert_eq!(report.pages_grown(), 6);
+    // An empty batch executes no calls.
+    assert!(kernel.call_batch(&mut store, &[]).unwrap().is_empty());
+    assert_eq!(calls(&store, &instance), 3);
+}
+
+#[test]
+fn typed_call_batch_stops_at_trap() {
+    let (mut store, instance) = test_setup(WAT);
+    let kernel = kernel(&store, &instance);
+    let error = kernel
+        .call_batch(&mut store, &[(1, 1), (1, 0), (1, 1)])
+        .unwrap_err();
+    assert!(error.as_trap_code().is_some());
+    assert_eq!(calls(&store, &instance), 2);
+}
+
+#[test]
+fn untyped_call_batch_works() {
+    let (mut store, instance) = test_setup(WAT);
+    let kernel = *kernel(&store, &instance).func();
+    let mut output0 = [Value::I32(0)];
+    let mut output1 = [Value::I32(0)];
+    kernel
+        .call_batch(
+            &mut store,
+            &[
+                &[Value::I32(4), Value::I32(2)],
+                &[Value::I32(0), Value::I32(5)],
+            ],
+            &mut [&mut output0, &mut output1],
+        )
+        .unwrap();
+    assert_eq!(output0[0].i32(), Some(2));
+    assert_eq!(output1[0].i32(), Some(0));
+    assert_eq!(calls(&store, &instance), 2);
+}
+
+#[test]
+fn untyped_call_batch_checks_signature() {
+    let (mut store, instance) = test_setup(WAT);
+    let kernel = *kernel(&store, &instance).func();
+    let mut output = [Value::I32(0)];
+    // Mismatching number of inputs and outputs.
+    let error = kernel
+        .call_batch(&mut store, &[&[Value::I32(1), Value::I32(1)]], &mut [])
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Func(FuncError::MismatchingBatchLen)
+    ));
+    // Mismatching types are detected before executing any call of the batch.
+    let error = kernel
+        .call_batch(
+            &mut store,
+            &[
+                &[Value::I32(1), Value::I32(1)],
+                &[Value::I64(1), Value::I32(1)],
+            ],
+            &mut [&mut [Value::I32(0)], &mut output],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Func(FuncError::MismatchingParameterType)
+    ));
+    assert_eq!(calls(&store, &instance), 0);
+}
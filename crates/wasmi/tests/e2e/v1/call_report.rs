@@ -0,0 +1,101 @@
+//! Tests to check if the [`CallReport`] of a [`Store`] is updated as intended.
+
+use wasmi::{CallReport, Config, Engine, Func, Linker, Module, Store};
+
+/// Instantiates `wat` in a new [`Store`] created with `config`.
+fn test_setup(config: &Config, wat: &str) -> (Store<()>, wasmi::Instance) {
+    let engine = Engine::new(config);
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// A module with a recursive function that also grows its linear memory.
+const WAT: &str = r#"
+    (module
+        (memory 0)
+        (func $recurse (export "recurse") (param $n i32)
+            (if (local.get $n)
+                (then
+                    (drop (memory.grow (i32.const 1)))
+                    (call $recurse (i32.sub (local.get $n) (i32.const 1)))
+                )
+            )
+        )
+    )
+"#;
+
+#[test]
+fn report_without_metering() {
+    let (mut store, instance) = test_setup(&Config::default(), WAT);
+    assert_eq!(store.last_call_report(), CallReport::default());
+    let func = instance
+        .get_typed_func::<i32, ()>(&store, "recurse")
+        .unwrap();
+    func.call(&mut store, 3).unwrap();
+    let report = store.last_call_report();
+    assert_eq!(report.fuel_consumed(), None);
+    assert_eq!(report.instructions_executed(), None);
+    assert_eq!(report.peak_stack_depth(), 4);
+    assert_eq!(report.pages_grown(), 3);
+    // The report only covers the most recent call.
+    func.call(&mut store, 1).unwrap();
+    let report = store.last_call_report();
+    assert_eq!(report.peak_stack_depth(), 2);
+    assert_eq!(report.pages_grown(), 1);
+}
+
+#[test]
+fn report_with_fuel_metering() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let (mut store, instance) = test_setup(&config, WAT);
+    store.add_fuel(10_000).unwrap();
+    let func = instance
+        .get_typed_func::<i32, ()>(&store, "recurse")
+        .unwrap();
+    func.call(&mut store, 2).unwrap();
+    let consumed = store.fuel_consumed().unwrap();
+    func.call(&mut store, 2).unwrap();
+    let report = store.last_call_report();
+    assert_eq!(
+        report.fuel_consumed(),
+        Some(store.fuel_consumed().unwrap() - consumed)
+    );
+    assert_eq!(report.instructions_executed(), None);
+}
+
+#[test]
+fn report_with_instruction_metering() {
+    let mut config = Config::default();
+    config.consume_instructions(true);
+    let (mut store, instance) = test_setup(&config, WAT);
+    store.set_instruction_budget(10_000);
+    let func = instance
+        .get_typed_func::<i32, ()>(&store, "recurse")
+        .unwrap();
+    func.call(&mut store, 2).unwrap();
+    let report = store.last_call_report();
+    assert_eq!(report.fuel_consumed(), None);
+    assert_eq!(
+        report.instructions_executed(),
+        Some(10_000 - store.instruction_budget())
+    );
+}
+
+#[test]
+fn report_of_host_function() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let func = Func::wrap(&mut store, || {});
+    func.call(&mut store, &[], &mut []).unwrap();
+    let report = store.last_call_report();
+    assert_eq!(report.peak_stack_depth(), 0);
+    assert_eq!(report.pages_grown(), 0);
+}
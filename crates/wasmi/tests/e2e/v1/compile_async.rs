@@ -0,0 +1,93 @@
+//! Tests for compiling Wasm modules on a background thread via `Engine::compile_async`.
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+use wasmi::{Engine, Linker, Store};
+
+const WAT: &str = r#"
+    (module
+        (func (export "add") (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+/// Wakes up the thread blocked in [`block_on`].
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs `future` to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// Calls the exported `add` function of a module compiled from [`WAT`].
+fn call_add(engine: &Engine, module: &wasmi::Module) -> i32 {
+    let mut store = Store::new(engine, ());
+    let instance = <Linker<()>>::new(engine)
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap()
+        .call(&mut store, (1, 2))
+        .unwrap()
+}
+
+#[test]
+fn compile_async_wait() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = engine.compile_async(wasm).wait().unwrap();
+    assert!(Engine::same(module.engine(), &engine));
+    assert_eq!(call_add(&engine, &module), 3);
+}
+
+#[test]
+fn compile_async_await() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let handle = engine.compile_async(wasm);
+    let module = block_on(handle).unwrap();
+    assert_eq!(call_add(&engine, &module), 3);
+}
+
+#[test]
+fn compile_async_is_finished() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let handle = engine.compile_async(wasm);
+    while !handle.is_finished() {
+        thread::yield_now();
+    }
+    assert!(handle.wait().is_ok());
+}
+
+#[test]
+fn compile_async_invalid() {
+    let engine = Engine::default();
+    // Note: A valid Wasm header followed by an invalid section.
+    let wasm: &[u8] = &[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, 0xFF];
+    assert!(engine.compile_async(wasm).wait().is_err());
+    assert!(block_on(engine.compile_async(wasm)).is_err());
+}
@@ -0,0 +1,45 @@
+//! Tests for the compilation phase timing of `Module::compile_stats`.
+
+use wasmi::{Config, Engine, Module};
+
+/// Returns a Wasm module with `len` functions.
+fn wasm(len: usize) -> Vec<u8> {
+    let funcs = (0..len)
+        .map(|_| {
+            r#"
+            (func (param i32 i32) (result i32)
+                (i32.add
+                    (i32.mul (local.get 0) (local.get 1))
+                    (i32.sub (local.get 0) (local.get 1))
+                )
+            )"#
+        })
+        .collect::<String>();
+    wat::parse_str(format!("(module (memory 1) {funcs})")).unwrap()
+}
+
+/// Compiles [`wasm`] with `len` functions using an [`Engine`] configured by `config`.
+fn compile(config: &Config, len: usize) -> Module {
+    let engine = Engine::new(config);
+    Module::new(&engine, &wasm(len)[..]).unwrap()
+}
+
+#[test]
+fn disabled_by_default() {
+    let module = compile(&Config::default(), 1);
+    assert!(module.compile_stats().is_none());
+}
+
+#[test]
+fn records_compilation_phases() {
+    let mut config = Config::default();
+    config.compile_stats(true);
+    let stats = compile(&config, 100).compile_stats().unwrap();
+    assert!(stats.parsing() > Default::default());
+    assert!(stats.validation() > Default::default());
+    assert!(stats.translation() > Default::default());
+    assert_eq!(
+        stats.total(),
+        stats.parsing() + stats.validation() + stats.translation()
+    );
+}
@@ -0,0 +1,79 @@
+//! Tests for the compiled code budget of an `Engine`.
+
+use wasmi::{errors::TranslationError, CompilationMode, Config, Engine, Linker, Module, Store};
+
+/// The Wasm module used by all tests of this file.
+const WAT: &str = r#"
+    (module
+        (func (export "add") (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+/// Compiles [`WAT`] on the `engine`.
+fn compile(engine: &Engine) -> Result<Module, wasmi::Error> {
+    let wasm = wat::parse_str(WAT).unwrap();
+    Module::new(engine, &wasm[..])
+}
+
+/// Asserts that `error` was caused by an exceeded compiled code budget.
+fn assert_budget_exceeded(error: &wasmi::Error) {
+    assert!(matches!(
+        error.kind(),
+        wasmi::errors::ErrorKind::Translation(TranslationError::CompiledCodeBudgetExceeded)
+    ));
+}
+
+/// Returns the number of bytes of compiled code of [`WAT`].
+fn code_size_of_module() -> usize {
+    let engine = Engine::default();
+    compile(&engine).unwrap();
+    engine.compiled_code_bytes()
+}
+
+#[test]
+fn unlimited_by_default() {
+    let engine = Engine::default();
+    for _ in 0..10 {
+        compile(&engine).unwrap();
+    }
+    assert_eq!(engine.compiled_code_bytes(), 10 * code_size_of_module());
+}
+
+#[test]
+fn budget_is_shared_across_modules() {
+    let size = code_size_of_module();
+    assert_ne!(size, 0);
+    let mut config = Config::default();
+    config.max_compiled_code_bytes(2 * size);
+    let engine = Engine::new(&config);
+    compile(&engine).unwrap();
+    compile(&engine).unwrap();
+    assert_eq!(engine.compiled_code_bytes(), 2 * size);
+    let error = compile(&engine).unwrap_err();
+    assert_budget_exceeded(&error);
+    // Note: Failed compilations are not charged against the budget.
+    assert_eq!(engine.compiled_code_bytes(), 2 * size);
+}
+
+#[test]
+fn budget_is_enforced_upon_lazy_compilation() {
+    let mut config = Config::default();
+    config.max_compiled_code_bytes(0);
+    config.compilation_mode(CompilationMode::Lazy);
+    let engine = Engine::new(&config);
+    let module = compile(&engine).unwrap();
+    assert_eq!(engine.compiled_code_bytes(), 0);
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    let error = add.call(&mut store, (1, 2)).unwrap_err();
+    assert_budget_exceeded(&error);
+}
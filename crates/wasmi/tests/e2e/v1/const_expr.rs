@@ -0,0 +1,146 @@
+//! Tests for parsing and evaluating Wasm constant expressions via `ConstExpr`.
+
+use wasmi::{
+    core::{ValueType, F32},
+    errors::ErrorKind,
+    Config,
+    ConstExpr,
+    Engine,
+    Func,
+    FuncRef,
+    Global,
+    Linker,
+    Module,
+    Mutability,
+    Store,
+    Value,
+};
+
+/// Parses the `bytes` and evaluates the [`ConstExpr`] to a [`Value`] of type `ty`.
+///
+/// The global variable at index `n` has the value `n + 10`.
+fn eval(ty: ValueType, bytes: &[u8]) -> Option<Value> {
+    ConstExpr::parse(bytes).unwrap().eval(
+        ty,
+        |index| Some(Value::I32(index as i32 + 10)),
+        |_index| None,
+    )
+}
+
+/// Returns the error code of parsing the `bytes` as [`ConstExpr`].
+fn parse_error_code(bytes: &[u8]) -> u16 {
+    ConstExpr::parse(bytes).unwrap_err().code()
+}
+
+#[test]
+fn constants() {
+    // i32.const 42
+    assert_eq!(
+        eval(ValueType::I32, &[0x41, 0x2A, 0x0B]).unwrap().i32(),
+        Some(42)
+    );
+    // i64.const -1
+    assert_eq!(
+        eval(ValueType::I64, &[0x42, 0x7F, 0x0B]).unwrap().i64(),
+        Some(-1)
+    );
+    // f32.const 1.0
+    assert_eq!(
+        eval(ValueType::F32, &[0x43, 0x00, 0x00, 0x80, 0x3F, 0x0B])
+            .unwrap()
+            .f32(),
+        Some(F32::from(1.0))
+    );
+    // ref.null func
+    let value = eval(ValueType::FuncRef, &[0xD0, 0x70, 0x0B]).unwrap();
+    assert!(value.funcref().unwrap().is_null());
+}
+
+#[test]
+fn extended_const() {
+    // global.get 1, i32.const 5, i32.add, i32.const 3, i32.mul
+    let bytes = [0x23, 0x01, 0x41, 0x05, 0x6A, 0x41, 0x03, 0x6C, 0x0B];
+    assert_eq!(eval(ValueType::I32, &bytes).unwrap().i32(), Some(48));
+    // i64.const 2, i64.const 7, i64.sub
+    let bytes = [0x42, 0x02, 0x42, 0x07, 0x7D, 0x0B];
+    assert_eq!(eval(ValueType::I64, &bytes).unwrap().i64(), Some(-5));
+}
+
+#[test]
+fn unresolved_items() {
+    // ref.func 0
+    let expr = ConstExpr::parse(&[0xD2, 0x00, 0x0B]).unwrap();
+    assert!(expr.eval(ValueType::FuncRef, |_| None, |_| None).is_none());
+    let mut store = Store::new(&Engine::default(), ());
+    let func = Func::wrap(&mut store, || ());
+    let value = expr
+        .eval(ValueType::FuncRef, |_| None, |_| Some(FuncRef::new(func)))
+        .unwrap();
+    assert!(!value.funcref().unwrap().is_null());
+    // global.get 0
+    let expr = ConstExpr::parse(&[0x23, 0x00, 0x0B]).unwrap();
+    assert!(expr.eval(ValueType::I32, |_| None, |_| None).is_none());
+}
+
+#[test]
+fn invalid_exprs() {
+    // nop
+    assert_eq!(parse_error_code(&[0x01, 0x0B]), 0x1000);
+    // local.get 0
+    assert_eq!(parse_error_code(&[0x20, 0x00, 0x0B]), 0x1000);
+    // i64.const 1, i32.const 1, i32.add
+    assert_eq!(
+        parse_error_code(&[0x42, 0x01, 0x41, 0x01, 0x6A, 0x0B]),
+        0x1001
+    );
+    // Operands produced by `global.get` are not type checked.
+    assert!(ConstExpr::parse(&[0x23, 0x00, 0x41, 0x01, 0x6A, 0x0B]).is_ok());
+    // end
+    assert_eq!(parse_error_code(&[0x0B]), 0x1002);
+    // i32.const 1, i32.const 2
+    assert_eq!(parse_error_code(&[0x41, 0x01, 0x41, 0x02, 0x0B]), 0x1002);
+    // i32.const 1, i32.add
+    assert_eq!(parse_error_code(&[0x41, 0x01, 0x6A, 0x0B]), 0x1002);
+    // Missing `end` and trailing bytes are malformed.
+    for bytes in [&[0x41, 0x01][..], &[0x41, 0x01, 0x0B, 0x0B][..]] {
+        let error = ConstExpr::parse(bytes).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::Wasm(_)));
+    }
+}
+
+#[test]
+fn matches_instantiation() {
+    let mut config = Config::default();
+    config.wasm_extended_const(true);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "base" (global $base i64))
+            (global (export "global") i64
+                (i64.mul (i64.add (global.get $base) (i64.const 3)) (i64.const 100))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let base = Global::new(&mut store, Value::I64(-7), Mutability::Const);
+    linker.define("env", "base", base).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let expected = instance.get_global(&store, "global").unwrap().get(&store);
+    // global.get 0, i64.const 3, i64.add, i64.const 100, i64.mul
+    let bytes = [0x23, 0x00, 0x42, 0x03, 0x7C, 0x42, 0xE4, 0x00, 0x7E, 0x0B];
+    let value = ConstExpr::parse(&bytes)
+        .unwrap()
+        .eval(ValueType::I64, |_| Some(base.get(&store)), |_| None)
+        .unwrap();
+    assert_eq!(value.i64(), expected.i64());
+    assert_eq!(value.i64(), Some(-400));
+}
@@ -0,0 +1,73 @@
+//! Tests for the execution of register span copies emitted around Wasm branches.
+
+use wasmi::{Engine, Linker, Module, Store, TypedFunc};
+
+/// The exported `test` function shuffling three `i32` values.
+type TestFunc = TypedFunc<(i32, i32, i32), (i32, i32, i32)>;
+
+/// Instantiates the Wasm `wat` and returns its exported `test` function.
+fn setup(wat: &str) -> (Store<()>, TestFunc) {
+    let wasm = wat::parse_str(wat).unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let test = instance
+        .get_typed_func::<(i32, i32, i32), (i32, i32, i32)>(&store, "test")
+        .unwrap();
+    (store, test)
+}
+
+#[test]
+fn overlapping_copy_span_works() {
+    // The block results are copied from a span starting at the last local
+    // into a span starting at the first dynamic register, thus overlapping.
+    let (mut store, test) = setup(
+        r#"
+        (module
+            (func (export "test") (param i32 i32 i32) (result i32 i32 i32)
+                (block (result i32 i32 i32)
+                    (local.get 2)
+                    (i32.add (local.get 0) (local.get 1))
+                    (i32.add (local.get 0) (local.get 2))
+                    (br_if 0 (local.get 0))
+                )
+            )
+        )
+        "#,
+    );
+    for (a, b, c) in [(0, 1, 2), (1, 2, 3), (-5, 7, 11)] {
+        assert_eq!(
+            test.call(&mut store, (a, b, c)).unwrap(),
+            (c, a.wrapping_add(b), a.wrapping_add(c)),
+        );
+    }
+}
+
+#[test]
+fn overlapping_copy_span_with_locals_works() {
+    let (mut store, test) = setup(
+        r#"
+        (module
+            (func (export "test") (param i32 i32 i32) (result i32 i32 i32)
+                (block (result i32 i32 i32)
+                    (local.get 1)
+                    (local.get 2)
+                    (i32.add (local.get 0) (local.get 1))
+                    (br_if 0 (local.get 0))
+                )
+            )
+        )
+        "#,
+    );
+    for (a, b, c) in [(0, 1, 2), (1, 2, 3), (-5, 7, 11)] {
+        assert_eq!(
+            test.call(&mut store, (a, b, c)).unwrap(),
+            (b, c, a.wrapping_add(b)),
+        );
+    }
+}
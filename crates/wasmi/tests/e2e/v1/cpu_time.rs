@@ -0,0 +1,84 @@
+//! Tests for per-`Store` CPU time accounting.
+
+use std::time::{Duration, Instant};
+use wasmi::{Caller, Config, Engine, Func, Linker, Module, Store, TypedFunc};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "host" (func $host))
+        (func (export "burn") (param $n i32)
+            (loop $continue
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br_if $continue (local.get $n))
+            )
+        )
+        (func (export "call_host")
+            (call $host)
+        )
+    )
+"#;
+
+/// Spins the calling thread for `duration`.
+fn spin(duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        std::hint::spin_loop();
+    }
+}
+
+/// Instantiates [`WAT`] in a new [`Store`] and returns its exported functions.
+fn setup(cpu_time_accounting: bool) -> (Store<()>, TypedFunc<i32, ()>, TypedFunc<(), ()>) {
+    let mut config = Config::default();
+    config.cpu_time_accounting(cpu_time_accounting);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let host = Func::wrap(&mut store, |_caller: Caller<()>| {
+        spin(Duration::from_millis(50));
+    });
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("env", "host", host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let burn = instance.get_typed_func::<i32, ()>(&store, "burn").unwrap();
+    let call_host = instance
+        .get_typed_func::<(), ()>(&store, "call_host")
+        .unwrap();
+    (store, burn, call_host)
+}
+
+#[test]
+fn disabled_by_default() {
+    let (mut store, burn, _) = setup(false);
+    burn.call(&mut store, 1_000).unwrap();
+    assert_eq!(store.cpu_time(), None);
+}
+
+#[test]
+#[cfg(unix)]
+fn accumulates_wasm_cpu_time() {
+    let (mut store, burn, _) = setup(true);
+    assert_eq!(store.cpu_time(), Some(Duration::ZERO));
+    burn.call(&mut store, 10_000_000).unwrap();
+    let first = store.cpu_time().unwrap();
+    assert!(first > Duration::ZERO);
+    burn.call(&mut store, 10_000_000).unwrap();
+    let second = store.cpu_time().unwrap();
+    assert!(second > first);
+}
+
+#[test]
+#[cfg(unix)]
+fn excludes_host_cpu_time() {
+    let (mut store, _, call_host) = setup(true);
+    call_host.call(&mut store, ()).unwrap();
+    let cpu_time = store.cpu_time().unwrap();
+    assert!(
+        cpu_time < Duration::from_millis(25),
+        "cpu_time = {cpu_time:?}"
+    );
+}
@@ -0,0 +1,140 @@
+//! Tests for collecting translation diagnostics via `Config::diagnostics_sink`.
+
+use std::sync::{Arc, Mutex};
+use wasmi::{CompilationMode, Config, Diagnostic, DiagnosticKind, Engine, Linker, Module, Store};
+
+/// Translates `wat` with the given `mode` and returns all emitted [`Diagnostic`]s.
+fn collect_diagnostics(wat: &str, mode: CompilationMode) -> Vec<Diagnostic> {
+    let diagnostics = Arc::new(Mutex::new(Vec::new()));
+    let mut config = Config::default();
+    config.compilation_mode(mode).diagnostics_sink({
+        let diagnostics = diagnostics.clone();
+        move |diagnostic| diagnostics.lock().unwrap().push(*diagnostic)
+    });
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(&engine, &wasm[..]).unwrap();
+    let diagnostics = diagnostics.lock().unwrap().clone();
+    diagnostics
+}
+
+#[test]
+fn preserved_local() {
+    let wat = r#"
+        (module
+            (func (param i32) (result i32)
+                (local.get 0)
+                (local.set 0 (i32.const 1))
+                (i32.add (local.get 0))
+            )
+        )
+    "#;
+    let diagnostics = collect_diagnostics(wat, CompilationMode::Eager);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].func_index(), 0);
+    assert_eq!(
+        diagnostics[0].kind(),
+        DiagnosticKind::PreservedLocal { local_index: 0 }
+    );
+}
+
+#[test]
+fn branch_table_slow_path() {
+    let wat = r#"
+        (module
+            (func (param i32) (result i32)
+                (block $a (result i32)
+                    (i32.add (local.get 0) (local.get 0))
+                    (block $b (result i32)
+                        (i32.const 10)
+                        (local.get 0)
+                        (br_table $a $b $a)
+                    )
+                    (i32.add)
+                )
+            )
+        )
+    "#;
+    let diagnostics = collect_diagnostics(wat, CompilationMode::Eager);
+    assert_eq!(
+        diagnostics.iter().map(Diagnostic::kind).collect::<Vec<_>>(),
+        [DiagnosticKind::BranchTableSlowPath { len_targets: 3 }],
+    );
+}
+
+#[test]
+fn no_diagnostics() {
+    let wat = r#"
+        (module
+            (func (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )
+    "#;
+    assert!(collect_diagnostics(wat, CompilationMode::Eager).is_empty());
+}
+
+#[test]
+fn lazy_translation_defers_diagnostics() {
+    let wat = r#"
+        (module
+            (func (param i32) (result i32)
+                (local.get 0)
+                (local.set 0 (i32.const 1))
+                (i32.add (local.get 0))
+            )
+        )
+    "#;
+    assert!(collect_diagnostics(wat, CompilationMode::LazyTranslation).is_empty());
+}
+
+#[test]
+fn lazy_translation_emits_after_call() {
+    let wat = r#"
+        (module
+            (func (export "f") (param i32) (result i32)
+                (local.get 0)
+                (local.set 0 (i32.const 1))
+                (i32.add (local.get 0))
+            )
+        )
+    "#;
+    let diagnostics = Arc::new(Mutex::new(Vec::new()));
+    let engine_slot = Arc::new(Mutex::new(None::<Engine>));
+    let mut config = Config::default();
+    config
+        .compilation_mode(CompilationMode::LazyTranslation)
+        .diagnostics_sink({
+            let diagnostics = diagnostics.clone();
+            let engine_slot = engine_slot.clone();
+            move |diagnostic| {
+                // The sink may use the engine since no engine locks are held.
+                let engine = engine_slot.lock().unwrap().clone().unwrap();
+                Module::new(&engine, &wat::parse_str("(module)").unwrap()[..]).unwrap();
+                diagnostics.lock().unwrap().push(*diagnostic);
+            }
+        });
+    let engine = Engine::new(&config);
+    *engine_slot.lock().unwrap() = Some(engine.clone());
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::<()>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let f = instance.get_typed_func::<i32, i32>(&store, "f").unwrap();
+    assert!(diagnostics.lock().unwrap().is_empty());
+    assert_eq!(f.call(&mut store, 5).unwrap(), 6);
+    assert_eq!(
+        diagnostics
+            .lock()
+            .unwrap()
+            .iter()
+            .map(Diagnostic::kind)
+            .collect::<Vec<_>>(),
+        [DiagnosticKind::PreservedLocal { local_index: 0 }],
+    );
+    engine_slot.lock().unwrap().take();
+}
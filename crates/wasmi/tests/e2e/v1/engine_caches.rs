@@ -0,0 +1,42 @@
+//! Tests for trimming the caches of an `Engine`.
+
+use wasmi::{Engine, Linker, Module, Store};
+
+/// Instantiates a Wasm module using constant values and asserts that it still executes.
+fn assert_executes(engine: &Engine) {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (export "f") (param i64) (result i64)
+                (i64.add (local.get 0) (i64.const 1000000000000))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(engine, &wasm[..]).unwrap();
+    let mut store = Store::new(engine, ());
+    let instance = Linker::new(engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let f = instance.get_typed_func::<i64, i64>(&store, "f").unwrap();
+    assert_eq!(f.call(&mut store, 1).unwrap(), 1_000_000_000_001);
+}
+
+#[test]
+fn clear_caches_works() {
+    let engine = Engine::default();
+    assert_executes(&engine);
+    engine.clear_caches();
+    assert_executes(&engine);
+}
+
+#[test]
+fn shrink_to_fit_works() {
+    let engine = Engine::default();
+    assert_executes(&engine);
+    engine.shrink_to_fit();
+    assert_executes(&engine);
+}
@@ -0,0 +1,41 @@
+//! Tests for querying the Wasm features of an `Engine`.
+
+use wasmi::{Config, Engine, StackLimits};
+
+#[test]
+fn default_features() {
+    let features = Engine::default().features();
+    assert!(features.mutable_global());
+    assert!(features.sign_extension());
+    assert!(features.saturating_float_to_int());
+    assert!(features.multi_value());
+    assert_eq!(features.bulk_memory(), cfg!(feature = "bulk-table"));
+    assert_eq!(
+        features.reference_types(),
+        cfg!(feature = "reference-types")
+    );
+    assert!(!features.tail_call());
+    assert!(!features.extended_const());
+    assert_eq!(features.floats(), cfg!(feature = "float"));
+    assert_eq!(features.stack_limits(), StackLimits::default());
+}
+
+#[test]
+fn configured_features() {
+    let stack_limits = StackLimits::new(256, 1024, 64).unwrap();
+    let mut config = Config::default();
+    config
+        .wasm_tail_call(true)
+        .wasm_extended_const(true)
+        .wasm_reference_types(false)
+        .floats(false)
+        .set_stack_limits(stack_limits);
+    let features = Engine::new(&config).features();
+    assert!(features.tail_call());
+    assert!(features.extended_const());
+    assert!(!features.reference_types());
+    assert!(!features.floats());
+    assert_eq!(features.stack_limits(), stack_limits);
+    assert_eq!(features, Engine::new(&config).features());
+    assert_ne!(features, Engine::default().features());
+}
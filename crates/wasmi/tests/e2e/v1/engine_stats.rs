@@ -0,0 +1,108 @@
+//! Tests for the statistics counters of an `Engine`.
+
+use wasmi::{core::TrapCode, CompilationMode, Config, Engine, Linker, Module, Store};
+
+/// The Wasm module used by all tests of this file.
+const WAT: &str = r#"
+    (module
+        (func (export "add") (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+        (func (export "div") (param i32 i32) (result i32)
+            (i32.div_s (local.get 0) (local.get 1))
+        )
+        (func (export "trap")
+            (unreachable)
+        )
+    )
+"#;
+
+/// Compiles and instantiates [`WAT`] on the `engine`, then calls `add`, `div` and `trap`.
+fn run(engine: &Engine) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(engine, &wasm[..]).unwrap();
+    let mut store = Store::new(engine, ());
+    let instance = Linker::new(engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    let div = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "div")
+        .unwrap();
+    let trap = instance.get_typed_func::<(), ()>(&store, "trap").unwrap();
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+    let error = div.call(&mut store, (1, 0)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    let error = trap.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+}
+
+#[test]
+fn stats_start_at_zero() {
+    let stats = Engine::default().stats();
+    assert_eq!(stats.modules_compiled(), 0);
+    assert_eq!(stats.funcs_translated(), 0);
+    assert_eq!(stats.stack_cache_hits(), 0);
+    assert_eq!(stats.stack_cache_misses(), 0);
+    assert_eq!(stats.translation_allocs_hits(), 0);
+    assert_eq!(stats.translation_allocs_misses(), 0);
+    assert_eq!(stats.instantiations(), 0);
+    assert_eq!(stats.total_traps(), 0);
+}
+
+#[test]
+fn stats_count_engine_activity() {
+    let engine = Engine::default();
+    run(&engine);
+    run(&engine);
+    let stats = engine.stats();
+    assert_eq!(stats.modules_compiled(), 2);
+    assert_eq!(stats.funcs_translated(), 6);
+    assert_eq!(stats.instantiations(), 2);
+    assert_eq!(stats.stack_cache_hits() + stats.stack_cache_misses(), 6);
+    assert_eq!(stats.stack_cache_misses(), 1);
+    assert_eq!(stats.traps(TrapCode::IntegerDivisionByZero), 2);
+    assert_eq!(stats.traps(TrapCode::UnreachableCodeReached), 2);
+    assert_eq!(stats.traps(TrapCode::MemoryOutOfBounds), 0);
+    assert_eq!(stats.total_traps(), 4);
+}
+
+#[test]
+fn stats_are_shared_by_engine_clones() {
+    let engine = Engine::default();
+    run(&engine.clone());
+    assert_eq!(engine.stats().modules_compiled(), 1);
+    assert_eq!(engine.stats(), engine.clone().stats());
+}
+
+#[test]
+fn lazy_compilation_counts_called_funcs() {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    let engine = Engine::new(&config);
+    run(&engine);
+    // All functions are called once and thus translated lazily.
+    assert_eq!(engine.stats().funcs_translated(), 3);
+}
+
+#[test]
+fn translation_allocs_are_reused_across_modules() {
+    for mode in [CompilationMode::Eager, CompilationMode::Lazy] {
+        let mut config = Config::default();
+        config.compilation_mode(mode);
+        let engine = Engine::new(&config);
+        for _ in 0..3 {
+            run(&engine);
+        }
+        let wasm = wat::parse_str(WAT).unwrap();
+        Module::validate(&engine, &wasm[..]).unwrap();
+        let stats = engine.stats();
+        assert_eq!(stats.funcs_translated(), 9);
+        assert_eq!(stats.translation_allocs_misses(), 1);
+        assert_eq!(stats.translation_allocs_hits(), 8);
+    }
+}
@@ -0,0 +1,137 @@
+//! Tests to check if Wasmi's epoch based interruption works as intended.
+
+use std::time::Duration;
+use wasmi::{
+    core::TrapCode,
+    watchdog::Watchdog,
+    Config,
+    Engine,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+    TypedResumableCall,
+    UpdateDeadline,
+};
+
+/// Setup [`Store`] and the exported `test` [`TypedFunc`] with epoch interruption.
+fn test_setup(wat: &str) -> (Store<()>, TypedFunc<i32, ()>) {
+    let mut config = Config::default();
+    config.epoch_interruption(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func(&store, "test").unwrap();
+    (store, func)
+}
+
+/// A function that counts down from its parameter to zero.
+///
+/// Loops forever if its parameter is negative.
+const COUNTDOWN: &str = r#"
+    (module
+        (func (export "test") (param $n i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+        )
+    )
+"#;
+
+#[test]
+fn no_deadline_never_interrupts() {
+    let (mut store, func) = test_setup(COUNTDOWN);
+    store.engine().increment_epoch();
+    func.call(&mut store, 1_000).unwrap();
+}
+
+#[test]
+fn reached_deadline_interrupts() {
+    let (mut store, func) = test_setup(COUNTDOWN);
+    store.set_epoch_deadline(1);
+    func.call(&mut store, 1_000).unwrap();
+    store.engine().increment_epoch();
+    let error = func.call(&mut store, 1_000).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+    store.clear_epoch_deadline();
+    func.call(&mut store, 1_000).unwrap();
+}
+
+#[test]
+fn watchdog_interrupts_infinite_loop() {
+    let (mut store, func) = test_setup(COUNTDOWN);
+    let watchdog = Watchdog::new(store.engine(), Duration::from_millis(1));
+    assert_eq!(watchdog.tick(), Duration::from_millis(1));
+    store.set_epoch_deadline(5);
+    let error = func.call(&mut store, -1).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+}
+
+#[test]
+fn deadline_callback_continues() {
+    let (mut store, func) = test_setup(COUNTDOWN);
+    store.set_epoch_deadline(1);
+    store.set_epoch_deadline_callback(|ctx| {
+        // Note: the callback is called once per reached deadline.
+        ctx.engine().increment_epoch();
+        Ok(UpdateDeadline::Continue(2))
+    });
+    store.engine().increment_epoch();
+    func.call(&mut store, 1_000).unwrap();
+    // The callback advanced the epoch once and the new deadline is not yet reached.
+    store.engine().increment_epoch();
+    func.call(&mut store, 1_000).unwrap();
+    store.clear_epoch_deadline_callback();
+    store.engine().increment_epoch();
+    let error = func.call(&mut store, 1_000).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+}
+
+#[test]
+fn deadline_callback_error() {
+    let (mut store, func) = test_setup(COUNTDOWN);
+    store.set_epoch_deadline(0);
+    store.set_epoch_deadline_callback(|_| Err(TrapCode::UnreachableCodeReached.into()));
+    let error = func.call(&mut store, 1_000).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+}
+
+#[test]
+fn deadline_callback_yields_resumable_calls() {
+    let (mut store, func) = test_setup(COUNTDOWN);
+    store.set_epoch_deadline(1);
+    store.set_epoch_deadline_callback(|_| Ok(UpdateDeadline::Yield(1)));
+    store.engine().increment_epoch();
+    let mut call = func.call_resumable(&mut store, 1_000).unwrap();
+    let mut yields = 0;
+    loop {
+        match call {
+            TypedResumableCall::Finished(()) => break,
+            TypedResumableCall::Yielded(invocation) => {
+                yields += 1;
+                if yields < 3 {
+                    store.engine().increment_epoch();
+                }
+                call = invocation.resume(&mut store).unwrap();
+            }
+            TypedResumableCall::Resumable(_) | TypedResumableCall::OutOfFuel(_) => {
+                panic!("unexpected host error")
+            }
+        }
+    }
+    assert_eq!(yields, 3);
+    // Non-resumable calls continue instead of yielding.
+    store.engine().increment_epoch();
+    func.call(&mut store, 1_000).unwrap();
+}
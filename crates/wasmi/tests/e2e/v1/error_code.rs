@@ -0,0 +1,37 @@
+//! Tests for the stable numeric codes of errors via `Error::code`.
+
+use wasmi::{core::TrapCode, Engine, Error, Linker, Module, Store};
+
+fn instantiate(wat: &str) -> Result<(), Error> {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..])?;
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    linker.instantiate(&mut store, &module)?.start(&mut store)?;
+    Ok(())
+}
+
+#[test]
+fn trap_codes() {
+    assert_eq!(Error::from(TrapCode::UnreachableCodeReached).code(), 0x0100);
+    assert_eq!(Error::from(TrapCode::StackOverflow).code(), 0x0107);
+    assert_eq!(Error::from(TrapCode::GrowthOperationLimited).code(), 0x010C);
+    let error = instantiate(r#"(module (func $f unreachable) (start $f))"#).unwrap_err();
+    assert_eq!(error.code(), 0x0100);
+}
+
+#[test]
+fn host_error_codes() {
+    assert_eq!(Error::new("message").code(), 0x0001);
+    assert_eq!(Error::i32_exit(1).code(), 0x0002);
+}
+
+#[test]
+fn module_error_codes() {
+    let error = instantiate(r#"(module (import "env" "f" (func)))"#).unwrap_err();
+    assert_eq!(error.code(), 0x0501);
+    let engine = Engine::default();
+    let error = Module::new(&engine, &b"invalid"[..]).unwrap_err();
+    assert_eq!(error.code(), 0x0A00);
+}
@@ -0,0 +1,158 @@
+//! Tests for `ExternType::matches` agreeing with import checks of instantiation.
+
+use wasmi::{
+    core::ValueType,
+    Engine,
+    Extern,
+    ExternType,
+    Func,
+    FuncType,
+    Global,
+    GlobalType,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Mutability,
+    Store,
+    Table,
+    TableType,
+    Value,
+};
+
+/// Returns the Wasm module with the single `import` and its `ExternType`.
+fn import_type(engine: &Engine, import: &str) -> (Module, ExternType) {
+    let wat = format!(r#"(module (import "env" "item" ({import})))"#);
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(engine, &wasm[..]).unwrap();
+    let ty = module.imports().next().unwrap().ty().clone();
+    (module, ty)
+}
+
+/// Asserts that `provided` matches the `import` if and only if instantiating
+/// a Wasm module with the `import` using `provided` succeeds.
+///
+/// Returns `true` if `provided` matched the `import`.
+fn assert_agrees(store: &mut Store<()>, provided: Extern, import: &str) -> bool {
+    let (module, required) = import_type(store.engine(), import);
+    let matches = provided.dynamic_ty(&*store).matches(&required);
+    let mut linker = <Linker<()>>::new(store.engine());
+    linker.define("env", "item", provided).unwrap();
+    let instantiated = linker.instantiate(&mut *store, &module).is_ok();
+    assert_eq!(
+        matches, instantiated,
+        "ExternType::matches and instantiation disagree for {import}"
+    );
+    matches
+}
+
+#[test]
+fn func_types() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let func = Extern::Func(Func::wrap(&mut store, |a: i32| a));
+    assert!(assert_agrees(
+        &mut store,
+        func,
+        "func (param i32) (result i32)"
+    ));
+    assert!(!assert_agrees(
+        &mut store,
+        func,
+        "func (param i64) (result i32)"
+    ));
+    assert!(!assert_agrees(&mut store, func, "func (param i32)"));
+}
+
+#[test]
+fn table_types() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let ty = TableType::new(ValueType::FuncRef, 2, Some(10));
+    let table =
+        Extern::Table(Table::new(&mut store, ty, Value::default(ValueType::FuncRef)).unwrap());
+    for (import, expected) in [
+        ("table 2 10 funcref", true),
+        ("table 1 funcref", true),
+        ("table 0 20 funcref", true),
+        ("table 3 funcref", false),
+        ("table 2 5 funcref", false),
+        ("table 2 10 externref", false),
+    ] {
+        assert_eq!(
+            assert_agrees(&mut store, table, import),
+            expected,
+            "{import}"
+        );
+    }
+    // The current size of the table counts as its minimum.
+    let Extern::Table(t) = table else {
+        unreachable!()
+    };
+    t.grow(&mut store, 2, Value::default(ValueType::FuncRef))
+        .unwrap();
+    assert!(assert_agrees(&mut store, table, "table 4 funcref"));
+    assert!(!table.ty(&store).matches(&ExternType::Table(TableType::new(
+        ValueType::FuncRef,
+        4,
+        None
+    ))));
+}
+
+#[test]
+fn memory_types() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let memory =
+        Extern::Memory(Memory::new(&mut store, MemoryType::new(1, Some(4)).unwrap()).unwrap());
+    for (import, expected) in [
+        ("memory 1 4", true),
+        ("memory 0", true),
+        ("memory 1 8", true),
+        ("memory 2", false),
+        ("memory 1 2", false),
+    ] {
+        assert_eq!(
+            assert_agrees(&mut store, memory, import),
+            expected,
+            "{import}"
+        );
+    }
+    let unbounded = MemoryType::new(1, None).unwrap();
+    assert!(!ExternType::Memory(unbounded)
+        .matches(&ExternType::Memory(MemoryType::new(1, Some(4)).unwrap())));
+}
+
+#[test]
+fn global_types() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let global = Extern::Global(Global::new(&mut store, Value::I32(0), Mutability::Var));
+    for (import, expected) in [
+        ("global (mut i32)", true),
+        ("global i32", false),
+        ("global (mut i64)", false),
+    ] {
+        assert_eq!(
+            assert_agrees(&mut store, global, import),
+            expected,
+            "{import}"
+        );
+    }
+    let funcref = ExternType::Global(GlobalType::new(ValueType::FuncRef, Mutability::Const));
+    let externref = ExternType::Global(GlobalType::new(ValueType::ExternRef, Mutability::Const));
+    assert!(funcref.matches(&funcref));
+    assert!(!funcref.matches(&externref));
+}
+
+#[test]
+fn kind_mismatch() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let global = Extern::Global(Global::new(&mut store, Value::I32(0), Mutability::Const));
+    assert!(!assert_agrees(&mut store, global, "memory 0"));
+    let func = ExternType::Func(FuncType::new([], []));
+    let memory = ExternType::Memory(MemoryType::new(0, None).unwrap());
+    assert!(!func.matches(&memory));
+    assert!(!memory.matches(&func));
+}
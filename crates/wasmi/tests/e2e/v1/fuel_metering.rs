@@ -1,7 +1,17 @@
 //! Tests to check if wasmi's fuel metering works as intended.
 
 use std::fmt::Debug;
-use wasmi::{core::TrapCode, Config, Engine, Error, Func, Linker, Module, Store};
+use wasmi::{
+    core::TrapCode,
+    Config,
+    Engine,
+    Error,
+    Func,
+    Linker,
+    Module,
+    Store,
+    TypedResumableCall,
+};
 
 /// Setup [`Engine`] and [`Store`] for fuel metering.
 fn test_setup() -> (Store<()>, Linker<()>) {
@@ -92,3 +102,84 @@ fn metered_i32_add() {
     assert_success(func.call(&mut store, (1, 2)));
     assert_eq!(store.fuel_consumed(), Some(3));
 }
+
+#[test]
+fn call_with_fuel_scopes_budget() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "test") (param $n i32)
+                (block $exit
+                    (loop $continue
+                        (br_if $exit (i32.eqz (local.get $n)))
+                        (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                        (br $continue)
+                    )
+                )
+            )
+        )
+    "#,
+    );
+    let (mut store, func) = default_test_setup(&wasm);
+    let func = func.typed::<i32, ()>(&store).unwrap();
+    store.add_fuel(1_000).unwrap();
+    // The call budget is independent of the remaining fuel of the store.
+    assert_out_of_fuel(func.call_with_fuel(&mut store, 1_000, 10));
+    assert_eq!(store.fuel_consumed(), Some(10));
+    assert_success(func.call_with_fuel(&mut store, 1, 5_000));
+    let consumed = store.fuel_consumed().unwrap();
+    assert!(consumed > 10);
+    // The remaining fuel of the store has been restored after both calls.
+    assert_eq!(store.consume_fuel(0).unwrap(), 1_000);
+}
+
+#[test]
+fn call_with_fuel_requires_fuel_metering() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let func = Func::wrap(&mut store, || {});
+    let func = func.typed::<(), ()>(&store).unwrap();
+    assert!(func.call_with_fuel(&mut store, (), 10).is_err());
+}
+
+#[test]
+fn resumable_typed_call_resumes_after_refuel() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (export "test") (param $n i32) (result i32)
+                (local $sum i32)
+                (block $exit
+                    (loop $continue
+                        (br_if $exit (i32.eqz (local.get $n)))
+                        (local.set $sum (i32.add (local.get $sum) (local.get $n)))
+                        (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                        (br $continue)
+                    )
+                )
+                (local.get $sum)
+            )
+        )
+    "#,
+    );
+    let (mut store, func) = default_test_setup(&wasm);
+    let func = func.typed::<i32, i32>(&store).unwrap();
+    store.add_fuel(10).unwrap();
+    let mut call = func.call_resumable(&mut store, 100).unwrap();
+    let mut suspensions = 0;
+    let result = loop {
+        match call {
+            TypedResumableCall::Finished(result) => break result,
+            TypedResumableCall::OutOfFuel(invocation) => {
+                suspensions += 1;
+                store.add_fuel(10).unwrap();
+                call = invocation.resume(&mut store).unwrap();
+            }
+            TypedResumableCall::Resumable(_) | TypedResumableCall::Yielded(_) => {
+                panic!("expected TypedResumableCall::OutOfFuel")
+            }
+        }
+    };
+    assert_eq!(result, 5050);
+    assert!(suspensions > 1);
+}
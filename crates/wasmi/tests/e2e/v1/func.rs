@@ -4,14 +4,19 @@ use core::slice;
 
 use assert_matches::assert_matches;
 use wasmi::{
-    errors::{ErrorKind, FuncError},
+    errors::{ErrorKind, FuncError, ValueError},
     Engine,
+    FromValues,
     Func,
+    FuncRef,
     FuncType,
+    IntoValues,
+    Linker,
+    Module,
     Store,
     Value,
 };
-use wasmi_core::{ValueType, F32, F64};
+use wasmi_core::{UntypedValue, ValueType, F32, F64};
 
 fn test_setup() -> Store<()> {
     let engine = Engine::default();
@@ -531,3 +536,180 @@ fn static_type_check_works() {
         ErrorKind::Func(FuncError::MismatchingResultType)
     );
 }
+
+#[test]
+fn untyped_call_works() {
+    let (mut store, add2, add2_dyn) = setup_add2();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (export "sub") (param i64 i64) (result i64)
+                (i64.sub (local.get 0) (local.get 1))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let sub = <Linker<()>>::new(store.engine())
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_func(&store, "sub")
+        .unwrap();
+    let mut result = UntypedValue::from(0_i32);
+    for func in [add2, add2_dyn] {
+        func.call_untyped(
+            &mut store,
+            &[UntypedValue::from(1_i32), UntypedValue::from(2_i32)],
+            slice::from_mut(&mut result),
+        )
+        .unwrap();
+        assert_eq!(i32::from(result), 3);
+    }
+    sub.call_untyped(
+        &mut store,
+        &[UntypedValue::from(1_i64), UntypedValue::from(3_i64)],
+        slice::from_mut(&mut result),
+    )
+    .unwrap();
+    assert_eq!(i64::from(result), -2);
+    // Case: Mismatching number of parameters.
+    assert_matches!(
+        sub.call_untyped(
+            &mut store,
+            &[UntypedValue::from(1_i64)],
+            slice::from_mut(&mut result),
+        )
+        .unwrap_err()
+        .kind(),
+        ErrorKind::Func(FuncError::MismatchingParameterLen)
+    );
+    // Case: Mismatching number of results.
+    assert_matches!(
+        sub.call_untyped(
+            &mut store,
+            &[UntypedValue::from(1_i64), UntypedValue::from(3_i64)],
+            &mut [],
+        )
+        .unwrap_err()
+        .kind(),
+        ErrorKind::Func(FuncError::MismatchingResultLen)
+    );
+}
+
+#[test]
+fn funcref_typed_works() {
+    let mut store = test_setup();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (table $t 2 funcref)
+            (elem (table $t) (i32.const 0) func $double)
+            (func $double (param i32) (result i32)
+                (i32.mul (local.get 0) (i32.const 2))
+            )
+            (func (export "get") (param i32) (result funcref)
+                (table.get $t (local.get 0))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(store.engine())
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let get = instance
+        .get_typed_func::<i32, FuncRef>(&store, "get")
+        .unwrap();
+    // Case: the guest returned a non-null function reference.
+    let double = get
+        .call(&mut store, 0)
+        .unwrap()
+        .typed::<i32, i32>(&store)
+        .unwrap()
+        .unwrap();
+    assert_eq!(double.call(&mut store, 21).unwrap(), 42);
+    // Case: the guest returned a null function reference.
+    let null = get.call(&mut store, 1).unwrap();
+    assert!(null.typed::<i32, i32>(&store).unwrap().is_none());
+    // Case: the function reference has a mismatching type.
+    let funcref = get.call(&mut store, 0).unwrap();
+    assert_matches!(
+        funcref.typed::<i64, i32>(&store).unwrap_err().kind(),
+        ErrorKind::Func(FuncError::MismatchingParameterType)
+    );
+}
+
+#[test]
+fn dynamic_call_with_value_conversions_works() {
+    let mut store = test_setup();
+    let func = Func::wrap(&mut store, |a: i32, b: u64, c: F64| (b, a, c));
+    let mut results = [Value::I32(0), Value::I32(0), Value::I32(0)];
+    func.call(
+        &mut store,
+        &(1_i32, 2_u64, 3.0_f64).into_values(),
+        &mut results,
+    )
+    .unwrap();
+    let (b, a, c) = <(u64, i32, f64)>::from_values(&results).unwrap();
+    assert_eq!((b, a, c), (2, 1, 3.0));
+    // Case: type mismatch upon conversion.
+    assert_eq!(
+        <(i32, i32, f64)>::from_values(&results),
+        Err(ValueError::TypeMismatch {
+            expected: ValueType::I32,
+            actual: ValueType::I64,
+        })
+    );
+    // Case: length mismatch upon conversion.
+    assert_eq!(
+        <[i64; 2]>::from_values(&results),
+        Err(ValueError::LenMismatch {
+            expected: 2,
+            actual: 3,
+        })
+    );
+    // Arrays of the same type are supported as well.
+    let sum = Func::wrap(&mut store, |a: i32, b: i32| a + b);
+    let mut result = [Value::I32(0)];
+    sum.call(&mut store, &[20, 22].into_values(), &mut result)
+        .unwrap();
+    assert_eq!(<[i32; 1]>::from_values(&result), Ok([42]));
+    assert_eq!(i32::try_from(result[0].clone()), Ok(42));
+}
+
+#[test]
+fn reexported_host_func_root_call_works() {
+    let mut store = test_setup();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "swap" (func $swap (param i32 i64) (result i64 i32)))
+            (export "swap" (func $swap))
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(store.engine());
+    linker
+        .func_wrap("env", "swap", |a: i32, b: i64| (b, a))
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let swap = instance.get_func(&store, "swap").unwrap();
+    let mut results = [Value::I64(0), Value::I32(0)];
+    swap.call(&mut store, &(1_i32, 2_i64).into_values(), &mut results)
+        .unwrap();
+    assert_eq!(<(i64, i32)>::from_values(&results), Ok((2, 1)));
+    let swap = swap.typed::<(i32, i64), (i64, i32)>(&store).unwrap();
+    assert_eq!(swap.call(&mut store, (3, 4)).unwrap(), (4, 3));
+}
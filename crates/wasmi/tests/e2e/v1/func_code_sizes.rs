@@ -0,0 +1,90 @@
+//! Tests to check if [`Module::func_code_sizes`] works as intended.
+
+use wasmi::{CompilationMode, Config, Engine, Linker, Module, Store};
+
+/// A module with an imported function and two internal functions of different size.
+const WAT: &str = r#"
+    (module
+        (import "env" "f" (func))
+        (func (export "small"))
+        (func (export "big") (param i32 i32) (result i32)
+            (i32.add
+                (i32.mul (local.get 0) (i32.const 100_000))
+                (i32.div_u (local.get 1) (i32.const 200_000))
+            )
+        )
+    )
+"#;
+
+/// Compiles [`WAT`] using `mode`.
+fn compile(mode: CompilationMode) -> Module {
+    let mut config = Config::default();
+    config.compilation_mode(mode);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    Module::new(&engine, &wasm[..]).unwrap()
+}
+
+#[test]
+fn eager_func_code_sizes() {
+    let module = compile(CompilationMode::Eager);
+    let sizes = module.func_code_sizes();
+    assert_eq!(sizes.len(), 2);
+    assert_eq!(sizes[0].func_index(), 1);
+    assert_eq!(sizes[1].func_index(), 2);
+    let small = sizes[0].size().unwrap();
+    let big = sizes[1].size().unwrap();
+    assert!(small > 0);
+    assert!(big > small);
+}
+
+#[test]
+fn lazy_func_code_sizes() {
+    let module = compile(CompilationMode::Lazy);
+    let sizes = module.func_code_sizes();
+    assert!(sizes.iter().all(|size| size.size().is_none()));
+    // Calling a function compiles it so that its size becomes available.
+    let engine = module.engine();
+    let mut store = Store::new(engine, ());
+    let mut linker = <Linker<()>>::new(engine);
+    linker.func_wrap("env", "f", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<(), ()>(&store, "small")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    let sizes = module.func_code_sizes();
+    assert!(sizes[0].size().is_some());
+    assert!(sizes[1].size().is_none());
+}
+
+#[test]
+fn pooled_consts_are_accounted() {
+    // Eagerly compiled functions share their constant values via a pool whereas
+    // lazily compiled functions own them which must not affect their code sizes.
+    let eager = compile(CompilationMode::Eager);
+    let lazy = compile(CompilationMode::Lazy);
+    let engine = lazy.engine();
+    let mut store = Store::new(engine, ());
+    let mut linker = <Linker<()>>::new(engine);
+    linker.func_wrap("env", "f", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &lazy)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<(i32, i32), i32>(&store, "big")
+        .unwrap()
+        .call(&mut store, (1, 2))
+        .unwrap();
+    assert_eq!(
+        eager.func_code_sizes()[1].size(),
+        lazy.func_code_sizes()[1].size(),
+    );
+}
@@ -0,0 +1,130 @@
+//! Tests for named parameters and results of `FuncType`.
+
+use wasmi::{
+    core::ValueType,
+    errors::{ErrorKind, FuncError},
+    Engine,
+    Error,
+    Func,
+    FuncType,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
+
+/// Returns the named `fn(lhs: i32, rhs: i32) -> (sum: i32)` function type.
+fn add_type() -> FuncType {
+    FuncType::new([ValueType::I32; 2], [ValueType::I32])
+        .with_param_names(["lhs", "rhs"])
+        .with_result_names(["sum"])
+}
+
+/// Creates a host function of type [`add_type`].
+fn add(store: &mut Store<()>) -> Func {
+    Func::new(&mut *store, add_type(), |_caller, params, results| {
+        let lhs = params[0].i32().unwrap();
+        let rhs = params[1].i32().unwrap();
+        results[0] = Value::I32(lhs.wrapping_add(rhs));
+        Ok(())
+    })
+}
+
+#[test]
+fn typed_func_mismatch_reports_names() {
+    let mut store = Store::new(&Engine::default(), ());
+    let add = add(&mut store);
+    assert_eq!(
+        add.ty(&store).to_string(),
+        "fn(lhs: i32, rhs: i32) -> (sum: i32)"
+    );
+    let error = add.typed::<(i32, i64), i32>(&store).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "encountered incorrect function parameter type at index 1 (`rhs`): expected I32 but found I64",
+    );
+    let error = add.typed::<(i32, i32), i64>(&store).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Func(FuncError::MismatchingResultType)
+    ));
+    assert_eq!(
+        error.to_string(),
+        "encountered incorrect function result type at index 0 (`sum`): expected I32 but found I64",
+    );
+}
+
+#[test]
+fn equal_types_keep_their_names() {
+    let mut store = Store::new(&Engine::default(), ());
+    let add = add(&mut store);
+    let ty = FuncType::new([ValueType::I32; 2], [ValueType::I32]).with_param_names(["a", "b"]);
+    let other = Func::new(&mut store, ty, |_caller, _params, _results| Ok(()));
+    let unnamed = Func::wrap(&mut store, |lhs: i32, rhs: i32| lhs.wrapping_add(rhs));
+    assert_eq!(
+        add.ty(&store).to_string(),
+        "fn(lhs: i32, rhs: i32) -> (sum: i32)"
+    );
+    assert_eq!(other.ty(&store).to_string(), "fn(a: i32, b: i32) -> i32");
+    assert_eq!(unnamed.ty(&store).to_string(), "fn(i32, i32) -> i32");
+}
+
+#[test]
+fn named_host_func_matches_unnamed_import() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (type $add (func (param i32 i32) (result i32)))
+            (import "env" "add" (func $add (type $add)))
+            (table funcref (elem $add))
+            (func (export "call") (param i32 i32) (result i32)
+                (call $add (local.get 0) (local.get 1))
+            )
+            (func (export "call_indirect") (param i32 i32) (result i32)
+                (call_indirect (type $add) (local.get 0) (local.get 1) (i32.const 0))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("env", "add", add(&mut store)).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    for name in ["call", "call_indirect"] {
+        let func = instance
+            .get_typed_func::<(i32, i32), i32>(&store, name)
+            .unwrap();
+        assert_eq!(func.call(&mut store, (1, 2)).unwrap(), 3);
+    }
+}
+
+#[test]
+fn signature_mismatch_displays_names() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "add" (func (param i64 i32) (result i32)))
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("env", "add", add(&mut store)).unwrap();
+    let error: Error = linker.instantiate(&mut store, &module).unwrap_err();
+    let message = error.to_string();
+    assert!(
+        message.contains("fn(i64, i32) -> i32")
+            && message.contains("fn(lhs: i32, rhs: i32) -> (sum: i32)"),
+        "{message}"
+    );
+}
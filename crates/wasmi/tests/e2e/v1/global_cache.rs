@@ -0,0 +1,48 @@
+//! Tests that the cached global variable of the executor is invalidated upon host calls.
+
+use wasmi::{core::ValueType, Caller, Engine, Global, Linker, Module, Mutability, Store, Value};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "alloc_globals" (func $alloc_globals))
+        (global $g0 (mut i32) (i32.const 10))
+        (global $g1 (mut i32) (i32.const 20))
+        (func (export "run") (result i32)
+            (global.set $g0 (i32.add (global.get $g0) (i32.const 1)))
+            ;; The host function grows the global variable storage of the store.
+            (call $alloc_globals)
+            (global.set $g0 (i32.add (global.get $g0) (i32.const 1)))
+            (global.set $g1 (i32.add (global.get $g1) (global.get $g0)))
+            (global.get $g1)
+        )
+    )
+"#;
+
+#[test]
+fn globals_are_accessible_after_host_call() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, Vec::new());
+    let mut linker = <Linker<Vec<Global>>>::new(&engine);
+    linker
+        .func_wrap("env", "alloc_globals", |mut caller: Caller<Vec<Global>>| {
+            for i in 0..1000 {
+                let global = Global::new(&mut caller, Value::I32(i), Mutability::Var);
+                caller.data_mut().push(global);
+            }
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<(), i32>(&store, "run").unwrap();
+    assert_eq!(run.call(&mut store, ()).unwrap(), 32);
+    assert_eq!(run.call(&mut store, ()).unwrap(), 46);
+    let globals = store.data();
+    assert_eq!(globals.len(), 2000);
+    assert_eq!(globals[999].ty(&store).content(), ValueType::I32);
+    assert_eq!(globals[999].get(&store).i32(), Some(999));
+}
@@ -0,0 +1,105 @@
+//! Tests for host call interceptors of a `Store`.
+
+use wasmi::{Caller, Engine, Error, Func, HostCall, Linker, Module, Store, Value};
+
+/// A log of all intercepted host function calls.
+type Log = Vec<(Option<String>, Vec<i32>, Vec<i32>)>;
+
+/// Extracts the `i32` values of `values`.
+fn i32s(values: impl Iterator<Item = Value>) -> Vec<i32> {
+    values.map(|value| value.i32().unwrap()).collect()
+}
+
+/// Returns the [`Store`], [`Linker`] and [`Module`] that calls the host function `env::add`.
+fn setup() -> (Store<Log>, Linker<Log>, Module) {
+    let engine = Engine::default();
+    let store = Store::new(&engine, Log::new());
+    let mut linker = <Linker<Log>>::new(&engine);
+    linker
+        .func_wrap("env", "add", |lhs: i32, rhs: i32| lhs + rhs)
+        .unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "add" (func $add (param i32 i32) (result i32)))
+            (func (export "run") (param i32) (result i32)
+                (call $add (local.get 0) (i32.const 1))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    (store, linker, module)
+}
+
+/// Logs the intercepted `call` into the [`Log`] of the `caller`.
+fn log_call(mut caller: Caller<Log>, call: &mut HostCall<Log>) -> Result<(), Error> {
+    let name = call
+        .name()
+        .map(|name| format!("{}::{name}", call.module().unwrap()));
+    let params = i32s(call.params());
+    call.proceed(&mut caller)?;
+    let results = i32s(call.results());
+    caller.data_mut().push((name, params, results));
+    Ok(())
+}
+
+#[test]
+fn interceptor_observes_host_calls() {
+    let (mut store, linker, module) = setup();
+    store.set_host_call_interceptor(log_call);
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    assert_eq!(run.call(&mut store, 41).unwrap(), 42);
+    assert_eq!(
+        store.data(),
+        &[(Some(String::from("env::add")), vec![41, 1], vec![42])]
+    );
+}
+
+#[test]
+fn interceptor_observes_unnamed_root_calls() {
+    let (mut store, _linker, _module) = setup();
+    store.set_host_call_interceptor(log_call);
+    let func = Func::wrap(&mut store, |value: i32| value * 2);
+    let func = func.typed::<i32, i32>(&store).unwrap();
+    assert_eq!(func.call(&mut store, 21).unwrap(), 42);
+    assert_eq!(store.data(), &[(None, vec![21], vec![42])]);
+}
+
+#[test]
+fn interceptor_rejects_host_calls() {
+    let (mut store, linker, module) = setup();
+    store.set_host_call_interceptor(|_caller, call| {
+        Err(Error::new(format!(
+            "denied call to {}",
+            call.name().unwrap()
+        )))
+    });
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    let error = run.call(&mut store, 41).unwrap_err();
+    assert!(error.to_string().contains("denied call to add"));
+}
+
+#[test]
+fn interceptor_must_proceed() {
+    let (mut store, linker, module) = setup();
+    store.set_host_call_interceptor(|_caller, _call| Ok(()));
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    assert!(run.call(&mut store, 41).is_err());
+}
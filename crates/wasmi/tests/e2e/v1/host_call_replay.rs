@@ -0,0 +1,209 @@
+//! Tests for recording and replaying host function calls of a `Store`.
+
+use wasmi::{
+    core::TrapCode,
+    Caller,
+    Engine,
+    Extern,
+    HostCallLog,
+    HostCallOutcome,
+    HostCallRecorder,
+    HostCallReplayer,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+/// The host state of the recording [`Store`].
+///
+/// Serves as source of non-determinism for the host functions.
+#[derive(Debug, Default)]
+struct Host {
+    seed: i32,
+}
+
+/// The Wasm module that calls the non-deterministic host functions.
+///
+/// - `env::random` returns a new value upon every call.
+/// - `env::fill` writes `len` random bytes to `ptr` into the linear memory.
+/// - `env::fail` traps if its parameter is non-zero.
+const WAT: &str = r#"
+    (module
+        (import "env" "random" (func $random (result i32)))
+        (import "env" "fill" (func $fill (param i32 i32)))
+        (import "env" "fail" (func $fail (param i32)))
+        (memory (export "memory") 1)
+        (func (export "run") (param i32) (result i32)
+            (call $fill (i32.const 100) (local.get 0))
+            (i32.add
+                (call $random)
+                (i32.load (i32.const 100))
+            )
+        )
+        (func (export "fail") (param i32)
+            (call $fail (local.get 0))
+        )
+    )
+"#;
+
+/// Creates a [`Linker`] defining the non-deterministic host functions.
+fn host_linker(engine: &Engine) -> Linker<Host> {
+    let mut linker = <Linker<Host>>::new(engine);
+    linker
+        .func_wrap("env", "random", |mut caller: Caller<Host>| {
+            let seed = &mut caller.data_mut().seed;
+            *seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            *seed
+        })
+        .unwrap();
+    linker
+        .func_wrap(
+            "env",
+            "fill",
+            |mut caller: Caller<Host>, ptr: u32, len: u32| {
+                let Some(Extern::Memory(memory)) = caller.get_export("memory") else {
+                    panic!("missing linear memory export")
+                };
+                let seed = caller.data().seed;
+                let (data, _) = memory.data_and_store_mut(&mut caller);
+                for (n, byte) in data[ptr as usize..][..len as usize].iter_mut().enumerate() {
+                    *byte = (seed as u8).wrapping_add(n as u8).wrapping_add(1);
+                }
+            },
+        )
+        .unwrap();
+    linker
+        .func_wrap("env", "fail", |code: i32| match code {
+            0 => Ok(()),
+            _ => Err(wasmi::Error::from(TrapCode::BadSignature)),
+        })
+        .unwrap();
+    linker
+}
+
+/// Creates a [`Linker`] defining host functions that must never be called.
+fn stub_linker(engine: &Engine) -> Linker<Host> {
+    let mut linker = <Linker<Host>>::new(engine);
+    linker
+        .func_wrap("env", "random", || -> i32 { unreachable!() })
+        .unwrap();
+    linker
+        .func_wrap("env", "fill", |_ptr: u32, _len: u32| -> () { unreachable!() })
+        .unwrap();
+    linker
+        .func_wrap("env", "fail", |_code: i32| -> () { unreachable!() })
+        .unwrap();
+    linker
+}
+
+/// Instantiates [`WAT`] into the `store` using the `linker`.
+fn instantiate(store: &mut Store<Host>, linker: &Linker<Host>) -> Instance {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Calls `run` with `len` and returns its result and the linear memory contents.
+fn run(store: &mut Store<Host>, instance: &Instance, len: i32) -> (i32, Vec<u8>) {
+    let run = instance.get_typed_func::<i32, i32>(&*store, "run").unwrap();
+    let result = run.call(&mut *store, len).unwrap();
+    let memory = instance.get_memory(&*store, "memory").unwrap();
+    (result, memory.data(&*store).to_vec())
+}
+
+/// Records calls to `run` with all `lens` and returns the results and the [`HostCallLog`].
+fn record(lens: &[i32]) -> (Vec<(i32, Vec<u8>)>, HostCallLog) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, Host { seed: 42 });
+    let recorder = HostCallRecorder::new();
+    recorder.install(&mut store);
+    let instance = instantiate(&mut store, &host_linker(&engine));
+    let results = lens
+        .iter()
+        .map(|len| run(&mut store, &instance, *len))
+        .collect();
+    (results, recorder.take_log())
+}
+
+/// Creates a replaying [`Store`] and instance for the `log`.
+fn replay(log: HostCallLog) -> (Store<Host>, Instance, HostCallReplayer) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, Host::default());
+    let replayer = HostCallReplayer::new(log);
+    replayer.install(&mut store);
+    let instance = instantiate(&mut store, &stub_linker(&engine));
+    (store, instance, replayer)
+}
+
+#[test]
+fn record_captures_host_calls() {
+    let (_, log) = record(&[4]);
+    assert_eq!(log.len(), 2);
+    let fill = &log.records()[0];
+    assert_eq!(fill.module(), Some("env"));
+    assert_eq!(fill.name(), Some("fill"));
+    assert_eq!(fill.params().len(), 2);
+    assert_eq!(fill.params()[0].i32(), Some(100));
+    assert_eq!(fill.params()[1].i32(), Some(4));
+    assert_eq!(fill.memory_writes().len(), 1);
+    assert_eq!(fill.memory_writes()[0].offset(), 100);
+    assert_eq!(fill.memory_writes()[0].bytes(), [43, 44, 45, 46]);
+    let random = &log.records()[1];
+    assert_eq!(random.name(), Some("random"));
+    assert!(random.memory_writes().is_empty());
+    assert!(matches!(random.outcome(), HostCallOutcome::Return(results) if results.len() == 1));
+}
+
+#[test]
+fn replay_reproduces_execution() {
+    let lens = [4, 8, 2];
+    let (expected, log) = record(&lens);
+    let (mut store, instance, replayer) = replay(log);
+    for (len, expected) in lens.iter().zip(expected) {
+        assert_eq!(run(&mut store, &instance, *len), expected);
+    }
+    assert_eq!(replayer.remaining(), 0);
+}
+
+#[test]
+fn replay_reproduces_traps() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, Host::default());
+    let recorder = HostCallRecorder::new();
+    recorder.install(&mut store);
+    let instance = instantiate(&mut store, &host_linker(&engine));
+    let fail = instance.get_typed_func::<i32, ()>(&store, "fail").unwrap();
+    fail.call(&mut store, 0).unwrap();
+    let error = fail.call(&mut store, 1).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::BadSignature));
+
+    let (mut store, instance, _replayer) = replay(recorder.take_log());
+    let fail = instance.get_typed_func::<i32, ()>(&store, "fail").unwrap();
+    fail.call(&mut store, 0).unwrap();
+    let error = fail.call(&mut store, 1).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::BadSignature));
+}
+
+#[test]
+fn replay_detects_divergence() {
+    let (_, log) = record(&[4]);
+    let (mut store, instance, _replayer) = replay(log);
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    let error = run.call(&mut store, 5).unwrap_err();
+    assert!(error.to_string().contains("host call replay diverged"));
+}
+
+#[test]
+fn replay_detects_exhausted_log() {
+    let (_, log) = record(&[4]);
+    let (mut store, instance, _replayer) = replay(log);
+    run(&mut store, &instance, 4);
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    let error = run.call(&mut store, 4).unwrap_err();
+    assert!(error.to_string().contains("exhausted"));
+}
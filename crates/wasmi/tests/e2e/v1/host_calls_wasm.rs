@@ -1,7 +1,22 @@
 //! Test to assert that host functions that call back into
 //! Wasm works correctly.
 
-use wasmi::{Caller, Engine, Extern, Func, Linker, Module, Store};
+use wasmi::{
+    core::TrapCode,
+    Caller,
+    Config,
+    Engine,
+    Error,
+    Extern,
+    Func,
+    Linker,
+    Module,
+    StackLimits,
+    Store,
+    TypedFunc,
+    TypedResumableCall,
+    Value,
+};
 
 fn test_setup() -> (Store<()>, Linker<()>) {
     let engine = Engine::default();
@@ -57,3 +72,192 @@ fn host_calls_wasm() {
     let result = wasm_fn.call(&mut store, input).unwrap();
     assert_eq!(result, expected);
 }
+
+/// Wasm that recursively calls itself via the `env.host_fn` host function.
+///
+/// The exported `count` function returns `n` after `n` host→Wasm re-entrancies.
+const REENTRANT_WASM: &str = r#"
+    (module
+        (import "env" "host_fn" (func $host_fn (param i32) (result i32)))
+        (func (export "count") (param $n i32) (result i32)
+            (if (result i32) (i32.eqz (local.get $n))
+                (then (i32.const 0))
+                (else
+                    (i32.add
+                        (call $host_fn (i32.sub (local.get $n) (i32.const 1)))
+                        (i32.const 1)
+                    )
+                )
+            )
+        )
+    )
+"#;
+
+/// Creates a [`Store`] with the `count` function of [`REENTRANT_WASM`].
+///
+/// The `env.host_fn` calls `count` of the calling instance via its [`Caller`].
+fn reentrant_setup(config: &Config) -> (Store<()>, TypedFunc<i32, i32>) {
+    let engine = Engine::new(config);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "host_fn",
+            |mut caller: Caller<()>, n: i32| -> Result<i32, Error> {
+                caller
+                    .get_export("count")
+                    .and_then(Extern::into_func)
+                    .unwrap()
+                    .typed::<i32, i32>(&caller)?
+                    .call(&mut caller, n)
+            },
+        )
+        .unwrap();
+    let wasm = wat::parse_str(REENTRANT_WASM).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let count = instance
+        .get_typed_func::<i32, i32>(&store, "count")
+        .unwrap();
+    (store, count)
+}
+
+#[test]
+fn reentrant_recursion() {
+    let (mut store, count) = reentrant_setup(&Config::default());
+    assert_eq!(count.call(&mut store, 0).unwrap(), 0);
+    assert_eq!(count.call(&mut store, 1).unwrap(), 1);
+    assert_eq!(count.call(&mut store, 100).unwrap(), 100);
+}
+
+#[test]
+fn reentrant_recursion_shares_stack_limit() {
+    let mut config = Config::default();
+    config.set_stack_limits(StackLimits::new(256, 1024 * 1024, 64).unwrap());
+    let (mut store, count) = reentrant_setup(&config);
+    // Every re-entrancy level uses one Wasm call frame so 64 levels fit exactly.
+    assert_eq!(count.call(&mut store, 63).unwrap(), 63);
+    let error = count.call(&mut store, 64).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::StackOverflow));
+    // The store recovers from the stack overflow.
+    assert_eq!(count.call(&mut store, 63).unwrap(), 63);
+}
+
+#[test]
+fn reentrant_recursion_max_depth() {
+    let mut config = Config::default();
+    config.max_reentrancy_depth(10);
+    let (mut store, count) = reentrant_setup(&config);
+    assert_eq!(count.call(&mut store, 10).unwrap(), 10);
+    let error = count.call(&mut store, 11).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::StackOverflow));
+    // Re-entrancy can be disabled entirely.
+    config.max_reentrancy_depth(0);
+    let (mut store, count) = reentrant_setup(&config);
+    assert_eq!(count.call(&mut store, 0).unwrap(), 0);
+    let error = count.call(&mut store, 1).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::StackOverflow));
+}
+
+#[test]
+fn reentrant_recursion_unbounded() {
+    let (mut store, count) = reentrant_setup(&Config::default());
+    let error = count.call(&mut store, i32::MAX).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::StackOverflow));
+}
+
+#[test]
+fn reentrant_fuel() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let (mut store, count) = reentrant_setup(&config);
+    store.add_fuel(1_000).unwrap();
+    assert_eq!(count.call(&mut store, 1).unwrap(), 1);
+    let consumed_1 = store.fuel_consumed().unwrap();
+    assert_eq!(count.call(&mut store, 2).unwrap(), 2);
+    let consumed_2 = store.fuel_consumed().unwrap() - consumed_1;
+    // Re-entrant executions consume fuel of the same store.
+    assert!(consumed_2 > consumed_1);
+    // Running out of fuel in a re-entrant execution traps the outer execution.
+    let (mut store, count) = reentrant_setup(&config);
+    store.add_fuel(consumed_1).unwrap();
+    let error = count.call(&mut store, 50).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+}
+
+#[test]
+fn reentrant_resumable() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    // Calls back into the `inner` Wasm function.
+    linker
+        .func_wrap(
+            "env",
+            "outer_host",
+            |mut caller: Caller<()>, input: i32| -> Result<i32, Error> {
+                caller
+                    .get_export("inner")
+                    .and_then(Extern::into_func)
+                    .unwrap()
+                    .typed::<i32, i32>(&caller)?
+                    .call(&mut caller, input)
+            },
+        )
+        .unwrap();
+    // Fails which makes the re-entrant execution fail.
+    linker
+        .func_wrap(
+            "env",
+            "inner_host",
+            |_caller: Caller<()>, input: i32| -> Result<i32, Error> { Err(Error::i32_exit(input)) },
+        )
+        .unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "outer_host" (func $outer_host (param i32) (result i32)))
+            (import "env" "inner_host" (func $inner_host (param i32) (result i32)))
+            (func (export "outer") (param i32) (result i32)
+                (i32.add (call $outer_host (local.get 0)) (i32.const 1))
+            )
+            (func (export "inner") (param i32) (result i32)
+                (call $inner_host (local.get 0))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let outer = instance
+        .get_typed_func::<i32, i32>(&store, "outer")
+        .unwrap();
+    let invocation = match outer.call_resumable(&mut store, 42).unwrap() {
+        TypedResumableCall::Resumable(invocation) => invocation,
+        TypedResumableCall::Finished(_)
+        | TypedResumableCall::Yielded(_)
+        | TypedResumableCall::OutOfFuel(_) => {
+            panic!("expected the resumable call to be suspended")
+        }
+    };
+    // The error of the re-entrant execution suspended the outer resumable call.
+    assert_eq!(invocation.host_error().i32_exit_status(), Some(42));
+    match invocation.resume(&mut store, &[Value::I32(10)]).unwrap() {
+        TypedResumableCall::Finished(result) => assert_eq!(result, 11),
+        TypedResumableCall::Resumable(_)
+        | TypedResumableCall::Yielded(_)
+        | TypedResumableCall::OutOfFuel(_) => {
+            panic!("expected the resumable call to finish")
+        }
+    }
+}
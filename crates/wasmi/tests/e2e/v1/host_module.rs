@@ -0,0 +1,193 @@
+//! Tests for the `#[host_module]` macro.
+
+use core::fmt;
+use wasmi::{core::HostError, host_module, Caller, Engine, Error, Instance, Linker, Module, Store};
+
+#[derive(Debug, Default)]
+struct Host {
+    log: Vec<String>,
+    bytes: usize,
+    counter: i64,
+}
+
+#[derive(Debug)]
+struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "counter overflow")
+    }
+}
+
+impl HostError for Overflow {}
+
+#[host_module(module = "host")]
+impl Host {
+    fn print(&mut self, message: &str) {
+        self.log.push(message.into());
+    }
+
+    fn count_bytes(&mut self, bytes: &[u8]) -> u32 {
+        self.bytes += bytes.len();
+        bytes.iter().map(|&byte| u32::from(byte)).sum()
+    }
+
+    #[host(name = "incr")]
+    fn increment(&mut self, delta: u8, negate: bool) -> Result<i64, Overflow> {
+        let delta = i64::from(delta);
+        let delta = if negate { -delta } else { delta };
+        self.counter = self.counter.checked_add(delta).ok_or(Overflow)?;
+        Ok(self.counter)
+    }
+
+    fn counter(&self) -> i64 {
+        self.counter
+    }
+
+    fn depth(caller: Caller<'_, Self>, value: i32) -> Result<i32, Error> {
+        if value < 0 {
+            return Err(Error::new("negative depth"));
+        }
+        Ok(value + caller.data().log.len() as i32)
+    }
+
+    fn add(lhs: i32, rhs: i32) -> i32 {
+        lhs.wrapping_add(rhs)
+    }
+
+    #[host(skip)]
+    fn reset(&mut self) {
+        self.counter = 0;
+    }
+}
+
+const WAT: &str = r#"
+    (module
+        (import "host" "print" (func $print (param i32 i32)))
+        (import "host" "count_bytes" (func $count_bytes (param i32 i32) (result i32)))
+        (import "host" "incr" (func $incr (param i32 i32) (result i64)))
+        (import "host" "counter" (func $counter (result i64)))
+        (import "host" "depth" (func $depth (param i32) (result i32)))
+        (import "host" "add" (func $add (param i32 i32) (result i32)))
+        (memory (export "memory") 1)
+        (data (i32.const 0) "Hello\01\02\ff\fe")
+        (func (export "print") (param i32 i32)
+            (call $print (local.get 0) (local.get 1))
+        )
+        (func (export "count_bytes") (param i32 i32) (result i32)
+            (call $count_bytes (local.get 0) (local.get 1))
+        )
+        (func (export "incr") (param i32 i32) (result i64)
+            (call $incr (local.get 0) (local.get 1))
+        )
+        (func (export "counter") (result i64)
+            (call $counter)
+        )
+        (func (export "depth") (param i32) (result i32)
+            (call $depth (local.get 0))
+        )
+        (func (export "add") (param i32 i32) (result i32)
+            (call $add (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+fn setup() -> (Store<Host>, Instance) {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, Host::default());
+    let mut linker = <Linker<Host>>::new(&engine);
+    Host::add_to_linker(&mut linker).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn host_module_works() {
+    let (mut store, instance) = setup();
+    let print = instance
+        .get_typed_func::<(u32, u32), ()>(&store, "print")
+        .unwrap();
+    let count_bytes = instance
+        .get_typed_func::<(u32, u32), u32>(&store, "count_bytes")
+        .unwrap();
+    let incr = instance
+        .get_typed_func::<(i32, i32), i64>(&store, "incr")
+        .unwrap();
+    let counter = instance
+        .get_typed_func::<(), i64>(&store, "counter")
+        .unwrap();
+    let depth = instance
+        .get_typed_func::<i32, i32>(&store, "depth")
+        .unwrap();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    print.call(&mut store, (0, 5)).unwrap();
+    print.call(&mut store, (1, 3)).unwrap();
+    assert_eq!(store.data().log, ["Hello", "ell"]);
+    assert_eq!(count_bytes.call(&mut store, (5, 4)).unwrap(), 0x200);
+    assert_eq!(store.data().bytes, 4);
+    assert_eq!(incr.call(&mut store, (200, 0)).unwrap(), 200);
+    assert_eq!(incr.call(&mut store, (50, 1)).unwrap(), 150);
+    assert_eq!(counter.call(&mut store, ()).unwrap(), 150);
+    assert_eq!(depth.call(&mut store, 10).unwrap(), 12);
+    assert_eq!(add.call(&mut store, (i32::MAX, 1)).unwrap(), i32::MIN);
+    store.data_mut().reset();
+    assert_eq!(counter.call(&mut store, ()).unwrap(), 0);
+}
+
+#[test]
+fn skipped_methods_are_not_defined() {
+    let engine = Engine::default();
+    let mut linker = <Linker<Host>>::new(&engine);
+    Host::add_to_linker(&mut linker).unwrap();
+    // Skipped and renamed methods are not defined under their method names.
+    linker.func_wrap("host", "reset", |_: i32| ()).unwrap();
+    linker.func_wrap("host", "increment", |_: i32| ()).unwrap();
+    assert!(linker.func_wrap("host", "incr", |_: i32| ()).is_err());
+    // Defining the host module twice fails due to duplicate definitions.
+    assert!(Host::add_to_linker(&mut linker).is_err());
+}
+
+#[test]
+fn host_module_errors() {
+    let (mut store, instance) = setup();
+    let print = instance
+        .get_typed_func::<(u32, u32), ()>(&store, "print")
+        .unwrap();
+    let incr = instance
+        .get_typed_func::<(i32, i32), i64>(&store, "incr")
+        .unwrap();
+    let depth = instance
+        .get_typed_func::<i32, i32>(&store, "depth")
+        .unwrap();
+    // Invalid UTF-8 string.
+    assert!(print.call(&mut store, (7, 2)).is_err());
+    // Out of bounds guest slice.
+    assert_eq!(
+        print
+            .call(&mut store, (65535, 2))
+            .unwrap_err()
+            .as_trap_code(),
+        Some(wasmi::core::TrapCode::MemoryOutOfBounds)
+    );
+    // Out of bounds integer and invalid `bool`.
+    assert!(incr.call(&mut store, (256, 0)).is_err());
+    assert!(incr.call(&mut store, (1, 2)).is_err());
+    assert_eq!(store.data().counter, 0);
+    // Host errors are converted to `wasmi::Error`.
+    store.data_mut().counter = i64::MAX;
+    let error = incr.call(&mut store, (1, 0)).unwrap_err();
+    assert!(error.downcast_ref::<Overflow>().is_some());
+    assert_eq!(
+        depth.call(&mut store, -1).unwrap_err().to_string(),
+        "negative depth"
+    );
+    assert!(store.data().log.is_empty());
+}
@@ -0,0 +1,77 @@
+//! Tests for converting panics of host functions into traps via `Config::host_panic_as_trap`.
+
+use wasmi::{Config, Engine, Func, Linker, Module, Store};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "host" (func $host (param i32) (result i32)))
+        (func (export "call_host") (param i32) (result i32)
+            (call $host (local.get 0))
+        )
+    )
+"#;
+
+/// Host function that panics for non-positive inputs and increments its input otherwise.
+fn host(input: i32) -> i32 {
+    if input <= 0 {
+        panic!("invalid input: {input}");
+    }
+    input + 1
+}
+
+fn store(host_panic_as_trap: bool) -> (Store<()>, Func) {
+    let mut config = Config::default();
+    config.host_panic_as_trap(host_panic_as_trap);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "host", host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, "call_host").unwrap();
+    (store, func)
+}
+
+#[test]
+fn host_panic_as_trap() {
+    let (mut store, func) = store(true);
+    let func = func.typed::<i32, i32>(&store).unwrap();
+    let error = func.call(&mut store, 0).unwrap_err();
+    assert_eq!(error.kind().as_host_panic(), Some("invalid input: 0"));
+    assert_eq!(
+        error.to_string(),
+        "host function panicked: invalid input: 0"
+    );
+    // The store remains usable after a caught host panic.
+    assert_eq!(func.call(&mut store, 1).unwrap(), 2);
+}
+
+#[test]
+fn host_panic_as_trap_for_root_host_func() {
+    let mut config = Config::default();
+    config.host_panic_as_trap(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let func = Func::wrap(&mut store, || -> () {
+        panic!("{}", String::from("owned message"))
+    });
+    let error = func
+        .typed::<(), ()>(&store)
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert_eq!(error.kind().as_host_panic(), Some("owned message"));
+}
+
+#[test]
+#[should_panic(expected = "invalid input: 0")]
+fn host_panic_unwinds_by_default() {
+    let (mut store, func) = store(false);
+    let func = func.typed::<i32, i32>(&store).unwrap();
+    let _ = func.call(&mut store, 0);
+}
@@ -0,0 +1,163 @@
+//! Tests to check if Wasmi's hot module reload works as intended.
+
+use wasmi::{
+    errors::{ErrorKind, InstantiationError},
+    Caller,
+    Engine,
+    Error,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+/// The first version of the guest code counting up by 1.
+const V1: &str = r#"
+    (module
+        (memory (export "mem") 1)
+        (global $count (mut i32) (i32.const 0))
+        (data (i32.const 0) "v1")
+        (func (export "tick") (result i32)
+            (global.set $count (i32.add (global.get $count) (i32.const 1)))
+            (global.get $count)
+        )
+    )
+"#;
+
+/// The second version of the guest code counting up by 10.
+const V2: &str = r#"
+    (module
+        (memory (export "mem") 1)
+        (global $count (mut i32) (i32.const 1000))
+        (data (i32.const 0) "v2")
+        (func (export "tick") (result i32)
+            (global.set $count (i32.add (global.get $count) (i32.const 10)))
+            (global.get $count)
+        )
+    )
+"#;
+
+/// Compiles the `wat` into a [`Module`].
+fn compile(engine: &Engine, wat: &str) -> Module {
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(engine, &wasm[..]).unwrap()
+}
+
+/// Calls the exported `tick` function of the `instance`.
+fn tick(store: &mut Store<()>, instance: Instance) -> i32 {
+    instance
+        .get_typed_func::<(), i32>(&*store, "tick")
+        .unwrap()
+        .call(store, ())
+        .unwrap()
+}
+
+#[test]
+fn reload_preserves_state() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let instance = linker
+        .instantiate(&mut store, &compile(&engine, V1))
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    assert_eq!(tick(&mut store, instance), 1);
+    assert_eq!(tick(&mut store, instance), 2);
+    linker
+        .reload(&mut store, &instance, &compile(&engine, V2))
+        .unwrap();
+    // The new code operates on the preserved global variable.
+    assert_eq!(tick(&mut store, instance), 12);
+    // The linear memory is preserved and not overwritten by the new data segments.
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    assert_eq!(&memory.data(&store)[..2], b"v1");
+}
+
+#[test]
+fn reload_incompatible_layout_fails() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let instance = linker
+        .instantiate(&mut store, &compile(&engine, V1))
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let v3 = compile(
+        &engine,
+        r#"
+        (module
+            (memory (export "mem") 1)
+            (global $count (mut i64) (i64.const 0))
+            (func (export "tick") (result i32) (i32.const 0))
+        )
+    "#,
+    );
+    let error = linker.reload(&mut store, &instance, &v3).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Instantiation(InstantiationError::Global(_))
+    ));
+    let v4 = compile(
+        &engine,
+        r#"
+        (module
+            (memory (export "mem") 1)
+            (func (export "tick") (result i32) (i32.const 0))
+        )
+    "#,
+    );
+    let error = linker.reload(&mut store, &instance, &v4).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Instantiation(InstantiationError::IncompatibleReload)
+    ));
+    // The failed reloads did not affect the instance.
+    assert_eq!(tick(&mut store, instance), 1);
+}
+
+#[test]
+fn reload_while_executing_fails() {
+    let engine = Engine::default();
+    let v2 = compile(&engine, V2);
+    let mut store = Store::new(&engine, None);
+    let mut linker = <Linker<Option<Instance>>>::new(&engine);
+    let reloader = <Linker<Option<Instance>>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "reload",
+            move |mut caller: Caller<Option<Instance>>| -> Result<(), Error> {
+                let instance = caller.data().unwrap();
+                reloader.reload(&mut caller, &instance, &v2)
+            },
+        )
+        .unwrap();
+    let module = compile(
+        &engine,
+        r#"
+        (module
+            (import "env" "reload" (func $reload))
+            (memory (export "mem") 1)
+            (global $count (mut i32) (i32.const 0))
+            (func (export "reload") (call $reload))
+        )
+    "#,
+    );
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    *store.data_mut() = Some(instance);
+    let error = instance
+        .get_typed_func::<(), ()>(&store, "reload")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Instantiation(InstantiationError::ReloadWhileExecuting)
+    ));
+}
@@ -0,0 +1,76 @@
+//! Tests for the import policy of a `Store`.
+
+use wasmi::{
+    errors::{ErrorKind, InstantiationError},
+    Engine,
+    Error,
+    ImportPolicy,
+    Linker,
+    Module,
+    Store,
+};
+
+/// Returns a [`Linker`] defining `env::print` and `env::exit` and a [`Module`] importing both.
+fn setup() -> (Engine, Linker<()>, Module) {
+    let engine = Engine::default();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "print", |_: i32| {}).unwrap();
+    linker.func_wrap("env", "exit", |_: i32| {}).unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "print" (func (param i32)))
+            (import "env" "exit" (func (param i32)))
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    (engine, linker, module)
+}
+
+/// Asserts that `error` was caused by the denied import `module::name`.
+fn assert_import_denied(error: Error, module: &str, name: &str) {
+    match error.kind() {
+        ErrorKind::Instantiation(InstantiationError::ImportDenied { name: import }) => {
+            assert_eq!(import.module(), module);
+            assert_eq!(import.name(), name);
+        }
+        _ => panic!("expected denied import but found: {error}"),
+    }
+}
+
+#[test]
+fn default_policy_allows_imports() {
+    let (engine, linker, module) = setup();
+    let mut store = Store::new(&engine, ());
+    linker.instantiate(&mut store, &module).unwrap();
+}
+
+#[test]
+fn denied_import_fails() {
+    let (engine, linker, module) = setup();
+    let mut store = Store::new(&engine, ());
+    store.set_import_policy(ImportPolicy::new().deny("env", "exit"));
+    let error = linker.instantiate(&mut store, &module).unwrap_err();
+    assert_import_denied(error, "env", "exit");
+}
+
+#[test]
+fn denied_module_fails() {
+    let (engine, linker, module) = setup();
+    let mut store = Store::new(&engine, ());
+    store.set_import_policy(ImportPolicy::new().deny_module("env"));
+    let error = linker.instantiate(&mut store, &module).unwrap_err();
+    assert_import_denied(error, "env", "print");
+}
+
+#[test]
+fn policy_is_per_store() {
+    let (engine, linker, module) = setup();
+    let mut trusted = Store::new(&engine, ());
+    let mut untrusted = Store::new(&engine, ());
+    untrusted.set_import_policy(ImportPolicy::new().deny("env", "exit"));
+    linker.instantiate(&mut trusted, &module).unwrap();
+    assert!(linker.instantiate(&mut untrusted, &module).is_err());
+}
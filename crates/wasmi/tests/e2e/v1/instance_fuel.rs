@@ -0,0 +1,97 @@
+//! Tests to check if per-instance fuel accounts work as intended.
+
+use wasmi::{core::TrapCode, Config, Engine, Instance, Linker, Module, Store};
+
+/// A module that counts down from its parameter to zero.
+const COUNTDOWN: &str = r#"
+    (module
+        (func (export "test") (param $n i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+        )
+    )
+"#;
+
+/// Setup a fuel metered [`Store`] with two instances of the [`COUNTDOWN`] module.
+fn test_setup() -> (Store<()>, Instance, Instance) {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    store.add_fuel(1_000_000).unwrap();
+    let linker = <Linker<()>>::new(&engine);
+    let wasm = wat::parse_str(COUNTDOWN).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut instantiate = || {
+        linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+    };
+    let (a, b) = (instantiate(), instantiate());
+    (store, a, b)
+}
+
+/// Calls the `test` function of the `instance` with `n`.
+fn call(store: &mut Store<()>, instance: Instance, n: i32) -> Result<(), wasmi::Error> {
+    instance
+        .get_typed_func::<i32, ()>(&*store, "test")
+        .unwrap()
+        .call(store, n)
+}
+
+#[test]
+fn instances_are_billed_independently() {
+    let (mut store, a, b) = test_setup();
+    store.add_instance_fuel(&a, 1_000).unwrap();
+    store.add_instance_fuel(&b, 1_000).unwrap();
+    call(&mut store, a, 10).unwrap();
+    call(&mut store, b, 20).unwrap();
+    let consumed_a = store.instance_fuel_consumed(&a).unwrap();
+    let consumed_b = store.instance_fuel_consumed(&b).unwrap();
+    assert!(consumed_a > 0);
+    assert!(consumed_b > consumed_a);
+    // The store itself is charged for both instances.
+    assert_eq!(store.fuel_consumed(), Some(consumed_a + consumed_b));
+}
+
+#[test]
+fn exhausted_account_traps_only_its_instance() {
+    let (mut store, a, b) = test_setup();
+    store.add_instance_fuel(&a, 10).unwrap();
+    let error = call(&mut store, a, 1_000).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    // Instance `b` has no fuel account and is only limited by the store.
+    call(&mut store, b, 1_000).unwrap();
+    assert_eq!(store.instance_fuel_consumed(&b), None);
+}
+
+#[test]
+fn remove_instance_fuel_works() {
+    let (mut store, a, _b) = test_setup();
+    store.add_instance_fuel(&a, 10).unwrap();
+    assert!(call(&mut store, a, 1_000).is_err());
+    assert_eq!(store.remove_instance_fuel(&a), Some(10));
+    call(&mut store, a, 1_000).unwrap();
+    assert_eq!(store.instance_fuel_consumed(&a), None);
+}
+
+#[test]
+fn instance_fuel_requires_fuel_metering() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(COUNTDOWN).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    assert!(store.add_instance_fuel(&instance, 10).is_err());
+}
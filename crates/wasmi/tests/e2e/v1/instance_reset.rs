@@ -0,0 +1,132 @@
+//! Tests for recycling instances via `Instance::reset`.
+
+use wasmi::{
+    errors::{ErrorKind, InstantiationError},
+    Config,
+    Engine,
+    Global,
+    Instance,
+    Linker,
+    Module,
+    Mutability,
+    Store,
+    Value,
+};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "calls" (global $calls (mut i32)))
+        (global $counter (export "counter") (mut i32) (i32.const 10))
+        (memory (export "memory") 1 2)
+        (table $table (export "table") 2 4 funcref)
+        (data (i32.const 0) "init")
+        (data $passive "passive")
+        (elem (table $table) (i32.const 1) func $answer)
+        (func $answer (result i32)
+            (i32.const 42)
+        )
+        (func (export "answer") (result i32)
+            (call_indirect (result i32) (i32.const 1))
+        )
+        ;; Loads the passive data segment to offset 100 and drops it afterwards.
+        (func (export "load")
+            (memory.init $passive (i32.const 100) (i32.const 0) (i32.const 7))
+            (data.drop $passive)
+        )
+        (func (export "scribble")
+            (global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+            (i32.store (i32.const 0) (i32.const -1))
+            (drop (memory.grow (i32.const 1)))
+            (table.set $table (i32.const 1) (ref.null func))
+            (drop (table.grow $table (ref.null func) (i32.const 2)))
+        )
+    )
+"#;
+
+/// Instantiates [`WAT`] and returns the instance and the imported `calls` global.
+fn instantiate(store: &mut Store<()>) -> (Instance, Global) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let calls = Global::new(&mut *store, Value::I32(0), Mutability::Var);
+    let mut linker = <Linker<()>>::new(store.engine());
+    linker.define("env", "calls", calls).unwrap();
+    let instance = linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap();
+    (instance, calls)
+}
+
+/// Calls the exported function `name` of `instance` without parameters and results.
+fn call(store: &mut Store<()>, instance: Instance, name: &str) {
+    instance
+        .get_typed_func::<(), ()>(&*store, name)
+        .unwrap()
+        .call(store, ())
+        .unwrap();
+}
+
+#[test]
+fn reset_restores_instance() {
+    let mut config = Config::default();
+    config.instance_reset(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let (instance, calls) = instantiate(&mut store);
+    let counter = instance.get_global(&store, "counter").unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let table = instance.get_table(&store, "table").unwrap();
+    let answer = instance
+        .get_typed_func::<(), i32>(&store, "answer")
+        .unwrap();
+    call(&mut store, instance, "load");
+    call(&mut store, instance, "scribble");
+    assert_eq!(counter.get(&store).i32(), Some(11));
+    assert_eq!(&memory.data(&store)[100..107], b"passive");
+    assert_eq!(table.size(&store), 4);
+    assert!(answer.call(&mut store, ()).is_err());
+
+    instance.reset(&mut store).unwrap();
+    assert_eq!(counter.get(&store).i32(), Some(10));
+    assert_eq!(u32::from(memory.current_pages(&store)), 1);
+    assert_eq!(&memory.data(&store)[0..4], b"init");
+    assert_eq!(&memory.data(&store)[100..107], &[0x00; 7]);
+    assert_eq!(table.size(&store), 2);
+    assert_eq!(answer.call(&mut store, ()).unwrap(), 42);
+    // Imported entities are owned by the host and therefore not reset.
+    assert_eq!(calls.get(&store).i32(), Some(1));
+    // The dropped passive data segment is restored.
+    call(&mut store, instance, "load");
+    assert_eq!(&memory.data(&store)[100..107], b"passive");
+}
+
+#[test]
+fn reset_is_repeatable() {
+    let mut config = Config::default();
+    config.instance_reset(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let (instance, calls) = instantiate(&mut store);
+    let counter = instance.get_global(&store, "counter").unwrap();
+    for round in 1..=3 {
+        call(&mut store, instance, "scribble");
+        assert_eq!(counter.get(&store).i32(), Some(11));
+        instance.reset(&mut store).unwrap();
+        assert_eq!(counter.get(&store).i32(), Some(10));
+        assert_eq!(calls.get(&store).i32(), Some(round));
+    }
+}
+
+#[test]
+fn reset_disabled() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let (instance, _calls) = instantiate(&mut store);
+    let error = instance.reset(&mut store).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Instantiation(InstantiationError::ResetDisabled)
+    ));
+}
@@ -0,0 +1,87 @@
+//! Tests to check if Wasmi's instruction budget metering works as intended.
+
+use wasmi::{core::TrapCode, Config, Engine, Func, Linker, Module, Store};
+
+/// Setup [`Store`] and the exported `test` [`Func`] with instruction budget metering.
+fn test_setup(wat: &str) -> (Store<()>, Func) {
+    let mut config = Config::default();
+    config.consume_instructions(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, "test").unwrap();
+    (store, func)
+}
+
+/// A function that counts down from its parameter to zero.
+const COUNTDOWN: &str = r#"
+    (module
+        (func (export "test") (param $n i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+        )
+    )
+"#;
+
+#[test]
+fn empty_budget_traps() {
+    let (mut store, func) = test_setup(COUNTDOWN);
+    let func = func.typed::<i32, ()>(&store).unwrap();
+    let error = func.call(&mut store, 0).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfInstructions));
+}
+
+#[test]
+fn exhausted_budget_traps() {
+    let (mut store, func) = test_setup(COUNTDOWN);
+    let func = func.typed::<i32, ()>(&store).unwrap();
+    store.set_instruction_budget(1_000);
+    let error = func.call(&mut store, 1_000_000).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfInstructions));
+}
+
+#[test]
+fn sufficient_budget_succeeds() {
+    let (mut store, func) = test_setup(COUNTDOWN);
+    let func = func.typed::<i32, ()>(&store).unwrap();
+    store.set_instruction_budget(1_000);
+    func.call(&mut store, 10).unwrap();
+    let remaining = store.instruction_budget();
+    assert!(remaining < 1_000);
+    // Executing more iterations consumes more of the instruction budget.
+    store.set_instruction_budget(1_000);
+    func.call(&mut store, 20).unwrap();
+    assert!(store.instruction_budget() < remaining);
+}
+
+#[test]
+fn fuel_metering_takes_precedence() {
+    let mut config = Config::default();
+    config.consume_fuel(true).consume_instructions(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    store.add_fuel(1_000).unwrap();
+    let wasm = wat::parse_str(COUNTDOWN).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func::<i32, ()>(&store, "test").unwrap();
+    func.call(&mut store, 10).unwrap();
+    assert_eq!(store.instruction_budget(), 0);
+    assert!(store.fuel_consumed().unwrap() > 0);
+}
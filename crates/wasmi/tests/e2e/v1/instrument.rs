@@ -0,0 +1,248 @@
+//! Tests for the built-in `InstructionCounter` and `CoverageProbes` instrumentations.
+
+use std::sync::{Arc, Mutex};
+use wasmi::{
+    CoverageProbes,
+    Engine,
+    FuncBody,
+    FuncTransform,
+    InstructionCounter,
+    Linker,
+    Module,
+    Store,
+};
+
+/// Wasm module with straight-line code, a branch and a loop.
+const WAT: &str = r#"
+    (module
+        (func (export "add") (result i32)
+            (i32.add (i32.const 1) (i32.const 2))
+        )
+        (func (export "select") (param i32) (result i32)
+            (if (result i32) (local.get 0)
+                (then (i32.const 10))
+                (else (i32.const 20))
+            )
+        )
+        (func (export "sum") (param $n i32) (result i32)
+            (local $sum i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $sum (i32.add (local.get $sum) (local.get $n)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $sum)
+        )
+    )
+"#;
+
+/// Instantiates the Wasm module `wasm` with a `probe` host function recording its arguments.
+fn instantiate(engine: &Engine, module: &Module) -> (Store<Vec<i32>>, wasmi::Instance) {
+    let mut store = Store::new(engine, Vec::new());
+    let mut linker = <Linker<Vec<i32>>>::new(engine);
+    linker
+        .func_wrap(
+            "env",
+            "probe",
+            |mut caller: wasmi::Caller<Vec<i32>>, id: i32| {
+                caller.data_mut().push(id);
+            },
+        )
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Instruments [`WAT`] with an [`InstructionCounter`] exported as `"count"`.
+///
+/// Returns a closure calling the exported function `name` with `arg` which returns
+/// the result of the call and the number of executed instructions.
+fn counted(name: &'static str) -> impl FnMut(i32) -> (i32, i64) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let wasm = InstructionCounter::instrument(&wasm, "count").unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let (mut store, instance) = instantiate(&engine, &module);
+    let count = instance.get_global(&store, "count").unwrap();
+    let func = instance.get_func(&store, name).unwrap();
+    move |arg| {
+        let before = count.get(&store).i64().unwrap();
+        let result = match name {
+            "add" => func.typed::<(), i32>(&store).unwrap().call(&mut store, ()),
+            _ => func
+                .typed::<i32, i32>(&store)
+                .unwrap()
+                .call(&mut store, arg),
+        }
+        .unwrap();
+        let after = count.get(&store).i64().unwrap();
+        (result, after - before)
+    }
+}
+
+#[test]
+fn count_straight_line() {
+    let mut add = counted("add");
+    // i32.const, i32.const, i32.add, end
+    assert_eq!(add(0), (3, 4));
+    assert_eq!(add(0), (3, 4));
+}
+
+#[test]
+fn count_branches() {
+    let mut select = counted("select");
+    let (_, then_count) = select(1);
+    let (_, else_count) = select(0);
+    assert_eq!(select(1).0, 10);
+    assert_eq!(select(0).0, 20);
+    assert!(then_count > 0);
+    assert!(else_count > 0);
+}
+
+#[test]
+fn count_loops() {
+    let mut sum = counted("sum");
+    let counts = (0..5)
+        .map(|n| {
+            let (result, count) = sum(n);
+            assert_eq!(result, (0..=n).sum::<i32>());
+            count
+        })
+        .collect::<Vec<_>>();
+    // Every loop iteration executes the same number of instructions.
+    let per_iteration = counts[1] - counts[0];
+    assert!(per_iteration > 0);
+    for window in counts.windows(2) {
+        assert_eq!(window[1] - window[0], per_iteration);
+    }
+}
+
+#[test]
+fn count_via_transform() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (global $count (export "count") (mut i64) (i64.const 0))
+            (func (export "run") (result i32)
+                (i32.const 42)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let engine = Engine::default();
+    let module =
+        Module::new_with_transform(&engine, &wasm[..], InstructionCounter::new(0)).unwrap();
+    let (mut store, instance) = instantiate(&engine, &module);
+    let run = instance.get_typed_func::<(), i32>(&store, "run").unwrap();
+    assert_eq!(run.call(&mut store, ()).unwrap(), 42);
+    let count = instance.get_global(&store, "count").unwrap();
+    // i32.const, end
+    assert_eq!(count.get(&store).i64(), Some(2));
+}
+
+#[test]
+fn instrument_preserves_exports_and_globals() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "g" (global $g i32))
+            (global $h (mut i32) (i32.const 7))
+            (func (export "get") (result i32)
+                (i32.add (global.get $g) (global.get $h))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let wasm = InstructionCounter::instrument(&wasm, "count").unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let g = wasmi::Global::new(&mut store, wasmi::Value::I32(5), wasmi::Mutability::Const);
+    linker.define("env", "g", g).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let get = instance.get_typed_func::<(), i32>(&store, "get").unwrap();
+    assert_eq!(get.call(&mut store, ()).unwrap(), 12);
+    let count = instance.get_global(&store, "count").unwrap();
+    // global.get, global.get, i32.add, end
+    assert_eq!(count.get(&store).i64(), Some(4));
+}
+
+#[test]
+fn coverage() {
+    let wat = WAT.replacen(
+        "(module",
+        r#"(module (import "env" "probe" (func $probe (param i32)))"#,
+        1,
+    );
+    let wasm = wat::parse_str(wat).unwrap();
+    let mut probes = CoverageProbes::new(0);
+    let instrumented = probes.transform_module(&wasm).unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &instrumented[..]).unwrap();
+    let (mut store, instance) = instantiate(&engine, &module);
+    // All probes refer to the defined functions and are ordered by their offsets.
+    assert!(probes.probes().iter().all(|probe| probe.func_index() >= 1));
+    assert!(probes
+        .probes()
+        .windows(2)
+        .all(|window| window[0].offset() < window[1].offset()));
+    // The offsets refer to operators of the original Wasm module.
+    let first = probes.probes()[0];
+    assert_eq!(first.func_index(), 1);
+    assert_eq!(wasm[first.offset()], 0x41); // i32.const
+    let select = instance
+        .get_typed_func::<i32, i32>(&store, "select")
+        .unwrap();
+    assert_eq!(select.call(&mut store, 1).unwrap(), 10);
+    let then_hits = core::mem::take(store.data_mut());
+    assert_eq!(select.call(&mut store, 0).unwrap(), 20);
+    let else_hits = core::mem::take(store.data_mut());
+    assert_ne!(then_hits, else_hits);
+    assert_eq!(then_hits.first(), else_hits.first());
+    for id in then_hits.iter().chain(&else_hits) {
+        assert_eq!(probes.probes()[*id as usize].func_index(), 2);
+    }
+}
+
+#[test]
+fn coverage_via_transform() {
+    let wat = WAT.replacen(
+        "(module",
+        r#"(module (import "env" "probe" (func $probe (param i32)))"#,
+        1,
+    );
+    let wasm = wat::parse_str(wat).unwrap();
+    let probes = Arc::new(Mutex::new(CoverageProbes::new(0)));
+    let engine = Engine::default();
+    let module = Module::new_with_transform(&engine, &wasm[..], {
+        let probes = probes.clone();
+        move |body: FuncBody| probes.lock().unwrap().transform(body)
+    })
+    .unwrap();
+    let (mut store, instance) = instantiate(&engine, &module);
+    let add = instance.get_typed_func::<(), i32>(&store, "add").unwrap();
+    assert_eq!(add.call(&mut store, ()).unwrap(), 3);
+    let probes = probes.lock().unwrap();
+    assert_eq!(store.data(), &[0]);
+    assert_eq!(probes.probes()[0].func_index(), 1);
+}
+
+#[test]
+fn malformed_module() {
+    assert!(InstructionCounter::instrument(b"invalid", "count").is_err());
+    assert!(CoverageProbes::new(0).transform_module(&[]).is_err());
+}
@@ -0,0 +1,186 @@
+//! Tests for host intrinsics.
+
+use wasmi::{
+    core::{TrapCode, UntypedValue, ValueType},
+    errors::{ErrorKind, IntrinsicError},
+    Engine,
+    Error,
+    FuncType,
+    Instance,
+    Intrinsic,
+    Linker,
+    Module,
+    Store,
+};
+
+/// Defines the `host::add` and `host::divmod` intrinsics for `engine`.
+fn define_intrinsics(engine: &Engine) {
+    let add = Intrinsic::new(
+        FuncType::new([ValueType::I32, ValueType::I32], [ValueType::I32]),
+        |inout: &mut [UntypedValue]| {
+            let lhs = i32::from(inout[0]);
+            let rhs = i32::from(inout[1]);
+            inout[0] = UntypedValue::from(lhs.wrapping_add(rhs));
+            Ok(())
+        },
+    )
+    .unwrap();
+    let divmod = Intrinsic::new(
+        FuncType::new(
+            [ValueType::I32, ValueType::I32],
+            [ValueType::I32, ValueType::I32],
+        ),
+        |inout: &mut [UntypedValue]| {
+            let lhs = i32::from(inout[0]);
+            let rhs = i32::from(inout[1]);
+            if rhs == 0 {
+                return Err(Error::from(TrapCode::IntegerDivisionByZero));
+            }
+            inout[0] = UntypedValue::from(lhs / rhs);
+            inout[1] = UntypedValue::from(lhs % rhs);
+            Ok(())
+        },
+    )
+    .unwrap();
+    engine.define_intrinsic("host", "add", add).unwrap();
+    engine.define_intrinsic("host", "divmod", divmod).unwrap();
+}
+
+/// Instantiates `wat` using an [`Engine`] with the `host` intrinsics defined.
+fn setup(wat: &str) -> (Store<()>, Instance) {
+    let wasm = wat::parse_str(wat).unwrap();
+    let engine = Engine::default();
+    define_intrinsics(&engine);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+const WAT: &str = r#"
+    (module
+        (import "host" "add" (func $add (param i32 i32) (result i32)))
+        (import "host" "divmod" (func $divmod (param i32 i32) (result i32 i32)))
+        (type $binop (func (param i32 i32) (result i32)))
+        (table funcref (elem $add))
+        (func (export "add") (param i32 i32) (result i32)
+            (call $add (local.get 0) (local.get 1))
+        )
+        (func (export "add_indirect") (param i32 i32) (result i32)
+            (call_indirect (type $binop) (local.get 0) (local.get 1) (i32.const 0))
+        )
+        (func (export "divmod") (param i32 i32) (result i32 i32)
+            (call $divmod (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+#[test]
+fn call_intrinsic_works() {
+    let (mut store, instance) = setup(WAT);
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+    let divmod = instance
+        .get_typed_func::<(i32, i32), (i32, i32)>(&store, "divmod")
+        .unwrap();
+    assert_eq!(divmod.call(&mut store, (17, 5)).unwrap(), (3, 2));
+}
+
+#[test]
+fn call_intrinsic_indirect_works() {
+    let (mut store, instance) = setup(WAT);
+    let add_indirect = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add_indirect")
+        .unwrap();
+    assert_eq!(add_indirect.call(&mut store, (40, 2)).unwrap(), 42);
+}
+
+#[test]
+fn call_intrinsic_traps() {
+    let (mut store, instance) = setup(WAT);
+    let divmod = instance
+        .get_typed_func::<(i32, i32), (i32, i32)>(&store, "divmod")
+        .unwrap();
+    let error = divmod.call(&mut store, (1, 0)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+}
+
+#[test]
+fn mismatching_intrinsic_uses_linker() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "host" "add" (func $add (param i64 i64) (result i64)))
+            (func (export "add") (param i64 i64) (result i64)
+                (call $add (local.get 0) (local.get 1))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let engine = Engine::default();
+    define_intrinsics(&engine);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("host", "add", |lhs: i64, rhs: i64| lhs * rhs)
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let add = instance
+        .get_typed_func::<(i64, i64), i64>(&store, "add")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (3, 4)).unwrap(), 12);
+}
+
+#[test]
+fn linker_definition_takes_precedence() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let engine = Engine::default();
+    define_intrinsics(&engine);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("host", "add", |lhs: i32, rhs: i32| lhs * rhs)
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (3, 4)).unwrap(), 12);
+    let add_indirect = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add_indirect")
+        .unwrap();
+    assert_eq!(add_indirect.call(&mut store, (3, 4)).unwrap(), 12);
+    let divmod = instance
+        .get_typed_func::<(i32, i32), (i32, i32)>(&store, "divmod")
+        .unwrap();
+    assert_eq!(divmod.call(&mut store, (17, 5)).unwrap(), (3, 2));
+}
+
+#[test]
+fn define_intrinsic_twice_fails() {
+    let engine = Engine::default();
+    define_intrinsics(&engine);
+    let nop = Intrinsic::new(FuncType::new([], []), |_| Ok(())).unwrap();
+    let error = engine.define_intrinsic("host", "add", nop).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Intrinsic(IntrinsicError::DuplicateDefinition { .. })
+    ));
+}
@@ -0,0 +1,199 @@
+//! Tests for multi-tenant isolation via `Isolate` and `IsolateRegistry`.
+
+use std::thread;
+use wasmi::{
+    core::TrapCode,
+    isolate::{Isolate, IsolateQuota, IsolateRegistry},
+    Config,
+    Engine,
+    Instance,
+    Linker,
+    Module,
+    StoreLimitsBuilder,
+    TypedFunc,
+};
+
+const WAT: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (func (export "nop"))
+        (func (export "spin")
+            (loop $continue (br $continue))
+        )
+        (func (export "grow") (param i32) (result i32)
+            (memory.grow (local.get 0))
+        )
+    )
+"#;
+
+/// Returns an [`Engine`] with epoch interruption enabled.
+fn engine() -> Engine {
+    let mut config = Config::default();
+    config.epoch_interruption(true);
+    Engine::new(&config)
+}
+
+/// Returns an [`Engine`] with fuel metering and epoch interruption enabled.
+fn fuel_engine() -> Engine {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    config.epoch_interruption(true);
+    Engine::new(&config)
+}
+
+/// Instantiates [`WAT`] within `isolate`.
+fn instantiate(isolate: &mut Isolate<()>) -> Instance {
+    let engine = isolate.store().engine().clone();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    Linker::new(&engine)
+        .instantiate(&mut *isolate, &module)
+        .unwrap()
+        .start(&mut *isolate)
+        .unwrap()
+}
+
+/// Returns the exported `nop` function of `instance`.
+fn nop(isolate: &Isolate<()>, instance: &Instance) -> TypedFunc<(), ()> {
+    instance.get_typed_func::<(), ()>(isolate, "nop").unwrap()
+}
+
+#[test]
+fn registry_enumerates_live_isolates() {
+    let engine = engine();
+    let registry = IsolateRegistry::new();
+    let a = Isolate::new(&registry, &engine, "a", IsolateQuota::new(), ()).unwrap();
+    let b = Isolate::new(&registry, &engine, "b", IsolateQuota::new(), ()).unwrap();
+    let isolates = registry.isolates();
+    assert_eq!(isolates.len(), 2);
+    assert_eq!(isolates[0].id(), a.id());
+    assert_eq!(isolates[0].tenant(), "a");
+    assert_eq!(isolates[1].id(), b.id());
+    assert_eq!(isolates[1].tenant(), "b");
+    assert!(isolates.iter().all(|info| !info.is_terminated()));
+    drop(a);
+    let isolates = registry.isolates();
+    assert_eq!(isolates.len(), 1);
+    assert_eq!(isolates[0].id(), b.id());
+    drop(b);
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn terminate_traps_all_further_calls() {
+    let engine = engine();
+    let registry = IsolateRegistry::new();
+    let mut isolate = Isolate::new(&registry, &engine, "a", IsolateQuota::new(), ()).unwrap();
+    let mut other = Isolate::new(&registry, &engine, "b", IsolateQuota::new(), ()).unwrap();
+    let instance = instantiate(&mut isolate);
+    let other_instance = instantiate(&mut other);
+    let nop = nop(&isolate, &instance);
+    nop.call(&mut isolate, ()).unwrap();
+    assert!(registry.terminate(isolate.id()));
+    assert!(isolate.is_terminated());
+    for _ in 0..2 {
+        let error = nop.call(&mut isolate, ()).unwrap_err();
+        assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+    }
+    // Other isolates are unaffected.
+    let other_nop = self::nop(&other, &other_instance);
+    other_nop.call(&mut other, ()).unwrap();
+    let id = isolate.id();
+    drop(isolate);
+    assert!(!registry.terminate(id));
+}
+
+#[test]
+fn terminate_tenant() {
+    let engine = engine();
+    let registry = IsolateRegistry::new();
+    let _a0 = Isolate::new(&registry, &engine, "a", IsolateQuota::new(), ()).unwrap();
+    let _a1 = Isolate::new(&registry, &engine, "a", IsolateQuota::new(), ()).unwrap();
+    let b = Isolate::new(&registry, &engine, "b", IsolateQuota::new(), ()).unwrap();
+    assert_eq!(registry.terminate_tenant("a"), 2);
+    assert_eq!(registry.terminate_tenant("c"), 0);
+    for info in registry.isolates() {
+        assert_eq!(info.is_terminated(), info.tenant() == "a");
+    }
+    assert!(!b.is_terminated());
+}
+
+#[test]
+fn terminate_running_isolate() {
+    let engine = engine();
+    let registry = IsolateRegistry::new();
+    let guest = thread::spawn({
+        let registry = registry.clone();
+        move || {
+            let mut isolate =
+                Isolate::new(&registry, &engine, "a", IsolateQuota::new(), ()).unwrap();
+            let instance = instantiate(&mut isolate);
+            let spin = instance.get_typed_func::<(), ()>(&isolate, "spin").unwrap();
+            spin.call(&mut isolate, ()).unwrap_err()
+        }
+    });
+    // Note: Terminate repeatedly since the guest might not have registered yet.
+    while !guest.is_finished() {
+        registry.terminate_tenant("a");
+        thread::yield_now();
+    }
+    let error = guest.join().unwrap();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn fuel_quota() {
+    let engine = fuel_engine();
+    let registry = IsolateRegistry::new();
+    let quota = IsolateQuota::new().fuel(1_000);
+    let mut isolate = Isolate::new(&registry, &engine, "a", quota, ()).unwrap();
+    let instance = instantiate(&mut isolate);
+    let spin = instance.get_typed_func::<(), ()>(&isolate, "spin").unwrap();
+    let error = spin.call(&mut isolate, ()).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    let nop = nop(&isolate, &instance);
+    assert!(nop.call(&mut isolate, ()).is_err());
+    isolate.renew().unwrap();
+    nop.call(&mut isolate, ()).unwrap();
+}
+
+#[test]
+fn fuel_quota_requires_fuel_metering() {
+    let engine = Engine::default();
+    let registry = IsolateRegistry::new();
+    let quota = IsolateQuota::new().fuel(1_000);
+    assert!(Isolate::new(&registry, &engine, "a", quota, ()).is_err());
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn epoch_deadline_quota() {
+    let engine = engine();
+    let registry = IsolateRegistry::new();
+    let quota = IsolateQuota::new().epoch_deadline(1);
+    let mut isolate = Isolate::new(&registry, &engine, "a", quota, ()).unwrap();
+    let instance = instantiate(&mut isolate);
+    let nop = nop(&isolate, &instance);
+    nop.call(&mut isolate, ()).unwrap();
+    engine.increment_epoch();
+    let error = nop.call(&mut isolate, ()).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+    isolate.renew().unwrap();
+    nop.call(&mut isolate, ()).unwrap();
+}
+
+#[test]
+fn memory_quota() {
+    let engine = engine();
+    let registry = IsolateRegistry::new();
+    let limits = StoreLimitsBuilder::new().memory_size(2 * 65536).build();
+    let quota = IsolateQuota::new().limits(limits);
+    let mut isolate = Isolate::new(&registry, &engine, "a", quota, ()).unwrap();
+    let instance = instantiate(&mut isolate);
+    let grow = instance
+        .get_typed_func::<i32, i32>(&isolate, "grow")
+        .unwrap();
+    assert_eq!(grow.call(&mut isolate, 1).unwrap(), 1);
+    assert_eq!(grow.call(&mut isolate, 1).unwrap(), -1);
+}
@@ -0,0 +1,156 @@
+//! Tests for the Wasmi `0.x` compatibility layer.
+
+use wasmi::{
+    errors::{ErrorKind, LinkerError},
+    legacy::{
+        Externals,
+        FuncInstance,
+        ImportsBuilder,
+        ModuleImportResolver,
+        ModuleInstance,
+        NopExternals,
+    },
+    Engine,
+    Error,
+    FuncType,
+    Module,
+    Value,
+};
+
+/// Resolves `env.add` to index 0 and `env.log` to index 1.
+struct Env;
+
+impl ModuleImportResolver for Env {
+    fn resolve_func(&self, field_name: &str, signature: &FuncType) -> Result<FuncInstance, Error> {
+        let index = match field_name {
+            "add" => 0,
+            "log" => 1,
+            _ => return Err(Error::new(format!("unknown host function: {field_name}"))),
+        };
+        Ok(FuncInstance::alloc_host(signature.clone(), index))
+    }
+}
+
+/// Host state that records all logged values.
+#[derive(Default)]
+struct Host {
+    logged: Vec<i32>,
+}
+
+impl Externals for Host {
+    fn invoke_index(&mut self, index: usize, args: &[Value]) -> Result<Option<Value>, Error> {
+        match index {
+            0 => {
+                let lhs = args[0].i32().unwrap();
+                let rhs = args[1].i32().unwrap();
+                Ok(Some(Value::I32(lhs + rhs)))
+            }
+            1 => {
+                self.logged.push(args[0].i32().unwrap());
+                Ok(None)
+            }
+            _ => panic!("unexpected host function index: {index}"),
+        }
+    }
+}
+
+fn module(wat: &str) -> Module {
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(&Engine::default(), &wasm[..]).unwrap()
+}
+
+const WAT: &str = r#"
+    (module
+        (import "env" "add" (func $add (param i32 i32) (result i32)))
+        (import "env" "log" (func $log (param i32)))
+        (func $start
+            (call $log (i32.const 1))
+        )
+        (func (export "run") (param i32) (result i32)
+            (call $log (local.get 0))
+            (call $add (local.get 0) (i32.const 10))
+        )
+        (start $start)
+    )
+"#;
+
+#[test]
+fn run_start_and_invoke_export() {
+    let module = module(WAT);
+    let imports = ImportsBuilder::new().with_resolver("env", &Env);
+    let mut host = Host::default();
+    let mut instance = ModuleInstance::new(&module, &imports)
+        .unwrap()
+        .run_start(&mut host)
+        .unwrap();
+    assert_eq!(host.logged, [1]);
+    let result = instance
+        .invoke_export("run", &[Value::I32(5)], &mut host)
+        .unwrap();
+    assert_eq!(result.and_then(|value| value.i32()), Some(15));
+    assert_eq!(host.logged, [1, 5]);
+}
+
+#[test]
+fn nop_externals_trap() {
+    let module = module(WAT);
+    let imports = ImportsBuilder::new().with_resolver("env", &Env);
+    let error = ModuleInstance::new(&module, &imports)
+        .unwrap()
+        .run_start(&mut NopExternals)
+        .unwrap_err();
+    assert!(error.as_trap_code().is_some());
+}
+
+#[test]
+fn missing_resolver() {
+    let module = module(WAT);
+    let error = ModuleInstance::new(&module, &ImportsBuilder::new()).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Linker(LinkerError::MissingDefinition { .. })
+    ));
+}
+
+#[test]
+fn mismatching_signature() {
+    struct BadEnv;
+
+    impl ModuleImportResolver for BadEnv {
+        fn resolve_func(
+            &self,
+            _field_name: &str,
+            _signature: &FuncType,
+        ) -> Result<FuncInstance, Error> {
+            Ok(FuncInstance::alloc_host(FuncType::new([], []), 0))
+        }
+    }
+
+    let module = module(WAT);
+    let imports = ImportsBuilder::new().with_resolver("env", &BadEnv);
+    let error = ModuleInstance::new(&module, &imports).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Linker(LinkerError::FuncTypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn assert_no_start() {
+    let module = module(
+        r#"
+        (module
+            (func (export "answer") (result i32)
+                (i32.const 42)
+            )
+        )
+    "#,
+    );
+    let mut instance = ModuleInstance::new(&module, &ImportsBuilder::default())
+        .unwrap()
+        .assert_no_start();
+    let result = instance
+        .invoke_export("answer", &[], &mut NopExternals)
+        .unwrap();
+    assert_eq!(result.and_then(|value| value.i32()), Some(42));
+}
@@ -0,0 +1,150 @@
+//! Tests for the instantiation of Wasm modules with thousands of imports.
+
+use wasmi::{
+    core::ValueType,
+    errors::{ErrorKind, LinkerError},
+    Engine,
+    Global,
+    Linker,
+    Module,
+    Mutability,
+    Store,
+    Value,
+};
+
+/// The number of imported functions of the tested Wasm modules.
+const LEN_FUNCS: usize = 5_000;
+
+/// Returns a Wasm module importing [`LEN_FUNCS`] functions and a global variable.
+///
+/// The exported `call` function calls the `n`-th imported function.
+/// The imported function `fN` has the type of `ty` if `N` equals `odd` and
+/// `(param i32) (result i32)` otherwise.
+fn wat(odd: Option<(usize, &str)>) -> String {
+    let mut wat = String::from("(module\n");
+    for i in 0..LEN_FUNCS {
+        let ty = match odd {
+            Some((n, ty)) if n == i => ty,
+            _ => "(param i32) (result i32)",
+        };
+        wat.push_str(&format!(
+            "(import \"env{}\" \"f{i}\" (func $f{i} {ty}))\n",
+            i % 7
+        ));
+    }
+    wat.push_str("(import \"env\" \"offset\" (global $offset i32))\n");
+    wat.push_str("(table funcref (elem");
+    for i in 0..LEN_FUNCS {
+        wat.push_str(&format!(" $f{i}"));
+    }
+    wat.push_str("))\n");
+    wat.push_str(
+        r#"
+        (type $ty (func (param i32) (result i32)))
+        (func (export "call") (param $n i32) (result i32)
+            (call_indirect (type $ty) (global.get $offset) (local.get $n))
+        )
+    )"#,
+    );
+    wat
+}
+
+/// Returns a [`Linker`] defining the `fN` functions returning `N + x` and the `offset` global.
+///
+/// Leaves out the function `fN` if `N` equals `skip`.
+fn linker(store: &mut Store<()>, skip: Option<usize>) -> Linker<()> {
+    let mut linker = <Linker<()>>::new(store.engine());
+    for i in 0..LEN_FUNCS {
+        if Some(i) == skip {
+            continue;
+        }
+        let n = i as i32;
+        linker
+            .func_wrap(&format!("env{}", i % 7), &format!("f{i}"), move |x: i32| {
+                n + x
+            })
+            .unwrap();
+    }
+    let offset = Global::new(&mut *store, Value::I32(1000), Mutability::Const);
+    linker.define("env", "offset", offset).unwrap();
+    linker
+}
+
+#[test]
+fn resolves_all_imports() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat(None)).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let linker = linker(&mut store, None);
+    // Instantiating multiple times reuses the definitions of the `Linker`.
+    for _ in 0..3 {
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .ensure_no_start(&mut store)
+            .unwrap();
+        let call = instance.get_typed_func::<i32, i32>(&store, "call").unwrap();
+        for n in [0, 1, 6, 7, 2_500, 4_999] {
+            assert_eq!(call.call(&mut store, n).unwrap(), 1000 + n);
+        }
+    }
+}
+
+#[test]
+fn resolved_module_instantiates_repeatedly() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat(None)).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let linker = linker(&mut store, None);
+    // The imports are resolved once and reused by all instantiations.
+    let resolved = linker.resolve(&store, &module).unwrap();
+    for _ in 0..3 {
+        let instance = resolved
+            .instantiate(&mut store)
+            .unwrap()
+            .ensure_no_start(&mut store)
+            .unwrap();
+        let call = instance.get_typed_func::<i32, i32>(&store, "call").unwrap();
+        for n in [0, 3, 4_999] {
+            assert_eq!(call.call(&mut store, n).unwrap(), 1000 + n);
+        }
+    }
+}
+
+#[test]
+fn missing_import() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat(None)).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let linker = linker(&mut store, Some(3_333));
+    let error = linker.instantiate(&mut store, &module).unwrap_err();
+    let ErrorKind::Linker(LinkerError::MissingDefinition { name, .. }) = error.kind() else {
+        panic!("unexpected error: {error}")
+    };
+    assert_eq!(name.module(), "env1");
+    assert_eq!(name.name(), "f3333");
+}
+
+#[test]
+fn func_type_mismatch() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat(Some((4_321, "(param i64) (result i32)")))).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let linker = linker(&mut store, None);
+    let error = linker.instantiate(&mut store, &module).unwrap_err();
+    let ErrorKind::Linker(LinkerError::FuncTypeMismatch {
+        name,
+        expected,
+        found,
+    }) = error.kind()
+    else {
+        panic!("unexpected error: {error}")
+    };
+    assert_eq!(name.name(), "f4321");
+    assert_eq!(expected.params(), [ValueType::I64]);
+    assert_eq!(found.params(), [ValueType::I32]);
+}
@@ -0,0 +1,159 @@
+//! Tests for marshaling serializable values to and from guest memory.
+
+use serde::{Deserialize, Serialize};
+use wasmi::{
+    core::TrapCode,
+    marshal::{Format, GuestSlice, Json, Marshal, Postcard},
+    Engine,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    limits: Vec<u32>,
+    verbose: bool,
+}
+
+/// A guest with a bump allocator that fails for allocations of more than 1000 bytes.
+const WAT: &str = r#"
+    (module
+        (memory (export "mem") 1)
+        (global $next (mut i32) (i32.const 16))
+        (global $frees (export "frees") (mut i32) (i32.const 0))
+        (func (export "alloc") (param i32) (result i32)
+            (global.get $next)
+            (if (i32.gt_u (local.get 0) (i32.const 1000))
+                (then (return (i32.const 0)))
+            )
+            (global.set $next (i32.add (global.get $next) (local.get 0)))
+        )
+        (func (export "dealloc") (param i32)
+            (global.set $frees (i32.add (global.get $frees) (i32.const 1)))
+        )
+    )
+"#;
+
+fn setup() -> (Store<()>, Instance, Marshal<Json>) {
+    setup_with(WAT, Json)
+}
+
+fn setup_with<F: Format>(wat: &str, format: F) -> (Store<()>, Instance, Marshal<F>) {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let marshal =
+        Marshal::with_exports(&store, &instance, format, "mem", "alloc", "dealloc").unwrap();
+    (store, instance, marshal)
+}
+
+fn frees(store: &Store<()>, instance: &Instance) -> Option<i32> {
+    instance
+        .get_global(store, "frees")
+        .unwrap()
+        .get(store)
+        .i32()
+}
+
+#[test]
+fn marshal_roundtrip_works() {
+    let (mut store, instance, marshal) = setup();
+    let config = Config {
+        name: String::from("guest"),
+        limits: vec![1, 2, 3],
+        verbose: true,
+    };
+    let slice = marshal.write(&mut store, &config).unwrap();
+    assert_eq!(slice.ptr, 16);
+    let bytes = &instance.get_memory(&store, "mem").unwrap().data(&store)
+        [slice.ptr as usize..(slice.ptr + slice.len) as usize];
+    assert_eq!(
+        bytes,
+        br#"{"name":"guest","limits":[1,2,3],"verbose":true}"#
+    );
+    assert_eq!(marshal.read::<Config>(&store, slice).unwrap(), config);
+    assert_eq!(frees(&store, &instance), Some(0));
+    assert_eq!(marshal.take::<Config>(&mut store, slice).unwrap(), config);
+    assert_eq!(frees(&store, &instance), Some(1));
+    // Unsized values are supported as well.
+    let slice = marshal.write(&mut store, "text").unwrap();
+    assert_eq!(
+        slice,
+        GuestSlice {
+            ptr: 16 + 48,
+            len: 6
+        }
+    );
+    assert_eq!(marshal.take::<String>(&mut store, slice).unwrap(), "text");
+}
+
+#[test]
+fn marshal_errors() {
+    let (mut store, instance, marshal) = setup();
+    // The guest fails to allocate.
+    let large = vec![0_u8; 1000];
+    assert!(marshal.write(&mut store, &large).is_err());
+    // Out of bounds reads.
+    let slice = GuestSlice {
+        ptr: 65530,
+        len: 10,
+    };
+    assert_eq!(
+        marshal
+            .read::<Config>(&store, slice)
+            .unwrap_err()
+            .as_trap_code(),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+    // Bytes that do not encode the requested type.
+    let slice = marshal.write(&mut store, &42).unwrap();
+    assert!(marshal.read::<Config>(&store, slice).is_err());
+    // Missing and mistyped exports.
+    assert!(Marshal::new(&store, &instance, Json).is_err());
+    assert!(Marshal::with_exports(&store, &instance, Json, "mem", "dealloc", "alloc").is_err());
+}
+
+#[test]
+fn marshal_postcard_roundtrip_works() {
+    let (mut store, instance, marshal) = setup_with(WAT, Postcard);
+    let config = Config {
+        name: String::from("guest"),
+        limits: vec![1, 2, 3],
+        verbose: true,
+    };
+    let slice = marshal.write(&mut store, &config).unwrap();
+    let bytes = &instance.get_memory(&store, "mem").unwrap().data(&store)
+        [slice.ptr as usize..(slice.ptr + slice.len) as usize];
+    assert_eq!(bytes, b"\x05guest\x03\x01\x02\x03\x01");
+    assert_eq!(marshal.take::<Config>(&mut store, slice).unwrap(), config);
+    assert_eq!(frees(&store, &instance), Some(1));
+}
+
+#[test]
+fn marshal_write_frees_on_error() {
+    /// A guest whose allocator returns memory that is out of bounds.
+    const WAT: &str = r#"
+        (module
+            (memory (export "mem") 1)
+            (global $frees (export "frees") (mut i32) (i32.const 0))
+            (func (export "alloc") (param i32) (result i32)
+                (i32.const 65530)
+            )
+            (func (export "dealloc") (param i32)
+                (global.set $frees (i32.add (global.get $frees) (i32.const 1)))
+            )
+        )
+    "#;
+    let (mut store, instance, marshal) = setup_with(WAT, Json);
+    assert!(marshal.write(&mut store, "out of bounds").is_err());
+    assert_eq!(frees(&store, &instance), Some(1));
+}
@@ -0,0 +1,57 @@
+//! Tests that the cached default linear memory of the executor is invalidated upon growth.
+
+use wasmi::{core::Pages, Caller, Engine, Extern, Linker, Module, Store};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "grow" (func $grow (param i32) (result i32)))
+        (memory (export "mem") 1 4)
+        (func (export "host_grow") (result i32)
+            (i32.store (i32.const 0) (i32.const 1))
+            (drop (call $grow (i32.const 1)))
+            ;; Access the page that has been grown by the host function.
+            (i32.store (i32.const 65536) (i32.const 2))
+            (i32.add (i32.load (i32.const 0)) (i32.load (i32.const 65536)))
+        )
+        (func (export "wasm_grow") (result i32)
+            (i32.store (i32.const 0) (i32.const 3))
+            (drop (memory.grow (i32.const 1)))
+            ;; Access the page that has just been grown.
+            (i32.store (i32.const 131072) (i32.const 4))
+            (i32.add (i32.load (i32.const 0)) (i32.load (i32.const 131072)))
+        )
+    )
+"#;
+
+#[test]
+fn grown_memory_is_accessible() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "grow", |mut caller: Caller<()>, delta: u32| {
+            let Some(Extern::Memory(memory)) = caller.get_export("mem") else {
+                panic!("missing exported memory")
+            };
+            memory
+                .grow(&mut caller, Pages::new(delta).unwrap())
+                .map(u32::from)
+                .unwrap_or(u32::MAX)
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let host_grow = instance
+        .get_typed_func::<(), i32>(&store, "host_grow")
+        .unwrap();
+    let wasm_grow = instance
+        .get_typed_func::<(), i32>(&store, "wasm_grow")
+        .unwrap();
+    assert_eq!(host_grow.call(&mut store, ()).unwrap(), 3);
+    assert_eq!(wasm_grow.call(&mut store, ()).unwrap(), 7);
+}
@@ -0,0 +1,96 @@
+//! Tests for copying between linear memories via `Memory::copy_to`.
+
+use wasmi::{errors::MemoryError, Engine, Memory, MemoryProtection, MemoryType, Store};
+
+/// Creates a [`Store`] with two linear memories of a single page each.
+///
+/// The first bytes of the memories are `0..8` and `10..18` respectively.
+fn setup() -> (Store<()>, Memory, Memory) {
+    let mut store = Store::new(&Engine::default(), ());
+    let ty = MemoryType::new(1, None).unwrap();
+    let lhs = Memory::new(&mut store, ty).unwrap();
+    let rhs = Memory::new(&mut store, ty).unwrap();
+    lhs.write(&mut store, 0, &[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+    rhs.write(&mut store, 0, &[10, 11, 12, 13, 14, 15, 16, 17])
+        .unwrap();
+    (store, lhs, rhs)
+}
+
+#[test]
+fn copy_between_memories() {
+    let (mut store, lhs, rhs) = setup();
+    lhs.copy_to(&mut store, &rhs, 2..5, 4).unwrap();
+    assert_eq!(&lhs.data(&store)[..8], &[0, 1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(&rhs.data(&store)[..8], &[10, 11, 12, 13, 2, 3, 4, 17]);
+    rhs.copy_to(&mut store, &lhs, 0..2, 0).unwrap();
+    assert_eq!(&lhs.data(&store)[..8], &[10, 11, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+fn copy_within_memory() {
+    let (mut store, lhs, _rhs) = setup();
+    // Overlapping ranges behave like `memory.copy`.
+    lhs.copy_to(&mut store, &lhs, 0..4, 2).unwrap();
+    assert_eq!(&lhs.data(&store)[..8], &[0, 1, 0, 1, 2, 3, 6, 7]);
+    lhs.copy_to(&mut store, &lhs, 2..6, 0).unwrap();
+    assert_eq!(&lhs.data(&store)[..8], &[0, 1, 2, 3, 2, 3, 6, 7]);
+}
+
+#[test]
+fn copy_empty_range() {
+    let (mut store, lhs, rhs) = setup();
+    let len = lhs.data(&store).len();
+    lhs.copy_to(&mut store, &rhs, len..len, len).unwrap();
+    lhs.copy_to(&mut store, &lhs, 0..0, len).unwrap();
+    assert_eq!(&rhs.data(&store)[..8], &[10, 11, 12, 13, 14, 15, 16, 17]);
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn copy_out_of_bounds() {
+    let (mut store, lhs, rhs) = setup();
+    let len = lhs.data(&store).len();
+    let before = rhs.data(&store).to_vec();
+    for (src, dst_offset) in [
+        (len - 1..len + 1, 0),
+        (0..4, len - 3),
+        (0..4, usize::MAX),
+        (4..2, 0),
+    ] {
+        for dst in [&rhs, &lhs] {
+            assert!(matches!(
+                lhs.copy_to(&mut store, dst, src.clone(), dst_offset),
+                Err(MemoryError::OutOfBoundsAccess)
+            ));
+        }
+    }
+    // Nothing is copied upon failure.
+    assert_eq!(rhs.data(&store), &before[..]);
+}
+
+#[test]
+fn copy_to_read_only_region() {
+    let (mut store, lhs, rhs) = setup();
+    rhs.protect(&mut store, 4..6, MemoryProtection::ReadOnly)
+        .unwrap();
+    assert!(matches!(
+        lhs.copy_to(&mut store, &rhs, 0..2, 5),
+        Err(MemoryError::WriteProtected)
+    ));
+    assert!(matches!(
+        rhs.copy_to(&mut store, &rhs, 0..2, 3),
+        Err(MemoryError::WriteProtected)
+    ));
+    // Reading from read-only regions is allowed.
+    rhs.copy_to(&mut store, &lhs, 4..6, 0).unwrap();
+    assert_eq!(&lhs.data(&store)[..2], &[14, 15]);
+    lhs.copy_to(&mut store, &rhs, 0..4, 0).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn copy_to_foreign_store() {
+    let (mut store, lhs, _rhs) = setup();
+    let (_other_store, foreign, _) = setup();
+    let _ = lhs.copy_to(&mut store, &foreign, 0..1, 0);
+}
@@ -0,0 +1,77 @@
+//! Tests to check if the memory grow hook of the [`Store`] works as intended.
+
+use std::sync::{Arc, Mutex};
+use wasmi::{core::Pages, Engine, Linker, Memory, Module, Store, TypedFunc};
+
+/// Records of `(memory, old_pages, new_pages)` reported by the memory grow hook.
+type Records = Arc<Mutex<Vec<(Memory, u32, u32)>>>;
+
+/// Setup [`Store`] with a recording memory grow hook and instantiate the test module.
+fn test_setup() -> (Store<()>, Memory, Records, TypedFunc<i32, i32>) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let records = Records::default();
+    store.set_memory_grow_hook({
+        let records = records.clone();
+        move |memory, old, new| {
+            records
+                .lock()
+                .unwrap()
+                .push((memory, u32::from(old), u32::from(new)))
+        }
+    });
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (memory (export "mem") 1 4)
+            (func (export "grow") (param $delta i32) (result i32)
+                (memory.grow (local.get $delta))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    let grow = instance.get_typed_func(&store, "grow").unwrap();
+    (store, memory, records, grow)
+}
+
+#[test]
+fn memory_grow_instr_notifies() {
+    let (mut store, memory, records, grow) = test_setup();
+    assert_eq!(grow.call(&mut store, 2).unwrap(), 1);
+    let records = records.lock().unwrap();
+    assert_eq!(records.len(), 1);
+    let (grown, old, new) = records[0];
+    assert_eq!(grown.ty(&store), memory.ty(&store));
+    assert_eq!((old, new), (1, 3));
+}
+
+#[test]
+fn failed_or_empty_grow_does_not_notify() {
+    let (mut store, _memory, records, grow) = test_setup();
+    assert_eq!(grow.call(&mut store, 0).unwrap(), 1);
+    assert_eq!(grow.call(&mut store, 10).unwrap(), -1);
+    assert!(records.lock().unwrap().is_empty());
+}
+
+#[test]
+fn host_memory_grow_notifies() {
+    let (mut store, memory, records, _grow) = test_setup();
+    memory.grow(&mut store, Pages::new(1).unwrap()).unwrap();
+    assert_eq!(
+        records
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, old, new)| (*old, *new))
+            .collect::<Vec<_>>(),
+        [(1, 2)]
+    );
+}
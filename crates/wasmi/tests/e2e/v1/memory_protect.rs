@@ -0,0 +1,163 @@
+//! Tests for read-only regions of linear memory via `Memory::protect`.
+
+use wasmi::{
+    core::TrapCode,
+    errors::MemoryError,
+    Engine,
+    Instance,
+    Linker,
+    Memory,
+    MemoryProtection,
+    Module,
+    Store,
+};
+
+const WAT: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (data $data "abcd")
+        (func (export "store8") (param $address i32) (param $value i32)
+            (i32.store8 (local.get $address) (local.get $value))
+        )
+        (func (export "store32") (param $address i32) (param $value i32)
+            (i32.store offset=2 (local.get $address) (local.get $value))
+        )
+        (func (export "fill") (param $dst i32) (param $len i32)
+            (memory.fill (local.get $dst) (i32.const 0xFF) (local.get $len))
+        )
+        (func (export "copy") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.copy (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "init") (param $dst i32)
+            (memory.init $data (local.get $dst) (i32.const 0) (i32.const 4))
+        )
+    )
+"#;
+
+/// Instantiates [`WAT`] and returns its instance and exported memory.
+fn setup() -> (Store<()>, Instance, Memory) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    (store, instance, memory)
+}
+
+/// Asserts that `result` is a [`TrapCode::MemoryWriteProtected`] trap.
+fn assert_protected<T>(result: Result<T, wasmi::Error>) {
+    let error = result.err().expect("expected a trap");
+    assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryWriteProtected));
+}
+
+#[test]
+fn store_to_read_only_region_traps() {
+    let (mut store, instance, memory) = setup();
+    let store8 = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "store8")
+        .unwrap();
+    let store32 = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "store32")
+        .unwrap();
+    memory
+        .protect(&mut store, 100..200, MemoryProtection::ReadOnly)
+        .unwrap();
+    let ranges = memory.protected_ranges(&store);
+    assert_eq!(ranges.len(), 1);
+    assert_eq!(ranges[0], 100..200);
+    store8.call(&mut store, (99, 1)).unwrap();
+    store8.call(&mut store, (200, 2)).unwrap();
+    assert_protected(store8.call(&mut store, (100, 3)));
+    assert_protected(store8.call(&mut store, (199, 4)));
+    // The 4 bytes written at `95 + 2` overlap the read-only byte at 100.
+    assert_protected(store32.call(&mut store, (95, 5)));
+    store32.call(&mut store, (94, 6)).unwrap();
+    // Out of bounds stores still trap with their usual trap code.
+    let error = store8.call(&mut store, (65536, 7)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    assert!(memory.data(&store)[100..200].iter().all(|&byte| byte == 0));
+    // Making the region writable again lifts the protection.
+    memory
+        .protect(&mut store, 100..200, MemoryProtection::ReadWrite)
+        .unwrap();
+    assert!(memory.protected_ranges(&store).is_empty());
+    store8.call(&mut store, (150, 8)).unwrap();
+    assert_eq!(memory.data(&store)[150], 8);
+}
+
+#[test]
+fn bulk_memory_to_read_only_region_traps() {
+    let (mut store, instance, memory) = setup();
+    let fill = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "fill")
+        .unwrap();
+    let copy = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "copy")
+        .unwrap();
+    let init = instance.get_typed_func::<i32, ()>(&store, "init").unwrap();
+    memory
+        .protect(&mut store, 16..32, MemoryProtection::ReadOnly)
+        .unwrap();
+    assert_protected(fill.call(&mut store, (0, 17)));
+    fill.call(&mut store, (0, 16)).unwrap();
+    // Reading from read-only regions is allowed.
+    copy.call(&mut store, (32, 16, 16)).unwrap();
+    assert_protected(copy.call(&mut store, (30, 0, 4)));
+    assert_protected(init.call(&mut store, 14));
+    init.call(&mut store, 40).unwrap();
+    assert_eq!(&memory.data(&store)[40..44], b"abcd");
+    assert!(memory.data(&store)[16..32].iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn out_of_bounds_write_to_read_only_region_traps_out_of_bounds() {
+    let (mut store, instance, memory) = setup();
+    let store32 = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "store32")
+        .unwrap();
+    let fill = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "fill")
+        .unwrap();
+    let copy = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "copy")
+        .unwrap();
+    let init = instance.get_typed_func::<i32, ()>(&store, "init").unwrap();
+    memory
+        .protect(&mut store, 0..8, MemoryProtection::ReadOnly)
+        .unwrap();
+    memory
+        .protect(&mut store, 65528..65536, MemoryProtection::ReadOnly)
+        .unwrap();
+    let assert_out_of_bounds = |result: Result<(), wasmi::Error>| {
+        let error = result.unwrap_err();
+        assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    };
+    // The bounds checks take precedence over the write protection checks.
+    assert_out_of_bounds(store32.call(&mut store, (65532, 1)));
+    assert_out_of_bounds(fill.call(&mut store, (65530, 10)));
+    assert_out_of_bounds(copy.call(&mut store, (65532, 0, 8)));
+    assert_out_of_bounds(copy.call(&mut store, (0, 65532, 8)));
+    assert_out_of_bounds(init.call(&mut store, 65534));
+}
+
+#[test]
+fn host_write_to_read_only_region_fails() {
+    let (mut store, _, memory) = setup();
+    memory
+        .protect(&mut store, 0..8, MemoryProtection::ReadOnly)
+        .unwrap();
+    assert!(matches!(
+        memory.write(&mut store, 4, &[1, 2, 3, 4, 5]),
+        Err(MemoryError::WriteProtected)
+    ));
+    memory.write(&mut store, 8, &[1, 2, 3]).unwrap();
+    assert!(matches!(
+        memory.protect(&mut store, 65530..65540, MemoryProtection::ReadOnly),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+}
@@ -0,0 +1,109 @@
+//! Tests for restoring linear memories to their post-instantiation contents via `Memory::reset`.
+
+use wasmi::{errors::MemoryError, Config, Engine, Linker, Memory, MemoryType, Module, Store};
+
+const WAT: &str = r#"
+    (module
+        (memory (export "memory") 1 4)
+        (data (i32.const 0) "hello")
+        (data (i32.const 100) "world")
+        (func (export "scribble")
+            (drop (memory.grow (i32.const 2)))
+            (memory.fill (i32.const 0) (i32.const 0xFF) (i32.const 0x30000))
+        )
+    )
+"#;
+
+/// Returns an [`Engine`] with [`Config::memory_reset`] enabled.
+fn engine() -> Engine {
+    let mut config = Config::default();
+    config.memory_reset(true);
+    Engine::new(&config)
+}
+
+/// Instantiates the module of `wat` in `store`.
+fn instantiate(store: &mut Store<()>, wat: &str) -> wasmi::Instance {
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    <Linker<()>>::new(store.engine())
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+#[test]
+fn reset_restores_data_segments() {
+    let engine = engine();
+    let mut store = Store::new(&engine, ());
+    let instance = instantiate(&mut store, WAT);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let pristine = memory.data(&store).to_vec();
+    instance
+        .get_typed_func::<(), ()>(&store, "scribble")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(u32::from(memory.current_pages(&store)), 3);
+    memory.reset(&mut store).unwrap();
+    assert_eq!(u32::from(memory.current_pages(&store)), 1);
+    assert_eq!(memory.data(&store), &pristine[..]);
+    assert_eq!(&memory.data(&store)[0..5], b"hello");
+    assert_eq!(&memory.data(&store)[100..105], b"world");
+    // The memory can still be grown and the regained pages are zeroed.
+    memory.grow(&mut store, 1.into()).unwrap();
+    assert!(memory.data(&store)[0x10000..]
+        .iter()
+        .all(|&byte| byte == 0x00));
+}
+
+#[test]
+fn reset_host_memory() {
+    let engine = engine();
+    let mut store = Store::new(&engine, ());
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    memory.grow(&mut store, 1.into()).unwrap();
+    memory.data_mut(&mut store).fill(0xAB);
+    memory.reset(&mut store).unwrap();
+    assert_eq!(memory.data(&store), &[0x00; 0x10000][..]);
+}
+
+#[test]
+fn reset_out_of_bounds_segment() {
+    let engine = engine();
+    let mut store = Store::new(&engine, ());
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    memory.grow(&mut store, 1.into()).unwrap();
+    let wat = r#"
+        (module
+            (import "env" "memory" (memory 1))
+            (data (i32.const 0x10000) "beyond")
+        )
+    "#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("env", "memory", memory).unwrap();
+    linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    memory.data_mut(&mut store)[0] = 0x42;
+    assert!(memory.reset(&mut store).is_err());
+    // The memory is left untouched upon failure.
+    assert_eq!(u32::from(memory.current_pages(&store)), 2);
+    assert_eq!(memory.data(&store)[0], 0x42);
+}
+
+#[test]
+fn reset_disabled() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let instance = instantiate(&mut store, WAT);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    assert!(matches!(
+        memory.reset(&mut store),
+        Err(MemoryError::ResetDisabled)
+    ));
+}
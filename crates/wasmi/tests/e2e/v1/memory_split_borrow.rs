@@ -0,0 +1,105 @@
+//! Tests for simultaneously borrowing linear memories and the host state of a `Store`.
+
+use wasmi::{Caller, Engine, Extern, Linker, Memory, MemoryType, Module, Store};
+
+/// The host state used by the tests.
+#[derive(Debug, Default)]
+struct HostState {
+    /// The number of bytes copied by the host.
+    copied: usize,
+    /// The prefix all copied bytes are checked against.
+    prefix: Vec<u8>,
+    /// The memory owned by the host that bytes are copied into.
+    scratch: Option<Memory>,
+}
+
+#[test]
+fn data_and_store() {
+    let engine = Engine::default();
+    let mut store = Store::new(
+        &engine,
+        HostState {
+            prefix: b"wasm".to_vec(),
+            ..HostState::default()
+        },
+    );
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    memory.write(&mut store, 0, b"wasmi").unwrap();
+    let (data, state) = memory.data_and_store(&store);
+    assert!(data.starts_with(&state.prefix));
+}
+
+#[test]
+fn data_pair_and_store_mut() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, HostState::default());
+    let ty = MemoryType::new(1, None).unwrap();
+    let src = Memory::new(&mut store, ty).unwrap();
+    let dst = Memory::new(&mut store, ty).unwrap();
+    src.write(&mut store, 0, b"hello").unwrap();
+    let (src_data, dst_data, state) = src.data_pair_and_store_mut(&dst, &mut store);
+    dst_data[10..15].copy_from_slice(&src_data[0..5]);
+    src_data[0..5].fill(0);
+    state.copied += 5;
+    assert_eq!(&dst.data(&store)[10..15], b"hello");
+    assert_eq!(&src.data(&store)[0..5], &[0; 5]);
+    assert_eq!(store.data().copied, 5);
+}
+
+#[test]
+#[should_panic]
+fn data_pair_and_store_mut_same_memory() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, HostState::default());
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let _ = memory.data_pair_and_store_mut(&memory, &mut store);
+}
+
+#[test]
+fn data_pair_and_store_mut_from_host() {
+    let wat = r#"
+        (module
+            (import "env" "copy" (func $copy (param i32 i32 i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "hello")
+            (func (export "run")
+                (call $copy (i32.const 0) (i32.const 8) (i32.const 5))
+            )
+        )
+    "#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, HostState::default());
+    let scratch = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    store.data_mut().scratch = Some(scratch);
+    let mut linker = <Linker<HostState>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "copy",
+            |mut caller: Caller<HostState>, src: u32, dst: u32, len: u32| {
+                let Some(Extern::Memory(memory)) = caller.get_export("memory") else {
+                    panic!("missing exported memory")
+                };
+                let scratch = caller.data().scratch.unwrap();
+                let (src, dst, len) = (src as usize, dst as usize, len as usize);
+                let (data, scratch, state) = memory.data_pair_and_store_mut(&scratch, &mut caller);
+                scratch[dst..dst + len].copy_from_slice(&data[src..src + len]);
+                state.copied += len;
+            },
+        )
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<(), ()>(&store, "run")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(&scratch.data(&store)[8..13], b"hello");
+    assert_eq!(store.data().copied, 5);
+}
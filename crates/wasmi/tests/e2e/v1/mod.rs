@@ -1,6 +1,83 @@
+mod address_map;
+#[cfg(feature = "fault-injection")]
+mod alloc_failures;
+mod artifact;
+mod backtrace;
+#[cfg(all(feature = "macros", feature = "float"))]
+mod bindgen;
+mod br_table;
+mod bytecode_json;
+#[cfg(feature = "bulk-table")]
+mod bulk_memory;
+mod call_batch;
+mod call_report;
+mod compile_async;
+mod compile_stats;
+mod compiled_code_budget;
+mod const_expr;
+mod copy_span;
+mod cpu_time;
+mod diagnostics;
+mod engine_caches;
+mod engine_features;
+mod engine_stats;
+mod epoch_interruption;
+mod error_code;
+mod extern_type_matches;
 mod fuel_consumption;
 mod fuel_metering;
 mod func;
+mod func_code_sizes;
+mod func_type_names;
+mod global_cache;
+mod host_call_interceptor;
+mod host_call_replay;
 mod host_calls_wasm;
+#[cfg(feature = "macros")]
+mod host_module;
+mod host_panic;
+mod hot_reload;
+mod import_policy;
+mod instance_reset;
+mod instance_fuel;
+mod instrument;
+mod instruction_budget;
+mod intrinsic;
+mod isolate;
+#[cfg(feature = "legacy")]
+mod legacy;
+#[cfg(feature = "serde")]
+mod marshal;
+mod many_imports;
+mod memory_cache;
+mod memory_copy;
+mod memory_grow_hook;
+mod memory_protect;
+mod memory_reset;
+mod memory_split_borrow;
+mod module_analysis;
+#[cfg(not(feature = "float"))]
+mod no_float;
+mod operator_deny_list;
+mod preinitialize;
+#[cfg(not(all(feature = "reference-types", feature = "bulk-table")))]
+mod proposal_features;
 mod resource_limiter;
 mod resumable_call;
+mod safepoint_granularity;
+mod scheduler;
+#[cfg(unix)]
+mod signal_preemption;
+mod store_introspection;
+mod store_teardown;
+mod symbol_provider;
+mod table_bulk;
+mod table_hook;
+mod threading;
+mod time_slice;
+mod time_travel;
+mod trap_hook;
+mod trap_location;
+mod transform;
+mod translation_error;
+mod wasm_func_hook;
@@ -0,0 +1,78 @@
+//! Tests to check if [`Module::analysis`] works as intended.
+
+use wasmi::{Config, Engine, Module};
+
+/// A module with an imported function and internal functions covering the analyzed properties.
+const WAT: &str = r#"
+    (module
+        (import "env" "f" (func))
+        (memory 1)
+        (table 1 funcref)
+        (type $t (func))
+        ;; 1: calls an import and grows memory by constant deltas.
+        (func $leaf
+            (call 0)
+            (drop (memory.grow (i32.const 2)))
+            (block
+                (drop (memory.grow (i32.const 3)))
+            )
+        )
+        ;; 2: calls $leaf and tail calls $leaf.
+        (func $mid (param i32)
+            (call $leaf)
+            (return_call $leaf)
+        )
+        ;; 3: calls $mid.
+        (func $top
+            (call $mid (i32.const 0))
+        )
+        ;; 4: recursive and grows memory by a dynamic delta.
+        (func $rec (param i32)
+            (drop (memory.grow (local.get 0)))
+            (call $rec (local.get 0))
+        )
+        ;; 5: calls indirectly and grows memory within a loop.
+        (func $dyn
+            (call_indirect (type $t) (i32.const 0))
+            (loop
+                (drop (memory.grow (i32.const 1)))
+            )
+        )
+    )
+"#;
+
+/// Compiles [`WAT`] with module analysis set to `enable`.
+fn compile(enable: bool) -> Module {
+    let mut config = Config::default();
+    config.wasm_tail_call(true).module_analysis(enable);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    Module::new(&engine, &wasm[..]).unwrap()
+}
+
+#[test]
+fn module_analysis_disabled() {
+    assert!(compile(false).analysis().is_none());
+}
+
+#[test]
+fn module_analysis_works() {
+    let analysis = compile(true).analysis().unwrap();
+    let funcs = analysis.funcs();
+    assert_eq!(funcs.len(), 5);
+    assert!(analysis.func(0).is_none());
+    assert!(analysis.func(6).is_none());
+    assert!(funcs.iter().all(|func| func.stack_cells().is_some()));
+    let depth_and_growth = |func_index: u32| {
+        let func = analysis.func(func_index).unwrap();
+        assert_eq!(func.func_index(), func_index);
+        (func.max_call_depth(), func.max_memory_growth())
+    };
+    assert_eq!(depth_and_growth(1), (Some(1), Some(5)));
+    assert_eq!(depth_and_growth(2), (Some(2), Some(0)));
+    assert_eq!(depth_and_growth(3), (Some(3), Some(0)));
+    assert_eq!(depth_and_growth(4), (None, None));
+    assert_eq!(depth_and_growth(5), (None, None));
+    // The call frame of $mid has at least its parameter.
+    assert!(analysis.func(2).unwrap().stack_cells().unwrap() >= 1);
+}
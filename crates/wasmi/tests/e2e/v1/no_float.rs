@@ -0,0 +1,52 @@
+//! Tests for `wasmi` builds without the `float` crate feature.
+
+use wasmi::{Config, Engine, Linker, Module, Store};
+
+#[test]
+fn floats_are_disabled() {
+    let mut config = Config::default();
+    config.floats(true);
+    assert!(!Engine::new(&config).features().floats());
+}
+
+#[test]
+fn float_modules_are_rejected() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (export "add") (param f32 f32) (result f32)
+                (f32.add (local.get 0) (local.get 1))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    assert!(Module::new(&engine, &wasm[..]).is_err());
+}
+
+#[test]
+fn integer_modules_work() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (export "mul") (param i64 i64) (result i64)
+                (i64.mul (local.get 0) (local.get 1))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let mul = instance
+        .get_typed_func::<(i64, i64), i64>(&store, "mul")
+        .unwrap();
+    assert_eq!(mul.call(&mut store, (6, 7)).unwrap(), 42);
+}
@@ -0,0 +1,76 @@
+//! Tests for Wasm operators denied via [`Config::deny_operators`].
+
+use wasmi::{
+    errors::{ErrorKind, TranslationError},
+    CompilationMode,
+    Config,
+    Engine,
+    Module,
+};
+
+/// A Wasm module that uses `memory.grow`, `call_indirect` and `i64` operators.
+const WAT: &str = r#"
+    (module
+        (import "env" "f" (func))
+        (memory 1)
+        (table 1 funcref)
+        (func (result i32)
+            (memory.grow (i32.const 1))
+        )
+        (func
+            (call_indirect (i32.const 0))
+        )
+        (func (param i64) (result i64)
+            (i64.clz (local.get 0))
+        )
+    )
+"#;
+
+/// Compiles [`WAT`] with the Wasm operators `denied` and the compilation `mode`.
+fn compile(mode: CompilationMode, denied: &[&str]) -> Result<Module, wasmi::Error> {
+    let mut config = Config::default();
+    config.compilation_mode(mode).deny_operators(denied);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    Module::new(&engine, &wasm[..])
+}
+
+/// Asserts that `error` was caused by translating the denied Wasm operator `name`.
+fn assert_denied(error: &wasmi::Error, func_index: u32, name: &str) {
+    match error.kind() {
+        ErrorKind::Translation(TranslationError::DeniedOperator(denied)) => {
+            assert_eq!(&**denied, name)
+        }
+        _ => panic!("expected denied Wasm operator `{name}` but found: {error}"),
+    }
+    assert_eq!(error.func_index(), Some(func_index));
+    assert_eq!(error.wasm_operator(), Some(name));
+}
+
+#[test]
+fn no_operators_denied() {
+    compile(CompilationMode::Eager, &[]).unwrap();
+    compile(CompilationMode::Eager, &["table.grow", "f64.*"]).unwrap();
+}
+
+#[test]
+fn denied_operator_fails() {
+    let error = compile(CompilationMode::Eager, &["memory.grow"]).unwrap_err();
+    assert_denied(&error, 1, "memory.grow");
+    let error = compile(CompilationMode::Eager, &["call_indirect"]).unwrap_err();
+    assert_denied(&error, 2, "call_indirect");
+}
+
+#[test]
+fn denied_operator_prefix_fails() {
+    let error = compile(CompilationMode::Eager, &["i64.*"]).unwrap_err();
+    assert_denied(&error, 3, "i64.clz");
+}
+
+#[test]
+fn denied_operator_fails_lazily_compiled() {
+    for mode in [CompilationMode::LazyTranslation, CompilationMode::Lazy] {
+        let error = compile(mode, &["memory.grow"]).unwrap_err();
+        assert_denied(&error, 1, "memory.grow");
+    }
+}
@@ -0,0 +1,110 @@
+//! Tests to check if Wasmi's Wizer-style module pre-initialization works as intended.
+
+use wasmi::{
+    errors::{ErrorKind, PreinitError},
+    Caller,
+    Engine,
+    Error,
+    ExternRef,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+/// Pre-initializes the `wat` module using its `init` export.
+fn preinitialize(wat: &str) -> Result<(Engine, Module), Error> {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let module = module.preinitialize(&mut store, &linker, "init")?;
+    Ok((engine, module))
+}
+
+/// Instantiates `module` in a new [`Store`].
+fn instantiate(engine: &Engine, module: &Module) -> (Store<()>, Instance) {
+    let mut store = Store::new(engine, ());
+    let instance = <Linker<()>>::new(engine)
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn snapshots_memory_and_globals() {
+    let (engine, module) = preinitialize(
+        r#"
+        (module
+            (memory (export "mem") 1 4)
+            (global $counter (export "counter") (mut i32) (i32.const 0))
+            (global $table (export "table") (mut funcref) (ref.null func))
+            (data (i32.const 0) "\01\02")
+            (func $start
+                (global.set $counter (i32.add (global.get $counter) (i32.const 10)))
+            )
+            (start $start)
+            (func $f)
+            (elem declare func $f)
+            (func (export "init")
+                (drop (memory.grow (i32.const 1)))
+                (i32.store8 (i32.const 70000) (i32.const 42))
+                (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+                (global.set $table (ref.func $f))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let (store, instance) = instantiate(&engine, &module);
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    assert_eq!(u32::from(memory.current_pages(&store)), 2);
+    let data = memory.data(&store);
+    assert_eq!(&data[..2], &[1, 2]);
+    assert_eq!(data[70000], 42);
+    // The start function is not run again for the pre-initialized module.
+    let counter = instance.get_global(&store, "counter").unwrap();
+    assert_eq!(counter.get(&store).i32(), Some(11));
+    let table = instance.get_global(&store, "table").unwrap();
+    assert!(!table.get(&store).funcref().unwrap().is_null());
+}
+
+#[test]
+fn missing_entry_fails() {
+    assert!(preinitialize("(module)").is_err());
+}
+
+#[test]
+fn non_null_externref_global_fails() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "host" "make" (func $make (result externref)))
+            (global $g (mut externref) (ref.null extern))
+            (func (export "init")
+                (global.set $g (call $make))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("host", "make", |caller: Caller<()>| {
+            ExternRef::new::<i32>(caller, 42)
+        })
+        .unwrap();
+    let error = module
+        .preinitialize(&mut store, &linker, "init")
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Preinit(PreinitError::UnsupportedGlobalValue { global_index: 0 })
+    ));
+}
@@ -0,0 +1,24 @@
+//! Tests for `wasmi` builds without the `reference-types` or `bulk-table` crate features.
+
+use wasmi::{Config, Engine};
+
+#[test]
+#[cfg(not(feature = "reference-types"))]
+fn reference_types_are_disabled() {
+    let mut config = Config::default();
+    config.wasm_reference_types(true);
+    assert!(!Engine::new(&config).features().reference_types());
+}
+
+#[test]
+#[cfg(not(feature = "bulk-table"))]
+fn bulk_memory_is_disabled_by_default() {
+    assert!(!Engine::new(&Config::default()).features().bulk_memory());
+}
+
+#[test]
+#[cfg(not(feature = "bulk-table"))]
+#[should_panic]
+fn enabling_bulk_memory_panics() {
+    Config::default().wasm_bulk_memory(true);
+}
@@ -78,7 +78,11 @@ impl<Results> UnwrapResumable for Result<TypedResumableCall<Results>, Error> {
     fn unwrap_resumable(self) -> TypedResumableInvocation<Self::Results> {
         match self.unwrap() {
             TypedResumableCall::Resumable(invocation) => invocation,
-            TypedResumableCall::Finished(_) => panic!("expected TypedResumableCall::Resumable"),
+            TypedResumableCall::Finished(_)
+            | TypedResumableCall::Yielded(_)
+            | TypedResumableCall::OutOfFuel(_) => {
+                panic!("expected TypedResumableCall::Resumable")
+            }
         }
     }
 }
@@ -98,7 +102,11 @@ fn resumable_call_smoldot_01() {
     let invocation = wasm_fn.call_resumable(&mut store, ()).unwrap_resumable();
     match invocation.resume(&mut store, &[Value::I32(42)]).unwrap() {
         TypedResumableCall::Finished(result) => assert_eq!(result, 42),
-        TypedResumableCall::Resumable(_) => panic!("expected TypeResumableCall::Finished"),
+        TypedResumableCall::Resumable(_)
+        | TypedResumableCall::Yielded(_)
+        | TypedResumableCall::OutOfFuel(_) => {
+            panic!("expected TypeResumableCall::Finished")
+        }
     }
 }
 
@@ -141,7 +149,11 @@ fn resumable_call_smoldot_tail_02() {
     let invocation = wasm_fn.call_resumable(&mut store, ()).unwrap_resumable();
     match invocation.resume(&mut store, &[Value::I32(42)]).unwrap() {
         TypedResumableCall::Finished(result) => assert_eq!(result, 42),
-        TypedResumableCall::Resumable(_) => panic!("expected TypeResumableCall::Finished"),
+        TypedResumableCall::Resumable(_)
+        | TypedResumableCall::Yielded(_)
+        | TypedResumableCall::OutOfFuel(_) => {
+            panic!("expected TypeResumableCall::Finished")
+        }
     }
 }
 
@@ -167,7 +179,11 @@ fn resumable_call_smoldot_02() {
     let invocation = wasm_fn.call_resumable(&mut store, ()).unwrap_resumable();
     match invocation.resume(&mut store, &[Value::I32(42)]).unwrap() {
         TypedResumableCall::Finished(result) => assert_eq!(result, 11),
-        TypedResumableCall::Resumable(_) => panic!("expected TypeResumableCall::Finished"),
+        TypedResumableCall::Resumable(_)
+        | TypedResumableCall::Yielded(_)
+        | TypedResumableCall::OutOfFuel(_) => {
+            panic!("expected TypeResumableCall::Finished")
+        }
     }
 }
 
@@ -274,14 +290,18 @@ impl AssertResumable for ResumableCall {
                 assert_eq!(invocation.host_func().ty(store).results(), host_results,);
                 invocation
             }
-            Self::Finished => panic!("expected host function trap with exit code 10"),
+            Self::Finished | Self::Yielded(_) | Self::OutOfFuel(_) => {
+                panic!("expected host function trap with exit code 10")
+            }
         }
     }
 
     fn assert_finish(self) -> Self::Results {
         match self {
             Self::Finished => (),
-            Self::Resumable(_) => panic!("expected the resumable call to finish"),
+            Self::Resumable(_) | Self::Yielded(_) | Self::OutOfFuel(_) => {
+                panic!("expected the resumable call to finish")
+            }
         }
     }
 }
@@ -330,14 +350,18 @@ impl<Results> AssertResumable for TypedResumableCall<Results> {
                 assert_eq!(invocation.host_func().ty(store).results(), host_results,);
                 invocation
             }
-            Self::Finished(_) => panic!("expected host function trap with exit code 10"),
+            Self::Finished(_) | Self::Yielded(_) | Self::OutOfFuel(_) => {
+                panic!("expected host function trap with exit code 10")
+            }
         }
     }
 
     fn assert_finish(self) -> Self::Results {
         match self {
             Self::Finished(results) => results,
-            Self::Resumable(_) => panic!("expected the resumable call to finish"),
+            Self::Resumable(_) | Self::Yielded(_) | Self::OutOfFuel(_) => {
+                panic!("expected the resumable call to finish")
+            }
         }
     }
 }
@@ -365,3 +389,58 @@ fn run_test_typed(wasm_fn: Func, mut store: &mut Store<TestData>, wasm_trap: boo
         assert_eq!(call.unwrap().assert_finish(), 4);
     }
 }
+
+#[test]
+fn resumable_call_inspect_host_call() {
+    let (mut store, mut linker) = test_setup(0);
+    let host_fn = Func::wrap(&mut store, |_: i32, _: i64| -> Result<i32, Error> {
+        Err(Error::i32_exit(10))
+    });
+    linker.define("env", "host_fn", host_fn).unwrap();
+    let wasm = wat::parse_str(
+        r#"
+            (module
+                (import "env" "host_fn" (func $host_fn (param i32 i64) (result i32)))
+                (func $inner (param $x i32) (result i32)
+                    (call $host_fn (local.get $x) (i64.const 42))
+                )
+                (func (export "wasm_fn") (param $x i32) (result i32)
+                    (call $inner (local.get $x))
+                    (call $host_fn (i32.const 2) (i64.const -1))
+                    (i32.add)
+                )
+            )
+            "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &mut &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let wasm_fn = instance
+        .get_typed_func::<i32, i32>(&store, "wasm_fn")
+        .unwrap();
+    let invocation = wasm_fn.call_resumable(&mut store, 1).unwrap_resumable();
+    assert_eq!(
+        invocation.host_func().ty(&store).params(),
+        [ValueType::I32, ValueType::I64]
+    );
+    assert_eq!(invocation.call_depth(), 2);
+    let params = invocation.host_params();
+    assert_eq!(params.len(), 2);
+    assert_eq!(params[0].i32(), Some(1));
+    assert_eq!(params[1].i64(), Some(42));
+    let invocation = invocation
+        .resume(&mut store, &[Value::I32(10)])
+        .unwrap_resumable();
+    assert_eq!(invocation.call_depth(), 1);
+    let params = invocation.host_params();
+    assert_eq!(params[0].i32(), Some(2));
+    assert_eq!(params[1].i64(), Some(-1));
+    match invocation.resume(&mut store, &[Value::I32(20)]).unwrap() {
+        TypedResumableCall::Finished(result) => assert_eq!(result, 30),
+        _ => panic!("expected the resumable call to finish"),
+    }
+}
@@ -0,0 +1,132 @@
+//! Tests to check if [`Config::safepoint_granularity`] works as intended.
+
+use wasmi::{
+    core::TrapCode,
+    Caller,
+    Config,
+    Engine,
+    Error,
+    Linker,
+    Module,
+    SafepointGranularity,
+    Store,
+    TypedFunc,
+};
+
+/// A function that advances the epoch via a host call before it updates `$g`.
+///
+/// The second update of `$g` is guarded by the `if` of the function parameter.
+const WAT: &str = r#"
+    (module
+        (import "env" "tick" (func $tick))
+        (global $g (export "g") (mut i32) (i32.const 0))
+        (func (export "test") (param $c i32) (result i32)
+            (call $tick)
+            (global.set $g (i32.const 1))
+            (if (local.get $c)
+                (then
+                    (global.set $g (i32.add (global.get $g) (i32.const 1)))
+                    (global.set $g (i32.add (global.get $g) (i32.const 1)))
+                    (global.set $g (i32.add (global.get $g) (i32.const 1)))
+                )
+            )
+            (global.get $g)
+        )
+    )
+"#;
+
+/// Setup [`Store`] and the exported `test` [`TypedFunc`] for `config`.
+fn test_setup(config: &Config) -> (Store<()>, TypedFunc<i32, i32>) {
+    let engine = Engine::new(config);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "tick", |caller: Caller<()>| {
+            caller.engine().increment_epoch();
+        })
+        .unwrap();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func(&store, "test").unwrap();
+    (store, func)
+}
+
+/// Calls `test` with `c` using epoch interruption with `granularity` and a deadline of one tick.
+fn call_epoch(granularity: Option<SafepointGranularity>, c: i32) -> Result<i32, Error> {
+    let mut config = Config::default();
+    config.epoch_interruption(true);
+    if let Some(granularity) = granularity {
+        config.safepoint_granularity(granularity);
+    }
+    let (mut store, func) = test_setup(&config);
+    store.set_epoch_deadline(1);
+    func.call(&mut store, c)
+}
+
+/// Asserts that `result` is an interruption trap.
+fn assert_interrupted(result: Result<i32, Error>) {
+    assert_eq!(
+        result.unwrap_err().as_trap_code(),
+        Some(TrapCode::Interrupted)
+    );
+}
+
+#[test]
+fn epoch_loops_and_calls() {
+    for granularity in [None, Some(SafepointGranularity::LoopsAndCalls)] {
+        assert_eq!(call_epoch(granularity, 0).unwrap(), 1);
+        assert_eq!(call_epoch(granularity, 1).unwrap(), 4);
+    }
+}
+
+#[test]
+fn epoch_basic_block() {
+    let granularity = Some(SafepointGranularity::BasicBlock);
+    assert_eq!(call_epoch(granularity, 0).unwrap(), 1);
+    assert_interrupted(call_epoch(granularity, 1));
+}
+
+#[test]
+fn epoch_instruction() {
+    let granularity = Some(SafepointGranularity::Instruction);
+    assert_interrupted(call_epoch(granularity, 0));
+    assert_interrupted(call_epoch(granularity, 1));
+}
+
+/// Returns the fuel consumed by calling `test` with `c` using fuel metering with `granularity`.
+fn fuel_consumed(granularity: Option<SafepointGranularity>, c: i32) -> u64 {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    if let Some(granularity) = granularity {
+        config.safepoint_granularity(granularity);
+    }
+    let (mut store, func) = test_setup(&config);
+    store.add_fuel(1_000).unwrap();
+    let expected = if c == 0 { 1 } else { 4 };
+    assert_eq!(func.call(&mut store, c).unwrap(), expected);
+    store.fuel_consumed().unwrap()
+}
+
+#[test]
+fn fuel_granularity() {
+    let default = fuel_consumed(None, 0);
+    assert_eq!(
+        fuel_consumed(Some(SafepointGranularity::BasicBlock), 0),
+        default
+    );
+    // The fuel of the `then` branch is consumed upfront even if it is not taken.
+    let loops_and_calls = fuel_consumed(Some(SafepointGranularity::LoopsAndCalls), 0);
+    assert!(loops_and_calls > default);
+    assert_eq!(
+        fuel_consumed(Some(SafepointGranularity::LoopsAndCalls), 1),
+        loops_and_calls,
+    );
+    // Every Wasm instruction consumes its base fuel on its own.
+    assert!(fuel_consumed(Some(SafepointGranularity::Instruction), 0) > default);
+    assert!(fuel_consumed(Some(SafepointGranularity::Instruction), 1) > 0);
+}
@@ -0,0 +1,195 @@
+//! Tests to check if the fuel-sliced [`Scheduler`] works as intended.
+
+use wasmi::{
+    core::TrapCode,
+    Config,
+    Engine,
+    Error,
+    Func,
+    Linker,
+    Module,
+    Scheduler,
+    Store,
+    TaskTurn,
+    Value,
+};
+
+/// A module with a recursive and an iterative function that both compute a sum.
+const WAT: &str = r#"
+    (module
+        (func $sum_rec (export "sum_rec") (param $n i32) (result i32)
+            (if (result i32) (i32.eqz (local.get $n))
+                (then (i32.const 0))
+                (else
+                    (i32.add
+                        (local.get $n)
+                        (call $sum_rec (i32.sub (local.get $n) (i32.const 1)))
+                    )
+                )
+            )
+        )
+        (func (export "sum_loop") (param $n i32) (result i32)
+            (local $sum i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $sum (i32.add (local.get $sum) (local.get $n)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $sum)
+        )
+        (func (export "trap") (unreachable))
+    )
+"#;
+
+/// Setup a fuel metered [`Store`] and returns the exported `sum_rec`, `sum_loop` and `trap` functions.
+fn test_setup(consume_fuel: bool) -> (Store<()>, [Func; 3]) {
+    let mut config = Config::default();
+    config.consume_fuel(consume_fuel);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let funcs =
+        ["sum_rec", "sum_loop", "trap"].map(|name| instance.get_func(&store, name).unwrap());
+    (store, funcs)
+}
+
+#[test]
+fn tasks_interleave_and_finish() {
+    let (mut store, [sum_rec, sum_loop, _]) = test_setup(true);
+    let mut scheduler = Scheduler::new(20);
+    let a = scheduler
+        .spawn(&store, sum_rec, &[Value::I32(100)])
+        .unwrap();
+    let b = scheduler
+        .spawn(&store, sum_loop, &[Value::I32(1_000)])
+        .unwrap();
+    assert_eq!(scheduler.len(), 2);
+    // Both tasks require many turns and therefore yield in turns.
+    assert!(matches!(scheduler.step(&mut store), Some(TaskTurn::Yielded(id)) if id == a));
+    assert!(matches!(scheduler.step(&mut store), Some(TaskTurn::Yielded(id)) if id == b));
+    let results = scheduler.run(&mut store);
+    assert!(scheduler.is_empty());
+    assert_eq!(results.len(), 2);
+    // The task with less work finishes first.
+    assert_eq!(results[0].0, a);
+    assert_eq!(results[1].0, b);
+    let [Value::I32(sum_a)] = results[0].1.as_ref().unwrap()[..] else {
+        panic!("unexpected results: {:?}", results[0]);
+    };
+    let [Value::I32(sum_b)] = results[1].1.as_ref().unwrap()[..] else {
+        panic!("unexpected results: {:?}", results[1]);
+    };
+    assert_eq!(sum_a, 5050);
+    assert_eq!(sum_b, 500500);
+    // All fuel of the tasks has been provided by the slices.
+    assert!(store.fuel_consumed().unwrap() > 0);
+    assert_eq!(store.consume_fuel(0).unwrap(), 0);
+}
+
+#[test]
+fn failing_task_is_removed() {
+    let (mut store, [_, sum_loop, trap]) = test_setup(true);
+    let mut scheduler = Scheduler::new(1_000_000);
+    let a = scheduler.spawn(&store, trap, &[]).unwrap();
+    let b = scheduler
+        .spawn(&store, sum_loop, &[Value::I32(10)])
+        .unwrap();
+    let Some(TaskTurn::Failed(id, error)) = scheduler.step(&mut store) else {
+        panic!("expected task to fail")
+    };
+    assert_eq!(id, a);
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    assert!(matches!(scheduler.step(&mut store), Some(TaskTurn::Finished(id, _)) if id == b));
+    assert!(scheduler.step(&mut store).is_none());
+}
+
+#[test]
+fn cancel_task() {
+    let (mut store, [_, sum_loop, _]) = test_setup(true);
+    let mut scheduler = Scheduler::new(10);
+    let a = scheduler
+        .spawn(&store, sum_loop, &[Value::I32(1_000)])
+        .unwrap();
+    assert!(matches!(
+        scheduler.step(&mut store),
+        Some(TaskTurn::Yielded(_))
+    ));
+    assert!(scheduler.cancel(a));
+    assert!(!scheduler.cancel(a));
+    assert!(scheduler.is_empty());
+}
+
+#[test]
+fn spawn_errors() {
+    let (store, [sum_rec, ..]) = test_setup(false);
+    let mut scheduler = Scheduler::new(10);
+    // Fuel metering is required.
+    assert!(scheduler.spawn(&store, sum_rec, &[Value::I32(1)]).is_err());
+    let (store, [sum_rec, ..]) = test_setup(true);
+    // Parameters must match.
+    assert!(scheduler.spawn(&store, sum_rec, &[]).is_err());
+    assert!(scheduler.spawn(&store, sum_rec, &[Value::I64(1)]).is_err());
+    assert!(scheduler.is_empty());
+}
+
+#[test]
+fn too_small_fuel_slice_fails() {
+    let (mut store, [_, sum_loop, _]) = test_setup(true);
+    let mut scheduler = Scheduler::new(0);
+    let a = scheduler
+        .spawn(&store, sum_loop, &[Value::I32(10)])
+        .unwrap();
+    let Some(TaskTurn::Failed(id, error)) = scheduler.step(&mut store) else {
+        panic!("expected task to fail")
+    };
+    assert_eq!(id, a);
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    assert!(scheduler.is_empty());
+}
+
+#[test]
+fn host_error_fails_task() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "host" "fail" (func $fail))
+            (func (export "run") (call $fail))
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("host", "fail", || -> Result<(), Error> {
+            Err(Error::new("host failure"))
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_func(&store, "run").unwrap();
+    let mut scheduler = Scheduler::new(1_000);
+    let a = scheduler.spawn(&store, run, &[]).unwrap();
+    let Some(TaskTurn::Failed(id, error)) = scheduler.step(&mut store) else {
+        panic!("expected task to fail")
+    };
+    assert_eq!(id, a);
+    assert_eq!(error.to_string(), "host failure");
+    assert!(scheduler.is_empty());
+}
@@ -0,0 +1,111 @@
+//! Tests for interrupting Wasm executions via `InterruptHandle` and POSIX signals.
+
+use std::{sync::mpsc, thread, time::Duration};
+use wasmi::{
+    core::TrapCode,
+    preempt::{install_signal_handler, PreemptibleThread},
+    Config,
+    Engine,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+};
+
+const WAT: &str = r#"
+    (module
+        (func (export "spin")
+            (loop $continue (br $continue))
+        )
+        (func (export "add") (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+/// The store and the exported `spin` and `add` functions of [`WAT`].
+type Setup = (Store<()>, TypedFunc<(), ()>, TypedFunc<(i32, i32), i32>);
+
+/// Instantiates [`WAT`] with signal based preemption configured to `enable`.
+fn setup(enable: bool) -> Setup {
+    let mut config = Config::default();
+    config.signal_preemption(enable);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let spin = instance.get_typed_func::<(), ()>(&store, "spin").unwrap();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    (store, spin, add)
+}
+
+#[test]
+fn pending_interrupt_traps_once() {
+    let (mut store, _spin, add) = setup(true);
+    let handle = store.interrupt_handle();
+    handle.interrupt();
+    assert!(handle.is_interrupted());
+    let error = add.call(&mut store, (1, 2)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+    // The interruption request is consumed by the trapping execution.
+    assert!(!handle.is_interrupted());
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+}
+
+#[test]
+fn cleared_interrupt_does_not_trap() {
+    let (mut store, _spin, add) = setup(true);
+    let handle = store.interrupt_handle();
+    handle.interrupt();
+    handle.clear();
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+}
+
+#[test]
+fn interrupt_ignored_when_disabled() {
+    let (mut store, _spin, add) = setup(false);
+    store.interrupt_handle().interrupt();
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+}
+
+#[test]
+fn interrupt_from_other_thread() {
+    let (mut store, spin, _add) = setup(true);
+    let handle = store.interrupt_handle();
+    let interrupter = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        handle.interrupt();
+    });
+    let error = spin.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+    interrupter.join().unwrap();
+}
+
+#[test]
+fn signal_preempts_spinning_guest() {
+    install_signal_handler(libc::SIGUSR1).unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let guest = thread::spawn(move || {
+        let (mut store, spin, add) = setup(true);
+        sender.send(PreemptibleThread::current()).unwrap();
+        let error = spin.call(&mut store, ()).unwrap_err();
+        // The store is usable again after it has been preempted.
+        (error, add.call(&mut store, (1, 2)).unwrap())
+    });
+    let target = receiver.recv().unwrap();
+    while !guest.is_finished() {
+        // Safety: The guest thread has not yet been joined.
+        unsafe { target.preempt(libc::SIGUSR1) }.unwrap();
+        thread::sleep(Duration::from_millis(1));
+    }
+    let (error, sum) = guest.join().unwrap();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::Interrupted));
+    assert_eq!(sum, 3);
+}
@@ -0,0 +1,57 @@
+//! Tests to check if the [`Store`] introspection iterators work as intended.
+
+use wasmi::{
+    core::{Pages, ValueType},
+    Engine,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+};
+
+#[test]
+fn empty_store() {
+    let engine = Engine::default();
+    let store = Store::new(&engine, ());
+    assert_eq!(store.instances().count(), 0);
+    assert_eq!(store.memories().len(), 0);
+    assert_eq!(store.tables().len(), 0);
+}
+
+#[test]
+fn enumerates_store_entities() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let wat = r#"
+        (module
+            (memory 2)
+            (table 3 funcref)
+        )
+    "#;
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let linker = <Linker<()>>::new(&engine);
+    for _ in 0..2 {
+        linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+    }
+    Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    assert_eq!(store.instances().count(), 2);
+    let pages: Vec<Pages> = store
+        .memories()
+        .map(|memory| memory.current_pages(&store))
+        .collect();
+    assert_eq!(
+        pages,
+        [Pages::new(2), Pages::new(2), Pages::new(1)].map(Option::unwrap)
+    );
+    assert_eq!(store.tables().len(), 2);
+    for table in store.tables() {
+        assert_eq!(table.ty(&store).element(), ValueType::FuncRef);
+        assert_eq!(table.size(&store), 3);
+    }
+}
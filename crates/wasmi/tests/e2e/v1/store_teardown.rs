@@ -0,0 +1,64 @@
+//! Tests for incrementally tearing down a `Store` via `Store::into_teardown`.
+
+use wasmi::{Engine, Linker, Module, Store, StoreTeardown};
+
+const WAT: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (table 2 funcref)
+        (global (mut i32) (i32.const 0))
+        (func (export "f"))
+        (data (i32.const 0) "hello")
+    )
+"#;
+
+/// Returns a [`Store`] with `len_instances` instances of [`WAT`].
+fn store(len_instances: usize) -> Store<Vec<u8>> {
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+    let mut store = Store::new(&engine, vec![0; 1024]);
+    let linker = <Linker<Vec<u8>>>::new(&engine);
+    for _ in 0..len_instances {
+        linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+    }
+    store
+}
+
+#[test]
+fn teardown_steps_are_bounded() {
+    let mut teardown = store(10).into_teardown();
+    let mut remaining = teardown.remaining();
+    assert!(remaining > 10);
+    while !teardown.step(3) {
+        assert_eq!(teardown.remaining(), remaining - 3);
+        remaining = teardown.remaining();
+    }
+    assert!(remaining <= 3);
+    assert_eq!(teardown.remaining(), 0);
+    assert!(teardown.is_finished());
+    // Further steps are no-ops.
+    assert!(teardown.step(1));
+}
+
+#[test]
+fn teardown_zero_step() {
+    let mut teardown = store(1).into_teardown();
+    let remaining = teardown.remaining();
+    assert!(!teardown.step(0));
+    assert_eq!(teardown.remaining(), remaining);
+}
+
+#[test]
+fn teardown_in_background() {
+    let teardown: StoreTeardown<Vec<u8>> = store(100).into_teardown();
+    let reclaimer = std::thread::spawn(move || {
+        let mut teardown = teardown;
+        while !teardown.step(16) {}
+        teardown.remaining()
+    });
+    assert_eq!(reclaimer.join().unwrap(), 0);
+}
@@ -0,0 +1,121 @@
+//! Tests for resolving human readable symbols via an `Engine` registered `SymbolProvider`.
+
+use wasmi::{Caller, Config, Engine, Linker, Module, Store, Symbol, SymbolQuery, WasmBacktrace};
+
+const WAT: &str = r#"
+    (module $app
+        (import "env" "trace" (func $trace))
+        (func $inner
+            (call $trace)
+        )
+        (func $outer (export "outer")
+            (call $inner)
+        )
+    )
+"#;
+
+/// Resolves the functions of the `app` module to made up source locations.
+fn provider(query: &SymbolQuery) -> Option<Symbol> {
+    if query.module_name() != Some("app") {
+        return None;
+    }
+    let line = match query.func_name()? {
+        "inner" => 10,
+        "outer" => 20,
+        _ => return None,
+    };
+    let symbol = Symbol::new(format!("app::{}", query.func_name()?))
+        .with_file("app.rs")
+        .with_line(line);
+    match query.wasm_offset() {
+        Some(offset) => Some(symbol.with_column(offset)),
+        None => Some(symbol),
+    }
+}
+
+/// Runs the exported `outer` function of [`WAT`] and returns the backtrace captured by `trace`.
+fn capture(engine: &Engine, module: &Module) -> WasmBacktrace {
+    let mut store = <Store<Option<WasmBacktrace>>>::new(engine, None);
+    let mut linker = <Linker<Option<WasmBacktrace>>>::new(engine);
+    linker
+        .func_wrap(
+            "env",
+            "trace",
+            |mut caller: Caller<Option<WasmBacktrace>>| {
+                *caller.data_mut() = Some(caller.backtrace());
+            },
+        )
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<(), ()>(&store, "outer")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    store.data_mut().take().unwrap()
+}
+
+#[test]
+fn symbolicate_backtrace() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let mut config = Config::default();
+    config.generate_address_maps(true);
+    let engine = Engine::new(&config);
+    engine.set_symbol_provider(provider);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    assert_eq!(module.name(), Some("app"));
+    let backtrace = capture(&engine, &module);
+    let frames = backtrace.frames();
+    let inner = frames[0].wasm_offset().unwrap();
+    let outer = frames[1].wasm_offset().unwrap();
+    assert_eq!(
+        frames[0].symbol(),
+        Some(
+            &Symbol::new("app::inner")
+                .with_file("app.rs")
+                .with_line(10)
+                .with_column(inner)
+        ),
+    );
+    assert_eq!(
+        backtrace.to_string(),
+        format!(
+            "   0: app::inner @ {inner:#x} at app.rs:10:{inner}\n   1: app::outer @ {outer:#x} at app.rs:20:{outer}\n"
+        ),
+    );
+}
+
+#[test]
+fn symbolicate_module() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    assert_eq!(engine.symbolicate(&module, 1, None), None);
+    engine.set_symbol_provider(provider);
+    let symbol = engine.symbolicate(&module, 2, Some(42)).unwrap();
+    assert_eq!(symbol.name(), "app::outer");
+    assert_eq!(symbol.file(), Some("app.rs"));
+    assert_eq!(symbol.line(), Some(20));
+    assert_eq!(symbol.column(), Some(42));
+    // The imported function has no name known to the provider.
+    assert_eq!(engine.symbolicate(&module, 0, None), None);
+    engine.remove_symbol_provider();
+    assert_eq!(engine.symbolicate(&module, 2, None), None);
+}
+
+#[test]
+fn backtrace_without_provider() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let backtrace = capture(&engine, &module);
+    assert!(backtrace
+        .frames()
+        .iter()
+        .all(|frame| frame.symbol().is_none()));
+    assert_eq!(backtrace.to_string(), "   0: inner\n   1: outer\n");
+}
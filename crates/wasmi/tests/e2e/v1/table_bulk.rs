@@ -0,0 +1,147 @@
+//! Tests for the bulk operations `Table::init_from_slice`, `Table::fill` and `Table::copy`.
+
+use wasmi::{
+    core::{TrapCode, ValueType},
+    errors::TableError,
+    Engine,
+    ExternRef,
+    Func,
+    FuncRef,
+    Linker,
+    Module,
+    Store,
+    Table,
+    TableType,
+    Value,
+};
+
+/// Creates a [`Store`] with a Wasm instance calling into the imported `funcref` table of size 100.
+///
+/// Returns the table as well as the exported `call` function calling the indexed table element.
+fn setup() -> (Store<()>, Table, Func) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let ty = TableType::new(ValueType::FuncRef, 100, None);
+    let table = Table::new(&mut store, ty, Value::default(ValueType::FuncRef)).unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "table" (table 100 funcref))
+            (type $ty (func (result i32)))
+            (func (export "call") (param i32) (result i32)
+                (call_indirect (type $ty) (local.get 0))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("env", "table", table).unwrap();
+    let call = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_func(&store, "call")
+        .unwrap();
+    (store, table, call)
+}
+
+/// Returns `len` host functions where the `n`-th function returns `n`.
+fn funcs(store: &mut Store<()>, len: i32) -> Vec<Value> {
+    (0..len)
+        .map(|n| Value::from(FuncRef::new(Func::wrap(&mut *store, move || n))))
+        .collect()
+}
+
+/// Calls the `index`-th table element via `call` and returns its result.
+fn call(store: &mut Store<()>, call: Func, index: i32) -> Result<i32, TrapCode> {
+    call.typed::<i32, i32>(&*store)
+        .unwrap()
+        .call(store, index)
+        .map_err(|error| error.as_trap_code().unwrap())
+}
+
+#[test]
+fn init_from_slice() {
+    let (mut store, table, func) = setup();
+    let funcs = funcs(&mut store, 50);
+    table.init_from_slice(&mut store, 40, &funcs).unwrap();
+    for n in [0, 1, 25, 49] {
+        assert_eq!(call(&mut store, func, 40 + n), Ok(n));
+    }
+    assert_eq!(
+        call(&mut store, func, 39),
+        Err(TrapCode::IndirectCallToNull)
+    );
+    assert_eq!(
+        call(&mut store, func, 90),
+        Err(TrapCode::IndirectCallToNull)
+    );
+    // Initializing with no elements is allowed at the end of the table.
+    table.init_from_slice(&mut store, 100, &[]).unwrap();
+}
+
+#[test]
+fn init_from_slice_out_of_bounds() {
+    let (mut store, table, func) = setup();
+    let funcs = funcs(&mut store, 50);
+    for dst in [51, 100, u32::MAX] {
+        assert!(matches!(
+            table.init_from_slice(&mut store, dst, &funcs),
+            Err(TableError::AccessOutOfBounds { current: 100, offset }) if offset == dst
+        ));
+    }
+    // Nothing has been written.
+    assert!((0..100).all(|n| table.get(&store, n).unwrap().funcref().unwrap().is_null()));
+    assert_eq!(
+        call(&mut store, func, 51),
+        Err(TrapCode::IndirectCallToNull)
+    );
+}
+
+#[test]
+fn init_from_slice_type_mismatch() {
+    let (mut store, table, _func) = setup();
+    let mut values = funcs(&mut store, 3);
+    values.push(Value::from(ExternRef::new::<()>(&mut store, None)));
+    assert!(matches!(
+        table.init_from_slice(&mut store, 0, &values),
+        Err(TableError::ElementTypeMismatch {
+            expected: ValueType::FuncRef,
+            actual: ValueType::ExternRef
+        })
+    ));
+    // Nothing has been written.
+    assert!(table.get(&store, 0).unwrap().funcref().unwrap().is_null());
+}
+
+#[test]
+fn fill_and_copy() {
+    let (mut store, table, func) = setup();
+    let funcs = funcs(&mut store, 2);
+    table.fill(&mut store, 10, funcs[1].clone(), 20).unwrap();
+    assert_eq!(call(&mut store, func, 10), Ok(1));
+    assert_eq!(call(&mut store, func, 29), Ok(1));
+    assert_eq!(
+        call(&mut store, func, 30),
+        Err(TrapCode::IndirectCallToNull)
+    );
+    assert_eq!(
+        table.fill(&mut store, 90, funcs[0].clone(), 11),
+        Err(TrapCode::TableOutOfBounds)
+    );
+    table.init_from_slice(&mut store, 0, &funcs[..1]).unwrap();
+    Table::copy(&mut store, &table, 50, &table, 0, 30).unwrap();
+    assert_eq!(call(&mut store, func, 50), Ok(0));
+    assert_eq!(call(&mut store, func, 79), Ok(1));
+    let ty = TableType::new(ValueType::FuncRef, 10, None);
+    let other = Table::new(&mut store, ty, funcs[0].clone()).unwrap();
+    Table::copy(&mut store, &table, 95, &other, 0, 5).unwrap();
+    assert_eq!(call(&mut store, func, 99), Ok(0));
+    assert!(matches!(
+        Table::copy(&mut store, &table, 95, &other, 0, 6),
+        Err(TableError::CopyOutOfBounds)
+    ));
+}
@@ -0,0 +1,80 @@
+//! Tests to check if the table hook of the [`Store`] works as intended.
+
+use std::sync::{Arc, Mutex};
+use wasmi::{Engine, Func, FuncRef, Linker, Module, Store, Table, TableEvent, Value};
+
+/// Events reported by the table hook.
+type Events = Arc<Mutex<Vec<TableEvent>>>;
+
+/// Setup [`Store`] with a recording table hook and instantiate the test module.
+fn test_setup() -> (Store<()>, Table, Events, Func) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let events = Events::default();
+    store.set_table_hook({
+        let events = events.clone();
+        move |_table, event| events.lock().unwrap().push(event)
+    });
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (table $t (export "table") 2 10 funcref)
+            (elem $e func $f $f)
+            (func $f)
+            (func (export "test")
+                (drop (table.grow $t (ref.null func) (i32.const 2)))
+                (table.set $t (i32.const 1) (ref.func $f))
+                (table.init $t $e (i32.const 2) (i32.const 0) (i32.const 2))
+                (table.fill $t (i32.const 0) (ref.null func) (i32.const 1))
+                (table.copy $t $t (i32.const 3) (i32.const 0) (i32.const 1))
+                (table.copy $t $t (i32.const 3) (i32.const 0) (i32.const 0))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let table = instance.get_table(&store, "table").unwrap();
+    let func = instance.get_func(&store, "test").unwrap();
+    events.lock().unwrap().clear();
+    (store, table, events, func)
+}
+
+#[test]
+fn table_instrs_notify() {
+    let (mut store, _table, events, func) = test_setup();
+    func.call(&mut store, &[], &mut []).unwrap();
+    assert_eq!(
+        &events.lock().unwrap()[..],
+        [
+            TableEvent::Grow {
+                old_size: 2,
+                new_size: 4
+            },
+            TableEvent::Set { index: 1 },
+            TableEvent::Init { dst: 2, len: 2 },
+            TableEvent::Fill { dst: 0, len: 1 },
+            TableEvent::Copy { dst: 3, len: 1 },
+        ]
+    );
+}
+
+#[test]
+fn host_table_grow_notifies() {
+    let (mut store, table, events, _func) = test_setup();
+    let init = Value::FuncRef(FuncRef::null());
+    table.grow(&mut store, 3, init.clone()).unwrap();
+    table.grow(&mut store, 0, init).unwrap();
+    assert_eq!(
+        &events.lock().unwrap()[..],
+        [TableEvent::Grow {
+            old_size: 2,
+            new_size: 5
+        }]
+    );
+}
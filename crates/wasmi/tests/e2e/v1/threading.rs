@@ -0,0 +1,48 @@
+//! Tests to check if Wasmi's thread spawning helper works as intended.
+
+use std::sync::{
+    atomic::{AtomicI32, Ordering},
+    Arc,
+};
+use wasmi::{threading::spawn_instance, Caller, Engine, Linker, Module};
+
+#[test]
+fn spawned_instances_share_host_state() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "host" "add" (func $add (param i32)))
+            (func (export "run") (param $n i32)
+                (call $add (local.get $n))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Arc::new(Module::new(&engine, &wasm[..]).unwrap());
+    let mut linker = <Linker<Arc<AtomicI32>>>::new(&engine);
+    linker
+        .func_wrap("host", "add", |caller: Caller<Arc<AtomicI32>>, n: i32| {
+            caller.data().fetch_add(n, Ordering::Relaxed);
+        })
+        .unwrap();
+    let counter = Arc::new(AtomicI32::new(0));
+    let threads = (1..=4)
+        .map(|n| spawn_instance::<_, i32, ()>(&linker, &module, counter.clone(), "run", n))
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap().unwrap();
+    }
+    assert_eq!(counter.load(Ordering::Relaxed), 10);
+}
+
+#[test]
+fn missing_entry_errors() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str("(module)").unwrap();
+    let module = Arc::new(Module::new(&engine, &wasm[..]).unwrap());
+    let linker = <Linker<()>>::new(&engine);
+    let thread = spawn_instance::<_, (), ()>(&linker, &module, (), "run", ());
+    assert!(thread.join().unwrap().is_err());
+}
@@ -0,0 +1,162 @@
+//! Tests for time-sliced execution of resumable calls.
+
+use wasmi::{
+    Caller,
+    Config,
+    Engine,
+    Error,
+    Func,
+    Linker,
+    Module,
+    ResumableCall,
+    Store,
+    TypedResumableCall,
+    Value,
+};
+
+/// Wasm that counts down from `n` to zero and returns the number of iterations.
+///
+/// The host function `env.check` is called once the loop finished.
+const WASM: &str = r#"
+    (module
+        (import "env" "check" (func $check (param i32) (result i32)))
+        (func (export "count") (param $n i32) (result i32)
+            (local $i i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (call $check (local.get $i))
+        )
+    )
+"#;
+
+/// Creates a [`Store`] and the `count` [`Func`] using the given `time_slice`.
+///
+/// The host function `env.check` returns its input if `trap_check` is `false`
+/// and traps with exit code `10` otherwise.
+fn setup(time_slice: u64, trap_check: bool) -> (Store<()>, Func) {
+    let mut config = Config::default();
+    config.time_slice(time_slice);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "check",
+            move |_caller: Caller<()>, input: i32| -> Result<i32, Error> {
+                if trap_check {
+                    return Err(Error::i32_exit(10));
+                }
+                Ok(input)
+            },
+        )
+        .unwrap();
+    let wasm = wat::parse_str(WASM).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, "count").unwrap();
+    (store, func)
+}
+
+#[test]
+fn yields_until_finished() {
+    let (mut store, func) = setup(100, false);
+    let mut results = [Value::I32(0)];
+    let mut call = func
+        .call_resumable(&mut store, &[Value::I32(1000)], &mut results)
+        .unwrap();
+    let mut yields = 0;
+    loop {
+        match call {
+            ResumableCall::Finished => break,
+            ResumableCall::Yielded(invocation) => {
+                yields += 1;
+                call = invocation.resume(&mut store, &mut results).unwrap();
+            }
+            ResumableCall::Resumable(_) | ResumableCall::OutOfFuel(_) => {
+                panic!("unexpected host trap")
+            }
+        }
+    }
+    assert!(yields > 1, "expected multiple yields but got {yields}");
+    assert_eq!(results[0].i32(), Some(1000));
+}
+
+#[test]
+fn typed_yields_until_finished() {
+    let (mut store, func) = setup(100, false);
+    let func = func.typed::<i32, i32>(&store).unwrap();
+    let mut call = func.call_resumable(&mut store, 1000).unwrap();
+    let mut yields = 0;
+    let result = loop {
+        match call {
+            TypedResumableCall::Finished(result) => break result,
+            TypedResumableCall::Yielded(invocation) => {
+                yields += 1;
+                call = invocation.resume(&mut store).unwrap();
+            }
+            TypedResumableCall::Resumable(_) | TypedResumableCall::OutOfFuel(_) => {
+                panic!("unexpected host trap")
+            }
+        }
+    };
+    assert!(yields > 1, "expected multiple yields but got {yields}");
+    assert_eq!(result, 1000);
+}
+
+#[test]
+fn call_never_yields() {
+    let (mut store, func) = setup(1, false);
+    let mut results = [Value::I32(0)];
+    func.call(&mut store, &[Value::I32(1000)], &mut results)
+        .unwrap();
+    assert_eq!(results[0].i32(), Some(1000));
+}
+
+#[test]
+fn large_time_slice_does_not_yield() {
+    let (mut store, func) = setup(u64::MAX, false);
+    let func = func.typed::<i32, i32>(&store).unwrap();
+    match func.call_resumable(&mut store, 1000).unwrap() {
+        TypedResumableCall::Finished(result) => assert_eq!(result, 1000),
+        TypedResumableCall::Yielded(_)
+        | TypedResumableCall::Resumable(_)
+        | TypedResumableCall::OutOfFuel(_) => {
+            panic!("expected the resumable call to finish")
+        }
+    }
+}
+
+#[test]
+fn yield_then_host_trap() {
+    let (mut store, func) = setup(100, true);
+    let mut results = [Value::I32(0)];
+    let mut call = func
+        .call_resumable(&mut store, &[Value::I32(1000)], &mut results)
+        .unwrap();
+    let invocation = loop {
+        match call {
+            ResumableCall::Yielded(invocation) => {
+                call = invocation.resume(&mut store, &mut results).unwrap();
+            }
+            ResumableCall::Resumable(invocation) => break invocation,
+            ResumableCall::Finished | ResumableCall::OutOfFuel(_) => panic!("expected a host trap"),
+        }
+    };
+    assert_eq!(invocation.host_error().i32_exit_status(), Some(10));
+    let call = invocation
+        .resume(&mut store, &[Value::I32(42)], &mut results)
+        .unwrap();
+    assert!(matches!(call, ResumableCall::Finished));
+    assert_eq!(results[0].i32(), Some(42));
+}
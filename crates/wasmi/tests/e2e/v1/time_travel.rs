@@ -0,0 +1,200 @@
+//! Tests for time-travel debugging via `TimeTravel`.
+
+use wasmi::{
+    debug::{InstanceSnapshot, TimeTravel},
+    Caller,
+    Engine,
+    Func,
+    Global,
+    HostCallLog,
+    HostCallRecorder,
+    Instance,
+    Linker,
+    Memory,
+    Module,
+    Store,
+    Value,
+};
+
+/// Accumulates the values returned by `env::next` into the `$acc` global and linear memory.
+///
+/// Also grows the linear memory by one page upon every iteration to test that
+/// growth is undone when travelling backwards.
+const WAT: &str = r#"
+    (module
+        (import "env" "next" (func $next (result i32)))
+        (memory (export "memory") 1)
+        (global $acc (export "acc") (mut i32) (i32.const 0))
+        (func (export "run") (param $n i32) (result i32)
+            (local $i i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.ge_u (local.get $i) (local.get $n)))
+                    (global.set $acc (i32.add (global.get $acc) (call $next)))
+                    (i32.store
+                        (i32.mul (local.get $i) (i32.const 4))
+                        (global.get $acc)
+                    )
+                    (drop (memory.grow (i32.const 1)))
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (global.get $acc)
+        )
+    )
+"#;
+
+/// The debugged instance and its entities.
+struct Setup {
+    store: Store<i32>,
+    instance: Instance,
+    run: Func,
+    acc: Global,
+    memory: Memory,
+}
+
+/// Instantiates [`WAT`] with `env::next` returning `1, 2, 3, ...`.
+fn setup() -> Setup {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, 0);
+    let mut linker = <Linker<i32>>::new(&engine);
+    linker
+        .func_wrap("env", "next", |mut caller: Caller<i32>| {
+            *caller.data_mut() += 1;
+            *caller.data()
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_func(&store, "run").unwrap();
+    let acc = instance.get_global(&store, "acc").unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    Setup {
+        store,
+        instance,
+        run,
+        acc,
+        memory,
+    }
+}
+
+/// Records an execution of `run` with `n` and restores the initial state afterwards.
+fn record(setup: &mut Setup, n: i32) -> HostCallLog {
+    let snapshot = InstanceSnapshot::capture(&setup.store, &setup.instance);
+    let recorder = HostCallRecorder::new();
+    recorder.install(&mut setup.store);
+    let mut results = [Value::I32(0)];
+    setup
+        .run
+        .call(&mut setup.store, &[Value::I32(n)], &mut results)
+        .unwrap();
+    snapshot.restore(&mut setup.store);
+    recorder.take_log()
+}
+
+/// Returns the value of the `acc` global and the size of the linear memory in pages.
+fn state(setup: &Setup) -> (i32, u32) {
+    let acc = setup.acc.get(&setup.store).i32().unwrap();
+    let pages = u32::from(setup.memory.current_pages(&setup.store));
+    (acc, pages)
+}
+
+#[test]
+fn snapshot_restores_instance_state() {
+    let mut setup = setup();
+    let snapshot = InstanceSnapshot::capture(&setup.store, &setup.instance);
+    let mut results = [Value::I32(0)];
+    setup
+        .run
+        .call(&mut setup.store, &[Value::I32(3)], &mut results)
+        .unwrap();
+    assert_eq!(state(&setup), (6, 4));
+    snapshot.restore(&mut setup.store);
+    assert_eq!(state(&setup), (0, 1));
+    assert!(setup
+        .memory
+        .data(&setup.store)
+        .iter()
+        .all(|byte| *byte == 0));
+}
+
+#[test]
+fn time_travel_steps_forward_and_backward() {
+    let mut setup = setup();
+    let log = record(&mut setup, 5);
+    assert_eq!(log.len(), 5);
+    let mut debugger = TimeTravel::new(
+        &mut setup.store,
+        setup.instance,
+        setup.run,
+        &[Value::I32(5)],
+        log,
+        2,
+    )
+    .unwrap();
+    assert_eq!(debugger.position(), 0);
+    assert_eq!(debugger.current_host_call().unwrap().name(), Some("next"));
+    assert_eq!(state(&setup), (0, 1));
+    let expected = [(1, 2), (3, 3), (6, 4), (10, 5), (15, 6)];
+    for (position, expected) in expected.iter().enumerate() {
+        // The state upon the next host function call or at the end of the execution.
+        debugger.step_forward(&mut setup.store).unwrap();
+        assert_eq!(debugger.position(), position + 1);
+        assert_eq!(state(&setup), *expected);
+    }
+    assert!(debugger.is_finished());
+    let outcome = debugger.outcome().unwrap().as_ref().unwrap();
+    assert_eq!(outcome[0].i32(), Some(15));
+    assert_eq!(debugger.checkpoints().collect::<Vec<_>>(), [0, 2, 4]);
+    debugger.step_backward(&mut setup.store).unwrap();
+    assert_eq!(debugger.position(), 4);
+    assert!(!debugger.is_finished());
+    assert_eq!(state(&setup), (10, 5));
+    debugger.seek(&mut setup.store, 1).unwrap();
+    assert_eq!(state(&setup), (1, 2));
+    debugger.seek(&mut setup.store, 0).unwrap();
+    assert_eq!(state(&setup), (0, 1));
+    debugger.seek(&mut setup.store, 5).unwrap();
+    assert!(debugger.is_finished());
+    assert_eq!(state(&setup), (15, 6));
+}
+
+#[test]
+fn time_travel_detects_divergence() {
+    let mut setup = setup();
+    let log = record(&mut setup, 1);
+    let mut debugger = TimeTravel::new(
+        &mut setup.store,
+        setup.instance,
+        setup.run,
+        &[Value::I32(2)],
+        log,
+        1,
+    )
+    .unwrap();
+    let error = debugger.step_forward(&mut setup.store).unwrap_err();
+    assert!(error.to_string().contains("exhausted"));
+    assert!(debugger.is_finished());
+    assert!(debugger.step_forward(&mut setup.store).is_err());
+}
+
+#[test]
+fn time_travel_rejects_zero_interval() {
+    let mut setup = setup();
+    let log = record(&mut setup, 1);
+    let result = TimeTravel::new(
+        &mut setup.store,
+        setup.instance,
+        setup.run,
+        &[Value::I32(1)],
+        log,
+        0,
+    );
+    assert!(result.is_err());
+}
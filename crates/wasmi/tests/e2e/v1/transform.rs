@@ -0,0 +1,80 @@
+//! Tests for pre-translation Wasm function body transforms.
+
+use wasmi::{Engine, Error, FuncBody, Linker, Module, Store};
+
+/// A Wasm module with a single exported function returning `1`.
+fn wasm() -> Vec<u8> {
+    wat::parse_str(
+        r#"
+        (module
+            (func (export "f") (result i32)
+                (i32.const 1)
+            )
+        )
+        "#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn transform_rewrites_func_body() {
+    let engine = Engine::default();
+    let module = Module::new_with_transform(&engine, &wasm()[..], |body: FuncBody| {
+        // `(i32.const 1)` is rewritten to `(i32.const 42)`.
+        assert_eq!(body.bytes(), [0x00, 0x41, 0x01, 0x0B]);
+        Ok(Some(vec![0x00, 0x41, 0x2A, 0x0B]))
+    })
+    .unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let f = instance.get_typed_func::<(), i32>(&store, "f").unwrap();
+    assert_eq!(f.call(&mut store, ()).unwrap(), 42);
+}
+
+#[test]
+fn transform_observes_func_bodies() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "g" (func))
+            (func)
+            (func)
+        )
+        "#,
+    )
+    .unwrap();
+    let mut indices = Vec::new();
+    Module::new_with_transform(&engine, &wasm[..], |body: FuncBody| {
+        indices.push(body.func_index());
+        Ok(None)
+    })
+    .unwrap();
+    assert_eq!(indices, [1, 2]);
+}
+
+#[test]
+fn transform_invalid_func_body_fails() {
+    let engine = Engine::default();
+    // The rewritten function body is missing its `i32` result.
+    let result = Module::new_with_transform(&engine, &wasm()[..], |_body: FuncBody| {
+        Ok(Some(vec![0x00, 0x0B]))
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn transform_error_is_forwarded() {
+    let engine = Engine::default();
+    let result = Module::new_with_transform(&engine, &wasm()[..], |_body: FuncBody| {
+        Err(Error::new("rejected function body"))
+    });
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("rejected function body"));
+}
@@ -0,0 +1,90 @@
+//! Tests for the context of errors of failed Wasm function translations.
+
+use wasmi::{
+    errors::{ErrorKind, TranslationError},
+    Config,
+    Engine,
+    Module,
+};
+
+/// Compiles the Wasm module `wat` on an [`Engine`] with `config` and returns its error.
+fn compile_error(config: &Config, wat: &str) -> (Vec<u8>, wasmi::Error) {
+    let wasm = wat::parse_str(wat).unwrap();
+    let engine = Engine::new(config);
+    let error = Module::new(&engine, &wasm[..]).unwrap_err();
+    (wasm, error)
+}
+
+#[test]
+fn invalid_operator() {
+    let (wasm, error) = compile_error(
+        &Config::default(),
+        r#"
+        (module
+            (import "env" "f" (func))
+            (func)
+            (func (param i64) (result i32)
+                (i32.add (i32.const 1) (local.get 0))
+            )
+        )
+        "#,
+    );
+    // Imported functions are part of the function index space.
+    assert_eq!(error.func_index(), Some(2));
+    assert_eq!(error.wasm_operator(), Some("i32.add"));
+    let offset = error.wasm_offset().unwrap() as usize;
+    // The offset points to the `i32.add` opcode.
+    assert_eq!(wasm[offset], 0x6A);
+    let message = error.to_string();
+    assert!(
+        message.starts_with(&format!(
+            "failed to translate Wasm function 2 at offset {offset:#x} (i32.add): "
+        )),
+        "unexpected error message: {message}"
+    );
+}
+
+#[test]
+fn disabled_proposal() {
+    let mut config = Config::default();
+    config.wasm_tail_call(false);
+    let (wasm, error) = compile_error(
+        &config,
+        r#"
+        (module
+            (func $f)
+            (func (return_call $f))
+        )
+        "#,
+    );
+    assert_eq!(error.func_index(), Some(1));
+    assert_eq!(error.wasm_operator(), Some("return_call"));
+    let offset = error.wasm_offset().unwrap() as usize;
+    assert_eq!(wasm[offset], 0x12);
+}
+
+#[test]
+fn not_caused_by_operator() {
+    let mut config = Config::default();
+    config.max_compiled_code_bytes(1);
+    let (_wasm, error) = compile_error(
+        &config,
+        r#"
+        (module
+            (func (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )
+        "#,
+    );
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Translation(TranslationError::CompiledCodeBudgetExceeded)
+    ));
+    assert_eq!(error.func_index(), Some(0));
+    assert!(error.wasm_offset().is_some());
+    assert_eq!(error.wasm_operator(), None);
+    assert!(error
+        .to_string()
+        .starts_with("failed to translate Wasm function 0 at offset "));
+}
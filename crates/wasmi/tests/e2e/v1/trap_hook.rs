@@ -0,0 +1,140 @@
+//! Tests for observing traps at the point they are raised via `Store::set_trap_hook`.
+
+use std::sync::{Arc, Mutex};
+use wasmi::{
+    core::TrapCode,
+    Config,
+    Engine,
+    Error,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    WasmBacktrace,
+};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "host" (func $host))
+        (func (export "call_div") (param i32 i32) (result i32)
+            (call $div (local.get 0) (local.get 1))
+        )
+        (func $div (param i32 i32) (result i32)
+            (i32.div_u (local.get 0) (local.get 1))
+        )
+        (func (export "call_host")
+            (call $host)
+        )
+    )
+"#;
+
+/// Information recorded by the trap hook.
+#[derive(Debug, PartialEq)]
+struct Recorded {
+    trap_code: Option<TrapCode>,
+    func_index: Option<u32>,
+    depth: usize,
+    params: Vec<u32>,
+}
+
+fn setup() -> (Store<()>, Instance, Arc<Mutex<Vec<Recorded>>>) {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let recorded = Arc::new(Mutex::new(Vec::new()));
+    let hook_recorded = recorded.clone();
+    store.set_trap_hook(move |frame| {
+        let params = frame.registers()[..2]
+            .iter()
+            .map(|value| u32::from(*value))
+            .collect();
+        hook_recorded.lock().unwrap().push(Recorded {
+            trap_code: frame.error().as_trap_code(),
+            func_index: frame.error().func_index(),
+            depth: frame.depth(),
+            params,
+        });
+    });
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "host", || -> Result<(), Error> {
+            Err(Error::new("host failure"))
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance, recorded)
+}
+
+#[test]
+fn trap_hook_observes_trapping_frame() {
+    let (mut store, instance, recorded) = setup();
+    let func = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "call_div")
+        .unwrap();
+    assert_eq!(func.call(&mut store, (6, 3)).unwrap(), 2);
+    assert!(recorded.lock().unwrap().is_empty());
+    let error = func.call(&mut store, (7, 0)).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert_eq!(
+        *recorded.lock().unwrap(),
+        [Recorded {
+            trap_code: Some(TrapCode::IntegerDivisionByZero),
+            func_index: Some(2),
+            depth: 2,
+            params: vec![7, 0],
+        }]
+    );
+}
+
+#[test]
+fn trap_hook_ignores_host_errors() {
+    let (mut store, instance, recorded) = setup();
+    let func = instance
+        .get_typed_func::<(), ()>(&store, "call_host")
+        .unwrap();
+    func.call(&mut store, ()).unwrap_err();
+    assert!(recorded.lock().unwrap().is_empty());
+}
+
+#[test]
+fn trap_hook_captures_backtrace() {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let mut config = Config::default();
+    config.generate_address_maps(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let backtrace = Arc::new(Mutex::new(<Option<WasmBacktrace>>::None));
+    let hook_backtrace = backtrace.clone();
+    store.set_trap_hook(move |frame| {
+        *hook_backtrace.lock().unwrap() = Some(frame.backtrace().clone());
+    });
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "host", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "call_div")
+        .unwrap();
+    func.call(&mut store, (7, 0)).unwrap_err();
+    let backtrace = backtrace.lock().unwrap().take().unwrap();
+    let frames = backtrace.frames();
+    assert_eq!(backtrace.len(), 2);
+    assert_eq!(frames[0].func_index(), 2);
+    assert_eq!(frames[0].func_name(), Some("div"));
+    assert_eq!(frames[1].func_index(), 1);
+    // The innermost frame points to the trapping `i32.div_u` instruction
+    // and the outer frame to its pending `call $div` instruction.
+    let div = frames[0].wasm_offset().unwrap() as usize;
+    assert_eq!(wasm[div], 0x6E);
+    let call = frames[1].wasm_offset().unwrap() as usize;
+    assert_eq!(&wasm[call..call + 2], &[0x10, 0x02]);
+}
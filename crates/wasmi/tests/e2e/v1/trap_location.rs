@@ -0,0 +1,128 @@
+//! Tests for the function index and Wasm offset of traps via `Error::func_index` and `Error::wasm_offset`.
+
+use wasmi::{core::TrapCode, CompilationMode, Config, Engine, Error, Linker, Module, Store};
+
+const WAT: &str = r#"
+    (module
+        (import "env" "host" (func $host))
+        (func (export "call_div") (param i32 i32) (result i32)
+            (call $div (local.get 0) (local.get 1))
+        )
+        (func $div (param i32 i32) (result i32)
+            (i32.div_u (local.get 0) (local.get 1))
+        )
+        (func (export "call_host")
+            (call $host)
+        )
+    )
+"#;
+
+/// Returns the Wasm binary of [`WAT`] and the offset of its `i32.div_u` instruction.
+fn wasm_and_offset() -> (Vec<u8>, u32) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    // Note: `local.get 1` (0x20 0x01) is followed by `i32.div_u` (0x6E) and `end` (0x0B).
+    let div = wasm
+        .windows(4)
+        .position(|window| window == [0x20, 0x01, 0x6E, 0x0B])
+        .unwrap() as u32;
+    (wasm, div + 2)
+}
+
+fn call_div(mode: CompilationMode, address_maps: bool, wasm: &[u8]) -> Error {
+    let mut config = Config::default();
+    config
+        .compilation_mode(mode)
+        .generate_address_maps(address_maps);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "host", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "call_div")
+        .unwrap();
+    assert_eq!(func.call(&mut store, (6, 3)).unwrap(), 2);
+    func.call(&mut store, (1, 0)).unwrap_err()
+}
+
+#[test]
+fn trap_location() {
+    let (wasm, div) = wasm_and_offset();
+    for mode in [CompilationMode::Eager, CompilationMode::Lazy] {
+        let error = call_div(mode, true, &wasm);
+        assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+        assert_eq!(error.func_index(), Some(2));
+        assert_eq!(error.wasm_offset(), Some(div));
+    }
+}
+
+#[test]
+fn trap_location_without_address_maps() {
+    let (wasm, _) = wasm_and_offset();
+    let error = call_div(CompilationMode::Eager, false, &wasm);
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert_eq!(error.func_index(), Some(2));
+    assert_eq!(error.wasm_offset(), None);
+}
+
+#[test]
+fn host_error_has_no_location() {
+    let (wasm, _) = wasm_and_offset();
+    let mut config = Config::default();
+    config.generate_address_maps(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "host", || -> Result<(), Error> {
+            Err(Error::new("host failure"))
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance
+        .get_typed_func::<(), ()>(&store, "call_host")
+        .unwrap();
+    let error = func.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.func_index(), None);
+    assert_eq!(error.wasm_offset(), None);
+}
+
+#[test]
+fn out_of_fuel_has_location() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (export "loop")
+                (loop $continue (br $continue))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    store.add_fuel(100).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func::<(), ()>(&store, "loop").unwrap();
+    let error = func.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    assert_eq!(error.func_index(), Some(0));
+    assert_eq!(engine.stats().traps(TrapCode::OutOfFuel), 1);
+}
@@ -0,0 +1,75 @@
+//! Tests to check if the Wasm function hook of the [`Store`] works as intended.
+
+use std::sync::{Arc, Mutex};
+use wasmi::{Config, Engine, Func, Linker, Module, Store, WasmFuncEvent};
+
+/// Events reported by the Wasm function hook.
+type Events = Arc<Mutex<Vec<(u32, WasmFuncEvent)>>>;
+
+/// Setup [`Store`] with a recording Wasm function hook and instantiate the test module.
+fn test_setup(wasm_func_hooks: bool) -> (Store<()>, Events, Func) {
+    let mut config = Config::default();
+    config.wasm_tail_call(true);
+    config.wasm_func_hooks(wasm_func_hooks);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let events = Events::default();
+    store.set_wasm_func_hook({
+        let events = events.clone();
+        move |func_index, event| events.lock().unwrap().push((func_index, event))
+    });
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "host" (func $host))
+            (func $test (export "test") (result i32)
+                (call $nested)
+                (return_call $tail)
+            )
+            (func $nested
+                (call $host)
+            )
+            (func $tail (result i32)
+                (i32.const 42)
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "host", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, "test").unwrap();
+    (store, events, func)
+}
+
+#[test]
+fn wasm_func_hook_works() {
+    let (mut store, events, func) = test_setup(true);
+    let result = func.typed::<(), i32>(&store).unwrap().call(&mut store, ());
+    assert_eq!(result.unwrap(), 42);
+    assert_eq!(
+        &events.lock().unwrap()[..],
+        [
+            (1, WasmFuncEvent::Enter),
+            (2, WasmFuncEvent::Enter),
+            (2, WasmFuncEvent::Exit),
+            (1, WasmFuncEvent::Exit),
+            (3, WasmFuncEvent::Enter),
+            (3, WasmFuncEvent::Exit),
+        ]
+    );
+}
+
+#[test]
+fn wasm_func_hook_requires_config() {
+    let (mut store, events, func) = test_setup(false);
+    let result = func.typed::<(), i32>(&store).unwrap().call(&mut store, ());
+    assert_eq!(result.unwrap(), 42);
+    assert!(events.lock().unwrap().is_empty());
+}